@@ -0,0 +1,83 @@
+use backend_comparison::persistence::save;
+use burn::tensor::{activation::softmax, backend::Backend, module, Distribution, Shape, Tensor};
+use burn_common::benchmark::{run_benchmark, Benchmark};
+use derive_new::new;
+
+#[derive(new)]
+struct AttentionBenchmark<B: Backend> {
+    shape: Shape,
+    fused: bool,
+    device: B::Device,
+}
+
+impl<B: Backend> Benchmark for AttentionBenchmark<B> {
+    type Args = (Tensor<B, 4>, Tensor<B, 4>, Tensor<B, 4>);
+
+    fn name(&self) -> String {
+        match self.fused {
+            true => "attention".into(),
+            false => "attention-matmul-softmax".into(),
+        }
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![self.shape.dims.clone()]
+    }
+
+    fn num_samples(&self) -> usize {
+        10
+    }
+
+    fn execute(&self, (query, key, value): Self::Args) {
+        if self.fused {
+            module::attention(query, key, value, None);
+        } else {
+            let d_k = query.dims()[3];
+            let scores = query
+                .matmul(key.swap_dims(2, 3))
+                .div_scalar((d_k as f32).sqrt());
+            softmax(scores, 3).matmul(value);
+        }
+    }
+
+    fn prepare(&self) -> Self::Args {
+        let tensor = || Tensor::random(self.shape.clone(), Distribution::Default, &self.device);
+
+        (tensor(), tensor(), tensor())
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device)
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(
+    device: &B::Device,
+    feature_name: &str,
+    url: Option<&str>,
+    token: Option<&str>,
+) {
+    let batch_size = 8;
+    let n_heads = 12;
+    let seq_length = 512;
+    let d_k = 64;
+    let shape: Shape = [batch_size, n_heads, seq_length, d_k].into();
+
+    // The fused kernel is compared to the matmul and softmax path it replaces
+    let fused = AttentionBenchmark::<B>::new(shape.clone(), true, device.clone());
+    let reference = AttentionBenchmark::<B>::new(shape, false, device.clone());
+
+    save::<B>(
+        vec![run_benchmark(fused), run_benchmark(reference)],
+        device,
+        feature_name,
+        url,
+        token,
+    )
+    .unwrap();
+}
+
+fn main() {
+    backend_comparison::bench_on_backend!();
+}
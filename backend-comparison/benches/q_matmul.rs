@@ -0,0 +1,89 @@
+use backend_comparison::persistence::save;
+use burn::tensor::{
+    backend::Backend,
+    quantization::{QuantizationScheme, QuantizationType},
+    Distribution, Shape, Tensor,
+};
+use burn_common::benchmark::{run_benchmark, Benchmark};
+use derive_new::new;
+
+#[derive(new)]
+struct QMatmulBenchmark<B: Backend, const D: usize> {
+    shape_lhs: Shape,
+    shape_rhs: Shape,
+    dequantize: bool,
+    device: B::Device,
+}
+
+impl<B: Backend, const D: usize> Benchmark for QMatmulBenchmark<B, D> {
+    type Args = (Tensor<B, D>, Tensor<B, D>);
+
+    fn name(&self) -> String {
+        match self.dequantize {
+            true => "q-matmul-dequantized".into(),
+            false => "q-matmul".into(),
+        }
+    }
+
+    fn shapes(&self) -> Vec<Vec<usize>> {
+        vec![self.shape_lhs.dims.clone(), self.shape_rhs.dims.clone()]
+    }
+
+    fn num_samples(&self) -> usize {
+        10
+    }
+
+    fn execute(&self, (lhs, rhs): Self::Args) {
+        if self.dequantize {
+            lhs.dequantize().matmul(rhs.dequantize());
+        } else {
+            lhs.matmul(rhs);
+        }
+    }
+
+    fn prepare(&self) -> Self::Args {
+        let scheme = QuantizationScheme::PerTensorSymmetric(QuantizationType::QInt8);
+        let lhs = Tensor::random(self.shape_lhs.clone(), Distribution::Default, &self.device);
+        let rhs = Tensor::random(self.shape_rhs.clone(), Distribution::Default, &self.device);
+
+        (lhs.quantize_dynamic(&scheme), rhs.quantize_dynamic(&scheme))
+    }
+
+    fn sync(&self) {
+        B::sync(&self.device)
+    }
+}
+
+#[allow(dead_code)]
+fn bench<B: Backend>(
+    device: &B::Device,
+    feature_name: &str,
+    url: Option<&str>,
+    token: Option<&str>,
+) {
+    const D: usize = 3;
+    let batch_size = 8;
+    let m = 1024;
+    let k = 1024;
+    let n = 1024;
+    let shape_lhs: Shape = [batch_size, m, k].into();
+    let shape_rhs: Shape = [batch_size, k, n].into();
+
+    // The integer matmul is compared to the dequantized matmul it replaces
+    let quantized =
+        QMatmulBenchmark::<B, D>::new(shape_lhs.clone(), shape_rhs.clone(), false, device.clone());
+    let dequantized = QMatmulBenchmark::<B, D>::new(shape_lhs, shape_rhs, true, device.clone());
+
+    save::<B>(
+        vec![run_benchmark(quantized), run_benchmark(dequantized)],
+        device,
+        feature_name,
+        url,
+        token,
+    )
+    .unwrap();
+}
+
+fn main() {
+    backend_comparison::bench_on_backend!();
+}
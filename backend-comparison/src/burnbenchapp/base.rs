@@ -103,6 +103,10 @@ enum BenchmarkValues {
     Data,
     #[strum(to_string = "matmul")]
     Matmul,
+    #[strum(to_string = "attention")]
+    Attention,
+    #[strum(to_string = "q-matmul")]
+    QMatmul,
     #[strum(to_string = "unary")]
     Unary,
     #[strum(to_string = "max-pool2d")]
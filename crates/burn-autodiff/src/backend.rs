@@ -6,7 +6,7 @@ use crate::{
     AutodiffBridge,
 };
 use burn_tensor::{
-    backend::{AutodiffBackend, Backend},
+    backend::{AutodiffBackend, Backend, MemoryUsage},
     ops::{BoolTensor, IntTensor, QuantizedTensor},
 };
 use core::marker::PhantomData;
@@ -52,6 +52,10 @@ impl<B: Backend, C: CheckpointStrategy> Backend for Autodiff<B, C> {
     fn sync(device: &B::Device) {
         B::sync(device)
     }
+
+    fn memory_usage(device: &B::Device) -> Option<MemoryUsage> {
+        B::memory_usage(device)
+    }
 }
 
 impl<B: Backend, C: CheckpointStrategy> AutodiffBackend for Autodiff<B, C> {
@@ -0,0 +1,635 @@
+use crate::tensor::{
+    backend::Backend,
+    module::interpolate,
+    ops::{InterpolateMode, InterpolateOptions},
+    Bool, Int, Tensor, TensorData,
+};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// An augmentation of a batch of images with the shape `[batch_size, channels, height, width]`
+/// and values in `[0, 1]`, drawing its random parameters independently for each image.
+pub trait Augmentation<B: Backend>: Send + Sync {
+    /// Augments the images with the random numbers of the rng.
+    fn augment(&self, images: Tensor<B, 4>, rng: &mut StdRng) -> Tensor<B, 4>;
+}
+
+/// A batch-level augmentation mixing the images of a batch with their targets, such as one-hot
+/// or soft labels with the shape `[batch_size, num_classes]`.
+pub trait BatchMixing<B: Backend>: Send + Sync {
+    /// Mixes the images and their targets with the random numbers of the rng.
+    fn mix(
+        &self,
+        images: Tensor<B, 4>,
+        targets: Tensor<B, 2>,
+        rng: &mut StdRng,
+    ) -> (Tensor<B, 4>, Tensor<B, 2>);
+}
+
+/// Applies the augmentations of a pipeline in order.
+#[derive(Clone)]
+pub struct Compose<B: Backend> {
+    augmentations: Vec<Arc<dyn Augmentation<B>>>,
+}
+
+impl<B: Backend> Default for Compose<B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Backend> Compose<B> {
+    /// Create an empty pipeline.
+    pub fn new() -> Self {
+        Self {
+            augmentations: Vec::new(),
+        }
+    }
+
+    /// Appends an augmentation to the pipeline.
+    pub fn with<A: Augmentation<B> + 'static>(mut self, augmentation: A) -> Self {
+        self.augmentations.push(Arc::new(augmentation));
+        self
+    }
+}
+
+impl<B: Backend> Augmentation<B> for Compose<B> {
+    fn augment(&self, images: Tensor<B, 4>, rng: &mut StdRng) -> Tensor<B, 4> {
+        self.augmentations
+            .iter()
+            .fold(images, |images, augmentation| {
+                augmentation.augment(images, rng)
+            })
+    }
+}
+
+/// Applies an augmentation and an optional batch mixing in a
+/// [batcher](super::dataloader::batcher::Batcher).
+///
+/// The rng of each batch is seeded with the seed and the number of batches already augmented,
+/// which is shared by the clones of the augmenter, so the batchers of the workers of a data
+/// loader draw different random numbers. Using the seed of the training makes the augmentations
+/// reproducible with a single worker.
+#[derive(Clone)]
+pub struct Augmenter<B: Backend> {
+    augmentation: Arc<dyn Augmentation<B>>,
+    mixing: Option<Arc<dyn BatchMixing<B>>>,
+    seed: u64,
+    num_batches: Arc<AtomicU64>,
+}
+
+impl<B: Backend> Augmenter<B> {
+    /// Create the augmenter of the augmentation with the given seed.
+    pub fn new<A: Augmentation<B> + 'static>(augmentation: A, seed: u64) -> Self {
+        Self {
+            augmentation: Arc::new(augmentation),
+            mixing: None,
+            seed,
+            num_batches: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Sets the mixing applied after the augmentation by
+    /// [augment_with_targets](Self::augment_with_targets).
+    pub fn with_mixing<M: BatchMixing<B> + 'static>(mut self, mixing: M) -> Self {
+        self.mixing = Some(Arc::new(mixing));
+        self
+    }
+
+    /// Augments the images of a batch.
+    pub fn augment(&self, images: Tensor<B, 4>) -> Tensor<B, 4> {
+        self.augmentation.augment(images, &mut self.next_rng())
+    }
+
+    /// Augments the images of a batch, then mixes the images and their targets.
+    pub fn augment_with_targets(
+        &self,
+        images: Tensor<B, 4>,
+        targets: Tensor<B, 2>,
+    ) -> (Tensor<B, 4>, Tensor<B, 2>) {
+        let mut rng = self.next_rng();
+        let images = self.augmentation.augment(images, &mut rng);
+
+        match &self.mixing {
+            Some(mixing) => mixing.mix(images, targets, &mut rng),
+            None => (images, targets),
+        }
+    }
+
+    fn next_rng(&self) -> StdRng {
+        let batch = self.num_batches.fetch_add(1, Ordering::Relaxed);
+        StdRng::seed_from_u64(self.seed.wrapping_add(batch))
+    }
+}
+
+/// Resizes the images.
+#[derive(Clone, Debug)]
+pub struct Resize {
+    size: [usize; 2],
+    mode: InterpolateMode,
+}
+
+impl Resize {
+    /// Create the augmentation resizing the images to `[height, width]` with a bilinear
+    /// interpolation.
+    pub fn new(size: [usize; 2]) -> Self {
+        Self {
+            size,
+            mode: InterpolateMode::Bilinear,
+        }
+    }
+
+    /// Sets the interpolation mode.
+    pub fn with_mode(mut self, mode: InterpolateMode) -> Self {
+        self.mode = mode;
+        self
+    }
+}
+
+impl<B: Backend> Augmentation<B> for Resize {
+    fn augment(&self, images: Tensor<B, 4>, _rng: &mut StdRng) -> Tensor<B, 4> {
+        interpolate(
+            images,
+            self.size,
+            InterpolateOptions::new(self.mode.clone()),
+        )
+    }
+}
+
+/// Crops a random part of each image, optionally after padding the images with zeros.
+#[derive(Clone, Debug)]
+pub struct RandomCrop {
+    size: [usize; 2],
+    padding: usize,
+}
+
+impl RandomCrop {
+    /// Create the augmentation cropping `[height, width]` pixels.
+    pub fn new(size: [usize; 2]) -> Self {
+        Self { size, padding: 0 }
+    }
+
+    /// Sets the number of zeros padding each side of the images before cropping.
+    pub fn with_padding(mut self, padding: usize) -> Self {
+        self.padding = padding;
+        self
+    }
+}
+
+impl<B: Backend> Augmentation<B> for RandomCrop {
+    fn augment(&self, images: Tensor<B, 4>, rng: &mut StdRng) -> Tensor<B, 4> {
+        let [n, c, h, w] = images.dims();
+        let [crop_h, crop_w] = self.size;
+        let (h, w) = (h + 2 * self.padding, w + 2 * self.padding);
+        assert!(
+            crop_h <= h && crop_w <= w,
+            "The crop should be smaller than the padded images"
+        );
+
+        let images = match self.padding {
+            0 => images,
+            p => Tensor::zeros([n, c, h, w], &images.device())
+                .slice_assign([0..n, 0..c, p..h - p, p..w - p], images),
+        };
+
+        let crops = (0..n)
+            .map(|i| {
+                let top = rng.gen_range(0..=h - crop_h);
+                let left = rng.gen_range(0..=w - crop_w);
+                images
+                    .clone()
+                    .slice([i..i + 1, 0..c, top..top + crop_h, left..left + crop_w])
+            })
+            .collect();
+
+        Tensor::cat(crops, 0)
+    }
+}
+
+/// Crops a random part of each image with a random area and aspect ratio, resized to a fixed
+/// size.
+#[derive(Clone, Debug)]
+pub struct RandomResizedCrop {
+    size: [usize; 2],
+    scale: (f64, f64),
+    ratio: (f64, f64),
+}
+
+impl RandomResizedCrop {
+    /// Create the augmentation resizing the crops to `[height, width]`, with a crop area
+    /// between 8% and 100% of the image and an aspect ratio between 3/4 and 4/3.
+    pub fn new(size: [usize; 2]) -> Self {
+        Self {
+            size,
+            scale: (0.08, 1.0),
+            ratio: (3.0 / 4.0, 4.0 / 3.0),
+        }
+    }
+
+    /// Sets the range of the area of the crops, relative to the area of the image.
+    pub fn with_scale(mut self, min: f64, max: f64) -> Self {
+        self.scale = (min, max);
+        self
+    }
+
+    /// Sets the range of the aspect ratio (width over height) of the crops.
+    pub fn with_ratio(mut self, min: f64, max: f64) -> Self {
+        self.ratio = (min, max);
+        self
+    }
+
+    /// The `[top, left, height, width]` of a crop, the whole image when no valid crop is found.
+    fn crop(&self, h: usize, w: usize, rng: &mut StdRng) -> [usize; 4] {
+        let area = (h * w) as f64;
+        let (log_min, log_max) = (self.ratio.0.ln(), self.ratio.1.ln());
+
+        for _ in 0..10 {
+            let target = area * rng.gen_range(self.scale.0..=self.scale.1);
+            let ratio = rng.gen_range(log_min..=log_max).exp();
+            let crop_w = (target * ratio).sqrt().round() as usize;
+            let crop_h = (target / ratio).sqrt().round() as usize;
+
+            if 0 < crop_w && crop_w <= w && 0 < crop_h && crop_h <= h {
+                let top = rng.gen_range(0..=h - crop_h);
+                let left = rng.gen_range(0..=w - crop_w);
+                return [top, left, crop_h, crop_w];
+            }
+        }
+
+        [0, 0, h, w]
+    }
+}
+
+impl<B: Backend> Augmentation<B> for RandomResizedCrop {
+    fn augment(&self, images: Tensor<B, 4>, rng: &mut StdRng) -> Tensor<B, 4> {
+        let [n, c, h, w] = images.dims();
+
+        let crops = (0..n)
+            .map(|i| {
+                let [top, left, crop_h, crop_w] = self.crop(h, w, rng);
+                let crop =
+                    images
+                        .clone()
+                        .slice([i..i + 1, 0..c, top..top + crop_h, left..left + crop_w]);
+                interpolate(
+                    crop,
+                    self.size,
+                    InterpolateOptions::new(InterpolateMode::Bilinear),
+                )
+            })
+            .collect();
+
+        Tensor::cat(crops, 0)
+    }
+}
+
+/// Flips each image with a probability.
+#[derive(Clone, Debug)]
+pub struct RandomFlip {
+    probability: f64,
+    axis: isize,
+}
+
+impl RandomFlip {
+    /// Create the augmentation flipping the images left to right with the probability.
+    pub fn horizontal(probability: f64) -> Self {
+        Self {
+            probability,
+            axis: 3,
+        }
+    }
+
+    /// Create the augmentation flipping the images upside down with the probability.
+    pub fn vertical(probability: f64) -> Self {
+        Self {
+            probability,
+            axis: 2,
+        }
+    }
+}
+
+impl<B: Backend> Augmentation<B> for RandomFlip {
+    fn augment(&self, images: Tensor<B, 4>, rng: &mut StdRng) -> Tensor<B, 4> {
+        let [n, c, h, w] = images.dims();
+        let flips = (0..n)
+            .map(|_| rng.gen_bool(self.probability))
+            .collect::<Vec<_>>();
+
+        let mask =
+            Tensor::<B, 4, Bool>::from_data(TensorData::new(flips, [n, 1, 1, 1]), &images.device())
+                .expand([n, c, h, w]);
+        let flipped = images.clone().flip([self.axis]);
+
+        images.mask_where(mask, flipped)
+    }
+}
+
+/// Randomly changes the brightness, the contrast and the saturation of each image.
+///
+/// Each factor is drawn uniformly in `[1 - x, 1 + x]`, `x` being the strength of the change.
+/// The saturation is only changed for RGB images.
+#[derive(Clone, Debug, Default)]
+pub struct ColorJitter {
+    brightness: f64,
+    contrast: f64,
+    saturation: f64,
+}
+
+impl ColorJitter {
+    /// Create the augmentation without any change.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the strength of the brightness change.
+    pub fn with_brightness(mut self, brightness: f64) -> Self {
+        self.brightness = brightness;
+        self
+    }
+
+    /// Sets the strength of the contrast change.
+    pub fn with_contrast(mut self, contrast: f64) -> Self {
+        self.contrast = contrast;
+        self
+    }
+
+    /// Sets the strength of the saturation change.
+    pub fn with_saturation(mut self, saturation: f64) -> Self {
+        self.saturation = saturation;
+        self
+    }
+}
+
+impl<B: Backend> Augmentation<B> for ColorJitter {
+    fn augment(&self, images: Tensor<B, 4>, rng: &mut StdRng) -> Tensor<B, 4> {
+        let [n, c, _, _] = images.dims();
+        let device = images.device();
+        let mut factors = |strength: f64| {
+            let factors = (0..n)
+                .map(|_| rng.gen_range((1.0 - strength).max(0.0)..=1.0 + strength) as f32)
+                .collect::<Vec<_>>();
+            Tensor::<B, 4>::from_data(
+                TensorData::new(factors, [n, 1, 1, 1]).convert::<B::FloatElem>(),
+                &device,
+            )
+        };
+
+        let mut images = images;
+        if self.brightness > 0.0 {
+            images = images * factors(self.brightness);
+        }
+        if self.contrast > 0.0 {
+            let mean = grayscale(images.clone()).mean_dim(2).mean_dim(3);
+            images = (images - mean.clone()) * factors(self.contrast) + mean;
+        }
+        if self.saturation > 0.0 && c == 3 {
+            let gray = grayscale(images.clone());
+            images = (images - gray.clone()) * factors(self.saturation) + gray;
+        }
+
+        images.clamp(0.0, 1.0)
+    }
+}
+
+/// The luminance of the images with the shape `[batch_size, 1, height, width]`.
+fn grayscale<B: Backend>(images: Tensor<B, 4>) -> Tensor<B, 4> {
+    let [n, c, h, w] = images.dims();
+    if c != 3 {
+        return images.mean_dim(1);
+    }
+
+    let channel = |index: usize| images.clone().slice([0..n, index..index + 1, 0..h, 0..w]);
+    channel(0) * 0.299 + channel(1) * 0.587 + channel(2) * 0.114
+}
+
+/// Normalizes each channel of the images with its mean and standard deviation.
+#[derive(Clone, Debug)]
+pub struct Normalize {
+    mean: Vec<f32>,
+    std: Vec<f32>,
+}
+
+impl Normalize {
+    /// Create the augmentation with the mean and the standard deviation of each channel.
+    pub fn new(mean: Vec<f32>, std: Vec<f32>) -> Self {
+        assert_eq!(
+            mean.len(),
+            std.len(),
+            "The mean and the standard deviation should have one value for each channel"
+        );
+
+        Self { mean, std }
+    }
+
+    /// The normalization of the ImageNet dataset, commonly used by the pretrained models.
+    pub fn imagenet() -> Self {
+        Self::new(vec![0.485, 0.456, 0.406], vec![0.229, 0.224, 0.225])
+    }
+}
+
+impl<B: Backend> Augmentation<B> for Normalize {
+    fn augment(&self, images: Tensor<B, 4>, _rng: &mut StdRng) -> Tensor<B, 4> {
+        let device = images.device();
+        let channels = |values: &[f32]| {
+            Tensor::<B, 4>::from_data(
+                TensorData::new(values.to_vec(), [1, values.len(), 1, 1]).convert::<B::FloatElem>(),
+                &device,
+            )
+        };
+
+        (images - channels(&self.mean)) / channels(&self.std)
+    }
+}
+
+/// Mixes each image and its target with another one of the batch, with a weight drawn from a
+/// `Beta(alpha, alpha)` distribution.
+#[derive(Clone, Debug)]
+pub struct MixUp {
+    alpha: f64,
+}
+
+impl MixUp {
+    /// Create the augmentation with the parameter of the distribution of the weights.
+    pub fn new(alpha: f64) -> Self {
+        assert!(alpha > 0.0, "The alpha parameter should be positive");
+        Self { alpha }
+    }
+}
+
+impl<B: Backend> BatchMixing<B> for MixUp {
+    fn mix(
+        &self,
+        images: Tensor<B, 4>,
+        targets: Tensor<B, 2>,
+        rng: &mut StdRng,
+    ) -> (Tensor<B, 4>, Tensor<B, 2>) {
+        let lambda = sample_beta(self.alpha, rng);
+        let permutation = permutation::<B>(images.dims()[0], rng, &images.device());
+
+        let images =
+            images.clone() * lambda + images.select(0, permutation.clone()) * (1.0 - lambda);
+        let targets = targets.clone() * lambda + targets.select(0, permutation) * (1.0 - lambda);
+
+        (images, targets)
+    }
+}
+
+/// Replaces a random box of the images by the same box of other images of the batch, mixing
+/// the targets with the area of the box.
+///
+/// The area of the box is drawn from a `Beta(alpha, alpha)` distribution.
+#[derive(Clone, Debug)]
+pub struct CutMix {
+    alpha: f64,
+}
+
+impl CutMix {
+    /// Create the augmentation with the parameter of the distribution of the areas.
+    pub fn new(alpha: f64) -> Self {
+        assert!(alpha > 0.0, "The alpha parameter should be positive");
+        Self { alpha }
+    }
+}
+
+impl<B: Backend> BatchMixing<B> for CutMix {
+    fn mix(
+        &self,
+        images: Tensor<B, 4>,
+        targets: Tensor<B, 2>,
+        rng: &mut StdRng,
+    ) -> (Tensor<B, 4>, Tensor<B, 2>) {
+        let [n, c, h, w] = images.dims();
+        let cut = (1.0 - sample_beta(self.alpha, rng)).sqrt();
+        let (cut_h, cut_w) = ((h as f64 * cut) as usize, (w as f64 * cut) as usize);
+
+        // The box is centered on a random pixel and clipped to the image.
+        let (y, x) = (rng.gen_range(0..h), rng.gen_range(0..w));
+        let (top, bottom) = (y.saturating_sub(cut_h / 2), (y + cut_h / 2).min(h));
+        let (left, right) = (x.saturating_sub(cut_w / 2), (x + cut_w / 2).min(w));
+        if top == bottom || left == right {
+            return (images, targets);
+        }
+
+        let permutation = permutation::<B>(n, rng, &images.device());
+        let ranges = [0..n, 0..c, top..bottom, left..right];
+        let patch = images
+            .clone()
+            .select(0, permutation.clone())
+            .slice(ranges.clone());
+        let images = images.slice_assign(ranges, patch);
+
+        let lambda = 1.0 - ((bottom - top) * (right - left)) as f64 / (h * w) as f64;
+        let targets = targets.clone() * lambda + targets.select(0, permutation) * (1.0 - lambda);
+
+        (images, targets)
+    }
+}
+
+fn permutation<B: Backend>(n: usize, rng: &mut StdRng, device: &B::Device) -> Tensor<B, 1, Int> {
+    let mut indices = (0..n as i64).collect::<Vec<_>>();
+    indices.shuffle(rng);
+
+    Tensor::from_data(
+        TensorData::new(indices, [n]).convert::<B::IntElem>(),
+        device,
+    )
+}
+
+/// Samples a `Beta(alpha, alpha)` distribution with Jöhnk's algorithm, which is efficient for
+/// the small parameters used by the mixing augmentations.
+fn sample_beta(alpha: f64, rng: &mut StdRng) -> f64 {
+    loop {
+        let x = rng.gen::<f64>().powf(1.0 / alpha);
+        let y = rng.gen::<f64>().powf(1.0 / alpha);
+
+        if x + y <= 1.0 && x + y > 0.0 {
+            return x / (x + y);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+
+    fn images() -> Tensor<TestBackend, 4> {
+        Tensor::from_data(
+            TensorData::from([[[[0.1f32, 0.2], [0.3, 0.4]]], [[[0.5, 0.6], [0.7, 0.8]]]]),
+            &Default::default(),
+        )
+    }
+
+    #[test]
+    fn random_flip_should_flip_all_the_images_with_probability_one() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        let flipped = RandomFlip::horizontal(1.0).augment(images(), &mut rng);
+
+        flipped.into_data().assert_eq(
+            &TensorData::from([[[[0.2f32, 0.1], [0.4, 0.3]]], [[[0.6, 0.5], [0.8, 0.7]]]]),
+            false,
+        );
+    }
+
+    #[test]
+    fn pipeline_should_apply_the_augmentations_in_order() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let pipeline = Compose::new()
+            .with(RandomFlip::vertical(1.0))
+            .with(Normalize::new(vec![0.5], vec![0.5]))
+            .with(RandomCrop::new([1, 2]).with_padding(1))
+            .with(Resize::new([4, 4]));
+
+        let images = pipeline.augment(images(), &mut rng);
+
+        assert_eq!(images.dims(), [2, 1, 4, 4]);
+        let values = images.into_data().to_vec::<f32>().unwrap();
+        assert!(values.iter().all(|value| (-1.0..=1.0).contains(value)));
+    }
+
+    #[test]
+    fn random_resized_crop_should_resize_the_crops() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let crop = RandomResizedCrop::new([3, 3]).with_scale(0.5, 1.0);
+
+        assert_eq!(crop.augment(images(), &mut rng).dims(), [2, 1, 3, 3]);
+    }
+
+    #[test]
+    fn mixing_should_keep_the_targets_normalized() {
+        let targets = Tensor::<TestBackend, 2>::from_data(
+            TensorData::from([[1.0f32, 0.0], [0.0, 1.0]]),
+            &Default::default(),
+        );
+        let augmenter = Augmenter::new(ColorJitter::new().with_brightness(0.2), 42)
+            .with_mixing(CutMix::new(1.0));
+
+        for _ in 0..4 {
+            let (images, targets) = augmenter.augment_with_targets(images(), targets.clone());
+
+            assert_eq!(images.dims(), [2, 1, 2, 2]);
+            targets
+                .sum_dim(1)
+                .into_data()
+                .assert_approx_eq(&TensorData::from([[1.0f32], [1.0]]), 5);
+        }
+    }
+
+    #[test]
+    fn augmenter_should_be_reproducible_with_the_seed() {
+        let augment = || {
+            let augmenter = Augmenter::new(Compose::new(), 7).with_mixing(MixUp::new(0.2));
+            let targets = Tensor::<TestBackend, 2>::from_data(
+                TensorData::from([[1.0f32, 0.0], [0.0, 1.0]]),
+                &Default::default(),
+            );
+            augmenter.augment_with_targets(images(), targets).1
+        };
+
+        augment()
+            .into_data()
+            .assert_approx_eq(&augment().into_data(), 5);
+    }
+}
@@ -1,5 +1,8 @@
+use super::WorkerFailure;
 pub use crate::data::dataset::{Dataset, DatasetIterator};
 use core::iter::Iterator;
+use std::fmt::Display;
+use std::time::Duration;
 
 /// A progress struct that can be used to track the progress of a data loader.
 #[derive(new, Clone, Debug)]
@@ -24,6 +27,67 @@ pub trait DataLoader<O>: Send {
     /// The number of items (not the number of batches nor the number of iterations),
     /// corresponding to the items_total of the progress returned by the iterator.
     fn num_items(&self) -> usize;
+    /// Returns a boxed [iterator](DataLoaderIterator) to iterate over the data loader, which
+    /// returns the [error](DataLoaderError) of the worker threads instead of panicking, then
+    /// stops.
+    ///
+    /// The data loaders without worker threads never fail.
+    fn try_iter<'a>(&'a self) -> Box<dyn DataLoaderIterator<Result<O, DataLoaderError>> + 'a>
+    where
+        O: 'a,
+    {
+        Box::new(InfallibleIterator {
+            iterator: self.iter(),
+        })
+    }
+}
+
+/// The error of the worker threads of a data loader.
+#[derive(Clone, Debug)]
+pub enum DataLoaderError {
+    /// The workers produced no batch within the [timeout](super::DataLoaderBuilder::timeout).
+    Timeout(Duration),
+    /// The workers stopped before the end of the iteration.
+    Disconnected,
+    /// A worker failed more times than
+    /// [tolerated](super::DataLoaderBuilder::max_worker_failures).
+    WorkerFailed(WorkerFailure),
+}
+
+impl Display for DataLoaderError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Timeout(timeout) => {
+                write!(
+                    f,
+                    "The data loader workers produced no batch in {timeout:?}"
+                )
+            }
+            Self::Disconnected => write!(f, "The data loader workers stopped unexpectedly"),
+            Self::WorkerFailed(failure) => failure.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for DataLoaderError {}
+
+/// The iterator of a data loader without errors.
+struct InfallibleIterator<'a, O> {
+    iterator: Box<dyn DataLoaderIterator<O> + 'a>,
+}
+
+impl<O> Iterator for InfallibleIterator<'_, O> {
+    type Item = Result<O, DataLoaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iterator.next().map(Ok)
+    }
+}
+
+impl<O> DataLoaderIterator<Result<O, DataLoaderError>> for InfallibleIterator<'_, O> {
+    fn progress(&self) -> Progress {
+        self.iterator.progress()
+    }
 }
 
 /// A super trait for [dataloader](DataLoader) that allows it to be cloned dynamically.
@@ -1,12 +1,14 @@
 use super::{
-    batcher::DynBatcher, BatchStrategy, DataLoader, DataLoaderIterator, DynDataLoader,
-    MultiThreadDataLoader, Progress,
+    batcher::DynBatcher,
+    sampler::{partition, SampledDataset, SharedSampler},
+    BatchStrategy, DataLoader, DataLoaderIterator, DynDataLoader, MultiThreadDataLoader, Progress,
 };
 use burn_dataset::{
     transform::{PartialDataset, ShuffledDataset},
     Dataset,
 };
 use rand::{distributions::Standard, prelude::Distribution, rngs::StdRng, Rng, SeedableRng};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 /// A data loader that can be used to iterate over a dataset in batches.
@@ -15,6 +17,18 @@ pub struct BatchDataLoader<I, O> {
     dataset: Arc<dyn Dataset<I>>,
     batcher: Box<dyn DynBatcher<I, O>>,
     rng: Option<Arc<spin::Mutex<rand::rngs::StdRng>>>,
+    sampling: Option<Sampling>,
+}
+
+/// The part of the sampled items of each iteration batched by a data loader.
+#[derive(Clone)]
+struct Sampling {
+    sampler: Arc<SharedSampler>,
+    iteration: Arc<AtomicUsize>,
+    worker: usize,
+    num_workers: usize,
+    /// The number of items of the batches, the workers batching whole batches of the sampler.
+    batch_size: usize,
 }
 
 impl<I, O> Clone for BatchDataLoader<I, O> {
@@ -24,6 +38,7 @@ impl<I, O> Clone for BatchDataLoader<I, O> {
             dataset: self.dataset.clone(),
             batcher: self.batcher.clone_dyn(),
             rng: self.rng.clone(),
+            sampling: self.sampling.clone(),
         }
     }
 }
@@ -53,8 +68,27 @@ impl<I, O> BatchDataLoader<I, O> {
             dataset,
             batcher,
             rng: rng.map(|rng| Arc::new(spin::Mutex::new(rng))),
+            sampling: None,
         }
     }
+
+    /// Batches the part of the worker among the given number of workers of the items sampled
+    /// at each iteration, instead of all the items of the dataset.
+    pub(crate) fn with_sampler(
+        mut self,
+        sampler: Arc<SharedSampler>,
+        worker: usize,
+        num_workers: usize,
+    ) -> Self {
+        self.sampling = Some(Sampling {
+            sampler,
+            iteration: Arc::new(AtomicUsize::new(0)),
+            worker,
+            num_workers,
+            batch_size: self.strategy.batch_size().unwrap_or(1),
+        });
+        self
+    }
 }
 
 /// A data loader iterator that can be used to iterate over a data loader.
@@ -119,8 +153,21 @@ where
         // When starting a new iteration, we first check if the dataloader was created with an rng,
         // implying that we should shuffle the dataset beforehand, while advancing the current
         // rng to ensure that each new iteration shuffles the dataset differently.
-        let dataset = match &self.rng {
-            Some(rng) => {
+        let dataset: Arc<dyn Dataset<I>> = match (&self.sampling, &self.rng) {
+            (Some(sampling), _) => {
+                let iteration = sampling.iteration.fetch_add(1, Ordering::Relaxed);
+                let indices = sampling.sampler.indices(self.dataset.len(), iteration);
+
+                let range = partition(
+                    indices.len(),
+                    sampling.batch_size,
+                    sampling.worker,
+                    sampling.num_workers,
+                );
+
+                Arc::new(SampledDataset::new(self.dataset.clone(), indices, range))
+            }
+            (None, Some(rng)) => {
                 let mut rng = rng.lock();
 
                 Arc::new(ShuffledDataset::with_seed(
@@ -128,7 +175,7 @@ where
                     rng.sample(Standard),
                 ))
             }
-            None => self.dataset.clone(),
+            (None, None) => self.dataset.clone(),
         };
         Box::new(BatchDataloaderIterator::new(
             self.strategy.clone_dyn(),
@@ -138,7 +185,19 @@ where
     }
 
     fn num_items(&self) -> usize {
-        self.dataset.len()
+        match &self.sampling {
+            Some(sampling) => {
+                let num_samples = sampling.sampler.num_samples(self.dataset.len());
+                let (start, end) = partition(
+                    num_samples,
+                    sampling.batch_size,
+                    sampling.worker,
+                    sampling.num_workers,
+                );
+                end - start
+            }
+            None => self.dataset.len(),
+        }
     }
 }
 
@@ -172,8 +231,16 @@ impl<I, O> Iterator for BatchDataloaderIterator<I, O> {
     type Item = O;
 
     fn next(&mut self) -> Option<O> {
-        while let Some(item) = self.dataset.get(self.current_index) {
+        loop {
+            // The index is incremented beforehand, so a thread restarted after failing to read
+            // an item continues with the next one.
+            let index = self.current_index;
             self.current_index += 1;
+
+            let Some(item) = self.dataset.get(index) else {
+                self.current_index = index;
+                break;
+            };
             self.strategy.add(item);
 
             if let Some(items) = self.strategy.batch(false) {
@@ -198,11 +265,12 @@ impl<I, O> DataLoaderIterator<O> for BatchDataloaderIterator<I, O> {
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
+    use std::time::Duration;
 
     use super::*;
     use crate::data::dataloader::batcher::TestBatcher;
-    use crate::data::dataloader::FixBatchStrategy;
-    use crate::data::dataset::FakeDataset;
+    use crate::data::dataloader::{DataLoaderBuilder, DataLoaderError, FixBatchStrategy};
+    use crate::data::dataset::{FakeDataset, InMemDataset};
 
     #[test]
     fn test_batch_dataloader() {
@@ -266,4 +334,140 @@ mod tests {
 
         assert_eq!(items_single_thread, items_multi_thread);
     }
+
+    #[test]
+    fn test_prefetched_dataloader_keeps_the_order() {
+        let dataset = FakeDataset::<String>::new(27);
+        let items = dataset.iter().collect::<Vec<_>>();
+        let build = |prefetch: Option<usize>| {
+            let builder = DataLoaderBuilder::new(TestBatcher::new())
+                .batch_size(5)
+                .shuffle(42);
+            let builder = match prefetch {
+                Some(num_batches) => builder.prefetch(num_batches),
+                None => builder,
+            };
+            builder.build(InMemDataset::new(items.clone()))
+        };
+
+        let dataloader = build(None);
+        let prefetched = build(Some(2));
+
+        for _ in 0..2 {
+            assert_eq!(
+                prefetched.iter().collect::<Vec<_>>(),
+                dataloader.iter().collect::<Vec<_>>()
+            );
+        }
+        assert_eq!(prefetched.num_items(), 27);
+    }
+
+    #[test]
+    fn test_supervised_dataloader_skips_the_failing_items() {
+        let dataset = InMemDataset::new((0..6).collect::<Vec<i32>>()).map(|item: i32| {
+            assert_ne!(item, 3, "Corrupted item");
+            item
+        });
+        let dataloader = DataLoaderBuilder::new(TestBatcher::new())
+            .batch_size(2)
+            .max_worker_failures(1)
+            .timeout(Duration::from_secs(10))
+            .build(dataset);
+
+        let items = dataloader.iter().collect::<Vec<_>>();
+
+        assert_eq!(items, [vec![0, 1], vec![2, 4], vec![5]]);
+    }
+
+    #[test]
+    #[should_panic = "Data loader worker 0 failed on the items 2..4"]
+    fn test_supervised_dataloader_panics_after_the_max_failures() {
+        let dataset = InMemDataset::new((0..6).collect::<Vec<i32>>()).map(|item: i32| {
+            assert_ne!(item, 3, "Corrupted item");
+            item
+        });
+        let dataloader = DataLoaderBuilder::new(TestBatcher::new())
+            .batch_size(2)
+            .max_worker_failures(0)
+            .build(dataset);
+
+        dataloader.iter().for_each(drop);
+    }
+
+    #[test]
+    fn test_supervised_dataloader_returns_the_failure_after_the_max_failures() {
+        let dataset = InMemDataset::new((0..6).collect::<Vec<i32>>()).map(|item: i32| {
+            assert_ne!(item, 3, "Corrupted item");
+            item
+        });
+        let dataloader = DataLoaderBuilder::new(TestBatcher::new())
+            .batch_size(2)
+            .max_worker_failures(0)
+            .build(dataset);
+
+        let items = dataloader.try_iter().collect::<Vec<_>>();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].as_ref().unwrap(), &vec![0, 1]);
+        match &items[1] {
+            Err(DataLoaderError::WorkerFailed(failure)) => {
+                assert_eq!(failure.items, 2..4);
+                assert!(failure.message.contains("Corrupted item"));
+            }
+            item => panic!("Expected a worker failure, got {item:?}"),
+        }
+    }
+
+    #[test]
+    fn test_supervised_dataloader_keeps_the_panic_hook() {
+        use std::panic;
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        static IS_HOOKED: AtomicBool = AtomicBool::new(false);
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if info.to_string().contains("Hooked item") {
+                IS_HOOKED.store(true, Ordering::SeqCst);
+            }
+            previous(info);
+        }));
+
+        let dataset = InMemDataset::new((0..4).collect::<Vec<i32>>()).map(|item: i32| {
+            assert_ne!(item, 1, "Hooked item");
+            item
+        });
+        let dataloader = DataLoaderBuilder::new(TestBatcher::new())
+            .batch_size(2)
+            .num_workers(2)
+            .max_worker_failures(1)
+            .build(dataset);
+        let items = dataloader.try_iter().collect::<Vec<_>>();
+
+        // The panic of the worker reaches the hook of the application
+        assert!(IS_HOOKED.load(Ordering::SeqCst));
+        assert!(items.iter().all(|item| item.is_ok()));
+    }
+
+    #[test]
+    fn test_supervised_dataloader_returns_the_timeout() {
+        let dataset = InMemDataset::new((0..4).collect::<Vec<i32>>()).map(|item: i32| {
+            if item == 2 {
+                std::thread::sleep(Duration::from_secs(1));
+            }
+            item
+        });
+        let dataloader = DataLoaderBuilder::new(TestBatcher::new())
+            .batch_size(2)
+            .timeout(Duration::from_millis(100))
+            .build(dataset);
+
+        let items = dataloader.try_iter().collect::<Vec<_>>();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].as_ref().unwrap(), &vec![0, 1]);
+        assert!(matches!(
+            items[1],
+            Err(DataLoaderError::Timeout(timeout)) if timeout == Duration::from_millis(100)
+        ));
+    }
 }
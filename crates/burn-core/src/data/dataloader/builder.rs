@@ -1,7 +1,13 @@
-use super::{batcher::DynBatcher, BatchDataLoader, BatchStrategy, DataLoader, FixBatchStrategy};
+use super::{
+    batcher::DynBatcher,
+    sampler::{Sampler, SharedSampler},
+    BatchDataLoader, BatchStrategy, DataLoader, DynDataLoader, FixBatchStrategy,
+    MultiThreadDataLoader,
+};
 use burn_dataset::Dataset;
 use rand::{rngs::StdRng, SeedableRng};
 use std::sync::Arc;
+use std::time::Duration;
 
 /// A builder for data loaders.
 pub struct DataLoaderBuilder<I, O> {
@@ -9,6 +15,36 @@ pub struct DataLoaderBuilder<I, O> {
     batcher: Box<dyn DynBatcher<I, O>>,
     num_threads: Option<usize>,
     shuffle: Option<u64>,
+    sampler: Option<Box<dyn Sampler>>,
+    supervision: Supervision,
+}
+
+/// The options of the worker threads, which require batching on background threads.
+#[derive(Clone, Copy, Default)]
+struct Supervision {
+    prefetch: Option<usize>,
+    timeout: Option<Duration>,
+    max_worker_failures: Option<usize>,
+}
+
+impl Supervision {
+    fn is_enabled(&self) -> bool {
+        self.prefetch.is_some() || self.timeout.is_some() || self.max_worker_failures.is_some()
+    }
+
+    fn apply<O>(&self, mut dataloader: MultiThreadDataLoader<O>) -> MultiThreadDataLoader<O> {
+        if let Some(num_batches) = self.prefetch {
+            dataloader = dataloader.with_prefetch(num_batches);
+        }
+        if let Some(timeout) = self.timeout {
+            dataloader = dataloader.with_timeout(timeout);
+        }
+        if let Some(max_failures) = self.max_worker_failures {
+            dataloader = dataloader.with_max_failures(max_failures);
+        }
+
+        dataloader
+    }
 }
 
 impl<I, O> DataLoaderBuilder<I, O>
@@ -34,6 +70,8 @@ where
             strategy: None,
             num_threads: None,
             shuffle: None,
+            sampler: None,
+            supervision: Supervision::default(),
         }
     }
 
@@ -68,6 +106,27 @@ where
         self
     }
 
+    /// Sets the sampler selecting the items of each iteration, such as the
+    /// [weighted random sampler](super::sampler::WeightedRandomSampler)
+    /// or the [length bucket sampler](super::sampler::LengthBucketSampler).
+    ///
+    /// The sampler replaces the shuffling, whose seed is then used to seed the sampler.
+    ///
+    /// # Arguments
+    ///
+    /// * `sampler` - The sampler.
+    ///
+    /// # Returns
+    ///
+    /// The data loader builder.
+    pub fn sampler<S>(mut self, sampler: S) -> Self
+    where
+        S: Sampler + 'static,
+    {
+        self.sampler = Some(Box::new(sampler));
+        self
+    }
+
     /// Sets the number of workers.
     ///
     /// # Arguments
@@ -82,6 +141,64 @@ where
         self
     }
 
+    /// Sets the number of batches prepared in advance by background threads while the current
+    /// batch is used, so the batcher runs and uploads the next batches to the device during
+    /// the computations.
+    ///
+    /// This is the depth of the queue of the worker threads, limiting the memory used by the
+    /// batches not yet consumed. Without worker threads, a single background thread is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_batches` - The number of batches.
+    ///
+    /// # Returns
+    ///
+    /// The data loader builder.
+    pub fn prefetch(mut self, num_batches: usize) -> Self {
+        self.supervision.prefetch = Some(num_batches);
+        self
+    }
+
+    /// Sets the maximum time waiting for the next batch, after which the iteration fails
+    /// instead of hanging when a worker is stuck.
+    ///
+    /// The [fallible iteration](super::DataLoader::try_iter) returns the
+    /// [timeout error](super::DataLoaderError::Timeout), while the iteration panics.
+    ///
+    /// Without worker threads, a single background thread is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The maximum time.
+    ///
+    /// # Returns
+    ///
+    /// The data loader builder.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.supervision.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the number of panics of the workers tolerated during an iteration, such as an item
+    /// failing to decode.
+    ///
+    /// The failing worker is restarted from its next item, skipping the failing items, and the
+    /// [failure](super::WorkerFailure) with the failing items is logged. Without worker
+    /// threads, a single background thread is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_failures` - The number of failures.
+    ///
+    /// # Returns
+    ///
+    /// The data loader builder.
+    pub fn max_worker_failures(mut self, max_failures: usize) -> Self {
+        self.supervision.max_worker_failures = Some(max_failures);
+        self
+    }
+
     /// Builds the data loader.
     ///
     /// # Arguments
@@ -102,16 +219,48 @@ where
             Some(strategy) => strategy,
             None => Box::new(FixBatchStrategy::new(1)),
         };
+
+        if let Some(sampler) = self.sampler {
+            // The workers batch their part of the items sampled at each iteration.
+            let rng = rng.unwrap_or_else(StdRng::from_entropy);
+            let sampler = Arc::new(SharedSampler::new(sampler, rng));
+            let num_workers = self.num_threads.unwrap_or(1);
+            let mut dataloaders = (0..num_workers).map(|worker| {
+                BatchDataLoader::new(
+                    strategy.clone_dyn(),
+                    dataset.clone(),
+                    self.batcher.clone_dyn(),
+                    None,
+                )
+                .with_sampler(sampler.clone(), worker, num_workers)
+            });
+
+            if self.num_threads.is_none() && !self.supervision.is_enabled() {
+                return Arc::new(dataloaders.next().unwrap());
+            }
+
+            let dataloaders = dataloaders
+                .map(|dataloader| Box::new(dataloader) as Box<dyn DynDataLoader<O>>)
+                .collect();
+            return Arc::new(
+                self.supervision
+                    .apply(MultiThreadDataLoader::new(dataloaders)),
+            );
+        }
+
         if let Some(num_threads) = self.num_threads {
-            return Arc::new(BatchDataLoader::multi_thread(
-                strategy,
-                dataset,
-                self.batcher,
-                num_threads,
-                rng,
-            ));
+            let dataloader =
+                BatchDataLoader::multi_thread(strategy, dataset, self.batcher, num_threads, rng);
+            return Arc::new(self.supervision.apply(dataloader));
+        }
+
+        let dataloader = BatchDataLoader::new(strategy, dataset, self.batcher, rng);
+        if !self.supervision.is_enabled() {
+            return Arc::new(dataloader);
         }
 
-        Arc::new(BatchDataLoader::new(strategy, dataset, self.batcher, rng))
+        // A single worker thread batching all the items.
+        let dataloader = MultiThreadDataLoader::new(vec![Box::new(dataloader)]);
+        Arc::new(self.supervision.apply(dataloader))
     }
 }
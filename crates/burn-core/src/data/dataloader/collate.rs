@@ -0,0 +1,390 @@
+use core::marker::PhantomData;
+
+use super::batcher::Batcher;
+use crate::tensor::{backend::Backend, BasicOps, Bool, Element, Tensor, TensorData};
+
+/// Batches a part of the items, such as one of their fields, without taking their ownership, so
+/// several collate functions can batch the different fields of the same items.
+///
+/// The tuples of collate functions batch the items into the tuples of their batches, and are
+/// turned into a [batcher](Batcher) by the [composed batcher](ComposedBatcher).
+pub trait Collate<I, O>: Send {
+    /// Batches the given items.
+    fn collate(&self, items: &[I]) -> O;
+}
+
+macro_rules! impl_collate_tuple {
+    ($($collate:ident $output:ident $index:tt),+) => {
+        impl<I, $($collate, $output),+> Collate<I, ($($output,)+)> for ($($collate,)+)
+        where
+            $($collate: Collate<I, $output>),+
+        {
+            fn collate(&self, items: &[I]) -> ($($output,)+) {
+                ($(self.$index.collate(items),)+)
+            }
+        }
+    };
+}
+
+impl_collate_tuple!(C0 O0 0);
+impl_collate_tuple!(C0 O0 0, C1 O1 1);
+impl_collate_tuple!(C0 O0 0, C1 O1 1, C2 O2 2);
+impl_collate_tuple!(C0 O0 0, C1 O1 1, C2 O2 2, C3 O3 3);
+impl_collate_tuple!(C0 O0 0, C1 O1 1, C2 O2 2, C3 O3 3, C4 O4 4);
+impl_collate_tuple!(C0 O0 0, C1 O1 1, C2 O2 2, C3 O3 3, C4 O4 4, C5 O5 5);
+
+/// Collates a field of the items with its own batcher.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let tokens = |item: &Sample| item.tokens.clone();
+/// let tokens = FieldBatcher::new(tokens, PaddingBatcher::<B, i64, Int>::new(device));
+/// ```
+#[derive(Clone, Debug)]
+pub struct FieldBatcher<F, B> {
+    field: F,
+    batcher: B,
+}
+
+impl<F, B> FieldBatcher<F, B> {
+    /// Create the collate function batching the values returned by `field` with the batcher.
+    pub fn new(field: F, batcher: B) -> Self {
+        Self { field, batcher }
+    }
+}
+
+impl<I, J, O, F, B> Collate<I, O> for FieldBatcher<F, B>
+where
+    F: Fn(&I) -> J + Send,
+    B: Batcher<J, O>,
+{
+    fn collate(&self, items: &[I]) -> O {
+        self.batcher.batch(items.iter().map(&self.field).collect())
+    }
+}
+
+/// Batcher composed of the collate functions of the fields of multi-modal items, such as an
+/// image, a text and a label, assembling their batches into the batch of the items.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let images = |item: &Sample| item.image.clone();
+/// let tokens = |item: &Sample| item.tokens.clone();
+/// let labels = |item: &Sample| TensorData::from([item.label]);
+///
+/// let batcher = ComposedBatcher::new(
+///     (
+///         FieldBatcher::new(images, StackBatcher::<B, 4, Float>::new(device.clone())),
+///         FieldBatcher::new(tokens, PaddingBatcher::<B, i64, Int>::new(device.clone())),
+///         FieldBatcher::new(labels, StackBatcher::<B, 2, Int>::new(device)),
+///     ),
+///     |(images, tokens, labels)| SampleBatch { images, tokens, labels },
+/// );
+/// ```
+pub struct ComposedBatcher<C, F, P> {
+    collate: C,
+    assemble: F,
+    parts: PhantomData<fn() -> P>,
+}
+
+impl<C, F, P> ComposedBatcher<C, F, P> {
+    /// Create the batcher collating the items with `collate` and assembling the batch of the
+    /// items from the collated parts with `assemble`.
+    pub fn new(collate: C, assemble: F) -> Self {
+        Self {
+            collate,
+            assemble,
+            parts: PhantomData,
+        }
+    }
+}
+
+impl<C: Clone, F: Clone, P> Clone for ComposedBatcher<C, F, P> {
+    fn clone(&self) -> Self {
+        Self::new(self.collate.clone(), self.assemble.clone())
+    }
+}
+
+impl<I, O, C, F, P> Batcher<I, O> for ComposedBatcher<C, F, P>
+where
+    C: Collate<I, P>,
+    F: Fn(P) -> O + Send,
+{
+    fn batch(&self, items: Vec<I>) -> O {
+        (self.assemble)(self.collate.collate(&items))
+    }
+}
+
+/// The length of the padded sequences of a batch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PaddingStrategy {
+    /// The sequences are padded to the length of the longest sequence of the batch.
+    Longest,
+    /// The sequences are padded to the length of the longest sequence of the batch rounded up to
+    /// a multiple of the given value, which limits the number of shapes seen by the kernels.
+    MultipleOf(usize),
+    /// The sequences are padded or truncated to the given length.
+    Fixed(usize),
+}
+
+/// The side of the sequences where the padding is added.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PaddingSide {
+    /// The padding is added after the values.
+    #[default]
+    Right,
+    /// The padding is added before the values, such as for the prompts of autoregressive
+    /// generation.
+    Left,
+}
+
+/// Batch of padded sequences, created by the [padding batcher](PaddingBatcher).
+#[derive(Clone, Debug)]
+pub struct PaddedBatch<B: Backend, K: BasicOps<B>> {
+    /// The padded values with the shape `[batch_size, seq_length]`.
+    pub values: Tensor<B, 2, K>,
+    /// The padding mask with the shape `[batch_size, seq_length]`, `true` for the padding.
+    pub mask_pad: Tensor<B, 2, Bool>,
+    /// The length of each sequence without padding, after truncation.
+    pub lengths: Vec<usize>,
+}
+
+/// Batches sequences of different lengths into a [padded batch](PaddedBatch), such as the
+/// tokens of texts or the samples of audio clips.
+#[derive(Clone, Debug)]
+pub struct PaddingBatcher<B: Backend, E, K> {
+    device: B::Device,
+    strategy: PaddingStrategy,
+    side: PaddingSide,
+    pad_value: E,
+    kind: PhantomData<K>,
+}
+
+impl<B: Backend, E: Element, K> PaddingBatcher<B, E, K> {
+    /// Create the batcher padding the sequences to the longest one with zeros on the right.
+    pub fn new(device: B::Device) -> Self {
+        Self {
+            device,
+            strategy: PaddingStrategy::Longest,
+            side: PaddingSide::Right,
+            pad_value: E::default(),
+            kind: PhantomData,
+        }
+    }
+
+    /// Sets the length of the padded sequences.
+    pub fn with_strategy(mut self, strategy: PaddingStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Sets the side where the padding is added.
+    pub fn with_side(mut self, side: PaddingSide) -> Self {
+        self.side = side;
+        self
+    }
+
+    /// Sets the value of the padding, such as the padding token.
+    pub fn with_pad_value(mut self, pad_value: E) -> Self {
+        self.pad_value = pad_value;
+        self
+    }
+
+    fn seq_length(&self, max_length: usize) -> usize {
+        match self.strategy {
+            PaddingStrategy::Longest => max_length,
+            PaddingStrategy::MultipleOf(multiple) => max_length.div_ceil(multiple) * multiple,
+            PaddingStrategy::Fixed(length) => length,
+        }
+    }
+}
+
+impl<B, E, K> Batcher<Vec<E>, PaddedBatch<B, K>> for PaddingBatcher<B, E, K>
+where
+    B: Backend,
+    E: Element,
+    K: BasicOps<B> + Send,
+{
+    fn batch(&self, items: Vec<Vec<E>>) -> PaddedBatch<B, K> {
+        let batch_size = items.len();
+        let max_length = items.iter().map(Vec::len).max().unwrap_or(0);
+        let seq_length = self.seq_length(max_length);
+
+        let mut values = Vec::with_capacity(batch_size * seq_length);
+        let mut mask_pad = Vec::with_capacity(batch_size * seq_length);
+        let mut lengths = Vec::with_capacity(batch_size);
+
+        for mut item in items {
+            item.truncate(seq_length);
+            let padding = seq_length - item.len();
+            lengths.push(item.len());
+
+            if self.side == PaddingSide::Left {
+                values.resize(values.len() + padding, self.pad_value);
+                mask_pad.resize(mask_pad.len() + padding, true);
+            }
+            mask_pad.resize(mask_pad.len() + item.len(), false);
+            values.extend(item);
+            if self.side == PaddingSide::Right {
+                values.resize(values.len() + padding, self.pad_value);
+                mask_pad.resize(mask_pad.len() + padding, true);
+            }
+        }
+
+        let values = TensorData::new(values, [batch_size, seq_length]).convert::<K::Elem>();
+
+        PaddedBatch {
+            values: Tensor::from_data(values, &self.device),
+            mask_pad: Tensor::from_data(
+                TensorData::new(mask_pad, [batch_size, seq_length]),
+                &self.device,
+            ),
+            lengths,
+        }
+    }
+}
+
+/// Batches tensors of the same shape by stacking them along a new first dimension, such as the
+/// images or the labels of the items.
+#[derive(Clone, Debug)]
+pub struct StackBatcher<B: Backend, const D: usize, K> {
+    device: B::Device,
+    kind: PhantomData<K>,
+}
+
+impl<B: Backend, const D: usize, K> StackBatcher<B, D, K> {
+    /// Create the batcher creating the tensors on the device.
+    pub fn new(device: B::Device) -> Self {
+        Self {
+            device,
+            kind: PhantomData,
+        }
+    }
+}
+
+impl<B, const D: usize, K> Batcher<TensorData, Tensor<B, D, K>> for StackBatcher<B, D, K>
+where
+    B: Backend,
+    K: BasicOps<B> + Send,
+{
+    fn batch(&self, items: Vec<TensorData>) -> Tensor<B, D, K> {
+        let mut shape = vec![items.len()];
+        shape.extend(
+            items
+                .first()
+                .map(|item| item.shape.clone())
+                .unwrap_or_default(),
+        );
+        let mut values = Vec::new();
+
+        for item in items {
+            assert_eq!(
+                item.shape,
+                shape[1..],
+                "The stacked tensors should have the same shape"
+            );
+            values.extend(item.convert::<K::Elem>().iter::<K::Elem>());
+        }
+
+        Tensor::from_data(TensorData::new(values, shape), &self.device)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tensor::{Float, Int};
+    use crate::TestBackend;
+
+    #[derive(Clone)]
+    struct Sample {
+        image: TensorData,
+        tokens: Vec<i64>,
+    }
+
+    struct SampleBatch {
+        images: Tensor<TestBackend, 3>,
+        tokens: PaddedBatch<TestBackend, Int>,
+    }
+
+    fn samples() -> Vec<Sample> {
+        vec![
+            Sample {
+                image: TensorData::from([[1.0, 2.0], [3.0, 4.0]]),
+                tokens: vec![5, 6, 7],
+            },
+            Sample {
+                image: TensorData::from([[5.0, 6.0], [7.0, 8.0]]),
+                tokens: vec![8],
+            },
+        ]
+    }
+
+    #[test]
+    fn composed_batcher_should_batch_each_field() {
+        let batcher = ComposedBatcher::new(
+            (
+                FieldBatcher::new(
+                    |item: &Sample| item.image.clone(),
+                    StackBatcher::<TestBackend, 3, Float>::new(Default::default()),
+                ),
+                FieldBatcher::new(
+                    |item: &Sample| item.tokens.clone(),
+                    PaddingBatcher::<TestBackend, i64, Int>::new(Default::default())
+                        .with_pad_value(-1),
+                ),
+            ),
+            |(images, tokens)| SampleBatch { images, tokens },
+        );
+
+        let batch = batcher.batch(samples());
+
+        batch.images.into_data().assert_eq(
+            &TensorData::from([[[1.0, 2.0], [3.0, 4.0]], [[5.0, 6.0], [7.0, 8.0]]]),
+            false,
+        );
+        batch
+            .tokens
+            .values
+            .into_data()
+            .assert_eq(&TensorData::from([[5, 6, 7], [8, -1, -1]]), false);
+        assert_eq!(batch.tokens.lengths, [3, 1]);
+    }
+
+    #[test]
+    fn padding_batcher_should_pad_on_the_left_to_a_multiple() {
+        let batcher = PaddingBatcher::<TestBackend, i64, Int>::new(Default::default())
+            .with_strategy(PaddingStrategy::MultipleOf(4))
+            .with_side(PaddingSide::Left);
+
+        let batch = batcher.batch(vec![vec![1, 2, 3, 4, 5], vec![6]]);
+
+        batch.values.into_data().assert_eq(
+            &TensorData::from([[0, 0, 0, 1, 2, 3, 4, 5], [0, 0, 0, 0, 0, 0, 0, 6]]),
+            false,
+        );
+        batch.mask_pad.into_data().assert_eq(
+            &TensorData::from([
+                [true, true, true, false, false, false, false, false],
+                [true, true, true, true, true, true, true, false],
+            ]),
+            false,
+        );
+    }
+
+    #[test]
+    fn padding_batcher_should_truncate_to_the_fixed_length() {
+        let batcher = PaddingBatcher::<TestBackend, i64, Int>::new(Default::default())
+            .with_strategy(PaddingStrategy::Fixed(2));
+
+        let batch = batcher.batch(vec![vec![1, 2, 3], vec![4]]);
+
+        batch
+            .values
+            .into_data()
+            .assert_eq(&TensorData::from([[1, 2], [4, 0]]), false);
+        assert_eq!(batch.lengths, [2, 1]);
+    }
+}
@@ -0,0 +1,177 @@
+use super::batcher::Batcher;
+use crate::tensor::{backend::Backend, Bool, Int, Tensor, TensorData};
+use burn_dataset::vision::CocoItem;
+
+/// Batch of images with their object detection targets, padded to the largest image and to the
+/// largest number of objects of the batch, created by the [detection batcher](DetectionBatcher).
+#[derive(Clone, Debug)]
+pub struct DetectionBatch<B: Backend> {
+    /// Images in `[0, 1]` with the shape `[batch_size, 3, height, width]`, padded with zeros at
+    /// the bottom and on the right.
+    pub images: Tensor<B, 4>,
+    /// The `[x, y, width, height]` boxes of the objects with the shape
+    /// `[batch_size, num_objects, 4]`.
+    pub boxes: Tensor<B, 3>,
+    /// The labels of the objects with the shape `[batch_size, num_objects]`.
+    pub labels: Tensor<B, 2, Int>,
+    /// Whether each object is an actual object or padding, with the shape
+    /// `[batch_size, num_objects]`.
+    pub valid: Tensor<B, 2, Bool>,
+    /// The masks of the objects with the shape `[batch_size, num_objects, height, width]`,
+    /// when all the objects have a mask.
+    pub masks: Option<Tensor<B, 4>>,
+}
+
+/// Batches the [COCO items](CocoItem) into [detection batches](DetectionBatch), padding the
+/// variable number of objects of each image.
+///
+/// The number of objects of a batch is at least one, so the tensors are never empty even when
+/// the images of the batch have no objects.
+#[derive(Clone, Debug)]
+pub struct DetectionBatcher<B: Backend> {
+    device: B::Device,
+}
+
+impl<B: Backend> DetectionBatcher<B> {
+    /// Create the batcher creating the tensors on the device.
+    pub fn new(device: B::Device) -> Self {
+        Self { device }
+    }
+}
+
+impl<B: Backend> Batcher<CocoItem, DetectionBatch<B>> for DetectionBatcher<B> {
+    fn batch(&self, items: Vec<CocoItem>) -> DetectionBatch<B> {
+        let batch_size = items.len();
+        let height = items
+            .iter()
+            .map(|item| item.image.shape[0])
+            .max()
+            .unwrap_or(0);
+        let width = items
+            .iter()
+            .map(|item| item.image.shape[1])
+            .max()
+            .unwrap_or(0);
+        let num_objects = items
+            .iter()
+            .map(|item| item.annotations.len())
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let with_masks = items
+            .iter()
+            .flat_map(|item| item.annotations.iter())
+            .all(|annotation| annotation.mask.is_some());
+
+        let mut images = vec![0.0f32; batch_size * 3 * height * width];
+        let mut boxes = vec![0.0f32; batch_size * num_objects * 4];
+        let mut labels = vec![0i64; batch_size * num_objects];
+        let mut valid = vec![false; batch_size * num_objects];
+        let mut masks = match with_masks {
+            true => vec![0.0f32; batch_size * num_objects * height * width],
+            false => Vec::new(),
+        };
+
+        for (b, item) in items.into_iter().enumerate() {
+            // The pixels are channel last, the images channel first.
+            let image_width = item.image.shape[1];
+            let pixels = item.image.to_vec::<u8>().expect("The image pixels are u8");
+            for (index, &pixel) in pixels.iter().enumerate() {
+                let (y, x, c) = (
+                    index / (image_width * 3),
+                    index / 3 % image_width,
+                    index % 3,
+                );
+                images[((b * 3 + c) * height + y) * width + x] = pixel as f32 / 255.0;
+            }
+
+            for (o, annotation) in item.annotations.into_iter().enumerate() {
+                let object = b * num_objects + o;
+                boxes[object * 4..object * 4 + 4].copy_from_slice(&annotation.bbox.coords);
+                labels[object] = annotation.bbox.label as i64;
+                valid[object] = true;
+
+                if let Some(mask) = annotation.mask.filter(|_| with_masks) {
+                    let mask = mask.to_vec::<u8>().expect("The mask values are u8");
+                    for (index, &value) in mask.iter().enumerate() {
+                        let (y, x) = (index / image_width, index % image_width);
+                        masks[(object * height + y) * width + x] = value as f32;
+                    }
+                }
+            }
+        }
+
+        let float = |values: Vec<f32>, shape: Vec<usize>| {
+            TensorData::new(values, shape).convert::<B::FloatElem>()
+        };
+
+        DetectionBatch {
+            images: Tensor::from_data(
+                float(images, vec![batch_size, 3, height, width]),
+                &self.device,
+            ),
+            boxes: Tensor::from_data(float(boxes, vec![batch_size, num_objects, 4]), &self.device),
+            labels: Tensor::from_data(
+                TensorData::new(labels, [batch_size, num_objects]).convert::<B::IntElem>(),
+                &self.device,
+            ),
+            valid: Tensor::from_data(
+                TensorData::new(valid, [batch_size, num_objects]),
+                &self.device,
+            ),
+            masks: with_masks.then(|| {
+                Tensor::from_data(
+                    float(masks, vec![batch_size, num_objects, height, width]),
+                    &self.device,
+                )
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use burn_dataset::vision::{BoundingBox, CocoAnnotation};
+
+    fn item(height: usize, width: usize, labels: &[usize]) -> CocoItem {
+        CocoItem {
+            image_id: 0,
+            image: TensorData::new(vec![255u8; height * width * 3], [height, width, 3]),
+            annotations: labels
+                .iter()
+                .map(|&label| CocoAnnotation {
+                    bbox: BoundingBox {
+                        coords: [0.0, 0.0, 1.0, 1.0],
+                        label,
+                    },
+                    mask: None,
+                    is_crowd: false,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn detection_batcher_should_pad_the_images_and_the_objects() {
+        let batcher = DetectionBatcher::<TestBackend>::new(Default::default());
+
+        let batch = batcher.batch(vec![item(1, 2, &[3]), item(2, 1, &[1, 2])]);
+
+        assert_eq!(batch.images.dims(), [2, 3, 2, 2]);
+        batch.images.slice([0..2, 0..1]).into_data().assert_eq(
+            &TensorData::from([[[[1.0f32, 1.0], [0.0, 0.0]]], [[[1.0, 0.0], [1.0, 0.0]]]]),
+            false,
+        );
+        batch
+            .labels
+            .into_data()
+            .assert_eq(&TensorData::from([[3, 0], [1, 2]]), false);
+        batch
+            .valid
+            .into_data()
+            .assert_eq(&TensorData::from([[true, false], [true, true]]), false);
+        assert!(batch.masks.is_none());
+    }
+}
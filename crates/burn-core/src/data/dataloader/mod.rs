@@ -7,6 +7,19 @@ mod strategy;
 /// Module for batching items.
 pub mod batcher;
 
+/// Module for sampling the items of the iterations.
+pub mod sampler;
+
+/// Module for composing the batchers of multi-modal items.
+pub mod collate;
+
+/// Module for batching packed language-model sequences.
+pub mod packing;
+
+/// Module for batching object detection items.
+#[cfg(feature = "vision")]
+pub mod detection;
+
 pub use base::*;
 pub use batch::*;
 pub use builder::*;
@@ -1,12 +1,20 @@
-use super::{DataLoader, DataLoaderIterator, DynDataLoader, Progress};
+use super::{DataLoader, DataLoaderError, DataLoaderIterator, DynDataLoader, Progress};
+use std::any::Any;
+use std::fmt::Display;
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
 use std::sync::mpsc;
 use std::thread;
+use std::time::Duration;
 
 const MAX_QUEUED_ITEMS: usize = 100;
 
 /// A multi-threaded data loader that can be used to iterate over a dataset.
 pub struct MultiThreadDataLoader<O> {
     dataloaders: Vec<Box<dyn DynDataLoader<O>>>,
+    max_queued_items: usize,
+    timeout: Option<Duration>,
+    max_failures: usize,
 }
 
 /// A message that can be sent between threads.
@@ -15,12 +23,42 @@ pub enum Message<O> {
     /// A batch of items.
     Batch(usize, O, Progress),
 
+    /// The thread failed to load a batch.
+    Failed(WorkerFailure),
+
     /// The thread is done.
     Done,
 }
 
+/// The failure of a thread of a [multi-threaded data loader](MultiThreadDataLoader) while
+/// loading a batch, when reading an item of the dataset or batching the items panicked.
+#[derive(Clone, Debug)]
+pub struct WorkerFailure {
+    /// The index of the thread.
+    pub worker: usize,
+    /// The items of the thread loaded for the batch, in the order of the iteration.
+    ///
+    /// When reading an item panicked, the failing item is the last one.
+    pub items: Range<usize>,
+    /// The panic message.
+    pub message: String,
+}
+
+impl Display for WorkerFailure {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Data loader worker {} failed on the items {:?}: {}",
+            self.worker, self.items, self.message
+        )
+    }
+}
+
 struct MultiThreadsDataloaderIterator<O> {
     num_done: usize,
+    num_failures: usize,
+    timeout: Option<Duration>,
+    max_failures: usize,
     workers: Vec<thread::JoinHandle<()>>,
     receiver: mpsc::Receiver<Message<O>>,
     progresses: Vec<Progress>,
@@ -37,7 +75,65 @@ impl<O> MultiThreadDataLoader<O> {
     ///
     /// The multi-threaded data loader.
     pub fn new(dataloaders: Vec<Box<dyn DynDataLoader<O>>>) -> Self {
-        Self { dataloaders }
+        Self {
+            dataloaders,
+            max_queued_items: MAX_QUEUED_ITEMS,
+            timeout: None,
+            max_failures: 0,
+        }
+    }
+
+    /// Sets the maximum number of batches prepared in advance by the threads while the
+    /// previous batches are used.
+    ///
+    /// When the batcher creates the tensors on the training device, the batches are uploaded
+    /// in advance as well, overlapping the transfers with the computations. Each prefetched
+    /// batch stays in the memory of the device until it is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_batches` - The number of batches.
+    ///
+    /// # Returns
+    ///
+    /// The multi-threaded data loader.
+    pub fn with_prefetch(mut self, num_batches: usize) -> Self {
+        self.max_queued_items = num_batches;
+        self
+    }
+
+    /// Sets the maximum time waiting for the next batch, after which the iteration fails
+    /// instead of hanging on a stuck thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - The maximum time.
+    ///
+    /// # Returns
+    ///
+    /// The multi-threaded data loader.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the number of [failures](WorkerFailure) of the threads tolerated during an
+    /// iteration, zero by default.
+    ///
+    /// A thread failing to load a batch is restarted from its next item after logging the
+    /// failure, skipping the item which failed to be read or the items which failed to be
+    /// batched. When more failures happen, the iteration fails with the last failure.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_failures` - The number of failures.
+    ///
+    /// # Returns
+    ///
+    /// The multi-threaded data loader.
+    pub fn with_max_failures(mut self, max_failures: usize) -> Self {
+        self.max_failures = max_failures;
+        self
     }
 }
 
@@ -46,7 +142,30 @@ where
     O: Send + 'static + std::fmt::Debug,
 {
     fn iter<'a>(&'a self) -> Box<dyn DataLoaderIterator<O> + 'a> {
-        let (sender, receiver) = mpsc::sync_channel::<Message<O>>(MAX_QUEUED_ITEMS);
+        Box::new(self.spawn())
+    }
+
+    fn try_iter<'a>(&'a self) -> Box<dyn DataLoaderIterator<Result<O, DataLoaderError>> + 'a>
+    where
+        O: 'a,
+    {
+        Box::new(FallibleIterator {
+            iterator: self.spawn(),
+        })
+    }
+
+    fn num_items(&self) -> usize {
+        self.dataloaders.iter().map(|dl| dl.num_items()).sum()
+    }
+}
+
+impl<O> MultiThreadDataLoader<O>
+where
+    O: Send + 'static + std::fmt::Debug,
+{
+    /// Starts the worker threads of an iteration.
+    fn spawn(&self) -> MultiThreadsDataloaderIterator<O> {
+        let (sender, receiver) = mpsc::sync_channel::<Message<O>>(self.max_queued_items);
 
         let mut progresses = Vec::with_capacity(self.dataloaders.len());
 
@@ -61,10 +180,20 @@ where
 
                 thread::spawn(move || {
                     let mut iterator = dataloader_cloned.iter();
-                    while let Some(item) = iterator.next() {
-                        let progress = iterator.progress();
+                    loop {
+                        let start = iterator.progress().items_processed;
+                        let message =
+                            match panic::catch_unwind(AssertUnwindSafe(|| iterator.next())) {
+                                Ok(Some(item)) => Message::Batch(index, item, iterator.progress()),
+                                Ok(None) => break,
+                                Err(payload) => Message::Failed(WorkerFailure {
+                                    worker: index,
+                                    items: start..iterator.progress().items_processed,
+                                    message: panic_message(payload),
+                                }),
+                            };
 
-                        match sender_cloned.send(Message::Batch(index, item, progress)) {
+                        match sender_cloned.send(message) {
                             Ok(_) => {}
                             // The receiver is probably gone, no need to panic, just need to stop
                             // iterating.
@@ -77,13 +206,11 @@ where
             })
             .collect();
 
-        Box::new(MultiThreadsDataloaderIterator::new(
-            receiver, handlers, progresses,
-        ))
-    }
+        let mut iterator = MultiThreadsDataloaderIterator::new(receiver, handlers, progresses);
+        iterator.timeout = self.timeout;
+        iterator.max_failures = self.max_failures;
 
-    fn num_items(&self) -> usize {
-        self.dataloaders.iter().map(|dl| dl.num_items()).sum()
+        iterator
     }
 }
 
@@ -95,6 +222,9 @@ impl<O> MultiThreadsDataloaderIterator<O> {
     ) -> Self {
         MultiThreadsDataloaderIterator {
             num_done: 0,
+            num_failures: 0,
+            timeout: None,
+            max_failures: 0,
             workers,
             receiver,
             progresses,
@@ -119,20 +249,51 @@ impl<O: std::fmt::Debug> Iterator for MultiThreadsDataloaderIterator<O> {
     type Item = O;
 
     fn next(&mut self) -> Option<O> {
+        self.try_next()
+            .map(|item| item.unwrap_or_else(|err| panic!("{err}")))
+    }
+}
+
+impl<O> MultiThreadsDataloaderIterator<O> {
+    /// The next batch, or the error stopping the iteration.
+    fn try_next(&mut self) -> Option<Result<O, DataLoaderError>> {
         if self.workers.is_empty() {
             return None;
         }
 
         loop {
-            let item = self.receiver.recv();
-            let item = item.unwrap();
+            let item = match self.timeout {
+                Some(timeout) => self
+                    .receiver
+                    .recv_timeout(timeout)
+                    .map_err(|err| match err {
+                        mpsc::RecvTimeoutError::Timeout => DataLoaderError::Timeout(timeout),
+                        mpsc::RecvTimeoutError::Disconnected => DataLoaderError::Disconnected,
+                    }),
+                None => self
+                    .receiver
+                    .recv()
+                    .map_err(|_| DataLoaderError::Disconnected),
+            };
+
+            let item = match item {
+                Ok(item) => item,
+                Err(err) => return Some(Err(self.stop(err))),
+            };
 
             match item {
                 Message::Batch(index, item, progress) => {
                     if let Some(current) = self.progresses.get_mut(index) {
                         *current = progress;
                     }
-                    return Some(item);
+                    return Some(Ok(item));
+                }
+                Message::Failed(failure) => {
+                    self.num_failures += 1;
+                    if self.num_failures > self.max_failures {
+                        return Some(Err(self.stop(DataLoaderError::WorkerFailed(failure))));
+                    }
+                    log::error!("{failure}");
                 }
                 Message::Done => {
                     self.num_done += 1;
@@ -147,4 +308,42 @@ impl<O: std::fmt::Debug> Iterator for MultiThreadsDataloaderIterator<O> {
             }
         }
     }
+
+    /// Ends the iteration after an error, detaching the workers which stop when sending their
+    /// next batch.
+    fn stop(&mut self, err: DataLoaderError) -> DataLoaderError {
+        self.workers.clear();
+        err
+    }
+}
+
+/// The iterator of a multi-threaded data loader returning its errors.
+struct FallibleIterator<O> {
+    iterator: MultiThreadsDataloaderIterator<O>,
+}
+
+impl<O> Iterator for FallibleIterator<O> {
+    type Item = Result<O, DataLoaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iterator.try_next()
+    }
+}
+
+impl<O: std::fmt::Debug> DataLoaderIterator<Result<O, DataLoaderError>> for FallibleIterator<O> {
+    fn progress(&self) -> Progress {
+        self.iterator.progress()
+    }
+}
+
+/// The message of a panic caught in a worker thread, the panic itself being reported by the
+/// panic hook of the application.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&'static str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => "unknown panic".to_string(),
+        },
+    }
 }
@@ -0,0 +1,128 @@
+use super::batcher::Batcher;
+use crate::tensor::{backend::Backend, Bool, Int, Tensor, TensorData};
+use burn_dataset::transform::PackedSequence;
+
+/// Batch of packed sequences for next-token prediction, created by the
+/// [packed batcher](PackedBatcher).
+#[derive(Clone, Debug)]
+pub struct PackedBatch<B: Backend> {
+    /// The tokens with the shape `[batch_size, seq_length]`.
+    pub tokens: Tensor<B, 2, Int>,
+    /// The next token of each token with the shape `[batch_size, seq_length]`, zero when the
+    /// token has no target.
+    pub targets: Tensor<B, 2, Int>,
+    /// The position of each token in its document with the shape `[batch_size, seq_length]`.
+    pub positions: Tensor<B, 2, Int>,
+    /// Whether the loss of each target is computed with the shape `[batch_size, seq_length]`.
+    pub loss_mask: Tensor<B, 2, Bool>,
+    /// The causal attention mask with the shape `[batch_size, seq_length, seq_length]`, `true`
+    /// when the token can't attend to the other token, which is either after it or in another
+    /// document.
+    pub mask_attn: Tensor<B, 3, Bool>,
+}
+
+/// Batches the [packed sequences](PackedSequence) into [packed batches](PackedBatch), with an
+/// attention mask preventing the documents of a sequence from attending to each other.
+#[derive(Clone, Debug)]
+pub struct PackedBatcher<B: Backend> {
+    device: B::Device,
+}
+
+impl<B: Backend> PackedBatcher<B> {
+    /// Create the batcher creating the tensors on the device.
+    pub fn new(device: B::Device) -> Self {
+        Self { device }
+    }
+}
+
+impl<B: Backend> Batcher<PackedSequence, PackedBatch<B>> for PackedBatcher<B> {
+    fn batch(&self, items: Vec<PackedSequence>) -> PackedBatch<B> {
+        let batch_size = items.len();
+        let seq_length = items.first().map(|item| item.tokens.len()).unwrap_or(0);
+
+        let mut tokens = Vec::with_capacity(batch_size * seq_length);
+        let mut targets = Vec::with_capacity(batch_size * seq_length);
+        let mut positions = Vec::with_capacity(batch_size * seq_length);
+        let mut loss_mask = Vec::with_capacity(batch_size * seq_length);
+        let mut mask_attn = Vec::with_capacity(batch_size * seq_length * seq_length);
+
+        for item in items {
+            assert_eq!(
+                item.tokens.len(),
+                seq_length,
+                "The sequences of a batch should have the same length"
+            );
+            let mask = item.loss_mask();
+
+            for (i, &segment) in item.segment_ids.iter().enumerate() {
+                tokens.push(item.tokens[i] as i64);
+                targets.push(match mask[i] {
+                    true => item.tokens[i + 1] as i64,
+                    false => 0,
+                });
+                positions.push(item.positions[i] as i64);
+
+                // The padding attends to the previous padding, so no row is fully masked.
+                mask_attn.extend((0..seq_length).map(|j| j > i || item.segment_ids[j] != segment));
+            }
+            loss_mask.extend(mask);
+        }
+
+        let int = |values: Vec<i64>| {
+            Tensor::from_data(
+                TensorData::new(values, [batch_size, seq_length]).convert::<B::IntElem>(),
+                &self.device,
+            )
+        };
+
+        PackedBatch {
+            tokens: int(tokens),
+            targets: int(targets),
+            positions: int(positions),
+            loss_mask: Tensor::from_data(
+                TensorData::new(loss_mask, [batch_size, seq_length]),
+                &self.device,
+            ),
+            mask_attn: Tensor::from_data(
+                TensorData::new(mask_attn, [batch_size, seq_length, seq_length]),
+                &self.device,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+
+    #[test]
+    fn packed_batcher_should_mask_the_other_documents() {
+        let batcher = PackedBatcher::<TestBackend>::new(Default::default());
+        let sequence = PackedSequence {
+            tokens: vec![5, 6, 7, 0],
+            segment_ids: vec![1, 1, 2, 0],
+            positions: vec![0, 1, 0, 0],
+        };
+
+        let batch = batcher.batch(vec![sequence]);
+
+        batch
+            .targets
+            .into_data()
+            .assert_eq(&TensorData::from([[6, 0, 0, 0]]), false);
+        batch
+            .loss_mask
+            .into_data()
+            .assert_eq(&TensorData::from([[true, false, false, false]]), false);
+        batch.mask_attn.into_data().assert_eq(
+            &TensorData::from([[
+                [false, true, true, true],
+                [false, false, true, true],
+                [true, true, false, true],
+                [true, true, true, false],
+            ]]),
+            false,
+        );
+    }
+}
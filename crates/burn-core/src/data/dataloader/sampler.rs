@@ -0,0 +1,717 @@
+use burn_dataset::Dataset;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::sync::{Arc, Mutex};
+
+/// A sampler selects the items of the dataset used at each iteration of a data loader,
+/// registered with the [data loader builder](super::DataLoaderBuilder::sampler).
+pub trait Sampler: Send + Sync {
+    /// The number of items sampled at each iteration from a dataset of `num_items` items.
+    fn num_samples(&self, num_items: usize) -> usize;
+
+    /// Samples the indices of the items of the iteration.
+    ///
+    /// # Arguments
+    ///
+    /// * `num_items` - The number of items of the dataset.
+    /// * `iteration` - The number of iterations of the data loader before this one.
+    /// * `rng` - The rng of the data loader, seeded when the data loader is shuffled.
+    ///
+    /// # Returns
+    ///
+    /// The indices of the items, in the order they are batched.
+    fn sample(&self, num_items: usize, iteration: usize, rng: &mut StdRng) -> Vec<usize>;
+}
+
+/// Samples the items with a probability proportional to their weight, to rebalance
+/// imbalanced datasets without duplicating items.
+#[derive(Clone, Debug)]
+pub struct WeightedRandomSampler {
+    weights: Vec<f64>,
+    num_samples: Option<usize>,
+    replacement: bool,
+}
+
+impl WeightedRandomSampler {
+    /// Create the sampler with the weight of each item of the dataset, sampling as many items
+    /// as the dataset contains with replacement.
+    pub fn new(weights: Vec<f64>) -> Self {
+        assert!(
+            weights
+                .iter()
+                .all(|weight| weight.is_finite() && *weight >= 0.0)
+                && weights.iter().any(|weight| *weight > 0.0),
+            "The weights should be positive, with at least one non-zero weight"
+        );
+
+        Self {
+            weights,
+            num_samples: None,
+            replacement: true,
+        }
+    }
+
+    /// Sets the number of items sampled at each iteration.
+    pub fn with_num_samples(mut self, num_samples: usize) -> Self {
+        self.num_samples = Some(num_samples);
+        self
+    }
+
+    /// Sets whether an item can be sampled multiple times in an iteration.
+    ///
+    /// Without replacement, the items with a zero weight are never sampled, so an iteration
+    /// contains at most the number of items with a non-zero weight.
+    pub fn with_replacement(mut self, replacement: bool) -> Self {
+        self.replacement = replacement;
+        self
+    }
+}
+
+impl Sampler for WeightedRandomSampler {
+    fn num_samples(&self, num_items: usize) -> usize {
+        assert_eq!(
+            self.weights.len(),
+            num_items,
+            "The sampler should have a weight for each item of the dataset"
+        );
+
+        let num_samples = self.num_samples.unwrap_or(num_items);
+        match self.replacement {
+            true => num_samples,
+            false => {
+                let num_weighted = self.weights.iter().filter(|weight| **weight > 0.0).count();
+                num_samples.min(num_weighted)
+            }
+        }
+    }
+
+    fn sample(&self, num_items: usize, _iteration: usize, rng: &mut StdRng) -> Vec<usize> {
+        let num_samples = self.num_samples(num_items);
+
+        if self.replacement {
+            let distribution =
+                WeightedIndex::new(&self.weights).expect("The weights are validated");
+            return distribution.sample_iter(rng).take(num_samples).collect();
+        }
+
+        // Weighted sampling without replacement keeping the items with the largest
+        // `u^(1/weight)` keys, `u` being uniform in [0, 1).
+        let mut keys = self
+            .weights
+            .iter()
+            .enumerate()
+            .filter(|(_, weight)| **weight > 0.0)
+            .map(|(index, weight)| (rng.gen::<f64>().powf(1.0 / weight), index))
+            .collect::<Vec<_>>();
+        keys.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        keys.into_iter()
+            .take(num_samples)
+            .map(|(_, index)| index)
+            .collect()
+    }
+}
+
+/// Partitions the items between the processes of a data-parallel training, each one iterating
+/// over a different part of the dataset.
+///
+/// The items are shuffled with the same seed in all the processes, changing at each epoch, so
+/// the parts never overlap. Without dropping the last items, the first items are repeated so
+/// that all the processes have the same number of items.
+#[derive(Clone, Debug)]
+pub struct DistributedSampler {
+    world_size: usize,
+    rank: usize,
+    seed: u64,
+    shuffle: bool,
+    drop_last: bool,
+    start_epoch: usize,
+}
+
+impl DistributedSampler {
+    /// Create the sampler of the process with the given rank among `world_size` processes.
+    pub fn new(world_size: usize, rank: usize) -> Self {
+        assert!(
+            rank < world_size,
+            "The rank should be lower than the world size"
+        );
+
+        Self {
+            world_size,
+            rank,
+            seed: 0,
+            shuffle: true,
+            drop_last: false,
+            start_epoch: 0,
+        }
+    }
+
+    /// Sets the seed of the shuffling, which must be the same for all the processes.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets whether the items are shuffled at each epoch.
+    pub fn with_shuffle(mut self, shuffle: bool) -> Self {
+        self.shuffle = shuffle;
+        self
+    }
+
+    /// Sets whether the last items are dropped to evenly split the dataset, instead of repeating
+    /// the first ones.
+    pub fn with_drop_last(mut self, drop_last: bool) -> Self {
+        self.drop_last = drop_last;
+        self
+    }
+
+    /// Sets the epoch of the first iteration, to continue the shuffling when resuming a
+    /// training.
+    pub fn with_start_epoch(mut self, epoch: usize) -> Self {
+        self.start_epoch = epoch;
+        self
+    }
+}
+
+impl Sampler for DistributedSampler {
+    fn num_samples(&self, num_items: usize) -> usize {
+        match self.drop_last {
+            true => num_items / self.world_size,
+            false => num_items.div_ceil(self.world_size),
+        }
+    }
+
+    fn sample(&self, num_items: usize, iteration: usize, _rng: &mut StdRng) -> Vec<usize> {
+        let total = self.num_samples(num_items) * self.world_size;
+        let mut indices = (0..num_items).collect::<Vec<_>>();
+
+        if self.shuffle {
+            // Seeded by the epoch, not by the rng of the process.
+            let epoch = (self.start_epoch + iteration) as u64;
+            let mut rng = StdRng::seed_from_u64(self.seed.wrapping_add(epoch));
+            indices.shuffle(&mut rng);
+        }
+
+        // Repeat the first items when padding, possibly several times for tiny datasets.
+        let indices = indices.into_iter().cycle().take(total).collect::<Vec<_>>();
+
+        indices
+            .into_iter()
+            .skip(self.rank)
+            .step_by(self.world_size)
+            .collect()
+    }
+}
+
+/// Groups the items of similar lengths, such as the number of tokens of a text or the duration
+/// of an audio clip, in the same batches to minimize the padding.
+///
+/// The items are put in buckets by length, either delimited by the
+/// [boundaries](Self::with_boundaries) or by sorting all the items by length. Each bucket is
+/// split in batches, and the batches are shuffled. The items left over by the buckets are
+/// batched together at the end of each bucket, so that only the last batch is incomplete.
+///
+/// The batch size must be the one of the [batch strategy](super::FixBatchStrategy) of the data
+/// loader.
+#[derive(Clone, Debug)]
+pub struct LengthBucketSampler {
+    lengths: Vec<usize>,
+    batch_size: usize,
+    boundaries: Option<Vec<usize>>,
+    shuffle: bool,
+    drop_last: bool,
+}
+
+impl LengthBucketSampler {
+    /// Create the sampler with the length of each item of the dataset and the batch size.
+    pub fn new(lengths: Vec<usize>, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "The batch size should be at least one");
+
+        Self {
+            lengths,
+            batch_size,
+            boundaries: None,
+            shuffle: true,
+            drop_last: false,
+        }
+    }
+
+    /// Create the sampler computing the length of each item of the dataset.
+    pub fn from_dataset<I, D, F>(dataset: &D, length: F, batch_size: usize) -> Self
+    where
+        D: Dataset<I>,
+        F: Fn(&I) -> usize,
+    {
+        let lengths = dataset.iter().map(|item| length(&item)).collect();
+        Self::new(lengths, batch_size)
+    }
+
+    /// Sets the boundaries of the buckets, an item of length `length` being in the bucket of
+    /// the first boundary greater than `length`, or in the last bucket.
+    ///
+    /// Without boundaries, all the items are sorted by length.
+    pub fn with_boundaries(mut self, mut boundaries: Vec<usize>) -> Self {
+        boundaries.sort_unstable();
+        boundaries.dedup();
+        self.boundaries = Some(boundaries);
+        self
+    }
+
+    /// Sets whether the items of the buckets and the batches are shuffled at each iteration.
+    pub fn with_shuffle(mut self, shuffle: bool) -> Self {
+        self.shuffle = shuffle;
+        self
+    }
+
+    /// Sets whether the last incomplete batch is dropped.
+    pub fn with_drop_last(mut self, drop_last: bool) -> Self {
+        self.drop_last = drop_last;
+        self
+    }
+
+    fn buckets(&self, rng: &mut StdRng) -> Vec<Vec<usize>> {
+        let mut indices = (0..self.lengths.len()).collect::<Vec<_>>();
+        if self.shuffle {
+            indices.shuffle(rng);
+        }
+
+        let boundaries = match &self.boundaries {
+            Some(boundaries) => boundaries,
+            None => {
+                // The sort is stable, so the items of the same length stay shuffled.
+                indices.sort_by_key(|index| self.lengths[*index]);
+                return vec![indices];
+            }
+        };
+
+        let mut buckets = vec![Vec::new(); boundaries.len() + 1];
+        for index in indices {
+            let length = self.lengths[index];
+            buckets[boundaries.partition_point(|boundary| *boundary <= length)].push(index);
+        }
+
+        buckets
+    }
+}
+
+impl Sampler for LengthBucketSampler {
+    fn num_samples(&self, num_items: usize) -> usize {
+        assert_eq!(
+            self.lengths.len(),
+            num_items,
+            "The sampler should have a length for each item of the dataset"
+        );
+
+        match self.drop_last {
+            true => num_items - num_items % self.batch_size,
+            false => num_items,
+        }
+    }
+
+    fn sample(&self, num_items: usize, _iteration: usize, rng: &mut StdRng) -> Vec<usize> {
+        let num_samples = self.num_samples(num_items);
+
+        let mut batches = Vec::new();
+        let mut leftovers = Vec::new();
+        for bucket in self.buckets(rng) {
+            let mut chunks = bucket.chunks_exact(self.batch_size);
+            batches.extend(chunks.by_ref().map(|batch| batch.to_vec()));
+            leftovers.extend_from_slice(chunks.remainder());
+        }
+
+        // The leftovers are ordered by bucket, so their batches still group similar lengths.
+        let mut chunks = leftovers.chunks_exact(self.batch_size);
+        batches.extend(chunks.by_ref().map(|batch| batch.to_vec()));
+        let last = chunks.remainder().to_vec();
+
+        if self.shuffle {
+            batches.shuffle(rng);
+        }
+
+        let mut indices = batches.concat();
+        indices.extend(last);
+        indices.truncate(num_samples);
+        indices
+    }
+}
+
+/// Builds each batch from `P` random classes with `K` random items of each class, so the
+/// batches contain positive and negative pairs for the metric-learning losses.
+///
+/// The items of a class are sampled without replacement when the class has at least `K`
+/// items, with replacement otherwise. The batch size of the data loader must be `P * K`, and
+/// each iteration contains as many batches as the dataset contains `P * K` items by default.
+#[derive(Clone, Debug)]
+pub struct ClassBalancedSampler {
+    classes: Vec<Vec<usize>>,
+    num_items: usize,
+    num_classes_per_batch: usize,
+    num_items_per_class: usize,
+    num_batches: Option<usize>,
+}
+
+impl ClassBalancedSampler {
+    /// Create the sampler with the label of each item of the dataset, the number of classes of
+    /// each batch `P` and the number of items of each class `K`.
+    pub fn new(labels: &[usize], num_classes_per_batch: usize, num_items_per_class: usize) -> Self {
+        assert!(
+            num_classes_per_batch > 0 && num_items_per_class > 0,
+            "The batches should have at least one class and one item per class"
+        );
+
+        let num_classes = labels.iter().max().map_or(0, |label| label + 1);
+        let mut classes = vec![Vec::new(); num_classes];
+        for (index, label) in labels.iter().enumerate() {
+            classes[*label].push(index);
+        }
+        classes.retain(|items| !items.is_empty());
+
+        assert!(
+            classes.len() >= num_classes_per_batch,
+            "The dataset should contain at least {num_classes_per_batch} classes, got {}",
+            classes.len()
+        );
+
+        Self {
+            classes,
+            num_items: labels.len(),
+            num_classes_per_batch,
+            num_items_per_class,
+            num_batches: None,
+        }
+    }
+
+    /// Sets the number of batches of each iteration.
+    pub fn with_num_batches(mut self, num_batches: usize) -> Self {
+        self.num_batches = Some(num_batches);
+        self
+    }
+
+    fn batch_size(&self) -> usize {
+        self.num_classes_per_batch * self.num_items_per_class
+    }
+}
+
+impl Sampler for ClassBalancedSampler {
+    fn num_samples(&self, num_items: usize) -> usize {
+        assert_eq!(
+            self.num_items, num_items,
+            "The sampler should have a label for each item of the dataset"
+        );
+
+        let num_batches = self.num_batches.unwrap_or(num_items / self.batch_size());
+        num_batches * self.batch_size()
+    }
+
+    fn sample(&self, num_items: usize, _iteration: usize, rng: &mut StdRng) -> Vec<usize> {
+        let num_samples = self.num_samples(num_items);
+        let mut indices = Vec::with_capacity(num_samples);
+
+        while indices.len() < num_samples {
+            let classes = self
+                .classes
+                .choose_multiple(rng, self.num_classes_per_batch)
+                .collect::<Vec<_>>();
+
+            for items in classes {
+                if items.len() >= self.num_items_per_class {
+                    indices.extend(items.choose_multiple(rng, self.num_items_per_class));
+                } else {
+                    indices.extend(
+                        (0..self.num_items_per_class).map(|_| items[rng.gen_range(0..items.len())]),
+                    );
+                }
+            }
+        }
+
+        indices
+    }
+}
+
+/// The sampler of a data loader, whose indices are shared by the workers of a multi-threaded
+/// data loader, each one batching its part of the indices.
+pub(crate) struct SharedSampler {
+    sampler: Box<dyn Sampler>,
+    rng: Mutex<StdRng>,
+    /// The last sampled iteration and its indices.
+    current: Mutex<Option<(usize, Arc<Vec<usize>>)>>,
+}
+
+impl SharedSampler {
+    pub(crate) fn new(sampler: Box<dyn Sampler>, rng: StdRng) -> Self {
+        Self {
+            sampler,
+            rng: Mutex::new(rng),
+            current: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn num_samples(&self, num_items: usize) -> usize {
+        self.sampler.num_samples(num_items)
+    }
+
+    /// The indices of the iteration, only sampled by the first worker starting it.
+    pub(crate) fn indices(&self, num_items: usize, iteration: usize) -> Arc<Vec<usize>> {
+        let mut current = self.current.lock().unwrap();
+
+        match current.as_ref() {
+            Some((sampled, indices)) if *sampled == iteration => indices.clone(),
+            _ => {
+                let mut rng = self.rng.lock().unwrap();
+                let indices = Arc::new(self.sampler.sample(num_items, iteration, &mut rng));
+                *current = Some((iteration, indices.clone()));
+                indices
+            }
+        }
+    }
+}
+
+/// The items of a dataset at the sampled indices.
+pub(crate) struct SampledDataset<I> {
+    dataset: Arc<dyn Dataset<I>>,
+    indices: Arc<Vec<usize>>,
+    start: usize,
+    end: usize,
+}
+
+impl<I> SampledDataset<I> {
+    /// The items at the indices in the range of a worker.
+    pub(crate) fn new(
+        dataset: Arc<dyn Dataset<I>>,
+        indices: Arc<Vec<usize>>,
+        (start, end): (usize, usize),
+    ) -> Self {
+        Self {
+            dataset,
+            indices,
+            start,
+            end,
+        }
+    }
+}
+
+/// The range of the items of the worker, made of whole batches so that the batches built by the
+/// samplers aren't split between two workers. The last worker takes the incomplete batch.
+pub(crate) fn partition(
+    num_items: usize,
+    batch_size: usize,
+    worker: usize,
+    num_workers: usize,
+) -> (usize, usize) {
+    let num_batches = num_items.div_ceil(batch_size);
+    let start = worker * num_batches / num_workers * batch_size;
+    let end = (worker + 1) * num_batches / num_workers * batch_size;
+
+    (start.min(num_items), end.min(num_items))
+}
+
+impl<I> Dataset<I> for SampledDataset<I> {
+    fn get(&self, index: usize) -> Option<I> {
+        if index >= self.len() {
+            return None;
+        }
+
+        self.dataset.get(self.indices[self.start + index])
+    }
+
+    fn len(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::dataloader::{batcher::TestBatcher, DataLoaderBuilder};
+    use burn_dataset::InMemDataset;
+    use rand::SeedableRng;
+
+    #[test]
+    fn weighted_sampler_should_follow_the_weights() {
+        let sampler = WeightedRandomSampler::new(vec![1.0, 0.0, 3.0]).with_num_samples(4000);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let indices = sampler.sample(3, 0, &mut rng);
+        let count = |index| indices.iter().filter(|i| **i == index).count();
+
+        assert_eq!(indices.len(), 4000);
+        assert_eq!(count(1), 0);
+        assert!((900..1100).contains(&count(0)));
+    }
+
+    #[test]
+    fn weighted_sampler_without_replacement_should_sample_each_item_once() {
+        let sampler = WeightedRandomSampler::new(vec![1.0, 0.0, 3.0, 2.0]).with_replacement(false);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let mut indices = sampler.sample(4, 0, &mut rng);
+        indices.sort();
+
+        assert_eq!(sampler.num_samples(4), 3);
+        assert_eq!(indices, [0, 2, 3]);
+    }
+
+    #[test]
+    fn shared_sampler_should_sample_once_per_iteration() {
+        let sampler = WeightedRandomSampler::new(vec![1.0; 10]);
+        let shared = SharedSampler::new(Box::new(sampler), StdRng::seed_from_u64(42));
+
+        let first = shared.indices(10, 0);
+        assert!(Arc::ptr_eq(&first, &shared.indices(10, 0)));
+        assert!(!Arc::ptr_eq(&first, &shared.indices(10, 1)));
+    }
+
+    #[test]
+    fn dataloader_should_batch_the_sampled_items_with_multiple_workers() {
+        let sampler =
+            WeightedRandomSampler::new(vec![0.0, 1.0, 1.0, 1.0, 1.0, 0.0]).with_replacement(false);
+        let dataloader = DataLoaderBuilder::new(TestBatcher::new())
+            .batch_size(2)
+            .sampler(sampler)
+            .num_workers(2)
+            .shuffle(42)
+            .build(InMemDataset::new((0..6).collect::<Vec<usize>>()));
+
+        for _ in 0..2 {
+            let mut items = dataloader.iter().flatten().collect::<Vec<_>>();
+            items.sort();
+
+            assert_eq!(dataloader.num_items(), 4);
+            assert_eq!(items, [1, 2, 3, 4]);
+        }
+    }
+
+    #[test]
+    fn distributed_sampler_should_partition_the_items() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut items = (0..3)
+            .flat_map(|rank| DistributedSampler::new(3, rank).sample(10, 0, &mut rng))
+            .collect::<Vec<_>>();
+
+        // 10 items padded to 12, repeating 2 items.
+        assert_eq!(items.len(), 12);
+        items.sort();
+        items.dedup();
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn distributed_sampler_should_drop_the_last_items() {
+        let sampler = DistributedSampler::new(3, 1)
+            .with_shuffle(false)
+            .with_drop_last(true);
+        let mut rng = StdRng::seed_from_u64(0);
+
+        assert_eq!(sampler.num_samples(10), 3);
+        assert_eq!(sampler.sample(10, 0, &mut rng), [1, 4, 7]);
+    }
+
+    #[test]
+    fn distributed_sampler_should_reshuffle_at_each_epoch() {
+        let sampler = DistributedSampler::new(2, 0).with_seed(42);
+        let mut rng = StdRng::seed_from_u64(0);
+
+        let first = sampler.sample(100, 0, &mut rng);
+        assert_eq!(first, sampler.sample(100, 0, &mut rng));
+        assert_ne!(first, sampler.sample(100, 1, &mut rng));
+        assert_eq!(
+            sampler.sample(100, 1, &mut rng),
+            sampler.clone().with_start_epoch(1).sample(100, 0, &mut rng)
+        );
+    }
+
+    #[test]
+    fn length_bucket_sampler_should_batch_similar_lengths() {
+        let lengths = vec![5, 1, 9, 2, 8, 1, 7, 3, 9];
+        let sampler = LengthBucketSampler::new(lengths.clone(), 3);
+        let mut rng = StdRng::seed_from_u64(0);
+
+        let indices = sampler.sample(9, 0, &mut rng);
+        let mut batches = indices
+            .chunks(3)
+            .map(|batch| {
+                let mut batch = batch
+                    .iter()
+                    .map(|index| lengths[*index])
+                    .collect::<Vec<_>>();
+                batch.sort();
+                batch
+            })
+            .collect::<Vec<_>>();
+        batches.sort();
+
+        assert_eq!(batches, [[1, 1, 2], [3, 5, 7], [8, 9, 9]]);
+    }
+
+    #[test]
+    fn length_bucket_sampler_should_batch_the_leftovers_together() {
+        let lengths = vec![1, 1, 1, 10, 10, 10, 10, 20];
+        let sampler = LengthBucketSampler::new(lengths, 2)
+            .with_boundaries(vec![5, 15])
+            .with_shuffle(false);
+        let mut rng = StdRng::seed_from_u64(0);
+
+        assert_eq!(sampler.sample(8, 0, &mut rng), [0, 1, 3, 4, 5, 6, 2, 7]);
+
+        let sampler = LengthBucketSampler::new(vec![1, 1, 1, 10, 10, 10, 10], 2)
+            .with_boundaries(vec![5, 15])
+            .with_drop_last(true);
+        assert_eq!(sampler.num_samples(7), 6);
+        assert_eq!(sampler.sample(7, 0, &mut rng).len(), 6);
+    }
+
+    #[test]
+    fn class_balanced_sampler_should_sample_k_items_of_p_classes() {
+        let labels = [0, 0, 0, 1, 1, 1, 2, 2, 3, 3, 3, 3];
+        let sampler = ClassBalancedSampler::new(&labels, 2, 3);
+        let mut rng = StdRng::seed_from_u64(0);
+
+        let indices = sampler.sample(12, 0, &mut rng);
+
+        assert_eq!(indices.len(), 12);
+        for batch in indices.chunks(6) {
+            let mut batch_labels = batch.iter().map(|index| labels[*index]).collect::<Vec<_>>();
+            batch_labels.sort();
+            assert_eq!(batch_labels[0], batch_labels[2]);
+            assert_eq!(batch_labels[3], batch_labels[5]);
+            assert_ne!(batch_labels[0], batch_labels[3]);
+        }
+    }
+
+    #[test]
+    fn partition_should_split_whole_batches() {
+        let ranges = (0..3)
+            .map(|worker| partition(11, 2, worker, 3))
+            .collect::<Vec<_>>();
+
+        assert_eq!(ranges, [(0, 4), (4, 8), (8, 11)]);
+    }
+
+    #[test]
+    fn dataloader_should_keep_the_batches_of_the_sampler_with_multiple_workers() {
+        let labels = [0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5];
+        let sampler = ClassBalancedSampler::new(&labels, 1, 2).with_num_batches(5);
+        let dataloader = DataLoaderBuilder::new(TestBatcher::new())
+            .batch_size(2)
+            .sampler(sampler)
+            .num_workers(3)
+            .shuffle(42)
+            .build(InMemDataset::new(labels.to_vec()));
+
+        let batches = dataloader.iter().collect::<Vec<_>>();
+
+        assert_eq!(batches.len(), 5);
+        for batch in batches {
+            assert_eq!(batch.len(), 2);
+            assert_eq!(batch[0], batch[1]);
+        }
+    }
+
+    #[test]
+    #[should_panic = "The dataset should contain at least 3 classes"]
+    fn class_balanced_sampler_should_require_p_classes() {
+        ClassBalancedSampler::new(&[0, 1, 1], 3, 2);
+    }
+}
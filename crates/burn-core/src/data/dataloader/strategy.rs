@@ -24,6 +24,15 @@ pub trait BatchStrategy<I>: Send {
     ///
     /// The new strategy.
     fn clone_dyn(&self) -> Box<dyn BatchStrategy<I>>;
+
+    /// The number of items of the batches, when it is fixed.
+    ///
+    /// # Returns
+    ///
+    /// The batch size.
+    fn batch_size(&self) -> Option<usize> {
+        None
+    }
 }
 
 /// A strategy to batch items with a fixed batch size.
@@ -73,4 +82,8 @@ impl<I: Send + 'static> BatchStrategy<I> for FixBatchStrategy<I> {
     fn clone_dyn(&self) -> Box<dyn BatchStrategy<I>> {
         Box::new(Self::new(self.batch_size))
     }
+
+    fn batch_size(&self) -> Option<usize> {
+        Some(self.batch_size)
+    }
 }
@@ -2,6 +2,10 @@
 #[cfg(feature = "dataset")]
 pub mod dataloader;
 
+/// Image augmentation module.
+#[cfg(feature = "dataset")]
+pub mod augmentation;
+
 /// Dataset module.
 #[cfg(feature = "dataset")]
 pub mod dataset {
@@ -0,0 +1,167 @@
+use super::LrScheduler;
+use crate as burn;
+use crate::{config::Config, LearningRate};
+use burn_tensor::backend::Backend;
+
+/// The configuration for creating a [Cosine Annealing learning rate scheduler with warm restarts
+/// and growing periods](CosineAnnealingWarmRestartsLrScheduler).
+///
+/// This scheduler returns the learning rate `initial_lr` at the first step, then decreases it by
+/// following a cosine function for `t_0` iterations, after which the learning rate is reset to
+/// `initial_lr`. Each new period is `t_mult` times longer than the previous one.
+#[derive(Config)]
+pub struct CosineAnnealingWarmRestartsLrSchedulerConfig {
+    // The initial learning rate.
+    initial_lr: LearningRate,
+    // The number of iterations of the first period.
+    t_0: usize,
+    // The factor by which the period grows after each restart.
+    #[config(default = 1)]
+    t_mult: usize,
+    // The final learning rate.
+    #[config(default = 0.0)]
+    min_lr: LearningRate,
+}
+
+impl CosineAnnealingWarmRestartsLrSchedulerConfig {
+    /// Initializes a [Cosine learning rate scheduler with warm
+    /// restarts](CosineAnnealingWarmRestartsLrScheduler).
+    ///
+    /// # Panics
+    /// This function panics if `initial_lr` is not between 0 and 1, if `min_lr` is not between 0
+    /// and `initial_lr`, or if `t_0` or `t_mult` is 0.
+    pub fn init(&self) -> CosineAnnealingWarmRestartsLrScheduler {
+        assert!(
+            self.initial_lr > 0. && self.initial_lr <= 1.,
+            "Initial learning rate must be greater than 0 and at most 1"
+        );
+        assert!(
+            self.min_lr >= 0.0 && self.min_lr <= self.initial_lr,
+            "Minimum learning rate must be at least 0 and at most equal to the initial learning rate"
+        );
+        assert!(self.t_0 > 0, "Period length must be at least 1");
+        assert!(self.t_mult > 0, "Period multiplier must be at least 1");
+
+        CosineAnnealingWarmRestartsLrScheduler {
+            min_lr: self.min_lr,
+            max_lr: self.initial_lr,
+            t_mult: self.t_mult,
+            current_iter: 0,
+            period: self.t_0,
+        }
+    }
+}
+
+/// A Cosine Annealing learning rate scheduler with warm restarts and growing periods.
+///
+/// This scheduler is described in [SGDR: Stochastic Gradient Descent with Warm
+/// Restarts](https://arxiv.org/abs/1608.03983). See
+/// [CosineAnnealingWarmRestartsLrSchedulerConfig] for more information.
+///
+/// The recorded state holds both the position within the current period and the length of that
+/// period, so a loaded scheduler continues from the same phase of the same cycle.
+#[derive(Clone, Copy, Debug)]
+pub struct CosineAnnealingWarmRestartsLrScheduler {
+    min_lr: LearningRate,
+    max_lr: LearningRate,
+    t_mult: usize,
+    // The iteration within the current period.
+    current_iter: usize,
+    // The length of the current period.
+    period: usize,
+}
+
+impl LrScheduler for CosineAnnealingWarmRestartsLrScheduler {
+    type Record<B: Backend> = (usize, usize);
+
+    fn step(&mut self) -> LearningRate {
+        let lr = self.min_lr
+            + 0.5
+                * (self.max_lr - self.min_lr)
+                * (1.0
+                    + (self.current_iter as f64 / self.period as f64 * std::f64::consts::PI).cos());
+
+        self.current_iter += 1;
+        if self.current_iter >= self.period {
+            self.current_iter = 0;
+            self.period *= self.t_mult;
+        }
+
+        lr
+    }
+
+    fn to_record<B: Backend>(&self) -> Self::Record<B> {
+        (self.current_iter, self.period)
+    }
+
+    fn load_record<B: Backend>(mut self, record: Self::Record<B>) -> Self {
+        (self.current_iter, self.period) = record;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_utils;
+    use super::*;
+
+    #[test]
+    #[should_panic = "Initial learning rate must be greater than 0 and at most 1"]
+    fn config_initial_lr_too_low() {
+        CosineAnnealingWarmRestartsLrSchedulerConfig::new(0., 10).init();
+    }
+
+    #[test]
+    #[should_panic = "Minimum learning rate must be at least 0 and at most equal to the initial learning rate"]
+    fn config_min_lr_too_high() {
+        CosineAnnealingWarmRestartsLrSchedulerConfig::new(0.5, 10)
+            .with_min_lr(0.6)
+            .init();
+    }
+
+    #[test]
+    #[should_panic = "Period length must be at least 1"]
+    fn config_t_0_too_low() {
+        CosineAnnealingWarmRestartsLrSchedulerConfig::new(0.5, 0).init();
+    }
+
+    #[test]
+    #[should_panic = "Period multiplier must be at least 1"]
+    fn config_t_mult_too_low() {
+        CosineAnnealingWarmRestartsLrSchedulerConfig::new(0.5, 10)
+            .with_t_mult(0)
+            .init();
+    }
+
+    #[test]
+    fn test_lr_change() {
+        const INITIAL_LR: LearningRate = 0.5;
+        const MIN_LR: LearningRate = 0.1;
+        const MID_LR: LearningRate = (INITIAL_LR + MIN_LR) * 0.5;
+
+        let scheduler = CosineAnnealingWarmRestartsLrSchedulerConfig::new(INITIAL_LR, 2)
+            .with_t_mult(2)
+            .with_min_lr(MIN_LR)
+            .init();
+        let expected_lrs = [
+            INITIAL_LR, // cos(0)
+            MID_LR,     // cos(PI/2)
+            INITIAL_LR, // restart, the period is now 4
+            MIN_LR + (INITIAL_LR - MIN_LR) * 0.5 * (1.0 + (std::f64::consts::PI / 4.0).cos()),
+            MID_LR, // cos(PI/2)
+            MIN_LR + (INITIAL_LR - MIN_LR) * 0.5 * (1.0 + (std::f64::consts::PI * 0.75).cos()),
+            INITIAL_LR, // restart, the period is now 8
+        ];
+        test_utils::check_lr_sequence(scheduler, expected_lrs);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        const INITIAL_LR: LearningRate = 1.0;
+        let scheduler = CosineAnnealingWarmRestartsLrSchedulerConfig::new(INITIAL_LR, 3)
+            .with_t_mult(2)
+            .init();
+        // Save in the middle of the second period.
+        test_utils::check_save_load(scheduler, 5);
+    }
+}
@@ -0,0 +1,199 @@
+use super::LrScheduler;
+use crate as burn;
+use crate::{config::Config, LearningRate};
+use burn_tensor::backend::Backend;
+
+/// The policy used by the [cyclical learning rate scheduler](CyclicLrScheduler) to scale the
+/// amplitude of each cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum CyclicLrMode {
+    /// Every cycle has the same amplitude.
+    Triangular,
+    /// The amplitude is halved after each cycle.
+    Triangular2,
+    /// The amplitude is scaled by `gamma ^ iteration`.
+    ExpRange,
+}
+
+/// The configuration for creating a [cyclical learning rate scheduler](CyclicLrScheduler).
+///
+/// This scheduler returns the learning rate `base_lr` at the first step, then increases it
+/// linearly up to `max_lr` in `step_size_up` iterations, and decreases it back to `base_lr` in
+/// `step_size_down` iterations (`step_size_up` if unset). The amplitude of each cycle is scaled
+/// according to the [mode](CyclicLrMode).
+#[derive(Config)]
+pub struct CyclicLrSchedulerConfig {
+    // The lower learning rate boundary of the cycle.
+    base_lr: LearningRate,
+    // The upper learning rate boundary of the cycle.
+    max_lr: LearningRate,
+    // The number of iterations in the increasing half of a cycle.
+    step_size_up: usize,
+    // The number of iterations in the decreasing half of a cycle.
+    #[config(default = "None")]
+    step_size_down: Option<usize>,
+    // The scaling policy.
+    #[config(default = "CyclicLrMode::Triangular")]
+    mode: CyclicLrMode,
+    // The scaling constant used by the exp_range mode.
+    #[config(default = 1.0)]
+    gamma: f64,
+}
+
+impl CyclicLrSchedulerConfig {
+    /// Initializes a [cyclical learning rate scheduler](CyclicLrScheduler).
+    ///
+    /// # Panics
+    /// This function panics if the learning rates are not between 0 and 1, if `base_lr` is
+    /// greater than `max_lr`, or if `step_size_up` is 0.
+    pub fn init(&self) -> CyclicLrScheduler {
+        assert!(
+            self.base_lr > 0. && self.base_lr <= 1.,
+            "Base learning rate must be greater than 0 and at most 1"
+        );
+        assert!(
+            self.max_lr >= self.base_lr && self.max_lr <= 1.,
+            "Max learning rate must be at least equal to the base learning rate and at most 1"
+        );
+        // The increasing half of the cycle divides the learning rate range
+        assert!(self.step_size_up > 0, "Step size up must be at least 1");
+        let step_size_down = self.step_size_down.unwrap_or(self.step_size_up);
+
+        let cycle_size = (self.step_size_up + step_size_down) as f64;
+
+        CyclicLrScheduler {
+            base_lr: self.base_lr,
+            max_lr: self.max_lr,
+            cycle_size,
+            step_ratio: self.step_size_up as f64 / cycle_size,
+            mode: self.mode,
+            gamma: self.gamma,
+            current_iter: 0,
+        }
+    }
+}
+
+/// A cyclical learning rate scheduler.
+///
+/// This scheduler is described in [Cyclical Learning Rates for Training Neural
+/// Networks](https://arxiv.org/abs/1506.01186). See [CyclicLrSchedulerConfig] for more
+/// information.
+#[derive(Clone, Copy, Debug)]
+pub struct CyclicLrScheduler {
+    base_lr: LearningRate,
+    max_lr: LearningRate,
+    // The number of iterations in a full cycle.
+    cycle_size: f64,
+    // The fraction of the cycle spent increasing the learning rate.
+    step_ratio: f64,
+    mode: CyclicLrMode,
+    gamma: f64,
+    // The number of steps already performed.
+    current_iter: usize,
+}
+
+impl LrScheduler for CyclicLrScheduler {
+    type Record<B: Backend> = usize;
+
+    fn step(&mut self) -> LearningRate {
+        let iter = self.current_iter as f64;
+        self.current_iter += 1;
+
+        let cycle = (1.0 + iter / self.cycle_size).floor();
+        let x = 1.0 + iter / self.cycle_size - cycle;
+        let scale_factor = if x <= self.step_ratio {
+            x / self.step_ratio
+        } else {
+            (x - 1.0) / (self.step_ratio - 1.0)
+        };
+
+        let amplitude = match self.mode {
+            CyclicLrMode::Triangular => 1.0,
+            CyclicLrMode::Triangular2 => 1.0 / 2f64.powf(cycle - 1.0),
+            CyclicLrMode::ExpRange => self.gamma.powf(iter),
+        };
+
+        self.base_lr + (self.max_lr - self.base_lr) * scale_factor * amplitude
+    }
+
+    fn to_record<B: Backend>(&self) -> Self::Record<B> {
+        self.current_iter
+    }
+
+    fn load_record<B: Backend>(mut self, record: Self::Record<B>) -> Self {
+        self.current_iter = record;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_utils;
+    use super::*;
+
+    const BASE_LR: LearningRate = 0.1;
+    const MAX_LR: LearningRate = 0.5;
+
+    #[test]
+    #[should_panic = "Base learning rate must be greater than 0 and at most 1"]
+    fn config_base_lr_too_low() {
+        CyclicLrSchedulerConfig::new(0., MAX_LR, 2).init();
+    }
+
+    #[test]
+    #[should_panic = "Max learning rate must be at least equal to the base learning rate and at most 1"]
+    fn config_max_lr_too_low() {
+        CyclicLrSchedulerConfig::new(MAX_LR, BASE_LR, 2).init();
+    }
+
+    #[test]
+    #[should_panic = "Step size up must be at least 1"]
+    fn config_step_size_up_zero() {
+        CyclicLrSchedulerConfig::new(BASE_LR, MAX_LR, 0)
+            .with_step_size_down(Some(2))
+            .init();
+    }
+
+    #[test]
+    fn test_lr_change_triangular() {
+        let scheduler = CyclicLrSchedulerConfig::new(BASE_LR, MAX_LR, 2).init();
+        let expected_lrs = [0.1, 0.3, 0.5, 0.3, 0.1, 0.3, 0.5];
+        test_utils::check_lr_sequence(scheduler, expected_lrs);
+    }
+
+    #[test]
+    fn test_lr_change_triangular2() {
+        let scheduler = CyclicLrSchedulerConfig::new(BASE_LR, MAX_LR, 2)
+            .with_mode(CyclicLrMode::Triangular2)
+            .init();
+        let expected_lrs = [0.1, 0.3, 0.5, 0.3, 0.1, 0.2, 0.3, 0.2, 0.1];
+        test_utils::check_lr_sequence(scheduler, expected_lrs);
+    }
+
+    #[test]
+    fn test_lr_change_exp_range() {
+        let scheduler = CyclicLrSchedulerConfig::new(BASE_LR, MAX_LR, 1)
+            .with_mode(CyclicLrMode::ExpRange)
+            .with_gamma(0.5)
+            .init();
+        let expected_lrs = [0.1, 0.1 + 0.4 * 0.5, 0.1, 0.1 + 0.4 * 0.125];
+        test_utils::check_lr_sequence(scheduler, expected_lrs);
+    }
+
+    #[test]
+    fn test_lr_change_asymmetric() {
+        let scheduler = CyclicLrSchedulerConfig::new(BASE_LR, MAX_LR, 1)
+            .with_step_size_down(Some(4))
+            .init();
+        let expected_lrs = [0.1, 0.5, 0.4, 0.3, 0.2, 0.1, 0.5];
+        test_utils::check_lr_sequence(scheduler, expected_lrs);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let scheduler = CyclicLrSchedulerConfig::new(BASE_LR, MAX_LR, 3)
+            .with_mode(CyclicLrMode::Triangular2)
+            .init();
+        test_utils::check_save_load(scheduler, 7);
+    }
+}
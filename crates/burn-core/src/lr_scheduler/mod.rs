@@ -13,6 +13,21 @@ pub mod exponential;
 /// Cosine learning rate scheduler
 pub mod cosine;
 
+/// Cosine learning rate scheduler with warm restarts and growing periods
+pub mod cosine_warm_restarts;
+
+/// Cyclical learning rate scheduler
+pub mod cyclic;
+
+/// One-cycle learning rate scheduler
+pub mod one_cycle;
+
+/// Polynomial decay learning rate scheduler
+pub mod polynomial;
+
+/// Warmup wrapper for any learning rate scheduler
+pub mod warmup;
+
 mod base;
 
 pub use base::*;
@@ -0,0 +1,270 @@
+use super::LrScheduler;
+use crate as burn;
+use crate::{config::Config, LearningRate};
+use burn_tensor::backend::Backend;
+
+/// The annealing strategy used by the [one-cycle learning rate scheduler](OneCycleLrScheduler).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum OneCycleAnnealStrategy {
+    /// Cosine annealing.
+    Cos,
+    /// Linear annealing.
+    Linear,
+}
+
+/// The configuration for creating a [one-cycle learning rate scheduler](OneCycleLrScheduler).
+///
+/// This scheduler starts at `max_lr / div_factor`, warms up to `max_lr` during the first
+/// `pct_start` fraction of the `total_steps`, then anneals down to
+/// `max_lr / (div_factor * final_div_factor)` for the remaining steps. The momentum, available
+/// through [OneCycleLrScheduler::momentum], cycles inversely between `max_momentum` and
+/// `base_momentum` when `cycle_momentum` is enabled.
+#[derive(Config)]
+pub struct OneCycleLrSchedulerConfig {
+    // The maximum learning rate, reached at the end of the warmup phase.
+    max_lr: LearningRate,
+    // The total number of steps in the cycle.
+    total_steps: usize,
+    // The fraction of the cycle spent increasing the learning rate.
+    #[config(default = 0.3)]
+    pct_start: f64,
+    // The annealing strategy.
+    #[config(default = "OneCycleAnnealStrategy::Cos")]
+    anneal_strategy: OneCycleAnnealStrategy,
+    // Determines the initial learning rate via `initial_lr = max_lr / div_factor`.
+    #[config(default = 25.0)]
+    div_factor: f64,
+    // Determines the minimum learning rate via `min_lr = initial_lr / final_div_factor`.
+    #[config(default = 1e4)]
+    final_div_factor: f64,
+    // Whether the momentum is cycled inversely to the learning rate.
+    #[config(default = true)]
+    cycle_momentum: bool,
+    // The lowest momentum, reached when the learning rate is at its maximum.
+    #[config(default = 0.85)]
+    base_momentum: f64,
+    // The highest momentum, used at the start and at the end of the cycle.
+    #[config(default = 0.95)]
+    max_momentum: f64,
+}
+
+impl OneCycleLrSchedulerConfig {
+    /// Initializes a [one-cycle learning rate scheduler](OneCycleLrScheduler).
+    ///
+    /// # Panics
+    /// This function panics if `max_lr` is not between 0 and 1, if `total_steps` is lower than 2,
+    /// if `pct_start` is not between 0 and 1 or if the division factors are not positive.
+    pub fn init(&self) -> OneCycleLrScheduler {
+        assert!(
+            self.max_lr > 0. && self.max_lr <= 1.,
+            "Max learning rate must be greater than 0 and at most 1"
+        );
+        assert!(
+            self.total_steps > 1,
+            "Total number of steps must be at least 2"
+        );
+        assert!(
+            self.pct_start > 0. && self.pct_start < 1.,
+            "Warmup fraction must be greater than 0 and less than 1"
+        );
+        assert!(
+            self.div_factor > 0. && self.final_div_factor > 0.,
+            "Division factors must be greater than 0"
+        );
+        assert!(
+            self.base_momentum <= self.max_momentum,
+            "Base momentum must be at most equal to the max momentum"
+        );
+
+        let initial_lr = self.max_lr / self.div_factor;
+        let warmup_end = (self.pct_start * self.total_steps as f64 - 1.0).max(1.0);
+
+        OneCycleLrScheduler {
+            initial_lr,
+            max_lr: self.max_lr,
+            min_lr: initial_lr / self.final_div_factor,
+            warmup_end,
+            total_end: (self.total_steps - 1) as f64,
+            anneal_strategy: self.anneal_strategy,
+            momentum: self
+                .cycle_momentum
+                .then_some((self.base_momentum, self.max_momentum)),
+            current_step: 0,
+        }
+    }
+}
+
+/// A one-cycle learning rate scheduler.
+///
+/// This scheduler is described in [Super-Convergence: Very Fast Training of Neural Networks
+/// Using Large Learning Rates](https://arxiv.org/abs/1708.07120). See
+/// [OneCycleLrSchedulerConfig] for more information.
+#[derive(Clone, Copy, Debug)]
+pub struct OneCycleLrScheduler {
+    initial_lr: LearningRate,
+    max_lr: LearningRate,
+    min_lr: LearningRate,
+    // The step at which the warmup phase ends.
+    warmup_end: f64,
+    // The step at which the annealing phase ends.
+    total_end: f64,
+    anneal_strategy: OneCycleAnnealStrategy,
+    // The base and max momentum, if the momentum is cycled.
+    momentum: Option<(f64, f64)>,
+    // The number of steps already performed.
+    current_step: usize,
+}
+
+impl OneCycleLrScheduler {
+    /// The momentum matching the learning rate returned by the last call to
+    /// [step](LrScheduler::step), or `None` if momentum cycling is disabled.
+    pub fn momentum(&self) -> Option<f64> {
+        let (base, max) = self.momentum?;
+        let step = self.current_step.saturating_sub(1) as f64;
+
+        Some(self.schedule(step, max, base, max))
+    }
+
+    // Interpolates between `start`, `middle` and `end` depending on the cycle phase.
+    fn schedule(&self, step: f64, start: f64, middle: f64, end: f64) -> f64 {
+        let step = step.min(self.total_end);
+
+        if step <= self.warmup_end {
+            self.anneal(start, middle, step / self.warmup_end)
+        } else {
+            let pct = (step - self.warmup_end) / (self.total_end - self.warmup_end);
+            self.anneal(middle, end, pct)
+        }
+    }
+
+    fn anneal(&self, start: f64, end: f64, pct: f64) -> f64 {
+        match self.anneal_strategy {
+            OneCycleAnnealStrategy::Cos => {
+                end + (start - end) / 2.0 * (1.0 + (std::f64::consts::PI * pct).cos())
+            }
+            OneCycleAnnealStrategy::Linear => start + (end - start) * pct,
+        }
+    }
+}
+
+impl LrScheduler for OneCycleLrScheduler {
+    type Record<B: Backend> = usize;
+
+    fn step(&mut self) -> LearningRate {
+        let step = self.current_step as f64;
+        self.current_step += 1;
+
+        self.schedule(step, self.initial_lr, self.max_lr, self.min_lr)
+    }
+
+    fn to_record<B: Backend>(&self) -> Self::Record<B> {
+        self.current_step
+    }
+
+    fn load_record<B: Backend>(mut self, record: Self::Record<B>) -> Self {
+        self.current_step = record;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_utils;
+    use super::*;
+
+    const MAX_LR: LearningRate = 1.0;
+    const INITIAL_LR: LearningRate = MAX_LR / 25.0;
+    const MIN_LR: LearningRate = INITIAL_LR / 1e4;
+
+    #[test]
+    #[should_panic = "Max learning rate must be greater than 0 and at most 1"]
+    fn config_max_lr_too_high() {
+        OneCycleLrSchedulerConfig::new(1.5, 10).init();
+    }
+
+    #[test]
+    #[should_panic = "Total number of steps must be at least 2"]
+    fn config_total_steps_too_low() {
+        OneCycleLrSchedulerConfig::new(0.5, 1).init();
+    }
+
+    #[test]
+    #[should_panic = "Warmup fraction must be greater than 0 and less than 1"]
+    fn config_pct_start_too_high() {
+        OneCycleLrSchedulerConfig::new(0.5, 10)
+            .with_pct_start(1.0)
+            .init();
+    }
+
+    #[test]
+    fn test_lr_change_cos() {
+        let scheduler = OneCycleLrSchedulerConfig::new(MAX_LR, 10).init();
+        let expected_lrs = [
+            INITIAL_LR,                  // warmup start
+            (INITIAL_LR + MAX_LR) * 0.5, // warmup middle
+            MAX_LR,                      // warmup end
+            MIN_LR + (MAX_LR - MIN_LR) * 0.5 * (1.0 + (std::f64::consts::PI / 7.0).cos()),
+        ];
+        test_utils::check_lr_sequence(scheduler, expected_lrs);
+    }
+
+    #[test]
+    fn test_lr_change_linear() {
+        let scheduler = OneCycleLrSchedulerConfig::new(MAX_LR, 10)
+            .with_anneal_strategy(OneCycleAnnealStrategy::Linear)
+            .init();
+        let expected_lrs = [
+            INITIAL_LR,
+            (INITIAL_LR + MAX_LR) * 0.5,
+            MAX_LR,
+            MAX_LR + (MIN_LR - MAX_LR) / 7.0,
+        ];
+        test_utils::check_lr_sequence(scheduler, expected_lrs);
+    }
+
+    #[test]
+    fn test_lr_stays_at_min_after_the_cycle() {
+        let mut scheduler = OneCycleLrSchedulerConfig::new(MAX_LR, 10).init();
+        (0..9).for_each(|_| {
+            scheduler.step();
+        });
+        test_utils::check_lr_sequence(scheduler, [MIN_LR, MIN_LR, MIN_LR]);
+    }
+
+    #[test]
+    fn test_momentum_cycles_inversely() {
+        let mut scheduler = OneCycleLrSchedulerConfig::new(MAX_LR, 10).init();
+        let assert_momentum = |scheduler: &OneCycleLrScheduler, expected: f64| {
+            let momentum = scheduler.momentum().unwrap();
+            assert!(
+                (momentum - expected).abs() < 1e-10,
+                "Momentum {momentum} is not approximately equal to {expected}"
+            );
+        };
+
+        scheduler.step();
+        assert_momentum(&scheduler, 0.95);
+        scheduler.step();
+        scheduler.step();
+        assert_momentum(&scheduler, 0.85);
+        (3..10).for_each(|_| {
+            scheduler.step();
+        });
+        assert_momentum(&scheduler, 0.95);
+    }
+
+    #[test]
+    fn test_momentum_disabled() {
+        let mut scheduler = OneCycleLrSchedulerConfig::new(MAX_LR, 10)
+            .with_cycle_momentum(false)
+            .init();
+        scheduler.step();
+        assert_eq!(scheduler.momentum(), None);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let scheduler = OneCycleLrSchedulerConfig::new(MAX_LR, 12).init();
+        test_utils::check_save_load(scheduler, 6);
+    }
+}
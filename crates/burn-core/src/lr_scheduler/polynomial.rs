@@ -0,0 +1,159 @@
+use super::LrScheduler;
+use crate as burn;
+use crate::{config::Config, LearningRate};
+use burn_tensor::backend::Backend;
+
+/// The configuration for creating a [polynomial decay learning rate
+/// scheduler](PolynomialDecayLrScheduler).
+///
+/// This scheduler returns the learning rate `initial_lr` at the first step, then decays it
+/// following `(initial_lr - end_lr) * (1 - step / decay_steps) ^ power + end_lr` until reaching
+/// `end_lr` after `decay_steps` iterations. When `cycle` is enabled, `decay_steps` is instead
+/// extended to the next multiple of itself once reached, as done by TensorFlow.
+#[derive(Config)]
+pub struct PolynomialDecayLrSchedulerConfig {
+    // The initial learning rate.
+    initial_lr: LearningRate,
+    // The number of iterations before reaching the final learning rate.
+    decay_steps: usize,
+    // The final learning rate.
+    #[config(default = 0.0001)]
+    end_lr: LearningRate,
+    // The power of the polynomial.
+    #[config(default = 1.0)]
+    power: f64,
+    // Whether the decay restarts after `decay_steps` iterations.
+    #[config(default = false)]
+    cycle: bool,
+}
+
+impl PolynomialDecayLrSchedulerConfig {
+    /// Initializes a [polynomial decay learning rate scheduler](PolynomialDecayLrScheduler).
+    ///
+    /// # Panics
+    /// This function panics if `initial_lr` and `end_lr` are not between 0 and 1, or if
+    /// `decay_steps` is 0.
+    pub fn init(&self) -> PolynomialDecayLrScheduler {
+        assert!(
+            self.initial_lr > 0. && self.initial_lr <= 1.,
+            "Initial learning rate must be greater than 0 and at most 1"
+        );
+        assert!(
+            self.end_lr >= 0. && self.end_lr <= 1.,
+            "End learning rate must be at least 0 and at most 1"
+        );
+        assert!(
+            self.decay_steps > 0,
+            "Number of decay steps must be at least 1"
+        );
+
+        PolynomialDecayLrScheduler {
+            initial_lr: self.initial_lr,
+            end_lr: self.end_lr,
+            decay_steps: self.decay_steps,
+            power: self.power,
+            cycle: self.cycle,
+            current_step: 0,
+        }
+    }
+}
+
+/// A polynomial decay learning rate scheduler.
+///
+/// See [PolynomialDecayLrSchedulerConfig] for more information.
+#[derive(Clone, Copy, Debug)]
+pub struct PolynomialDecayLrScheduler {
+    initial_lr: LearningRate,
+    end_lr: LearningRate,
+    decay_steps: usize,
+    power: f64,
+    cycle: bool,
+    // The number of steps already performed.
+    current_step: usize,
+}
+
+impl LrScheduler for PolynomialDecayLrScheduler {
+    type Record<B: Backend> = usize;
+
+    fn step(&mut self) -> LearningRate {
+        let step = self.current_step;
+        self.current_step += 1;
+
+        let (step, decay_steps) = if self.cycle {
+            // The decay period is extended to the next multiple of `decay_steps`, the first step
+            // excepted.
+            let multiple = step.div_ceil(self.decay_steps).max(1);
+            (step, self.decay_steps * multiple)
+        } else {
+            (step.min(self.decay_steps), self.decay_steps)
+        };
+
+        let remaining = 1.0 - step as f64 / decay_steps as f64;
+        (self.initial_lr - self.end_lr) * remaining.powf(self.power) + self.end_lr
+    }
+
+    fn to_record<B: Backend>(&self) -> Self::Record<B> {
+        self.current_step
+    }
+
+    fn load_record<B: Backend>(mut self, record: Self::Record<B>) -> Self {
+        self.current_step = record;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_utils;
+    use super::*;
+
+    #[test]
+    #[should_panic = "Initial learning rate must be greater than 0 and at most 1"]
+    fn config_initial_lr_too_low() {
+        PolynomialDecayLrSchedulerConfig::new(0., 10).init();
+    }
+
+    #[test]
+    #[should_panic = "End learning rate must be at least 0 and at most 1"]
+    fn config_end_lr_too_high() {
+        PolynomialDecayLrSchedulerConfig::new(0.5, 10)
+            .with_end_lr(1.5)
+            .init();
+    }
+
+    #[test]
+    #[should_panic = "Number of decay steps must be at least 1"]
+    fn config_decay_steps_too_low() {
+        PolynomialDecayLrSchedulerConfig::new(0.5, 0).init();
+    }
+
+    #[test]
+    fn test_lr_change() {
+        let scheduler = PolynomialDecayLrSchedulerConfig::new(0.9, 4)
+            .with_end_lr(0.1)
+            .with_power(2.0)
+            .init();
+        // (0.9 - 0.1) * (1 - step / 4) ^ 2 + 0.1
+        let expected_lrs = [0.9, 0.55, 0.3, 0.15, 0.1, 0.1];
+        test_utils::check_lr_sequence(scheduler, expected_lrs);
+    }
+
+    #[test]
+    fn test_lr_change_cycle() {
+        let scheduler = PolynomialDecayLrSchedulerConfig::new(0.9, 2)
+            .with_end_lr(0.1)
+            .with_cycle(true)
+            .init();
+        // Steps 3 and 4 decay over 4 steps, steps 5 and 6 over 6 steps.
+        let expected_lrs = [0.9, 0.5, 0.1, 0.3, 0.1, 0.1 + 0.8 / 6.0, 0.1];
+        test_utils::check_lr_sequence(scheduler, expected_lrs);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let scheduler = PolynomialDecayLrSchedulerConfig::new(0.9, 10)
+            .with_power(0.9)
+            .init();
+        test_utils::check_save_load(scheduler, 6);
+    }
+}
@@ -0,0 +1,164 @@
+use super::LrScheduler;
+use crate as burn;
+use crate::{config::Config, LearningRate};
+use burn_tensor::backend::Backend;
+
+/// How the learning rate is ramped up by the [warmup learning rate scheduler](WarmupLrScheduler).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum WarmupStrategy {
+    /// The learning rate factor increases by a constant amount on each step.
+    Linear,
+    /// The learning rate factor is multiplied by a constant amount on each step.
+    Exponential,
+}
+
+/// The configuration for creating a [warmup learning rate scheduler](WarmupLrScheduler).
+///
+/// During the first `warmup_steps` steps, this scheduler returns the first learning rate of the
+/// wrapped scheduler scaled by a factor ramping from `start_factor` to 1. Afterward, it delegates
+/// to the wrapped scheduler, which starts from its own first step.
+#[derive(Config)]
+pub struct WarmupLrSchedulerConfig {
+    // The number of warmup steps.
+    warmup_steps: usize,
+    // The factor applied to the learning rate on the first step.
+    #[config(default = 0.01)]
+    start_factor: f64,
+    // The warmup strategy.
+    #[config(default = "WarmupStrategy::Linear")]
+    strategy: WarmupStrategy,
+}
+
+impl WarmupLrSchedulerConfig {
+    /// Initializes a [warmup learning rate scheduler](WarmupLrScheduler) wrapping the given
+    /// scheduler.
+    ///
+    /// # Panics
+    /// This function panics if `start_factor` is not greater than 0 and at most 1.
+    pub fn init<S: LrScheduler>(&self, inner: S) -> WarmupLrScheduler<S> {
+        assert!(
+            self.start_factor > 0. && self.start_factor <= 1.,
+            "Start factor must be greater than 0 and at most 1"
+        );
+
+        WarmupLrScheduler {
+            inner,
+            warmup_steps: self.warmup_steps,
+            start_factor: self.start_factor,
+            strategy: self.strategy,
+            current_step: 0,
+            target_lr: None,
+        }
+    }
+}
+
+/// A learning rate scheduler adding a warmup phase to any other scheduler.
+///
+/// See [WarmupLrSchedulerConfig] for more information.
+#[derive(Clone, Debug)]
+pub struct WarmupLrScheduler<S> {
+    inner: S,
+    warmup_steps: usize,
+    start_factor: f64,
+    strategy: WarmupStrategy,
+    // The number of steps already performed.
+    current_step: usize,
+    // The first learning rate of the inner scheduler, reached at the end of the warmup.
+    target_lr: Option<LearningRate>,
+}
+
+impl<S: LrScheduler> WarmupLrScheduler<S> {
+    fn factor(&self, step: usize) -> f64 {
+        let progress = step as f64 / self.warmup_steps as f64;
+
+        match self.strategy {
+            WarmupStrategy::Linear => self.start_factor + (1.0 - self.start_factor) * progress,
+            WarmupStrategy::Exponential => self.start_factor.powf(1.0 - progress),
+        }
+    }
+}
+
+impl<S: LrScheduler> LrScheduler for WarmupLrScheduler<S> {
+    type Record<B: Backend> = (usize, Option<LearningRate>, S::Record<B>);
+
+    fn step(&mut self) -> LearningRate {
+        let step = self.current_step;
+        self.current_step += 1;
+
+        if step >= self.warmup_steps {
+            // The first learning rate of the inner scheduler was already computed during warmup.
+            return match self.target_lr.take() {
+                Some(lr) => lr,
+                None => self.inner.step(),
+            };
+        }
+
+        let target_lr = *self.target_lr.get_or_insert_with(|| self.inner.step());
+        target_lr * self.factor(step)
+    }
+
+    fn to_record<B: Backend>(&self) -> Self::Record<B> {
+        (
+            self.current_step,
+            self.target_lr,
+            self.inner.to_record::<B>(),
+        )
+    }
+
+    fn load_record<B: Backend>(mut self, record: Self::Record<B>) -> Self {
+        let (current_step, target_lr, inner) = record;
+        self.current_step = current_step;
+        self.target_lr = target_lr;
+        self.inner = self.inner.load_record::<B>(inner);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::linear::LinearLrSchedulerConfig;
+    use super::super::test_utils;
+    use super::*;
+
+    #[test]
+    #[should_panic = "Start factor must be greater than 0 and at most 1"]
+    fn config_start_factor_too_low() {
+        WarmupLrSchedulerConfig::new(4)
+            .with_start_factor(0.)
+            .init(0.5);
+    }
+
+    #[test]
+    fn test_linear_warmup_then_delegate() {
+        let inner = LinearLrSchedulerConfig::new(0.5, 0.1, 2).init();
+        let scheduler = WarmupLrSchedulerConfig::new(4)
+            .with_start_factor(0.2)
+            .init(inner);
+        let expected_lrs = [0.1, 0.2, 0.3, 0.4, 0.5, 0.3, 0.1, 0.1];
+        test_utils::check_lr_sequence(scheduler, expected_lrs);
+    }
+
+    #[test]
+    fn test_exponential_warmup_then_delegate() {
+        let scheduler = WarmupLrSchedulerConfig::new(2)
+            .with_strategy(WarmupStrategy::Exponential)
+            .init(0.5);
+        let expected_lrs = [0.005, 0.05, 0.5, 0.5];
+        test_utils::check_lr_sequence(scheduler, expected_lrs);
+    }
+
+    #[test]
+    fn test_no_warmup() {
+        let inner = LinearLrSchedulerConfig::new(0.5, 0.1, 2).init();
+        let scheduler = WarmupLrSchedulerConfig::new(0).init(inner);
+        test_utils::check_lr_sequence(scheduler, [0.5, 0.3, 0.1]);
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let inner = LinearLrSchedulerConfig::new(0.5, 0.1, 6).init();
+        let scheduler = WarmupLrSchedulerConfig::new(4).init(inner);
+        test_utils::check_save_load(scheduler.clone(), 3);
+        test_utils::check_save_load(scheduler, 6);
+    }
+}
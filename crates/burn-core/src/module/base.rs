@@ -1,3 +1,5 @@
+#[cfg(feature = "std")]
+use super::{lenient::load_lenient, KeyRemap, LoadReport};
 use super::{ParamId, Quantizer};
 use crate::{
     record::Record,
@@ -148,6 +150,26 @@ pub trait Module<B: Backend>: Clone + Send + core::fmt::Debug {
     /// Convert the module into a record containing the state.
     fn into_record(self) -> Self::Record;
 
+    #[cfg(feature = "std")]
+    /// Load the parameters of a record, matched by their path, e.g. `encoder.linear.weight`.
+    ///
+    /// Unlike [load_record](Module::load_record), the record can be the record of another module,
+    /// such as a pre-trained module loaded with a [recorder](crate::record::Recorder), e.g. to
+    /// fine-tune it with a new head: the parameters missing from the record keep their values,
+    /// and the parameters of the record which don't exist in the module or don't have the same
+    /// shape are skipped. They are listed in the returned [report](LoadReport).
+    fn load_record_lenient<R: Record<B>>(self, record: R) -> (Self, LoadReport) {
+        load_lenient(self, record, &KeyRemap::default())
+    }
+
+    #[cfg(feature = "std")]
+    /// Load the parameters of a record as [load_record_lenient](Module::load_record_lenient),
+    /// with their paths renamed by the `remap` rules before they are matched, e.g. when the
+    /// fields of the module were renamed since the record was saved.
+    fn load_record_remapped<R: Record<B>>(self, record: R, remap: &KeyRemap) -> (Self, LoadReport) {
+        load_lenient(self, record, remap)
+    }
+
     #[cfg(feature = "std")]
     /// Save the module to a file using the provided [file recorder](crate::record::FileRecorder).
     ///
@@ -211,6 +233,11 @@ pub trait Module<B: Backend>: Clone + Send + core::fmt::Debug {
 
 /// Module visitor trait.
 pub trait ModuleVisitor<B: Backend> {
+    /// Called when entering a submodule, with the name of the field (or index) holding it.
+    fn enter_module(&mut self, _name: &str) {}
+    /// Called when leaving a submodule, with the same name given to
+    /// [enter_module](ModuleVisitor::enter_module).
+    fn exit_module(&mut self, _name: &str) {}
     /// Visit a float tensor in the module.
     fn visit_float<const D: usize>(&mut self, _id: ParamId, _tensor: &Tensor<B, D>) {}
     /// Visit an int tensor in the module.
@@ -221,6 +248,11 @@ pub trait ModuleVisitor<B: Backend> {
 
 /// Module mapper trait.
 pub trait ModuleMapper<B: Backend> {
+    /// Called when entering a submodule, with the name of the field (or index) holding it.
+    fn enter_module(&mut self, _name: &str) {}
+    /// Called when leaving a submodule, with the same name given to
+    /// [enter_module](ModuleMapper::enter_module).
+    fn exit_module(&mut self, _name: &str) {}
     /// Map a float tensor in the module.
     fn map_float<const D: usize>(&mut self, _id: ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
         tensor
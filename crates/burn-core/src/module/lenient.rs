@@ -0,0 +1,288 @@
+use super::{KeyRemap, Module, ModuleMapper, ParamId};
+use crate::record::{record_params, Record};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use burn_tensor::{backend::Backend, Bool, DType, Int, Tensor, TensorData};
+use core::fmt;
+use hashbrown::HashMap;
+
+/// The report of a [lenient load](super::Module::load_record_lenient), listing the parameters by path,
+/// e.g. `encoder.linear.weight`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LoadReport {
+    /// The parameters of the module missing from the record, which keep their values.
+    pub missing: Vec<String>,
+    /// The parameters of the record which don't exist in the module, which are skipped, with their
+    /// remapped paths.
+    pub unexpected: Vec<String>,
+    /// The parameters whose shape in the record differs from the module, which keep their values.
+    pub mismatched: Vec<ShapeMismatch>,
+}
+
+/// A parameter of the record with a different shape than in the module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShapeMismatch {
+    /// The path of the parameter.
+    pub path: String,
+    /// The shape of the parameter in the module.
+    pub expected: Vec<usize>,
+    /// The shape of the parameter in the record.
+    pub actual: Vec<usize>,
+}
+
+impl LoadReport {
+    /// Whether all the parameters of the module were loaded from the record, and all the
+    /// parameters of the record were used.
+    pub fn is_exact(&self) -> bool {
+        self.missing.is_empty() && self.unexpected.is_empty() && self.mismatched.is_empty()
+    }
+}
+
+impl fmt::Display for LoadReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_exact() {
+            return f.write_str("All the parameters were loaded");
+        }
+
+        for path in self.missing.iter() {
+            writeln!(f, "Missing: {path}")?;
+        }
+        for path in self.unexpected.iter() {
+            writeln!(f, "Unexpected: {path}")?;
+        }
+        for mismatch in self.mismatched.iter() {
+            writeln!(
+                f,
+                "Shape mismatch: {} expected {:?}, got {:?}",
+                mismatch.path, mismatch.expected, mismatch.actual
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) fn load_lenient<B, M, R>(module: M, record: R, remap: &KeyRemap) -> (M, LoadReport)
+where
+    B: Backend,
+    M: Module<B>,
+    R: Record<B>,
+{
+    let params = record_params(record)
+        .expect("The record should be serializable")
+        .into_iter()
+        .map(|(path, data)| ((remap.apply(&path), ParamKind::of(data.dtype)), data))
+        .collect();
+
+    load_params(module, params)
+}
+
+/// Loads the parameters of the module from their data, by path and kind.
+pub(crate) fn load_params<B, M>(
+    module: M,
+    params: HashMap<(String, ParamKind), TensorData>,
+) -> (M, LoadReport)
+where
+    B: Backend,
+    M: Module<B>,
+{
+    let mut loader = LenientLoader {
+        path: Vec::new(),
+        params,
+        report: LoadReport::default(),
+    };
+    let module = module.map(&mut loader);
+
+    let mut report = loader.report;
+    report.unexpected = loader.params.into_keys().map(|(path, _)| path).collect();
+    report.unexpected.sort();
+    report.unexpected.dedup();
+    report.missing.sort();
+
+    (module, report)
+}
+
+/// The kind of a parameter, the parameters of different kinds don't match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum ParamKind {
+    Float,
+    Int,
+    Bool,
+}
+
+impl ParamKind {
+    /// The kind of the parameters with the data type.
+    pub(crate) fn of(dtype: DType) -> Self {
+        match dtype {
+            DType::Bool => Self::Bool,
+            DType::I64 | DType::I32 | DType::I16 | DType::I8 => Self::Int,
+            DType::U64 | DType::U32 | DType::U16 | DType::U8 => Self::Int,
+            _ => Self::Float,
+        }
+    }
+}
+
+/// Replaces the parameters by the collected ones with the same path, kind and shape.
+struct LenientLoader {
+    path: Vec<String>,
+    params: HashMap<(String, ParamKind), TensorData>,
+    report: LoadReport,
+}
+
+impl LenientLoader {
+    /// The data of the parameter at the current path, if its shape matches.
+    fn take(&mut self, kind: ParamKind, shape: Vec<usize>) -> Option<TensorData> {
+        let path = self.path.join(".");
+
+        let Some(data) = self.params.remove(&(path.clone(), kind)) else {
+            self.report.missing.push(path);
+            return None;
+        };
+
+        if data.shape != shape {
+            self.report.mismatched.push(ShapeMismatch {
+                path,
+                expected: shape,
+                actual: data.shape,
+            });
+            return None;
+        }
+
+        Some(data)
+    }
+}
+
+impl<B: Backend> ModuleMapper<B> for LenientLoader {
+    fn enter_module(&mut self, name: &str) {
+        self.path.push(name.to_string());
+    }
+
+    fn exit_module(&mut self, _name: &str) {
+        self.path.pop();
+    }
+
+    fn map_float<const D: usize>(&mut self, _id: ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
+        match self.take(ParamKind::Float, tensor.dims().to_vec()) {
+            Some(data) => {
+                let is_require_grad = tensor.is_require_grad();
+                Tensor::from_data(data, &tensor.device()).set_require_grad(is_require_grad)
+            }
+            None => tensor,
+        }
+    }
+
+    fn map_int<const D: usize>(
+        &mut self,
+        _id: ParamId,
+        tensor: Tensor<B, D, Int>,
+    ) -> Tensor<B, D, Int> {
+        match self.take(ParamKind::Int, tensor.dims().to_vec()) {
+            Some(data) => Tensor::from_data(data, &tensor.device()),
+            None => tensor,
+        }
+    }
+
+    fn map_bool<const D: usize>(
+        &mut self,
+        _id: ParamId,
+        tensor: Tensor<B, D, Bool>,
+    ) -> Tensor<B, D, Bool> {
+        match self.take(ParamKind::Bool, tensor.dims().to_vec()) {
+            Some(data) => Tensor::from_data(data, &tensor.device()),
+            None => tensor,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        nn::{Linear, LinearConfig},
+        TestBackend,
+    };
+
+    use crate as burn;
+
+    #[derive(Module, Debug)]
+    struct Pretrained<B: Backend> {
+        encoder: Linear<B>,
+        head: Linear<B>,
+        extra: Linear<B>,
+    }
+
+    #[derive(Module, Debug)]
+    struct FineTuned<B: Backend> {
+        encoder: Linear<B>,
+        head: Linear<B>,
+        adapter: Linear<B>,
+    }
+
+    #[test]
+    fn test_load_record_lenient_reports_the_differences() {
+        let device = Default::default();
+        let pretrained = Pretrained::<TestBackend> {
+            encoder: LinearConfig::new(4, 4).init(&device),
+            head: LinearConfig::new(4, 10).init(&device),
+            extra: LinearConfig::new(4, 4).init(&device),
+        };
+        let fine_tuned = FineTuned::<TestBackend> {
+            encoder: LinearConfig::new(4, 4).init(&device),
+            head: LinearConfig::new(4, 2).init(&device),
+            adapter: LinearConfig::new(4, 4).init(&device),
+        };
+        let head_before = fine_tuned.head.weight.to_data();
+
+        let (fine_tuned, report) = fine_tuned.load_record_lenient(pretrained.clone().into_record());
+
+        assert_eq!(
+            fine_tuned.encoder.weight.to_data(),
+            pretrained.encoder.weight.to_data()
+        );
+        assert_eq!(fine_tuned.head.weight.to_data(), head_before);
+        assert_eq!(report.missing, ["adapter.bias", "adapter.weight"]);
+        assert_eq!(report.unexpected, ["extra.bias", "extra.weight"]);
+        assert_eq!(
+            report.mismatched,
+            [
+                ShapeMismatch {
+                    path: "head.weight".to_string(),
+                    expected: vec![4, 2],
+                    actual: vec![4, 10],
+                },
+                ShapeMismatch {
+                    path: "head.bias".to_string(),
+                    expected: vec![2],
+                    actual: vec![10],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_record_remapped_renames_the_source_paths() {
+        let device = Default::default();
+        let pretrained = Pretrained::<TestBackend> {
+            encoder: LinearConfig::new(4, 4).init(&device),
+            head: LinearConfig::new(4, 2).init(&device),
+            extra: LinearConfig::new(4, 4).init(&device),
+        };
+        let fine_tuned = FineTuned::<TestBackend> {
+            encoder: LinearConfig::new(4, 4).init(&device),
+            head: LinearConfig::new(4, 2).init(&device),
+            adapter: LinearConfig::new(4, 4).init(&device),
+        };
+        let remap = KeyRemap::new().with_rename("extra", "adapter");
+
+        let (fine_tuned, report) =
+            fine_tuned.load_record_remapped(pretrained.clone().into_record(), &remap);
+
+        assert!(report.is_exact());
+        assert_eq!(
+            fine_tuned.adapter.weight.to_data(),
+            pretrained.extra.weight.to_data()
+        );
+    }
+}
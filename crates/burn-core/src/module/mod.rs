@@ -1,9 +1,17 @@
 mod base;
 mod display;
+#[cfg(feature = "std")]
+mod lenient;
 mod param;
 mod quantize;
+mod remap;
 
 pub use base::*;
 pub use display::*;
+#[cfg(feature = "std")]
+pub(crate) use lenient::{load_params, ParamKind};
+#[cfg(feature = "std")]
+pub use lenient::{LoadReport, ShapeMismatch};
 pub use param::*;
 pub use quantize::*;
+pub use remap::KeyRemap;
@@ -0,0 +1,114 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[cfg(feature = "std")]
+use regex::Regex;
+
+/// Rules renaming the paths of the parameters of a source module, e.g. `encoder.linear.weight`,
+/// before they are matched against the module when
+/// [loading it](crate::module::Module::load_record_remapped).
+///
+/// The rules are applied in order, each one to the path renamed by the previous rules, so a
+/// checkpoint stays usable when the fields of a module are renamed.
+///
+/// # Example
+///
+/// ```rust, ignore
+/// let remap = KeyRemap::new()
+///     // The `encoder` field was renamed `backbone`
+///     .with_rename("encoder", "backbone")
+///     // The `layer_<i>` fields were moved to the `layers` vector
+///     .with_regex(r"^layer_(\d+)\.", "layers.$1.");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct KeyRemap {
+    rules: Vec<RemapRule>,
+}
+
+#[derive(Debug, Clone)]
+enum RemapRule {
+    Rename(String, String),
+    #[cfg(feature = "std")]
+    Regex(Regex, String),
+}
+
+impl KeyRemap {
+    /// Creates an empty set of rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renames the path `from` to `to`, along with the paths under it, e.g. renaming `encoder`
+    /// renames `encoder.linear.weight` but not `encoder_norm.weight`.
+    pub fn with_rename(mut self, from: &str, to: &str) -> Self {
+        self.rules
+            .push(RemapRule::Rename(from.to_string(), to.to_string()));
+        self
+    }
+
+    /// Replaces the first match of the pattern in the paths.
+    ///
+    /// See [Regex](https://docs.rs/regex/latest/regex/#syntax) for the pattern syntax and
+    /// [Replacement](https://docs.rs/regex/latest/regex/struct.Regex.html#method.replace) for the
+    /// replacement syntax.
+    #[cfg(feature = "std")]
+    pub fn with_regex(mut self, pattern: &str, replacement: &str) -> Self {
+        let regex = Regex::new(pattern).expect("Valid regex");
+
+        self.rules
+            .push(RemapRule::Regex(regex, replacement.to_string()));
+        self
+    }
+
+    /// Applies the rules to a path.
+    pub fn apply(&self, path: &str) -> String {
+        let mut path = path.to_string();
+
+        for rule in self.rules.iter() {
+            path = match rule {
+                RemapRule::Rename(from, to) => match path.strip_prefix(from.as_str()) {
+                    Some("") => to.clone(),
+                    Some(rest) if rest.starts_with('.') => format!("{to}{rest}"),
+                    _ => path,
+                },
+                #[cfg(feature = "std")]
+                RemapRule::Regex(regex, replacement) => {
+                    regex.replace(&path, replacement.as_str()).to_string()
+                }
+            };
+        }
+
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rename_applies_to_the_paths_under_it() {
+        let remap = KeyRemap::new().with_rename("encoder", "backbone");
+
+        assert_eq!(remap.apply("encoder"), "backbone");
+        assert_eq!(
+            remap.apply("encoder.linear.weight"),
+            "backbone.linear.weight"
+        );
+        assert_eq!(remap.apply("encoder_norm.weight"), "encoder_norm.weight");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_rules_apply_in_order() {
+        let remap = KeyRemap::new()
+            .with_regex(r"^layer_(\d+)\.", "layers.$1.")
+            .with_rename("layers.0", "stem");
+
+        assert_eq!(remap.apply("layer_0.weight"), "stem.weight");
+        assert_eq!(remap.apply("layer_1.weight"), "layers.1.weight");
+    }
+}
@@ -6,7 +6,10 @@ use crate::nn::Initializer;
 use crate::{
     config::Config,
     nn,
-    tensor::{activation, backend::Backend, Bool, Tensor},
+    tensor::{
+        activation, backend::Backend, module::attention, ops::attention::ATTENTION_MASK_VALUE,
+        Bool, Tensor,
+    },
 };
 
 #[cfg(not(feature = "std"))]
@@ -249,6 +252,104 @@ impl<B: Backend> MultiHeadAttention<B> {
         MhaOutput { weights, context }
     }
 
+    /// Applies the forward pass on the input tensors, returning the context without the attention
+    /// weights.
+    ///
+    /// The context is computed by the [fused attention](attention) of the backend, which doesn't
+    /// materialize the weights, unless the weights are changed by an active dropout, the quiet
+    /// softmax or a `min_float` other than the default one.
+    ///
+    /// # Shapes
+    ///
+    /// - query: `[batch_size, seq_length_1, d_model]`
+    /// - key: `[batch_size, seq_length_2, d_model]`
+    /// - value: `[batch_size, seq_length_2, d_model]`
+    /// - output: `[batch_size, seq_length_1, d_model]`
+    pub fn forward_context(&self, input: MhaInput<B>) -> Tensor<B, 3> {
+        let [batch_size, seq_length_1, d_model] = input.query.dims();
+
+        let query = self.attention_linear(input.query, &self.query);
+        let key = self.attention_linear(input.key, &self.key);
+        let value = self.attention_linear(input.value, &self.value);
+
+        let context = self.context(query, key, value, input.mask_pad, input.mask_attn);
+        let context = context
+            .swap_dims(1, 2)
+            .reshape([batch_size, seq_length_1, d_model]);
+
+        self.output.forward(context)
+    }
+
+    /// Applies the forward pass using a cache, returning the context without the attention
+    /// weights, as [forward_context](Self::forward_context).
+    ///
+    /// # Shapes
+    ///
+    /// - query: `[batch_size, seq_length_1, d_model]`
+    /// - key: `[batch_size, seq_length_2, d_model]`
+    /// - value: `[batch_size, seq_length_2, d_model]`
+    /// - output: `[batch_size, seq_length_1, d_model]`
+    pub fn forward_cache_context(
+        &self,
+        input: MhaInput<B>,
+        cache: &mut MhaCache<B>,
+    ) -> Tensor<B, 3> {
+        let [batch_size, seq_length_1, d_model] = input.query.dims();
+
+        let query = cache
+            .query
+            .forward(input.query, |t| self.attention_linear(t, &self.query));
+        let key = cache
+            .key
+            .forward(input.key, |t| self.attention_linear(t, &self.key));
+        let value = cache
+            .value
+            .forward(input.value, |t| self.attention_linear(t, &self.value));
+
+        let context = self.context(query, key, value, input.mask_pad, input.mask_attn);
+        let context = context
+            .swap_dims(1, 2)
+            .reshape([batch_size, seq_length_1, d_model]);
+
+        cache.output.forward(context, |t| self.output.forward(t))
+    }
+
+    /// The context of the heads, `[batch_size, n_heads, seq_length_1, d_k]`.
+    fn context(
+        &self,
+        query: Tensor<B, 4>,
+        key: Tensor<B, 4>,
+        value: Tensor<B, 4>,
+        mask_pad: Option<Tensor<B, 2, Bool>>,
+        mask_attn: Option<Tensor<B, 3, Bool>>,
+    ) -> Tensor<B, 4> {
+        let dropout = B::ad_enabled() && self.dropout.prob > 0.0;
+        if dropout || self.quiet_softmax || self.min_float != ATTENTION_MASK_VALUE as f64 {
+            let attn_scores = self.attn_scores(query, key);
+            let weights = self.attn_weights(attn_scores, mask_pad, mask_attn);
+
+            return weights.matmul(value);
+        }
+
+        let [batch_size, n_heads, seq_length_1, _] = query.dims();
+        let [_, _, seq_length_2, _] = key.dims();
+        let shape = [batch_size, n_heads, seq_length_1, seq_length_2];
+
+        // The fused attention takes a single mask, combined once for all the heads, which read it
+        // through a view expanded with a stride of 0.
+        let mask_pad = mask_pad.map(|mask| mask.reshape([batch_size, 1, 1, seq_length_2]));
+        let mask_attn =
+            mask_attn.map(|mask| mask.reshape([batch_size, 1, seq_length_1, seq_length_2]));
+        let mask = match (mask_pad, mask_attn) {
+            (Some(mask_pad), Some(mask_attn)) => {
+                Some((mask_pad.int() + mask_attn.int()).greater_elem(0))
+            }
+            (mask_pad, mask_attn) => mask_pad.or(mask_attn),
+        };
+
+        attention(query, key, value, mask.map(|mask| mask.expand(shape)))
+    }
+
     fn attn_scores(&self, query: Tensor<B, 4>, key: Tensor<B, 4>) -> Tensor<B, 4> {
         let attn_scores = query
             .matmul(key.transpose())
@@ -512,6 +613,36 @@ mod tests {
             .assert_approx_eq(&output_2.into_data(), 3);
     }
 
+    #[test]
+    fn test_forward_context_should_have_same_output_as_forward() {
+        let [batch_size, seq_length, d_model, n_heads] = [3, 5, 12, 2];
+        let device = Default::default();
+        let mha = MultiHeadAttentionConfig::new(d_model, n_heads).init::<TestBackend>(&device);
+
+        let tensor = Tensor::<TestBackend, 3>::random(
+            [batch_size, seq_length, d_model],
+            Distribution::Default,
+            &device,
+        );
+        let mask_attn = generate_autoregressive_mask(batch_size, seq_length, &device);
+        let mask_pad = Tensor::<TestBackend, 2, Int>::from_ints(
+            [[0, 0, 0, 0, 0], [0, 0, 0, 1, 1], [0, 0, 0, 0, 1]],
+            &device,
+        )
+        .equal_elem(1);
+        let input = MhaInput::self_attn(tensor)
+            .mask_pad(mask_pad)
+            .mask_attn(mask_attn);
+
+        let output = mha.forward(input.clone());
+        let context = mha.forward_context(input);
+
+        output
+            .context
+            .into_data()
+            .assert_approx_eq(&context.into_data(), 3);
+    }
+
     #[test]
     fn display() {
         let config = MultiHeadAttentionConfig::new(2, 4);
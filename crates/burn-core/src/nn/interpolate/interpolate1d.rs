@@ -10,7 +10,8 @@ use crate::tensor::backend::Backend;
 use crate::tensor::ops::InterpolateOptions;
 use crate::tensor::Tensor;
 
-use super::InterpolateMode;
+use super::resample::Resampling;
+use super::{CoordinateTransformationMode, InterpolateMode, NearestMode};
 
 /// Configuration for the 1D interpolation module.
 ///
@@ -32,6 +33,22 @@ pub struct Interpolate1dConfig {
     /// Determines how the output values are calculated.
     #[config(default = "InterpolateMode::Nearest")]
     pub mode: InterpolateMode,
+
+    /// Transformation of the output coordinates to the input coordinates.
+    /// If not specified, the backend interpolation is used, which aligns the origins for the
+    /// nearest mode and the corners otherwise.
+    #[config(default = "None")]
+    pub coordinate_transformation_mode: Option<CoordinateTransformationMode>,
+
+    /// Rounding of the input coordinates for the nearest mode.
+    /// This is used when `coordinate_transformation_mode` is specified.
+    #[config(default = "NearestMode::Floor")]
+    pub nearest_mode: NearestMode,
+
+    /// Coefficient of the cubic convolution for the cubic mode.
+    /// This is used when `coordinate_transformation_mode` is specified.
+    #[config(default = -0.75)]
+    pub cubic_coeff_a: f32,
 }
 
 /// Interpolate module for resizing 1D tensors with shape [N, C, L].
@@ -57,6 +74,15 @@ pub struct Interpolate1d {
 
     /// Interpolation mode used for resizing
     pub mode: Ignored<InterpolateMode>,
+
+    /// Transformation of the output coordinates to the input coordinates
+    pub coordinate_transformation_mode: Ignored<Option<CoordinateTransformationMode>>,
+
+    /// Rounding of the input coordinates for the nearest mode
+    pub nearest_mode: Ignored<NearestMode>,
+
+    /// Coefficient of the cubic convolution for the cubic mode
+    pub cubic_coeff_a: f32,
 }
 
 impl Interpolate1dConfig {
@@ -66,6 +92,9 @@ impl Interpolate1dConfig {
             output_size: self.output_size,
             scale_factor: self.scale_factor,
             mode: Ignored(self.mode),
+            coordinate_transformation_mode: Ignored(self.coordinate_transformation_mode),
+            nearest_mode: Ignored(self.nearest_mode),
+            cubic_coeff_a: self.cubic_coeff_a,
         }
     }
 }
@@ -95,6 +124,21 @@ impl Interpolate1d {
     pub fn forward<B: Backend>(&self, input: Tensor<B, 3>) -> Tensor<B, 3> {
         let output_size = calculate_output_size(input.dims(), self.output_size, self.scale_factor);
 
+        if let Some(coordinate_transformation_mode) = self.coordinate_transformation_mode.0 {
+            let [_, _, length] = input.dims();
+            let scale = self
+                .scale_factor
+                .unwrap_or(output_size as f32 / length as f32);
+
+            return Resampling::new(
+                self.mode.0.clone(),
+                coordinate_transformation_mode,
+                self.nearest_mode.0,
+                self.cubic_coeff_a,
+            )
+            .resample(input, 2, output_size, scale);
+        }
+
         // Use the interpolate operation to resize the temporal input tensor
         // by adding a new dimension for the interpolation axis
         let input = input.unsqueeze_dim(2);
@@ -163,6 +207,10 @@ impl ModuleDisplay for Interpolate1d {
             .add("mode", &self.mode)
             .add("output_size", &format!("{:?}", self.output_size))
             .add("scale_factor", &self.scale_factor)
+            .add(
+                "coordinate_transformation_mode",
+                &self.coordinate_transformation_mode,
+            )
             .optional()
     }
 }
@@ -242,7 +290,7 @@ mod tests {
         assert_eq!(
             alloc::format!("{}", layer),
             "Interpolate1d {mode: Nearest, output_size: Some(20), \
-            scale_factor: None}"
+            scale_factor: None, coordinate_transformation_mode: None}"
         );
     }
 }
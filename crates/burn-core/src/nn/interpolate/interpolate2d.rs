@@ -10,7 +10,8 @@ use crate::tensor::backend::Backend;
 use crate::tensor::ops::InterpolateOptions;
 use crate::tensor::Tensor;
 
-use super::InterpolateMode;
+use super::resample::Resampling;
+use super::{CoordinateTransformationMode, InterpolateMode, NearestMode};
 
 /// Configuration for the 2D interpolation module.
 ///
@@ -32,6 +33,22 @@ pub struct Interpolate2dConfig {
     /// Determines how the output values are calculated.
     #[config(default = "InterpolateMode::Nearest")]
     pub mode: InterpolateMode,
+
+    /// Transformation of the output coordinates to the input coordinates.
+    /// If not specified, the backend interpolation is used, which aligns the origins for the
+    /// nearest mode and the corners otherwise.
+    #[config(default = "None")]
+    pub coordinate_transformation_mode: Option<CoordinateTransformationMode>,
+
+    /// Rounding of the input coordinates for the nearest mode.
+    /// This is used when `coordinate_transformation_mode` is specified.
+    #[config(default = "NearestMode::Floor")]
+    pub nearest_mode: NearestMode,
+
+    /// Coefficient of the cubic convolution for the cubic mode.
+    /// This is used when `coordinate_transformation_mode` is specified.
+    #[config(default = -0.75)]
+    pub cubic_coeff_a: f32,
 }
 
 /// Interpolate module for resizing tensors with shape [N, C, H, W].
@@ -58,6 +75,15 @@ pub struct Interpolate2d {
 
     /// Interpolation mode used for resizing
     pub mode: Ignored<InterpolateMode>,
+
+    /// Transformation of the output coordinates to the input coordinates
+    pub coordinate_transformation_mode: Ignored<Option<CoordinateTransformationMode>>,
+
+    /// Rounding of the input coordinates for the nearest mode
+    pub nearest_mode: Ignored<NearestMode>,
+
+    /// Coefficient of the cubic convolution for the cubic mode
+    pub cubic_coeff_a: f32,
 }
 
 impl Interpolate2dConfig {
@@ -67,6 +93,9 @@ impl Interpolate2dConfig {
             output_size: self.output_size,
             scale_factor: self.scale_factor,
             mode: Ignored(self.mode),
+            coordinate_transformation_mode: Ignored(self.coordinate_transformation_mode),
+            nearest_mode: Ignored(self.nearest_mode),
+            cubic_coeff_a: self.cubic_coeff_a,
         }
     }
 }
@@ -94,6 +123,25 @@ impl Interpolate2d {
     /// ```
     pub fn forward<B: Backend>(&self, input: Tensor<B, 4>) -> Tensor<B, 4> {
         let output_size = calculate_output_size(input.dims(), self.output_size, self.scale_factor);
+
+        if let Some(coordinate_transformation_mode) = self.coordinate_transformation_mode.0 {
+            // The dimensions are resized one after the other
+            let [_, _, height, width] = input.dims();
+            let [scale_height, scale_width] = self.scale_factor.unwrap_or([
+                output_size[0] as f32 / height as f32,
+                output_size[1] as f32 / width as f32,
+            ]);
+            let resampling = Resampling::new(
+                self.mode.0.clone(),
+                coordinate_transformation_mode,
+                self.nearest_mode.0,
+                self.cubic_coeff_a,
+            );
+
+            let output = resampling.resample(input, 2, output_size[0], scale_height);
+            return resampling.resample(output, 3, output_size[1], scale_width);
+        }
+
         interpolate(
             input,
             output_size,
@@ -162,12 +210,16 @@ impl ModuleDisplay for Interpolate2d {
             .add("mode", &self.mode)
             .add("output_size", &format!("{:?}", self.output_size))
             .add("scale_factor", &self.scale_factor)
+            .add(
+                "coordinate_transformation_mode",
+                &self.coordinate_transformation_mode,
+            )
             .optional()
     }
 }
 #[cfg(test)]
 mod tests {
-    use burn_tensor::Distribution;
+    use burn_tensor::{Distribution, TensorData};
 
     use crate::TestBackend;
 
@@ -237,6 +289,29 @@ mod tests {
         assert_eq!(output.dims(), [2, 3, 6, 6]);
     }
 
+    #[test]
+    fn test_coordinate_transformation_mode() {
+        let device = Default::default();
+        let input = Tensor::<TestBackend, 4>::from_floats([[[[1.0, 2.0], [3.0, 4.0]]]], &device);
+
+        let interpolate = Interpolate2dConfig::new()
+            .with_output_size(Some([4, 4]))
+            .with_coordinate_transformation_mode(Some(CoordinateTransformationMode::HalfPixel))
+            .with_nearest_mode(NearestMode::RoundPreferFloor)
+            .init();
+        let output = interpolate.forward(input);
+
+        output.into_data().assert_eq(
+            &TensorData::from([[[
+                [1.0, 1.0, 2.0, 2.0],
+                [1.0, 1.0, 2.0, 2.0],
+                [3.0, 3.0, 4.0, 4.0],
+                [3.0, 3.0, 4.0, 4.0],
+            ]]]),
+            false,
+        );
+    }
+
     #[test]
     fn display() {
         let config = Interpolate2dConfig::new().with_output_size(Some([20, 20]));
@@ -245,7 +320,7 @@ mod tests {
         assert_eq!(
             alloc::format!("{}", layer),
             "Interpolate2d {mode: Nearest, output_size: Some([20, 20]), \
-            scale_factor: None}"
+            scale_factor: None, coordinate_transformation_mode: None}"
         );
     }
 }
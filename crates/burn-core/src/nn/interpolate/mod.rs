@@ -1,5 +1,6 @@
 mod interpolate1d;
 mod interpolate2d;
+mod resample;
 
 pub use interpolate1d::*;
 pub use interpolate2d::*;
@@ -35,6 +36,40 @@ pub enum InterpolateMode {
     Cubic,
 }
 
+/// Transformation of the coordinates of the resized tensor to the coordinates of the input
+/// tensor, as the `coordinate_transformation_mode` of the ONNX `Resize` operator.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum CoordinateTransformationMode {
+    /// The centers of the elements are aligned, `x_in = (x_out + 0.5) / scale - 0.5`.
+    HalfPixel,
+
+    /// Same as [half pixel](CoordinateTransformationMode::HalfPixel), except that an output of
+    /// size 1 samples the first input element.
+    PytorchHalfPixel,
+
+    /// The corner elements are aligned, `x_in = x_out * (size_in - 1) / (size_out - 1)`.
+    AlignCorners,
+
+    /// The origins are aligned, `x_in = x_out / scale`.
+    Asymmetric,
+}
+
+/// Rounding of the input coordinates for nearest-neighbor interpolation.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum NearestMode {
+    /// Round to the nearest element, the halfway coordinates being rounded down.
+    RoundPreferFloor,
+
+    /// Round to the nearest element, the halfway coordinates being rounded up.
+    RoundPreferCeil,
+
+    /// Round down.
+    Floor,
+
+    /// Round up.
+    Ceil,
+}
+
 impl From<InterpolateMode> for OpsInterpolateMode {
     fn from(mode: InterpolateMode) -> Self {
         match mode {
@@ -0,0 +1,240 @@
+use alloc::vec::Vec;
+
+use crate::tensor::backend::Backend;
+use crate::tensor::{ElementConversion, Int, Shape, Tensor, TensorData};
+
+use super::{CoordinateTransformationMode, InterpolateMode, NearestMode};
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
+/// Sampling of the input coordinates of an interpolation, following the semantics of the ONNX
+/// `Resize` operator.
+#[derive(new, Debug, Clone)]
+pub(crate) struct Resampling {
+    mode: InterpolateMode,
+    coordinate_transformation_mode: CoordinateTransformationMode,
+    nearest_mode: NearestMode,
+    cubic_coeff_a: f32,
+}
+
+impl Resampling {
+    /// Resizes a single dimension of the input tensor.
+    ///
+    /// Each output element is a weighted sum of input elements selected along the dimension,
+    /// the indices outside of the input being clamped to its edges.
+    pub(crate) fn resample<B: Backend, const D: usize>(
+        &self,
+        input: Tensor<B, D>,
+        dim: usize,
+        output_size: usize,
+        scale: f32,
+    ) -> Tensor<B, D> {
+        let input_size = input.dims()[dim];
+        let device = input.device();
+        let taps = self.taps(input_size, output_size, scale as f64);
+
+        let mut shape = [1; D];
+        shape[dim] = output_size;
+
+        taps.into_iter()
+            .map(|(indices, weights)| {
+                let indices = Tensor::<B, 1, Int>::from_data(
+                    TensorData::new(
+                        indices
+                            .into_iter()
+                            .map(|index| (index as i64).elem::<B::IntElem>())
+                            .collect(),
+                        Shape::new([output_size]),
+                    ),
+                    &device,
+                );
+                let selected = input.clone().select(dim, indices);
+
+                match weights {
+                    Some(weights) => {
+                        let weights = Tensor::<B, 1>::from_data(
+                            TensorData::new(weights, Shape::new([output_size])),
+                            &device,
+                        );
+                        selected * weights.reshape(shape)
+                    }
+                    None => selected,
+                }
+            })
+            .reduce(|lhs, rhs| lhs + rhs)
+            .unwrap()
+    }
+
+    /// The indices and the weights of the input elements contributing to each output element.
+    ///
+    /// The nearest mode has a single tap without weights.
+    fn taps(
+        &self,
+        input_size: usize,
+        output_size: usize,
+        scale: f64,
+    ) -> Vec<(Vec<usize>, Option<Vec<f32>>)> {
+        let coordinates = (0..output_size)
+            .map(|index| self.input_coordinate(index, input_size, output_size, scale))
+            .collect::<Vec<_>>();
+        let clamp = |index: f64| index.clamp(0.0, (input_size - 1) as f64) as usize;
+
+        let offsets: &[f64] = match self.mode {
+            InterpolateMode::Nearest => {
+                let indices = coordinates.iter().map(|x| clamp(self.round(*x))).collect();
+                return alloc::vec![(indices, None)];
+            }
+            InterpolateMode::Linear => &[0.0, 1.0],
+            InterpolateMode::Cubic => &[-1.0, 0.0, 1.0, 2.0],
+        };
+
+        let mut taps = offsets
+            .iter()
+            .map(|_| {
+                (
+                    Vec::with_capacity(output_size),
+                    Some(Vec::with_capacity(output_size)),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        for x in coordinates {
+            let start = x.floor();
+            let t = x - start;
+            let weights = match self.mode {
+                InterpolateMode::Cubic => cubic_coefficients(t, self.cubic_coeff_a as f64),
+                _ => alloc::vec![(1.0 - t) as f32, t as f32],
+            };
+
+            for ((indices, tap_weights), (offset, weight)) in
+                taps.iter_mut().zip(offsets.iter().zip(weights))
+            {
+                indices.push(clamp(start + offset));
+                tap_weights.as_mut().unwrap().push(weight);
+            }
+        }
+
+        taps
+    }
+
+    /// The coordinate in the input of an output element.
+    fn input_coordinate(
+        &self,
+        index: usize,
+        input_size: usize,
+        output_size: usize,
+        scale: f64,
+    ) -> f64 {
+        let index = index as f64;
+
+        match self.coordinate_transformation_mode {
+            CoordinateTransformationMode::HalfPixel => (index + 0.5) / scale - 0.5,
+            CoordinateTransformationMode::PytorchHalfPixel => match output_size > 1 {
+                true => (index + 0.5) / scale - 0.5,
+                false => 0.0,
+            },
+            CoordinateTransformationMode::AlignCorners => match output_size > 1 {
+                true => index * (input_size - 1) as f64 / (output_size - 1) as f64,
+                false => 0.0,
+            },
+            CoordinateTransformationMode::Asymmetric => index / scale,
+        }
+    }
+
+    fn round(&self, x: f64) -> f64 {
+        let halfway = x - x.floor() == 0.5;
+
+        match self.nearest_mode {
+            NearestMode::RoundPreferFloor if halfway => x.floor(),
+            NearestMode::RoundPreferCeil if halfway => x.ceil(),
+            NearestMode::RoundPreferFloor | NearestMode::RoundPreferCeil => x.round(),
+            NearestMode::Floor => x.floor(),
+            NearestMode::Ceil => x.ceil(),
+        }
+    }
+}
+
+/// The weights of the four neighbors of the cubic convolution, `t` being the distance to the
+/// second one.
+fn cubic_coefficients(t: f64, a: f64) -> Vec<f32> {
+    let outer = |x: f64| ((a * x - 5.0 * a) * x + 8.0 * a) * x - 4.0 * a;
+    let inner = |x: f64| ((a + 2.0) * x - (a + 3.0)) * x * x + 1.0;
+
+    [outer(t + 1.0), inner(t), inner(1.0 - t), outer(2.0 - t)]
+        .into_iter()
+        .map(|weight| weight as f32)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+
+    fn resample(
+        resampling: Resampling,
+        input: [f32; 4],
+        output_size: usize,
+        scale: f32,
+    ) -> TensorData {
+        let device = Default::default();
+        let input = Tensor::<TestBackend, 1>::from_floats(input, &device);
+
+        resampling
+            .resample(input, 0, output_size, scale)
+            .into_data()
+    }
+
+    #[test]
+    fn test_nearest_asymmetric_round_prefer_ceil() {
+        let resampling = Resampling::new(
+            InterpolateMode::Nearest,
+            CoordinateTransformationMode::Asymmetric,
+            NearestMode::RoundPreferCeil,
+            -0.75,
+        );
+
+        let output = resample(resampling, [1.0, 2.0, 3.0, 4.0], 8, 2.0);
+
+        output.assert_eq(
+            &TensorData::from([1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0, 4.0]),
+            false,
+        );
+    }
+
+    #[test]
+    fn test_linear_half_pixel() {
+        let resampling = Resampling::new(
+            InterpolateMode::Linear,
+            CoordinateTransformationMode::HalfPixel,
+            NearestMode::RoundPreferFloor,
+            -0.75,
+        );
+
+        let output = resample(resampling, [1.0, 2.0, 3.0, 4.0], 8, 2.0);
+
+        output.assert_approx_eq(
+            &TensorData::from([1.0, 1.25, 1.75, 2.25, 2.75, 3.25, 3.75, 4.0]),
+            4,
+        );
+    }
+
+    #[test]
+    fn test_cubic_align_corners() {
+        let resampling = Resampling::new(
+            InterpolateMode::Cubic,
+            CoordinateTransformationMode::AlignCorners,
+            NearestMode::RoundPreferFloor,
+            -0.5,
+        );
+
+        // The edges are replicated outside of the input.
+        let output = resample(resampling, [1.0, 2.0, 3.0, 4.0], 7, 1.75);
+
+        output.assert_approx_eq(
+            &TensorData::from([1.0, 1.4375, 2.0, 2.5, 3.0, 3.5625, 4.0]),
+            4,
+        );
+    }
+}
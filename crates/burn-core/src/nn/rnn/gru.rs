@@ -20,6 +20,11 @@ pub struct GruConfig {
     pub d_hidden: usize,
     /// If a bias should be applied during the Gru transformation.
     pub bias: bool,
+    /// If the reset gate should be applied after the hidden transformation of the new gate,
+    /// as in PyTorch and in the ONNX `GRU` operator with `linear_before_reset`, instead of
+    /// before it as in the original paper.
+    #[config(default = false)]
+    pub reset_after: bool,
     /// Gru initializer
     #[config(default = "Initializer::XavierNormal{gain:1.0}")]
     pub initializer: Initializer,
@@ -41,6 +46,8 @@ pub struct Gru<B: Backend> {
     pub new_gate: GateController<B>,
     /// The size of the hidden state.
     pub d_hidden: usize,
+    /// If the reset gate is applied after the hidden transformation of the new gate.
+    pub reset_after: bool,
 }
 
 impl<B: Backend> ModuleDisplay for Gru<B> {
@@ -94,6 +101,7 @@ impl GruConfig {
             reset_gate,
             new_gate,
             d_hidden: self.d_hidden,
+            reset_after: self.reset_after,
         }
     }
 }
@@ -129,25 +137,7 @@ impl<B: Backend> Gru<B> {
         {
             let input_t = input_t.squeeze(1);
             let hidden_t = hidden_t.squeeze(1);
-            // u(pdate)g(ate) tensors
-            let biased_ug_input_sum = self.gate_product(&input_t, &hidden_t, &self.update_gate);
-            let update_values = activation::sigmoid(biased_ug_input_sum); // Colloquially referred to as z(t)
-
-            // r(eset)g(ate) tensors
-            let biased_rg_input_sum = self.gate_product(&input_t, &hidden_t, &self.reset_gate);
-            let reset_values = activation::sigmoid(biased_rg_input_sum); // Colloquially referred to as r(t)
-            let reset_t = hidden_t.clone().mul(reset_values); // Passed as input to new_gate
-
-            // n(ew)g(ate) tensor
-            let biased_ng_input_sum = self.gate_product(&input_t, &reset_t, &self.new_gate);
-            let candidate_state = biased_ng_input_sum.tanh(); // Colloquially referred to as g(t)
-
-            // calculate linear interpolation between previous hidden state and candidate state:
-            // g(t) * (1 - z(t)) + z(t) * hidden_t
-            let state_vector = candidate_state
-                .clone()
-                .mul(update_values.clone().sub_scalar(1).mul_scalar(-1)) // (1 - z(t)) = -(z(t) - 1)
-                + update_values.clone().mul(hidden_t);
+            let state_vector = self.step(input_t, hidden_t);
 
             let current_shape = state_vector.shape().dims;
             let unsqueezed_shape = [current_shape[0], 1, current_shape[1]];
@@ -161,6 +151,70 @@ impl<B: Backend> Gru<B> {
         hidden_state
     }
 
+    /// Applies the forward pass on the input tensor, carrying the hidden state from each
+    /// element of the sequence to the next one. Returns the hidden state of each element of
+    /// the sequence and the final hidden state.
+    ///
+    /// # Shapes
+    /// - batched_input: `[batch_size, sequence_length, input_size]`.
+    /// - state: An optional tensor representing the initial hidden state with the shape
+    ///          `[batch_size, hidden_size]`. If none is provided, it is initialized to zeros.
+    /// - output: `[batch_size, sequence_length, hidden_size]` and `[batch_size, hidden_size]`.
+    pub fn forward_with_state(
+        &self,
+        batched_input: Tensor<B, 3>,
+        state: Option<Tensor<B, 2>>,
+    ) -> (Tensor<B, 3>, Tensor<B, 2>) {
+        let device = batched_input.device();
+        let [batch_size, seq_length, _] = batched_input.shape().dims();
+
+        let mut batched_hidden_state =
+            Tensor::empty([batch_size, seq_length, self.d_hidden], &device);
+
+        let mut hidden_t = match state {
+            Some(state) => state,
+            None => Tensor::zeros([batch_size, self.d_hidden], &device),
+        };
+
+        for (t, input_t) in batched_input.iter_dim(1).enumerate() {
+            hidden_t = self.step(input_t.squeeze(1), hidden_t);
+
+            batched_hidden_state = batched_hidden_state.slice_assign(
+                [0..batch_size, t..(t + 1), 0..self.d_hidden],
+                hidden_t.clone().unsqueeze_dim(1),
+            );
+        }
+
+        (batched_hidden_state, hidden_t)
+    }
+
+    /// Computes the hidden state of an element of the sequence from the previous hidden state.
+    fn step(&self, input_t: Tensor<B, 2>, hidden_t: Tensor<B, 2>) -> Tensor<B, 2> {
+        // u(pdate)g(ate) tensors
+        let biased_ug_input_sum = self.gate_product(&input_t, &hidden_t, None, &self.update_gate);
+        let update_values = activation::sigmoid(biased_ug_input_sum); // Colloquially referred to as z(t)
+
+        // r(eset)g(ate) tensors
+        let biased_rg_input_sum = self.gate_product(&input_t, &hidden_t, None, &self.reset_gate);
+        let reset_values = activation::sigmoid(biased_rg_input_sum); // Colloquially referred to as r(t)
+
+        // n(ew)g(ate) tensor
+        let biased_ng_input_sum = if self.reset_after {
+            self.gate_product(&input_t, &hidden_t, Some(&reset_values), &self.new_gate)
+        } else {
+            let reset_t = hidden_t.clone().mul(reset_values); // Passed as input to new_gate
+            self.gate_product(&input_t, &reset_t, None, &self.new_gate)
+        };
+        let candidate_state = biased_ng_input_sum.tanh(); // Colloquially referred to as g(t)
+
+        // calculate linear interpolation between previous hidden state and candidate state:
+        // g(t) * (1 - z(t)) + z(t) * hidden_t
+        candidate_state
+            .clone()
+            .mul(update_values.clone().sub_scalar(1).mul_scalar(-1)) // (1 - z(t)) = -(z(t) - 1)
+            + update_values.clone().mul(hidden_t)
+    }
+
     /// Helper function for performing weighted matrix product for a gate and adds
     /// bias, if any.
     ///
@@ -170,10 +224,13 @@ impl<B: Backend> Gru<B> {
     ///     X = input vector
     ///     H = hidden state
     ///     b = bias terms
+    ///
+    /// When the reset values are given, the hidden product and its bias are multiplied by them.
     fn gate_product(
         &self,
         input: &Tensor<B, 2>,
         hidden: &Tensor<B, 2>,
+        reset: Option<&Tensor<B, 2>>,
         gate: &GateController<B>,
     ) -> Tensor<B, 2> {
         let input_product = input.clone().matmul(gate.input_transform.weight.val());
@@ -190,13 +247,18 @@ impl<B: Backend> Gru<B> {
             .as_ref()
             .map(|bias_param| bias_param.val());
 
-        match (input_bias, hidden_bias) {
-            (Some(input_bias), Some(hidden_bias)) => {
-                input_product + input_bias.unsqueeze() + hidden_product + hidden_bias.unsqueeze()
-            }
-            (Some(input_bias), None) => input_product + input_bias.unsqueeze() + hidden_product,
-            (None, Some(hidden_bias)) => input_product + hidden_product + hidden_bias.unsqueeze(),
-            (None, None) => input_product + hidden_product,
+        let input_product = match input_bias {
+            Some(input_bias) => input_product + input_bias.unsqueeze(),
+            None => input_product,
+        };
+        let hidden_product = match hidden_bias {
+            Some(hidden_bias) => hidden_product + hidden_bias.unsqueeze(),
+            None => hidden_product,
+        };
+
+        match reset {
+            Some(reset) => input_product + reset.clone().mul(hidden_product),
+            None => input_product + hidden_product,
         }
     }
 }
@@ -207,19 +269,9 @@ mod tests {
     use crate::tensor::{Distribution, TensorData};
     use crate::{module::Param, nn::LinearRecord, TestBackend};
 
-    /// Test forward pass with simple input vector.
-    ///
-    /// z_t = sigmoid(0.5*0.1 + 0.5*0) = 0.5125
-    /// r_t = sigmoid(0.6*0.1 + 0.*0) = 0.5150
-    /// g_t = tanh(0.7*0.1 + 0.7*0) = 0.0699
-    ///
-    /// h_t = z_t * h' + (1 - z_t) * g_t = 0.0341
-    #[test]
-    fn tests_forward_single_input_single_feature() {
-        TestBackend::seed(0);
-        let config = GruConfig::new(1, 1, false);
-        let device = Default::default();
-        let mut gru = config.init::<TestBackend>(&device);
+    fn init_gru(reset_after: bool, device: &<TestBackend as Backend>::Device) -> Gru<TestBackend> {
+        let config = GruConfig::new(1, 1, false).with_reset_after(reset_after);
+        let mut gru = config.init::<TestBackend>(device);
 
         fn create_gate_controller(
             weights: f32,
@@ -255,7 +307,7 @@ mod tests {
             1,
             false,
             Initializer::XavierNormal { gain: 1.0 },
-            &device,
+            device,
         );
         gru.reset_gate = create_gate_controller(
             0.6,
@@ -264,7 +316,7 @@ mod tests {
             1,
             false,
             Initializer::XavierNormal { gain: 1.0 },
-            &device,
+            device,
         );
         gru.new_gate = create_gate_controller(
             0.7,
@@ -273,9 +325,25 @@ mod tests {
             1,
             false,
             Initializer::XavierNormal { gain: 1.0 },
-            &device,
+            device,
         );
 
+        gru
+    }
+
+    /// Test forward pass with simple input vector.
+    ///
+    /// z_t = sigmoid(0.5*0.1 + 0.5*0) = 0.5125
+    /// r_t = sigmoid(0.6*0.1 + 0.*0) = 0.5150
+    /// g_t = tanh(0.7*0.1 + 0.7*0) = 0.0699
+    ///
+    /// h_t = z_t * h' + (1 - z_t) * g_t = 0.0341
+    #[test]
+    fn tests_forward_single_input_single_feature() {
+        TestBackend::seed(0);
+        let device = Default::default();
+        let gru = init_gru(false, &device);
+
         let input = Tensor::<TestBackend, 3>::from_data(TensorData::from([[[0.1]]]), &device);
 
         let state = gru.forward(input, None);
@@ -288,6 +356,58 @@ mod tests {
         output.to_data().assert_approx_eq(&expected, 3);
     }
 
+    /// Test that the hidden state is carried over the sequence.
+    ///
+    /// h_1 = 0.0341 (see above)
+    /// z_2 = sigmoid(0.5*0.2 + 0.5*h_1) = 0.5292
+    /// r_2 = sigmoid(0.6*0.2 + 0.6*h_1) = 0.5351
+    /// g_2 = tanh(0.7*0.2 + 0.7*(r_2*h_1)) = 0.1516
+    ///
+    /// h_2 = z_2 * h_1 + (1 - z_2) * g_2 = 0.0894
+    #[test]
+    fn tests_forward_should_carry_the_hidden_state() {
+        let device = Default::default();
+        let input =
+            Tensor::<TestBackend, 3>::from_data(TensorData::from([[[0.1], [0.2]]]), &device);
+        let expected = TensorData::from([[[0.0341], [0.0894]]]);
+
+        // Without hidden bias, applying the reset gate after the hidden transformation is the same.
+        for reset_after in [false, true] {
+            let gru = init_gru(reset_after, &device);
+
+            let (output, state) = gru.forward_with_state(input.clone(), None);
+
+            output.to_data().assert_approx_eq(&expected, 3);
+            state
+                .to_data()
+                .assert_approx_eq(&TensorData::from([[0.0894]]), 3);
+        }
+    }
+
+    /// Test forward pass with an initial hidden state.
+    ///
+    /// z_t = sigmoid(0.5*0.1 + 0.5*0.5) = 0.5744
+    /// r_t = sigmoid(0.6*0.1 + 0.6*0.5) = 0.5890
+    /// g_t = tanh(0.7*0.1 + 0.7*(r_t*0.5)) = 0.2694
+    ///
+    /// h_t = z_t * 0.5 + (1 - z_t) * g_t = 0.4019
+    #[test]
+    fn tests_forward_with_initial_state() {
+        let device = Default::default();
+        let gru = init_gru(false, &device);
+        let input = Tensor::<TestBackend, 3>::from_data(TensorData::from([[[0.1]]]), &device);
+        let state = Tensor::<TestBackend, 2>::from_data(TensorData::from([[0.5]]), &device);
+
+        let (output, state) = gru.forward_with_state(input, Some(state));
+
+        output
+            .to_data()
+            .assert_approx_eq(&TensorData::from([[[0.4019]]]), 3);
+        state
+            .to_data()
+            .assert_approx_eq(&TensorData::from([[0.4019]]), 3);
+    }
+
     #[test]
     fn test_batched_forward_pass() {
         let device = Default::default();
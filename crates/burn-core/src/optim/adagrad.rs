@@ -9,7 +9,7 @@ use super::{
 };
 use crate::config::Config;
 use crate::optim::adaptor::OptimizerAdaptor;
-use crate::tensor::{backend::AutodiffBackend, Tensor};
+use crate::tensor::{backend::AutodiffBackend, Tensor, TensorData};
 use burn_tensor::backend::Backend;
 
 /// AdaGrad configuration.
@@ -65,6 +65,18 @@ impl<B: Backend> SimpleOptimizer<B> for AdaGrad<B> {
         (tensor - grad, Some(state))
     }
 
+    fn state_tensors<const D: usize>(state: &Self::State<D>) -> Vec<(String, TensorData)> {
+        vec![("sum".into(), state.lr_decay.sum.to_data())]
+    }
+
+    fn state_mismatch<const D: usize>(
+        state: &Self::State<D>,
+        shape: &[usize],
+    ) -> Option<Vec<usize>> {
+        let dims = state.lr_decay.sum.dims().to_vec();
+        (dims != shape).then_some(dims)
+    }
+
     fn to_device<const D: usize>(
         mut state: Self::State<D>,
         device: &<B as Backend>::Device,
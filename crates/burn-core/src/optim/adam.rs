@@ -4,12 +4,12 @@ use crate::{
 };
 
 use super::{
-    decay::{WeightDecay, WeightDecayConfig},
+    decay::{WeightDecay, WeightDecayConfig, WeightDecayExclusionConfig},
     SimpleOptimizer,
 };
 use crate::config::Config;
 use crate::optim::adaptor::OptimizerAdaptor;
-use crate::tensor::{backend::AutodiffBackend, Tensor};
+use crate::tensor::{backend::AutodiffBackend, Tensor, TensorData};
 use burn_tensor::{backend::Backend, ElementConversion};
 
 /// Adam configuration.
@@ -28,6 +28,8 @@ pub struct AdamConfig {
     weight_decay: Option<WeightDecayConfig>,
     /// [Gradient Clipping](GradientClippingConfig) config.
     grad_clipping: Option<GradientClippingConfig>,
+    /// [Weight decay exclusion](WeightDecayExclusionConfig) config.
+    weight_decay_exclusion: Option<WeightDecayExclusionConfig>,
 }
 
 /// Adam optimizer as described in the paper [Adam: A Method for Stochastic Optimization](https://arxiv.org/pdf/1412.6980.pdf).
@@ -50,16 +52,64 @@ impl<B: Backend> SimpleOptimizer<B> for Adam<B> {
         &self,
         lr: LearningRate,
         tensor: Tensor<B, D>,
-        mut grad: Tensor<B, D>,
+        grad: Tensor<B, D>,
+        state: Option<Self::State<D>>,
+    ) -> (Tensor<B, D>, Option<Self::State<D>>) {
+        self.update(lr, tensor, grad, state, self.weight_decay.as_ref())
+    }
+
+    fn step_without_weight_decay<const D: usize>(
+        &self,
+        lr: LearningRate,
+        tensor: Tensor<B, D>,
+        grad: Tensor<B, D>,
         state: Option<Self::State<D>>,
     ) -> (Tensor<B, D>, Option<Self::State<D>>) {
+        self.update(lr, tensor, grad, state, None)
+    }
+
+    fn state_tensors<const D: usize>(state: &Self::State<D>) -> Vec<(String, TensorData)> {
+        vec![
+            ("moment_1".into(), state.momentum.moment_1.to_data()),
+            ("moment_2".into(), state.momentum.moment_2.to_data()),
+        ]
+    }
+
+    fn state_mismatch<const D: usize>(
+        state: &Self::State<D>,
+        shape: &[usize],
+    ) -> Option<Vec<usize>> {
+        [&state.momentum.moment_1, &state.momentum.moment_2]
+            .into_iter()
+            .map(|tensor| tensor.dims().to_vec())
+            .find(|dims| dims != shape)
+    }
+
+    fn to_device<const D: usize>(
+        mut state: Self::State<D>,
+        device: &<B as Backend>::Device,
+    ) -> Self::State<D> {
+        state.momentum = state.momentum.to_device(device);
+        state
+    }
+}
+
+impl<B: Backend> Adam<B> {
+    fn update<const D: usize>(
+        &self,
+        lr: LearningRate,
+        tensor: Tensor<B, D>,
+        mut grad: Tensor<B, D>,
+        state: Option<AdamState<B, D>>,
+        weight_decay: Option<&WeightDecay<B>>,
+    ) -> (Tensor<B, D>, Option<AdamState<B, D>>) {
         let mut state_momentum = None;
 
         if let Some(state) = state {
             state_momentum = Some(state.momentum);
         }
 
-        if let Some(weight_decay) = &self.weight_decay {
+        if let Some(weight_decay) = weight_decay {
             grad = weight_decay.transform(grad, tensor.clone());
         }
 
@@ -70,14 +120,6 @@ impl<B: Backend> SimpleOptimizer<B> for Adam<B> {
 
         (tensor - delta, Some(state))
     }
-
-    fn to_device<const D: usize>(
-        mut state: Self::State<D>,
-        device: &<B as Backend>::Device,
-    ) -> Self::State<D> {
-        state.momentum = state.momentum.to_device(device);
-        state
-    }
 }
 
 impl AdamConfig {
@@ -102,6 +144,9 @@ impl AdamConfig {
         if let Some(config) = &self.grad_clipping {
             optim = optim.with_grad_clipping(config.init());
         }
+        if let Some(config) = &self.weight_decay_exclusion {
+            optim = optim.with_weight_decay_exclusion(config.init());
+        }
         optim
     }
 }
@@ -221,6 +266,78 @@ mod tests {
 
         assert_eq!(state_optim_before.len(), state_optim_after.len());
     }
+
+    #[test]
+    fn test_adam_optimizer_export_and_load_filtered_state() {
+        let device = Default::default();
+        let linear = nn::LinearConfig::new(6, 6).init(&device);
+        let x = Tensor::<TestAutodiffBackend, 2>::random([2, 6], Distribution::Default, &device);
+        let mut optimizer = create_adam();
+        let grads = linear.forward(x).backward();
+        let grads = GradientsParams::from_grads(grads, &linear);
+        let grad_weight = grads
+            .get::<TestBackend, 2>(linear.weight.id)
+            .unwrap()
+            .into_data();
+        let linear = optimizer.step(LEARNING_RATE, linear, grads);
+
+        let states = optimizer.export_states(&linear);
+        let paths = states
+            .iter()
+            .map(|(path, _)| path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(paths, ["weight", "bias"]);
+
+        let (_, tensors) = &states[0];
+        let names = tensors
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["moment_1", "moment_2"]);
+        let expected = Tensor::<TestBackend, 2>::from_data(grad_weight, &device).mul_scalar(0.1);
+        tensors[0].1.assert_approx_eq(&expected.into_data(), 5);
+
+        let bias_id = linear.bias.as_ref().unwrap().id;
+        let optimizer_loaded =
+            create_adam()
+                .load_record_filtered(&linear, optimizer.to_record(), |path| path == "bias");
+        assert_eq!(optimizer_loaded.state_ids(), [bias_id]);
+        assert!(optimizer_loaded.export_state(&linear.weight.id).is_none());
+    }
+
+    #[test]
+    fn test_adam_optimizer_load_record_by_path() {
+        let device = Default::default();
+        let linear = nn::LinearConfig::new(6, 6).init(&device);
+        let x = Tensor::<TestAutodiffBackend, 2>::random([2, 6], Distribution::Default, &device);
+        let mut optimizer = create_adam();
+        let grads = linear.forward(x).backward();
+        let grads = GradientsParams::from_grads(grads, &linear);
+        let linear = optimizer.step(LEARNING_RATE, linear, grads);
+        let record = optimizer.to_record_by_path(&linear);
+
+        // The parameters of another module with the same paths have other ids
+        let linear_same = nn::LinearConfig::new(6, 6).init::<TestAutodiffBackend>(&device);
+        let (optimizer_same, report) =
+            create_adam().load_record_by_path(&linear_same, record.clone());
+        assert!(report.is_exact());
+        assert_eq!(
+            optimizer_same.export_states(&linear_same),
+            optimizer.export_states(&linear)
+        );
+
+        let linear_resized = nn::LinearConfig::new(6, 4).init::<TestAutodiffBackend>(&device);
+        let (optimizer_resized, report) =
+            create_adam().load_record_by_path(&linear_resized, record);
+        assert!(optimizer_resized.state_ids().is_empty());
+        let paths = report
+            .mismatched
+            .iter()
+            .map(|mismatch| mismatch.path.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(paths, ["weight", "bias"]);
+    }
+
     const ASSERT_PRECISION: usize = 2;
 
     #[test]
@@ -4,10 +4,11 @@ use crate::{
 };
 use std::marker::PhantomData;
 
+use super::decay::WeightDecayExclusionConfig;
 use super::SimpleOptimizer;
 use crate::config::Config;
 use crate::optim::adaptor::OptimizerAdaptor;
-use crate::tensor::{backend::AutodiffBackend, Tensor};
+use crate::tensor::{backend::AutodiffBackend, Tensor, TensorData};
 use burn_tensor::{backend::Backend, ElementConversion};
 
 /// AdamW configuration.
@@ -27,6 +28,8 @@ pub struct AdamWConfig {
     weight_decay: f32,
     /// [Gradient Clipping](GradientClippingConfig) config.
     grad_clipping: Option<GradientClippingConfig>,
+    /// [Weight decay exclusion](WeightDecayExclusionConfig) config.
+    weight_decay_exclusion: Option<WeightDecayExclusionConfig>,
 }
 
 /// AdamW optimizer as described in the paper [Decoupled Weight Decay Regularization, Loshchilov and Hutter, 2019](https://arxiv.org/abs/1711.05101).
@@ -58,15 +61,34 @@ impl<B: Backend> SimpleOptimizer<B> for AdamW<B> {
         // State of the optimizer.
         state: Option<Self::State<D>>,
     ) -> (Tensor<B, D>, Option<Self::State<D>>) {
-        let tensor_updated = tensor.clone() - tensor.mul_scalar(lr).mul_scalar(self.weight_decay);
+        self.update(lr, tensor, grad, state, self.weight_decay)
+    }
 
-        let (raw_delta, momentum_state) = self.momentum.transform(grad, state.map(|s| s.momentum));
+    fn step_without_weight_decay<const D: usize>(
+        &self,
+        lr: LearningRate,
+        tensor: Tensor<B, D>,
+        grad: Tensor<B, D>,
+        state: Option<Self::State<D>>,
+    ) -> (Tensor<B, D>, Option<Self::State<D>>) {
+        self.update(lr, tensor, grad, state, 0.0)
+    }
 
-        let state = AdamWState {
-            momentum: momentum_state,
-        };
+    fn state_tensors<const D: usize>(state: &Self::State<D>) -> Vec<(String, TensorData)> {
+        vec![
+            ("moment_1".into(), state.momentum.moment_1.to_data()),
+            ("moment_2".into(), state.momentum.moment_2.to_data()),
+        ]
+    }
 
-        (tensor_updated - raw_delta.mul_scalar(lr), Some(state))
+    fn state_mismatch<const D: usize>(
+        state: &Self::State<D>,
+        shape: &[usize],
+    ) -> Option<Vec<usize>> {
+        [&state.momentum.moment_1, &state.momentum.moment_2]
+            .into_iter()
+            .map(|tensor| tensor.dims().to_vec())
+            .find(|dims| dims != shape)
     }
 
     fn to_device<const D: usize>(
@@ -78,6 +100,27 @@ impl<B: Backend> SimpleOptimizer<B> for AdamW<B> {
     }
 }
 
+impl<B: Backend> AdamW<B> {
+    fn update<const D: usize>(
+        &self,
+        lr: LearningRate,
+        tensor: Tensor<B, D>,
+        grad: Tensor<B, D>,
+        state: Option<AdamWState<B, D>>,
+        weight_decay: f32,
+    ) -> (Tensor<B, D>, Option<AdamWState<B, D>>) {
+        let tensor_updated = tensor.clone() - tensor.mul_scalar(lr).mul_scalar(weight_decay);
+
+        let (raw_delta, momentum_state) = self.momentum.transform(grad, state.map(|s| s.momentum));
+
+        let state = AdamWState {
+            momentum: momentum_state,
+        };
+
+        (tensor_updated - raw_delta.mul_scalar(lr), Some(state))
+    }
+}
+
 impl AdamWConfig {
     /// Initialize AdamW optimizer.
     ///
@@ -101,6 +144,9 @@ impl AdamWConfig {
         if let Some(config) = &self.grad_clipping {
             optim = optim.with_grad_clipping(config.init());
         }
+        if let Some(config) = &self.weight_decay_exclusion {
+            optim = optim.with_weight_decay_exclusion(config.init());
+        }
         optim
     }
 }
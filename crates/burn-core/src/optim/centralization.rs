@@ -0,0 +1,235 @@
+use crate::{
+    module::{AutodiffModule, ModuleVisitor, ParamId},
+    LearningRate,
+};
+use burn_tensor::{
+    backend::{AutodiffBackend, Backend},
+    Tensor,
+};
+use core::marker::PhantomData;
+
+use super::{GradientsParams, Optimizer};
+
+/// Optimizer decorator applying gradient centralization, as described in the paper [Gradient
+/// Centralization: A New Optimization Technique for Deep Neural
+/// Networks](https://arxiv.org/abs/2004.01461), before delegating to the wrapped optimizer.
+///
+/// The gradients of multi-dimensional weights are re-centered so that each output channel has a
+/// zero mean. Following burn's parameter layouts, the output channels are on the last dimension
+/// of 2-D weights (e.g. [Linear](crate::nn::Linear) `[d_input, d_output]`) and on the first
+/// dimension of higher rank weights (e.g. [Conv2d](crate::nn::conv::Conv2d)
+/// `[channels_out, channels_in, kernel_size_1, kernel_size_2]`). 1-D parameters, such as biases,
+/// are left untouched.
+///
+/// The rows of an [Embedding](crate::nn::Embedding) `[n_embedding, d_model]` are not output
+/// channels: centering them would push every unused token. The parameters whose path contains one
+/// of the [excluded patterns](GradientCentralization::with_excluded_patterns), `embedding` by
+/// default, are left untouched too. The path is made of the field names joined by dots, e.g.
+/// `encoder.token_embedding.weight`.
+pub struct GradientCentralization<O, M, B>
+where
+    B: AutodiffBackend,
+    M: AutodiffModule<B>,
+    O: Optimizer<M, B>,
+{
+    optim: O,
+    excluded_patterns: Vec<String>,
+    _phantom: PhantomData<(M, B)>,
+}
+
+impl<O, M, B> GradientCentralization<O, M, B>
+where
+    B: AutodiffBackend,
+    M: AutodiffModule<B>,
+    O: Optimizer<M, B>,
+{
+    /// Wraps the given optimizer with gradient centralization.
+    pub fn new(optim: O) -> Self {
+        Self {
+            optim,
+            excluded_patterns: vec!["embedding".to_string()],
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Sets the patterns of the paths of the parameters whose gradients are not centralized,
+    /// replacing the default `embedding` pattern.
+    pub fn with_excluded_patterns(mut self, patterns: &[&str]) -> Self {
+        self.excluded_patterns = patterns.iter().map(ToString::to_string).collect();
+        self
+    }
+}
+
+impl<O, M, B> Optimizer<M, B> for GradientCentralization<O, M, B>
+where
+    B: AutodiffBackend,
+    M: AutodiffModule<B>,
+    O: Optimizer<M, B>,
+{
+    type Record = O::Record;
+
+    fn step(&mut self, lr: LearningRate, module: M, mut grads: GradientsParams) -> M {
+        let mut visitor = GradientsCentralizer::<B>::new(&mut grads, &self.excluded_patterns);
+        module.visit(&mut visitor);
+
+        self.optim.step(lr, module, grads)
+    }
+
+    fn to_record(&self) -> Self::Record {
+        self.optim.to_record()
+    }
+
+    fn load_record(mut self, record: Self::Record) -> Self {
+        self.optim = self.optim.load_record(record);
+        self
+    }
+}
+
+#[derive(new)]
+struct GradientsCentralizer<'a, B: AutodiffBackend> {
+    grads: &'a mut GradientsParams,
+    excluded_patterns: &'a [String],
+    #[new(default)]
+    path: Vec<String>,
+    phantom: PhantomData<B>,
+}
+
+impl<'a, B: AutodiffBackend> ModuleVisitor<B> for GradientsCentralizer<'a, B> {
+    fn enter_module(&mut self, name: &str) {
+        self.path.push(name.into());
+    }
+
+    fn exit_module(&mut self, _name: &str) {
+        self.path.pop();
+    }
+
+    fn visit_float<const D: usize>(&mut self, id: ParamId, _tensor: &Tensor<B, D>) {
+        if D < 2 {
+            return;
+        }
+
+        let path = self.path.join(".");
+        if self
+            .excluded_patterns
+            .iter()
+            .any(|pattern| path.contains(pattern.as_str()))
+        {
+            return;
+        }
+
+        let Some(grad) = self.grads.remove::<B::InnerBackend, D>(id) else {
+            return;
+        };
+
+        self.grads.register(id, centralize(grad));
+    }
+}
+
+/// Subtract the mean of each output channel from the gradient.
+fn centralize<B: Backend, const D: usize>(grad: Tensor<B, D>) -> Tensor<B, D> {
+    if D == 2 {
+        return grad.clone().sub(grad.mean_dim(0));
+    }
+
+    let dims = grad.dims();
+    let channels = dims[0];
+    let grad = grad.reshape([channels, dims.iter().skip(1).product()]);
+
+    grad.clone().sub(grad.mean_dim(1)).reshape(dims)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as burn;
+    use crate::module::Module;
+    use crate::optim::SgdConfig;
+    use crate::tensor::{Distribution, Int, TensorData};
+    use crate::{nn, TestAutodiffBackend, TestBackend};
+
+    #[test]
+    fn centralize_2d_should_center_output_channels() {
+        let grad =
+            Tensor::<TestBackend, 2>::from_floats([[1.0, 2.0], [3.0, 6.0]], &Default::default());
+
+        centralize(grad)
+            .into_data()
+            .assert_eq(&TensorData::from([[-1.0, -2.0], [1.0, 2.0]]), false);
+    }
+
+    #[test]
+    fn centralize_4d_should_center_output_channels() {
+        let grad = Tensor::<TestBackend, 4>::from_floats(
+            [[[[1.0, 3.0]]], [[[2.0, 2.0]]]],
+            &Default::default(),
+        );
+
+        centralize(grad)
+            .into_data()
+            .assert_eq(&TensorData::from([[[[-1.0, 1.0]]], [[[0.0, 0.0]]]]), false);
+    }
+
+    #[test]
+    fn should_step_with_wrapped_optimizer() {
+        let device = Default::default();
+        let linear = nn::LinearConfig::new(6, 6).init::<TestAutodiffBackend>(&device);
+        let x = Tensor::<TestAutodiffBackend, 2>::random([2, 6], Distribution::Default, &device);
+        let mut optim = GradientCentralization::new(SgdConfig::new().init());
+
+        let grads = linear.forward(x).backward();
+        let grads = GradientsParams::from_grads(grads, &linear);
+        let updated = optim.step(0.1, linear.clone(), grads);
+
+        assert_ne!(
+            updated.weight.val().into_data(),
+            linear.weight.val().into_data()
+        );
+    }
+
+    #[derive(Module, Debug)]
+    struct Lookup<B: Backend> {
+        embedding: nn::Embedding<B>,
+        linear: nn::Linear<B>,
+    }
+
+    /// The update of the embeddings after a step with the tokens 0 and 2 only.
+    fn lookup_step(excluded_patterns: Option<&[&str]>) -> Tensor<TestBackend, 2> {
+        let device = Default::default();
+        let lookup = Lookup {
+            embedding: nn::EmbeddingConfig::new(4, 3).init(&device),
+            linear: nn::LinearConfig::new(3, 2).init(&device),
+        };
+        let mut optim = GradientCentralization::new(SgdConfig::new().init());
+        if let Some(patterns) = excluded_patterns {
+            optim = optim.with_excluded_patterns(patterns);
+        }
+
+        let tokens = Tensor::<TestAutodiffBackend, 2, Int>::from_ints([[0, 2]], &device);
+        let loss = lookup
+            .linear
+            .forward(lookup.embedding.forward(tokens))
+            .sum();
+        let grads = GradientsParams::from_grads(loss.backward(), &lookup);
+        let updated = optim.step(1.0, lookup.clone(), grads);
+
+        (updated.embedding.weight.val() - lookup.embedding.weight.val()).inner()
+    }
+
+    #[test]
+    fn should_leave_the_unused_embeddings_untouched() {
+        let delta = lookup_step(None);
+
+        let rows = delta.abs().sum_dim(1).into_data();
+        assert_eq!(rows.as_slice::<f32>().unwrap()[1], 0.0);
+        assert_eq!(rows.as_slice::<f32>().unwrap()[3], 0.0);
+    }
+
+    #[test]
+    fn should_centralize_the_embeddings_without_exclusion() {
+        let delta = lookup_step(Some(&[]));
+
+        // Every row is moved by the mean over the vocabulary
+        let rows = delta.abs().sum_dim(1).into_data();
+        assert!(rows.as_slice::<f32>().unwrap()[1] > 0.0);
+    }
+}
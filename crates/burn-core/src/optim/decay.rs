@@ -13,6 +13,55 @@ pub struct WeightDecayConfig {
     pub penalty: f64,
 }
 
+/// Configuration to create a [weight decay exclusion](WeightDecayExclusion).
+///
+/// The default configuration follows the usual transformer recipe: biases and 1-D parameters,
+/// such as normalization scales and offsets, are not decayed.
+#[derive(Config)]
+pub struct WeightDecayExclusionConfig {
+    /// Parameters whose path contains one of these patterns are excluded from weight decay.
+    #[config(default = "vec![\"bias\".to_string()]")]
+    pub name_patterns: Vec<String>,
+    /// Whether 1-D parameters are excluded from weight decay.
+    #[config(default = true)]
+    pub exclude_1d: bool,
+}
+
+impl WeightDecayExclusionConfig {
+    /// Initialize the weight decay exclusion.
+    pub fn init(&self) -> WeightDecayExclusion {
+        WeightDecayExclusion {
+            name_patterns: self.name_patterns.clone(),
+            exclude_1d: self.exclude_1d,
+        }
+    }
+}
+
+/// Rules deciding which parameters should not be affected by weight decay.
+///
+/// Parameters are matched using their path in the module tree, made of the field names
+/// (indices for collections and tuples, variant names for enums) joined by dots, e.g.
+/// `layers.0.norm.gamma`.
+#[derive(Clone, Debug)]
+pub struct WeightDecayExclusion {
+    name_patterns: Vec<String>,
+    exclude_1d: bool,
+}
+
+impl WeightDecayExclusion {
+    /// Returns whether the parameter at the given path and with the given rank is excluded from
+    /// weight decay.
+    pub fn is_excluded(&self, path: &str, rank: usize) -> bool {
+        if self.exclude_1d && rank == 1 {
+            return true;
+        }
+
+        self.name_patterns
+            .iter()
+            .any(|pattern| path.contains(pattern.as_str()))
+    }
+}
+
 /// State of [weight decay](WeightDecay).
 #[derive(Record, Clone, new)]
 pub struct WeightDecayState<B: Backend, const D: usize> {
@@ -67,3 +116,29 @@ impl<B: Backend, const D: usize> WeightDecayState<B, D> {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_exclusion_should_skip_bias_and_1d_params() {
+        let exclusion = WeightDecayExclusionConfig::new().init();
+
+        assert!(exclusion.is_excluded("linear.bias", 2));
+        assert!(exclusion.is_excluded("norm.gamma", 1));
+        assert!(!exclusion.is_excluded("linear.weight", 2));
+    }
+
+    #[test]
+    fn exclusion_should_match_custom_patterns() {
+        let exclusion = WeightDecayExclusionConfig::new()
+            .with_name_patterns(vec!["norm".to_string(), "embedding".to_string()])
+            .with_exclude_1d(false)
+            .init();
+
+        assert!(exclusion.is_excluded("layers.0.norm.gamma", 1));
+        assert!(exclusion.is_excluded("embedding.weight", 2));
+        assert!(!exclusion.is_excluded("linear.bias", 1));
+    }
+}
@@ -6,7 +6,7 @@ use burn_tensor::{
 
 use crate::module::{AutodiffModule, ParamId};
 
-use super::visitor::{GradientsParamsChangeDevice, GradientsParamsConverter};
+use super::visitor::{GradientsParamsChangeDevice, GradientsParamsConverter, GradientsParamsScale};
 
 /// Data type that contains gradients for parameters.
 #[derive(Default, Debug)]
@@ -109,6 +109,18 @@ impl GradientsParams {
         module.visit(&mut visitor);
         self
     }
+
+    /// Multiply each tensor gradients registered for the given [module](AutodiffModule) by a
+    /// factor, for instance to average gradients summed over multiple devices.
+    pub fn scale<B: AutodiffBackend, M: AutodiffModule<B>>(
+        mut self,
+        module: &M,
+        factor: f64,
+    ) -> Self {
+        let mut visitor = GradientsParamsScale::<M, B>::new(factor, &mut self);
+        module.visit(&mut visitor);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -140,6 +152,27 @@ mod tests {
         assert_eq!(grads_2.len(), param_ids_2.len());
     }
 
+    #[test]
+    fn test_scale_grads() {
+        let device = Default::default();
+        let layer = layer::<TestAutodiffBackend>(&device);
+        let loss = layer.forward(random_tensor(&device));
+        let grads = GradientsParams::from_grads(loss.backward(), &layer);
+        let weight_id = layer.weight.id;
+        let weight_grad = grads
+            .get::<<TestAutodiffBackend as AutodiffBackend>::InnerBackend, 2>(weight_id)
+            .unwrap();
+
+        let grads = grads.scale(&layer, 0.5);
+
+        let scaled = grads
+            .get::<<TestAutodiffBackend as AutodiffBackend>::InnerBackend, 2>(weight_id)
+            .unwrap();
+        scaled
+            .into_data()
+            .assert_approx_eq(&weight_grad.mul_scalar(0.5).into_data(), 5);
+    }
+
     fn layer<B: Backend>(device: &B::Device) -> Linear<B> {
         LinearConfig::new(20, 20).with_bias(true).init(device)
     }
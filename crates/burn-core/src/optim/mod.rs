@@ -8,10 +8,13 @@ mod adagrad;
 mod adam;
 mod adamw;
 mod base;
+mod centralization;
 mod grad_accum;
 mod grads;
 mod rmsprop;
+mod sam;
 mod sgd;
+mod shampoo;
 mod simple;
 mod visitor;
 
@@ -19,8 +22,11 @@ pub use adagrad::*;
 pub use adam::*;
 pub use adamw::*;
 pub use base::*;
+pub use centralization::*;
 pub use grad_accum::*;
 pub use grads::*;
 pub use rmsprop::*;
+pub use sam::*;
 pub use sgd::*;
+pub use shampoo::*;
 pub use simple::*;
@@ -23,7 +23,7 @@ pub struct MomentumConfig {
 /// State of [momentum](Momentum).
 #[derive(Record, Clone, new)]
 pub struct MomentumState<B: Backend, const D: usize> {
-    velocity: Tensor<B, D>,
+    pub(crate) velocity: Tensor<B, D>,
 }
 
 /// Momemtum implementation that transforms gradients.
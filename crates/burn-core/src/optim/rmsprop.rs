@@ -9,7 +9,7 @@ use super::{
 };
 use crate::config::Config;
 use crate::optim::adaptor::OptimizerAdaptor;
-use crate::tensor::{backend::AutodiffBackend, Tensor};
+use crate::tensor::{backend::AutodiffBackend, Tensor, TensorData};
 use burn_tensor::backend::Backend;
 
 /// Configuration to create the [RmsProp](RmsProp) optimizer.
@@ -125,6 +125,32 @@ impl<B: Backend> SimpleOptimizer<B> for RmsProp<B> {
         (tensor - delta, Some(state))
     }
 
+    fn state_tensors<const D: usize>(state: &Self::State<D>) -> Vec<(String, TensorData)> {
+        let mut tensors = vec![
+            ("square_avg".into(), state.square_avg.square_avg.to_data()),
+            ("avg".into(), state.centered.avg.to_data()),
+        ];
+        if let Some(grad_avg) = &state.centered.grad_avg {
+            tensors.push(("grad_avg".into(), grad_avg.to_data()));
+        }
+        if let Some(momentum) = &state.momentum {
+            tensors.push(("momentum".into(), momentum.buf.to_data()));
+        }
+        tensors
+    }
+
+    fn state_mismatch<const D: usize>(
+        state: &Self::State<D>,
+        shape: &[usize],
+    ) -> Option<Vec<usize>> {
+        [&state.square_avg.square_avg, &state.centered.avg]
+            .into_iter()
+            .chain(&state.centered.grad_avg)
+            .chain(state.momentum.iter().map(|momentum| &momentum.buf))
+            .map(|tensor| tensor.dims().to_vec())
+            .find(|dims| dims != shape)
+    }
+
     fn to_device<const D: usize>(
         mut state: Self::State<D>,
         device: &<B as Backend>::Device,
@@ -0,0 +1,263 @@
+use crate::{
+    self as burn,
+    config::Config,
+    module::{AutodiffModule, ModuleMapper, ModuleVisitor, ParamId},
+    LearningRate,
+};
+use burn_tensor::{backend::AutodiffBackend, ElementConversion, Tensor};
+use core::marker::PhantomData;
+
+use super::{GradientsParams, Optimizer};
+
+/// Configuration to create the [Sam](Sam) optimizer wrapper.
+#[derive(Config)]
+pub struct SamConfig {
+    /// The radius of the neighborhood in which the sharpness is minimized.
+    #[config(default = 0.05)]
+    rho: f64,
+    /// Whether the neighborhood is scaled by the parameters magnitude, as done by
+    /// [ASAM](https://arxiv.org/abs/2102.11600).
+    #[config(default = false)]
+    adaptive: bool,
+    /// A value required for numerical stability.
+    #[config(default = 1e-12)]
+    epsilon: f64,
+}
+
+impl SamConfig {
+    /// Wraps the given optimizer with sharpness-aware minimization.
+    pub fn init<B, M, O>(&self, optim: O) -> Sam<O, M, B>
+    where
+        B: AutodiffBackend,
+        M: AutodiffModule<B>,
+        O: Optimizer<M, B>,
+    {
+        Sam {
+            optim,
+            rho: self.rho,
+            adaptive: self.adaptive,
+            epsilon: self.epsilon,
+            perturbations: None,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// Sharpness-aware minimization as described in the paper [Sharpness-Aware Minimization for
+/// Efficiently Improving Generalization](https://arxiv.org/abs/2010.01412).
+///
+/// SAM needs two forward and backward passes for each optimization step:
+///
+/// 1. The gradients computed at the current parameters are given to [perturb](Sam::perturb),
+///    which moves the parameters toward the point of highest loss in their neighborhood.
+/// 2. The gradients computed at the perturbed parameters are given to [step](Optimizer::step),
+///    which restores the original parameters and updates them with the wrapped optimizer.
+pub struct Sam<O, M, B>
+where
+    B: AutodiffBackend,
+    M: AutodiffModule<B>,
+    O: Optimizer<M, B>,
+{
+    optim: O,
+    rho: f64,
+    adaptive: bool,
+    epsilon: f64,
+    perturbations: Option<GradientsParams>,
+    _phantom: PhantomData<(M, B)>,
+}
+
+impl<O, M, B> Sam<O, M, B>
+where
+    B: AutodiffBackend,
+    M: AutodiffModule<B>,
+    O: Optimizer<M, B>,
+{
+    /// Perform the ascent step, moving the parameters of the module in the direction of the
+    /// given gradients.
+    ///
+    /// The gradients of the perturbed module should then be given to [step](Optimizer::step).
+    pub fn perturb(&mut self, module: M, grads: GradientsParams) -> M {
+        let mut visitor = GradientsNorm::<B>::new(&grads, self.adaptive);
+        module.visit(&mut visitor);
+
+        let Some(norm) = visitor.sum else {
+            return module;
+        };
+        let norm = norm.sqrt().into_scalar().elem::<f64>();
+
+        let mut perturbations = GradientsParams::new();
+        let mut mapper = Perturbation::<B>::new(
+            &grads,
+            &mut perturbations,
+            self.rho / (norm + self.epsilon),
+            self.adaptive,
+        );
+        let module = module.map(&mut mapper);
+        self.perturbations = Some(perturbations);
+
+        module
+    }
+}
+
+impl<O, M, B> Optimizer<M, B> for Sam<O, M, B>
+where
+    B: AutodiffBackend,
+    M: AutodiffModule<B>,
+    O: Optimizer<M, B>,
+{
+    type Record = O::Record;
+
+    fn step(&mut self, lr: LearningRate, module: M, grads: GradientsParams) -> M {
+        let module = match self.perturbations.take() {
+            Some(mut perturbations) => {
+                let mut mapper = PerturbationRemoval::<B>::new(&mut perturbations);
+                module.map(&mut mapper)
+            }
+            None => module,
+        };
+
+        self.optim.step(lr, module, grads)
+    }
+
+    fn to_record(&self) -> Self::Record {
+        self.optim.to_record()
+    }
+
+    fn load_record(mut self, record: Self::Record) -> Self {
+        self.optim = self.optim.load_record(record);
+        self
+    }
+}
+
+#[derive(new)]
+struct GradientsNorm<'a, B: AutodiffBackend> {
+    grads: &'a GradientsParams,
+    adaptive: bool,
+    #[new(default)]
+    sum: Option<Tensor<B::InnerBackend, 1>>,
+}
+
+impl<'a, B: AutodiffBackend> ModuleVisitor<B> for GradientsNorm<'a, B> {
+    fn visit_float<const D: usize>(&mut self, id: ParamId, tensor: &Tensor<B, D>) {
+        let Some(grad) = self.grads.get::<B::InnerBackend, D>(id) else {
+            return;
+        };
+        let grad = if self.adaptive {
+            tensor.clone().inner().abs().mul(grad)
+        } else {
+            grad
+        };
+        let squared = grad.powf_scalar(2.0).sum();
+
+        self.sum = Some(match self.sum.take() {
+            Some(sum) => {
+                let device = sum.device();
+                sum.add(squared.to_device(&device))
+            }
+            None => squared,
+        });
+    }
+}
+
+#[derive(new)]
+struct Perturbation<'a, B: AutodiffBackend> {
+    grads: &'a GradientsParams,
+    perturbations: &'a mut GradientsParams,
+    scale: f64,
+    adaptive: bool,
+    phantom: PhantomData<B>,
+}
+
+impl<'a, B: AutodiffBackend> ModuleMapper<B> for Perturbation<'a, B> {
+    fn map_float<const D: usize>(&mut self, id: ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
+        let Some(grad) = self.grads.get::<B::InnerBackend, D>(id) else {
+            return tensor;
+        };
+
+        let is_require_grad = tensor.is_require_grad();
+        let tensor = tensor.inner();
+        let perturbation = if self.adaptive {
+            tensor.clone().powf_scalar(2.0).mul(grad)
+        } else {
+            grad
+        }
+        .mul_scalar(self.scale);
+
+        self.perturbations.register(id, perturbation.clone());
+
+        let mut tensor = Tensor::from_inner(tensor.add(perturbation));
+        if is_require_grad {
+            tensor = tensor.require_grad();
+        }
+        tensor
+    }
+}
+
+#[derive(new)]
+struct PerturbationRemoval<'a, B: AutodiffBackend> {
+    perturbations: &'a mut GradientsParams,
+    phantom: PhantomData<B>,
+}
+
+impl<'a, B: AutodiffBackend> ModuleMapper<B> for PerturbationRemoval<'a, B> {
+    fn map_float<const D: usize>(&mut self, id: ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
+        let Some(perturbation) = self.perturbations.remove::<B::InnerBackend, D>(id) else {
+            return tensor;
+        };
+
+        let is_require_grad = tensor.is_require_grad();
+        let mut tensor = Tensor::from_inner(tensor.inner().sub(perturbation));
+        if is_require_grad {
+            tensor = tensor.require_grad();
+        }
+        tensor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::Module;
+    use crate::optim::SgdConfig;
+    use crate::tensor::Distribution;
+    use crate::{nn, TestAutodiffBackend};
+
+    const LEARNING_RATE: LearningRate = 0.1;
+
+    #[test]
+    fn perturb_then_step_should_update_from_original_params() {
+        let device = Default::default();
+        let linear = nn::LinearConfig::new(6, 6).init::<TestAutodiffBackend>(&device);
+        let x = Tensor::<TestAutodiffBackend, 2>::random([2, 6], Distribution::Default, &device);
+        let mut optim = SamConfig::new().init(SgdConfig::new().init());
+        let mut optim_expected = SgdConfig::new().init();
+
+        let grads = linear.forward(x.clone()).backward();
+        let grads = GradientsParams::from_grads(grads, &linear);
+        let perturbed = optim.perturb(linear.clone(), grads);
+
+        assert_ne!(
+            perturbed.weight.val().into_data(),
+            linear.weight.val().into_data()
+        );
+
+        let grads = perturbed.forward(x.clone()).backward();
+        let grads = GradientsParams::from_grads(grads, &perturbed);
+        let updated = optim.step(LEARNING_RATE, perturbed.clone(), grads);
+
+        let grads = perturbed.forward(x).backward();
+        let grads = GradientsParams::from_grads(grads, &perturbed);
+        let expected = optim_expected.step(LEARNING_RATE, linear, grads);
+
+        let (updated, expected) = (updated.into_record(), expected.into_record());
+        updated
+            .weight
+            .to_data()
+            .assert_approx_eq(&expected.weight.to_data(), 3);
+        updated
+            .bias
+            .unwrap()
+            .to_data()
+            .assert_approx_eq(&expected.bias.unwrap().to_data(), 3);
+    }
+}
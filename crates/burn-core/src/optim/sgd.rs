@@ -2,13 +2,13 @@ use crate::grad_clipping::GradientClippingConfig;
 use crate::module::AutodiffModule;
 use crate::{self as burn, LearningRate};
 
-use super::decay::{WeightDecay, WeightDecayConfig};
+use super::decay::{WeightDecay, WeightDecayConfig, WeightDecayExclusionConfig};
 use super::momentum::{Momentum, MomentumConfig, MomentumState};
 use super::SimpleOptimizer;
 use crate::config::Config;
 use crate::optim::adaptor::OptimizerAdaptor;
 use crate::record::Record;
-use crate::tensor::Tensor;
+use crate::tensor::{Tensor, TensorData};
 use burn_tensor::backend::{AutodiffBackend, Backend};
 
 /// Configuration to create the [Sgd](Sgd) optimizer.
@@ -20,6 +20,8 @@ pub struct SgdConfig {
     momentum: Option<MomentumConfig>,
     /// [Gradient Clipping](GradientClippingConfig) config.
     gradient_clipping: Option<GradientClippingConfig>,
+    /// [Weight decay exclusion](WeightDecayExclusionConfig) config.
+    weight_decay_exclusion: Option<WeightDecayExclusionConfig>,
 }
 
 /// Optimizer that implements stochastic gradient descent with momentum.
@@ -52,27 +54,29 @@ impl SgdConfig {
         if let Some(config) = &self.gradient_clipping {
             optim = optim.with_grad_clipping(config.init());
         }
+        if let Some(config) = &self.weight_decay_exclusion {
+            optim = optim.with_weight_decay_exclusion(config.init());
+        }
         optim
     }
 }
 
-impl<B: Backend> SimpleOptimizer<B> for Sgd<B> {
-    type State<const D: usize> = SgdState<B, D>;
-
-    fn step<const D: usize>(
+impl<B: Backend> Sgd<B> {
+    fn update<const D: usize>(
         &self,
         lr: LearningRate,
         tensor: Tensor<B, D>,
         mut grad: Tensor<B, D>,
-        state: Option<Self::State<D>>,
-    ) -> (Tensor<B, D>, Option<Self::State<D>>) {
+        state: Option<SgdState<B, D>>,
+        weight_decay: Option<&WeightDecay<B>>,
+    ) -> (Tensor<B, D>, Option<SgdState<B, D>>) {
         let mut state_momemtum = None;
 
         if let Some(state) = state {
             state_momemtum = state.momentum;
         }
 
-        if let Some(weight_decay) = &self.weight_decay {
+        if let Some(weight_decay) = weight_decay {
             grad = weight_decay.transform(grad, tensor.clone());
         }
 
@@ -87,6 +91,49 @@ impl<B: Backend> SimpleOptimizer<B> for Sgd<B> {
 
         (tensor - delta, Some(state))
     }
+}
+
+impl<B: Backend> SimpleOptimizer<B> for Sgd<B> {
+    type State<const D: usize> = SgdState<B, D>;
+
+    fn step<const D: usize>(
+        &self,
+        lr: LearningRate,
+        tensor: Tensor<B, D>,
+        grad: Tensor<B, D>,
+        state: Option<Self::State<D>>,
+    ) -> (Tensor<B, D>, Option<Self::State<D>>) {
+        self.update(lr, tensor, grad, state, self.weight_decay.as_ref())
+    }
+
+    fn step_without_weight_decay<const D: usize>(
+        &self,
+        lr: LearningRate,
+        tensor: Tensor<B, D>,
+        grad: Tensor<B, D>,
+        state: Option<Self::State<D>>,
+    ) -> (Tensor<B, D>, Option<Self::State<D>>) {
+        self.update(lr, tensor, grad, state, None)
+    }
+
+    fn state_tensors<const D: usize>(state: &Self::State<D>) -> Vec<(String, TensorData)> {
+        state
+            .momentum
+            .iter()
+            .map(|momentum| ("velocity".into(), momentum.velocity.to_data()))
+            .collect()
+    }
+
+    fn state_mismatch<const D: usize>(
+        state: &Self::State<D>,
+        shape: &[usize],
+    ) -> Option<Vec<usize>> {
+        state
+            .momentum
+            .iter()
+            .map(|momentum| momentum.velocity.dims().to_vec())
+            .find(|dims| dims != shape)
+    }
 
     fn to_device<const D: usize>(mut state: Self::State<D>, device: &B::Device) -> Self::State<D> {
         state.momentum = state.momentum.map(|state| state.to_device(device));
@@ -99,6 +146,7 @@ mod tests {
     use super::*;
     use crate::{
         grad_clipping::GradientClipping,
+        module::Module,
         nn::{Linear, LinearConfig},
         optim::{GradientsParams, Optimizer},
         tensor::{Distribution, Shape},
@@ -155,6 +203,105 @@ mod tests {
         assert_eq!(record.len(), state_restored.len());
     }
 
+    #[test]
+    fn excluded_params_should_not_be_decayed() {
+        let device = Default::default();
+        let layer = layer::<TestAutodiffBackend>(&device);
+        let x = random_tensor::<TestAutodiffBackend>(&device);
+        let mut optim_exclusion = SgdConfig::new()
+            .with_weight_decay(Some(WeightDecayConfig::new(0.5)))
+            .with_weight_decay_exclusion(Some(WeightDecayExclusionConfig::new()))
+            .init();
+        let mut optim_no_decay = SgdConfig::new().init();
+
+        let grads = layer.forward(x.clone()).backward();
+        let grads = GradientsParams::from_grads(grads, &layer);
+        let layer_exclusion = optim_exclusion.step(LEARNING_RATE, layer.clone(), grads);
+        let grads = layer.forward(x).backward();
+        let grads = GradientsParams::from_grads(grads, &layer);
+        let layer_no_decay = optim_no_decay.step(LEARNING_RATE, layer, grads);
+
+        let record_exclusion = layer_exclusion.into_record();
+        let record_no_decay = layer_no_decay.into_record();
+        record_exclusion
+            .bias
+            .unwrap()
+            .to_data()
+            .assert_approx_eq(&record_no_decay.bias.unwrap().to_data(), 5);
+        assert_ne!(
+            record_exclusion.weight.to_data(),
+            record_no_decay.weight.to_data()
+        );
+    }
+
+    #[derive(Module, Debug)]
+    enum Head<B: Backend> {
+        Dense(Linear<B>),
+        Projection(Linear<B>),
+    }
+
+    #[derive(Module, Debug)]
+    struct Paired<B: Backend> {
+        pair: (Linear<B>, Linear<B>),
+        head: Head<B>,
+    }
+
+    impl<B: Backend> Paired<B> {
+        fn forward(&self, x: Tensor<B, 2>) -> Tensor<B, 2> {
+            let x = self.pair.1.forward(self.pair.0.forward(x));
+            match &self.head {
+                Head::Dense(linear) | Head::Projection(linear) => linear.forward(x),
+            }
+        }
+    }
+
+    #[test]
+    fn excluded_params_should_be_matched_by_tuple_index_and_variant() {
+        let device = Default::default();
+        let paired = Paired::<TestAutodiffBackend> {
+            pair: (layer(&device), layer(&device)),
+            head: Head::Dense(layer(&device)),
+        };
+        let x = random_tensor::<TestAutodiffBackend>(&device);
+        let exclusion = WeightDecayExclusionConfig::new()
+            .with_name_patterns(vec!["pair.1.".to_string(), "head.Dense.".to_string()])
+            .with_exclude_1d(false);
+        let mut optim_exclusion = SgdConfig::new()
+            .with_weight_decay(Some(WeightDecayConfig::new(0.5)))
+            .with_weight_decay_exclusion(Some(exclusion))
+            .init();
+        let mut optim_no_decay = SgdConfig::new().init();
+
+        let grads = paired.forward(x.clone()).backward();
+        let grads = GradientsParams::from_grads(grads, &paired);
+        let paired_exclusion = optim_exclusion.step(LEARNING_RATE, paired.clone(), grads);
+        let grads = paired.forward(x).backward();
+        let grads = GradientsParams::from_grads(grads, &paired);
+        let paired_no_decay = optim_no_decay.step(LEARNING_RATE, paired, grads);
+
+        let record_exclusion = paired_exclusion.into_record();
+        let record_no_decay = paired_no_decay.into_record();
+        assert_ne!(
+            record_exclusion.pair.0.weight.to_data(),
+            record_no_decay.pair.0.weight.to_data()
+        );
+        record_exclusion
+            .pair
+            .1
+            .weight
+            .to_data()
+            .assert_approx_eq(&record_no_decay.pair.1.weight.to_data(), 5);
+        let (HeadRecord::Dense(head_exclusion), HeadRecord::Dense(head_no_decay)) =
+            (record_exclusion.head, record_no_decay.head)
+        else {
+            panic!("Expected the dense head");
+        };
+        head_exclusion
+            .weight
+            .to_data()
+            .assert_approx_eq(&head_no_decay.weight.to_data(), 5);
+    }
+
     fn random_tensor<B: Backend>(device: &B::Device) -> Tensor<B, 2> {
         Tensor::<B, 2>::random(Shape::new([2, 20]), Distribution::Default, device)
     }
@@ -173,6 +320,7 @@ mod tests {
                 nesterov: true,
             }),
             gradient_clipping: None,
+            weight_decay_exclusion: None,
         }
         .init()
     }
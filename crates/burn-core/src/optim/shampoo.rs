@@ -0,0 +1,498 @@
+use crate::{
+    self as burn, grad_clipping::GradientClippingConfig, module::AutodiffModule, record::Record,
+    LearningRate,
+};
+
+use super::{
+    decay::{WeightDecay, WeightDecayConfig, WeightDecayExclusionConfig},
+    momentum::{Momentum, MomentumConfig, MomentumState},
+    SimpleOptimizer,
+};
+use crate::config::Config;
+use crate::optim::adaptor::OptimizerAdaptor;
+use crate::tensor::{backend::AutodiffBackend, Tensor, TensorData};
+use burn_tensor::{backend::Backend, ElementConversion};
+
+/// The order of the inverse root applied to each Kronecker factor of the preconditioner.
+const ROOT_ORDER: usize = 4;
+
+/// Shampoo configuration.
+#[derive(Config)]
+pub struct ShampooConfig {
+    /// A value added to the diagonal of the preconditioners for numerical stability.
+    #[config(default = 1e-4)]
+    epsilon: f64,
+    /// The number of steps between two computations of the preconditioners inverse roots.
+    #[config(default = 10)]
+    update_interval: usize,
+    /// Parameters with a dimension larger than this value use a diagonal preconditioner, since
+    /// the matrix preconditioners would be too expensive.
+    #[config(default = 1024)]
+    max_preconditioner_dim: usize,
+    /// The number of iterations used to compute the inverse roots.
+    #[config(default = 15)]
+    root_iterations: usize,
+    /// [Momentum](MomentumConfig) config.
+    momentum: Option<MomentumConfig>,
+    /// [Weight decay](WeightDecayConfig) config.
+    weight_decay: Option<WeightDecayConfig>,
+    /// [Gradient Clipping](GradientClippingConfig) config.
+    grad_clipping: Option<GradientClippingConfig>,
+    /// [Weight decay exclusion](WeightDecayExclusionConfig) config.
+    weight_decay_exclusion: Option<WeightDecayExclusionConfig>,
+}
+
+/// Shampoo optimizer as described in the paper [Shampoo: Preconditioned Stochastic Tensor
+/// Optimization](https://arxiv.org/abs/1802.09568).
+///
+/// The gradient of each parameter with at least two dimensions is reshaped into a
+/// `[dims[0], rest]` matrix, which is preconditioned by the inverse 4th roots of its left and
+/// right Kronecker factors. 1-D parameters and parameters with a dimension larger than
+/// `max_preconditioner_dim` fall back to a diagonal (Adagrad) preconditioner.
+#[derive(Clone)]
+pub struct Shampoo<B: Backend> {
+    epsilon: f64,
+    update_interval: usize,
+    max_preconditioner_dim: usize,
+    root_iterations: usize,
+    momentum: Option<Momentum<B>>,
+    weight_decay: Option<WeightDecay<B>>,
+}
+
+/// Shampoo state.
+#[derive(Record, Clone, new)]
+pub struct ShampooState<B: Backend, const D: usize> {
+    time: usize,
+    preconditioner: ShampooPreconditionerState<B, D>,
+    momentum: Option<MomentumState<B, D>>,
+}
+
+/// State of the preconditioner of a single parameter, either matrix or diagonal.
+#[derive(Record, Clone, new)]
+pub struct ShampooPreconditionerState<B: Backend, const D: usize> {
+    left: Option<Tensor<B, 2>>,
+    right: Option<Tensor<B, 2>>,
+    left_root: Option<Tensor<B, 2>>,
+    right_root: Option<Tensor<B, 2>>,
+    diagonal: Option<Tensor<B, D>>,
+}
+
+impl<B: Backend> SimpleOptimizer<B> for Shampoo<B> {
+    type State<const D: usize> = ShampooState<B, D>;
+
+    fn step<const D: usize>(
+        &self,
+        lr: LearningRate,
+        tensor: Tensor<B, D>,
+        grad: Tensor<B, D>,
+        state: Option<Self::State<D>>,
+    ) -> (Tensor<B, D>, Option<Self::State<D>>) {
+        self.update(lr, tensor, grad, state, self.weight_decay.as_ref())
+    }
+
+    fn step_without_weight_decay<const D: usize>(
+        &self,
+        lr: LearningRate,
+        tensor: Tensor<B, D>,
+        grad: Tensor<B, D>,
+        state: Option<Self::State<D>>,
+    ) -> (Tensor<B, D>, Option<Self::State<D>>) {
+        self.update(lr, tensor, grad, state, None)
+    }
+
+    fn state_tensors<const D: usize>(state: &Self::State<D>) -> Vec<(String, TensorData)> {
+        let preconditioner = &state.preconditioner;
+        let mut tensors = Vec::new();
+        for (name, tensor) in [
+            ("left", &preconditioner.left),
+            ("right", &preconditioner.right),
+            ("left_root", &preconditioner.left_root),
+            ("right_root", &preconditioner.right_root),
+        ] {
+            if let Some(tensor) = tensor {
+                tensors.push((name.into(), tensor.to_data()));
+            }
+        }
+        if let Some(diagonal) = &preconditioner.diagonal {
+            tensors.push(("diagonal".into(), diagonal.to_data()));
+        }
+        if let Some(momentum) = &state.momentum {
+            tensors.push(("velocity".into(), momentum.velocity.to_data()));
+        }
+        tensors
+    }
+
+    fn state_mismatch<const D: usize>(
+        state: &Self::State<D>,
+        shape: &[usize],
+    ) -> Option<Vec<usize>> {
+        // The Kronecker factors are square, with the rows or the columns of the matrix of the
+        // parameter, and the other tensors have its shape.
+        let preconditioner = &state.preconditioner;
+        let rows = shape.first().copied().unwrap_or(1);
+        let cols = shape.iter().skip(1).product();
+        let factors = [
+            (&preconditioner.left, rows),
+            (&preconditioner.right, cols),
+            (&preconditioner.left_root, rows),
+            (&preconditioner.right_root, cols),
+        ];
+        let factors = factors
+            .into_iter()
+            .filter_map(|(tensor, size)| Some((tensor.as_ref()?.dims().to_vec(), vec![size; 2])));
+        let tensors = preconditioner
+            .diagonal
+            .iter()
+            .chain(state.momentum.iter().map(|momentum| &momentum.velocity))
+            .map(|tensor| (tensor.dims().to_vec(), shape.to_vec()));
+
+        factors
+            .chain(tensors)
+            .find_map(|(dims, expected)| (dims != expected).then_some(dims))
+    }
+
+    fn to_device<const D: usize>(
+        mut state: Self::State<D>,
+        device: &<B as Backend>::Device,
+    ) -> Self::State<D> {
+        state.preconditioner = state.preconditioner.to_device(device);
+        state.momentum = state.momentum.map(|state| state.to_device(device));
+        state
+    }
+}
+
+impl<B: Backend> Shampoo<B> {
+    fn update<const D: usize>(
+        &self,
+        lr: LearningRate,
+        tensor: Tensor<B, D>,
+        mut grad: Tensor<B, D>,
+        state: Option<ShampooState<B, D>>,
+        weight_decay: Option<&WeightDecay<B>>,
+    ) -> (Tensor<B, D>, Option<ShampooState<B, D>>) {
+        let (time, preconditioner, state_momentum) = match state {
+            Some(state) => (state.time + 1, state.preconditioner, state.momentum),
+            None => (
+                1,
+                ShampooPreconditionerState::new(None, None, None, None, None),
+                None,
+            ),
+        };
+
+        if let Some(weight_decay) = weight_decay {
+            grad = weight_decay.transform(grad, tensor.clone());
+        }
+
+        let (grad, preconditioner) = match self.matrix_shape(&grad.dims()) {
+            Some(shape) => self.precondition_matrix(grad, shape, preconditioner, time),
+            None => self.precondition_diagonal(grad, preconditioner),
+        };
+
+        let (grad, state_momentum) = match &self.momentum {
+            Some(momentum) => {
+                let (grad, state) = momentum.transform(grad, state_momentum);
+                (grad, Some(state))
+            }
+            None => (grad, None),
+        };
+
+        let state = ShampooState::new(time, preconditioner, state_momentum);
+        let delta = grad.mul_scalar(lr);
+
+        (tensor - delta, Some(state))
+    }
+
+    /// The shape of the matrix used to compute the Kronecker factors, if the parameter isn't
+    /// preconditioned with a diagonal.
+    fn matrix_shape(&self, dims: &[usize]) -> Option<[usize; 2]> {
+        if dims.len() < 2 {
+            return None;
+        }
+
+        let rows = dims[0];
+        let cols = dims[1..].iter().product();
+
+        (rows <= self.max_preconditioner_dim && cols <= self.max_preconditioner_dim)
+            .then_some([rows, cols])
+    }
+
+    fn precondition_matrix<const D: usize>(
+        &self,
+        grad: Tensor<B, D>,
+        shape: [usize; 2],
+        state: ShampooPreconditionerState<B, D>,
+        time: usize,
+    ) -> (Tensor<B, D>, ShampooPreconditionerState<B, D>) {
+        let dims = grad.dims();
+        let grad: Tensor<B, 2> = grad.reshape(shape);
+
+        let left_stats = grad.clone().matmul(grad.clone().transpose());
+        let right_stats = grad.clone().transpose().matmul(grad.clone());
+        let left = match state.left {
+            Some(left) => left.add(left_stats),
+            None => left_stats,
+        };
+        let right = match state.right {
+            Some(right) => right.add(right_stats),
+            None => right_stats,
+        };
+
+        let (left_root, right_root) = match (state.left_root, state.right_root) {
+            (Some(left_root), Some(right_root)) if (time - 1) % self.update_interval != 0 => {
+                (left_root, right_root)
+            }
+            _ => (
+                inverse_root(left.clone(), ROOT_ORDER, self.epsilon, self.root_iterations),
+                inverse_root(
+                    right.clone(),
+                    ROOT_ORDER,
+                    self.epsilon,
+                    self.root_iterations,
+                ),
+            ),
+        };
+
+        let grad = left_root
+            .clone()
+            .matmul(grad)
+            .matmul(right_root.clone())
+            .reshape(dims);
+        let state = ShampooPreconditionerState::new(
+            Some(left),
+            Some(right),
+            Some(left_root),
+            Some(right_root),
+            None,
+        );
+
+        (grad, state)
+    }
+
+    fn precondition_diagonal<const D: usize>(
+        &self,
+        grad: Tensor<B, D>,
+        state: ShampooPreconditionerState<B, D>,
+    ) -> (Tensor<B, D>, ShampooPreconditionerState<B, D>) {
+        let squared = grad.clone().powf_scalar(2.0);
+        let diagonal = match state.diagonal {
+            Some(diagonal) => diagonal.add(squared),
+            None => squared,
+        };
+
+        let grad = grad.div(diagonal.clone().sqrt().add_scalar(self.epsilon));
+        let state = ShampooPreconditionerState::new(None, None, None, None, Some(diagonal));
+
+        (grad, state)
+    }
+}
+
+/// Computes `(matrix + epsilon * I) ^ (-1 / order)` of a symmetric positive semi-definite matrix
+/// using the coupled Newton iteration.
+fn inverse_root<B: Backend>(
+    matrix: Tensor<B, 2>,
+    order: usize,
+    epsilon: f64,
+    iterations: usize,
+) -> Tensor<B, 2> {
+    let [size, _] = matrix.dims();
+    let identity = Tensor::<B, 2>::eye(size, &matrix.device());
+    let matrix = matrix.add(identity.clone().mul_scalar(epsilon));
+
+    let norm = matrix
+        .clone()
+        .powf_scalar(2.0)
+        .sum()
+        .sqrt()
+        .into_scalar()
+        .elem::<f64>();
+    let scale = (1.0 + order as f64) / (2.0 * norm);
+    let alpha = -1.0 / order as f64;
+
+    let mut root = identity.clone().mul_scalar(scale.powf(1.0 / order as f64));
+    let mut m = matrix.mul_scalar(scale);
+
+    for _ in 0..iterations {
+        let m_i = m
+            .clone()
+            .mul_scalar(alpha)
+            .add(identity.clone().mul_scalar(1.0 - alpha));
+        root = root.matmul(m_i.clone());
+        m = (0..order).fold(m, |m, _| m_i.clone().matmul(m));
+    }
+
+    root
+}
+
+impl ShampooConfig {
+    /// Initialize Shampoo optimizer.
+    ///
+    /// # Returns
+    ///
+    /// Returns an optimizer that can be used to optimize a module.
+    pub fn init<B: AutodiffBackend, M: AutodiffModule<B>>(
+        &self,
+    ) -> OptimizerAdaptor<Shampoo<B::InnerBackend>, M, B> {
+        assert!(
+            self.update_interval > 0,
+            "The preconditioner update interval must be at least 1"
+        );
+
+        let optim = Shampoo {
+            epsilon: self.epsilon,
+            update_interval: self.update_interval,
+            max_preconditioner_dim: self.max_preconditioner_dim,
+            root_iterations: self.root_iterations,
+            momentum: self.momentum.as_ref().map(Momentum::new),
+            weight_decay: self.weight_decay.as_ref().map(WeightDecay::new),
+        };
+
+        let mut optim = OptimizerAdaptor::from(optim);
+        if let Some(config) = &self.grad_clipping {
+            optim = optim.with_grad_clipping(config.init());
+        }
+        if let Some(config) = &self.weight_decay_exclusion {
+            optim = optim.with_weight_decay_exclusion(config.init());
+        }
+        optim
+    }
+}
+
+impl<B: Backend, const D: usize> ShampooPreconditionerState<B, D> {
+    /// Move state to device.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - Device to move state to.
+    ///
+    /// # Returns
+    ///
+    /// Returns state moved to device.
+    pub fn to_device(mut self, device: &B::Device) -> Self {
+        self.left = self.left.map(|tensor| tensor.to_device(device));
+        self.right = self.right.map(|tensor| tensor.to_device(device));
+        self.left_root = self.left_root.map(|tensor| tensor.to_device(device));
+        self.right_root = self.right_root.map(|tensor| tensor.to_device(device));
+        self.diagonal = self.diagonal.map(|tensor| tensor.to_device(device));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optim::{GradientsParams, Optimizer};
+    use crate::tensor::{Distribution, TensorData};
+    use crate::{nn, TestAutodiffBackend, TestBackend};
+
+    const LEARNING_RATE: LearningRate = 0.01;
+
+    #[test]
+    fn test_inverse_root() {
+        let matrix =
+            Tensor::<TestBackend, 2>::from_floats([[2.0, 1.0], [1.0, 3.0]], &Default::default());
+
+        inverse_root(matrix, 4, 0.0, 15)
+            .into_data()
+            .assert_approx_eq(
+                &TensorData::from([[0.867793, -0.088206], [-0.088206, 0.779587]]),
+                3,
+            );
+    }
+
+    #[test]
+    fn test_shampoo_optimizer_save_load_state() {
+        let device = Default::default();
+        let linear = nn::LinearConfig::new(6, 6).init(&device);
+        let x = Tensor::<TestAutodiffBackend, 2>::random([2, 6], Distribution::Default, &device);
+        let mut optimizer = ShampooConfig::new()
+            .with_momentum(Some(MomentumConfig::new()))
+            .init();
+        let grads = linear.forward(x).backward();
+        let grads = GradientsParams::from_grads(grads, &linear);
+        let _linear = optimizer.step(LEARNING_RATE, linear, grads);
+
+        let state_optim_before = optimizer.to_record();
+        let state_optim_before_copy = optimizer.to_record();
+        let optimizer = ShampooConfig::new()
+            .with_momentum(Some(MomentumConfig::new()))
+            .init::<TestAutodiffBackend, nn::Linear<TestAutodiffBackend>>();
+        let optimizer = optimizer.load_record(state_optim_before_copy);
+        let state_optim_after = optimizer.to_record();
+
+        assert_eq!(state_optim_before.len(), state_optim_after.len());
+    }
+
+    #[test]
+    fn test_shampoo_optimizer_load_record_by_path() {
+        let device = Default::default();
+        // Preconditioned by factors of different sizes, `[6, 6]` and `[4, 4]`
+        let linear = nn::LinearConfig::new(6, 4).init(&device);
+        let x = Tensor::<TestAutodiffBackend, 2>::random([2, 6], Distribution::Default, &device);
+        let mut optimizer = create_shampoo();
+        let grads = linear.forward(x.clone()).backward();
+        let grads = GradientsParams::from_grads(grads, &linear);
+        let linear = optimizer.step(LEARNING_RATE, linear, grads);
+        let record = optimizer.to_record_by_path(&linear);
+
+        let linear_same = linear.clone();
+        let (mut optimizer_same, report) =
+            create_shampoo().load_record_by_path(&linear_same, record.clone());
+        assert!(report.is_exact());
+        assert_eq!(
+            optimizer_same.export_states(&linear_same),
+            optimizer.export_states(&linear)
+        );
+
+        // The loaded states are used by the next step
+        let grads = linear.forward(x.clone()).backward();
+        let grads = GradientsParams::from_grads(grads, &linear);
+        let linear = optimizer.step(LEARNING_RATE, linear, grads);
+        let grads = linear_same.forward(x).backward();
+        let grads = GradientsParams::from_grads(grads, &linear_same);
+        let linear_same = optimizer_same.step(LEARNING_RATE, linear_same, grads);
+        linear_same
+            .weight
+            .val()
+            .into_data()
+            .assert_approx_eq(&linear.weight.val().into_data(), 5);
+
+        let linear_resized = nn::LinearConfig::new(6, 5).init::<TestAutodiffBackend>(&device);
+        let (optimizer_resized, report) =
+            create_shampoo().load_record_by_path(&linear_resized, record);
+        assert!(optimizer_resized.state_ids().is_empty());
+        let mismatched = report
+            .mismatched
+            .iter()
+            .map(|mismatch| (mismatch.path.as_str(), mismatch.actual.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(mismatched, [("weight", vec![4, 4]), ("bias", vec![4])]);
+    }
+
+    type ShampooAdaptor = OptimizerAdaptor<
+        Shampoo<TestBackend>,
+        nn::Linear<TestAutodiffBackend>,
+        TestAutodiffBackend,
+    >;
+
+    fn create_shampoo() -> ShampooAdaptor {
+        ShampooConfig::new()
+            .with_momentum(Some(MomentumConfig::new()))
+            .init()
+    }
+
+    #[test]
+    fn test_shampoo_diagonal_fallback() {
+        let shampoo = Shampoo::<TestBackend> {
+            epsilon: 1e-4,
+            update_interval: 1,
+            max_preconditioner_dim: 4,
+            root_iterations: 15,
+            momentum: None,
+            weight_decay: None,
+        };
+
+        assert_eq!(shampoo.matrix_shape(&[4, 2, 2]), Some([4, 4]));
+        assert_eq!(shampoo.matrix_shape(&[4, 5]), None);
+        assert_eq!(shampoo.matrix_shape(&[4]), None);
+    }
+}
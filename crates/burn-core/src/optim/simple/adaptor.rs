@@ -1,11 +1,11 @@
 use super::{record::AdaptorRecord, SimpleOptimizer};
 use crate::{
     grad_clipping::GradientClipping,
-    module::{AutodiffModule, ModuleMapper, ParamId},
-    optim::{GradientsParams, Optimizer},
+    module::{AutodiffModule, LoadReport, ModuleMapper, ModuleVisitor, ParamId, ShapeMismatch},
+    optim::{decay::WeightDecayExclusion, GradientsParams, Optimizer},
     LearningRate,
 };
-use burn_tensor::{backend::AutodiffBackend, Tensor};
+use burn_tensor::{backend::AutodiffBackend, Tensor, TensorData};
 use core::marker::PhantomData;
 use hashbrown::HashMap;
 
@@ -22,6 +22,7 @@ where
     records: HashMap<ParamId, AdaptorRecord<O, B>>,
     module: PhantomData<M>,
     grad_clipping: Option<GradientClipping>,
+    weight_decay_exclusion: Option<WeightDecayExclusion>,
 }
 
 impl<O, B, M> From<O> for OptimizerAdaptor<O, M, B>
@@ -36,6 +37,7 @@ where
             records: HashMap::new(),
             module: PhantomData,
             grad_clipping: None,
+            weight_decay_exclusion: None,
         }
     }
 }
@@ -60,6 +62,159 @@ where
         self
     }
 
+    /// Sets the rules excluding parameters from weight decay.
+    ///
+    /// # Arguments
+    ///
+    /// * `exclusion` - The weight decay exclusion.
+    ///
+    /// # Returns
+    ///
+    /// The optimizer.
+    pub fn with_weight_decay_exclusion(mut self, exclusion: WeightDecayExclusion) -> Self {
+        self.weight_decay_exclusion = Some(exclusion);
+        self
+    }
+
+    /// The ids of the parameters having an optimizer state.
+    pub fn state_ids(&self) -> Vec<ParamId> {
+        self.records.keys().copied().collect()
+    }
+
+    /// Exports the optimizer state of a parameter.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The parameter id.
+    ///
+    /// # Returns
+    ///
+    /// The [state tensors](SimpleOptimizer::state_tensors) with their names, or `None` if the
+    /// parameter has no state yet.
+    pub fn export_state(&self, id: &ParamId) -> Option<Vec<(String, TensorData)>> {
+        self.records.get(id).map(AdaptorRecord::state_tensors)
+    }
+
+    /// Exports the optimizer state of every parameter of the module, keyed by the parameter
+    /// path (e.g. `layers.0.weight`).
+    ///
+    /// # Arguments
+    ///
+    /// * `module` - The module optimized.
+    ///
+    /// # Returns
+    ///
+    /// The state tensors of each parameter having a state, in the module order.
+    pub fn export_states(&self, module: &M) -> Vec<(String, Vec<(String, TensorData)>)> {
+        param_paths::<M, B>(module)
+            .into_iter()
+            .filter_map(|(path, id, _)| Some((path, self.export_state(&id)?)))
+            .collect()
+    }
+
+    /// Converts the optimizer state to a record keyed by the parameter path (e.g.
+    /// `layers.0.weight`) instead of the parameter id.
+    ///
+    /// The record can be saved apart from the module, so it can be left out of a deployment, and
+    /// loaded with [load_record_by_path](Self::load_record_by_path) to resume the training of a
+    /// module whose structure changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `module` - The module optimized.
+    ///
+    /// # Returns
+    ///
+    /// The state of each parameter having a state, by path.
+    pub fn to_record_by_path(&self, module: &M) -> HashMap<String, AdaptorRecord<O, B>> {
+        param_paths::<M, B>(module)
+            .into_iter()
+            .filter_map(|(path, id, _)| Some((path, self.records.get(&id)?.clone())))
+            .collect()
+    }
+
+    /// Loads the optimizer state of the parameters from a record keyed by the parameter path,
+    /// see [to_record_by_path](Self::to_record_by_path).
+    ///
+    /// The parameters of the module without a state in the record start without a state. The
+    /// states which [don't fit](SimpleOptimizer::state_mismatch) the shape of their parameter are
+    /// skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `module` - The module optimized.
+    /// * `record` - The record to load the states from.
+    ///
+    /// # Returns
+    ///
+    /// The optimizer, and the report of the parameters without a state in the record, of the
+    /// states without a parameter in the module, and of the states skipped.
+    pub fn load_record_by_path(
+        mut self,
+        module: &M,
+        mut record: HashMap<String, AdaptorRecord<O, B>>,
+    ) -> (Self, LoadReport) {
+        let mut report = LoadReport::default();
+
+        for (path, id, shape) in param_paths::<M, B>(module) {
+            self.records.remove(&id);
+
+            let Some(state) = record.remove(&path) else {
+                report.missing.push(path);
+                continue;
+            };
+            match state.state_mismatch(&shape) {
+                Some(actual) => report.mismatched.push(ShapeMismatch {
+                    path,
+                    expected: shape,
+                    actual,
+                }),
+                None => {
+                    self.records.insert(id, state);
+                }
+            }
+        }
+
+        report.unexpected = record.into_keys().collect();
+        report.unexpected.sort();
+        report.missing.sort();
+
+        (self, report)
+    }
+
+    /// Loads the optimizer state of the parameters whose path matches the predicate, keeping
+    /// the current state of the others.
+    ///
+    /// # Arguments
+    ///
+    /// * `module` - The module optimized.
+    /// * `record` - The record to load the states from.
+    /// * `predicate` - Whether to load the state of the parameter with the given path.
+    ///
+    /// # Returns
+    ///
+    /// The optimizer.
+    pub fn load_record_filtered<F>(
+        mut self,
+        module: &M,
+        mut record: HashMap<ParamId, AdaptorRecord<O, B>>,
+        predicate: F,
+    ) -> Self
+    where
+        F: Fn(&str) -> bool,
+    {
+        for (path, id, _) in param_paths::<M, B>(module) {
+            if !predicate(&path) {
+                continue;
+            }
+            match record.remove(&id) {
+                Some(state) => self.records.insert(id, state),
+                None => self.records.remove(&id),
+            };
+        }
+        self
+    }
+
     #[cfg(test)]
     pub(crate) fn has_gradient_clipping(&self) -> bool {
         self.grad_clipping.is_some()
@@ -81,6 +236,7 @@ where
             &mut grads,
             lr,
             self.grad_clipping.as_ref(),
+            self.weight_decay_exclusion.as_ref(),
         );
         module.map(&mut mapper)
     }
@@ -95,6 +251,36 @@ where
     }
 }
 
+/// The path, the id and the shape of the float parameters of the module.
+fn param_paths<M: AutodiffModule<B>, B: AutodiffBackend>(
+    module: &M,
+) -> Vec<(String, ParamId, Vec<usize>)> {
+    let mut visitor = ParamPathsVisitor::default();
+    module.visit(&mut visitor);
+    visitor.paths
+}
+
+#[derive(Default)]
+struct ParamPathsVisitor {
+    path: Vec<String>,
+    paths: Vec<(String, ParamId, Vec<usize>)>,
+}
+
+impl<B: AutodiffBackend> ModuleVisitor<B> for ParamPathsVisitor {
+    fn enter_module(&mut self, name: &str) {
+        self.path.push(name.into());
+    }
+
+    fn exit_module(&mut self, _name: &str) {
+        self.path.pop();
+    }
+
+    fn visit_float<const D: usize>(&mut self, id: ParamId, tensor: &Tensor<B, D>) {
+        self.paths
+            .push((self.path.join("."), id, tensor.dims().to_vec()));
+    }
+}
+
 #[derive(new)]
 struct SimpleOptimizerMapper<'a, M, B, O>
 where
@@ -108,6 +294,9 @@ where
     lr: LearningRate,
     phantom: PhantomData<M>,
     grad_clipping: Option<&'a GradientClipping>,
+    weight_decay_exclusion: Option<&'a WeightDecayExclusion>,
+    #[new(default)]
+    path: Vec<String>,
 }
 
 impl<'a, M, B, O> ModuleMapper<B> for SimpleOptimizerMapper<'a, M, B, O>
@@ -116,6 +305,14 @@ where
     B: AutodiffBackend,
     O: SimpleOptimizer<B::InnerBackend>,
 {
+    fn enter_module(&mut self, name: &str) {
+        self.path.push(name.into());
+    }
+
+    fn exit_module(&mut self, _name: &str) {
+        self.path.pop();
+    }
+
     fn map_float<const D: usize>(&mut self, id: ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
         let grad = self.grads.remove(id);
 
@@ -130,12 +327,22 @@ where
                 grad
             };
 
-            let (tensor, state) = self.optimizer.step(
-                self.lr,
-                tensor.inner(),
-                clipped_grad,
-                record.map(|record| O::to_device(record.into_state(), &device)),
-            );
+            let state = record.map(|record| O::to_device(record.into_state(), &device));
+            let is_excluded = self
+                .weight_decay_exclusion
+                .is_some_and(|exclusion| exclusion.is_excluded(&self.path.join("."), D));
+
+            let (tensor, state) = if is_excluded {
+                self.optimizer.step_without_weight_decay(
+                    self.lr,
+                    tensor.inner(),
+                    clipped_grad,
+                    state,
+                )
+            } else {
+                self.optimizer
+                    .step(self.lr, tensor.inner(), clipped_grad, state)
+            };
 
             if let Some(state) = state {
                 self.records
@@ -1,5 +1,5 @@
 use crate::{record::Record, LearningRate};
-use burn_tensor::{backend::Backend, Tensor};
+use burn_tensor::{backend::Backend, Tensor, TensorData};
 
 /// Simple optimizer is an opinionated trait to simplify the process of implementing an
 /// optimizer.
@@ -25,6 +25,47 @@ where
         state: Option<Self::State<D>>,
     ) -> (Tensor<B, D>, Option<Self::State<D>>);
 
+    /// Same as [step](SimpleOptimizer::step), but for a parameter that is
+    /// [excluded from weight decay](crate::optim::decay::WeightDecayExclusion).
+    ///
+    /// Optimizers without weight decay can rely on the default implementation, which simply
+    /// calls [step](SimpleOptimizer::step).
+    fn step_without_weight_decay<const D: usize>(
+        &self,
+        lr: LearningRate,
+        tensor: Tensor<B, D>,
+        grad: Tensor<B, D>,
+        state: Option<Self::State<D>>,
+    ) -> (Tensor<B, D>, Option<Self::State<D>>) {
+        self.step(lr, tensor, grad, state)
+    }
+
+    /// Export the tensors of the state, named after the quantity they hold (e.g. `moment_1`
+    /// for the first moment of Adam), so that they can be inspected.
+    ///
+    /// Optimizers without tensors in their state can rely on the default implementation, which
+    /// exports nothing.
+    fn state_tensors<const D: usize>(_state: &Self::State<D>) -> Vec<(String, TensorData)> {
+        Vec::new()
+    }
+
+    /// Checks the state against the shape of a parameter of the same rank, returning the shape
+    /// of a tensor of the state which doesn't fit the parameter, e.g. after the module changed.
+    ///
+    /// The default implementation expects all the
+    /// [tensors of the state](SimpleOptimizer::state_tensors) to have the shape of the parameter,
+    /// reading their data. Optimizers should rather compare the dimensions of their tensors,
+    /// which may have other shapes than the parameter.
+    fn state_mismatch<const D: usize>(
+        state: &Self::State<D>,
+        shape: &[usize],
+    ) -> Option<Vec<usize>> {
+        Self::state_tensors(state)
+            .into_iter()
+            .map(|(_, data)| data.shape)
+            .find(|dims| dims != shape)
+    }
+
     /// Change the device of the state.
     ///
     /// This function will be called accordindly to have the state on the same device as the
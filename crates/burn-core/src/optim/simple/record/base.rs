@@ -3,7 +3,7 @@ use crate::{
     optim::SimpleOptimizer,
     record::{PrecisionSettings, Record},
 };
-use burn_tensor::backend::AutodiffBackend;
+use burn_tensor::{backend::AutodiffBackend, TensorData};
 use serde::{Deserialize, Serialize};
 
 /// [Optimizer adaptor](crate::optim::simple::adaptor::OptimizerAdaptor) record.
@@ -90,4 +90,27 @@ where
     pub fn from_state<const D: usize>(state: O::State<D>) -> Self {
         Self::V1(AdaptorRecordV1::from_state(state))
     }
+
+    /// Exports the tensors of the optimizer state.
+    ///
+    /// # Returns
+    ///
+    /// The [state tensors](SimpleOptimizer::state_tensors) with their names.
+    pub fn state_tensors(&self) -> Vec<(String, TensorData)> {
+        match self {
+            AdaptorRecord::V1(record) => record.state_tensors(),
+        }
+    }
+
+    /// Checks the optimizer state against the shape of a parameter.
+    ///
+    /// # Returns
+    ///
+    /// The shape of a tensor of the [state](SimpleOptimizer::state_mismatch) which doesn't fit
+    /// the parameter, if any.
+    pub fn state_mismatch(&self, shape: &[usize]) -> Option<Vec<usize>> {
+        match self {
+            AdaptorRecord::V1(record) => record.state_mismatch(shape),
+        }
+    }
 }
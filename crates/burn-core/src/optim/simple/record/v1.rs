@@ -2,7 +2,7 @@ use crate::{
     optim::SimpleOptimizer,
     record::{PrecisionSettings, Record},
 };
-use burn_tensor::backend::Backend;
+use burn_tensor::{backend::Backend, TensorData};
 use core::any::Any;
 use serde::{Deserialize, Serialize};
 
@@ -141,6 +141,62 @@ where
             _ => panic!("Unsupported state dimension, dimension up to 8 are supported."),
         }
     }
+
+    /// Export the tensors of the state.
+    ///
+    /// # Returns
+    ///
+    /// The [state tensors](SimpleOptimizer::state_tensors) with their names.
+    pub fn state_tensors(&self) -> Vec<(String, TensorData)> {
+        match self {
+            AdaptorRecordV1::Rank0(s) => O::state_tensors(s),
+            AdaptorRecordV1::Rank1(s) => O::state_tensors(s),
+            AdaptorRecordV1::Rank2(s) => O::state_tensors(s),
+            AdaptorRecordV1::Rank3(s) => O::state_tensors(s),
+            AdaptorRecordV1::Rank4(s) => O::state_tensors(s),
+            AdaptorRecordV1::Rank5(s) => O::state_tensors(s),
+            AdaptorRecordV1::Rank6(s) => O::state_tensors(s),
+            AdaptorRecordV1::Rank7(s) => O::state_tensors(s),
+            AdaptorRecordV1::Rank8(s) => O::state_tensors(s),
+        }
+    }
+
+    /// Checks the state against the shape of a parameter.
+    ///
+    /// # Returns
+    ///
+    /// The shape of a tensor of the state which doesn't fit the parameter, if any.
+    pub fn state_mismatch(&self, shape: &[usize]) -> Option<Vec<usize>> {
+        match self {
+            AdaptorRecordV1::Rank0(s) => state_mismatch::<O, B, 0>(s, shape),
+            AdaptorRecordV1::Rank1(s) => state_mismatch::<O, B, 1>(s, shape),
+            AdaptorRecordV1::Rank2(s) => state_mismatch::<O, B, 2>(s, shape),
+            AdaptorRecordV1::Rank3(s) => state_mismatch::<O, B, 3>(s, shape),
+            AdaptorRecordV1::Rank4(s) => state_mismatch::<O, B, 4>(s, shape),
+            AdaptorRecordV1::Rank5(s) => state_mismatch::<O, B, 5>(s, shape),
+            AdaptorRecordV1::Rank6(s) => state_mismatch::<O, B, 6>(s, shape),
+            AdaptorRecordV1::Rank7(s) => state_mismatch::<O, B, 7>(s, shape),
+            AdaptorRecordV1::Rank8(s) => state_mismatch::<O, B, 8>(s, shape),
+        }
+    }
+}
+
+fn state_mismatch<O: SimpleOptimizer<B>, B: Backend, const D: usize>(
+    state: &O::State<D>,
+    shape: &[usize],
+) -> Option<Vec<usize>> {
+    if D == shape.len() {
+        return O::state_mismatch(state, shape);
+    }
+
+    // The state of a parameter of another rank, whose shape is only known from its tensors.
+    let tensors = O::state_tensors(state);
+    Some(
+        tensors
+            .into_iter()
+            .next()
+            .map_or_else(Vec::new, |(_, data)| data.shape),
+    )
 }
 
 impl<O, B> Record<B> for AdaptorRecordV1<O, B>
@@ -18,6 +18,13 @@ pub struct GradientsParamsChangeDevice<'a, M: AutodiffModule<B>, B: AutodiffBack
     phatom: PhantomData<M>,
 }
 
+#[derive(new)]
+pub struct GradientsParamsScale<'a, M: AutodiffModule<B>, B: AutodiffBackend> {
+    factor: f64,
+    grads: &'a mut GradientsParams,
+    phatom: PhantomData<(M, B)>,
+}
+
 impl<'a, B, M> ModuleVisitor<B> for GradientsParamsConverter<'a, M, B>
 where
     B: AutodiffBackend,
@@ -51,3 +58,18 @@ where
             .register::<B::InnerBackend, D>(id, grad.to_device(self.device));
     }
 }
+
+impl<'a, B, M> ModuleVisitor<B> for GradientsParamsScale<'a, M, B>
+where
+    B: AutodiffBackend,
+    M: AutodiffModule<B>,
+{
+    fn visit_float<const D: usize>(&mut self, id: ParamId, _tensor: &Tensor<B, D>) {
+        let Some(grad) = self.grads.remove::<B::InnerBackend, D>(id) else {
+            return;
+        };
+
+        self.grads
+            .register::<B::InnerBackend, D>(id, grad.mul_scalar(self.factor));
+    }
+}
@@ -0,0 +1,232 @@
+use super::{read_params, ParamMap, RecorderError};
+use burn_tensor::{DType, TensorData};
+use core::fmt;
+use std::path::PathBuf;
+
+/// The differences between the parameters of two records, by path, e.g. `encoder.linear.weight`.
+///
+/// # Example
+///
+/// ```rust, ignore
+/// let diff = RecordDiff::from_files("checkpoint-1", "checkpoint-2")?;
+///
+/// for tensor in diff.changed() {
+///     println!("{}: {:?}", tensor.path, tensor.max_abs_delta);
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RecordDiff {
+    /// The parameters of the first record missing from the second one.
+    pub removed: Vec<String>,
+    /// The parameters of the second record missing from the first one.
+    pub added: Vec<String>,
+    /// The parameters of both records, sorted by path.
+    pub tensors: Vec<TensorDiff>,
+}
+
+/// The differences between a parameter of two records.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TensorDiff {
+    /// The path of the parameter.
+    pub path: String,
+    /// The data type of the parameter in the first and the second record.
+    pub dtype: (DType, DType),
+    /// The shape of the parameter in the first and the second record.
+    pub shape: (Vec<usize>, Vec<usize>),
+    /// The maximum absolute difference between the values, if they can be compared.
+    ///
+    /// The values can't be compared if the shapes differ or if a parameter is quantized, unless
+    /// the quantized parameters are identical.
+    pub max_abs_delta: Option<f64>,
+    /// The mean absolute difference between the values, if they can be compared.
+    pub mean_abs_delta: Option<f64>,
+}
+
+impl RecordDiff {
+    /// Compares the parameters of two records.
+    pub fn new(first: &ParamMap, second: &ParamMap) -> Self {
+        let mut diff = Self::default();
+
+        for (path, data) in first.iter() {
+            match second.get(path) {
+                Some(other) => diff.tensors.push(TensorDiff::new(path, data, other)),
+                None => diff.removed.push(path.clone()),
+            }
+        }
+        diff.added = second
+            .keys()
+            .filter(|path| !first.contains_key(*path))
+            .cloned()
+            .collect();
+
+        diff.removed.sort();
+        diff.added.sort();
+        diff.tensors.sort_by(|a, b| a.path.cmp(&b.path));
+        diff
+    }
+
+    /// Compares the parameters of two [named msgpack](super::NamedMpkFileRecorder) files.
+    pub fn from_files(
+        first: impl Into<PathBuf>,
+        second: impl Into<PathBuf>,
+    ) -> Result<Self, RecorderError> {
+        let (_, first) = read_params(first)?;
+        let (_, second) = read_params(second)?;
+
+        Ok(Self::new(&first, &second))
+    }
+
+    /// The parameters of both records which differ.
+    pub fn changed(&self) -> impl Iterator<Item = &TensorDiff> {
+        self.tensors.iter().filter(|tensor| tensor.is_changed())
+    }
+
+    /// Whether both records have the same parameters with the same values.
+    pub fn is_identical(&self) -> bool {
+        self.removed.is_empty() && self.added.is_empty() && self.changed().next().is_none()
+    }
+}
+
+impl TensorDiff {
+    fn new(path: &str, first: &TensorData, second: &TensorData) -> Self {
+        let is_comparable = first.shape == second.shape
+            && !matches!(first.dtype, DType::QFloat(_))
+            && !matches!(second.dtype, DType::QFloat(_));
+
+        let (max_abs_delta, mean_abs_delta) = match is_comparable {
+            true => {
+                let (max, sum) = first
+                    .iter::<f64>()
+                    .zip(second.iter::<f64>())
+                    .map(|(a, b)| (a - b).abs())
+                    .fold((0.0_f64, 0.0), |(max, sum), delta| {
+                        // `f64::max` ignores NaN, which must be reported as a change
+                        let max = match delta.is_nan() || max.is_nan() {
+                            true => f64::NAN,
+                            false => max.max(delta),
+                        };
+                        (max, sum + delta)
+                    });
+                let mean = sum / first.num_elements().max(1) as f64;
+
+                (Some(max), Some(mean))
+            }
+            // Identical quantized parameters are the only ones known to be unchanged
+            false
+                if first.dtype == second.dtype
+                    && first.shape == second.shape
+                    && first.as_bytes() == second.as_bytes() =>
+            {
+                (Some(0.0), Some(0.0))
+            }
+            false => (None, None),
+        };
+
+        Self {
+            path: path.to_string(),
+            dtype: (first.dtype, second.dtype),
+            shape: (first.shape.clone(), second.shape.clone()),
+            max_abs_delta,
+            mean_abs_delta,
+        }
+    }
+
+    /// Whether the data type, the shape or the values of the parameter differ.
+    ///
+    /// Quantized parameters are compared by bytes, and NaN values are always changed.
+    pub fn is_changed(&self) -> bool {
+        if self.dtype.0 != self.dtype.1 || self.shape.0 != self.shape.1 {
+            return true;
+        }
+
+        self.max_abs_delta
+            .map_or(true, |delta| delta != 0.0)
+    }
+}
+
+impl fmt::Display for RecordDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_identical() {
+            return f.write_str("The records are identical");
+        }
+
+        for path in self.removed.iter() {
+            writeln!(f, "Removed: {path}")?;
+        }
+        for path in self.added.iter() {
+            writeln!(f, "Added: {path}")?;
+        }
+        for tensor in self.changed() {
+            writeln!(f, "{tensor}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for TensorDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Changed: {}", self.path)?;
+
+        if self.dtype.0 != self.dtype.1 {
+            write!(f, " dtype {:?} -> {:?}", self.dtype.0, self.dtype.1)?;
+        }
+        if self.shape.0 != self.shape.1 {
+            write!(f, " shape {:?} -> {:?}", self.shape.0, self.shape.1)?;
+        }
+        if let (Some(max), Some(mean)) = (self.max_abs_delta, self.mean_abs_delta) {
+            write!(f, " max abs delta {max:e}, mean abs delta {mean:e}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_reports_the_differences() {
+        let mut first = ParamMap::new();
+        first.insert("a".to_string(), TensorData::new(vec![1.0f32, 2.0], [2]));
+        first.insert("b".to_string(), TensorData::new(vec![1.0f32, 2.0], [2]));
+        first.insert("c".to_string(), TensorData::new(vec![1.0f32, 2.0], [2]));
+        first.insert("removed".to_string(), TensorData::new(vec![1.0f32], [1]));
+        let mut second = ParamMap::new();
+        second.insert("a".to_string(), TensorData::new(vec![1.0f32, 2.0], [2]));
+        second.insert("b".to_string(), TensorData::new(vec![2.0f32, 2.5], [2]));
+        second.insert("c".to_string(), TensorData::new(vec![1.0f64, 2.0], [1, 2]));
+        second.insert("added".to_string(), TensorData::new(vec![1.0f32], [1]));
+
+        let diff = RecordDiff::new(&first, &second);
+
+        assert_eq!(diff.removed, ["removed"]);
+        assert_eq!(diff.added, ["added"]);
+        let changed = diff.changed().collect::<Vec<_>>();
+        assert_eq!(changed.len(), 2);
+        assert_eq!(changed[0].path, "b");
+        assert_eq!(changed[0].max_abs_delta, Some(1.0));
+        assert_eq!(changed[0].mean_abs_delta, Some(0.75));
+        assert_eq!(changed[1].path, "c");
+        assert_eq!(changed[1].dtype, (DType::F32, DType::F64));
+        assert_eq!(changed[1].max_abs_delta, None);
+        assert!(!diff.is_identical());
+    }
+
+    #[test]
+    fn test_diff_reports_the_nan_values() {
+        let mut first = ParamMap::new();
+        first.insert("a".to_string(), TensorData::new(vec![1.0f32, 2.0], [2]));
+        let mut second = ParamMap::new();
+        second.insert("a".to_string(), TensorData::new(vec![f32::NAN, 2.0], [2]));
+
+        let diff = RecordDiff::new(&first, &second);
+
+        let tensor = &diff.tensors[0];
+        assert!(tensor.max_abs_delta.unwrap().is_nan());
+        assert!(tensor.mean_abs_delta.unwrap().is_nan());
+        assert!(tensor.is_changed());
+        assert!(!diff.is_identical());
+    }
+}
@@ -0,0 +1,199 @@
+use super::{BytesRecorder, FileRecorder, Recorder, RecorderError};
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    Aes256Gcm, Key, Nonce,
+};
+use burn_tensor::backend::Backend;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{path::PathBuf, sync::Arc};
+
+/// The size of the nonce written before the encrypted record.
+const NONCE_SIZE: usize = 12;
+
+/// The function providing the 256-bit encryption key, e.g. from a keyring.
+pub type KeyProvider = Arc<dyn Fn() -> Result<[u8; 32], String> + Send + Sync>;
+
+/// File recorder encrypting the bytes of another [recorder](BytesRecorder) with AES-256-GCM.
+///
+/// The file holds a random nonce followed by the encrypted record, whose authentication tag
+/// makes the loading fail if the file was modified or the key is wrong.
+///
+/// # Example
+///
+/// ```rust, ignore
+/// let recorder = BinBytesRecorder::<FullPrecisionSettings>::new();
+/// let recorder = EncryptedFileRecorder::new(recorder, key);
+/// model.save_file("model", &recorder)?;
+/// ```
+#[derive(Clone)]
+pub struct EncryptedFileRecorder<R> {
+    recorder: R,
+    key: Option<KeyProvider>,
+}
+
+impl<R> EncryptedFileRecorder<R> {
+    /// Creates a recorder encrypting the records of `recorder` with the key.
+    pub fn new(recorder: R, key: [u8; 32]) -> Self {
+        Self::with_key_provider(recorder, move || Ok(key))
+    }
+
+    /// Creates a recorder encrypting the records of `recorder` with the key returned by
+    /// `provider`, which is called each time a record is saved or loaded.
+    pub fn with_key_provider<F>(recorder: R, provider: F) -> Self
+    where
+        F: Fn() -> Result<[u8; 32], String> + Send + Sync + 'static,
+    {
+        Self {
+            recorder,
+            key: Some(Arc::new(provider)),
+        }
+    }
+
+    fn cipher(&self) -> Result<Aes256Gcm, RecorderError> {
+        let provider = self.key.as_ref().ok_or_else(|| {
+            RecorderError::Unknown("No key for the encrypted recorder".to_string())
+        })?;
+        let key = provider().map_err(RecorderError::Unknown)?;
+
+        Ok(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)))
+    }
+}
+
+/// The recorder has no key by default, saving or loading a record fails.
+impl<R: Default> Default for EncryptedFileRecorder<R> {
+    fn default() -> Self {
+        Self {
+            recorder: R::default(),
+            key: None,
+        }
+    }
+}
+
+impl<R: core::fmt::Debug> core::fmt::Debug for EncryptedFileRecorder<R> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // The key is never printed
+        f.debug_struct("EncryptedFileRecorder")
+            .field("recorder", &self.recorder)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R: BytesRecorder<B>, B: Backend> FileRecorder<B> for EncryptedFileRecorder<R> {
+    fn file_extension() -> &'static str {
+        "enc"
+    }
+}
+
+impl<R: BytesRecorder<B>, B: Backend> Recorder<B> for EncryptedFileRecorder<R> {
+    type Settings = R::Settings;
+    type RecordArgs = PathBuf;
+    type RecordOutput = ();
+    type LoadArgs = PathBuf;
+
+    fn save_item<I: Serialize>(
+        &self,
+        item: I,
+        mut file: Self::RecordArgs,
+    ) -> Result<(), RecorderError> {
+        let cipher = self.cipher()?;
+        let bytes = self.recorder.save_item(item, ())?;
+
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let encrypted = cipher
+            .encrypt(&nonce, bytes.as_slice())
+            .map_err(|err| RecorderError::Unknown(format!("Encryption failed: {err}")))?;
+
+        file.set_extension(<Self as FileRecorder<B>>::file_extension());
+        if let Some(parent) = file.parent() {
+            std::fs::create_dir_all(parent).ok();
+        }
+
+        let mut content = nonce.to_vec();
+        content.extend(encrypted);
+        std::fs::write(&file, content).map_err(|err| RecorderError::Unknown(err.to_string()))
+    }
+
+    fn load_item<I: DeserializeOwned>(&self, mut file: Self::LoadArgs) -> Result<I, RecorderError> {
+        let cipher = self.cipher()?;
+
+        file.set_extension(<Self as FileRecorder<B>>::file_extension());
+        let content = std::fs::read(&file).map_err(|err| match err.kind() {
+            std::io::ErrorKind::NotFound => RecorderError::FileNotFound(err.to_string()),
+            _ => RecorderError::Unknown(err.to_string()),
+        })?;
+
+        if content.len() < NONCE_SIZE {
+            return Err(RecorderError::Unknown(
+                "The encrypted record is truncated".to_string(),
+            ));
+        }
+        let (nonce, encrypted) = content.split_at(NONCE_SIZE);
+        let bytes = cipher
+            .decrypt(Nonce::from_slice(nonce), encrypted)
+            .map_err(|_| {
+                RecorderError::Unknown(
+                    "Decryption failed, the key is wrong or the file was modified".to_string(),
+                )
+            })?;
+
+        self.recorder.load_item(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        module::Module,
+        nn::{Linear, LinearConfig},
+        record::{BinBytesRecorder, FullPrecisionSettings},
+        TestBackend,
+    };
+
+    type TestRecorder = EncryptedFileRecorder<BinBytesRecorder<FullPrecisionSettings>>;
+
+    #[test]
+    fn test_can_save_and_load_encrypted_record() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("model");
+        let device = Default::default();
+        let recorder = TestRecorder::new(BinBytesRecorder::new(), [7; 32]);
+
+        let model_before: Linear<TestBackend> = LinearConfig::new(4, 4).init(&device);
+        model_before
+            .clone()
+            .save_file(file.clone(), &recorder)
+            .unwrap();
+        let model_after = LinearConfig::new(4, 4)
+            .init(&device)
+            .load_file(file, &recorder, &device)
+            .unwrap();
+
+        assert_eq!(model_after.weight.to_data(), model_before.weight.to_data());
+    }
+
+    #[test]
+    fn test_loading_with_the_wrong_key_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("model");
+        let device = Default::default();
+
+        let model: Linear<TestBackend> = LinearConfig::new(4, 4).init(&device);
+        model
+            .save_file(
+                file.clone(),
+                &TestRecorder::new(BinBytesRecorder::new(), [7; 32]),
+            )
+            .unwrap();
+
+        let result = LinearConfig::new(4, 4)
+            .init::<TestBackend>(&device)
+            .load_file(
+                file,
+                &TestRecorder::new(BinBytesRecorder::new(), [8; 32]),
+                &device,
+            );
+
+        assert!(result.is_err());
+    }
+}
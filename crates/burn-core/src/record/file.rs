@@ -23,12 +23,96 @@ pub struct BinFileRecorder<S: PrecisionSettings> {
     _settings: PhantomData<S>,
 }
 
+/// The default size of the chunks of the encoded records given to the compressors, 1 MiB.
+pub const DEFAULT_COMPRESSION_CHUNK_SIZE: usize = 1024 * 1024;
+
 /// File recorder using the [bincode format](bincode) compressed with gzip.
-#[derive(new, Debug, Default, Clone)]
+///
+/// The record is compressed by chunks as it's encoded, so it's never held in memory in addition
+/// to the compressed file.
+#[derive(Debug, Clone)]
 pub struct BinGzFileRecorder<S: PrecisionSettings> {
+    level: u32,
+    chunk_size: usize,
     _settings: PhantomData<S>,
 }
 
+impl<S: PrecisionSettings> BinGzFileRecorder<S> {
+    /// Creates a recorder with the default compression level, 6.
+    pub fn new() -> Self {
+        Self {
+            level: Compression::default().level(),
+            chunk_size: DEFAULT_COMPRESSION_CHUNK_SIZE,
+            _settings: PhantomData,
+        }
+    }
+
+    /// Sets the compression level, from 0 (no compression) to 9 (best compression).
+    pub fn with_level(mut self, level: u32) -> Self {
+        assert!(level <= 9, "The gzip compression level should be at most 9");
+        self.level = level;
+        self
+    }
+
+    /// Sets the size in bytes of the chunks of the encoded record given to the compressor.
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+}
+
+impl<S: PrecisionSettings> Default for BinGzFileRecorder<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// File recorder using the [bincode format](bincode) compressed with zstd.
+///
+/// Zstd compresses faster than gzip for a similar size at the default level, and smaller at the
+/// higher levels. Like with the [BinGzFileRecorder], the record is compressed by chunks as it's
+/// encoded.
+#[cfg(feature = "record-zstd")]
+#[derive(Debug, Clone)]
+pub struct BinZstdFileRecorder<S: PrecisionSettings> {
+    level: i32,
+    chunk_size: usize,
+    _settings: PhantomData<S>,
+}
+
+#[cfg(feature = "record-zstd")]
+impl<S: PrecisionSettings> BinZstdFileRecorder<S> {
+    /// Creates a recorder with the default compression level, 3.
+    pub fn new() -> Self {
+        Self {
+            level: zstd::DEFAULT_COMPRESSION_LEVEL,
+            chunk_size: DEFAULT_COMPRESSION_CHUNK_SIZE,
+            _settings: PhantomData,
+        }
+    }
+
+    /// Sets the compression level, from 1 (fastest) to 22 (best compression).
+    ///
+    /// The negative levels compress even faster at the cost of the size.
+    pub fn with_level(mut self, level: i32) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Sets the size in bytes of the chunks of the encoded record given to the compressor.
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+}
+
+#[cfg(feature = "record-zstd")]
+impl<S: PrecisionSettings> Default for BinZstdFileRecorder<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// File recorder using the [json format](serde_json) compressed with gzip.
 #[derive(new, Debug, Default, Clone)]
 pub struct JsonGzFileRecorder<S: PrecisionSettings> {
@@ -58,6 +142,12 @@ impl<S: PrecisionSettings, B: Backend> FileRecorder<B> for BinGzFileRecorder<S>
         "bin.gz"
     }
 }
+#[cfg(feature = "record-zstd")]
+impl<S: PrecisionSettings, B: Backend> FileRecorder<B> for BinZstdFileRecorder<S> {
+    fn file_extension() -> &'static str {
+        "bin.zst"
+    }
+}
 impl<S: PrecisionSettings, B: Backend> FileRecorder<B> for BinFileRecorder<S> {
     fn file_extension() -> &'static str {
         "bin"
@@ -141,10 +231,16 @@ impl<S: PrecisionSettings, B: Backend> Recorder<B> for BinGzFileRecorder<S> {
     ) -> Result<(), RecorderError> {
         let config = bin_config();
         let writer = str2writer!(file)?;
-        let mut writer = GzEncoder::new(writer, Compression::default());
+        let encoder = GzEncoder::new(writer, Compression::new(self.level));
+        let mut writer = BufWriter::with_capacity(self.chunk_size, encoder);
 
         bincode::serde::encode_into_std_write(&item, &mut writer, config)
             .map_err(|err| RecorderError::Unknown(err.to_string()))?;
+        writer
+            .into_inner()
+            .map_err(|err| RecorderError::Unknown(err.to_string()))?
+            .finish()
+            .map_err(|err| RecorderError::Unknown(err.to_string()))?;
 
         Ok(())
     }
@@ -159,6 +255,47 @@ impl<S: PrecisionSettings, B: Backend> Recorder<B> for BinGzFileRecorder<S> {
     }
 }
 
+#[cfg(feature = "record-zstd")]
+impl<S: PrecisionSettings, B: Backend> Recorder<B> for BinZstdFileRecorder<S> {
+    type Settings = S;
+    type RecordArgs = PathBuf;
+    type RecordOutput = ();
+    type LoadArgs = PathBuf;
+
+    fn save_item<I: Serialize>(
+        &self,
+        item: I,
+        mut file: Self::RecordArgs,
+    ) -> Result<(), RecorderError> {
+        let config = bin_config();
+        let writer = str2writer!(file)?;
+        let encoder = zstd::stream::write::Encoder::new(writer, self.level)
+            .map_err(|err| RecorderError::Unknown(err.to_string()))?;
+        let mut writer = BufWriter::with_capacity(self.chunk_size, encoder);
+
+        bincode::serde::encode_into_std_write(&item, &mut writer, config)
+            .map_err(|err| RecorderError::Unknown(err.to_string()))?;
+        // The frame is only complete once the encoder is finished
+        writer
+            .into_inner()
+            .map_err(|err| RecorderError::Unknown(err.to_string()))?
+            .finish()
+            .map_err(|err| RecorderError::Unknown(err.to_string()))?;
+
+        Ok(())
+    }
+
+    fn load_item<I: DeserializeOwned>(&self, mut file: Self::LoadArgs) -> Result<I, RecorderError> {
+        let reader = str2reader!(file)?;
+        let mut reader = zstd::stream::read::Decoder::with_buffer(reader)
+            .map_err(|err| RecorderError::Unknown(err.to_string()))?;
+        let state = bincode::serde::decode_from_std_read(&mut reader, bin_config())
+            .map_err(|err| RecorderError::Unknown(err.to_string()))?;
+
+        Ok(state)
+    }
+}
+
 impl<S: PrecisionSettings, B: Backend> Recorder<B> for BinFileRecorder<S> {
     type Settings = S;
     type RecordArgs = PathBuf;
@@ -337,6 +474,30 @@ mod tests {
         test_can_save_and_load(BinGzFileRecorder::<FullPrecisionSettings>::default())
     }
 
+    #[test]
+    fn test_can_save_and_load_bingz_format_with_level() {
+        // Not the file of the default gzip recorder test, which runs concurrently
+        let file = std::env::temp_dir()
+            .as_path()
+            .join("burn_test_file_recorder_level");
+        test_can_save_and_load_with_file(
+            BinGzFileRecorder::<FullPrecisionSettings>::new()
+                .with_level(1)
+                .with_chunk_size(64),
+            file,
+        )
+    }
+
+    #[cfg(feature = "record-zstd")]
+    #[test]
+    fn test_can_save_and_load_binzstd_format() {
+        test_can_save_and_load(
+            BinZstdFileRecorder::<FullPrecisionSettings>::new()
+                .with_level(19)
+                .with_chunk_size(64),
+        )
+    }
+
     #[test]
     fn test_can_save_and_load_pretty_json_format() {
         test_can_save_and_load(PrettyJsonFileRecorder::<FullPrecisionSettings>::default())
@@ -353,17 +514,23 @@ mod tests {
     }
 
     fn test_can_save_and_load<Recorder>(recorder: Recorder)
+    where
+        Recorder: FileRecorder<TestBackend>,
+    {
+        test_can_save_and_load_with_file(recorder, file_path())
+    }
+
+    fn test_can_save_and_load_with_file<Recorder>(recorder: Recorder, file: PathBuf)
     where
         Recorder: FileRecorder<TestBackend>,
     {
         let device = Default::default();
         let model_before = create_model(&device);
         recorder
-            .record(model_before.clone().into_record(), file_path())
+            .record(model_before.clone().into_record(), file.clone())
             .unwrap();
 
-        let model_after =
-            create_model(&device).load_record(recorder.load(file_path(), &device).unwrap());
+        let model_after = create_model(&device).load_record(recorder.load(file, &device).unwrap());
 
         let byte_recorder = BinBytesRecorder::<FullPrecisionSettings>::default();
         let model_bytes_before = byte_recorder
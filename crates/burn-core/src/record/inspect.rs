@@ -0,0 +1,312 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use burn_tensor::DType;
+use core::fmt;
+use serde::{
+    de::{DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer,
+};
+
+/// A parameter of a record listed by [inspect](super::Recorder::inspect).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordEntry {
+    /// The path of the parameter, e.g. `encoder.linear.weight`.
+    pub path: String,
+    /// The shape of the parameter.
+    pub shape: Vec<usize>,
+    /// The data type of the parameter.
+    pub dtype: DType,
+    /// The size of the data of the parameter in bytes.
+    pub size: usize,
+}
+
+impl fmt::Display for RecordEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {:?} {:?} ({} bytes)",
+            self.path, self.dtype, self.shape, self.size
+        )
+    }
+}
+
+/// The parameters of a record item, deserialized without keeping their data.
+pub(crate) struct RecordEntries(pub(crate) Vec<RecordEntry>);
+
+impl<'de> Deserialize<'de> for RecordEntries {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut entries = Vec::new();
+        NodeSeed {
+            path: String::new(),
+            entries: &mut entries,
+        }
+        .deserialize(deserializer)?;
+
+        Ok(Self(entries))
+    }
+}
+
+/// Walks a node of the item, collecting the parameters, the maps with an `id` and a `param`.
+struct NodeSeed<'a> {
+    path: String,
+    entries: &'a mut Vec<RecordEntry>,
+}
+
+impl NodeSeed<'_> {
+    fn child(&mut self, name: &str) -> NodeSeed<'_> {
+        let path = match self.path.is_empty() {
+            true => name.to_string(),
+            false => format!("{}.{name}", self.path),
+        };
+
+        NodeSeed {
+            path,
+            entries: self.entries,
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for NodeSeed<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for NodeSeed<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a record item")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(mut self, mut map: A) -> Result<(), A::Error> {
+        while let Some(key) = map.next_key::<MapKey>()? {
+            match key.0 {
+                Some(name) if name == "param" => {
+                    if let Some((shape, dtype, size)) = map.next_value_seed(TensorSeed)? {
+                        self.entries.push(RecordEntry {
+                            path: self.path.clone(),
+                            shape,
+                            dtype,
+                            size,
+                        });
+                    }
+                }
+                Some(name) => map.next_value_seed(self.child(&name))?,
+                None => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<(), A::Error> {
+        let mut index = 0;
+        while seq
+            .next_element_seed(self.child(&index.to_string()))?
+            .is_some()
+        {
+            index += 1;
+        }
+
+        Ok(())
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_none<E>(self) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_unit<E>(self) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_bool<E>(self, _v: bool) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_i64<E>(self, _v: i64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_u64<E>(self, _v: u64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_f64<E>(self, _v: f64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_str<E>(self, _v: &str) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_bytes<E>(self, _v: &[u8]) -> Result<(), E> {
+        Ok(())
+    }
+}
+
+/// A key of a map, `None` if it isn't a string or an integer.
+struct MapKey(Option<String>);
+
+impl<'de> Deserialize<'de> for MapKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MapKeyVisitor;
+
+        impl Visitor<'_> for MapKeyVisitor {
+            type Value = MapKey;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map key")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<MapKey, E> {
+                Ok(MapKey(Some(v.to_string())))
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<MapKey, E> {
+                Ok(MapKey(Some(v.to_string())))
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<MapKey, E> {
+                Ok(MapKey(Some(v.to_string())))
+            }
+
+            fn visit_bytes<E>(self, _v: &[u8]) -> Result<MapKey, E> {
+                Ok(MapKey(None))
+            }
+        }
+
+        deserializer.deserialize_any(MapKeyVisitor)
+    }
+}
+
+/// Reads the shape, the data type and the size of the data of a parameter, skipping its data.
+///
+/// The parameters saved with another layout than [TensorData](burn_tensor::TensorData) are
+/// skipped.
+struct TensorSeed;
+
+impl<'de> DeserializeSeed<'de> for TensorSeed {
+    type Value = Option<(Vec<usize>, DType, usize)>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for TensorSeed {
+    type Value = Option<(Vec<usize>, DType, usize)>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("tensor data")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let (mut shape, mut dtype, mut size) = (None, None, None);
+
+        while let Some(key) = map.next_key::<MapKey>()? {
+            match key.0.as_deref() {
+                Some("bytes") => size = Some(map.next_value::<ByteCount>()?.0),
+                Some("shape") => shape = Some(map.next_value::<Vec<usize>>()?),
+                Some("dtype") => dtype = Some(map.next_value::<DType>()?),
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        match (shape, dtype, size) {
+            (Some(shape), Some(dtype), Some(size)) => Ok(Some((shape, dtype, size))),
+            _ => Ok(None),
+        }
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        while seq.next_element::<IgnoredAny>()?.is_some() {}
+
+        Ok(None)
+    }
+}
+
+/// The number of bytes of a byte array, which isn't kept.
+struct ByteCount(usize);
+
+impl<'de> Deserialize<'de> for ByteCount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ByteCountVisitor;
+
+        impl<'de> Visitor<'de> for ByteCountVisitor {
+            type Value = ByteCount;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("bytes")
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<ByteCount, E> {
+                Ok(ByteCount(v.len()))
+            }
+
+            // The formats without bytes save them as a sequence
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<ByteCount, A::Error> {
+                let mut count = 0;
+                while seq.next_element::<IgnoredAny>()?.is_some() {
+                    count += 1;
+                }
+
+                Ok(ByteCount(count))
+            }
+        }
+
+        deserializer.deserialize_bytes(ByteCountVisitor)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{
+        module::Module,
+        nn::{Linear, LinearConfig},
+        record::{FullPrecisionSettings, NamedMpkBytesRecorder, Recorder},
+        TestBackend,
+    };
+
+    #[test]
+    fn test_inspect_lists_the_parameters() {
+        let device = Default::default();
+        let recorder = NamedMpkBytesRecorder::<FullPrecisionSettings>::new();
+        let model: Linear<TestBackend> = LinearConfig::new(4, 2).init(&device);
+
+        let bytes = recorder.record(model.into_record(), ()).unwrap();
+        let entries = Recorder::<TestBackend>::inspect(&recorder, bytes).unwrap();
+
+        assert_eq!(
+            entries,
+            [
+                RecordEntry {
+                    path: "weight".to_string(),
+                    shape: vec![4, 2],
+                    dtype: DType::F32,
+                    size: 32,
+                },
+                RecordEntry {
+                    path: "bias".to_string(),
+                    shape: vec![2],
+                    dtype: DType::F32,
+                    size: 8,
+                },
+            ]
+        );
+    }
+}
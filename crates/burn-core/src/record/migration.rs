@@ -0,0 +1,618 @@
+use super::{
+    FullPrecisionSettings, NamedMpkFileRecorder, PrecisionSettings, Record, Recorder, RecorderError,
+};
+use crate::module::{load_params, KeyRemap, Module, ParamKind};
+use burn_tensor::{backend::Backend, DType, TensorData};
+use core::fmt;
+use hashbrown::HashMap;
+use rmpv::Value;
+use serde::{Deserialize, Serialize};
+use std::{fs::File, io::BufReader, path::PathBuf, sync::Arc};
+
+/// The parameters of a record by path, e.g. `encoder.linear.weight`, upgraded by the
+/// [migrations](Migration).
+pub type ParamMap = HashMap<String, TensorData>;
+
+type Transform = Arc<dyn Fn(&mut ParamMap) -> Result<(), String> + Send + Sync>;
+
+/// A record tagged with the version of the structure of its module, see [MigrationRegistry].
+#[derive(new, Debug, Clone)]
+pub struct VersionedRecord<R> {
+    /// The version of the structure of the module.
+    pub version: u32,
+    /// The record of the module.
+    pub record: R,
+}
+
+/// The item of a [versioned record](VersionedRecord).
+#[derive(new, Debug, Clone, Serialize, Deserialize)]
+pub struct VersionedRecordItem<I> {
+    /// The version of the structure of the module.
+    pub version: u32,
+    /// The item of the record.
+    pub record: I,
+}
+
+impl<B: Backend, R: Record<B>> Record<B> for VersionedRecord<R> {
+    type Item<S: PrecisionSettings> = VersionedRecordItem<R::Item<S>>;
+
+    fn into_item<S: PrecisionSettings>(self) -> Self::Item<S> {
+        VersionedRecordItem::new(self.version, self.record.into_item())
+    }
+
+    fn from_item<S: PrecisionSettings>(item: Self::Item<S>, device: &B::Device) -> Self {
+        VersionedRecord::new(item.version, R::from_item(item.record, device))
+    }
+}
+
+/// The changes upgrading the parameters of a record from one version of the structure of a
+/// module to the next one.
+///
+/// The steps are applied in order.
+///
+/// # Example
+///
+/// ```rust, ignore
+/// // The `layer` field was renamed `encoder`, and the query, key and value projections merged
+/// let qkv = ["attn.query.weight", "attn.key.weight", "attn.value.weight"];
+/// let migration = Migration::new()
+///     .with_remap(KeyRemap::new().with_rename("layer", "encoder"))
+///     .with_merge(&qkv, 1, "attn.qkv.weight");
+/// ```
+#[derive(Clone, Default)]
+pub struct Migration {
+    steps: Vec<MigrationStep>,
+}
+
+#[derive(Clone)]
+enum MigrationStep {
+    Remap(KeyRemap),
+    Reshape(String, Vec<usize>),
+    Split(String, usize, Vec<(String, usize)>),
+    Merge(Vec<String>, usize, String),
+    Transform(Transform),
+}
+
+impl Migration {
+    /// Creates a migration without any step.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renames the parameters with the rules.
+    pub fn with_remap(mut self, remap: KeyRemap) -> Self {
+        self.steps.push(MigrationStep::Remap(remap));
+        self
+    }
+
+    /// Reshapes a parameter, keeping its values in the same order.
+    pub fn with_reshape(mut self, path: &str, shape: &[usize]) -> Self {
+        self.steps
+            .push(MigrationStep::Reshape(path.to_string(), shape.to_vec()));
+        self
+    }
+
+    /// Splits a parameter along the dimension `dim` into the parameters with the given paths and
+    /// sizes.
+    pub fn with_split(mut self, path: &str, dim: usize, parts: &[(&str, usize)]) -> Self {
+        let parts = parts
+            .iter()
+            .map(|(path, size)| (path.to_string(), *size))
+            .collect();
+
+        self.steps
+            .push(MigrationStep::Split(path.to_string(), dim, parts));
+        self
+    }
+
+    /// Concatenates the parameters along the dimension `dim` into the parameter `into`.
+    pub fn with_merge(mut self, paths: &[&str], dim: usize, into: &str) -> Self {
+        let paths = paths.iter().map(|path| path.to_string()).collect();
+
+        self.steps
+            .push(MigrationStep::Merge(paths, dim, into.to_string()));
+        self
+    }
+
+    /// Transforms the parameters with a function, for the changes not covered by the other steps.
+    pub fn with_transform<F>(mut self, transform: F) -> Self
+    where
+        F: Fn(&mut ParamMap) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.steps
+            .push(MigrationStep::Transform(Arc::new(transform)));
+        self
+    }
+
+    /// Applies the steps to the parameters.
+    pub fn apply(&self, params: &mut ParamMap) -> Result<(), String> {
+        for step in self.steps.iter() {
+            match step {
+                MigrationStep::Remap(remap) => {
+                    *params = params
+                        .drain()
+                        .map(|(path, data)| (remap.apply(&path), data))
+                        .collect();
+                }
+                MigrationStep::Reshape(path, shape) => {
+                    let data = get_mut(params, path)?;
+                    if data.num_elements() != shape.iter().product::<usize>() {
+                        return Err(format!(
+                            "Can't reshape {path} from {:?} to {shape:?}",
+                            data.shape
+                        ));
+                    }
+                    data.shape = shape.clone();
+                }
+                MigrationStep::Split(path, dim, parts) => {
+                    let data = params
+                        .remove(path)
+                        .ok_or_else(|| format!("No parameter {path}"))?;
+                    let sizes = parts.iter().map(|(_, size)| *size).collect::<Vec<_>>();
+
+                    for ((path, _), part) in parts.iter().zip(split(data, *dim, &sizes)?) {
+                        params.insert(path.clone(), part);
+                    }
+                }
+                MigrationStep::Merge(paths, dim, into) => {
+                    let parts = paths
+                        .iter()
+                        .map(|path| {
+                            params
+                                .remove(path)
+                                .ok_or_else(|| format!("No parameter {path}"))
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+
+                    params.insert(into.clone(), merge(parts, *dim)?);
+                }
+                MigrationStep::Transform(transform) => transform(params)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Migration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Migration")
+            .field("steps", &self.steps.len())
+            .finish()
+    }
+}
+
+/// The migrations upgrading the records of a module saved with the older versions of its
+/// structure.
+///
+/// The records are saved with the current version by [save_file](MigrationRegistry::save_file).
+/// When loaded by [load_file](MigrationRegistry::load_file), the migrations from their version
+/// up to the current one are applied to their parameters. The records saved without a version
+/// have the version 0.
+///
+/// # Example
+///
+/// ```rust, ignore
+/// let rename = KeyRemap::new().with_rename("layer", "encoder");
+/// let registry = MigrationRegistry::new(2)
+///     .with_migration(0, Migration::new().with_reshape("head.weight", &[512, 10]))
+///     .with_migration(1, Migration::new().with_remap(rename));
+///
+/// let model = registry.load_file(Model::new(&device), "model")?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct MigrationRegistry {
+    version: u32,
+    migrations: HashMap<u32, Migration>,
+}
+
+impl MigrationRegistry {
+    /// Creates a registry for the current version of the structure of the module.
+    pub fn new(version: u32) -> Self {
+        Self {
+            version,
+            migrations: HashMap::new(),
+        }
+    }
+
+    /// Registers the migration upgrading the records of version `from` to the version `from + 1`.
+    pub fn with_migration(mut self, from: u32, migration: Migration) -> Self {
+        self.migrations.insert(from, migration);
+        self
+    }
+
+    /// Upgrades the parameters of a record from its version to the current one.
+    pub fn migrate(&self, params: &mut ParamMap, version: u32) -> Result<(), RecorderError> {
+        if version > self.version {
+            return Err(RecorderError::Unknown(format!(
+                "The record version {version} is newer than the module version {}",
+                self.version
+            )));
+        }
+
+        for from in version..self.version {
+            let migration = self.migrations.get(&from).ok_or_else(|| {
+                RecorderError::Unknown(format!("No migration from the record version {from}"))
+            })?;
+
+            migration.apply(params).map_err(|err| {
+                RecorderError::Unknown(format!("Migration from the version {from} failed: {err}"))
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Saves the record of the module tagged with the current version, with the
+    /// [named msgpack recorder](NamedMpkFileRecorder).
+    pub fn save_file<B, M, S>(
+        &self,
+        module: M,
+        file: impl Into<PathBuf>,
+        recorder: &NamedMpkFileRecorder<S>,
+    ) -> Result<(), RecorderError>
+    where
+        B: Backend,
+        M: Module<B>,
+        S: PrecisionSettings,
+    {
+        let record = VersionedRecord::new(self.version, module.into_record());
+        recorder.record(record, file.into())
+    }
+
+    /// Loads the record of a [named msgpack](NamedMpkFileRecorder) file into the module, upgrading
+    /// its parameters to the current version.
+    ///
+    /// The loading fails if the upgraded parameters don't match the module exactly.
+    pub fn load_file<B, M>(&self, module: M, file: impl Into<PathBuf>) -> Result<M, RecorderError>
+    where
+        B: Backend,
+        M: Module<B>,
+    {
+        let (version, mut params) = read_params(file)?;
+        self.migrate(&mut params, version)?;
+
+        let params = params
+            .into_iter()
+            .map(|(path, data)| ((path, ParamKind::of(data.dtype)), data))
+            .collect();
+        let (module, report) = load_params(module, params);
+
+        match report.is_exact() {
+            true => Ok(module),
+            false => Err(RecorderError::Unknown(format!(
+                "The record of version {version} doesn't match the module:\n{report}"
+            ))),
+        }
+    }
+}
+
+/// Reads the parameters of a [named msgpack](NamedMpkFileRecorder) file by path, along with the
+/// version of the record, which is 0 if the record isn't [versioned](VersionedRecord).
+pub fn read_params(file: impl Into<PathBuf>) -> Result<(u32, ParamMap), RecorderError> {
+    let mut file = file.into();
+    file.set_extension("mpk");
+
+    let reader = File::open(&file).map_err(|err| match err.kind() {
+        std::io::ErrorKind::NotFound => RecorderError::FileNotFound(err.to_string()),
+        _ => RecorderError::Unknown(err.to_string()),
+    })?;
+    let value = rmpv::decode::read_value(&mut BufReader::new(reader))
+        .map_err(|err| RecorderError::Unknown(err.to_string()))?;
+
+    let item = field(value, "item")
+        .ok_or_else(|| RecorderError::Unknown("No item in the record".to_string()))?;
+    let (version, item) = match is_versioned(&item) {
+        true => versioned_item(item)?,
+        false => (0, item),
+    };
+
+    let mut params = ParamMap::new();
+    collect_params(item, &mut Vec::new(), &mut params)?;
+
+    Ok((version, params))
+}
+
+/// Reads the parameters of a record by path, encoded as by the
+/// [named msgpack recorder](NamedMpkFileRecorder).
+pub(crate) fn record_params<B: Backend, R: Record<B>>(
+    record: R,
+) -> Result<ParamMap, RecorderError> {
+    let item = record.into_item::<FullPrecisionSettings>();
+    let bytes = rmp_serde::encode::to_vec_named(&item)
+        .map_err(|err| RecorderError::Unknown(err.to_string()))?;
+    let value = rmpv::decode::read_value(&mut bytes.as_slice())
+        .map_err(|err| RecorderError::Unknown(err.to_string()))?;
+
+    let mut params = ParamMap::new();
+    collect_params(value, &mut Vec::new(), &mut params)?;
+
+    Ok(params)
+}
+
+fn field(value: Value, name: &str) -> Option<Value> {
+    match value {
+        Value::Map(entries) => entries
+            .into_iter()
+            .find(|(key, _)| key.as_str() == Some(name))
+            .map(|(_, value)| value),
+        _ => None,
+    }
+}
+
+/// Whether the item is the item of a [versioned record](VersionedRecord).
+fn is_versioned(item: &Value) -> bool {
+    match item {
+        Value::Map(entries) => {
+            let mut keys = entries.iter().filter_map(|(key, _)| key.as_str());
+            entries.len() == 2 && keys.all(|key| key == "version" || key == "record")
+        }
+        _ => false,
+    }
+}
+
+/// The version and the item of the record of a [versioned record](VersionedRecord).
+fn versioned_item(item: Value) -> Result<(u32, Value), RecorderError> {
+    let mut version = None;
+    let mut record = Value::Nil;
+
+    if let Value::Map(entries) = item {
+        for (key, value) in entries {
+            match key.as_str() {
+                Some("version") => version = value.as_u64(),
+                Some("record") => record = value,
+                _ => {}
+            }
+        }
+    }
+
+    let version = version
+        .and_then(|version| u32::try_from(version).ok())
+        .ok_or_else(|| RecorderError::Unknown("Invalid record version".to_string()))?;
+    Ok((version, record))
+}
+
+/// Collects the parameters of a record item, the maps with an `id` and a `param`.
+fn collect_params(
+    value: Value,
+    path: &mut Vec<String>,
+    params: &mut ParamMap,
+) -> Result<(), RecorderError> {
+    match value {
+        Value::Map(entries) => {
+            let is_param = entries.len() == 2
+                && entries
+                    .iter()
+                    .all(|(key, _)| matches!(key.as_str(), Some("id" | "param")));
+
+            if is_param {
+                let param = field(Value::Map(entries), "param").unwrap_or(Value::Nil);
+
+                // The data is decoded as it was encoded, by the named msgpack recorder
+                let mut bytes = Vec::new();
+                rmpv::encode::write_value(&mut bytes, &param)
+                    .map_err(|err| RecorderError::Unknown(err.to_string()))?;
+                let data = rmp_serde::decode::from_slice::<TensorData>(&bytes).map_err(|err| {
+                    RecorderError::Unknown(format!("Invalid parameter {}: {err}", path.join(".")))
+                })?;
+                params.insert(path.join("."), data);
+                return Ok(());
+            }
+
+            for (key, value) in entries {
+                if let Some(name) = key.as_str() {
+                    path.push(name.to_string());
+                    collect_params(value, path, params)?;
+                    path.pop();
+                }
+            }
+        }
+        Value::Array(values) => {
+            for (i, value) in values.into_iter().enumerate() {
+                path.push(i.to_string());
+                collect_params(value, path, params)?;
+                path.pop();
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+fn get_mut<'a>(params: &'a mut ParamMap, path: &str) -> Result<&'a mut TensorData, String> {
+    params
+        .get_mut(path)
+        .ok_or_else(|| format!("No parameter {path}"))
+}
+
+/// The size in bytes of the blocks of values along `dim`, and their number.
+fn blocks(data: &TensorData, dim: usize) -> Result<(usize, usize), String> {
+    if matches!(data.dtype, DType::QFloat(_)) {
+        return Err("Quantized parameters can't be split or merged".to_string());
+    }
+    if dim >= data.shape.len() {
+        return Err(format!(
+            "Invalid dimension {dim} for the shape {:?}",
+            data.shape
+        ));
+    }
+
+    let outer = data.shape[..dim].iter().product::<usize>();
+    let inner = data.shape[dim + 1..].iter().product::<usize>() * data.dtype.size();
+    Ok((inner, outer))
+}
+
+fn split(data: TensorData, dim: usize, sizes: &[usize]) -> Result<Vec<TensorData>, String> {
+    let (inner, outer) = blocks(&data, dim)?;
+    if sizes.iter().sum::<usize>() != data.shape[dim] {
+        return Err(format!(
+            "Can't split the dimension {dim} of {:?} into {sizes:?}",
+            data.shape
+        ));
+    }
+
+    let mut parts = sizes
+        .iter()
+        .map(|size| Vec::with_capacity(outer * size * inner))
+        .collect::<Vec<_>>();
+    let mut chunks = data.bytes.chunks(inner * data.shape[dim]);
+    for _ in 0..outer {
+        let mut chunk = chunks.next().unwrap_or_default();
+        for (part, size) in parts.iter_mut().zip(sizes) {
+            let (values, rest) = chunk.split_at(size * inner);
+            part.extend_from_slice(values);
+            chunk = rest;
+        }
+    }
+
+    Ok(parts
+        .into_iter()
+        .zip(sizes)
+        .map(|(bytes, size)| {
+            let mut shape = data.shape.clone();
+            shape[dim] = *size;
+            TensorData {
+                bytes,
+                shape,
+                dtype: data.dtype,
+            }
+        })
+        .collect())
+}
+
+fn merge(parts: Vec<TensorData>, dim: usize) -> Result<TensorData, String> {
+    let first = parts.first().ok_or("No parameter to merge")?;
+    let (_, outer) = blocks(first, dim)?;
+    let mut shape = first.shape.clone();
+    let dtype = first.dtype;
+
+    // The parts must have the same rank and the same extents, except along `dim`
+    for part in parts.iter() {
+        let is_compatible = part.shape.len() == shape.len()
+            && part
+                .shape
+                .iter()
+                .zip(shape.iter())
+                .enumerate()
+                .all(|(i, (a, b))| i == dim || a == b);
+        if !is_compatible || part.dtype != dtype {
+            return Err(format!(
+                "Can't merge {:?} {:?} with {:?} {dtype:?} along the dimension {dim}",
+                part.shape, part.dtype, first.shape
+            ));
+        }
+    }
+    shape[dim] = parts.iter().map(|part| part.shape[dim]).sum();
+
+    let mut chunks = parts
+        .iter()
+        .map(|part| {
+            let (inner, _) = blocks(part, dim)?;
+            Ok(part.bytes.chunks(inner * part.shape[dim]))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let mut bytes = Vec::with_capacity(parts.iter().map(|part| part.bytes.len()).sum());
+    for _ in 0..outer {
+        for chunk in chunks.iter_mut() {
+            bytes.extend_from_slice(chunk.next().unwrap_or_default());
+        }
+    }
+
+    Ok(TensorData {
+        bytes,
+        shape,
+        dtype,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        nn::{Linear, LinearConfig},
+        record::FullPrecisionSettings,
+        TestBackend,
+    };
+
+    use crate as burn;
+
+    #[derive(Module, Debug)]
+    struct ModelV1<B: Backend> {
+        layer: Linear<B>,
+    }
+
+    #[derive(Module, Debug)]
+    struct ModelV2<B: Backend> {
+        encoder: Linear<B>,
+    }
+
+    #[test]
+    fn test_load_file_applies_the_migrations() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("model");
+        let device = Default::default();
+        let recorder = NamedMpkFileRecorder::<FullPrecisionSettings>::new();
+
+        let model_v1 = ModelV1::<TestBackend> {
+            layer: LinearConfig::new(4, 6).init(&device),
+        };
+        MigrationRegistry::new(1)
+            .save_file(model_v1.clone(), file.clone(), &recorder)
+            .unwrap();
+
+        let registry = MigrationRegistry::new(2).with_migration(
+            1,
+            Migration::new().with_remap(KeyRemap::new().with_rename("layer", "encoder")),
+        );
+        let model_v2 = ModelV2::<TestBackend> {
+            encoder: LinearConfig::new(4, 6).init(&device),
+        };
+        let model_v2 = registry.load_file(model_v2, file).unwrap();
+
+        assert_eq!(
+            model_v2.encoder.weight.to_data(),
+            model_v1.layer.weight.to_data()
+        );
+    }
+
+    #[test]
+    fn test_split_and_merge_are_inverse() {
+        let data = TensorData::new((0..24).map(|i| i as f32).collect::<Vec<_>>(), [4, 6]);
+        let mut params = ParamMap::new();
+        params.insert("weight".to_string(), data.clone());
+
+        Migration::new()
+            .with_split("weight", 1, &[("a", 2), ("b", 4)])
+            .apply(&mut params)
+            .unwrap();
+        assert_eq!(
+            params["a"],
+            TensorData::new(vec![0.0f32, 1., 6., 7., 12., 13., 18., 19.], [4, 2])
+        );
+
+        Migration::new()
+            .with_merge(&["a", "b"], 1, "weight")
+            .apply(&mut params)
+            .unwrap();
+        assert_eq!(params["weight"], data);
+    }
+
+    #[test]
+    fn test_merge_rejects_the_parts_of_another_rank() {
+        let mut params = ParamMap::new();
+        params.insert("a".to_string(), TensorData::new(vec![0.0f32; 4], [2, 2]));
+        params.insert("b".to_string(), TensorData::new(vec![0.0f32; 2], [2]));
+
+        let err = Migration::new()
+            .with_merge(&["a", "b"], 1, "weight")
+            .apply(&mut params)
+            .unwrap_err();
+
+        assert_eq!(
+            err,
+            "Can't merge [2] F32 with [2, 2] F32 along the dimension 1"
+        );
+    }
+}
@@ -2,20 +2,44 @@ mod primitive;
 mod tensor;
 
 mod base;
+mod inspect;
 mod memory;
 mod recorder;
 mod settings;
 
 pub use base::*;
+pub use inspect::RecordEntry;
 pub use memory::*;
 pub use recorder::*;
 pub use settings::*;
 
+pub(crate) use inspect::RecordEntries;
+
 #[cfg(feature = "std")]
 mod file;
 #[cfg(feature = "std")]
 pub use file::*;
 
+#[cfg(feature = "std")]
+mod diff;
+#[cfg(feature = "std")]
+pub use diff::*;
+
+#[cfg(feature = "std")]
+mod migration;
+#[cfg(feature = "std")]
+pub use migration::*;
+
+#[cfg(feature = "std")]
+mod sharded;
+#[cfg(feature = "std")]
+pub use sharded::*;
+
+#[cfg(feature = "record-encryption")]
+mod encrypted;
+#[cfg(feature = "record-encryption")]
+pub use encrypted::*;
+
 pub use primitive::ParamSerde;
 
 #[cfg(feature = "record-item-custom-serde")]
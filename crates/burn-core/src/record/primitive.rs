@@ -143,6 +143,26 @@ where
     }
 }
 
+impl<T, B> Record<B> for HashMap<String, T>
+where
+    T: Record<B>,
+    B: Backend,
+{
+    type Item<S: PrecisionSettings> = HashMap<String, T::Item<S>>;
+
+    fn into_item<S: PrecisionSettings>(self) -> Self::Item<S> {
+        self.into_iter()
+            .map(|(key, record)| (key, record.into_item()))
+            .collect()
+    }
+
+    fn from_item<S: PrecisionSettings>(item: Self::Item<S>, device: &B::Device) -> Self {
+        item.into_iter()
+            .map(|(key, item)| (key, T::from_item(item, device)))
+            .collect()
+    }
+}
+
 #[allow(deprecated)]
 impl<E, B> Record<B> for DataSerialize<E>
 where
@@ -3,10 +3,13 @@ use core::marker::PhantomData;
 
 use alloc::format;
 use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use burn_tensor::backend::Backend;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-use super::{BinBytesRecorder, FullPrecisionSettings, PrecisionSettings, Record};
+use super::{
+    BinBytesRecorder, FullPrecisionSettings, PrecisionSettings, Record, RecordEntries, RecordEntry,
+};
 
 #[cfg(feature = "std")]
 use super::{
@@ -104,6 +107,17 @@ pub trait Recorder<B: Backend>:
         Ok(R::from_item(item.item, device))
     }
 
+    /// Lists the parameters of a record with their shapes, data types and sizes, without
+    /// loading them in tensors, e.g. to check that a record matches a module before loading it.
+    ///
+    /// The data of the parameters is skipped as it's read, which requires a self-describing
+    /// format such as the named msgpack or the JSON formats, the bincode format isn't supported.
+    fn inspect(&self, args: Self::LoadArgs) -> Result<Vec<RecordEntry>, RecorderError> {
+        let record: BurnRecord<RecordEntries, B> = self.load_item(args)?;
+
+        Ok(record.item.0)
+    }
+
     /// Saves an item.
     ///
     /// This method is used by [record](Recorder::record) to save the item.
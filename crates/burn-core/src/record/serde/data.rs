@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use super::adapter::BurnModuleAdapter;
 use super::de::Deserializer;
@@ -66,8 +67,40 @@ pub enum NestedValue {
 
     /// A vector of 32-bit floating point values.
     F32s(Vec<f32>),
+
+    /// A value read when it is deserialized, e.g. a tensor of a memory mapped file, so that the
+    /// values of a large record are not all copied before being deserialized.
+    Lazy(LazyValue),
 }
+
+/// A nested value read when it is deserialized.
+#[derive(Clone)]
+pub struct LazyValue(Arc<dyn Fn() -> Result<NestedValue, Error> + Send + Sync>);
+
+impl LazyValue {
+    /// Creates a lazy value from the function reading it, which may be called more than once.
+    pub fn new<F>(read: F) -> Self
+    where
+        F: Fn() -> Result<NestedValue, Error> + Send + Sync + 'static,
+    {
+        Self(Arc::new(read))
+    }
+
+    /// Reads the value.
+    pub fn read(&self) -> Result<NestedValue, Error> {
+        (self.0)()
+    }
+}
+
 impl NestedValue {
+    /// Reads the value if it is lazy.
+    pub fn resolve(self) -> Result<NestedValue, Error> {
+        match self {
+            NestedValue::Lazy(value) => value.read(),
+            value => Ok(value),
+        }
+    }
+
     /// Get the nested value as a map.
     pub fn as_map(self) -> Option<HashMap<String, NestedValue>> {
         match self {
@@ -385,6 +418,7 @@ impl fmt::Debug for NestedValue {
             NestedValue::U8s(vec) => f.debug_list().entries(vec.iter()).finish(),
             NestedValue::U16s(vec) => f.debug_list().entries(vec.iter()).finish(),
             NestedValue::F32s(vec) => f.debug_list().entries(vec.iter()).finish(),
+            NestedValue::Lazy(_) => f.write_str("Lazy"),
         }
     }
 }
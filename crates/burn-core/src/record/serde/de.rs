@@ -58,6 +58,8 @@ impl<'de, A: BurnModuleAdapter> serde::Deserializer<'de> for Deserializer<A> {
     {
         let value = match self.value {
             Some(value) => {
+                let value = value.resolve()?;
+
                 // Adapt modules
                 if let Some(name) = name.strip_suffix(RECORD_ITEM_SUFFIX) {
                     A::adapt(name, value)
@@ -118,7 +120,9 @@ impl<'de, A: BurnModuleAdapter> serde::Deserializer<'de> for Deserializer<A> {
     where
         V: Visitor<'de>,
     {
-        match self.value {
+        let value = self.value.map(NestedValue::resolve).transpose()?;
+
+        match value {
             Some(NestedValue::Map(map)) => visitor.visit_map(HashMapAccess::<A>::new(
                 map,
                 self.default_for_missing_fields,
@@ -126,7 +130,7 @@ impl<'de, A: BurnModuleAdapter> serde::Deserializer<'de> for Deserializer<A> {
 
             _ => Err(de::Error::custom(format!(
                 "Expected map value but got {:?}",
-                self.value
+                value
             ))),
         }
     }
@@ -287,24 +291,23 @@ impl<'de, A: BurnModuleAdapter> serde::Deserializer<'de> for Deserializer<A> {
         V: Visitor<'de>,
     {
         if let Some(value) = self.value {
-            match value {
-                NestedValue::Vec(_) => visitor.visit_seq(VecSeqAccess::<A, NestedValue>::new(
-                    value,
-                    self.default_for_missing_fields,
-                )),
-                NestedValue::U8s(_) => visitor.visit_seq(VecSeqAccess::<A, u8>::new(
+            match value.resolve()? {
+                value @ NestedValue::Vec(_) => visitor.visit_seq(
+                    VecSeqAccess::<A, NestedValue>::new(value, self.default_for_missing_fields),
+                ),
+                value @ NestedValue::U8s(_) => visitor.visit_seq(VecSeqAccess::<A, u8>::new(
                     value,
                     self.default_for_missing_fields,
                 )),
-                NestedValue::U16s(_) => visitor.visit_seq(VecSeqAccess::<A, u16>::new(
+                value @ NestedValue::U16s(_) => visitor.visit_seq(VecSeqAccess::<A, u16>::new(
                     value,
                     self.default_for_missing_fields,
                 )),
-                NestedValue::F32s(_) => visitor.visit_seq(VecSeqAccess::<A, f32>::new(
+                value @ NestedValue::F32s(_) => visitor.visit_seq(VecSeqAccess::<A, f32>::new(
                     value,
                     self.default_for_missing_fields,
                 )),
-                _ => Err(de::Error::custom(format!(
+                value => Err(de::Error::custom(format!(
                     "Expected Vec but got {:?}",
                     value
                 ))),
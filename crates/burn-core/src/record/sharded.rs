@@ -0,0 +1,395 @@
+use super::{FileRecorder, NamedMpkFileRecorder, PrecisionSettings, Recorder, RecorderError};
+use burn_tensor::backend::Backend;
+use core::marker::PhantomData;
+use rmpv::Value;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// The default maximum size of a shard, in bytes.
+pub const DEFAULT_MAX_SHARD_SIZE: usize = 2_000_000_000;
+
+/// File recorder using the [named msgpack](rmp_serde) format, split across shard files listed by a
+/// JSON index.
+///
+/// The record is saved as an index `<name>.mpk.index.json`, mapping the keys of the record, e.g.
+/// `item.linear.weight`, to their shard, e.g. `<name>-00001-of-00003.mpk`. A value larger than the
+/// maximum shard size, such as a large tensor, is saved in a shard of its own.
+///
+/// When there is no index, the record is loaded from the single `<name>.mpk` file saved by the
+/// [NamedMpkFileRecorder].
+#[derive(Debug, Clone)]
+pub struct ShardedMpkFileRecorder<S: PrecisionSettings> {
+    max_shard_size: usize,
+    _settings: PhantomData<S>,
+}
+
+impl<S: PrecisionSettings> ShardedMpkFileRecorder<S> {
+    /// Creates a recorder with the [default maximum shard size](DEFAULT_MAX_SHARD_SIZE).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum size of the shards, in bytes.
+    pub fn with_max_shard_size(mut self, max_shard_size: usize) -> Self {
+        self.max_shard_size = max_shard_size;
+        self
+    }
+}
+
+impl<S: PrecisionSettings> Default for ShardedMpkFileRecorder<S> {
+    fn default() -> Self {
+        Self {
+            max_shard_size: DEFAULT_MAX_SHARD_SIZE,
+            _settings: PhantomData,
+        }
+    }
+}
+
+impl<S: PrecisionSettings, B: Backend> FileRecorder<B> for ShardedMpkFileRecorder<S> {
+    fn file_extension() -> &'static str {
+        "mpk.index.json"
+    }
+}
+
+/// The index of a sharded record.
+#[derive(Serialize, Deserialize)]
+struct ShardIndex {
+    metadata: ShardIndexMetadata,
+    /// The shard file of each key, relative to the index.
+    ///
+    /// The structure of the record, without the values saved under their own key, is saved under
+    /// the empty key.
+    weight_map: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ShardIndexMetadata {
+    /// The size of all the shards, in bytes.
+    total_size: usize,
+}
+
+impl<S: PrecisionSettings, B: Backend> Recorder<B> for ShardedMpkFileRecorder<S> {
+    type Settings = S;
+    type RecordArgs = PathBuf;
+    type RecordOutput = ();
+    type LoadArgs = PathBuf;
+
+    fn save_item<I: Serialize>(
+        &self,
+        item: I,
+        mut file: Self::RecordArgs,
+    ) -> Result<(), RecorderError> {
+        let bytes = rmp_serde::encode::to_vec_named(&item)
+            .map_err(|err| RecorderError::Unknown(err.to_string()))?;
+        let value = rmpv::decode::read_value(&mut bytes.as_slice())
+            .map_err(|err| RecorderError::Unknown(err.to_string()))?;
+        drop(bytes);
+
+        let mut entries = Vec::new();
+        let structure = split(value, "", self.max_shard_size, &mut entries);
+
+        // The structure comes first, unless the whole record fits in a shard
+        if !matches!(entries.first(), Some((key, _)) if key.is_empty()) {
+            entries.insert(0, (String::new(), structure));
+        }
+
+        // Fill the shards in order, so the values of a module are saved together
+        let mut shards: Vec<Vec<(String, Value)>> = Vec::new();
+        let mut shard_size = 0;
+        let mut total_size = 0;
+        for (key, value) in entries {
+            let size = encoded_size(&value);
+            total_size += size;
+
+            match shards.last_mut() {
+                Some(shard) if shard_size + size <= self.max_shard_size => {
+                    shard.push((key, value));
+                    shard_size += size;
+                }
+                _ => {
+                    shards.push(vec![(key, value)]);
+                    shard_size = size;
+                }
+            }
+        }
+
+        let name = shard_name(&file);
+        file.set_extension(<Self as FileRecorder<B>>::file_extension());
+        let base_dir = file.parent().unwrap_or(Path::new("")).to_path_buf();
+        std::fs::create_dir_all(&base_dir).ok();
+
+        let mut weight_map = BTreeMap::new();
+        let num_shards = shards.len();
+        for (i, shard) in shards.into_iter().enumerate() {
+            let shard_file = format!("{name}-{:05}-of-{num_shards:05}.mpk", i + 1);
+
+            let shard = shard
+                .into_iter()
+                .map(|(key, value)| {
+                    weight_map.insert(key.clone(), shard_file.clone());
+                    (Value::from(key), value)
+                })
+                .collect();
+
+            let mut writer =
+                BufWriter::new(File::create(base_dir.join(&shard_file)).map_err(io_error)?);
+            rmpv::encode::write_value(&mut writer, &Value::Map(shard))
+                .map_err(|err| RecorderError::Unknown(err.to_string()))?;
+            writer.flush().map_err(io_error)?;
+        }
+
+        let index = ShardIndex {
+            metadata: ShardIndexMetadata { total_size },
+            weight_map,
+        };
+        let writer = BufWriter::new(File::create(&file).map_err(io_error)?);
+        serde_json::to_writer_pretty(writer, &index)
+            .map_err(|err| RecorderError::Unknown(err.to_string()))?;
+
+        Ok(())
+    }
+
+    fn load_item<I: DeserializeOwned>(&self, file: Self::LoadArgs) -> Result<I, RecorderError> {
+        let mut index_file = file.clone();
+        index_file.set_extension(<Self as FileRecorder<B>>::file_extension());
+
+        if !index_file.exists() {
+            // The record is saved as a single file
+            return <NamedMpkFileRecorder<S> as Recorder<B>>::load_item(
+                &NamedMpkFileRecorder::new(),
+                file,
+            );
+        }
+
+        let reader = BufReader::new(File::open(&index_file).map_err(io_error)?);
+        let index: ShardIndex = serde_json::from_reader(reader)
+            .map_err(|err| RecorderError::Unknown(err.to_string()))?;
+        let base_dir = index_file.parent().unwrap_or(Path::new(""));
+
+        let mut entries = HashMap::new();
+        for shard_file in index.weight_map.values().collect::<BTreeSet<_>>() {
+            let mut reader =
+                BufReader::new(File::open(base_dir.join(shard_file)).map_err(io_error)?);
+            let shard = rmpv::decode::read_value(&mut reader)
+                .map_err(|err| RecorderError::Unknown(err.to_string()))?;
+
+            let Value::Map(shard) = shard else {
+                return Err(RecorderError::Unknown(format!(
+                    "Invalid shard {shard_file}, expected a map"
+                )));
+            };
+            for (key, value) in shard {
+                let key = key.as_str().map(ToString::to_string).ok_or_else(|| {
+                    RecorderError::Unknown(format!("Invalid key in the shard {shard_file}"))
+                })?;
+                entries.insert(key, value);
+            }
+        }
+
+        let mut value = entries.remove("").ok_or_else(|| {
+            RecorderError::Unknown("No record structure in the shards".to_string())
+        })?;
+        for (key, entry_value) in entries {
+            let slot = entry(&mut value, &key).ok_or_else(|| {
+                RecorderError::Unknown(format!("No entry for the key {key} in the record"))
+            })?;
+            *slot = entry_value;
+        }
+
+        let mut bytes = Vec::new();
+        rmpv::encode::write_value(&mut bytes, &value)
+            .map_err(|err| RecorderError::Unknown(err.to_string()))?;
+        drop(value);
+
+        rmp_serde::decode::from_slice(&bytes).map_err(|err| RecorderError::Unknown(err.to_string()))
+    }
+}
+
+/// Moves the values larger than `max_size` out of `value`, recursively, and returns what's left
+/// of it.
+///
+/// The values are moved to `entries` with their keys, the names of the fields and the indices of
+/// the items from the root joined by dots.
+fn split(value: Value, key: &str, max_size: usize, entries: &mut Vec<(String, Value)>) -> Value {
+    let is_splittable = match &value {
+        Value::Map(map) => map
+            .iter()
+            .all(|(name, _)| name.as_str().is_some_and(|name| !name.contains('.'))),
+        // The items of the vectors of tensor data are not split
+        Value::Array(values) => values
+            .iter()
+            .all(|value| matches!(value, Value::Map(_) | Value::Array(_))),
+        _ => false,
+    };
+
+    if !is_splittable || encoded_size(&value) <= max_size {
+        entries.push((key.to_string(), value));
+        return Value::Nil;
+    }
+
+    match value {
+        Value::Map(map) => Value::Map(
+            map.into_iter()
+                .map(|(name, value)| {
+                    let key = child_key(key, name.as_str().unwrap_or_default());
+                    let value = split(value, &key, max_size, entries);
+                    (name, value)
+                })
+                .collect(),
+        ),
+        Value::Array(values) => Value::Array(
+            values
+                .into_iter()
+                .enumerate()
+                .map(|(i, value)| split(value, &child_key(key, &i.to_string()), max_size, entries))
+                .collect(),
+        ),
+        _ => unreachable!("Only the maps and arrays are split"),
+    }
+}
+
+/// The value of a key in the record structure.
+fn entry<'a>(mut value: &'a mut Value, key: &str) -> Option<&'a mut Value> {
+    for name in key.split('.') {
+        value = match value {
+            Value::Map(map) => map
+                .iter_mut()
+                .find(|(field, _)| field.as_str() == Some(name))
+                .map(|(_, value)| value)?,
+            Value::Array(values) => values.get_mut(name.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+
+    Some(value)
+}
+
+fn child_key(key: &str, name: &str) -> String {
+    match key.is_empty() {
+        true => name.to_string(),
+        false => format!("{key}.{name}"),
+    }
+}
+
+/// The name of the shards, the name of the record file without its extension.
+fn shard_name(file: &Path) -> String {
+    let mut file = file.to_path_buf();
+    file.set_extension("");
+
+    file.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// The size of a value encoded as msgpack, in bytes.
+fn encoded_size(value: &Value) -> usize {
+    struct Counter(usize);
+
+    impl Write for Counter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0 += buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut counter = Counter(0);
+    rmpv::encode::write_value(&mut counter, value).expect("Counting the bytes can't fail");
+    counter.0
+}
+
+fn io_error(err: std::io::Error) -> RecorderError {
+    match err.kind() {
+        std::io::ErrorKind::NotFound => RecorderError::FileNotFound(err.to_string()),
+        _ => RecorderError::Unknown(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        module::Module,
+        nn::{Linear, LinearConfig},
+        record::{BinBytesRecorder, FullPrecisionSettings},
+        TestBackend,
+    };
+
+    use crate as burn;
+
+    #[derive(Module, Debug)]
+    pub struct Model<B: Backend> {
+        linears: Vec<Linear<B>>,
+    }
+
+    fn create_model(device: &<TestBackend as Backend>::Device) -> Model<TestBackend> {
+        Model {
+            linears: (0..3)
+                .map(|_| LinearConfig::new(16, 16).init(device))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_can_save_and_load_shards() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("model");
+        let device = Default::default();
+        // A linear weight is about 1 KB, so each shard holds a linear
+        let recorder =
+            ShardedMpkFileRecorder::<FullPrecisionSettings>::new().with_max_shard_size(1200);
+
+        let model_before = create_model(&device);
+        recorder
+            .record(model_before.clone().into_record(), file.clone())
+            .unwrap();
+
+        let num_shards = std::fs::read_dir(dir.path())
+            .unwrap()
+            .filter(|entry| entry.as_ref().unwrap().path().extension().unwrap() == "mpk")
+            .count();
+        assert!(num_shards > 1);
+
+        let model_after = create_model(&device).load_record(recorder.load(file, &device).unwrap());
+
+        let byte_recorder = BinBytesRecorder::<FullPrecisionSettings>::default();
+        assert_eq!(
+            byte_recorder.record(model_after.into_record(), ()).unwrap(),
+            byte_recorder
+                .record(model_before.into_record(), ())
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_can_load_single_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("model");
+        let device = Default::default();
+
+        let model_before = create_model(&device);
+        NamedMpkFileRecorder::<FullPrecisionSettings>::new()
+            .record(model_before.clone().into_record(), file.clone())
+            .unwrap();
+
+        let model_after = create_model(&device).load_record(
+            ShardedMpkFileRecorder::<FullPrecisionSettings>::new()
+                .load(file, &device)
+                .unwrap(),
+        );
+
+        let byte_recorder = BinBytesRecorder::<FullPrecisionSettings>::default();
+        assert_eq!(
+            byte_recorder.record(model_after.into_record(), ()).unwrap(),
+            byte_recorder
+                .record(model_before.into_record(), ())
+                .unwrap()
+        );
+    }
+}
@@ -0,0 +1,330 @@
+use crate::Dataset;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
+
+/// The maximum number of distinct values counted for each field.
+const MAX_DISTINCT_VALUES: usize = 1000;
+
+/// The statistics of the fields of the items of a dataset, computed by [Dataset::analyze].
+///
+/// The fields are the ones of the serialized items, named by their path in the item, such as
+/// `image.label`. The elements of the sequences are analyzed as the field `path[]`, and an item
+/// which isn't a struct is analyzed as the field with an empty name.
+#[derive(Clone, Debug, Default)]
+pub struct DatasetAnalysis {
+    /// The number of items.
+    pub num_items: usize,
+    /// The statistics of each field.
+    pub fields: BTreeMap<String, FieldStatistics>,
+}
+
+/// The type of a field value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FieldKind {
+    /// A boolean.
+    Bool,
+    /// A number.
+    Number,
+    /// A string.
+    String,
+    /// A sequence.
+    Sequence,
+    /// A struct or a map.
+    Struct,
+}
+
+/// The statistics of a field of the items of a dataset.
+#[derive(Clone, Debug, Default)]
+pub struct FieldStatistics {
+    /// The number of values.
+    pub count: usize,
+    /// The number of null values, including the `None` options and the NaN numbers.
+    pub nulls: usize,
+    /// The types of the values.
+    pub kinds: BTreeSet<FieldKind>,
+    /// The statistics of the numeric values.
+    pub numeric: Option<NumericStatistics>,
+    /// The number of occurrences of the string, boolean and integer values, such as the
+    /// distribution of the labels, with at most 1000 distinct values.
+    pub values: BTreeMap<String, usize>,
+    /// Whether some distinct values aren't counted in the [values](Self::values).
+    pub values_truncated: bool,
+    /// The statistics of the lengths of the sequence values.
+    pub lengths: Option<LengthStatistics>,
+}
+
+/// The statistics of numeric values.
+#[derive(Clone, Debug)]
+pub struct NumericStatistics {
+    /// The minimum value.
+    pub min: f64,
+    /// The maximum value.
+    pub max: f64,
+    /// The mean value.
+    pub mean: f64,
+    count: usize,
+}
+
+/// The statistics of the lengths of sequences.
+#[derive(Clone, Debug)]
+pub struct LengthStatistics {
+    /// The minimum length.
+    pub min: usize,
+    /// The maximum length.
+    pub max: usize,
+    /// The mean length.
+    pub mean: f64,
+    /// The number of sequences of each power of two lengths, the bucket `i` counting the
+    /// lengths in `[2^(i-1), 2^i)`, the first bucket counting the empty sequences.
+    pub histogram: Vec<usize>,
+}
+
+impl DatasetAnalysis {
+    /// Analyzes the items of a dataset.
+    pub fn new<D, I>(dataset: &D) -> Self
+    where
+        D: Dataset<I>,
+        I: Serialize,
+    {
+        let mut analysis = Self::default();
+
+        for item in dataset.iter() {
+            let value = serde_json::to_value(&item).expect("The items should be serializable");
+            analysis.add_value(String::new(), &value);
+            analysis.num_items += 1;
+        }
+
+        analysis
+    }
+
+    /// The issues found in the data, such as missing values or fields of different types,
+    /// which are better fixed before a long training.
+    pub fn issues(&self) -> Vec<String> {
+        let mut issues = Vec::new();
+
+        for (path, field) in self.fields.iter() {
+            let name = match path.is_empty() {
+                true => "The items".to_string(),
+                false => format!("The field `{path}`"),
+            };
+
+            // The fields of the sequence elements occur a variable number of times.
+            if !path.contains("[]") && field.count + field.nulls < self.num_items {
+                issues.push(format!(
+                    "{name} is missing in {} items",
+                    self.num_items - field.count - field.nulls
+                ));
+            }
+            if field.nulls > 0 {
+                issues.push(format!("{name} has {} null or NaN values", field.nulls));
+            }
+            if field.kinds.len() > 1 {
+                issues.push(format!("{name} has values of types {:?}", field.kinds));
+            }
+        }
+
+        issues
+    }
+
+    fn field(&mut self, path: &str) -> &mut FieldStatistics {
+        self.fields.entry(path.to_string()).or_default()
+    }
+
+    fn add_value(&mut self, path: String, value: &Value) {
+        match value {
+            Value::Null => self.field(&path).nulls += 1,
+            Value::Bool(value) => self.field(&path).add(FieldKind::Bool, Some(value)),
+            Value::String(value) => self.field(&path).add(FieldKind::String, Some(value)),
+            Value::Number(number) => {
+                let field = self.field(&path);
+                let value = number.as_f64().unwrap_or(f64::NAN);
+                match number.is_f64() {
+                    true => field.add::<String>(FieldKind::Number, None),
+                    false => field.add(FieldKind::Number, Some(number)),
+                }
+                field.add_number(value);
+            }
+            Value::Array(values) => {
+                let field = self.field(&path);
+                field.add::<String>(FieldKind::Sequence, None);
+                field.add_length(values.len());
+
+                let path = format!("{path}[]");
+                for value in values {
+                    self.add_value(path.clone(), value);
+                }
+            }
+            Value::Object(fields) => {
+                if !path.is_empty() {
+                    self.field(&path).add::<String>(FieldKind::Struct, None);
+                }
+
+                for (name, value) in fields {
+                    let path = match path.is_empty() {
+                        true => name.clone(),
+                        false => format!("{path}.{name}"),
+                    };
+                    self.add_value(path, value);
+                }
+            }
+        }
+    }
+}
+
+impl FieldStatistics {
+    fn add<V: Display>(&mut self, kind: FieldKind, value: Option<V>) {
+        self.count += 1;
+        self.kinds.insert(kind);
+
+        let Some(value) = value else {
+            return;
+        };
+        let value = value.to_string();
+
+        if let Some(count) = self.values.get_mut(&value) {
+            *count += 1;
+        } else if self.values.len() < MAX_DISTINCT_VALUES {
+            self.values.insert(value, 1);
+        } else {
+            self.values_truncated = true;
+        }
+    }
+
+    fn add_number(&mut self, value: f64) {
+        let numeric = self.numeric.get_or_insert(NumericStatistics {
+            min: value,
+            max: value,
+            mean: 0.0,
+            count: 0,
+        });
+        numeric.min = numeric.min.min(value);
+        numeric.max = numeric.max.max(value);
+        numeric.count += 1;
+        numeric.mean += (value - numeric.mean) / numeric.count as f64;
+    }
+
+    fn add_length(&mut self, length: usize) {
+        let lengths = self.lengths.get_or_insert(LengthStatistics {
+            min: length,
+            max: length,
+            mean: 0.0,
+            histogram: Vec::new(),
+        });
+        lengths.min = lengths.min.min(length);
+        lengths.max = lengths.max.max(length);
+
+        let bucket = (usize::BITS - length.leading_zeros()) as usize;
+        if lengths.histogram.len() <= bucket {
+            lengths.histogram.resize(bucket + 1, 0);
+        }
+        lengths.histogram[bucket] += 1;
+
+        let count = lengths.histogram.iter().sum::<usize>();
+        lengths.mean += (length as f64 - lengths.mean) / count as f64;
+    }
+}
+
+impl Display for DatasetAnalysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} items", self.num_items)?;
+
+        for (path, field) in self.fields.iter() {
+            let name = if path.is_empty() { "<item>" } else { path };
+            write!(f, "{name}: {} values, {} nulls", field.count, field.nulls)?;
+
+            if let Some(numeric) = &field.numeric {
+                write!(
+                    f,
+                    ", min {}, max {}, mean {:.4}",
+                    numeric.min, numeric.max, numeric.mean
+                )?;
+            }
+            if let Some(lengths) = &field.lengths {
+                write!(
+                    f,
+                    ", lengths from {} to {} (mean {:.1})",
+                    lengths.min, lengths.max, lengths.mean
+                )?;
+            }
+            if !field.values.is_empty() && field.values.len() <= 20 {
+                write!(f, ", values {:?}", field.values)?;
+            } else if !field.values.is_empty() {
+                write!(f, ", {} distinct values", field.values.len())?;
+            }
+            writeln!(f)?;
+        }
+
+        for issue in self.issues() {
+            writeln!(f, "Warning: {issue}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InMemDataset;
+
+    #[derive(Serialize, Clone)]
+    struct Item {
+        label: usize,
+        score: Option<f32>,
+        tokens: Vec<u32>,
+    }
+
+    fn dataset() -> InMemDataset<Item> {
+        InMemDataset::new(vec![
+            Item {
+                label: 1,
+                score: Some(0.5),
+                tokens: vec![1, 2, 3],
+            },
+            Item {
+                label: 1,
+                score: Some(1.5),
+                tokens: vec![4],
+            },
+            Item {
+                label: 0,
+                score: None,
+                tokens: vec![],
+            },
+        ])
+    }
+
+    #[test]
+    fn analyze_should_compute_the_field_statistics() {
+        let analysis = dataset().analyze();
+
+        assert_eq!(analysis.num_items, 3);
+
+        let label = &analysis.fields["label"];
+        assert_eq!(label.count, 3);
+        assert_eq!(
+            label.values,
+            BTreeMap::from([("0".to_string(), 1), ("1".to_string(), 2)])
+        );
+
+        let score = analysis.fields["score"].numeric.as_ref().unwrap();
+        assert_eq!((score.min, score.max, score.mean), (0.5, 1.5, 1.0));
+
+        let lengths = analysis.fields["tokens"].lengths.as_ref().unwrap();
+        assert_eq!((lengths.min, lengths.max), (0, 3));
+        assert_eq!(lengths.histogram, [1, 1, 1]);
+        assert_eq!(analysis.fields["tokens[]"].count, 4);
+    }
+
+    #[test]
+    fn analyze_should_report_the_issues() {
+        let analysis = dataset().analyze();
+
+        assert_eq!(
+            analysis.issues(),
+            ["The field `score` has 1 null or NaN values"]
+        );
+    }
+}
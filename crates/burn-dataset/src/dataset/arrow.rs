@@ -0,0 +1,325 @@
+use std::fs::File;
+use std::path::Path;
+use std::ptr::NonNull;
+use std::sync::Arc;
+
+use crate::Dataset;
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::ArrowPrimitiveType;
+use arrow_array::{Array, ArrayRef, RecordBatch};
+use arrow_buffer::Buffer;
+use arrow_ipc::convert::fb_to_schema;
+use arrow_ipc::reader::{read_footer_length, FileDecoder};
+use arrow_ipc::root_as_footer;
+use arrow_schema::{ArrowError, SchemaRef};
+use memmap2::Mmap;
+
+/// The length of the footer length and of the magic number ending the Arrow IPC files.
+const TRAILER_LENGTH: usize = 10;
+
+/// Arrow dataset error.
+#[derive(thiserror::Error, Debug)]
+pub enum ArrowDatasetError {
+    /// IO related error.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Arrow related error.
+    #[error("Arrow error: {0}")]
+    Arrow(#[from] ArrowError),
+
+    /// The file isn't an Arrow IPC file.
+    #[error("Invalid Arrow IPC file: {0}")]
+    InvalidFile(String),
+}
+
+type Result<T> = core::result::Result<T, ArrowDatasetError>;
+
+/// The record batches of an Arrow IPC file, backed by the memory map of the file.
+#[derive(Debug)]
+struct ArrowTable {
+    schema: SchemaRef,
+    batches: Vec<RecordBatch>,
+    /// The index of the first row of each batch.
+    offsets: Vec<usize>,
+    len: usize,
+}
+
+/// Dataset of the rows of an [Arrow IPC file][ipc], also known as Feather V2.
+///
+/// The file is memory-mapped and its columns are read in place, without copying nor
+/// deserializing them, so the [rows](ArrowRow) give access to the values of the fixed-size
+/// numeric columns as slices borrowed from the file. This is much faster than the row datasets
+/// for dense tabular data, such as embeddings or features, and the batchers can copy the slices
+/// of all the rows of a batch directly into a tensor.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let dataset = ArrowDataset::from_file("train.arrow")?;
+///
+/// // In the batcher.
+/// let mut features = Vec::new();
+/// for row in items {
+///     features.extend_from_slice(row.values::<Float32Type>("features").unwrap());
+/// }
+/// ```
+///
+/// [ipc]: https://arrow.apache.org/docs/format/Columnar.html#ipc-file-format
+#[derive(Clone, Debug)]
+pub struct ArrowDataset {
+    table: Arc<ArrowTable>,
+}
+
+impl ArrowDataset {
+    /// Memory-maps the Arrow IPC file and decodes its record batches.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let file = File::open(path)?;
+        // SAFETY: the file must not be modified while the dataset is used, like the files of the
+        // other datasets.
+        let mmap = Arc::new(unsafe { Mmap::map(&file)? });
+
+        let ptr = NonNull::new(mmap.as_ptr() as *mut u8)
+            .ok_or_else(|| ArrowDatasetError::InvalidFile("empty file".to_string()))?;
+        // SAFETY: the buffer keeps the memory map alive.
+        let buffer = unsafe { Buffer::from_custom_allocation(ptr, mmap.len(), mmap) };
+
+        Self::from_buffer(buffer)
+    }
+
+    fn from_buffer(buffer: Buffer) -> Result<Self> {
+        if buffer.len() < TRAILER_LENGTH {
+            return Err(ArrowDatasetError::InvalidFile("missing footer".to_string()));
+        }
+
+        let trailer_start = buffer.len() - TRAILER_LENGTH;
+        let footer_length = read_footer_length(buffer[trailer_start..].try_into().unwrap())?;
+        let footer = buffer
+            .get(trailer_start.saturating_sub(footer_length)..trailer_start)
+            .and_then(|footer| root_as_footer(footer).ok())
+            .ok_or_else(|| ArrowDatasetError::InvalidFile("invalid footer".to_string()))?;
+        let schema = footer
+            .schema()
+            .ok_or_else(|| ArrowDatasetError::InvalidFile("missing schema".to_string()))?;
+        let schema = Arc::new(fb_to_schema(schema));
+
+        let mut decoder = FileDecoder::new(schema.clone(), footer.version());
+
+        // The blocks only reference the buffer, so the arrays point into the memory map.
+        let block_data =
+            |offset: i64, length: usize| buffer.slice_with_length(offset as usize, length);
+
+        for block in footer.dictionaries().iter().flatten() {
+            let length = block.bodyLength() as usize + block.metaDataLength() as usize;
+            decoder.read_dictionary(block, &block_data(block.offset(), length))?;
+        }
+
+        let mut batches = Vec::new();
+        for block in footer.recordBatches().iter().flatten() {
+            let length = block.bodyLength() as usize + block.metaDataLength() as usize;
+            if let Some(batch) =
+                decoder.read_record_batch(block, &block_data(block.offset(), length))?
+            {
+                batches.push(batch);
+            }
+        }
+
+        let mut offsets = Vec::with_capacity(batches.len());
+        let mut len = 0;
+        for batch in batches.iter() {
+            offsets.push(len);
+            len += batch.num_rows();
+        }
+
+        Ok(Self {
+            table: Arc::new(ArrowTable {
+                schema,
+                batches,
+                offsets,
+                len,
+            }),
+        })
+    }
+
+    /// The schema of the file.
+    pub fn schema(&self) -> SchemaRef {
+        self.table.schema.clone()
+    }
+}
+
+impl Dataset<ArrowRow> for ArrowDataset {
+    fn get(&self, index: usize) -> Option<ArrowRow> {
+        if index >= self.table.len {
+            return None;
+        }
+
+        let batch = self
+            .table
+            .offsets
+            .partition_point(|offset| *offset <= index)
+            - 1;
+
+        Some(ArrowRow {
+            table: self.table.clone(),
+            batch,
+            row: index - self.table.offsets[batch],
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.table.len
+    }
+}
+
+/// A row of an [Arrow dataset](ArrowDataset), reading its values in place.
+///
+/// The rows are cheap handles which can be sent to the batchers, and the values are read with
+/// their Arrow type, such as [Float32Type](arrow_array::types::Float32Type).
+///
+/// # Panics
+///
+/// The accessors panic when the column doesn't exist or doesn't have the requested type.
+#[derive(Clone, Debug)]
+pub struct ArrowRow {
+    table: Arc<ArrowTable>,
+    batch: usize,
+    row: usize,
+}
+
+impl ArrowRow {
+    /// The value of a primitive column, `None` when the value is null.
+    pub fn value<T: ArrowPrimitiveType>(&self, column: &str) -> Option<T::Native> {
+        let array = self.column(column);
+        let array = array.as_primitive_opt::<T>().unwrap_or_else(|| {
+            panic!(
+                "The column `{column}` should have the type {:?}, got {:?}",
+                T::DATA_TYPE,
+                array.data_type()
+            )
+        });
+
+        match array.is_null(self.row) {
+            true => None,
+            false => Some(array.value(self.row)),
+        }
+    }
+
+    /// The values of a fixed-size list column, such as a feature vector, borrowed from the file
+    /// without copy. `None` when the list is null.
+    pub fn values<T: ArrowPrimitiveType>(&self, column: &str) -> Option<&[T::Native]> {
+        let array = self.column(column);
+        let expected = || {
+            format!(
+                "The column `{column}` should be a fixed-size list of {:?}, got {:?}",
+                T::DATA_TYPE,
+                array.data_type()
+            )
+        };
+
+        let list = array
+            .as_fixed_size_list_opt()
+            .unwrap_or_else(|| panic!("{}", expected()));
+        let values = list
+            .values()
+            .as_primitive_opt::<T>()
+            .unwrap_or_else(|| panic!("{}", expected()));
+
+        if list.is_null(self.row) {
+            return None;
+        }
+
+        let start = list.value_offset(self.row) as usize;
+        let length = list.value_length() as usize;
+
+        Some(&values.values()[start..start + length])
+    }
+
+    fn column(&self, column: &str) -> &ArrayRef {
+        self.table.batches[self.batch]
+            .column_by_name(column)
+            .unwrap_or_else(|| panic!("The column `{column}` doesn't exist"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::types::{Float32Type, Int64Type};
+    use arrow_array::{FixedSizeListArray, Float32Array, Int64Array};
+    use arrow_ipc::writer::FileWriter;
+    use arrow_schema::{DataType, Field, Schema};
+
+    fn write_file(path: &Path) {
+        let item = Arc::new(Field::new("item", DataType::Float32, false));
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("label", DataType::Int64, true),
+            Field::new("features", DataType::FixedSizeList(item.clone(), 2), true),
+        ]));
+        let batch = |labels: Vec<Option<i64>>, features: Vec<f32>| {
+            let features = FixedSizeListArray::try_new(
+                item.clone(),
+                2,
+                Arc::new(Float32Array::from(features)),
+                None,
+            )
+            .unwrap();
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![Arc::new(Int64Array::from(labels)), Arc::new(features)],
+            )
+            .unwrap()
+        };
+
+        let mut writer = FileWriter::try_new(File::create(path).unwrap(), &schema).unwrap();
+        writer
+            .write(&batch(vec![Some(1), None], vec![0.1, 0.2, 0.3, 0.4]))
+            .unwrap();
+        writer.write(&batch(vec![Some(3)], vec![0.5, 0.6])).unwrap();
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn arrow_dataset_should_read_the_rows_of_all_the_batches() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.arrow");
+        write_file(&path);
+
+        let dataset = ArrowDataset::from_file(&path).unwrap();
+
+        assert_eq!(dataset.len(), 3);
+        assert_eq!(dataset.schema().fields().len(), 2);
+
+        let labels = dataset
+            .iter()
+            .map(|row| row.value::<Int64Type>("label"))
+            .collect::<Vec<_>>();
+        assert_eq!(labels, [Some(1), None, Some(3)]);
+
+        let row = dataset.get(1).unwrap();
+        assert_eq!(row.values::<Float32Type>("features").unwrap(), [0.3, 0.4]);
+        let row = dataset.get(2).unwrap();
+        assert_eq!(row.values::<Float32Type>("features").unwrap(), [0.5, 0.6]);
+        assert!(dataset.get(3).is_none());
+    }
+
+    #[test]
+    #[should_panic = "The column `label` should have the type Float32, got Int64"]
+    fn arrow_row_should_panic_on_the_wrong_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.arrow");
+        write_file(&path);
+
+        let dataset = ArrowDataset::from_file(&path).unwrap();
+        dataset.get(0).unwrap().value::<Float32Type>("label");
+    }
+
+    #[test]
+    fn arrow_dataset_should_reject_invalid_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data.arrow");
+        std::fs::write(&path, b"not an arrow file").unwrap();
+
+        assert!(ArrowDataset::from_file(&path).is_err());
+    }
+}
@@ -1,6 +1,12 @@
+use std::path::Path;
 use std::sync::Arc;
 
-use crate::DatasetIterator;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::transform::{
+    CachedDataset, FilterDataset, MapDataset, PartialDataset, ShuffleBufferDataset,
+};
+use crate::{DatasetAnalysis, DatasetIterator};
 
 /// The dataset trait defines a basic collection of items with a predefined size.
 pub trait Dataset<I>: Send + Sync {
@@ -22,6 +28,68 @@ pub trait Dataset<I>: Send + Sync {
     {
         DatasetIterator::new(self)
     }
+
+    /// Computes the statistics of the fields of the items and finds the issues in the data,
+    /// reading all the items.
+    fn analyze(&self) -> DatasetAnalysis
+    where
+        Self: Sized,
+        I: Serialize,
+    {
+        DatasetAnalysis::new(self)
+    }
+
+    /// Maps each item with the function when it's accessed.
+    fn map<O, F>(self, func: F) -> MapDataset<Self, F, I>
+    where
+        Self: Sized,
+        F: Fn(I) -> O + Send + Sync,
+    {
+        MapDataset::new(self, func)
+    }
+
+    /// Keeps the items matching the predicate.
+    fn filter<P>(self, predicate: P) -> FilterDataset<Self, P, I>
+    where
+        Self: Sized,
+        P: Fn(&I) -> bool + Send + Sync,
+    {
+        FilterDataset::new(self, predicate)
+    }
+
+    /// Shuffles the items with a buffer of the given size and a fixed seed.
+    fn shuffle_buffer(self, buffer_size: usize, seed: u64) -> ShuffleBufferDataset<Self, I>
+    where
+        Self: Sized,
+    {
+        ShuffleBufferDataset::new(self, buffer_size, seed)
+    }
+
+    /// Keeps the first `num` items.
+    fn take(self, num: usize) -> PartialDataset<Self, I>
+    where
+        Self: Sized,
+    {
+        PartialDataset::new(self, 0, num)
+    }
+
+    /// Caches the items on disk in the given directory, computing them only once.
+    fn cached<P: AsRef<Path>>(self, directory: P) -> CachedDataset<Self, I>
+    where
+        Self: Sized,
+        I: Serialize + DeserializeOwned,
+    {
+        CachedDataset::new(self, directory)
+    }
+
+    /// Skips the first `num` items.
+    fn skip(self, num: usize) -> PartialDataset<Self, I>
+    where
+        Self: Sized,
+    {
+        let len = self.len();
+        PartialDataset::new(self, num.min(len), len)
+    }
 }
 
 impl<D, I> Dataset<I> for Arc<D>
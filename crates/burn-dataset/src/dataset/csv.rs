@@ -0,0 +1,253 @@
+use std::fs::File;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::Dataset;
+
+use csv::{Position, Reader, ReaderBuilder, StringRecord};
+use serde::de::DeserializeOwned;
+
+/// Csv dataset error.
+#[derive(thiserror::Error, Debug)]
+pub enum CsvDatasetError {
+    /// IO related error.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Csv related error, including the deserialization of the rows.
+    #[error("Csv error: {0}")]
+    Csv(#[from] csv::Error),
+}
+
+/// Loads a [Csv dataset](CsvDataset), deserializing the rows into items with serde.
+///
+/// The columns are matched with the fields of the items by name when the file has headers,
+/// otherwise by position.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[derive(Clone, Debug, Deserialize)]
+/// struct Item {
+///     text: String,
+///     label: u8,
+/// }
+///
+/// let dataset = CsvDatasetLoader::new("train.tsv")
+///     .with_delimiter(b'\t')
+///     .lazy()
+///     .load::<Item>()?;
+/// ```
+pub struct CsvDatasetLoader {
+    path: PathBuf,
+    delimiter: u8,
+    has_headers: bool,
+    lazy: bool,
+}
+
+impl CsvDatasetLoader {
+    /// Create the loader of the Csv file, separated by commas and with headers.
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            delimiter: b',',
+            has_headers: true,
+            lazy: false,
+        }
+    }
+
+    /// Sets the delimiter of the fields.
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Sets whether the first row contains the names of the columns.
+    pub fn with_headers(mut self, has_headers: bool) -> Self {
+        self.has_headers = has_headers;
+        self
+    }
+
+    /// Read the rows from the file when accessed, instead of loading all the items in memory.
+    ///
+    /// Only the position of each row is kept in memory, so files larger than the memory can be
+    /// used, at the cost of reading the file for each item.
+    pub fn lazy(mut self) -> Self {
+        self.lazy = true;
+        self
+    }
+
+    /// Loads the dataset.
+    ///
+    /// All the rows are deserialized in memory, or only indexed in lazy mode.
+    pub fn load<I>(self) -> Result<CsvDataset<I>, CsvDatasetError>
+    where
+        I: Clone + Send + Sync + DeserializeOwned,
+    {
+        let mut reader = ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .has_headers(self.has_headers)
+            .from_path(&self.path)?;
+
+        let storage = match self.lazy {
+            false => {
+                let items = reader.deserialize().collect::<Result<Vec<I>, _>>()?;
+                CsvStorage::Memory(items)
+            }
+            true => {
+                // The headers are read first, so the positions are the ones of the rows.
+                let headers = match self.has_headers {
+                    true => Some(reader.headers()?.clone()),
+                    false => None,
+                };
+
+                let mut positions = Vec::new();
+                let mut record = csv::ByteRecord::new();
+                loop {
+                    let position = reader.position().clone();
+                    if !reader.read_byte_record(&mut record)? {
+                        break;
+                    }
+                    positions.push(position);
+                }
+
+                CsvStorage::Lazy {
+                    reader: Mutex::new(reader),
+                    positions,
+                    headers,
+                }
+            }
+        };
+
+        Ok(CsvDataset {
+            path: self.path,
+            storage,
+            phantom: PhantomData,
+        })
+    }
+}
+
+enum CsvStorage<I> {
+    Memory(Vec<I>),
+    Lazy {
+        reader: Mutex<Reader<File>>,
+        positions: Vec<Position>,
+        headers: Option<StringRecord>,
+    },
+}
+
+/// Dataset of the rows of a Csv file, created with the [Csv dataset loader](CsvDatasetLoader).
+pub struct CsvDataset<I> {
+    path: PathBuf,
+    storage: CsvStorage<I>,
+    phantom: PhantomData<I>,
+}
+
+impl<I> CsvDataset<I> {
+    /// The path of the Csv file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl<I> Dataset<I> for CsvDataset<I>
+where
+    I: Clone + Send + Sync + DeserializeOwned,
+{
+    fn get(&self, index: usize) -> Option<I> {
+        match &self.storage {
+            CsvStorage::Memory(items) => items.get(index).cloned(),
+            CsvStorage::Lazy {
+                reader,
+                positions,
+                headers,
+            } => {
+                let position = positions.get(index)?;
+                let mut reader = reader.lock().unwrap();
+                let mut record = StringRecord::new();
+
+                reader.seek(position.clone()).unwrap();
+                reader.read_record(&mut record).unwrap();
+
+                Some(
+                    record
+                        .deserialize(headers.as_ref())
+                        .expect("Can deserialize the row of the Csv file"),
+                )
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        match &self.storage {
+            CsvStorage::Memory(items) => items.len(),
+            CsvStorage::Lazy { positions, .. } => positions.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::io::Write;
+
+    const CSV_FILE: &str = "tests/data/dataset.csv";
+
+    #[derive(Clone, Debug, Deserialize, PartialEq)]
+    struct SampleCsv {
+        column_str: String,
+        column_int: i64,
+        column_bool: bool,
+        column_float: f64,
+    }
+
+    fn sample(column_str: &str, column_bool: bool) -> SampleCsv {
+        SampleCsv {
+            column_str: column_str.to_string(),
+            column_int: 1,
+            column_bool,
+            column_float: 1.0,
+        }
+    }
+
+    #[test]
+    fn csv_dataset_should_load_the_rows() {
+        let dataset = CsvDatasetLoader::new(CSV_FILE).load::<SampleCsv>().unwrap();
+
+        assert_eq!(dataset.len(), 2);
+        assert_eq!(dataset.get(1), Some(sample("HI2", false)));
+        assert_eq!(dataset.get(2), None);
+    }
+
+    #[test]
+    fn lazy_csv_dataset_should_read_the_rows_in_any_order() {
+        let dataset = CsvDatasetLoader::new(CSV_FILE)
+            .lazy()
+            .load::<SampleCsv>()
+            .unwrap();
+
+        assert_eq!(dataset.len(), 2);
+        assert_eq!(dataset.get(1), Some(sample("HI2", false)));
+        assert_eq!(dataset.get(0), Some(sample("HI1", true)));
+        assert_eq!(dataset.get(2), None);
+    }
+
+    #[test]
+    fn lazy_csv_dataset_should_support_delimiter_without_headers() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"HI1;1;true;1.0\n\"H;2\";1;false;1.0\n")
+            .unwrap();
+
+        let dataset = CsvDatasetLoader::new(file.path())
+            .with_delimiter(b';')
+            .with_headers(false)
+            .lazy()
+            .load::<SampleCsv>()
+            .unwrap();
+
+        let items = dataset.iter().collect::<Vec<_>>();
+        assert_eq!(items, [sample("HI1", true), sample("H;2", false)]);
+    }
+}
@@ -1,7 +1,11 @@
+mod analysis;
 mod base;
+mod csv;
 mod in_memory;
 mod iterator;
 
+pub use self::csv::*;
+pub use analysis::*;
 pub use base::*;
 pub use in_memory::*;
 pub use iterator::*;
@@ -18,6 +22,27 @@ mod dataframe;
 #[cfg(feature = "dataframe")]
 pub use dataframe::*;
 
+#[cfg(feature = "arrow")]
+mod arrow;
+
+#[cfg(feature = "arrow")]
+pub use self::arrow::*;
+
+#[cfg(feature = "parquet")]
+mod parquet;
+
+#[cfg(feature = "parquet")]
+pub use self::parquet::*;
+
+#[cfg(feature = "object-store")]
+mod remote;
+
+#[cfg(feature = "webdataset")]
+mod webdataset;
+
+#[cfg(feature = "webdataset")]
+pub use webdataset::*;
+
 #[cfg(any(feature = "sqlite", feature = "sqlite-bundled"))]
 pub use sqlite::*;
 
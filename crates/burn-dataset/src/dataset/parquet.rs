@@ -0,0 +1,562 @@
+use std::cmp::Ordering;
+use std::fs::File;
+use std::marker::PhantomData;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use crate::Dataset;
+
+use parquet::errors::ParquetError;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::file::statistics::Statistics;
+use parquet::schema::types::Type;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+/// Parquet dataset error.
+#[derive(thiserror::Error, Debug)]
+pub enum ParquetDatasetError {
+    /// IO related error.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Parquet related error.
+    #[error("Parquet error: {0}")]
+    Parquet(#[from] ParquetError),
+
+    /// Serde related error.
+    #[error("Serde error: {0}")]
+    Serde(#[from] serde_json::Error),
+
+    /// The column doesn't exist in the file.
+    #[error("Unknown column: {0}")]
+    UnknownColumn(String),
+
+    /// Object store related error.
+    #[cfg(feature = "object-store")]
+    #[error("Object store error: {0}")]
+    ObjectStore(#[from] object_store::Error),
+
+    /// The URL of the file is invalid.
+    #[cfg(feature = "object-store")]
+    #[error("Invalid URL: {0}")]
+    Url(#[from] url::ParseError),
+}
+
+type Result<T> = core::result::Result<T, ParquetDatasetError>;
+
+/// The comparison of a [Parquet filter](ParquetDatasetLoader::with_filter).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParquetCompare {
+    /// The column is equal to the value.
+    Eq,
+    /// The column is not equal to the value.
+    NotEq,
+    /// The column is lower than the value.
+    Lt,
+    /// The column is lower than or equal to the value.
+    LtEq,
+    /// The column is greater than the value.
+    Gt,
+    /// The column is greater than or equal to the value.
+    GtEq,
+}
+
+impl ParquetCompare {
+    fn accepts(&self, ordering: Ordering) -> bool {
+        match self {
+            ParquetCompare::Eq => ordering.is_eq(),
+            ParquetCompare::NotEq => ordering.is_ne(),
+            ParquetCompare::Lt => ordering.is_lt(),
+            ParquetCompare::LtEq => ordering.is_le(),
+            ParquetCompare::Gt => ordering.is_gt(),
+            ParquetCompare::GtEq => ordering.is_ge(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Predicate {
+    column: String,
+    compare: ParquetCompare,
+    value: Value,
+}
+
+impl Predicate {
+    /// Whether the row matches the predicate.
+    fn matches(&self, row: &Map<String, Value>) -> bool {
+        let Some(field) = row.get(&self.column) else {
+            return false;
+        };
+
+        let ordering = match (field, &self.value) {
+            (Value::Number(field), Value::Number(value)) => field
+                .as_f64()
+                .zip(value.as_f64())
+                .and_then(|(field, value)| field.partial_cmp(&value)),
+            (Value::String(field), Value::String(value)) => Some(field.cmp(value)),
+            (field, value) if field == value => Some(Ordering::Equal),
+            _ => None,
+        };
+
+        match ordering {
+            Some(ordering) => self.compare.accepts(ordering),
+            // Values of different types are only different.
+            None => self.compare == ParquetCompare::NotEq,
+        }
+    }
+
+    /// Whether a row group may contain rows matching the predicate, given the statistics of the
+    /// column in the row group.
+    fn may_match(&self, statistics: Option<&Statistics>) -> bool {
+        let bounds = statistics.and_then(numeric_bounds);
+        let (Some((min, max)), Some(value)) = (bounds, self.value.as_f64()) else {
+            return true;
+        };
+
+        match self.compare {
+            ParquetCompare::Eq => min <= value && value <= max,
+            ParquetCompare::NotEq => !(min == value && max == value),
+            ParquetCompare::Lt => min < value,
+            ParquetCompare::LtEq => min <= value,
+            ParquetCompare::Gt => max > value,
+            ParquetCompare::GtEq => max >= value,
+        }
+    }
+}
+
+/// The minimum and the maximum of a numeric column chunk.
+fn numeric_bounds(statistics: &Statistics) -> Option<(f64, f64)> {
+    match statistics {
+        Statistics::Int32(stats) => Some((*stats.min_opt()? as f64, *stats.max_opt()? as f64)),
+        Statistics::Int64(stats) => Some((*stats.min_opt()? as f64, *stats.max_opt()? as f64)),
+        Statistics::Float(stats) => Some((*stats.min_opt()? as f64, *stats.max_opt()? as f64)),
+        Statistics::Double(stats) => Some((*stats.min_opt()?, *stats.max_opt()?)),
+        _ => None,
+    }
+}
+
+/// Loads a [Parquet dataset](ParquetDataset) from a local file, or from S3 and GCS with the
+/// `object-store` feature.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[derive(Clone, Debug, Deserialize)]
+/// struct Item {
+///     label: u8,
+///     features: Vec<f32>,
+/// }
+///
+/// let dataset = ParquetDatasetLoader::from_file("train.parquet")?
+///     .with_columns(["label", "features"])
+///     .with_filter("label", ParquetCompare::LtEq, 9)
+///     .load::<Item>()?;
+/// ```
+pub struct ParquetDatasetLoader {
+    reader: Box<dyn FileReader>,
+    columns: Option<Vec<String>>,
+    predicates: Vec<Predicate>,
+}
+
+impl ParquetDatasetLoader {
+    /// Opens the local Parquet file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let reader = SerializedFileReader::new(File::open(path)?)?;
+
+        Ok(Self::new(Box::new(reader)))
+    }
+
+    /// Opens the Parquet file at the URL, such as `s3://bucket/path/train.parquet` or
+    /// `gs://bucket/path/train.parquet`.
+    ///
+    /// The credentials are read from the usual environment variables of each store, such as
+    /// `AWS_ACCESS_KEY_ID` or `GOOGLE_SERVICE_ACCOUNT`. Only the byte ranges of the footer and
+    /// of the read column chunks are downloaded.
+    #[cfg(feature = "object-store")]
+    pub fn from_url(url: &str) -> Result<Self> {
+        let reader = SerializedFileReader::new(remote::ObjectStoreReader::new(url)?)?;
+
+        Ok(Self::new(Box::new(reader)))
+    }
+
+    fn new(reader: Box<dyn FileReader>) -> Self {
+        Self {
+            reader,
+            columns: None,
+            predicates: Vec::new(),
+        }
+    }
+
+    /// Only read the given top-level columns, all the columns being read by default.
+    pub fn with_columns<I, S>(mut self, columns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Only keep the rows where the column compares to the value.
+    ///
+    /// The row groups whose statistics show that no row can match a numeric filter are skipped
+    /// without being read. Multiple filters are combined, the rows matching all of them.
+    pub fn with_filter<V: Serialize>(
+        mut self,
+        column: &str,
+        compare: ParquetCompare,
+        value: V,
+    ) -> Self {
+        self.predicates.push(Predicate {
+            column: column.to_string(),
+            compare,
+            value: serde_json::to_value(value).expect("Can serialize the filter value"),
+        });
+        self
+    }
+
+    /// Loads the dataset, deserializing each row into an item.
+    ///
+    /// With filters, the remaining row groups are read once to count the matching rows.
+    pub fn load<I>(self) -> Result<ParquetDataset<I>>
+    where
+        I: Clone + Send + Sync + DeserializeOwned,
+    {
+        let metadata = self.reader.metadata();
+        let schema = metadata.file_metadata().schema_descr().root_schema();
+        let field = |name: &str| {
+            schema
+                .get_fields()
+                .iter()
+                .find(|field| field.name() == name)
+                .cloned()
+                .ok_or_else(|| ParquetDatasetError::UnknownColumn(name.to_string()))
+        };
+
+        for predicate in self.predicates.iter() {
+            field(&predicate.column)?;
+        }
+
+        // The filtered columns are read with the projected ones.
+        let projection = match &self.columns {
+            Some(columns) => {
+                let mut names = columns.clone();
+                for predicate in self.predicates.iter() {
+                    if !names.contains(&predicate.column) {
+                        names.push(predicate.column.clone());
+                    }
+                }
+
+                let fields = names
+                    .iter()
+                    .map(|name| field(name))
+                    .collect::<Result<Vec<_>>>()?;
+                Some(
+                    Type::group_type_builder(schema.name())
+                        .with_fields(fields)
+                        .build()?,
+                )
+            }
+            None => None,
+        };
+
+        let mut dataset = ParquetDataset {
+            reader: self.reader,
+            projection,
+            predicates: self.predicates,
+            row_groups: Vec::new(),
+            offsets: vec![0],
+            cache: Mutex::new(None),
+            phantom: PhantomData,
+        };
+
+        for index in 0..dataset.reader.num_row_groups() {
+            let row_group = dataset.reader.metadata().row_group(index);
+            let skipped = dataset.predicates.iter().any(|predicate| {
+                let statistics = row_group
+                    .columns()
+                    .iter()
+                    .find(|column| column.column_path().string() == predicate.column)
+                    .and_then(|column| column.statistics());
+                !predicate.may_match(statistics)
+            });
+            if skipped {
+                continue;
+            }
+
+            let num_rows = match dataset.predicates.is_empty() {
+                true => row_group.num_rows() as usize,
+                false => dataset.read_rows(index)?.len(),
+            };
+            if num_rows == 0 {
+                continue;
+            }
+
+            dataset.row_groups.push(index);
+            dataset.offsets.push(dataset.len() + num_rows);
+        }
+
+        Ok(dataset)
+    }
+}
+
+/// Dataset reading the rows of a Parquet file, created with the
+/// [Parquet dataset loader](ParquetDatasetLoader).
+///
+/// The rows are read one row group at a time, the last read row group being kept in memory, so
+/// files larger than the memory can be iterated. Random access to rows of different row groups
+/// reads a whole row group for each row, so the dataset is best shuffled by row group, or
+/// converted to another dataset first.
+pub struct ParquetDataset<I> {
+    reader: Box<dyn FileReader>,
+    projection: Option<Type>,
+    predicates: Vec<Predicate>,
+    /// The indices of the row groups with matching rows.
+    row_groups: Vec<usize>,
+    /// The index of the first item of each row group, followed by the number of items.
+    offsets: Vec<usize>,
+    cache: Mutex<Option<(usize, Arc<Vec<I>>)>>,
+    phantom: PhantomData<I>,
+}
+
+impl<I> ParquetDataset<I>
+where
+    I: Clone + Send + Sync + DeserializeOwned,
+{
+    /// Reads the matching rows of a row group.
+    fn read_rows(&self, row_group: usize) -> Result<Vec<Map<String, Value>>> {
+        let reader = self.reader.get_row_group(row_group)?;
+        let mut rows = Vec::new();
+
+        for row in reader.get_row_iter(self.projection.clone())? {
+            let Value::Object(row) = row?.to_json_value() else {
+                unreachable!("A row is a group of fields");
+            };
+
+            if self
+                .predicates
+                .iter()
+                .all(|predicate| predicate.matches(&row))
+            {
+                rows.push(row);
+            }
+        }
+
+        Ok(rows)
+    }
+
+    /// Reads the items of a row group, from the cache when it was the last one read.
+    fn items(&self, row_group: usize) -> Result<Arc<Vec<I>>> {
+        let mut cache = self.cache.lock().unwrap();
+        if let Some((index, items)) = cache.as_ref() {
+            if *index == row_group {
+                return Ok(items.clone());
+            }
+        }
+
+        let items = self
+            .read_rows(row_group)?
+            .into_iter()
+            .map(|row| serde_json::from_value(Value::Object(row)))
+            .collect::<core::result::Result<Vec<I>, _>>()?;
+        let items = Arc::new(items);
+        *cache = Some((row_group, items.clone()));
+
+        Ok(items)
+    }
+}
+
+impl<I> Dataset<I> for ParquetDataset<I>
+where
+    I: Clone + Send + Sync + DeserializeOwned,
+{
+    fn get(&self, index: usize) -> Option<I> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let position = self.offsets.partition_point(|offset| *offset <= index) - 1;
+        let items = self
+            .items(self.row_groups[position])
+            .expect("Can read the row group of the Parquet file");
+
+        items.get(index - self.offsets[position]).cloned()
+    }
+
+    fn len(&self) -> usize {
+        *self.offsets.last().unwrap()
+    }
+}
+
+#[cfg(feature = "object-store")]
+mod remote {
+    use super::Result;
+    use crate::dataset::remote::{RangeReader, RemoteObject};
+    use bytes::Bytes;
+    use parquet::errors::ParquetError;
+    use parquet::file::reader::{ChunkReader, Length};
+    use std::sync::Arc;
+
+    /// The size of the ranges downloaded when reading the page headers.
+    const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+    /// Reads the byte ranges of a file in an object store.
+    pub(super) struct ObjectStoreReader {
+        object: Arc<RemoteObject>,
+    }
+
+    impl ObjectStoreReader {
+        pub(super) fn new(url: &str) -> Result<Self> {
+            Ok(Self {
+                object: Arc::new(RemoteObject::open(url)?),
+            })
+        }
+    }
+
+    impl Length for ObjectStoreReader {
+        fn len(&self) -> u64 {
+            self.object.size()
+        }
+    }
+
+    impl ChunkReader for ObjectStoreReader {
+        type T = RangeReader;
+
+        fn get_read(&self, start: u64) -> parquet::errors::Result<Self::T> {
+            Ok(RangeReader::new(
+                self.object.clone(),
+                start,
+                READ_CHUNK_SIZE,
+            ))
+        }
+
+        fn get_bytes(&self, start: u64, length: usize) -> parquet::errors::Result<Bytes> {
+            self.object
+                .get(start, length)
+                .map_err(|err| ParquetError::External(Box::new(err)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parquet::data_type::{ByteArray, ByteArrayType, DoubleType, Int32Type};
+    use parquet::file::properties::WriterProperties;
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::schema::parser::parse_message_type;
+    use serde::Deserialize;
+    use tempfile::NamedTempFile;
+
+    #[derive(Clone, Debug, PartialEq, Deserialize)]
+    struct Sample {
+        id: i32,
+        name: String,
+    }
+
+    /// Writes two row groups, with the ids 0 to 4 and 5 to 9.
+    fn write_file() -> NamedTempFile {
+        let file = NamedTempFile::new().unwrap();
+        let schema = parse_message_type(
+            "message schema {
+                REQUIRED INT32 id;
+                REQUIRED BINARY name (UTF8);
+                REQUIRED DOUBLE score;
+            }",
+        )
+        .unwrap();
+        let mut writer = SerializedFileWriter::new(
+            file.reopen().unwrap(),
+            Arc::new(schema),
+            Arc::new(WriterProperties::builder().build()),
+        )
+        .unwrap();
+
+        for row_group in 0..2 {
+            let ids = (row_group * 5..row_group * 5 + 5).collect::<Vec<i32>>();
+            let names = ids
+                .iter()
+                .map(|id| ByteArray::from(format!("item {id}").as_str()))
+                .collect::<Vec<_>>();
+            let scores = ids.iter().map(|id| *id as f64 / 10.0).collect::<Vec<_>>();
+
+            let mut row_group = writer.next_row_group().unwrap();
+            let mut column = row_group.next_column().unwrap().unwrap();
+            column
+                .typed::<Int32Type>()
+                .write_batch(&ids, None, None)
+                .unwrap();
+            column.close().unwrap();
+            let mut column = row_group.next_column().unwrap().unwrap();
+            column
+                .typed::<ByteArrayType>()
+                .write_batch(&names, None, None)
+                .unwrap();
+            column.close().unwrap();
+            let mut column = row_group.next_column().unwrap().unwrap();
+            column
+                .typed::<DoubleType>()
+                .write_batch(&scores, None, None)
+                .unwrap();
+            column.close().unwrap();
+            row_group.close().unwrap();
+        }
+        writer.close().unwrap();
+
+        file
+    }
+
+    #[test]
+    fn parquet_dataset_should_read_all_row_groups() {
+        let file = write_file();
+
+        let dataset = ParquetDatasetLoader::from_file(file.path())
+            .unwrap()
+            .with_columns(["id", "name"])
+            .load::<Sample>()
+            .unwrap();
+
+        assert_eq!(dataset.len(), 10);
+        assert_eq!(
+            dataset.get(7),
+            Some(Sample {
+                id: 7,
+                name: "item 7".to_string()
+            })
+        );
+        assert_eq!(dataset.get(10), None);
+        let ids = dataset.iter().map(|item| item.id).collect::<Vec<_>>();
+        assert_eq!(ids, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn parquet_dataset_should_filter_rows_and_skip_row_groups() {
+        let file = write_file();
+
+        let dataset = ParquetDatasetLoader::from_file(file.path())
+            .unwrap()
+            .with_columns(["id", "name"])
+            .with_filter("score", ParquetCompare::Gt, 0.55)
+            .with_filter("id", ParquetCompare::NotEq, 8)
+            .load::<Sample>()
+            .unwrap();
+
+        let ids = dataset.iter().map(|item| item.id).collect::<Vec<_>>();
+        assert_eq!(ids, [6, 7, 9]);
+        // The first row group has no score greater than 0.55.
+        assert_eq!(dataset.row_groups, [1]);
+    }
+
+    #[test]
+    fn parquet_dataset_should_fail_with_unknown_column() {
+        let file = write_file();
+
+        let result = ParquetDatasetLoader::from_file(file.path())
+            .unwrap()
+            .with_columns(["id", "label"])
+            .load::<Sample>();
+
+        assert!(matches!(result, Err(ParquetDatasetError::UnknownColumn(name)) if name == "label"));
+    }
+}
@@ -0,0 +1,96 @@
+use bytes::{Buf, Bytes};
+use object_store::{path::Path, ObjectStore};
+use std::io::Read;
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+/// A file in an object store, such as S3, GCS or an HTTP server, read by byte ranges.
+pub(crate) struct RemoteObject {
+    store: Box<dyn ObjectStore>,
+    path: Path,
+    size: u64,
+    runtime: Runtime,
+}
+
+impl RemoteObject {
+    /// Opens the file at the URL, such as `s3://bucket/path/file` or `https://host/path/file`.
+    ///
+    /// The options of the stores are read from their environment variables, such as
+    /// `AWS_ACCESS_KEY_ID` or `GOOGLE_SERVICE_ACCOUNT`.
+    pub(crate) fn open<E>(url: &str) -> Result<Self, E>
+    where
+        E: From<url::ParseError> + From<object_store::Error> + From<std::io::Error>,
+    {
+        let url = url::Url::parse(url)?;
+        // The options of the stores are their environment variables in lowercase.
+        let options = std::env::vars().map(|(key, value)| (key.to_ascii_lowercase(), value));
+        let (store, path) = object_store::parse_url_opts(&url, options)?;
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()?;
+        let size = runtime.block_on(store.head(&path))?.size as u64;
+
+        Ok(Self {
+            store,
+            path,
+            size,
+            runtime,
+        })
+    }
+
+    /// The size of the file in bytes.
+    pub(crate) fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Downloads the byte range of the file.
+    pub(crate) fn get(&self, start: u64, length: usize) -> object_store::Result<Bytes> {
+        let start = start as usize;
+        self.runtime
+            .block_on(self.store.get_range(&self.path, start..start + length))
+    }
+}
+
+/// Reads a file from a position, downloading it by chunks.
+pub(crate) struct RangeReader {
+    object: Arc<RemoteObject>,
+    position: u64,
+    chunk_size: usize,
+    buffer: Bytes,
+}
+
+impl RangeReader {
+    /// Create the reader of the file from the position, downloading chunks of the given size.
+    pub(crate) fn new(object: Arc<RemoteObject>, position: u64, chunk_size: usize) -> Self {
+        Self {
+            object,
+            position,
+            chunk_size,
+            buffer: Bytes::new(),
+        }
+    }
+}
+
+impl Read for RangeReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if !self.buffer.has_remaining() {
+            let remaining = self.object.size.saturating_sub(self.position) as usize;
+            if remaining == 0 {
+                return Ok(0);
+            }
+
+            let length = remaining.min(self.chunk_size);
+            self.buffer = self
+                .object
+                .get(self.position, length)
+                .map_err(std::io::Error::other)?;
+            self.position += length as u64;
+        }
+
+        let length = buf.len().min(self.buffer.remaining());
+        self.buffer.copy_to_slice(&mut buf[..length]);
+
+        Ok(length)
+    }
+}
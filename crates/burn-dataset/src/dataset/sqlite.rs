@@ -8,6 +8,7 @@ use std::{
 
 use crate::Dataset;
 
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
 use gix_tempfile::{
     handle::{persist, Writable},
     AutoRemove, ContainingDirectory, Handle,
@@ -17,9 +18,17 @@ use r2d2_sqlite::{
     rusqlite::{OpenFlags, OptionalExtension},
     SqliteConnectionManager,
 };
+use rand::{rngs::StdRng, Rng, SeedableRng};
 use sanitize_filename::sanitize;
 use serde::{de::DeserializeOwned, Serialize};
 use serde_rusqlite::{columns_from_statement, from_row_with_columns};
+use std::io::{Read, Write};
+
+/// The column of the serialized items.
+const ITEM_COLUMN: &str = "item";
+
+/// The column of the serialized items compressed with deflate.
+const COMPRESSED_ITEM_COLUMN: &str = "item_deflate";
 
 /// Result type for the sqlite dataset.
 pub type Result<T> = core::result::Result<T, SqliteDatasetError>;
@@ -83,7 +92,9 @@ impl From<&'static str> for SqliteDatasetError {
 /// 2. The fields in the `I` struct can be serialized into a single column `item` in the table. In this case, the table
 ///    should have a single column named `item` of type `BLOB`. This is useful when the `I` struct contains complex fields
 ///    that cannot be mapped to a SQLite type, such as nested structs, vectors, etc. The serialization is done using
-///    [MessagePack](https://msgpack.org/).
+///    [MessagePack](https://msgpack.org/). The items written by a compressing
+///    [writer](SqliteDatasetWriter::with_compression) are stored in a column named `item_deflate`
+///    instead, and decompressed when read.
 ///
 /// Note: The code automatically figures out which of the above two cases is applicable, and uses the appropriate
 /// method to read the data from the table.
@@ -96,6 +107,7 @@ pub struct SqliteDataset<I> {
     len: usize,
     select_statement: String,
     row_serialized: bool,
+    compressed: bool,
     phantom: PhantomData<I>,
 }
 
@@ -106,13 +118,14 @@ impl<I> SqliteDataset<I> {
         let conn_pool = create_conn_pool(&db_file, false)?;
 
         // Determine how the table is stored
-        let row_serialized = Self::check_if_row_serialized(&conn_pool, split)?;
+        let item_column = Self::check_if_row_serialized(&conn_pool, split)?;
+        let row_serialized = item_column.is_some();
+        let compressed = item_column == Some(COMPRESSED_ITEM_COLUMN);
 
         // Create a select statement and save it
-        let select_statement = if row_serialized {
-            format!("select item from {split} where row_id = ?")
-        } else {
-            format!("select * from {split} where row_id = ?")
+        let select_statement = match item_column {
+            Some(column) => format!("select {column} from {split} where row_id = ?"),
+            None => format!("select * from {split} where row_id = ?"),
         };
 
         // Save the column names and the number of rows
@@ -126,17 +139,19 @@ impl<I> SqliteDataset<I> {
             len,
             select_statement,
             row_serialized,
+            compressed,
             phantom: PhantomData,
         })
     }
 
-    /// Returns true if table has two columns: row_id (integer) and item (blob).
+    /// Returns the item column if table has two columns: row_id (integer) and item or
+    /// item_deflate (blob).
     ///
     /// This is used to determine if the table is row serialized or not.
     fn check_if_row_serialized(
         conn_pool: &Pool<SqliteConnectionManager>,
         split: &str,
-    ) -> Result<bool> {
+    ) -> Result<Option<&'static str>> {
         // This struct is used to store the column name and type
         struct Column {
             name: String,
@@ -170,15 +185,16 @@ impl<I> SqliteDataset<I> {
             columns.push(column?);
         }
 
-        if columns.len() != 2 {
-            Ok(false)
-        } else {
-            // Check if the column names and types match the expected values
-            Ok(columns[0].name == "row_id"
-                && columns[0].ty == "integer"
-                && columns[1].name == "item"
-                && columns[1].ty == "blob")
+        if columns.len() != 2 || columns[0].name != "row_id" || columns[0].ty != "integer" {
+            return Ok(None);
         }
+
+        // Check if the item column name and type match the expected values
+        let item_column = [ITEM_COLUMN, COMPRESSED_ITEM_COLUMN]
+            .into_iter()
+            .find(|name| columns[1].name == *name && columns[1].ty == "blob");
+
+        Ok(item_column)
     }
 
     /// Get the database file name.
@@ -209,11 +225,18 @@ where
             // Fetch with a single column `item` and deserialize it with MessagePack
             statement
                 .query_row([row_id], |row| {
+                    let blob = row.get_ref(0).unwrap().as_blob().unwrap();
+
                     // Deserialize item (blob) with MessagePack (rmp-serde)
-                    Ok(
-                        rmp_serde::from_slice::<I>(row.get_ref(0).unwrap().as_blob().unwrap())
-                            .unwrap(),
-                    )
+                    if self.compressed {
+                        let mut serialized_item = Vec::new();
+                        DeflateDecoder::new(blob)
+                            .read_to_end(&mut serialized_item)
+                            .unwrap();
+                        Ok(rmp_serde::from_slice::<I>(&serialized_item).unwrap())
+                    } else {
+                        Ok(rmp_serde::from_slice::<I>(blob).unwrap())
+                    }
                 })
                 .optional() //Converts Error (not found) to None
                 .unwrap()
@@ -409,7 +432,11 @@ impl SqliteDatasetStorage {
 /// This `SqliteDatasetWriter` struct is a SQLite database writer dedicated to storing datasets.
 /// It retains the current writer's state and its database connection.
 ///
-/// Being thread-safe, this writer can be concurrently used across multiple threads.
+/// Being thread-safe, this writer can be concurrently used across multiple threads, such as
+/// parallel preprocessing workers sharing the writer with an [Arc].
+///
+/// The items are either written to a given split with [write](Self::write), or
+/// [appended](Self::append) to a split chosen with the [split ratios](Self::with_split_ratios).
 ///
 /// Typical applications include:
 ///
@@ -424,6 +451,9 @@ pub struct SqliteDatasetWriter<I> {
     overwrite: bool,
     conn_pool: Option<Pool<SqliteConnectionManager>>,
     is_completed: Arc<RwLock<bool>>,
+    compression: bool,
+    split_ratios: Vec<(String, f64)>,
+    split_seed: u64,
     phantom: PhantomData<I>,
 }
 
@@ -449,12 +479,49 @@ where
             overwrite,
             conn_pool: None,
             is_completed: Arc::new(RwLock::new(false)),
+            compression: false,
+            split_ratios: vec![("train".to_string(), 1.0)],
+            split_seed: 0,
             phantom: PhantomData,
         };
 
         writer.init()
     }
 
+    /// Sets whether the serialized items are compressed with deflate, which reduces the size of
+    /// the database of large items such as images or token sequences.
+    ///
+    /// The compression applies to the splits created after it is set, and is detected by the
+    /// [dataset](SqliteDataset) when reading the items.
+    pub fn with_compression(mut self, compression: bool) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Sets the splits of the [appended](Self::append) items with their ratios, such as
+    /// `[("train", 0.9), ("valid", 0.1)]`. All the items are appended to the `train` split by
+    /// default.
+    ///
+    /// The split of an item is drawn from a hash of its content and the seed, so the splits are
+    /// the same for each run regardless of the order of the items, even with parallel writers.
+    pub fn with_split_ratios(mut self, ratios: &[(&str, f64)], seed: u64) -> Self {
+        assert!(
+            !ratios.is_empty() && ratios.iter().all(|(_, ratio)| *ratio >= 0.0),
+            "The split ratios should be non-negative"
+        );
+        assert!(
+            ratios.iter().map(|(_, ratio)| ratio).sum::<f64>() > 0.0,
+            "The sum of the split ratios should be positive"
+        );
+
+        self.split_ratios = ratios
+            .iter()
+            .map(|(split, ratio)| (split.to_string(), *ratio))
+            .collect();
+        self.split_seed = seed;
+        self
+    }
+
     /// Initializes the dataset writer by creating the database file, tables, and connection pool.
     ///
     /// # Returns
@@ -516,6 +583,60 @@ where
     ///
     /// * A `Result` containing the index of the inserted row if successful, an error otherwise.
     pub fn write(&self, split: &str, item: &I) -> Result<usize> {
+        // Serialize the item using MessagePack
+        let serialized_item = rmp_serde::to_vec(item)?;
+
+        self.insert(split, serialized_item)
+    }
+
+    /// Serializes and appends an item to the split drawn from the
+    /// [split ratios](Self::with_split_ratios).
+    ///
+    /// # Returns
+    ///
+    /// * A `Result` containing the split and the index of the inserted row if successful, an
+    ///   error otherwise.
+    pub fn append(&self, item: &I) -> Result<(&str, usize)> {
+        let serialized_item = rmp_serde::to_vec(item)?;
+        let split = self.split_of(&serialized_item);
+        let index = self.insert(split, serialized_item)?;
+
+        Ok((split, index))
+    }
+
+    /// Draws the split of a serialized item from its hash.
+    fn split_of(&self, serialized_item: &[u8]) -> &str {
+        // The 64 bits FNV-1a hash, which is stable between the runs.
+        let hash = serialized_item
+            .iter()
+            .fold(0xcbf29ce484222325, |hash, byte| {
+                (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+            });
+        let total = self
+            .split_ratios
+            .iter()
+            .map(|(_, ratio)| ratio)
+            .sum::<f64>();
+        let mut sample = StdRng::seed_from_u64(hash ^ self.split_seed).gen::<f64>() * total;
+
+        for (split, ratio) in self.split_ratios.iter() {
+            if sample < *ratio {
+                return split;
+            }
+            sample -= ratio;
+        }
+
+        // Rounding errors can leave the sample past the last split.
+        let (split, _) = self
+            .split_ratios
+            .iter()
+            .rfind(|(_, ratio)| *ratio > 0.0)
+            .unwrap();
+        split
+    }
+
+    /// Inserts a serialized item in the table of the split.
+    fn insert(&self, split: &str, serialized_item: Vec<u8>) -> Result<usize> {
         // Acquire the read lock (wont't block other reads)
         let is_completed = self.is_completed.read().unwrap();
 
@@ -535,8 +656,13 @@ where
         let conn_pool = self.conn_pool.as_ref().unwrap();
         let conn = conn_pool.get()?;
 
-        // Serialize the item using MessagePack
-        let serialized_item = rmp_serde::to_vec(item)?;
+        let (column, serialized_item) = if self.compression {
+            let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(&serialized_item)?;
+            (COMPRESSED_ITEM_COLUMN, encoder.finish()?)
+        } else {
+            (ITEM_COLUMN, serialized_item)
+        };
 
         // Turn off the synchronous and journal mode for speed up
         // We are sacrificing durability for speed but it's okay because
@@ -545,7 +671,7 @@ where
         pragma_update_with_error_handling(&conn, "journal_mode", "OFF")?;
 
         // Insert the serialized item into the database
-        let insert_statement = format!("insert into {split} (item) values (?)", split = split);
+        let insert_statement = format!("insert into {split} ({column}) values (?)");
         conn.execute(insert_statement.as_str(), [serialized_item])?;
 
         // Get the primary key of the last inserted row and convert to index (row_id-1)
@@ -598,9 +724,13 @@ where
 
         let conn_pool = self.conn_pool.as_ref().unwrap();
         let connection = conn_pool.get()?;
+        let column = match self.compression {
+            true => COMPRESSED_ITEM_COLUMN,
+            false => ITEM_COLUMN,
+        };
         let create_table_statement = format!(
             "create table if not exists  {split} (row_id integer primary key autoincrement not \
-             null, item blob not null)"
+             null, {column} blob not null)"
         );
 
         connection.execute(create_table_statement.as_str(), [])?;
@@ -850,4 +980,51 @@ mod tests {
         assert_eq!(train.len(), record_count as usize / 2);
         assert_eq!(test.len(), record_count as usize / 2);
     }
+
+    #[rstest]
+    pub fn sqlite_writer_append_compressed_splits(writer_fixture: (Writer, TempDir)) {
+        let (writer, _tmp_dir) = writer_fixture;
+        let writer = Arc::new(
+            writer
+                .with_compression(true)
+                .with_split_ratios(&[("train", 0.8), ("valid", 0.2)], 42),
+        );
+        let record_count = 100;
+
+        let splits: Vec<String> = (0..record_count)
+            .into_par_iter()
+            .map(|index: i64| {
+                let sample = Complex {
+                    column_str: format!("test_{index}"),
+                    column_bytes: vec![0; 64],
+                    column_int: index,
+                    column_bool: true,
+                    column_float: 1.0,
+                    column_complex: vec![vec![vec![[1, index as u8, 3]]]],
+                };
+
+                let (split, _index) = writer.append(&sample).unwrap();
+                split.to_string()
+            })
+            .collect();
+
+        let mut writer = Arc::try_unwrap(writer).unwrap();
+        writer.set_completed().unwrap();
+
+        let train =
+            SqliteDataset::<Complex>::from_db_file(writer.db_file.clone(), "train").unwrap();
+        let valid = SqliteDataset::<Complex>::from_db_file(writer.db_file, "valid").unwrap();
+
+        let num_valid = splits.iter().filter(|split| *split == "valid").count();
+        assert_eq!(train.len() + valid.len(), record_count as usize);
+        assert_eq!(valid.len(), num_valid);
+        assert!(num_valid > 5 && num_valid < 40);
+        assert!(train.compressed);
+
+        // The split of an item only depends on its content.
+        let item = train.get(0).unwrap();
+        let index = item.column_int as usize;
+        assert_eq!(splits[index], "train");
+        assert_eq!(item.column_str, format!("test_{index}"));
+    }
 }
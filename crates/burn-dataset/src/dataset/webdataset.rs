@@ -0,0 +1,385 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Read;
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+
+use crate::InMemDataset;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+/// WebDataset error.
+#[derive(thiserror::Error, Debug)]
+pub enum WebDatasetError {
+    /// IO related error, including the reading of the archives.
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The shard pattern is invalid.
+    #[error("Invalid shard pattern: {0}")]
+    InvalidPattern(String),
+
+    /// The shard is an URL, which requires the `object-store` feature.
+    #[error("Unsupported shard URL, the object-store feature is required: {0}")]
+    UnsupportedUrl(String),
+
+    /// Object store related error.
+    #[cfg(feature = "object-store")]
+    #[error("Object store error: {0}")]
+    ObjectStore(#[from] object_store::Error),
+
+    /// The URL of the shard is invalid.
+    #[cfg(feature = "object-store")]
+    #[error("Invalid URL: {0}")]
+    Url(#[from] url::ParseError),
+}
+
+type Result<T> = core::result::Result<T, WebDatasetError>;
+
+/// The size of the ranges downloaded when streaming remote shards.
+#[cfg(feature = "object-store")]
+const READ_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+/// A sample of a WebDataset, made of the consecutive files of a shard sharing the same key.
+///
+/// The key of a file is its path up to the first dot of its name, and the rest of the name is
+/// its extension, so `images/0001.jpg` and `images/0001.cls.txt` are the `jpg` and `cls.txt`
+/// files of the sample `images/0001`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WebDatasetSample {
+    /// The key of the sample.
+    pub key: String,
+    /// The shard containing the sample.
+    pub shard: String,
+    /// The content of the files of the sample, by extension.
+    pub files: BTreeMap<String, Vec<u8>>,
+}
+
+impl WebDatasetSample {
+    /// The content of the file with the extension, such as `jpg` or `cls`.
+    pub fn get(&self, extension: &str) -> Option<&[u8]> {
+        self.files.get(extension).map(Vec::as_slice)
+    }
+}
+
+/// Reads the samples of the `.tar` shards of a [WebDataset](https://github.com/webdataset/webdataset).
+///
+/// The shards are local files or URLs, such as `s3://bucket/train-0001.tar` or
+/// `https://host/train-0001.tar` with the `object-store` feature, and are streamed, so they
+/// are never entirely downloaded nor kept in memory.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let loader = WebDatasetLoader::from_pattern("s3://bucket/train-{0000..0146}.tar")?
+///     .with_shard_shuffle(42);
+///
+/// for epoch in 0..num_epochs {
+///     for sample in loader.iter_epoch(epoch) {
+///         let sample = sample?;
+///         let image = sample.get("jpg");
+///     }
+/// }
+/// ```
+pub struct WebDatasetLoader {
+    shards: Vec<String>,
+    seed: Option<u64>,
+    prefetch: usize,
+}
+
+impl WebDatasetLoader {
+    /// Create the loader of the shards, read in the given order.
+    pub fn new<I, S>(shards: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            shards: shards.into_iter().map(Into::into).collect(),
+            seed: None,
+            prefetch: 64,
+        }
+    }
+
+    /// Create the loader of the shards matching the pattern, where the numeric ranges between
+    /// braces are expanded, such as `train-{0000..0146}.tar`.
+    pub fn from_pattern(pattern: &str) -> Result<Self> {
+        Ok(Self::new(expand_pattern(pattern)?))
+    }
+
+    /// Shuffle the order of the shards with the seed, changing at each epoch.
+    ///
+    /// The samples of a shard are still read in order, since the archives can only be streamed
+    /// sequentially, so a [shuffled dataset](crate::transform::ShuffledDataset) or a shuffle
+    /// buffer is still needed for a sample-level shuffling.
+    pub fn with_shard_shuffle(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets the number of samples read in advance in the background, 64 by default.
+    pub fn with_prefetch(mut self, prefetch: usize) -> Self {
+        self.prefetch = prefetch;
+        self
+    }
+
+    /// The shards of the dataset.
+    pub fn shards(&self) -> &[String] {
+        &self.shards
+    }
+
+    /// Iterates over the samples of the first epoch.
+    pub fn iter(&self) -> WebDatasetIterator {
+        self.iter_epoch(0)
+    }
+
+    /// Iterates over the samples of the epoch, whose number changes the order of the shards
+    /// when they are shuffled.
+    pub fn iter_epoch(&self, epoch: usize) -> WebDatasetIterator {
+        let mut shards = self.shards.clone();
+        if let Some(seed) = self.seed {
+            let mut rng = StdRng::seed_from_u64(seed.wrapping_add(epoch as u64));
+            shards.shuffle(&mut rng);
+        }
+
+        let (sender, receiver) = sync_channel(self.prefetch);
+        std::thread::spawn(move || {
+            for shard in shards {
+                let result =
+                    open_shard(&shard).and_then(|reader| send_shard(&shard, reader, &sender));
+
+                match result {
+                    Ok(true) => {}
+                    // The iterator was dropped.
+                    Ok(false) => return,
+                    Err(err) => {
+                        let _ = sender.send(Err(err));
+                        return;
+                    }
+                }
+            }
+        });
+
+        WebDatasetIterator { receiver }
+    }
+
+    /// Loads all the samples of the first epoch in memory.
+    pub fn load(&self) -> Result<InMemDataset<WebDatasetSample>> {
+        let items = self.iter().collect::<Result<Vec<_>>>()?;
+
+        Ok(InMemDataset::new(items))
+    }
+}
+
+/// Iterator over the samples of a WebDataset, read in the background by the
+/// [WebDataset loader](WebDatasetLoader).
+pub struct WebDatasetIterator {
+    receiver: Receiver<Result<WebDatasetSample>>,
+}
+
+impl Iterator for WebDatasetIterator {
+    type Item = Result<WebDatasetSample>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.recv().ok()
+    }
+}
+
+/// Opens the shard, streaming it when it's an URL.
+fn open_shard(shard: &str) -> Result<Box<dyn Read>> {
+    if !shard.contains("://") {
+        return Ok(Box::new(File::open(shard)?));
+    }
+
+    #[cfg(feature = "object-store")]
+    {
+        use crate::dataset::remote::{RangeReader, RemoteObject};
+        use std::sync::Arc;
+
+        let object = Arc::new(RemoteObject::open::<WebDatasetError>(shard)?);
+        Ok(Box::new(RangeReader::new(object, 0, READ_CHUNK_SIZE)))
+    }
+
+    #[cfg(not(feature = "object-store"))]
+    Err(WebDatasetError::UnsupportedUrl(shard.to_string()))
+}
+
+/// Sends the samples of the shard, returning false when the receiver is dropped.
+fn send_shard<R: Read>(
+    shard: &str,
+    reader: R,
+    sender: &SyncSender<Result<WebDatasetSample>>,
+) -> Result<bool> {
+    let mut archive = tar::Archive::new(reader);
+    let mut sample: Option<WebDatasetSample> = None;
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+
+        let path = entry.path()?.to_string_lossy().into_owned();
+        let Some((key, extension)) = split_key(&path) else {
+            continue;
+        };
+
+        if sample.as_ref().is_some_and(|sample| sample.key != key)
+            && sender.send(Ok(sample.take().unwrap())).is_err()
+        {
+            return Ok(false);
+        }
+
+        let mut content = Vec::with_capacity(entry.size() as usize);
+        entry.read_to_end(&mut content)?;
+
+        sample
+            .get_or_insert_with(|| WebDatasetSample {
+                key: key.to_string(),
+                shard: shard.to_string(),
+                files: BTreeMap::new(),
+            })
+            .files
+            .insert(extension.to_string(), content);
+    }
+
+    Ok(match sample {
+        Some(sample) => sender.send(Ok(sample)).is_ok(),
+        None => true,
+    })
+}
+
+/// Splits the path of a file into the key of its sample and its extension.
+fn split_key(path: &str) -> Option<(&str, &str)> {
+    let name = path.rfind('/').map_or(0, |index| index + 1);
+    let dot = name + path[name..].find('.')?;
+
+    Some((&path[..dot], &path[dot + 1..]))
+}
+
+/// Expands the numeric ranges between braces of the pattern, keeping the zero padding.
+fn expand_pattern(pattern: &str) -> Result<Vec<String>> {
+    let Some(open) = pattern.find('{') else {
+        return Ok(vec![pattern.to_string()]);
+    };
+    let invalid = || WebDatasetError::InvalidPattern(pattern.to_string());
+
+    let close = open + pattern[open..].find('}').ok_or_else(invalid)?;
+    let (first, last) = pattern[open + 1..close]
+        .split_once("..")
+        .ok_or_else(invalid)?;
+    let width = first.len();
+    let first = first.parse::<usize>().map_err(|_| invalid())?;
+    let last = last.parse::<usize>().map_err(|_| invalid())?;
+
+    let prefix = &pattern[..open];
+    let suffixes = expand_pattern(&pattern[close + 1..])?;
+
+    Ok((first..=last)
+        .flat_map(|index| {
+            suffixes
+                .iter()
+                .map(move |suffix| format!("{prefix}{index:0width$}{suffix}"))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Dataset;
+    use std::path::Path;
+
+    fn write_shard(path: &Path, files: &[(&str, &str)]) -> String {
+        let mut builder = tar::Builder::new(File::create(path).unwrap());
+        for (name, content) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            builder
+                .append_data(&mut header, name, content.as_bytes())
+                .unwrap();
+        }
+        builder.finish().unwrap();
+
+        path.to_string_lossy().into_owned()
+    }
+
+    fn shards(dir: &Path) -> Vec<String> {
+        vec![
+            write_shard(
+                &dir.join("shard-0.tar"),
+                &[
+                    ("images/0001.txt", "one"),
+                    ("images/0001.cls", "0"),
+                    ("images/0002.txt", "two"),
+                    ("images/0002.cls", "1"),
+                ],
+            ),
+            write_shard(
+                &dir.join("shard-1.tar"),
+                &[("images/0003.txt", "three"), ("images/0003.cls", "1")],
+            ),
+        ]
+    }
+
+    #[test]
+    fn webdataset_should_group_the_files_by_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let dataset = WebDatasetLoader::new(shards(dir.path())).load().unwrap();
+
+        assert_eq!(dataset.len(), 3);
+        let sample = dataset.get(1).unwrap();
+        assert_eq!(sample.key, "images/0002");
+        assert_eq!(sample.get("txt"), Some(b"two".as_slice()));
+        assert_eq!(sample.get("cls"), Some(b"1".as_slice()));
+        assert_eq!(dataset.get(2).unwrap().key, "images/0003");
+    }
+
+    #[test]
+    fn webdataset_should_shuffle_the_shards_with_the_seed() {
+        let dir = tempfile::tempdir().unwrap();
+        let shards = shards(dir.path());
+        let loader = WebDatasetLoader::new(shards.clone()).with_shard_shuffle(42);
+
+        let keys = |epoch| {
+            loader
+                .iter_epoch(epoch)
+                .map(|sample| sample.unwrap().key)
+                .collect::<Vec<_>>()
+        };
+
+        for epoch in 0..4 {
+            let keys = keys(epoch);
+            assert!(
+                keys == ["images/0001", "images/0002", "images/0003"]
+                    || keys == ["images/0003", "images/0001", "images/0002"]
+            );
+        }
+        assert_eq!(keys(3), keys(3));
+    }
+
+    #[test]
+    fn webdataset_should_fail_with_a_missing_shard() {
+        let loader = WebDatasetLoader::new(["missing.tar"]);
+
+        assert!(matches!(loader.load(), Err(WebDatasetError::Io(_))));
+    }
+
+    #[test]
+    fn expand_pattern_should_keep_the_padding() {
+        let shards = expand_pattern("s3://bucket/train-{008..011}.tar").unwrap();
+
+        assert_eq!(
+            shards,
+            [
+                "s3://bucket/train-008.tar",
+                "s3://bucket/train-009.tar",
+                "s3://bucket/train-010.tar",
+                "s3://bucket/train-011.tar",
+            ]
+        );
+        assert!(expand_pattern("train-{0..}.tar").is_err());
+    }
+}
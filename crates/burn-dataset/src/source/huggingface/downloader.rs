@@ -1,11 +1,13 @@
 use std::fs::{self, create_dir_all};
+use std::io::{BufRead, BufReader, Lines};
+use std::marker::PhantomData;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Child, ChildStdout, Command, Stdio};
 
 use crate::{SqliteDataset, SqliteDatasetError, SqliteDatasetStorage};
 
 use sanitize_filename::sanitize;
-use serde::de::DeserializeOwned;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use thiserror::Error;
 
 const PYTHON_SOURCE: &str = include_str!("importer.py");
@@ -36,6 +38,10 @@ pub enum ImporterError {
     /// venv environment is not initialized.
     #[error("venv environment is not initialized")]
     VenvNotInitialized,
+
+    /// Fail to stream the dataset.
+    #[error("stream: `{0}`")]
+    Stream(String),
 }
 
 /// Load a dataset from [huggingface datasets](https://huggingface.co/datasets).
@@ -136,7 +142,7 @@ impl HuggingfaceDatasetLoader {
     /// If the database file does not exist, it will be downloaded and imported.
     pub fn db_file(self) -> Result<PathBuf, ImporterError> {
         // determine (and create if needed) the base directory
-        let base_dir = SqliteDatasetStorage::base_dir(self.base_dir);
+        let base_dir = SqliteDatasetStorage::base_dir(self.base_dir.clone());
 
         if !base_dir.exists() {
             create_dir_all(&base_dir).expect("Failed to create base directory");
@@ -156,67 +162,274 @@ impl HuggingfaceDatasetLoader {
 
         // import the dataset if needed
         if !Path::new(&db_file).exists() {
-            import(
-                self.name,
-                self.subset,
-                db_file.clone(),
-                base_dir,
-                self.huggingface_token,
-                self.huggingface_cache_dir,
-                self.trust_remote_code,
-            )?;
+            let mut command = self.importer_command(&base_dir)?;
+
+            command.arg("--file");
+            command.arg(&db_file);
+
+            let mut handle = command.spawn().unwrap();
+            handle
+                .wait()
+                .map_err(|err| ImporterError::Unknown(format!("{err:?}")))?;
         }
 
         Ok(db_file)
     }
+
+    /// Stream the items of the split while they are downloaded, without storing the dataset.
+    ///
+    /// Nothing is written to the disk, which makes it possible to iterate over datasets larger
+    /// than the available space. A stream that was interrupted can be resumed with
+    /// [stream_from](Self::stream_from) and the [position](HuggingfaceStream::position) after the
+    /// last read item.
+    ///
+    /// # Example
+    /// ```no_run
+    ///  use burn_dataset::HuggingfaceDatasetLoader;
+    ///  use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize, Debug, Clone)]
+    /// struct TextItem {
+    ///     pub text: String,
+    /// }
+    ///
+    ///  let stream = HuggingfaceDatasetLoader::new("wikitext")
+    ///       .with_subset("wikitext-103-raw-v1")
+    ///       .stream::<TextItem>("train")
+    ///       .unwrap();
+    ///
+    ///  for batch in stream.batches(32) {
+    ///      let batch = batch.unwrap();
+    ///  }
+    /// ```
+    pub fn stream<I: DeserializeOwned>(
+        self,
+        split: &str,
+    ) -> Result<HuggingfaceStream<I>, ImporterError> {
+        self.stream_from(split, StreamPosition::default())
+    }
+
+    /// Stream the items of the split from the position of a previous stream.
+    ///
+    /// The download resumes from the shard and the offset of the position, without reading the
+    /// previous items. When the state of the stream is unknown, which is the case with older
+    /// versions of the `datasets` library, the items before the position are skipped instead.
+    pub fn stream_from<I: DeserializeOwned>(
+        self,
+        split: &str,
+        position: StreamPosition,
+    ) -> Result<HuggingfaceStream<I>, ImporterError> {
+        let base_dir = SqliteDatasetStorage::base_dir(self.base_dir.clone());
+
+        if !base_dir.exists() {
+            create_dir_all(&base_dir).expect("Failed to create base directory");
+        }
+
+        let mut command = self.importer_command(&base_dir)?;
+        stream_args(&mut command, split, &position);
+
+        HuggingfaceStream::spawn(command, position)
+    }
+
+    /// Create the command running the importer script with the options of the dataset.
+    fn importer_command(&self, base_dir: &Path) -> Result<Command, ImporterError> {
+        let venv_python_path = install_python_deps(base_dir)?;
+
+        let mut command = Command::new(venv_python_path);
+
+        command.arg(importer_script_path(base_dir));
+
+        command.arg("--name");
+        command.arg(&self.name);
+
+        if let Some(subset) = &self.subset {
+            command.arg("--subset");
+            command.arg(subset);
+        }
+
+        if let Some(huggingface_token) = &self.huggingface_token {
+            command.arg("--token");
+            command.arg(huggingface_token);
+        }
+
+        if let Some(huggingface_cache_dir) = &self.huggingface_cache_dir {
+            command.arg("--cache_dir");
+            command.arg(huggingface_cache_dir);
+        }
+        if self.trust_remote_code {
+            command.arg("--trust_remote_code");
+            command.arg("True");
+        }
+
+        Ok(command)
+    }
 }
 
-/// Import a dataset from huggingface. The transformed dataset is stored as sqlite database.
-fn import(
-    name: String,
-    subset: Option<String>,
-    base_file: PathBuf,
-    base_dir: PathBuf,
-    huggingface_token: Option<String>,
-    huggingface_cache_dir: Option<String>,
-    trust_remote_code: bool,
-) -> Result<(), ImporterError> {
-    let venv_python_path = install_python_deps(&base_dir)?;
+/// Add the arguments streaming the split from the position to the importer command.
+fn stream_args(command: &mut Command, split: &str, position: &StreamPosition) {
+    command.arg("--stream");
+    command.arg("--split");
+    command.arg(split);
+    command.arg("--skip");
+    command.arg(position.index.to_string());
+
+    if let Some(state) = &position.state {
+        command.arg("--state");
+        command.arg(state.to_string());
+    }
+}
 
-    let mut command = Command::new(venv_python_path);
+/// The position of a [huggingface stream](HuggingfaceStream), to be saved with the checkpoints
+/// to [resume](HuggingfaceDatasetLoader::stream_from) the stream after an interruption.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StreamPosition {
+    /// The number of items already read.
+    pub index: usize,
+    /// The state of the streamed dataset after the last read item, giving its shard and offset.
+    pub state: Option<serde_json::Value>,
+}
 
-    command.arg(importer_script_path(&base_dir));
+/// A line written by the importer for each streamed item.
+#[derive(Deserialize)]
+struct StreamLine<I> {
+    row: I,
+    state: Option<serde_json::Value>,
+}
 
-    command.arg("--name");
-    command.arg(name);
+/// Items of a split of a huggingface dataset, streamed while they are downloaded by the
+/// [huggingface dataset loader](HuggingfaceDatasetLoader::stream).
+pub struct HuggingfaceStream<I> {
+    child: Child,
+    lines: Lines<BufReader<ChildStdout>>,
+    position: StreamPosition,
+    done: bool,
+    _item: PhantomData<I>,
+}
 
-    command.arg("--file");
-    command.arg(base_file);
+impl<I> HuggingfaceStream<I> {
+    fn spawn(mut command: Command, position: StreamPosition) -> Result<Self, ImporterError> {
+        command.stdout(Stdio::piped());
+
+        let mut child = command
+            .spawn()
+            .map_err(|err| ImporterError::Unknown(format!("{err:?}")))?;
+        let stdout = BufReader::new(child.stdout.take().expect("Stdout should be piped"));
+
+        Ok(Self {
+            child,
+            lines: stdout.lines(),
+            position,
+            done: false,
+            _item: PhantomData,
+        })
+    }
 
-    if let Some(subset) = subset {
-        command.arg("--subset");
-        command.arg(subset);
+    /// The position after the last read item, to [resume](HuggingfaceDatasetLoader::stream_from)
+    /// the stream after an interruption.
+    pub fn position(&self) -> StreamPosition {
+        self.position.clone()
     }
 
-    if let Some(huggingface_token) = huggingface_token {
-        command.arg("--token");
-        command.arg(huggingface_token);
+    /// Group the items into batches of the given size, the last one being smaller if the
+    /// number of items isn't a multiple of the batch size.
+    pub fn batches(self, batch_size: usize) -> HuggingfaceBatches<I> {
+        assert!(batch_size > 0, "The batch size should be at least one item");
+
+        HuggingfaceBatches {
+            stream: self,
+            batch_size,
+        }
     }
+}
+
+impl<I: DeserializeOwned> Iterator for HuggingfaceStream<I> {
+    type Item = Result<I, ImporterError>;
 
-    if let Some(huggingface_cache_dir) = huggingface_cache_dir {
-        command.arg("--cache_dir");
-        command.arg(huggingface_cache_dir);
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.lines.next() {
+            Some(Ok(line)) => {
+                self.position.index += 1;
+                match serde_json::from_str::<StreamLine<I>>(&line) {
+                    Ok(line) => {
+                        self.position.state = line.state;
+                        Some(Ok(line.row))
+                    }
+                    Err(err) => {
+                        // The items are skipped up to the position when resuming.
+                        self.position.state = None;
+                        Some(Err(ImporterError::Stream(err.to_string())))
+                    }
+                }
+            }
+            Some(Err(err)) => {
+                self.done = true;
+                Some(Err(ImporterError::Stream(err.to_string())))
+            }
+            None => {
+                self.done = true;
+                // The importer fails when the download is interrupted.
+                match self.child.wait() {
+                    Ok(status) if status.success() => None,
+                    Ok(status) => Some(Err(ImporterError::Stream(format!(
+                        "importer exited with {status} at position {}",
+                        self.position.index
+                    )))),
+                    Err(err) => Some(Err(ImporterError::Stream(err.to_string()))),
+                }
+            }
+        }
     }
-    if trust_remote_code {
-        command.arg("--trust_remote_code");
-        command.arg("True");
+}
+
+impl<I> Drop for HuggingfaceStream<I> {
+    fn drop(&mut self) {
+        // Stop the download when the stream isn't entirely read.
+        if !self.done {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
     }
-    let mut handle = command.spawn().unwrap();
-    handle
-        .wait()
-        .map_err(|err| ImporterError::Unknown(format!("{err:?}")))?;
+}
+
+/// Batches of the items of a [huggingface stream](HuggingfaceStream).
+pub struct HuggingfaceBatches<I> {
+    stream: HuggingfaceStream<I>,
+    batch_size: usize,
+}
+
+impl<I> HuggingfaceBatches<I> {
+    /// The position after the last batch.
+    pub fn position(&self) -> StreamPosition {
+        self.stream.position()
+    }
+}
+
+impl<I: DeserializeOwned> Iterator for HuggingfaceBatches<I> {
+    type Item = Result<Vec<I>, ImporterError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut batch = Vec::with_capacity(self.batch_size);
+
+        for item in self.stream.by_ref() {
+            match item {
+                Ok(item) => batch.push(item),
+                Err(err) => return Some(Err(err)),
+            }
 
-    Ok(())
+            if batch.len() == self.batch_size {
+                break;
+            }
+        }
+
+        match batch.is_empty() {
+            true => None,
+            false => Some(Ok(batch)),
+        }
+    }
 }
 
 /// check python --version output is `Python 3.x.x`
@@ -307,3 +520,59 @@ fn install_python_deps(base_dir: &Path) -> Result<PathBuf, ImporterError> {
 
     Ok(venv_python_path)
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// A command printing the lines of an importer.
+    fn importer(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(script).arg("importer");
+        command
+    }
+
+    #[test]
+    fn stream_should_keep_the_state_of_the_last_item() {
+        let command = importer(
+            r#"for offset in 1 2 3; do echo "{\"row\": $offset, \"state\": {\"offset\": $offset}}"; done"#,
+        );
+        let mut stream = HuggingfaceStream::<usize>::spawn(command, StreamPosition::default())
+            .unwrap()
+            .batches(2);
+
+        assert_eq!(stream.next().unwrap().unwrap(), [1, 2]);
+        assert_eq!(
+            stream.position(),
+            StreamPosition {
+                index: 2,
+                state: Some(json!({ "offset": 2 })),
+            }
+        );
+        assert_eq!(stream.next().unwrap().unwrap(), [3]);
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    fn stream_should_resume_from_the_state_of_the_position() {
+        let position = StreamPosition {
+            index: 2,
+            state: Some(json!({ "offset": 2 })),
+        };
+        // The importer echoes the number of skipped items and the state it resumes from.
+        let mut command = importer(r#"echo "{\"row\": $5, \"state\": $7}""#);
+        stream_args(&mut command, "train", &position);
+        let mut stream = HuggingfaceStream::<usize>::spawn(command, position).unwrap();
+
+        assert_eq!(stream.next().unwrap().unwrap(), 2);
+        assert_eq!(
+            stream.position(),
+            StreamPosition {
+                index: 3,
+                state: Some(json!({ "offset": 2 })),
+            }
+        );
+        assert!(stream.next().is_none());
+    }
+}
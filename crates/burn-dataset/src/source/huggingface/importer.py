@@ -1,4 +1,6 @@
 import argparse
+import json
+import sys
 
 import pyarrow as pa
 from datasets import Audio, Image, load_dataset
@@ -81,6 +83,75 @@ def download_and_export(
     print_table_info(engine)
 
 
+def stream(
+    name: str,
+    subset: str,
+    split: str,
+    skip: int,
+    state: str,
+    token: str,
+    cache_dir: str,
+    trust_remote_code: bool,
+):
+    """
+    Stream a split of a dataset from HuggingFace, writing each row as a JSON line to stdout
+    along with the state of the stream after the row.
+    The rows are flattened like the exported ones, so the same rust items can be used.
+    The stream resumes from the state without reading the previous shards, or skips the
+    given number of rows when the state is unknown.
+    """
+
+    print(f"Streaming dataset: {name} - {subset} - {split}", file=sys.stderr)
+
+    dataset = load_dataset(
+        name,
+        subset,
+        split=split,
+        streaming=True,
+        cache_dir=cache_dir,
+        use_auth_token=token,
+        trust_remote_code=trust_remote_code,
+    )
+
+    # The features are unknown for some streamed datasets
+    if dataset.features is not None:
+        dataset = disable_decoding(dataset)
+
+    if state is not None:
+        print("Resuming from the checkpointed state", file=sys.stderr)
+        dataset.load_state_dict(json.loads(state))
+    elif skip > 0:
+        print(f"Skipping {skip} rows", file=sys.stderr)
+        dataset = dataset.skip(skip)
+
+    # The state of the stream is only available with recent versions of the datasets library
+    resumable = hasattr(dataset, "state_dict")
+
+    for row in dataset:
+        line = {
+            "row": flatten_row(row),
+            "state": dataset.state_dict() if resumable else None,
+        }
+        sys.stdout.write(json.dumps(line, default=list))
+        sys.stdout.write("\n")
+
+    sys.stdout.flush()
+
+
+def flatten_row(row, prefix=""):
+    """
+    Flatten the nested fields of a row, joining the names with underscores like the
+    flattened and renamed columns of the exported datasets.
+    """
+    flat = {}
+    for name, value in row.items():
+        if isinstance(value, dict):
+            flat.update(flatten_row(value, f"{prefix}{name}_"))
+        else:
+            flat[f"{prefix}{name}"] = value
+    return flat
+
+
 def disable_decoding(dataset):
     """
     Disable decoding for audio and image fields. The fields will be saved as raw file bytes.
@@ -158,7 +229,11 @@ def parse_args():
         "--name", type=str, help="Name of the dataset to download", required=True
     )
     parser.add_argument(
-        "--file", type=str, help="Base file name where the data is saved", required=True
+        "--file",
+        type=str,
+        help="Base file name where the data is saved",
+        required=False,
+        default=None,
     )
     parser.add_argument(
         "--subset", type=str, help="Subset name", required=False, default=None
@@ -181,12 +256,48 @@ def parse_args():
         default=None,
     )
 
+    parser.add_argument(
+        "--stream",
+        action="store_true",
+        help="Stream the rows of a split to stdout instead of exporting the dataset",
+    )
+    parser.add_argument(
+        "--split", type=str, help="Split name to stream", required=False, default=None
+    )
+    parser.add_argument(
+        "--skip",
+        type=int,
+        help="Number of rows to skip when streaming",
+        required=False,
+        default=0,
+    )
+    parser.add_argument(
+        "--state",
+        type=str,
+        help="JSON state of the stream to resume from, instead of skipping rows",
+        required=False,
+        default=None,
+    )
+
     return parser.parse_args()
 
 
 def run():
     args = parse_args()
 
+    if args.stream:
+        stream(
+            args.name,
+            args.subset,
+            args.split,
+            args.skip,
+            args.state,
+            args.token,
+            args.cache_dir,
+            args.trust_remote_code,
+        )
+        return
+
     download_and_export(
         args.name,
         args.subset,
@@ -0,0 +1,265 @@
+use crate::Dataset;
+use serde::{de::DeserializeOwned, Serialize};
+use std::fs::{self, File};
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+const CACHE_PREFIX: &str = "cache-";
+
+/// Dataset caching the items of an inner dataset on disk, created with [Dataset::cached].
+///
+/// All the items are computed and written to the cache directory the first time the dataset
+/// is accessed, and read from the cache afterward, including by the next runs. This avoids
+/// recomputing expensive transformations, such as tokenization, at each epoch.
+///
+/// The cache files are named after a hash of the [configuration](Self::with_config) of the
+/// transformations, the number of items and their type. When one of them changes, the cache is
+/// rebuilt and the stale cache files of the directory are removed, so each cached dataset
+/// needs its own directory.
+pub struct CachedDataset<D, I> {
+    dataset: D,
+    directory: PathBuf,
+    hash: u64,
+    cache: OnceLock<Cache>,
+    input: PhantomData<I>,
+}
+
+/// The data file of the cache with the offsets of the items.
+struct Cache {
+    file: Mutex<File>,
+    offsets: Vec<u64>,
+}
+
+impl<D, I> CachedDataset<D, I>
+where
+    D: Dataset<I>,
+    I: Serialize + DeserializeOwned,
+{
+    /// Creates a new cached dataset writing the cache in the given directory.
+    pub fn new<P: AsRef<Path>>(dataset: D, directory: P) -> Self {
+        let mut dataset = Self {
+            dataset,
+            directory: directory.as_ref().to_path_buf(),
+            hash: 0,
+            cache: OnceLock::new(),
+            input: PhantomData,
+        };
+        dataset.hash = dataset.hash_config(&());
+        dataset
+    }
+
+    /// Sets the configuration of the transformations of the inner dataset, invalidating the
+    /// cache when it changes.
+    pub fn with_config<C: Serialize>(mut self, config: &C) -> Self {
+        self.hash = self.hash_config(config);
+        self
+    }
+
+    fn hash_config<C: Serialize>(&self, config: &C) -> u64 {
+        let config = rmp_serde::to_vec(config).expect("The config should be serializable");
+
+        let mut bytes = config;
+        bytes.extend((self.dataset.len() as u64).to_le_bytes());
+        bytes.extend(std::any::type_name::<I>().as_bytes());
+
+        fnv1a(&bytes)
+    }
+
+    fn paths(&self) -> (PathBuf, PathBuf) {
+        let name = format!("{CACHE_PREFIX}{:016x}", self.hash);
+        (
+            self.directory.join(format!("{name}.bin")),
+            self.directory.join(format!("{name}.idx")),
+        )
+    }
+
+    fn cache(&self) -> &Cache {
+        self.cache.get_or_init(|| {
+            let (data_path, index_path) = self.paths();
+
+            // The index is written last, so the cache is complete when it exists.
+            let offsets = match fs::read(&index_path) {
+                Ok(index) => rmp_serde::from_slice(&index).expect("The cache index is corrupted"),
+                Err(_) => self.build(&data_path, &index_path),
+            };
+            let file = File::open(&data_path).expect("The cache data file should be readable");
+
+            Cache {
+                file: Mutex::new(file),
+                offsets,
+            }
+        })
+    }
+
+    fn build(&self, data_path: &Path, index_path: &Path) -> Vec<u64> {
+        fs::create_dir_all(&self.directory).expect("The cache directory should be writable");
+        self.remove_stale();
+
+        // The files are written under unique names and renamed once complete, so the processes
+        // building the same cache concurrently never read or truncate a partial file.
+        let tmp_data_path = tmp_path(data_path);
+        let file = File::create(&tmp_data_path).expect("The cache data file should be writable");
+        let mut writer = BufWriter::new(file);
+        let mut offset = 0;
+        let mut offsets = vec![offset];
+
+        for item in self.dataset.iter() {
+            let bytes = rmp_serde::to_vec(&item).expect("The items should be serializable");
+            writer
+                .write_all(&bytes)
+                .expect("The cache data file should be writable");
+            offset += bytes.len() as u64;
+            offsets.push(offset);
+        }
+        writer
+            .flush()
+            .expect("The cache data file should be writable");
+        fs::rename(&tmp_data_path, data_path).expect("The cache data file should be writable");
+
+        let index = rmp_serde::to_vec(&offsets).expect("The offsets are serializable");
+        let tmp_index_path = tmp_path(index_path);
+        fs::write(&tmp_index_path, index).expect("The cache index should be writable");
+        fs::rename(&tmp_index_path, index_path).expect("The cache index should be writable");
+
+        offsets
+    }
+
+    /// Removes the cache files of the previous configurations, keeping the ones of the current
+    /// configuration which can be written by another process.
+    fn remove_stale(&self) {
+        let Ok(entries) = fs::read_dir(&self.directory) else {
+            return;
+        };
+        let current = format!("{CACHE_PREFIX}{:016x}", self.hash);
+
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if name.starts_with(CACHE_PREFIX) && !name.starts_with(&current) {
+                fs::remove_file(entry.path()).ok();
+            }
+        }
+    }
+}
+
+/// A path next to the given one, unique to the process and to the call.
+fn tmp_path(path: &Path) -> PathBuf {
+    static COUNT: AtomicUsize = AtomicUsize::new(0);
+    let count = COUNT.fetch_add(1, Ordering::Relaxed);
+
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}-{count}.tmp", std::process::id()));
+    PathBuf::from(name)
+}
+
+impl<D, I> Dataset<I> for CachedDataset<D, I>
+where
+    D: Dataset<I>,
+    I: Serialize + DeserializeOwned + Send + Sync,
+{
+    fn get(&self, index: usize) -> Option<I> {
+        let cache = self.cache();
+        let start = *cache.offsets.get(index)?;
+        let end = *cache.offsets.get(index + 1)?;
+
+        let mut bytes = vec![0; (end - start) as usize];
+        let mut file = cache.file.lock().unwrap();
+        file.seek(SeekFrom::Start(start))
+            .and_then(|_| file.read_exact(&mut bytes))
+            .expect("The cache data file should be readable");
+        drop(file);
+
+        Some(rmp_serde::from_slice(&bytes).expect("The cache data file is corrupted"))
+    }
+
+    fn len(&self) -> usize {
+        self.dataset.len()
+    }
+}
+
+/// The 64 bits FNV-1a hash, which is stable between the runs.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InMemDataset;
+    use std::sync::Arc;
+
+    #[test]
+    fn cached_dataset_should_compute_the_items_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let dataset = |calls: Arc<AtomicUsize>| {
+            InMemDataset::new(vec![1, 2, 3])
+                .map(move |item: i32| {
+                    calls.fetch_add(1, Ordering::Relaxed);
+                    format!("item {item}")
+                })
+                .cached(dir.path())
+                .with_config(&"v1")
+        };
+
+        let first = dataset(calls.clone());
+        assert_eq!(first.get(1), Some("item 2".to_string()));
+        assert_eq!(first.iter().count(), 3);
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+
+        // A new dataset with the same configuration reads the cache of the previous one.
+        let second = dataset(calls.clone());
+        assert_eq!(
+            second.iter().collect::<Vec<_>>(),
+            first.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(second.get(3), None);
+        assert_eq!(calls.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn cached_dataset_should_be_rebuilt_when_the_config_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let cached = |offset: i32| {
+            InMemDataset::new(vec![1, 2, 3])
+                .map(move |item: i32| item + offset)
+                .cached(dir.path())
+                .with_config(&offset)
+        };
+
+        assert_eq!(cached(0).iter().collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(cached(10).iter().collect::<Vec<_>>(), [11, 12, 13]);
+        // Only the files of the last configuration are kept.
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn cached_dataset_should_be_built_concurrently() {
+        let dir = tempfile::tempdir().unwrap();
+        let items = (0..1000).collect::<Vec<i32>>();
+
+        std::thread::scope(|scope| {
+            let handles = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        InMemDataset::new(items.clone())
+                            .cached(dir.path())
+                            .iter()
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            for handle in handles {
+                assert_eq!(handle.join().unwrap(), items);
+            }
+        });
+        // Only the complete files of the cache are left.
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+}
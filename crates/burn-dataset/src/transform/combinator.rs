@@ -0,0 +1,210 @@
+use crate::Dataset;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::marker::PhantomData;
+use std::sync::OnceLock;
+
+/// Dataset mapping each item of an inner dataset with a function when accessed, created with
+/// [Dataset::map].
+pub struct MapDataset<D, F, I> {
+    dataset: D,
+    func: F,
+    input: PhantomData<I>,
+}
+
+impl<D, F, I> MapDataset<D, F, I> {
+    /// Creates a new map dataset.
+    pub fn new(dataset: D, func: F) -> Self {
+        Self {
+            dataset,
+            func,
+            input: PhantomData,
+        }
+    }
+}
+
+impl<D, F, I, O> Dataset<O> for MapDataset<D, F, I>
+where
+    D: Dataset<I>,
+    F: Fn(I) -> O + Send + Sync,
+    I: Send + Sync,
+{
+    fn get(&self, index: usize) -> Option<O> {
+        self.dataset.get(index).map(&self.func)
+    }
+
+    fn len(&self) -> usize {
+        self.dataset.len()
+    }
+}
+
+/// Dataset keeping the items of an inner dataset matching a predicate, created with
+/// [Dataset::filter].
+///
+/// The predicate is applied to all the items the first time the dataset is accessed, since
+/// the indices of the kept items are needed to access them.
+pub struct FilterDataset<D, P, I> {
+    dataset: D,
+    predicate: P,
+    indices: OnceLock<Vec<usize>>,
+    input: PhantomData<I>,
+}
+
+impl<D, P, I> FilterDataset<D, P, I>
+where
+    D: Dataset<I>,
+    P: Fn(&I) -> bool,
+{
+    /// Creates a new filter dataset.
+    pub fn new(dataset: D, predicate: P) -> Self {
+        Self {
+            dataset,
+            predicate,
+            indices: OnceLock::new(),
+            input: PhantomData,
+        }
+    }
+
+    fn indices(&self) -> &[usize] {
+        self.indices.get_or_init(|| {
+            (0..self.dataset.len())
+                .filter(|&index| {
+                    self.dataset
+                        .get(index)
+                        .is_some_and(|item| (self.predicate)(&item))
+                })
+                .collect()
+        })
+    }
+}
+
+impl<D, P, I> Dataset<I> for FilterDataset<D, P, I>
+where
+    D: Dataset<I>,
+    P: Fn(&I) -> bool + Send + Sync,
+    I: Send + Sync,
+{
+    fn get(&self, index: usize) -> Option<I> {
+        let index = self.indices().get(index)?;
+        self.dataset.get(*index)
+    }
+
+    fn len(&self) -> usize {
+        self.indices().len()
+    }
+}
+
+/// Dataset shuffling the items of an inner dataset with a buffer, created with
+/// [Dataset::shuffle_buffer].
+///
+/// The order is the one of a buffer filled with the items in order, from which a random item
+/// is taken and replaced by the next one. Unlike the [shuffled dataset](super::ShuffledDataset),
+/// the items stay close to their original position, so the datasets reading their items from
+/// the disk mostly read them sequentially.
+pub struct ShuffleBufferDataset<D, I> {
+    dataset: D,
+    indices: Vec<usize>,
+    input: PhantomData<I>,
+}
+
+impl<D, I> ShuffleBufferDataset<D, I>
+where
+    D: Dataset<I>,
+{
+    /// Creates a new shuffle buffer dataset with a fixed seed.
+    pub fn new(dataset: D, buffer_size: usize, seed: u64) -> Self {
+        assert!(
+            buffer_size > 0,
+            "The buffer should contain at least one item"
+        );
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut indices = Vec::with_capacity(dataset.len());
+        let mut buffer = Vec::with_capacity(buffer_size);
+
+        for index in 0..dataset.len() {
+            if buffer.len() < buffer_size {
+                buffer.push(index);
+                continue;
+            }
+
+            let slot = rng.gen_range(0..buffer_size);
+            indices.push(std::mem::replace(&mut buffer[slot], index));
+        }
+
+        while !buffer.is_empty() {
+            let slot = rng.gen_range(0..buffer.len());
+            indices.push(buffer.swap_remove(slot));
+        }
+
+        Self {
+            dataset,
+            indices,
+            input: PhantomData,
+        }
+    }
+}
+
+impl<D, I> Dataset<I> for ShuffleBufferDataset<D, I>
+where
+    D: Dataset<I>,
+    I: Send + Sync,
+{
+    fn get(&self, index: usize) -> Option<I> {
+        let index = self.indices.get(index)?;
+        self.dataset.get(*index)
+    }
+
+    fn len(&self) -> usize {
+        self.indices.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_data, InMemDataset};
+
+    #[test]
+    fn combinators_should_be_chained() {
+        let dataset = InMemDataset::new(test_data::string_items())
+            .skip(1)
+            .map(|item: String| item.len())
+            .filter(|len| *len > 6)
+            .take(1);
+
+        assert_eq!(dataset.len(), 1);
+        assert_eq!(dataset.iter().collect::<Vec<_>>(), [7]);
+    }
+
+    #[test]
+    fn filter_dataset_should_keep_the_matching_items() {
+        let dataset = InMemDataset::new((0..10).collect::<Vec<i32>>()).filter(|item| item % 3 == 0);
+
+        assert_eq!(dataset.iter().collect::<Vec<_>>(), [0, 3, 6, 9]);
+        assert_eq!(dataset.get(4), None);
+    }
+
+    #[test]
+    fn shuffle_buffer_dataset_should_keep_the_items_close() {
+        let dataset = InMemDataset::new((0..100).collect::<Vec<usize>>()).shuffle_buffer(4, 42);
+
+        let items = dataset.iter().collect::<Vec<_>>();
+        let mut sorted = items.clone();
+        sorted.sort();
+
+        assert_ne!(items, sorted);
+        assert_eq!(sorted, (0..100).collect::<Vec<_>>());
+        // Each item is taken before the buffer is filled with the 4th next one.
+        for (position, item) in items.iter().enumerate().take(96) {
+            assert!(*item <= position + 3);
+        }
+    }
+
+    #[test]
+    fn skip_should_be_empty_after_the_end() {
+        let dataset = InMemDataset::new(test_data::string_items()).skip(10);
+
+        assert_eq!(dataset.len(), 0);
+        assert_eq!(dataset.get(0), None);
+    }
+}
@@ -0,0 +1,270 @@
+use crate::Dataset;
+
+/// The normalization of each window of a [forecasting dataset](ForecastingDataset), computed
+/// from the lookback values only so the horizon doesn't leak into the inputs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WindowNormalization {
+    /// The values are left unchanged.
+    #[default]
+    None,
+    /// Each feature is centered by its mean and divided by its standard deviation.
+    Standard,
+    /// Each feature is divided by its mean absolute value, keeping the sign and the zeros of the
+    /// series.
+    MeanScale,
+}
+
+/// A training example of a [forecasting dataset](ForecastingDataset).
+#[derive(Clone, Debug, PartialEq)]
+pub struct ForecastingWindow {
+    /// The features of the lookback steps, the inputs of the model.
+    pub lookback: Vec<Vec<f32>>,
+    /// The features of the horizon steps, the targets of the model.
+    pub horizon: Vec<Vec<f32>>,
+    /// The offset subtracted from each feature by the normalization.
+    pub offset: Vec<f32>,
+    /// The scale dividing each feature by the normalization.
+    pub scale: Vec<f32>,
+}
+
+impl ForecastingWindow {
+    /// Reverts the normalization of the steps predicted by the model, such as the forecast of the
+    /// horizon.
+    pub fn denormalize(&self, mut steps: Vec<Vec<f32>>) -> Vec<Vec<f32>> {
+        for step in steps.iter_mut() {
+            for ((value, offset), scale) in step.iter_mut().zip(&self.offset).zip(&self.scale) {
+                *value = *value * scale + offset;
+            }
+        }
+
+        steps
+    }
+}
+
+/// Dataset of the windows of a long multivariate time series for forecasting, each item of the
+/// inner dataset being the features of a time step.
+///
+/// Each window is made of `lookback` steps followed by `horizon` steps, separated by `gap`
+/// steps, and the windows start every `stride` steps:
+///
+/// ```text
+/// | lookback | gap | horizon |
+/// <- stride ->| lookback | gap | horizon |
+/// ```
+///
+/// The windows must fit in the series, so the last steps are dropped when the stride doesn't
+/// divide the remaining steps.
+pub struct ForecastingDataset<D> {
+    dataset: D,
+    lookback: usize,
+    horizon: usize,
+    stride: usize,
+    gap: usize,
+    normalization: WindowNormalization,
+}
+
+impl<D> ForecastingDataset<D>
+where
+    D: Dataset<Vec<f32>>,
+{
+    /// Creates the windows of `lookback` steps followed by `horizon` steps, starting at each step.
+    pub fn new(dataset: D, lookback: usize, horizon: usize) -> Self {
+        assert!(
+            lookback > 0 && horizon > 0,
+            "The lookback and the horizon should be at least one step"
+        );
+
+        Self {
+            dataset,
+            lookback,
+            horizon,
+            stride: 1,
+            gap: 0,
+            normalization: WindowNormalization::None,
+        }
+    }
+
+    /// Sets the number of steps between the starts of the windows, one by default.
+    pub fn with_stride(mut self, stride: usize) -> Self {
+        assert!(stride > 0, "The stride should be at least one step");
+        self.stride = stride;
+        self
+    }
+
+    /// Sets the number of steps skipped between the lookback and the horizon, zero by default.
+    pub fn with_gap(mut self, gap: usize) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    /// Sets the normalization of each window.
+    pub fn with_normalization(mut self, normalization: WindowNormalization) -> Self {
+        self.normalization = normalization;
+        self
+    }
+
+    fn window_length(&self) -> usize {
+        self.lookback + self.gap + self.horizon
+    }
+
+    /// The offset and the scale of each feature of the lookback.
+    fn statistics(&self, lookback: &[Vec<f32>]) -> (Vec<f32>, Vec<f32>) {
+        let num_features = lookback.first().map(Vec::len).unwrap_or(0);
+        let count = lookback.len() as f32;
+        let mean_of = |value: &dyn Fn(&[f32]) -> Vec<f32>| {
+            let mut sums = vec![0.0; num_features];
+            for step in lookback {
+                for (sum, value) in sums.iter_mut().zip(value(step)) {
+                    *sum += value;
+                }
+            }
+            sums.into_iter().map(|sum| sum / count).collect::<Vec<_>>()
+        };
+        // A constant feature is left unscaled instead of being divided by zero.
+        let non_zero = |scale: f32| if scale > f32::EPSILON { scale } else { 1.0 };
+
+        match self.normalization {
+            WindowNormalization::None => (vec![0.0; num_features], vec![1.0; num_features]),
+            WindowNormalization::Standard => {
+                let mean = mean_of(&|step| step.to_vec());
+                let variance = mean_of(&|step| {
+                    step.iter()
+                        .zip(&mean)
+                        .map(|(value, mean)| (value - mean).powi(2))
+                        .collect()
+                });
+                let std = variance.into_iter().map(|var| non_zero(var.sqrt()));
+
+                (mean, std.collect())
+            }
+            WindowNormalization::MeanScale => {
+                let scale = mean_of(&|step| step.iter().map(|value| value.abs()).collect());
+
+                (
+                    vec![0.0; num_features],
+                    scale.into_iter().map(non_zero).collect(),
+                )
+            }
+        }
+    }
+}
+
+impl<D> Dataset<ForecastingWindow> for ForecastingDataset<D>
+where
+    D: Dataset<Vec<f32>>,
+{
+    fn get(&self, index: usize) -> Option<ForecastingWindow> {
+        if index >= self.len() {
+            return None;
+        }
+
+        let start = index * self.stride;
+        let steps = |range: std::ops::Range<usize>| {
+            range
+                .map(|step| self.dataset.get(step))
+                .collect::<Option<Vec<_>>>()
+        };
+
+        let mut lookback = steps(start..start + self.lookback)?;
+        let horizon_start = start + self.lookback + self.gap;
+        let mut horizon = steps(horizon_start..horizon_start + self.horizon)?;
+
+        let (offset, scale) = self.statistics(&lookback);
+        for step in lookback.iter_mut().chain(horizon.iter_mut()) {
+            assert_eq!(
+                step.len(),
+                offset.len(),
+                "The steps of the series should have the same number of features"
+            );
+            for ((value, offset), scale) in step.iter_mut().zip(&offset).zip(&scale) {
+                *value = (*value - offset) / scale;
+            }
+        }
+
+        Some(ForecastingWindow {
+            lookback,
+            horizon,
+            offset,
+            scale,
+        })
+    }
+
+    fn len(&self) -> usize {
+        match self.dataset.len().checked_sub(self.window_length()) {
+            Some(remaining) => remaining / self.stride + 1,
+            None => 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InMemDataset;
+
+    fn series(len: usize) -> InMemDataset<Vec<f32>> {
+        InMemDataset::new(
+            (0..len)
+                .map(|step| vec![step as f32, 10.0])
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn forecasting_dataset_should_create_the_windows() {
+        let dataset = ForecastingDataset::new(series(10), 3, 2)
+            .with_stride(2)
+            .with_gap(1);
+
+        // The windows of 6 steps start at the steps 0, 2 and 4.
+        assert_eq!(dataset.len(), 3);
+
+        let window = dataset.get(1).unwrap();
+        let first_feature =
+            |steps: &[Vec<f32>]| steps.iter().map(|step| step[0]).collect::<Vec<_>>();
+        assert_eq!(first_feature(&window.lookback), [2.0, 3.0, 4.0]);
+        assert_eq!(first_feature(&window.horizon), [6.0, 7.0]);
+        assert_eq!(dataset.get(3), None);
+    }
+
+    #[test]
+    fn forecasting_dataset_should_be_empty_when_the_series_is_too_short() {
+        let dataset = ForecastingDataset::new(series(4), 3, 2);
+
+        assert_eq!(dataset.len(), 0);
+        assert_eq!(dataset.get(0), None);
+    }
+
+    #[test]
+    fn forecasting_dataset_should_normalize_with_the_lookback_statistics() {
+        let dataset = ForecastingDataset::new(series(5), 3, 1)
+            .with_normalization(WindowNormalization::Standard);
+
+        let window = dataset.get(0).unwrap();
+        let std = (2.0f32 / 3.0).sqrt();
+
+        assert_eq!(window.offset, [1.0, 10.0]);
+        assert_eq!(window.scale, [std, 1.0]);
+        assert_eq!(window.lookback[0], [-1.0 / std, 0.0]);
+        assert_eq!(window.horizon[0], [2.0 / std, 0.0]);
+
+        let horizon = window.denormalize(window.horizon.clone());
+        assert!((horizon[0][0] - 3.0).abs() < 1e-6);
+        assert_eq!(horizon[0][1], 10.0);
+    }
+
+    #[test]
+    fn forecasting_dataset_should_scale_by_the_mean_absolute_value() {
+        let dataset = ForecastingDataset::new(series(5), 2, 1)
+            .with_normalization(WindowNormalization::MeanScale);
+
+        let window = dataset.get(1).unwrap();
+
+        assert_eq!(window.scale, [1.5, 10.0]);
+        assert_eq!(
+            window.lookback,
+            [vec![1.0 / 1.5, 1.0], vec![2.0 / 1.5, 1.0]]
+        );
+        assert_eq!(window.horizon, [vec![2.0, 1.0]]);
+    }
+}
@@ -1,12 +1,20 @@
+mod cache;
+mod combinator;
 mod composed;
+mod forecasting;
 mod mapper;
+mod packing;
 mod partial;
 mod random;
 mod sampler;
 mod window;
 
+pub use cache::*;
+pub use combinator::*;
 pub use composed::*;
+pub use forecasting::*;
 pub use mapper::*;
+pub use packing::*;
 pub use partial::*;
 pub use random::*;
 pub use sampler::*;
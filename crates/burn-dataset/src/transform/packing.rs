@@ -0,0 +1,279 @@
+use crate::Dataset;
+use std::collections::BTreeSet;
+use std::ops::Range;
+use std::sync::OnceLock;
+
+/// A fixed-length training sequence made of several documents, created by the
+/// [packed dataset](PackedDataset).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PackedSequence {
+    /// The tokens, padded to the sequence length.
+    pub tokens: Vec<usize>,
+    /// The segment of each token, starting at one for the first document of the sequence,
+    /// zero being the padding.
+    pub segment_ids: Vec<usize>,
+    /// The position of each token in its segment, starting at zero for each segment.
+    pub positions: Vec<usize>,
+}
+
+impl PackedSequence {
+    /// Whether each token has a next-token prediction target, the next token being in the same
+    /// segment.
+    ///
+    /// The last token of each segment is masked, so the loss never predicts the first token of
+    /// a document from the previous one.
+    pub fn loss_mask(&self) -> Vec<bool> {
+        (0..self.segment_ids.len())
+            .map(|index| {
+                let segment = self.segment_ids[index];
+                segment != 0 && self.segment_ids.get(index + 1) == Some(&segment)
+            })
+            .collect()
+    }
+}
+
+/// The part of a document in a sequence.
+#[derive(Clone, Debug)]
+struct Part {
+    document: usize,
+    range: Range<usize>,
+}
+
+/// Dataset packing the token documents of an inner dataset into fixed-length sequences for
+/// language-model pretraining.
+///
+/// By default, the documents are concatenated in order and split at the sequence boundaries,
+/// so only the last sequence is padded. Without [splitting](Self::with_split_documents), each
+/// document is placed whole in the sequence with the least remaining space that fits it.
+///
+/// The layout of the sequences is computed the first time the dataset is accessed, reading all
+/// the documents once.
+pub struct PackedDataset<D> {
+    dataset: D,
+    seq_length: usize,
+    pad_token: usize,
+    eos_token: Option<usize>,
+    split_documents: bool,
+    sequences: OnceLock<Vec<Vec<Part>>>,
+}
+
+impl<D> PackedDataset<D>
+where
+    D: Dataset<Vec<usize>>,
+{
+    /// Creates a new packed dataset of sequences of `seq_length` tokens.
+    pub fn new(dataset: D, seq_length: usize) -> Self {
+        assert!(seq_length > 0, "The sequence length should be at least one");
+
+        Self {
+            dataset,
+            seq_length,
+            pad_token: 0,
+            eos_token: None,
+            split_documents: true,
+            sequences: OnceLock::new(),
+        }
+    }
+
+    /// Sets the token padding the sequences, zero by default.
+    pub fn with_pad_token(mut self, pad_token: usize) -> Self {
+        self.pad_token = pad_token;
+        self
+    }
+
+    /// Sets the token appended to each document.
+    pub fn with_eos_token(mut self, eos_token: usize) -> Self {
+        self.eos_token = Some(eos_token);
+        self
+    }
+
+    /// Sets whether the documents are split between sequences.
+    ///
+    /// Without splitting, the documents longer than the sequence length are truncated.
+    pub fn with_split_documents(mut self, split_documents: bool) -> Self {
+        self.split_documents = split_documents;
+        self
+    }
+
+    fn document(&self, index: usize) -> Option<Vec<usize>> {
+        let mut tokens = self.dataset.get(index)?;
+        tokens.extend(self.eos_token);
+        Some(tokens)
+    }
+
+    fn sequences(&self) -> &[Vec<Part>] {
+        self.sequences.get_or_init(|| {
+            let lengths = (0..self.dataset.len())
+                .filter_map(|index| Some((index, self.document(index)?.len())))
+                .filter(|(_, length)| *length > 0);
+
+            match self.split_documents {
+                true => self.concatenate(lengths),
+                false => self.best_fit(lengths),
+            }
+        })
+    }
+
+    fn concatenate(&self, lengths: impl Iterator<Item = (usize, usize)>) -> Vec<Vec<Part>> {
+        let mut sequences = Vec::new();
+        let mut current = Vec::new();
+        let mut remaining = self.seq_length;
+
+        for (document, length) in lengths {
+            let mut start = 0;
+            while start < length {
+                let end = length.min(start + remaining);
+                current.push(Part {
+                    document,
+                    range: start..end,
+                });
+                remaining -= end - start;
+                start = end;
+
+                if remaining == 0 {
+                    sequences.push(std::mem::take(&mut current));
+                    remaining = self.seq_length;
+                }
+            }
+        }
+
+        if !current.is_empty() {
+            sequences.push(current);
+        }
+
+        sequences
+    }
+
+    fn best_fit(&self, lengths: impl Iterator<Item = (usize, usize)>) -> Vec<Vec<Part>> {
+        let mut sequences: Vec<Vec<Part>> = Vec::new();
+        // The remaining space of the sequences which are not full.
+        let mut available = BTreeSet::new();
+
+        for (document, length) in lengths {
+            let length = length.min(self.seq_length);
+            let part = Part {
+                document,
+                range: 0..length,
+            };
+
+            let (remaining, sequence) = match available.range((length, 0)..).next() {
+                Some(&(remaining, sequence)) => {
+                    available.remove(&(remaining, sequence));
+                    sequences[sequence].push(part);
+                    (remaining - length, sequence)
+                }
+                None => {
+                    sequences.push(vec![part]);
+                    (self.seq_length - length, sequences.len() - 1)
+                }
+            };
+
+            if remaining > 0 {
+                available.insert((remaining, sequence));
+            }
+        }
+
+        sequences
+    }
+}
+
+impl<D> Dataset<PackedSequence> for PackedDataset<D>
+where
+    D: Dataset<Vec<usize>>,
+{
+    fn get(&self, index: usize) -> Option<PackedSequence> {
+        let parts = self.sequences().get(index)?;
+
+        let mut sequence = PackedSequence {
+            tokens: Vec::with_capacity(self.seq_length),
+            segment_ids: Vec::with_capacity(self.seq_length),
+            positions: Vec::with_capacity(self.seq_length),
+        };
+
+        for (segment, part) in parts.iter().enumerate() {
+            let tokens = self.document(part.document)?;
+            let tokens = tokens.get(part.range.clone())?;
+
+            sequence.tokens.extend_from_slice(tokens);
+            sequence
+                .segment_ids
+                .resize(sequence.tokens.len(), segment + 1);
+            sequence.positions.extend(0..tokens.len());
+        }
+
+        sequence.tokens.resize(self.seq_length, self.pad_token);
+        sequence.segment_ids.resize(self.seq_length, 0);
+        sequence.positions.resize(self.seq_length, 0);
+
+        Some(sequence)
+    }
+
+    fn len(&self) -> usize {
+        self.sequences().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InMemDataset;
+
+    fn documents() -> InMemDataset<Vec<usize>> {
+        InMemDataset::new(vec![vec![1, 2, 3], vec![4, 5], vec![6, 7, 8, 9, 10]])
+    }
+
+    #[test]
+    fn packed_dataset_should_split_the_documents() {
+        let dataset = PackedDataset::new(documents(), 4).with_eos_token(99);
+
+        assert_eq!(dataset.len(), 4);
+        assert_eq!(
+            dataset.get(0).unwrap(),
+            PackedSequence {
+                tokens: vec![1, 2, 3, 99],
+                segment_ids: vec![1, 1, 1, 1],
+                positions: vec![0, 1, 2, 3],
+            }
+        );
+        assert_eq!(
+            dataset.get(1).unwrap(),
+            PackedSequence {
+                tokens: vec![4, 5, 99, 6],
+                segment_ids: vec![1, 1, 1, 2],
+                positions: vec![0, 1, 2, 0],
+            }
+        );
+        assert_eq!(dataset.get(2).unwrap().tokens, [7, 8, 9, 10]);
+        assert_eq!(dataset.get(3).unwrap().tokens, [99, 0, 0, 0]);
+        assert_eq!(dataset.get(4), None);
+    }
+
+    #[test]
+    fn packed_dataset_should_fit_the_whole_documents() {
+        let dataset = PackedDataset::new(documents(), 5)
+            .with_pad_token(42)
+            .with_split_documents(false);
+
+        let sequences = dataset.iter().collect::<Vec<_>>();
+
+        assert_eq!(sequences.len(), 2);
+        assert_eq!(sequences[0].tokens, [1, 2, 3, 4, 5]);
+        assert_eq!(sequences[0].segment_ids, [1, 1, 1, 2, 2]);
+        assert_eq!(sequences[1].tokens, [6, 7, 8, 9, 10]);
+    }
+
+    #[test]
+    fn loss_mask_should_exclude_the_document_joins_and_the_padding() {
+        let dataset = PackedDataset::new(documents(), 7)
+            .with_pad_token(42)
+            .with_split_documents(false);
+
+        let sequence = dataset.get(0).unwrap();
+
+        assert_eq!(sequence.tokens, [1, 2, 3, 4, 5, 42, 42]);
+        assert_eq!(
+            sequence.loss_mask(),
+            [true, true, false, true, false, false, false]
+        );
+    }
+}
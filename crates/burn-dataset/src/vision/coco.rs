@@ -0,0 +1,451 @@
+use crate::Dataset;
+
+use super::BoundingBox;
+use burn_tensor::TensorData;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Error type for the [COCO dataset](CocoDataset).
+#[derive(Error, Debug)]
+pub enum CocoDatasetError {
+    /// I/O operation error.
+    #[error("I/O error: `{0}`")]
+    Io(#[from] std::io::Error),
+
+    /// The annotation file isn't valid.
+    #[error("Invalid annotation file: `{0}`")]
+    Json(#[from] serde_json::Error),
+
+    /// An annotation refers to an unknown image.
+    #[error("Unknown image id: `{0}`")]
+    UnknownImage(u64),
+
+    /// An annotation refers to an unknown category.
+    #[error("Unknown category id: `{0}`")]
+    UnknownCategory(u64),
+}
+
+/// Object annotation of a [COCO item](CocoItem).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CocoAnnotation {
+    /// Bounding box with the `[x, y, width, height]` coordinates, in pixels, and the index of
+    /// the category.
+    pub bbox: BoundingBox,
+
+    /// Binary mask of the object with the shape `[height, width]`, when the masks are loaded.
+    pub mask: Option<TensorData>,
+
+    /// Whether the annotation is a group of objects.
+    pub is_crowd: bool,
+}
+
+/// COCO dataset item.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CocoItem {
+    /// The id of the image in the annotation file.
+    pub image_id: u64,
+
+    /// The RGB pixels of the image with the shape `[height, width, 3]`.
+    pub image: TensorData,
+
+    /// The objects of the image, possibly none.
+    pub annotations: Vec<CocoAnnotation>,
+}
+
+#[derive(Deserialize)]
+struct CocoFile {
+    images: Vec<CocoImageRaw>,
+    #[serde(default)]
+    annotations: Vec<CocoAnnotationRaw>,
+    categories: Vec<CocoCategoryRaw>,
+}
+
+#[derive(Deserialize)]
+struct CocoImageRaw {
+    id: u64,
+    file_name: String,
+    width: usize,
+    height: usize,
+}
+
+#[derive(Deserialize)]
+struct CocoCategoryRaw {
+    id: u64,
+    name: String,
+}
+
+#[derive(Deserialize, Clone)]
+struct CocoAnnotationRaw {
+    image_id: u64,
+    category_id: u64,
+    bbox: [f32; 4],
+    #[serde(default)]
+    iscrowd: u8,
+    segmentation: Option<Segmentation>,
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+enum Segmentation {
+    Polygons(Vec<Vec<f64>>),
+    Rle { counts: RleCounts, size: [usize; 2] },
+}
+
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+enum RleCounts {
+    Uncompressed(Vec<u32>),
+    Compressed(String),
+}
+
+struct CocoImage {
+    id: u64,
+    path: PathBuf,
+    width: usize,
+    height: usize,
+    annotations: Vec<CocoAnnotationRaw>,
+}
+
+/// Loads a [COCO dataset](CocoDataset) from an annotation file, such as
+/// `annotations/instances_train2017.json`, and the folder of its images.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let dataset = CocoDatasetLoader::new("annotations/instances_val2017.json", "val2017")
+///     .with_masks(true)
+///     .load()?;
+/// ```
+pub struct CocoDatasetLoader {
+    annotations: PathBuf,
+    images: PathBuf,
+    masks: bool,
+}
+
+impl CocoDatasetLoader {
+    /// Create the loader of the annotation file and of the folder containing the images.
+    pub fn new<A: AsRef<Path>, I: AsRef<Path>>(annotations: A, images: I) -> Self {
+        Self {
+            annotations: annotations.as_ref().to_path_buf(),
+            images: images.as_ref().to_path_buf(),
+            masks: false,
+        }
+    }
+
+    /// Decode the segmentation masks of the objects, which are not loaded by default.
+    pub fn with_masks(mut self, masks: bool) -> Self {
+        self.masks = masks;
+        self
+    }
+
+    /// Loads the annotations, the images being read when the items are accessed.
+    pub fn load(self) -> Result<CocoDataset, CocoDatasetError> {
+        let file: CocoFile =
+            serde_json::from_reader(BufReader::new(File::open(&self.annotations)?))?;
+
+        // The category ids are sparse, so they are mapped to contiguous labels.
+        let mut categories = file.categories;
+        categories.sort_by_key(|category| category.id);
+        let labels = categories
+            .iter()
+            .enumerate()
+            .map(|(label, category)| (category.id, label))
+            .collect::<HashMap<_, _>>();
+
+        let mut images = file
+            .images
+            .into_iter()
+            .map(|image| CocoImage {
+                id: image.id,
+                path: self.images.join(image.file_name),
+                width: image.width,
+                height: image.height,
+                annotations: Vec::new(),
+            })
+            .collect::<Vec<_>>();
+        images.sort_by_key(|image| image.id);
+
+        for annotation in file.annotations {
+            if !labels.contains_key(&annotation.category_id) {
+                return Err(CocoDatasetError::UnknownCategory(annotation.category_id));
+            }
+            let index = images
+                .binary_search_by_key(&annotation.image_id, |image| image.id)
+                .map_err(|_| CocoDatasetError::UnknownImage(annotation.image_id))?;
+
+            images[index].annotations.push(annotation);
+        }
+
+        Ok(CocoDataset {
+            images,
+            labels,
+            categories: categories
+                .into_iter()
+                .map(|category| category.name)
+                .collect(),
+            masks: self.masks,
+        })
+    }
+}
+
+/// Object detection and segmentation dataset in the
+/// [COCO format](https://cocodataset.org/#format-data), created with the
+/// [COCO dataset loader](CocoDatasetLoader).
+///
+/// The annotations are kept in memory and the images are read from the disk when accessed.
+pub struct CocoDataset {
+    images: Vec<CocoImage>,
+    labels: HashMap<u64, usize>,
+    categories: Vec<String>,
+    masks: bool,
+}
+
+impl CocoDataset {
+    /// The category names, in the order of their labels.
+    pub fn categories(&self) -> &[String] {
+        &self.categories
+    }
+}
+
+impl Dataset<CocoItem> for CocoDataset {
+    fn get(&self, index: usize) -> Option<CocoItem> {
+        let image = self.images.get(index)?;
+
+        let pixels = image::open(&image.path)
+            .expect("Can open the image of the COCO dataset")
+            .into_rgb8();
+        let shape = [pixels.height() as usize, pixels.width() as usize, 3];
+
+        let annotations = image
+            .annotations
+            .iter()
+            .map(|annotation| CocoAnnotation {
+                bbox: BoundingBox {
+                    coords: annotation.bbox,
+                    label: self.labels[&annotation.category_id],
+                },
+                mask: match (&annotation.segmentation, self.masks) {
+                    (Some(segmentation), true) => {
+                        let mask = segmentation.decode(image.height, image.width);
+                        Some(TensorData::new(mask, [image.height, image.width]))
+                    }
+                    _ => None,
+                },
+                is_crowd: annotation.iscrowd != 0,
+            })
+            .collect();
+
+        Some(CocoItem {
+            image_id: image.id,
+            image: TensorData::new(pixels.into_raw(), shape),
+            annotations,
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.images.len()
+    }
+}
+
+impl Segmentation {
+    /// Decodes the segmentation into a row-major binary mask.
+    fn decode(&self, height: usize, width: usize) -> Vec<u8> {
+        match self {
+            Segmentation::Polygons(polygons) => rasterize(polygons, height, width),
+            Segmentation::Rle { counts, size } => {
+                let counts = match counts {
+                    RleCounts::Uncompressed(counts) => counts.clone(),
+                    RleCounts::Compressed(counts) => decompress_counts(counts),
+                };
+                decode_rle(&counts, size[0], size[1])
+            }
+        }
+    }
+}
+
+/// Fills the pixels whose center is inside any of the polygons, with the even-odd rule.
+fn rasterize(polygons: &[Vec<f64>], height: usize, width: usize) -> Vec<u8> {
+    let mut mask = vec![0; height * width];
+    let mut crossings = Vec::new();
+
+    for polygon in polygons {
+        let points = polygon
+            .chunks_exact(2)
+            .map(|point| (point[0], point[1]))
+            .collect::<Vec<_>>();
+
+        for y in 0..height {
+            let center = y as f64 + 0.5;
+            crossings.clear();
+
+            for (i, &(x0, y0)) in points.iter().enumerate() {
+                let (x1, y1) = points[(i + 1) % points.len()];
+                if (y0 <= center) != (y1 <= center) {
+                    crossings.push(x0 + (center - y0) / (y1 - y0) * (x1 - x0));
+                }
+            }
+            crossings.sort_by(f64::total_cmp);
+
+            for span in crossings.chunks_exact(2) {
+                // The pixels whose center is between the crossings.
+                let start = (span[0] - 0.5).ceil().max(0.0) as usize;
+                let end = ((span[1] - 0.5).floor() + 1.0).clamp(0.0, width as f64) as usize;
+                for x in start..end {
+                    mask[y * width + x] = 1;
+                }
+            }
+        }
+    }
+
+    mask
+}
+
+/// Decodes the run-length encoding of a COCO mask, whose alternating runs of zeros and ones
+/// are in column-major order, into a row-major mask.
+fn decode_rle(counts: &[u32], height: usize, width: usize) -> Vec<u8> {
+    let mut mask = vec![0; height * width];
+    let mut position = 0;
+
+    for (i, &count) in counts.iter().enumerate() {
+        let count = count as usize;
+        if i % 2 == 1 {
+            for index in position..(position + count).min(height * width) {
+                let (x, y) = (index / height, index % height);
+                mask[y * width + x] = 1;
+            }
+        }
+        position += count;
+    }
+
+    mask
+}
+
+/// Decompresses the counts of a COCO run-length encoding, stored as a string with 5 bits per
+/// character and the counts after the second one being relative to the one before the previous.
+fn decompress_counts(counts: &str) -> Vec<u32> {
+    let mut decompressed: Vec<i64> = Vec::new();
+    let mut bytes = counts.bytes();
+
+    while bytes.len() > 0 {
+        let mut value = 0i64;
+        let mut shift = 0;
+        loop {
+            let Some(byte) = bytes.next() else {
+                break;
+            };
+            let chunk = byte as i64 - 48;
+            value |= (chunk & 0x1f) << shift;
+            shift += 5;
+
+            if chunk & 0x20 == 0 {
+                // Sign extension.
+                if chunk & 0x10 != 0 {
+                    value |= -1 << shift;
+                }
+                break;
+            }
+        }
+
+        if decompressed.len() > 2 {
+            value += decompressed[decompressed.len() - 2];
+        }
+        decompressed.push(value);
+    }
+
+    decompressed.into_iter().map(|count| count as u32).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn rasterize_should_fill_the_pixels_inside_the_polygon() {
+        // Square covering the pixels 1 to 2 of the rows 1 to 2.
+        let polygons = vec![vec![1.0, 1.0, 3.0, 1.0, 3.0, 3.0, 1.0, 3.0]];
+
+        let mask = rasterize(&polygons, 4, 4);
+
+        #[rustfmt::skip]
+        assert_eq!(mask, [
+            0, 0, 0, 0,
+            0, 1, 1, 0,
+            0, 1, 1, 0,
+            0, 0, 0, 0,
+        ]);
+    }
+
+    #[test]
+    fn rle_should_be_decoded_in_column_major_order() {
+        // 3x2 mask, the ones being the second and third pixels of the first column.
+        let segmentation = Segmentation::Rle {
+            counts: RleCounts::Uncompressed(vec![1, 2, 3]),
+            size: [3, 2],
+        };
+
+        assert_eq!(segmentation.decode(3, 2), [0, 0, 1, 0, 1, 0]);
+    }
+
+    #[test]
+    fn compressed_rle_counts_should_be_decompressed() {
+        // Encoded by pycocotools, with a count on two characters and a negative difference.
+        assert_eq!(decompress_counts("T312O"), [100, 1, 2, 0]);
+    }
+
+    #[test]
+    fn coco_dataset_should_group_the_annotations_by_image() {
+        let root = std::fs::canonicalize("tests/data/image_folder").unwrap();
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            file,
+            r#"{{
+                "images": [
+                    {{"id": 7, "file_name": "red/dot.png", "width": 1, "height": 1}},
+                    {{"id": 3, "file_name": "orange/dot.jpg", "width": 1, "height": 1}}
+                ],
+                "annotations": [
+                    {{"id": 1, "image_id": 7, "category_id": 18, "bbox": [0, 0, 1, 1],
+                      "iscrowd": 0, "segmentation": [[0, 0, 1, 0, 1, 1, 0, 1]]}},
+                    {{"id": 2, "image_id": 7, "category_id": 1, "bbox": [0, 0, 0.5, 0.5],
+                      "iscrowd": 1, "segmentation": {{"counts": [0, 1], "size": [1, 1]}}}}
+                ],
+                "categories": [
+                    {{"id": 18, "name": "dog"}},
+                    {{"id": 1, "name": "person"}}
+                ]
+            }}"#
+        )
+        .unwrap();
+
+        let dataset = CocoDatasetLoader::new(file.path(), root)
+            .with_masks(true)
+            .load()
+            .unwrap();
+
+        assert_eq!(dataset.categories(), ["person", "dog"]);
+        assert_eq!(dataset.len(), 2);
+
+        let item = dataset.get(0).unwrap();
+        assert_eq!(item.image_id, 3);
+        assert!(item.annotations.is_empty());
+
+        let item = dataset.get(1).unwrap();
+        assert_eq!(item.image.shape, [1, 1, 3]);
+        let labels = item
+            .annotations
+            .iter()
+            .map(|annotation| annotation.bbox.label)
+            .collect::<Vec<_>>();
+        assert_eq!(labels, [1, 0]);
+        assert!(item.annotations[1].is_crowd);
+        assert_eq!(
+            item.annotations[0].mask,
+            Some(TensorData::new(vec![1u8], [1, 1]))
+        );
+    }
+}
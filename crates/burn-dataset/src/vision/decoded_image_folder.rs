@@ -0,0 +1,442 @@
+use crate::Dataset;
+
+use super::ImageLoaderError;
+use burn_tensor::TensorData;
+use globwalk::DirEntry;
+use image::imageops::FilterType;
+use image::ImageFormat;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+const SUPPORTED_FILES: [&str; 5] = ["bmp", "jpg", "jpeg", "png", "webp"];
+
+/// Image decoded by the [decoded image folder dataset](DecodedImageFolderDataset).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedImageItem {
+    /// The RGB pixels of the image with the shape `[height, width, 3]`.
+    pub image: TensorData,
+
+    /// The index of the class of the image.
+    pub label: usize,
+}
+
+/// Where the images and their classes are listed.
+enum ImageSource {
+    /// The images are in a folder per class.
+    Folder(PathBuf),
+    /// A Csv file with a row `image path,class name` per image.
+    Annotations(PathBuf),
+}
+
+/// Loads a [decoded image folder dataset](DecodedImageFolderDataset), listing the images and
+/// their classes.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let dataset = DecodedImageFolderLoader::from_folder("data/train")
+///     .with_resize([224, 224])
+///     .with_skip_corrupted(true)
+///     .with_cache(true)
+///     .load()?;
+/// ```
+pub struct DecodedImageFolderLoader {
+    source: ImageSource,
+    resize: Option<[usize; 2]>,
+    skip_corrupted: bool,
+    cache: bool,
+}
+
+impl DecodedImageFolderLoader {
+    /// Create the loader of the images of the root folder, with a sub folder per class.
+    ///
+    /// The classes are the names of the sub folders, sorted alphabetically.
+    pub fn from_folder<P: AsRef<Path>>(root: P) -> Self {
+        Self::new(ImageSource::Folder(root.as_ref().to_path_buf()))
+    }
+
+    /// Create the loader of the images listed in a Csv file, without headers, with the path of
+    /// an image and its class name on each row.
+    ///
+    /// Relative image paths are resolved from the folder of the annotation file, and the classes
+    /// are sorted alphabetically.
+    pub fn from_annotations<P: AsRef<Path>>(file: P) -> Self {
+        Self::new(ImageSource::Annotations(file.as_ref().to_path_buf()))
+    }
+
+    fn new(source: ImageSource) -> Self {
+        Self {
+            source,
+            resize: None,
+            skip_corrupted: false,
+            cache: false,
+        }
+    }
+
+    /// Resize the images to `[height, width]` when they are decoded.
+    pub fn with_resize(mut self, size: [usize; 2]) -> Self {
+        self.resize = Some(size);
+        self
+    }
+
+    /// Skip the images that can't be decoded instead of failing, keeping their paths in the
+    /// [skipped images](DecodedImageFolderDataset::skipped).
+    ///
+    /// Each image is fully decoded when loading the dataset, since a truncated file can have a
+    /// valid header, and a JPEG image must end with its end of image marker, since its missing data
+    /// is padded by the decoder. The images that are kept can't fail when accessed. With the
+    /// [cache](DecodedImageFolderLoader::with_cache), the decoded images are kept.
+    pub fn with_skip_corrupted(mut self, skip_corrupted: bool) -> Self {
+        self.skip_corrupted = skip_corrupted;
+        self
+    }
+
+    /// Keep the images in memory once decoded, trading memory for the decoding time of the
+    /// following epochs.
+    pub fn with_cache(mut self, cache: bool) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Loads the dataset, listing the images without decoding them unless the
+    /// [corrupted images are skipped](DecodedImageFolderLoader::with_skip_corrupted).
+    pub fn load(self) -> Result<DecodedImageFolderDataset, ImageLoaderError> {
+        let files = match &self.source {
+            ImageSource::Folder(root) => folder_files(root)?,
+            ImageSource::Annotations(file) => annotation_files(file)?,
+        };
+
+        let classes = files
+            .iter()
+            .map(|(_, class)| class.clone())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        let mut images = Vec::with_capacity(files.len());
+        let mut cache = Vec::new();
+        let mut skipped = Vec::new();
+        for (path, class) in files {
+            let cached = OnceLock::new();
+            if self.skip_corrupted {
+                match verify(&path).and_then(|_| decode(&path, self.resize)) {
+                    Ok(image) if self.cache => cached.set(image).unwrap(),
+                    Ok(_) => {}
+                    Err(_) => {
+                        skipped.push(path);
+                        continue;
+                    }
+                }
+            }
+
+            if self.cache {
+                cache.push(cached);
+            }
+            images.push(ImageFile {
+                label: classes.binary_search(&class).unwrap(),
+                path,
+            });
+        }
+
+        Ok(DecodedImageFolderDataset {
+            images,
+            resize: self.resize,
+            cache,
+            classes,
+            skipped,
+        })
+    }
+}
+
+/// Dataset of images decoded into [tensor data](TensorData) when accessed, created with the
+/// [decoded image folder loader](DecodedImageFolderLoader).
+///
+/// Unlike the [image folder dataset](super::ImageFolderDataset), which returns the pixels as
+/// annotated items, the images are decoded into tensor data ready to be batched. The images are
+/// decoded by the threads of the data loader, and can be
+/// [kept in memory](DecodedImageFolderLoader::with_cache) once decoded.
+#[derive(Debug)]
+pub struct DecodedImageFolderDataset {
+    images: Vec<ImageFile>,
+    resize: Option<[usize; 2]>,
+    /// The decoded images, empty without cache.
+    cache: Vec<OnceLock<TensorData>>,
+    classes: Vec<String>,
+    skipped: Vec<PathBuf>,
+}
+
+/// An image of the dataset, not decoded yet.
+#[derive(Debug)]
+struct ImageFile {
+    path: PathBuf,
+    label: usize,
+}
+
+impl DecodedImageFolderDataset {
+    /// The class names, in the order of their labels.
+    pub fn classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    /// The paths of the images that couldn't be decoded and were skipped.
+    pub fn skipped(&self) -> &[PathBuf] {
+        &self.skipped
+    }
+}
+
+impl Dataset<DecodedImageItem> for DecodedImageFolderDataset {
+    /// Decodes the image.
+    ///
+    /// # Panics
+    ///
+    /// When the image can't be decoded, unless the corrupted images were
+    /// [skipped](DecodedImageFolderLoader::with_skip_corrupted) when loading the dataset.
+    fn get(&self, index: usize) -> Option<DecodedImageItem> {
+        let file = self.images.get(index)?;
+        let decode = || decode(&file.path, self.resize).unwrap_or_else(|err| panic!("{err}"));
+
+        let image = match self.cache.get(index) {
+            Some(cached) => cached.get_or_init(decode).clone(),
+            None => decode(),
+        };
+
+        Some(DecodedImageItem {
+            image,
+            label: file.label,
+        })
+    }
+
+    fn len(&self) -> usize {
+        self.images.len()
+    }
+}
+
+/// Lists the supported images of the root folder, with the name of their parent folder.
+fn folder_files(root: &Path) -> Result<Vec<(PathBuf, String)>, ImageLoaderError> {
+    let walker = globwalk::GlobWalkerBuilder::from_patterns(
+        root,
+        &[format!("*.{{{}}}", SUPPORTED_FILES.join(","))],
+    )
+    .follow_links(true)
+    .sort_by(|p1: &DirEntry, p2: &DirEntry| p1.path().cmp(p2.path()))
+    .build()
+    .map_err(|err| ImageLoaderError::Unknown(format!("{err:?}")))?
+    .filter_map(Result::ok);
+
+    walker
+        .map(|entry| {
+            let class = entry
+                .path()
+                .parent()
+                .and_then(Path::file_name)
+                .ok_or_else(|| {
+                    ImageLoaderError::IOError(
+                        "Could not resolve image parent folder name".to_string(),
+                    )
+                })?
+                .to_string_lossy()
+                .into_owned();
+
+            Ok((entry.into_path(), class))
+        })
+        .collect()
+}
+
+/// Reads the image paths and their class names from the annotation file.
+fn annotation_files(file: &Path) -> Result<Vec<(PathBuf, String)>, ImageLoaderError> {
+    let folder = file.parent().unwrap_or(Path::new(""));
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .from_path(file)
+        .map_err(|err| ImageLoaderError::IOError(err.to_string()))?;
+
+    reader
+        .deserialize::<(PathBuf, String)>()
+        .map(|row| {
+            let (path, class) = row.map_err(|err| ImageLoaderError::IOError(err.to_string()))?;
+            Ok((folder.join(path), class))
+        })
+        .collect()
+}
+
+/// Checks that a JPEG image isn't truncated, since the decoder pads the missing data.
+fn verify(path: &Path) -> Result<(), ImageLoaderError> {
+    let bytes = std::fs::read(path)
+        .map_err(|err| ImageLoaderError::IOError(format!("{}: {err}", path.display())))?;
+
+    if image::guess_format(&bytes).ok() != Some(ImageFormat::Jpeg) {
+        return Ok(());
+    }
+
+    // The image ends with the end of image marker, ignoring the padding.
+    let end = bytes
+        .iter()
+        .rposition(|byte| *byte != 0)
+        .map_or(0, |i| i + 1);
+    match bytes[..end].ends_with(&[0xFF, 0xD9]) {
+        true => Ok(()),
+        false => Err(ImageLoaderError::DecodingError(format!(
+            "{}: truncated JPEG image",
+            path.display()
+        ))),
+    }
+}
+
+/// Decodes the image as RGB, resizing it if needed.
+fn decode(path: &Path, resize: Option<[usize; 2]>) -> Result<TensorData, ImageLoaderError> {
+    let image = image::open(path)
+        .map_err(|err| ImageLoaderError::DecodingError(format!("{}: {err}", path.display())))?;
+
+    let image = match resize {
+        Some([height, width]) => {
+            image.resize_exact(width as u32, height as u32, FilterType::Triangle)
+        }
+        None => image,
+    }
+    .into_rgb8();
+
+    let shape = [image.height() as usize, image.width() as usize, 3];
+    Ok(TensorData::new(image.into_raw(), shape))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATASET_ROOT: &str = "tests/data/image_folder";
+
+    #[test]
+    fn decoded_image_folder_should_decode_and_resize_the_images() {
+        let dataset = DecodedImageFolderLoader::from_folder(DATASET_ROOT)
+            .with_resize([4, 6])
+            .load()
+            .unwrap();
+
+        assert_eq!(dataset.classes(), ["orange", "red"]);
+        assert_eq!(dataset.len(), 3);
+
+        let labels = dataset.iter().map(|item| item.label).collect::<Vec<_>>();
+        assert_eq!(labels, [0, 1, 1]);
+        for item in dataset.iter() {
+            assert_eq!(item.image.shape, [4, 6, 3]);
+        }
+    }
+
+    #[test]
+    fn decoded_image_folder_should_load_the_annotation_file() {
+        let root = std::fs::canonicalize(DATASET_ROOT).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let annotations = dir.path().join("annotations.csv");
+        std::fs::write(
+            &annotations,
+            format!(
+                "{},red\n{},orange\n",
+                root.join("red/dot.png").display(),
+                root.join("orange/dot.jpg").display()
+            ),
+        )
+        .unwrap();
+
+        let dataset = DecodedImageFolderLoader::from_annotations(annotations)
+            .load()
+            .unwrap();
+
+        let labels = dataset.iter().map(|item| item.label).collect::<Vec<_>>();
+        assert_eq!(labels, [1, 0]);
+    }
+
+    #[test]
+    fn decoded_image_folder_should_skip_the_corrupted_images() {
+        let dir = tempfile::tempdir().unwrap();
+        let class = dir.path().join("red");
+        std::fs::create_dir(&class).unwrap();
+        std::fs::copy(
+            Path::new(DATASET_ROOT).join("red/dot.png"),
+            class.join("dot.png"),
+        )
+        .unwrap();
+        std::fs::write(class.join("corrupted.jpg"), b"not an image").unwrap();
+
+        let dataset = DecodedImageFolderLoader::from_folder(dir.path())
+            .with_skip_corrupted(true)
+            .load()
+            .unwrap();
+
+        assert_eq!(dataset.len(), 1);
+        assert_eq!(dataset.skipped(), [class.join("corrupted.jpg")]);
+    }
+
+    #[test]
+    fn decoded_image_folder_should_skip_the_truncated_images() {
+        let dir = tempfile::tempdir().unwrap();
+        let class = dir.path().join("orange");
+        std::fs::create_dir(&class).unwrap();
+        let image = std::fs::read(Path::new(DATASET_ROOT).join("orange/dot.jpg")).unwrap();
+        // Drops the end of the scan, which the decoder pads.
+        std::fs::write(class.join("truncated.jpg"), &image[..image.len() - 8]).unwrap();
+        assert!(image::image_dimensions(class.join("truncated.jpg")).is_ok());
+
+        let dataset = DecodedImageFolderLoader::from_folder(dir.path())
+            .with_skip_corrupted(true)
+            .load()
+            .unwrap();
+
+        assert_eq!(dataset.len(), 0);
+        assert_eq!(dataset.skipped(), [class.join("truncated.jpg")]);
+    }
+
+    #[test]
+    fn decoded_image_folder_should_cache_the_images_decoded_when_skipping() {
+        let dir = tempfile::tempdir().unwrap();
+        let class = dir.path().join("red");
+        std::fs::create_dir(&class).unwrap();
+        let path = class.join("dot.png");
+        std::fs::copy(Path::new(DATASET_ROOT).join("red/dot.png"), &path).unwrap();
+
+        let dataset = DecodedImageFolderLoader::from_folder(dir.path())
+            .with_skip_corrupted(true)
+            .with_cache(true)
+            .load()
+            .unwrap();
+
+        // The image was decoded when loading the dataset.
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(dataset.get(0).unwrap().image.shape, [1, 1, 3]);
+    }
+
+    #[test]
+    #[should_panic = "corrupted.jpg"]
+    fn decoded_image_folder_should_decode_the_images_when_accessed() {
+        let dir = tempfile::tempdir().unwrap();
+        let class = dir.path().join("red");
+        std::fs::create_dir(&class).unwrap();
+        std::fs::write(class.join("corrupted.jpg"), b"not an image").unwrap();
+
+        let dataset = DecodedImageFolderLoader::from_folder(dir.path())
+            .load()
+            .unwrap();
+        assert_eq!(dataset.len(), 1);
+
+        dataset.get(0);
+    }
+
+    #[test]
+    fn decoded_image_folder_should_cache_the_decoded_images() {
+        let dir = tempfile::tempdir().unwrap();
+        let class = dir.path().join("red");
+        std::fs::create_dir(&class).unwrap();
+        let path = class.join("dot.png");
+        std::fs::copy(Path::new(DATASET_ROOT).join("red/dot.png"), &path).unwrap();
+
+        let dataset = DecodedImageFolderLoader::from_folder(dir.path())
+            .with_cache(true)
+            .load()
+            .unwrap();
+        let image = dataset.get(0).unwrap().image;
+
+        // The cached image doesn't need the file anymore.
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(dataset.get(0).unwrap().image, image);
+    }
+}
@@ -235,6 +235,10 @@ pub enum ImageLoaderError {
     /// Invalid file error.
     #[error("Invalid file extension: `{0}`")]
     InvalidFileExtensionError(String),
+
+    /// Image decoding error.
+    #[error("Could not decode image: `{0}`")]
+    DecodingError(String),
 }
 
 type ImageDatasetMapper =
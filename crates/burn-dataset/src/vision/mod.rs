@@ -1,5 +1,9 @@
+mod coco;
+mod decoded_image_folder;
 mod image_folder;
 mod mnist;
 
+pub use coco::*;
+pub use decoded_image_folder::*;
 pub use image_folder::*;
 pub use mnist::*;
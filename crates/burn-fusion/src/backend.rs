@@ -3,7 +3,7 @@ use crate::{
     QFusionTensor,
 };
 use burn_tensor::{
-    backend::{Backend, DeviceOps},
+    backend::{Backend, DeviceOps, MemoryUsage},
     ops::{BoolTensor, FloatTensor, IntTensor, QuantizedTensor},
     repr::{OperationDescription, QuantizedKind, ReprBackend, TensorHandle},
     Device,
@@ -56,6 +56,10 @@ impl<B: FusionBackend> Backend for Fusion<B> {
         B::sync(device);
     }
 
+    fn memory_usage(device: &Self::Device) -> Option<MemoryUsage> {
+        B::memory_usage(device)
+    }
+
     fn ad_enabled() -> bool {
         false
     }
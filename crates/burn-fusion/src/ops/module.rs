@@ -5,9 +5,9 @@ use burn_tensor::{
             calculate_conv_output_size, calculate_conv_transpose_output_size,
             calculate_pool_output_size,
         },
-        ConvOptions, ConvTransposeOptions, DeformConv2dBackward, DeformConvOptions, FloatTensor,
-        IntTensor, InterpolateOptions, MaxPool1dBackward, MaxPool1dWithIndices, MaxPool2dBackward,
-        MaxPool2dWithIndices, ModuleOps,
+        BoolTensor, ConvOptions, ConvTransposeOptions, DeformConv2dBackward, DeformConvOptions,
+        FloatTensor, IntTensor, InterpolateOptions, MaxPool1dBackward, MaxPool1dWithIndices,
+        MaxPool2dBackward, MaxPool2dWithIndices, ModuleOps,
     },
     repr::*,
     Element,
@@ -1402,4 +1402,58 @@ impl<B: FusionBackend> ModuleOps<Fusion<B>> for Fusion<B> {
         );
         out
     }
+
+    fn attention(
+        query: FloatTensor<Self>,
+        key: FloatTensor<Self>,
+        value: FloatTensor<Self>,
+        mask: Option<BoolTensor<Self>>,
+    ) -> FloatTensor<Self> {
+        make_ops!(
+            AttentionOps,
+            AttentionDescription,
+            |args: AttentionDescription, handles: &mut HandleContainer<B::Handle>| {
+                let query = handles.get_float_tensor::<B>(&args.query);
+                let key = handles.get_float_tensor::<B>(&args.key);
+                let value = handles.get_float_tensor::<B>(&args.value);
+                let mask = args
+                    .mask
+                    .as_ref()
+                    .map(|mask| handles.get_bool_tensor::<B>(mask));
+
+                let output = B::attention(query, key, value, mask);
+
+                handles.register_float_tensor::<B>(&args.out.id, output);
+            }
+        );
+
+        let mut streams = vec![query.stream, key.stream, value.stream];
+        if let Some(mask) = &mask {
+            streams.push(mask.stream);
+        }
+        let shape = vec![
+            query.shape[0],
+            query.shape[1],
+            query.shape[2],
+            value.shape[3],
+        ];
+        let out = query
+            .client
+            .tensor_uninitialized(shape, B::FloatElem::dtype());
+
+        let desc = AttentionDescription {
+            query: query.into_description(),
+            key: key.into_description(),
+            value: value.into_description(),
+            mask: mask.map(|mask| mask.into_description()),
+            out: out.to_description_out(),
+        };
+        out.client.register(
+            streams,
+            OperationDescription::Module(ModuleOperationDescription::Attention(desc.clone())),
+            AttentionOps::<B>::new(desc),
+        );
+
+        out
+    }
 }
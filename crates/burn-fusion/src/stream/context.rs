@@ -443,6 +443,15 @@ impl RelativeOps for ModuleOperationDescription {
                     out: desc.out.to_relative(converter),
                 })
             }
+            ModuleOperationDescription::Attention(desc) => {
+                ModuleOperationDescription::Attention(AttentionDescription {
+                    query: desc.query.to_relative(converter),
+                    key: desc.key.to_relative(converter),
+                    value: desc.value.to_relative(converter),
+                    mask: desc.mask.as_ref().map(|t| t.to_relative(converter)),
+                    out: desc.out.to_relative(converter),
+                })
+            }
         }
     }
 }
@@ -28,9 +28,11 @@ fn main() {
         .input("tests/div/div.onnx")
         .input("tests/dropout/dropout_opset16.onnx")
         .input("tests/dropout/dropout_opset7.onnx")
+        .input("tests/einsum/einsum.onnx")
         .input("tests/equal/equal.onnx")
         .input("tests/erf/erf.onnx")
         .input("tests/exp/exp.onnx")
+        .input("tests/external_data/external_data.onnx")
         .input("tests/expand/expand.onnx")
         .input("tests/expand/expand_tensor.onnx")
         .input("tests/expand/expand_shape.onnx")
@@ -40,12 +42,14 @@ fn main() {
         .input("tests/gather/gather_scalar.onnx")
         .input("tests/gather/gather_shape.onnx")
         .input("tests/gather_elements/gather_elements.onnx")
+        .input("tests/gather_nd/gather_nd.onnx")
         .input("tests/gelu/gelu.onnx")
         .input("tests/global_avr_pool/global_avr_pool.onnx")
         .input("tests/greater/greater.onnx")
         .input("tests/greater/greater_scalar.onnx")
         .input("tests/greater_or_equal/greater_or_equal.onnx")
         .input("tests/greater_or_equal/greater_or_equal_scalar.onnx")
+        .input("tests/gru/gru.onnx")
         .input("tests/hard_sigmoid/hard_sigmoid.onnx")
         .input("tests/layer_norm/layer_norm.onnx")
         .input("tests/leaky_relu/leaky_relu.onnx")
@@ -56,6 +60,7 @@ fn main() {
         .input("tests/linear/linear.onnx")
         .input("tests/log/log.onnx")
         .input("tests/log_softmax/log_softmax.onnx")
+        .input("tests/lstm/lstm.onnx")
         .input("tests/mask_where/mask_where.onnx")
         .input("tests/mask_where/mask_where_broadcast.onnx")
         .input("tests/mask_where/mask_where_scalar_x.onnx")
@@ -70,6 +75,7 @@ fn main() {
         .input("tests/mul/mul.onnx")
         .input("tests/neg/neg.onnx")
         .input("tests/not/not.onnx")
+        .input("tests/optimize/optimize.onnx")
         .input("tests/pad/pad.onnx")
         .input("tests/pow/pow.onnx")
         .input("tests/pow/pow_int.onnx")
@@ -94,14 +100,22 @@ fn main() {
         .input("tests/resize/resize_2d_bicubic_scale.onnx")
         .input("tests/resize/resize_2d_bilinear_scale.onnx")
         .input("tests/resize/resize_2d_nearest_scale.onnx")
+        .input("tests/resize/resize_opset10.onnx")
+        .input("tests/resize/upsample_opset7.onnx")
+        .input("tests/resize/upsample_opset9.onnx")
+        .input("tests/scatter_elements/scatter_elements.onnx")
+        .input("tests/scatter_nd/scatter_nd.onnx")
+        .input("tests/scatter_nd/scatter_nd_max.onnx")
         .input("tests/shape/shape.onnx")
         .input("tests/sigmoid/sigmoid.onnx")
         .input("tests/sign/sign.onnx")
         .input("tests/sin/sin.onnx")
         .input("tests/slice/slice.onnx")
+        .input("tests/slice/slice_opset9.onnx")
         .input("tests/softmax/softmax.onnx")
         .input("tests/sqrt/sqrt.onnx")
         .input("tests/squeeze/squeeze_multiple.onnx")
+        .input("tests/squeeze/squeeze_opset11.onnx")
         .input("tests/squeeze/squeeze_opset13.onnx")
         .input("tests/squeeze/squeeze_opset16.onnx")
         .input("tests/sub/sub.onnx")
@@ -119,6 +133,20 @@ fn main() {
         .out_dir("model/")
         .run_from_script();
 
+    // TorchScript models take the rank of each input, which is not stored in the traced graph.
+    ModelGen::new()
+        .input("tests/torchscript/torchscript.pt")
+        .input_ranks(&[4])
+        .out_dir("model/")
+        .run_from_script();
+
+    // The optimized graph is compared to the graph of `tests/optimize/optimize.onnx`.
+    ModelGen::new()
+        .input("tests/optimize/optimize.onnx")
+        .out_dir("model/optimized/")
+        .optimize_graph(true)
+        .run_from_script();
+
     // The following tests are used to generate the model with different record types.
     // (e.g. bincode, pretty_json, etc.) Do not need to add new tests here, just use the default
     // record type to the ModelGen::new() call above.
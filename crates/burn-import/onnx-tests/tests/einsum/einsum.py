@@ -0,0 +1,62 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/einsum/einsum.onnx
+#
+# A batched matrix product, a contraction whose batch dimensions are broadcast from both
+# operands, and a chain of three operands.
+
+import onnx
+from onnx import helper, TensorProto
+
+
+def main() -> None:
+    nodes = [
+        helper.make_node(
+            "Einsum", inputs=["x", "y"], outputs=["matmul"], name="/Einsum", equation="bij,bjk->bik"
+        ),
+        helper.make_node(
+            "Einsum",
+            inputs=["p", "q"],
+            outputs=["broadcast"],
+            name="/Einsum_1",
+            equation="...ijk,...kl->...ijl",
+        ),
+        helper.make_node(
+            "Einsum", inputs=["a", "b", "v"], outputs=["chain"], name="/Einsum_2", equation="ij,jk,k->i"
+        ),
+    ]
+
+    graph_def = helper.make_graph(
+        nodes=nodes,
+        name="EinsumGraph",
+        inputs=[
+            helper.make_tensor_value_info("x", TensorProto.FLOAT, [2, 2, 3]),
+            helper.make_tensor_value_info("y", TensorProto.FLOAT, [2, 3, 2]),
+            helper.make_tensor_value_info("p", TensorProto.FLOAT, [2, 1, 2, 2, 3]),
+            helper.make_tensor_value_info("q", TensorProto.FLOAT, [1, 3, 3, 2]),
+            helper.make_tensor_value_info("a", TensorProto.FLOAT, [2, 3]),
+            helper.make_tensor_value_info("b", TensorProto.FLOAT, [3, 2]),
+            helper.make_tensor_value_info("v", TensorProto.FLOAT, [2]),
+        ],
+        outputs=[
+            helper.make_tensor_value_info("matmul", TensorProto.FLOAT, [2, 2, 2]),
+            helper.make_tensor_value_info("broadcast", TensorProto.FLOAT, [2, 3, 2, 2, 2]),
+            helper.make_tensor_value_info("chain", TensorProto.FLOAT, [2]),
+        ],
+    )
+
+    model_def = helper.make_model(
+        graph_def,
+        producer_name="einsum",
+        opset_imports=[helper.make_operatorsetid("", 16)],
+    )
+
+    # Ensure valid ONNX:
+    onnx.checker.check_model(model_def)
+
+    # Save the model to a file
+    onnx.save(model_def, "einsum.onnx")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,61 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/external_data/external_data.onnx
+#
+# The weight and the bias are saved in `external_data.onnx.data`, next to the model.
+
+import onnx
+from onnx import helper, TensorProto
+
+
+def main() -> None:
+    weight = [float(i) / 4.0 for i in range(12)]
+    initializers = [
+        helper.make_tensor("weight", TensorProto.FLOAT, [4, 3], weight),
+        helper.make_tensor("bias", TensorProto.FLOAT, [3], [1.0, -1.0, 0.5]),
+    ]
+
+    nodes = [
+        helper.make_node("MatMul", inputs=["input", "weight"], outputs=["matmul"], name="/MatMul"),
+        helper.make_node("Add", inputs=["matmul", "bias"], outputs=["output"], name="/Add"),
+    ]
+
+    graph_def = helper.make_graph(
+        nodes=nodes,
+        name="ExternalData",
+        inputs=[helper.make_tensor_value_info("input", TensorProto.FLOAT, [2, 4])],
+        outputs=[helper.make_tensor_value_info("output", TensorProto.FLOAT, [2, 3])],
+        initializer=initializers,
+    )
+
+    model_def = helper.make_model(
+        graph_def,
+        producer_name="external_data",
+        opset_imports=[helper.make_operatorsetid("", 16)],
+    )
+
+    # Ensure valid ONNX:
+    onnx.checker.check_model(model_def)
+
+    # Save the model to a file, with the tensors in the external data file
+    onnx.save_model(
+        model_def,
+        "external_data.onnx",
+        save_as_external_data=True,
+        all_tensors_to_one_file=True,
+        location="external_data.onnx.data",
+        size_threshold=0,
+    )
+
+    # The output of the test input
+    inputs = [[1.0, 2.0, 3.0, 4.0], [-1.0, 0.0, 1.0, 0.5]]
+    bias = [1.0, -1.0, 0.5]
+    output = [
+        [sum(row[k] * weight[k * 3 + j] for k in range(4)) + bias[j] for j in range(3)]
+        for row in inputs
+    ]
+    print(f"Test output: {output}")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,42 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/gather_nd/gather_nd.onnx
+#
+# The number of rows of the data and the number of indices are symbolic, only the size of the
+# last dimension of the indices is static.
+
+import onnx
+from onnx import helper, TensorProto
+
+
+def main() -> None:
+    nodes = [
+        helper.make_node("GatherND", inputs=["data", "indices"], outputs=["gathered"], name="/GatherND"),
+        helper.make_node("Relu", inputs=["gathered"], outputs=["output"], name="/Relu"),
+    ]
+
+    graph_def = helper.make_graph(
+        nodes=nodes,
+        name="GatherNdGraph",
+        inputs=[
+            helper.make_tensor_value_info("data", TensorProto.FLOAT, ["rows", 3, 2]),
+            helper.make_tensor_value_info("indices", TensorProto.INT64, ["num_indices", 2]),
+        ],
+        outputs=[helper.make_tensor_value_info("output", TensorProto.FLOAT, ["num_indices", 2])],
+    )
+
+    model_def = helper.make_model(
+        graph_def,
+        producer_name="gather_nd",
+        opset_imports=[helper.make_operatorsetid("", 16)],
+    )
+
+    # Ensure valid ONNX:
+    onnx.checker.check_model(model_def)
+
+    # Save the model to a file
+    onnx.save(model_def, "gather_nd.onnx")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,65 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/gru/gru.onnx
+#
+# A GRU applying the reset gate after the hidden transformation (linear_before_reset), with an
+# initial hidden state given as input.
+
+import onnx
+from onnx import helper, TensorProto
+
+
+def values(count: int, scale: float) -> list:
+    # Deterministic values in [-scale, scale]
+    return [scale * (((i * 37) % 19) / 9.0 - 1.0) for i in range(count)]
+
+
+def main() -> None:
+    seq_length, batch_size, input_size, hidden_size = 3, 1, 2, 3
+
+    initializers = [
+        helper.make_tensor("W", TensorProto.FLOAT, [1, 3 * hidden_size, input_size], values(18, 0.5)),
+        helper.make_tensor("R", TensorProto.FLOAT, [1, 3 * hidden_size, hidden_size], values(27, 0.4)),
+        helper.make_tensor("B", TensorProto.FLOAT, [1, 6 * hidden_size], values(18, 0.2)),
+    ]
+
+    nodes = [
+        helper.make_node(
+            "GRU",
+            inputs=["X", "W", "R", "B", "", "initial_h"],
+            outputs=["Y", "Y_h"],
+            name="/GRU",
+            hidden_size=hidden_size,
+            linear_before_reset=1,
+        ),
+    ]
+
+    graph_def = helper.make_graph(
+        nodes=nodes,
+        name="GruGraph",
+        inputs=[
+            helper.make_tensor_value_info("X", TensorProto.FLOAT, [seq_length, batch_size, input_size]),
+            helper.make_tensor_value_info("initial_h", TensorProto.FLOAT, [1, batch_size, hidden_size]),
+        ],
+        outputs=[
+            helper.make_tensor_value_info("Y", TensorProto.FLOAT, [seq_length, 1, batch_size, hidden_size]),
+            helper.make_tensor_value_info("Y_h", TensorProto.FLOAT, [1, batch_size, hidden_size]),
+        ],
+        initializer=initializers,
+    )
+
+    model_def = helper.make_model(
+        graph_def,
+        producer_name="gru",
+        opset_imports=[helper.make_operatorsetid("", 16)],
+    )
+
+    # Ensure valid ONNX:
+    onnx.checker.check_model(model_def)
+
+    # Save the model to a file
+    onnx.save(model_def, "gru.onnx")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,64 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/lstm/lstm.onnx
+#
+# A bidirectional LSTM without initial states.
+
+import onnx
+from onnx import helper, TensorProto
+
+
+def values(count: int, scale: float) -> list:
+    # Deterministic values in [-scale, scale]
+    return [scale * (((i * 37) % 19) / 9.0 - 1.0) for i in range(count)]
+
+
+def main() -> None:
+    seq_length, batch_size, input_size, hidden_size = 2, 1, 2, 2
+
+    initializers = [
+        helper.make_tensor("W", TensorProto.FLOAT, [2, 4 * hidden_size, input_size], values(32, 0.5)),
+        helper.make_tensor("R", TensorProto.FLOAT, [2, 4 * hidden_size, hidden_size], values(32, 0.4)),
+        helper.make_tensor("B", TensorProto.FLOAT, [2, 8 * hidden_size], values(32, 0.2)),
+    ]
+
+    nodes = [
+        helper.make_node(
+            "LSTM",
+            inputs=["X", "W", "R", "B"],
+            outputs=["Y", "Y_h", "Y_c"],
+            name="/LSTM",
+            hidden_size=hidden_size,
+            direction="bidirectional",
+        ),
+    ]
+
+    graph_def = helper.make_graph(
+        nodes=nodes,
+        name="LstmGraph",
+        inputs=[
+            helper.make_tensor_value_info("X", TensorProto.FLOAT, [seq_length, batch_size, input_size]),
+        ],
+        outputs=[
+            helper.make_tensor_value_info("Y", TensorProto.FLOAT, [seq_length, 2, batch_size, hidden_size]),
+            helper.make_tensor_value_info("Y_h", TensorProto.FLOAT, [2, batch_size, hidden_size]),
+            helper.make_tensor_value_info("Y_c", TensorProto.FLOAT, [2, batch_size, hidden_size]),
+        ],
+        initializer=initializers,
+    )
+
+    model_def = helper.make_model(
+        graph_def,
+        producer_name="lstm",
+        opset_imports=[helper.make_operatorsetid("", 16)],
+    )
+
+    # Ensure valid ONNX:
+    onnx.checker.check_model(model_def)
+
+    # Save the model to a file
+    onnx.save(model_def, "lstm.onnx")
+
+
+if __name__ == "__main__":
+    main()
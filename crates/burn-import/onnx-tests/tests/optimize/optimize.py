@@ -0,0 +1,93 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/optimize/optimize.onnx
+#
+# The graph has the artifacts removed by the graph optimization: a dropout, a convolution without
+# bias followed by a batch norm, inverse and non-inverse transposes, and constant nodes.
+
+import onnx
+from onnx import helper, TensorProto
+
+
+def values(count: int, scale: float) -> list:
+    # Deterministic values in [-scale, scale]
+    return [scale * (((i * 37) % 19) / 9.0 - 1.0) for i in range(count)]
+
+
+def main() -> None:
+    initializers = [
+        helper.make_tensor("weight", TensorProto.FLOAT, [3, 2, 3, 3], values(54, 0.5)),
+        helper.make_tensor("scale", TensorProto.FLOAT, [3], [1.0, 0.5, 2.0]),
+        helper.make_tensor("offset", TensorProto.FLOAT, [3], [0.1, -0.2, 0.3]),
+        helper.make_tensor("mean", TensorProto.FLOAT, [3], [0.5, -0.5, 0.0]),
+        helper.make_tensor("var", TensorProto.FLOAT, [3], [1.0, 2.0, 0.5]),
+        helper.make_tensor("ratio", TensorProto.FLOAT, [], [0.1]),
+    ]
+
+    nodes = [
+        helper.make_node(
+            "Conv",
+            inputs=["input", "weight"],
+            outputs=["conv"],
+            name="/Conv",
+            kernel_shape=[3, 3],
+            pads=[1, 1, 1, 1],
+        ),
+        helper.make_node(
+            "BatchNormalization",
+            inputs=["conv", "scale", "offset", "mean", "var"],
+            outputs=["batch_norm"],
+            name="/BatchNormalization",
+            epsilon=1e-3,
+        ),
+        # Cancel each other
+        helper.make_node(
+            "Transpose", inputs=["batch_norm"], outputs=["nhwc"], name="/Transpose", perm=[0, 2, 3, 1]
+        ),
+        helper.make_node(
+            "Transpose", inputs=["nhwc"], outputs=["nchw"], name="/Transpose_1", perm=[0, 3, 1, 2]
+        ),
+        # Merged into a single transpose
+        helper.make_node(
+            "Transpose", inputs=["nchw"], outputs=["nchw_t"], name="/Transpose_2", perm=[0, 1, 3, 2]
+        ),
+        helper.make_node(
+            "Transpose", inputs=["nchw_t"], outputs=["nhcw"], name="/Transpose_3", perm=[0, 2, 1, 3]
+        ),
+        helper.make_node(
+            "Dropout", inputs=["nhcw", "ratio"], outputs=["dropout"], name="/Dropout"
+        ),
+        # Folded into a single constant
+        helper.make_node(
+            "Constant", inputs=[], outputs=["two"], name="/Constant", value_float=2.0
+        ),
+        helper.make_node(
+            "Constant", inputs=[], outputs=["half"], name="/Constant_1", value_float=0.5
+        ),
+        helper.make_node("Mul", inputs=["two", "half"], outputs=["one"], name="/Mul"),
+        helper.make_node("Add", inputs=["dropout", "one"], outputs=["output"], name="/Add"),
+    ]
+
+    graph_def = helper.make_graph(
+        nodes=nodes,
+        name="OptimizeGraph",
+        inputs=[helper.make_tensor_value_info("input", TensorProto.FLOAT, [1, 2, 4, 4])],
+        outputs=[helper.make_tensor_value_info("output", TensorProto.FLOAT, [1, 4, 3, 4])],
+        initializer=initializers,
+    )
+
+    model_def = helper.make_model(
+        graph_def,
+        producer_name="optimize",
+        opset_imports=[helper.make_operatorsetid("", 16)],
+    )
+
+    # Ensure valid ONNX:
+    onnx.checker.check_model(model_def)
+
+    # Save the model to a file
+    onnx.save(model_def, "optimize.onnx")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,90 @@
+#!/usr/bin/env python3
+
+# used to generate models: onnx-tests/tests/resize/upsample_opset7.onnx,
+# upsample_opset9.onnx and resize_opset10.onnx
+
+# Upsample takes its scales as an attribute until opset 9, where they become an input.
+# Resize replaces Upsample in opset 10, with the data and the scales as inputs, before
+# opset 11 adds the region of interest and the sizes.
+
+import onnx
+from onnx import helper, TensorProto
+
+SCALES = [1.0, 1.0, 2.0, 2.0]
+
+
+def scales_constant():
+    scales_tensor = helper.make_tensor(
+        name="scales",
+        data_type=TensorProto.FLOAT,
+        dims=[len(SCALES)],
+        vals=SCALES,
+    )
+
+    return helper.make_node(
+        "Constant",
+        name="scales_constant",
+        inputs=[],
+        outputs=["scales"],
+        value=scales_tensor,
+    )
+
+
+def save(nodes, mode, opset, file_name) -> None:
+    graph_def = helper.make_graph(
+        nodes=nodes,
+        name="ResizeGraph",
+        inputs=[
+            helper.make_tensor_value_info("input_tensor", TensorProto.FLOAT, [1, 1, 2, 2]),
+        ],
+        outputs=[
+            helper.make_tensor_value_info("output", TensorProto.FLOAT, [1, 1, 4, 4]),
+        ],
+    )
+
+    model_def = helper.make_model(
+        graph_def,
+        producer_name=mode,
+        opset_imports=[helper.make_operatorsetid("", opset)],
+    )
+
+    # Ensure valid ONNX:
+    onnx.checker.check_model(model_def)
+
+    # Save the model to a file
+    onnx.save(model_def, file_name)
+
+
+def main() -> None:
+    upsample_node = helper.make_node(
+        "Upsample",
+        name="/Upsample",
+        inputs=["input_tensor"],
+        outputs=["output"],
+        mode="nearest",
+        scales=SCALES,
+    )
+    save([upsample_node], "upsample", 7, "upsample_opset7.onnx")
+
+    upsample_node = helper.make_node(
+        "Upsample",
+        name="/Upsample",
+        inputs=["input_tensor", "scales"],
+        outputs=["output"],
+        mode="nearest",
+    )
+    save([scales_constant(), upsample_node], "upsample", 9, "upsample_opset9.onnx")
+
+    # The coordinates of Resize-10 are asymmetric, the output pixel x maps to x / scale
+    resize_node = helper.make_node(
+        "Resize",
+        name="/Resize",
+        inputs=["input_tensor", "scales"],
+        outputs=["output"],
+        mode="linear",
+    )
+    save([scales_constant(), resize_node], "resize", 10, "resize_opset10.onnx")
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,46 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/scatter_elements/scatter_elements.onnx
+
+import onnx
+from onnx import helper, TensorProto
+
+
+def main() -> None:
+    scatter_node = helper.make_node(
+        "ScatterElements",
+        name="/ScatterElements",
+        inputs=["data", "indices", "updates"],
+        outputs=["output"],
+        axis=1,
+        reduction="min",
+    )
+
+    graph_def = helper.make_graph(
+        nodes=[scatter_node],
+        name="ScatterElementsGraph",
+        inputs=[
+            helper.make_tensor_value_info("data", TensorProto.FLOAT, [2, 3]),
+            helper.make_tensor_value_info("indices", TensorProto.INT64, [2, 2]),
+            helper.make_tensor_value_info("updates", TensorProto.FLOAT, [2, 2]),
+        ],
+        outputs=[
+            helper.make_tensor_value_info("output", TensorProto.FLOAT, [2, 3]),
+        ],
+    )
+
+    model_def = helper.make_model(
+        graph_def,
+        producer_name="scatter_elements",
+        opset_imports=[helper.make_operatorsetid("", 18)],
+    )
+
+    # Ensure valid ONNX:
+    onnx.checker.check_model(model_def)
+
+    # Save the model to a file
+    onnx.save(model_def, "scatter_elements.onnx")
+
+
+if __name__ == "__main__":
+    main()
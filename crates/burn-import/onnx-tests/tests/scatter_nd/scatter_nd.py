@@ -0,0 +1,53 @@
+#!/usr/bin/env python3
+
+# used to generate models: onnx-tests/tests/scatter_nd/scatter_nd.onnx and
+# onnx-tests/tests/scatter_nd/scatter_nd_max.onnx
+
+import onnx
+from onnx import helper, TensorProto
+
+
+def build_model(name: str, reduction: str, indices_shape: list, updates_shape: list) -> None:
+    scatter_node = helper.make_node(
+        "ScatterND",
+        name="/ScatterND",
+        inputs=["data", "indices", "updates"],
+        outputs=["output"],
+        reduction=reduction,
+    )
+
+    graph_def = helper.make_graph(
+        nodes=[scatter_node],
+        name="ScatterNDGraph",
+        inputs=[
+            helper.make_tensor_value_info("data", TensorProto.FLOAT, [3, 2]),
+            helper.make_tensor_value_info("indices", TensorProto.INT64, indices_shape),
+            helper.make_tensor_value_info("updates", TensorProto.FLOAT, updates_shape),
+        ],
+        outputs=[
+            helper.make_tensor_value_info("output", TensorProto.FLOAT, [3, 2]),
+        ],
+    )
+
+    model_def = helper.make_model(
+        graph_def,
+        producer_name="scatter_nd",
+        opset_imports=[helper.make_operatorsetid("", 18)],
+    )
+
+    # Ensure valid ONNX:
+    onnx.checker.check_model(model_def)
+
+    # Save the model to a file
+    onnx.save(model_def, name)
+
+
+def main() -> None:
+    # Replaces the rows of the data
+    build_model("scatter_nd.onnx", "none", [2, 1], [2, 2])
+    # Keeps the maximum of the elements, the indices being duplicated
+    build_model("scatter_nd_max.onnx", "max", [3, 1], [3, 2])
+
+
+if __name__ == "__main__":
+    main()
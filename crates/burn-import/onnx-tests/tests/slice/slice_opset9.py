@@ -0,0 +1,47 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/slice/slice_opset9.onnx
+
+# Before opset 10, the starts, ends and axes of Slice are attributes.
+
+import onnx
+from onnx import helper, TensorProto
+
+
+def main() -> None:
+    slice_node = helper.make_node(
+        "Slice",
+        name="/Slice",
+        inputs=["input_tensor"],
+        outputs=["output"],
+        starts=[1, 2],
+        ends=[3, 5],
+        axes=[0, 1],
+    )
+
+    graph_def = helper.make_graph(
+        nodes=[slice_node],
+        name="SliceGraph",
+        inputs=[
+            helper.make_tensor_value_info("input_tensor", TensorProto.FLOAT, [3, 6]),
+        ],
+        outputs=[
+            helper.make_tensor_value_info("output", TensorProto.FLOAT, [2, 3]),
+        ],
+    )
+
+    model_def = helper.make_model(
+        graph_def,
+        producer_name="slice",
+        opset_imports=[helper.make_operatorsetid("", 9)],
+    )
+
+    # Ensure valid ONNX:
+    onnx.checker.check_model(model_def)
+
+    # Save the model to a file
+    onnx.save(model_def, "slice_opset9.onnx")
+
+
+if __name__ == "__main__":
+    main()
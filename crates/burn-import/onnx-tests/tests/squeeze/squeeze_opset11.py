@@ -0,0 +1,45 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/squeeze/squeeze_opset11.onnx
+
+# Before opset 13, the axes of Squeeze are an attribute.
+
+import onnx
+from onnx import helper, TensorProto
+
+
+def main() -> None:
+    squeeze_node = helper.make_node(
+        "Squeeze",
+        name="/Squeeze",
+        inputs=["input_tensor"],
+        outputs=["output"],
+        axes=[2],
+    )
+
+    graph_def = helper.make_graph(
+        nodes=[squeeze_node],
+        name="SqueezeGraph",
+        inputs=[
+            helper.make_tensor_value_info("input_tensor", TensorProto.FLOAT, [3, 4, 1, 5]),
+        ],
+        outputs=[
+            helper.make_tensor_value_info("output", TensorProto.FLOAT, [3, 4, 5]),
+        ],
+    )
+
+    model_def = helper.make_model(
+        graph_def,
+        producer_name="squeeze",
+        opset_imports=[helper.make_operatorsetid("", 11)],
+    )
+
+    # Ensure valid ONNX:
+    onnx.checker.check_model(model_def)
+
+    # Save the model to a file
+    onnx.save(model_def, "squeeze_opset11.onnx")
+
+
+if __name__ == "__main__":
+    main()
@@ -37,9 +37,11 @@ include_models!(
     div,
     dropout_opset16,
     dropout_opset7,
+    einsum,
     equal,
     erf,
     exp,
+    external_data,
     expand,
     expand_tensor,
     expand_shape,
@@ -49,12 +51,14 @@ include_models!(
     gather_scalar,
     gather_shape,
     gather_elements,
+    gather_nd,
     gelu,
     global_avr_pool,
     greater,
     greater_scalar,
     greater_or_equal,
     greater_or_equal_scalar,
+    gru,
     hard_sigmoid,
     layer_norm,
     leaky_relu,
@@ -65,6 +69,7 @@ include_models!(
     linear,
     log,
     log_softmax,
+    lstm,
     mask_where,
     mask_where_broadcast,
     mask_where_scalar_x,
@@ -79,6 +84,7 @@ include_models!(
     mul,
     neg,
     not,
+    optimize,
     pad,
     pow,
     pow_int,
@@ -103,14 +109,22 @@ include_models!(
     resize_2d_bicubic_scale,
     resize_2d_bilinear_scale,
     resize_2d_nearest_scale,
+    resize_opset10,
+    upsample_opset7,
+    upsample_opset9,
+    scatter_elements,
+    scatter_nd,
+    scatter_nd_max,
     shape,
     sigmoid,
     sign,
     sin,
     slice,
+    slice_opset9,
     softmax,
     sqrt,
     squeeze_multiple,
+    squeeze_opset11,
     squeeze_opset13,
     squeeze_opset16,
     sub,
@@ -119,6 +133,7 @@ include_models!(
     sum_int,
     tanh,
     tile,
+    torchscript,
     trilu_upper,
     trilu_lower,
     transpose,
@@ -127,13 +142,19 @@ include_models!(
     unsqueeze_opset16
 );
 
+// The model of `tests/optimize` generated from the optimized graph
+#[allow(clippy::type_complexity)]
+pub mod optimized {
+    include!(concat!(env!("OUT_DIR"), "/model/optimized/optimize.rs"));
+}
+
 #[cfg(test)]
 mod tests {
     use core::f64::consts;
 
     use super::*;
 
-    use burn::tensor::{Bool, Int, Shape, Tensor, TensorData};
+    use burn::tensor::{Bool, Distribution, Int, Shape, Tensor, TensorData};
 
     use float_cmp::ApproxEq;
 
@@ -389,6 +410,28 @@ mod tests {
         assert!(expected_sum.approx_eq(output_sum, (1.0e-4, 2)));
     }
 
+    #[test]
+    fn optimize_graph() {
+        // Initialize the models with weights (loaded from the exported files)
+        let model: optimize::Model<Backend> = optimize::Model::default();
+        let model_optimized: optimized::Model<Backend> = optimized::Model::default();
+
+        let input = Tensor::<Backend, 4>::random(
+            [1, 2, 4, 4],
+            Distribution::Uniform(-1.0, 1.0),
+            &Default::default(),
+        );
+
+        let output = model.forward(input.clone());
+        let output_optimized = model_optimized.forward(input);
+
+        // The batch norm is folded into the convolution, so the results are rounded differently
+        assert_eq!(output.shape(), Shape::from([1, 4, 3, 4]));
+        output
+            .to_data()
+            .assert_approx_eq(&output_optimized.to_data(), 4);
+    }
+
     #[test]
     fn conv3d() {
         // Initialize the model with weights (loaded from the exported file)
@@ -449,6 +492,70 @@ mod tests {
         assert!(expected_sum.approx_eq(output_sum, (1.0e-4, 2)));
     }
 
+    #[test]
+    fn external_data() {
+        // The weight and the bias are read from `external_data.onnx.data`
+        let model: external_data::Model<Backend> = external_data::Model::default();
+
+        let device = Default::default();
+        let input = Tensor::<Backend, 2>::from_floats(
+            [[1.0, 2.0, 3.0, 4.0], [-1.0, 0.0, 1.0, 0.5]],
+            &device,
+        );
+        let output = model.forward(input);
+        let expected = TensorData::from([[16f32, 16.5, 20.5], [3.625, 1.75, 3.375]]);
+
+        output.to_data().assert_approx_eq(&expected, 4);
+    }
+
+    #[test]
+    fn einsum() {
+        let model: einsum::Model<Backend> = einsum::Model::default();
+
+        let device = Default::default();
+        let input = |n: i64| {
+            Tensor::<Backend, 1, Int>::arange(0..n, &device)
+                .float()
+                .sub_scalar(n / 2)
+        };
+        let (matmul, broadcast, chain) = model.forward(
+            input(12).reshape([2, 2, 3]),
+            input(12).reshape([2, 3, 2]),
+            input(24).reshape([2, 1, 2, 2, 3]),
+            input(18).reshape([1, 3, 3, 2]),
+            input(6).reshape([2, 3]),
+            input(6).reshape([3, 2]),
+            input(2),
+        );
+
+        let expected_matmul =
+            TensorData::from([[[64f32, 49.], [28., 22.]], [[10., 13.], [28., 40.]]]);
+        // The batch dimensions [2, 1] and [1, 3] are broadcast to [2, 3]
+        let expected_broadcast = TensorData::from([
+            [
+                [[[235f32, 202.], [172., 148.]], [[109., 94.], [46., 40.]]],
+                [[[37., 4.], [28., 4.]], [[19., 4.], [10., 4.]]],
+                [
+                    [[-161., -194.], [-116., -140.]],
+                    [[-71., -86.], [-26., -32.]],
+                ],
+            ],
+            [
+                [
+                    [[-17., -14.], [-80., -68.]],
+                    [[-143., -122.], [-206., -176.]],
+                ],
+                [[[1., 4.], [-8., 4.]], [[-17., 4.], [-26., 4.]]],
+                [[[19., 22.], [64., 76.]], [[109., 130.], [154., 184.]]],
+            ],
+        ]);
+        let expected_chain = TensorData::from([-10f32, -1.]);
+
+        matmul.to_data().assert_eq(&expected_matmul, true);
+        broadcast.to_data().assert_eq(&expected_broadcast, true);
+        chain.to_data().assert_eq(&expected_chain, true);
+    }
+
     #[test]
     fn erf() {
         let model: erf::Model<Backend> = erf::Model::default();
@@ -534,6 +641,23 @@ mod tests {
         assert_eq!(output.to_data(), expected);
     }
 
+    #[test]
+    fn gather_nd() {
+        // The model has symbolic dimensions for the number of rows and of indices
+        let model: gather_nd::Model<Backend> = gather_nd::Model::default();
+
+        let device = Default::default();
+        let data = Tensor::<Backend, 1, Int>::arange(0..12, &device)
+            .float()
+            .sub_scalar(6)
+            .reshape([2, 3, 2]);
+        let indices = Tensor::<Backend, 2, Int>::from_ints([[0, 1], [1, 2], [1, 0]], &device);
+        let output = model.forward(data, indices);
+        let expected = TensorData::from([[0f32, 0.], [4., 5.], [0., 1.]]);
+
+        output.to_data().assert_eq(&expected, true);
+    }
+
     #[test]
     fn argmax() {
         // Initialize the model with weights (loaded from the exported file)
@@ -600,6 +724,78 @@ mod tests {
         output.to_data().assert_eq(&expected, true);
     }
 
+    #[test]
+    fn scatter_nd() {
+        let model: scatter_nd::Model<Backend> = scatter_nd::Model::default();
+        let device = Default::default();
+
+        let data = Tensor::<Backend, 2>::from_floats(
+            [[1., f32::INFINITY], [3., 4.], [f32::NEG_INFINITY, 6.]],
+            &device,
+        );
+        let indices = Tensor::<Backend, 2, Int>::from_ints([[0], [-1]], &device);
+        let updates = Tensor::<Backend, 2>::from_floats([[7., 8.], [9., f32::INFINITY]], &device);
+        let output = model.forward(data, indices, updates);
+        let expected = TensorData::from([[7f32, 8.], [3., 4.], [9., f32::INFINITY]]);
+
+        output.to_data().assert_eq(&expected, true);
+    }
+
+    #[test]
+    fn scatter_nd_max() {
+        let model: scatter_nd_max::Model<Backend> = scatter_nd_max::Model::default();
+        let device = Default::default();
+
+        let data = Tensor::<Backend, 2>::from_floats(
+            [[1., f32::NEG_INFINITY], [3., 4.], [5., 6.]],
+            &device,
+        );
+        let indices = Tensor::<Backend, 2, Int>::from_ints([[0], [0], [2]], &device);
+        let updates = Tensor::<Backend, 2>::from_floats(
+            [[2., f32::NEG_INFINITY], [0., 1.], [f32::INFINITY, 5.]],
+            &device,
+        );
+        let output = model.forward(data, indices, updates);
+        let expected = TensorData::from([[2f32, 1.], [3., 4.], [f32::INFINITY, 6.]]);
+
+        output.to_data().assert_eq(&expected, true);
+    }
+
+    #[test]
+    fn scatter_elements() {
+        let model: scatter_elements::Model<Backend> = scatter_elements::Model::default();
+        let device = Default::default();
+
+        let data =
+            Tensor::<Backend, 2>::from_floats([[1., f32::INFINITY, 3.], [4., 5., 6.]], &device);
+        let indices = Tensor::<Backend, 2, Int>::from_ints([[1, 1], [2, 0]], &device);
+        let updates =
+            Tensor::<Backend, 2>::from_floats([[7., 2.], [f32::NEG_INFINITY, 8.]], &device);
+        let output = model.forward(data, indices, updates);
+        let expected = TensorData::from([[1f32, 2., 3.], [4., 5., f32::NEG_INFINITY]]);
+
+        output.to_data().assert_eq(&expected, true);
+    }
+
+    #[test]
+    fn slice_opset9() {
+        let model: slice_opset9::Model<Backend> = slice_opset9::Model::default();
+        let device = Default::default();
+
+        let input = Tensor::<Backend, 2>::from_floats(
+            [
+                [1., 2., 3., 4., 5., 6.],
+                [7., 8., 9., 10., 11., 12.],
+                [13., 14., 15., 16., 17., 18.],
+            ],
+            &device,
+        );
+        let output = model.forward(input);
+        let expected = TensorData::from([[9f32, 10., 11.], [15., 16., 17.]]);
+
+        output.to_data().assert_eq(&expected, true);
+    }
+
     #[test]
     fn softmax() {
         // Initialize the model without weights (because the exported file does not contain them)
@@ -965,11 +1161,11 @@ mod tests {
             &device,
         );
 
-        // The sizes are [1, 1, 2, 3]
+        // The sizes are [1, 1, 2, 3], sampled with the default half_pixel coordinates
         let output = model.forward(input);
-        let expected = TensorData::from([[[[0.0f32, 1.5, 3.0], [12.0, 13.5, 15.0]]]]);
+        let expected = TensorData::from([[[[2.1667f32, 3.5, 4.8333], [10.1667, 11.5, 12.8333]]]]);
 
-        output.to_data().assert_eq(&expected, true);
+        output.to_data().assert_approx_eq(&expected, 3);
     }
 
     #[test]
@@ -1055,6 +1251,61 @@ mod tests {
         assert!(expected_sum.approx_eq(output_sum, (1.0e-4, 2)));
     }
 
+    #[test]
+    fn resize_opset10() {
+        let device = Default::default();
+        let model: resize_opset10::Model<Backend> = resize_opset10::Model::new(&device);
+
+        let input = Tensor::<Backend, 4>::from_floats([[[[1.0, 2.0], [3.0, 4.0]]]], &device);
+
+        // The output pixel x maps to x / 2, clamped to the last input pixel
+        let output = model.forward(input);
+        let expected = TensorData::from([[[
+            [1.0f32, 1.5, 2.0, 2.0],
+            [2.0, 2.5, 3.0, 3.0],
+            [3.0, 3.5, 4.0, 4.0],
+            [3.0, 3.5, 4.0, 4.0],
+        ]]]);
+
+        output.to_data().assert_approx_eq(&expected, 4);
+    }
+
+    #[test]
+    fn upsample_opset7() {
+        let device = Default::default();
+        let model: upsample_opset7::Model<Backend> = upsample_opset7::Model::new(&device);
+
+        let input = Tensor::<Backend, 4>::from_floats([[[[1.0, 2.0], [3.0, 4.0]]]], &device);
+
+        let output = model.forward(input);
+        let expected = TensorData::from([[[
+            [1.0f32, 1.0, 2.0, 2.0],
+            [1.0, 1.0, 2.0, 2.0],
+            [3.0, 3.0, 4.0, 4.0],
+            [3.0, 3.0, 4.0, 4.0],
+        ]]]);
+
+        output.to_data().assert_eq(&expected, true);
+    }
+
+    #[test]
+    fn upsample_opset9() {
+        let device = Default::default();
+        let model: upsample_opset9::Model<Backend> = upsample_opset9::Model::new(&device);
+
+        let input = Tensor::<Backend, 4>::from_floats([[[[1.0, 2.0], [3.0, 4.0]]]], &device);
+
+        let output = model.forward(input);
+        let expected = TensorData::from([[[
+            [1.0f32, 1.0, 2.0, 2.0],
+            [1.0, 1.0, 2.0, 2.0],
+            [3.0, 3.0, 4.0, 4.0],
+            [3.0, 3.0, 4.0, 4.0],
+        ]]]);
+
+        output.to_data().assert_eq(&expected, true);
+    }
+
     #[test]
     fn resize_with_scales_1d_nearest() {
         // Initialize the model without weights (because the exported file does not contain them)
@@ -1153,6 +1404,31 @@ mod tests {
         assert!(expected_sum.approx_eq(output_sum, (1.0e-8, 2)));
     }
 
+    #[test]
+    fn gru() {
+        // The reset gate is applied after the hidden transformation (linear_before_reset)
+        let model: gru::Model<Backend> = gru::Model::default();
+
+        let device = Default::default();
+        let input = Tensor::<Backend, 3>::from_floats(
+            [[[1.0, -0.5]], [[0.25, 0.75]], [[-1.0, 0.5]]],
+            &device,
+        );
+        let initial_hidden = Tensor::<Backend, 3>::from_floats([[[0.1, -0.2, 0.3]]], &device);
+        let (output, output_hidden) = model.forward(input, initial_hidden);
+        let expected = TensorData::from([
+            [[[0.093_33f32, -0.146_54, 0.112_98]]],
+            [[[0.003_99, -0.183_86, -0.079_15]]],
+            [[[0.033_85, -0.063_51, 0.003_65]]],
+        ]);
+        let expected_hidden = TensorData::from([[[0.033_85f32, -0.063_51, 0.003_65]]]);
+
+        output.to_data().assert_approx_eq(&expected, 4);
+        output_hidden
+            .to_data()
+            .assert_approx_eq(&expected_hidden, 4);
+    }
+
     #[test]
     fn layer_norm() {
         let device = Default::default();
@@ -1898,6 +2174,30 @@ mod tests {
         output.assert_eq(&expected, true);
     }
 
+    #[test]
+    fn torchscript() {
+        // Traced with `torch.jit.trace`, the convolution, batch norm and linear layers are
+        // submodules called from the root forward method
+        let model: torchscript::Model<Backend> = torchscript::Model::default();
+
+        let device = Default::default();
+        let input = Tensor::<Backend, 1, Int>::arange(0..32, &device)
+            .float()
+            .reshape([1, 2, 4, 4])
+            .div_scalar(16.0)
+            .sub_scalar(1.0);
+        let output = model.forward(input);
+        let expected = TensorData::from([[
+            2.101_538e-10f32,
+            5.514_274e-8,
+            1.446_903e-5,
+            0.003_796_563,
+            0.996_188_9,
+        ]]);
+
+        output.to_data().assert_approx_eq(&expected, 4);
+    }
+
     #[test]
     fn trilu_upper() {
         let device = Default::default();
@@ -2019,6 +2319,29 @@ mod tests {
         assert_eq!(output_scalar, expected_scalar);
     }
 
+    #[test]
+    fn lstm() {
+        // The reverse direction is the second one of the outputs
+        let model: lstm::Model<Backend> = lstm::Model::default();
+
+        let device = Default::default();
+        let input = Tensor::<Backend, 3>::from_floats([[[1.0, -0.5]], [[0.25, 0.75]]], &device);
+        let (output, output_hidden, output_cell) = model.forward(input);
+        let expected = TensorData::from([
+            [[[-0.004_73f32, -0.044_34]], [[0.065_73, 0.018_68]]],
+            [[[-0.048_82, -0.128_35]], [[0.034_39, -0.001_67]]],
+        ]);
+        let expected_hidden =
+            TensorData::from([[[-0.048_82f32, -0.128_35]], [[0.065_73, 0.018_68]]]);
+        let expected_cell = TensorData::from([[[-0.079_87f32, -0.226_55]], [[0.121_23, 0.035_99]]]);
+
+        output.to_data().assert_approx_eq(&expected, 4);
+        output_hidden
+            .to_data()
+            .assert_approx_eq(&expected_hidden, 4);
+        output_cell.to_data().assert_approx_eq(&expected_cell, 4);
+    }
+
     #[test]
     fn mask_where() {
         let device = Default::default();
@@ -2119,6 +2442,17 @@ mod tests {
         assert_eq!(expected_shape, output.shape());
     }
 
+    #[test]
+    fn squeeze_opset11() {
+        let device = Default::default();
+        let model = squeeze_opset11::Model::<Backend>::new(&device);
+        let input_shape = Shape::from([3, 4, 1, 5]);
+        let expected_shape = Shape::from([3, 4, 5]);
+        let input = Tensor::ones(input_shape, &device);
+        let output = model.forward(input);
+        assert_eq!(expected_shape, output.shape());
+    }
+
     #[test]
     fn squeeze_opset13() {
         let device = Default::default();
@@ -0,0 +1,44 @@
+#!/usr/bin/env python3
+
+# used to generate model: onnx-tests/tests/torchscript/torchscript.pt
+
+import torch
+import torch.nn as nn
+import torch.nn.functional as F
+
+
+class Model(nn.Module):
+    def __init__(self):
+        super(Model, self).__init__()
+        self.conv = nn.Conv2d(2, 3, kernel_size=3, padding=1)
+        self.norm = nn.BatchNorm2d(3)
+        self.fc = nn.Linear(12, 5)
+
+    def forward(self, x):
+        x = F.relu(self.norm(self.conv(x)))
+        x = F.max_pool2d(x, 2)
+        x = torch.flatten(x, 1)
+        return torch.softmax(self.fc(x), -1)
+
+
+def main():
+    model = Model()
+    model.eval()
+
+    # Deterministic weights, evenly spaced in [-1, 1]
+    with torch.no_grad():
+        for param in model.parameters():
+            param.copy_(torch.linspace(-1, 1, param.numel()).reshape(param.shape))
+        model.norm.running_mean.copy_(torch.tensor([0.1, -0.1, 0.2]))
+        model.norm.running_var.copy_(torch.tensor([1.0, 0.5, 2.0]))
+
+    test_input = torch.arange(32, dtype=torch.float32).reshape(1, 2, 4, 4) / 16 - 1
+    traced = torch.jit.trace(model, test_input)
+    torch.jit.save(traced, "torchscript.pt")
+
+    print(f"Test input data: {test_input}")
+    print(f"Test output data: {traced(test_input)}")
+
+
+if __name__ == "__main__":
+    main()
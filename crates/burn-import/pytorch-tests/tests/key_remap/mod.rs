@@ -114,4 +114,44 @@ mod tests {
 
         output.to_data().assert_approx_eq(&expected.to_data(), 7);
     }
+
+    #[test]
+    fn key_remap_dry_run() {
+        let device = Default::default();
+        let model = Net::<Backend>::init(&device);
+        let recorder = PyTorchFileRecorder::<FullPrecisionSettings>::default();
+
+        // Without remapping, the keys of the file are nested in "conv"
+        let report = recorder
+            .dry_run(LoadArgs::new("tests/key_remap/key_remap.pt".into()), &model)
+            .expect("Should read the keys of the file");
+
+        assert!(!report.is_complete());
+        assert_eq!(report.unused.len(), 3);
+        assert_eq!(report.missing.len(), 3);
+
+        let report = recorder
+            .dry_run(
+                LoadArgs::new("tests/key_remap/key_remap.pt".into())
+                    .with_key_remap("conv\\.(.*)", "$1"),
+                &model,
+            )
+            .expect("Should read the keys of the file");
+
+        assert!(report.is_complete());
+        assert!(report.unused.is_empty());
+        let keys = report
+            .matched
+            .iter()
+            .map(|entry| (entry.key.as_str(), entry.shape.as_slice()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            keys,
+            vec![
+                ("conv1.bias", [2].as_slice()),
+                ("conv1.weight", [2, 2, 2, 2].as_slice()),
+                ("conv2.weight", [2, 2, 2, 2].as_slice()),
+            ]
+        );
+    }
 }
@@ -0,0 +1,343 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::error::Error;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use burn::backend::NdArray;
+use burn::record::{FullPrecisionSettings, NamedMpkFileRecorder, Recorder};
+use burn_import::onnx::unsupported_ops;
+use onnx_ir::{
+    ir::{ArgType, Argument, Data},
+    parse_onnx,
+};
+use serde::Deserialize;
+
+/// The summary of a model file.
+#[derive(Debug, Default)]
+pub struct Report {
+    format: String,
+    inputs: Vec<TensorEntry>,
+    outputs: Vec<TensorEntry>,
+    /// The number of nodes by operator, for the graphs.
+    operators: BTreeMap<String, usize>,
+    unsupported: Vec<String>,
+    tensors: Vec<TensorEntry>,
+}
+
+/// A tensor of a model, whose dimensions are `None` when they are only known at runtime.
+#[derive(Debug)]
+struct TensorEntry {
+    name: String,
+    dtype: String,
+    shape: Vec<Option<usize>>,
+}
+
+impl TensorEntry {
+    fn new(name: String, dtype: String, shape: Vec<usize>) -> Self {
+        Self {
+            name,
+            dtype,
+            shape: shape.into_iter().map(Some).collect(),
+        }
+    }
+
+    fn num_elements(&self) -> usize {
+        self.shape.iter().map(|dim| dim.unwrap_or(0)).product()
+    }
+}
+
+impl core::fmt::Display for TensorEntry {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let shape = self
+            .shape
+            .iter()
+            .map(|dim| match dim {
+                Some(dim) => dim.to_string(),
+                None => "?".to_string(),
+            })
+            .collect::<Vec<_>>();
+
+        write!(f, "{} {} [{}]", self.name, self.dtype, shape.join(", "))
+    }
+}
+
+/// Reads the summary of a model, by extension.
+pub fn inspect(path: &Path, top_level_key: Option<&str>) -> Result<Report, Box<dyn Error>> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("onnx") => Ok(inspect_onnx(path)),
+        Some("pt") | Some("pth") => inspect_pytorch(path, top_level_key),
+        Some("safetensors") => inspect_safetensors(path),
+        Some("mpk") => inspect_mpk(path),
+        _ => Err("unknown extension, expected .onnx, .pt, .pth, .safetensors or .mpk".into()),
+    }
+}
+
+fn inspect_onnx(path: &Path) -> Report {
+    let graph = parse_onnx(path);
+
+    let mut operators = BTreeMap::new();
+    for node in graph.nodes.iter() {
+        *operators.entry(node.node_type.to_string()).or_insert(0) += 1;
+    }
+
+    // The initializers and the lifted constants are the inputs with a value
+    let mut names = HashSet::new();
+    let tensors = graph
+        .nodes
+        .iter()
+        .flat_map(|node| node.inputs.iter())
+        .filter(|input| input.value.is_some() && names.insert(input.name.clone()))
+        .map(onnx_entry)
+        .collect();
+
+    Report {
+        format: format!("ONNX (opset {})", graph.opset_version),
+        inputs: graph.inputs.iter().map(onnx_entry).collect(),
+        outputs: graph.outputs.iter().map(onnx_entry).collect(),
+        operators,
+        unsupported: unsupported_ops(&graph)
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        tensors,
+    }
+}
+
+fn onnx_entry(arg: &Argument) -> TensorEntry {
+    let (dtype, shape) = match &arg.ty {
+        ArgType::Scalar(elem_type) => (format!("{elem_type:?}"), vec![]),
+        ArgType::Shape(rank) => ("Int64".to_string(), vec![Some(*rank)]),
+        ArgType::Tensor(tensor) => {
+            let shape = match (&tensor.shape, &arg.value) {
+                (Some(shape), _) => shape.clone(),
+                // The values of a rank 1 tensor give its shape
+                (None, Some(data)) if tensor.dim == 1 => vec![Some(data_len(data))],
+                (None, _) => vec![None; tensor.dim],
+            };
+            (format!("{:?}", tensor.elem_type), shape)
+        }
+    };
+
+    TensorEntry {
+        name: arg.name.clone(),
+        dtype,
+        shape,
+    }
+}
+
+fn data_len(data: &Data) -> usize {
+    match data {
+        Data::Bools(values) => values.len(),
+        Data::Float16s(values) => values.len(),
+        Data::Float32s(values) => values.len(),
+        Data::Float64s(values) => values.len(),
+        Data::Int32s(values) => values.len(),
+        Data::Int64s(values) => values.len(),
+        Data::Strings(values) => values.len(),
+        _ => 1,
+    }
+}
+
+fn inspect_pytorch(path: &Path, top_level_key: Option<&str>) -> Result<Report, Box<dyn Error>> {
+    let mut tensors = candle_core::pickle::read_pth_tensor_info(path, false, top_level_key)?
+        .into_iter()
+        .map(|info| {
+            let shape = info.layout.shape().dims().to_vec();
+            TensorEntry::new(info.name, format!("{:?}", info.dtype), shape)
+        })
+        .collect::<Vec<_>>();
+    tensors.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(Report {
+        format: "PyTorch".to_string(),
+        tensors,
+        ..Default::default()
+    })
+}
+
+/// The maximum size of the header of a safetensors file, as in the safetensors format.
+const MAX_SAFETENSORS_HEADER_SIZE: u64 = 100_000_000;
+
+/// The description of a tensor in the header of a safetensors file.
+#[derive(Deserialize)]
+struct SafetensorsInfo {
+    dtype: String,
+    shape: Vec<usize>,
+}
+
+fn inspect_safetensors(path: &Path) -> Result<Report, Box<dyn Error>> {
+    // The file starts with the size of its JSON header, as a little-endian u64
+    let mut file = File::open(path)?;
+    let mut size = [0; 8];
+    file.read_exact(&mut size)?;
+
+    let size = u64::from_le_bytes(size);
+    if size > MAX_SAFETENSORS_HEADER_SIZE {
+        return Err(format!("the safetensors header of {size} bytes exceeds the limit").into());
+    }
+    if size > file.metadata()?.len().saturating_sub(8) {
+        return Err(format!("the safetensors header of {size} bytes exceeds the file").into());
+    }
+
+    let mut header = vec![0; size as usize];
+    file.read_exact(&mut header)?;
+
+    let header: HashMap<String, serde_json::Value> = serde_json::from_slice(&header)?;
+
+    let mut tensors = Vec::new();
+    for (name, value) in header {
+        if name == "__metadata__" {
+            continue;
+        }
+        let info: SafetensorsInfo = serde_json::from_value(value)?;
+        tensors.push(TensorEntry::new(name, info.dtype, info.shape));
+    }
+    tensors.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(Report {
+        format: "safetensors".to_string(),
+        tensors,
+        ..Default::default()
+    })
+}
+
+fn inspect_mpk(path: &Path) -> Result<Report, Box<dyn Error>> {
+    let recorder = NamedMpkFileRecorder::<FullPrecisionSettings>::new();
+    let tensors = Recorder::<NdArray>::inspect(&recorder, path.to_path_buf())?
+        .into_iter()
+        .map(|entry| TensorEntry::new(entry.path, format!("{:?}", entry.dtype), entry.shape))
+        .collect();
+
+    Ok(Report {
+        format: "Burn named msgpack".to_string(),
+        tensors,
+        ..Default::default()
+    })
+}
+
+impl Report {
+    /// Prints the summary, with each tensor if `tensors` is true.
+    pub fn print(&self, tensors: bool) {
+        println!("Format: {}", self.format);
+
+        if !self.inputs.is_empty() {
+            println!("Inputs:");
+            self.inputs.iter().for_each(|input| println!("  {input}"));
+            println!("Outputs:");
+            self.outputs
+                .iter()
+                .for_each(|output| println!("  {output}"));
+        }
+
+        if !self.operators.is_empty() {
+            let nodes = self.operators.values().sum::<usize>();
+            println!("Operators ({nodes} nodes):");
+            for (operator, count) in self.operators.iter() {
+                println!("  {operator}: {count}");
+            }
+
+            match self.unsupported.is_empty() {
+                true => println!("Unsupported operators: none"),
+                false => println!("Unsupported operators: {}", self.unsupported.join(", ")),
+            }
+        }
+
+        let mut dtypes = BTreeMap::new();
+        for tensor in self.tensors.iter() {
+            *dtypes.entry(tensor.dtype.as_str()).or_insert(0) += tensor.num_elements();
+        }
+
+        println!("Tensors: {}", self.tensors.len());
+        println!("Parameters: {}", dtypes.values().sum::<usize>());
+        for (dtype, count) in dtypes {
+            println!("  {dtype}: {count}");
+        }
+
+        if tensors {
+            println!("Shapes:");
+            self.tensors
+                .iter()
+                .for_each(|tensor| println!("  {tensor}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::write_safetensors;
+
+    #[test]
+    fn inspects_the_graph_of_an_onnx_file() {
+        let file =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("onnx-tests/tests/gather_nd/gather_nd.onnx");
+
+        let report = inspect(&file, None).unwrap();
+
+        assert_eq!(report.format, "ONNX (opset 16)");
+        assert_eq!(report.operators.values().sum::<usize>(), 2);
+        assert_eq!(report.operators.get("Relu"), Some(&1));
+        assert!(report.unsupported.is_empty());
+        assert_eq!(report.inputs[0].shape, [None, Some(3), Some(2)]);
+        assert_eq!(report.outputs[0].shape.len(), 2);
+    }
+
+    #[test]
+    fn inspects_the_tensors_of_a_safetensors_file() {
+        let file = write_safetensors(
+            "burn_import_cli_inspect.safetensors",
+            &[
+                ("linear.weight", "F16", vec![2, 3], vec![0; 12]),
+                ("linear.bias", "F16", vec![3], vec![0; 6]),
+            ],
+        );
+
+        let report = inspect(&file, None).unwrap();
+
+        assert_eq!(report.format, "safetensors");
+        let tensors = report
+            .tensors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(tensors, ["linear.bias F16 [3]", "linear.weight F16 [2, 3]"]);
+        assert_eq!(report.tensors[1].num_elements(), 6);
+    }
+
+    #[test]
+    fn rejects_a_safetensors_header_larger_than_the_file() {
+        let path = std::env::temp_dir().join("burn_import_cli_truncated.safetensors");
+        let mut file = 1024_u64.to_le_bytes().to_vec();
+        file.extend(b"{}");
+        std::fs::write(&path, file).unwrap();
+
+        let err = inspect(&path, None).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "the safetensors header of 1024 bytes exceeds the file"
+        );
+    }
+
+    #[test]
+    fn rejects_a_safetensors_header_larger_than_the_limit() {
+        // The file is sparse, its size is not allocated
+        let path = std::env::temp_dir().join("burn_import_cli_huge_header.safetensors");
+        let size = MAX_SAFETENSORS_HEADER_SIZE + 1;
+        std::fs::write(&path, size.to_le_bytes()).unwrap();
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_len(size + 8)
+            .unwrap();
+
+        let err = inspect(&path, None).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            format!("the safetensors header of {size} bytes exceeds the limit")
+        );
+    }
+}
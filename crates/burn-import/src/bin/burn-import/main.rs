@@ -0,0 +1,349 @@
+mod inspect;
+mod record;
+
+use std::path::PathBuf;
+
+use burn::record::RecordDiff;
+use burn_import::onnx::{ModelGen, RecordType};
+use clap::{Parser, Subcommand, ValueEnum};
+use regex::Regex;
+
+/// Inspects and converts models for Burn.
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    #[clap(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand, Debug)]
+enum Commands {
+    /// Prints the operators, the tensors and the parameter counts of a model
+    Inspect(InspectArgs),
+    /// Generates the code and the record of an ONNX or TorchScript model
+    Convert(ConvertArgs),
+    /// Converts the tensors of a PyTorch or safetensors file to a Burn record
+    Record(RecordArgs),
+    /// Compares the parameters of two named msgpack (.mpk) records
+    Diff(DiffArgs),
+}
+
+#[derive(Parser, Debug)]
+struct InspectArgs {
+    /// The ONNX (.onnx), PyTorch (.pt, .pth), safetensors (.safetensors) or Burn (.mpk) file
+    file: PathBuf,
+
+    /// Print each tensor of the model
+    #[clap(short = 't', long = "tensors")]
+    tensors: bool,
+
+    /// The top-level key of the state dict of a PyTorch file
+    #[clap(long = "top-level-key")]
+    top_level_key: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+struct ConvertArgs {
+    /// The ONNX (.onnx) or TorchScript (.pt) file
+    file: PathBuf,
+
+    /// The directory of the generated code and record
+    #[clap(short = 'o', long = "out-dir")]
+    out_dir: PathBuf,
+
+    /// The format of the record
+    #[clap(
+        short = 'r',
+        long = "record-type",
+        value_enum,
+        default_value_t = RecordTypeValues::NamedMpk
+    )]
+    record_type: RecordTypeValues,
+
+    /// Save the parameters in half precision
+    #[clap(long = "half-precision")]
+    half_precision: bool,
+
+    /// Embed the record in the generated code, which requires the bincode record type
+    #[clap(long = "embed-states")]
+    embed_states: bool,
+
+    /// Optimize the graph, e.g. fold the batch norms into the convolutions
+    #[clap(short = 'O', long = "optimize")]
+    optimize: bool,
+
+    /// Also save the parsed graph, as `.graph.txt`
+    #[clap(short = 'd', long = "development")]
+    development: bool,
+
+    /// Comma separated ranks of the inputs of a TorchScript model, e.g. `4,2`
+    #[clap(long = "input-ranks", value_delimiter = ',')]
+    input_ranks: Vec<usize>,
+}
+
+#[derive(Parser, Debug)]
+struct RecordArgs {
+    /// The PyTorch (.pt, .pth) or safetensors (.safetensors) file
+    file: PathBuf,
+
+    /// The record file, whose extension is set by the record type
+    #[clap(short = 'o', long = "out-file")]
+    out_file: PathBuf,
+
+    /// The format of the record
+    #[clap(
+        short = 'r',
+        long = "record-type",
+        value_enum,
+        default_value_t = RecordTypeValues::NamedMpk
+    )]
+    record_type: RecordTypeValues,
+
+    /// Save the parameters in half precision
+    #[clap(long = "half-precision")]
+    half_precision: bool,
+
+    /// The top-level key of the state dict of a PyTorch file
+    #[clap(long = "top-level-key")]
+    top_level_key: Option<String>,
+
+    /// Rename the keys matching a pattern, e.g. `^conv\.(.*)=$1`
+    #[clap(long = "key-remap", value_parser = parse_key_remap)]
+    key_remap: Vec<(Regex, String)>,
+
+    /// Transpose the last two dimensions of the tensors whose keys match a pattern, e.g. the
+    /// weights of the linear layers
+    #[clap(long = "transpose")]
+    transpose: Vec<Regex>,
+}
+
+#[derive(Parser, Debug)]
+struct DiffArgs {
+    /// The first record
+    first: PathBuf,
+
+    /// The second record
+    second: PathBuf,
+
+    /// Print the parameters which are unchanged too
+    #[clap(short = 'a', long = "all")]
+    all: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum RecordTypeValues {
+    PrettyJson,
+    NamedMpkGz,
+    NamedMpk,
+    Bincode,
+}
+
+impl From<RecordTypeValues> for RecordType {
+    fn from(value: RecordTypeValues) -> Self {
+        match value {
+            RecordTypeValues::PrettyJson => RecordType::PrettyJson,
+            RecordTypeValues::NamedMpkGz => RecordType::NamedMpkGz,
+            RecordTypeValues::NamedMpk => RecordType::NamedMpk,
+            RecordTypeValues::Bincode => RecordType::Bincode,
+        }
+    }
+}
+
+/// Parses a key remapping, as `PATTERN=REPLACEMENT`.
+fn parse_key_remap(value: &str) -> Result<(Regex, String), String> {
+    let (pattern, replacement) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected PATTERN=REPLACEMENT, got {value}"))?;
+    let pattern = Regex::new(pattern).map_err(|err| err.to_string())?;
+
+    Ok((pattern, replacement.to_string()))
+}
+
+fn main() {
+    let args = Args::parse();
+
+    match args.command {
+        Commands::Inspect(args) => {
+            let report = inspect::inspect(&args.file, args.top_level_key.as_deref())
+                .unwrap_or_else(|err| panic!("Could not inspect {:?}: {err}", args.file));
+            report.print(args.tensors);
+        }
+        Commands::Convert(args) => convert(args),
+        Commands::Record(args) => record(args),
+        Commands::Diff(args) => diff(args),
+    }
+}
+
+fn diff(args: DiffArgs) {
+    let diff = RecordDiff::from_files(&args.first, &args.second).unwrap_or_else(|err| {
+        panic!(
+            "Could not compare {:?} and {:?}: {err}",
+            args.first, args.second
+        )
+    });
+
+    println!("{}", diff.to_string().trim_end());
+    if args.all {
+        for tensor in diff.tensors.iter().filter(|tensor| !tensor.is_changed()) {
+            println!("Unchanged: {}", tensor.path);
+        }
+    }
+}
+
+fn record(args: RecordArgs) {
+    let options = record::RecordOptions {
+        record_type: args.record_type.into(),
+        half_precision: args.half_precision,
+        top_level_key: args.top_level_key,
+        key_remap: args.key_remap,
+        transpose: args.transpose,
+    };
+
+    let num_params = record::convert(&args.file, args.out_file.clone(), &options)
+        .unwrap_or_else(|err| panic!("Could not convert {:?}: {err}", args.file));
+    println!("Saved {num_params} parameters to {:?}", args.out_file);
+}
+
+fn convert(args: ConvertArgs) {
+    let mut model_gen = ModelGen::new();
+    model_gen
+        .input(args.file.to_str().expect("The path should be valid UTF-8"))
+        .out_dir(
+            args.out_dir
+                .to_str()
+                .expect("The path should be valid UTF-8"),
+        )
+        .record_type(args.record_type.into())
+        .half_precision(args.half_precision)
+        .embed_states(args.embed_states)
+        .optimize_graph(args.optimize)
+        .development(args.development);
+
+    if !args.input_ranks.is_empty() {
+        model_gen.input_ranks(&args.input_ranks);
+    }
+
+    model_gen.run_from_cli();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A tensor `(name, dtype, shape, bytes)` of a safetensors file.
+    pub(crate) type TensorEntry = (&'static str, &'static str, Vec<usize>, Vec<u8>);
+
+    /// Writes the tensors to a safetensors file.
+    pub(crate) fn write_safetensors(name: &str, tensors: &[TensorEntry]) -> PathBuf {
+        let mut header = serde_json::Map::new();
+        let mut data = Vec::new();
+        for (key, dtype, shape, bytes) in tensors {
+            header.insert(
+                key.to_string(),
+                serde_json::json!({
+                    "dtype": dtype,
+                    "shape": shape,
+                    "data_offsets": [data.len(), data.len() + bytes.len()],
+                }),
+            );
+            data.extend_from_slice(bytes);
+        }
+        let header = serde_json::to_vec(&header).unwrap();
+
+        let path = std::env::temp_dir().join(name);
+        let mut file = (header.len() as u64).to_le_bytes().to_vec();
+        file.extend(header);
+        file.extend(data);
+        std::fs::write(&path, file).unwrap();
+        path
+    }
+
+    #[test]
+    fn parses_the_record_command() {
+        let args = Args::try_parse_from([
+            "burn-import",
+            "record",
+            "model.pt",
+            "-o",
+            "model",
+            "--half-precision",
+            "--key-remap",
+            r"^conv\.(.*)=$1",
+            "--transpose",
+            r"fc\d\.weight",
+        ])
+        .unwrap();
+
+        let Commands::Record(args) = args.command else {
+            panic!("Expected the record command");
+        };
+        assert_eq!(args.file, PathBuf::from("model.pt"));
+        assert_eq!(args.out_file, PathBuf::from("model"));
+        assert_eq!(args.record_type, RecordTypeValues::NamedMpk);
+        assert!(args.half_precision);
+        assert_eq!(args.key_remap.len(), 1);
+        assert_eq!(args.key_remap[0].0.as_str(), r"^conv\.(.*)");
+        assert_eq!(args.key_remap[0].1, "$1");
+        assert!(args.transpose[0].is_match("fc1.weight"));
+    }
+
+    #[test]
+    fn rejects_a_key_remap_without_replacement() {
+        let args = Args::try_parse_from([
+            "burn-import",
+            "record",
+            "model.pt",
+            "-o",
+            "model",
+            "--key-remap",
+            "conv",
+        ]);
+
+        assert!(args.is_err());
+    }
+
+    #[test]
+    fn parses_the_input_ranks_of_the_convert_command() {
+        let args = Args::try_parse_from([
+            "burn-import",
+            "convert",
+            "model.pt",
+            "-o",
+            "out",
+            "--input-ranks",
+            "4,2",
+        ])
+        .unwrap();
+
+        let Commands::Convert(args) = args.command else {
+            panic!("Expected the convert command");
+        };
+        assert_eq!(args.input_ranks, [4, 2]);
+    }
+
+    #[test]
+    fn records_a_safetensors_file() {
+        let file = write_safetensors(
+            "burn_import_cli_command.safetensors",
+            &[("weight", "F32", vec![1], 1.0_f32.to_le_bytes().to_vec())],
+        );
+        let out_file = std::env::temp_dir().join("burn_import_cli_command");
+        let args = Args::try_parse_from([
+            "burn-import".as_ref(),
+            "record".as_ref(),
+            file.as_os_str(),
+            "-o".as_ref(),
+            out_file.as_os_str(),
+            "-r".as_ref(),
+            "pretty-json".as_ref(),
+        ])
+        .unwrap();
+
+        let Commands::Record(args) = args.command else {
+            panic!("Expected the record command");
+        };
+        record(args);
+
+        assert!(out_file.with_extension("json").exists());
+    }
+}
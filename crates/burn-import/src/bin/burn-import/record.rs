@@ -0,0 +1,383 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+use burn::backend::NdArray;
+use burn::module::ParamId;
+use burn::record::serde::data::remap;
+use burn::record::{
+    BinFileRecorder, FullPrecisionSettings, HalfPrecisionSettings, NamedMpkFileRecorder,
+    NamedMpkGzFileRecorder, ParamSerde, PrecisionSettings, PrettyJsonFileRecorder, Record,
+    Recorder, RecorderError,
+};
+use burn::tensor::{backend::Backend, TensorData};
+use burn_import::onnx::RecordType;
+use candle_core::{pickle, safetensors, DType, Device, Tensor};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// The options of the conversion of the tensors of a file to a record.
+#[derive(Debug, Default)]
+pub struct RecordOptions {
+    /// The format of the record.
+    pub record_type: RecordType,
+    /// Whether to save the parameters in half precision.
+    pub half_precision: bool,
+    /// The top-level key of the state dict of a PyTorch file.
+    pub top_level_key: Option<String>,
+    /// The patterns of the keys to rename, with their replacement.
+    pub key_remap: Vec<(Regex, String)>,
+    /// The patterns of the keys whose last two dimensions are transposed.
+    pub transpose: Vec<Regex>,
+}
+
+/// The record of a module which isn't known, built from the keys of the tensors.
+///
+/// The fields of a node whose names are all indices are the items of a list, as the record of a
+/// `Vec` of modules.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum RecordNode {
+    Param(ParamSerde<TensorData>),
+    Items(Vec<RecordNode>),
+    Fields(BTreeMap<String, RecordNode>),
+}
+
+// The parameters are converted to the precision settings when read, so the item is the record.
+impl<B: Backend> Record<B> for RecordNode {
+    type Item<S: PrecisionSettings> = RecordNode;
+
+    fn into_item<S: PrecisionSettings>(self) -> Self::Item<S> {
+        self
+    }
+
+    fn from_item<S: PrecisionSettings>(item: Self::Item<S>, _device: &B::Device) -> Self {
+        item
+    }
+}
+
+/// Converts the tensors of a PyTorch (.pt, .pth) or safetensors (.safetensors) file to a record,
+/// and returns the number of parameters of the record.
+pub fn convert(
+    file: &Path,
+    out_file: PathBuf,
+    options: &RecordOptions,
+) -> Result<usize, Box<dyn Error>> {
+    match options.half_precision {
+        true => convert_with::<HalfPrecisionSettings>(file, out_file, options),
+        false => convert_with::<FullPrecisionSettings>(file, out_file, options),
+    }
+}
+
+fn convert_with<PS: PrecisionSettings>(
+    file: &Path,
+    out_file: PathBuf,
+    options: &RecordOptions,
+) -> Result<usize, Box<dyn Error>> {
+    let tensors = read_tensors(file, options.top_level_key.as_deref())?;
+    let num_params = tensors.len();
+
+    let record = record_node::<PS>(tensors, options)?;
+    save::<PS>(record, options.record_type, out_file)?;
+
+    Ok(num_params)
+}
+
+/// Reads the tensors of a file, by key.
+fn read_tensors(
+    file: &Path,
+    top_level_key: Option<&str>,
+) -> Result<HashMap<String, Tensor>, Box<dyn Error>> {
+    match file.extension().and_then(|ext| ext.to_str()) {
+        Some("pt") | Some("pth") => Ok(pickle::read_all_with_key(file, top_level_key)?
+            .into_iter()
+            .collect()),
+        Some("safetensors") if top_level_key.is_some() => {
+            Err("a safetensors file has no top-level key".into())
+        }
+        Some("safetensors") => Ok(safetensors::load(file, &Device::Cpu)?),
+        _ => Err("unknown extension, expected .pt, .pth or .safetensors".into()),
+    }
+}
+
+/// Builds the record of the tensors, with the remapped keys as the paths of the parameters.
+fn record_node<PS: PrecisionSettings>(
+    tensors: HashMap<String, Tensor>,
+    options: &RecordOptions,
+) -> Result<RecordNode, Box<dyn Error>> {
+    let (tensors, _) = remap(tensors, options.key_remap.clone());
+
+    let mut fields = BTreeMap::new();
+    for (key, mut tensor) in tensors {
+        let rank = tensor.rank();
+        if rank >= 2
+            && options
+                .transpose
+                .iter()
+                .any(|pattern| pattern.is_match(&key))
+        {
+            tensor = tensor.transpose(rank - 2, rank - 1)?.contiguous()?;
+        }
+
+        let param = ParamSerde::new(ParamId::new().serialize(), tensor_data::<PS>(&tensor)?);
+        let path = key.split('.').collect::<Vec<_>>();
+        insert(&mut fields, &path, param)
+            .map_err(|name| format!("{name} is both a parameter and a module, in {key}"))?;
+    }
+
+    Ok(RecordNode::Fields(fields).with_items())
+}
+
+/// Inserts the parameter in the fields, returning the name of the conflicting field if any.
+fn insert(
+    fields: &mut BTreeMap<String, RecordNode>,
+    path: &[&str],
+    param: ParamSerde<TensorData>,
+) -> Result<(), String> {
+    match path {
+        [name] => match fields.insert(name.to_string(), RecordNode::Param(param)) {
+            Some(_) => Err(name.to_string()),
+            None => Ok(()),
+        },
+        [name, path @ ..] => {
+            let node = fields
+                .entry(name.to_string())
+                .or_insert_with(|| RecordNode::Fields(BTreeMap::new()));
+
+            match node {
+                RecordNode::Fields(fields) => insert(fields, path, param),
+                _ => Err(name.to_string()),
+            }
+        }
+        [] => Err(String::new()),
+    }
+}
+
+impl RecordNode {
+    /// Turns the nodes whose fields are all indices into lists, sorted by index.
+    fn with_items(self) -> Self {
+        let fields = match self {
+            RecordNode::Fields(fields) => fields,
+            node => return node,
+        };
+
+        let fields = fields
+            .into_iter()
+            .map(|(name, node)| (name, node.with_items()));
+
+        let mut items = Vec::new();
+        let mut named = BTreeMap::new();
+        for (name, node) in fields {
+            match name.parse::<usize>() {
+                Ok(index) => items.push((index, node)),
+                Err(_) => {
+                    named.insert(name, node);
+                }
+            }
+        }
+
+        if !named.is_empty() || items.is_empty() {
+            named.extend(items.into_iter().map(|(i, node)| (i.to_string(), node)));
+            return RecordNode::Fields(named);
+        }
+
+        items.sort_by_key(|(index, _)| *index);
+        RecordNode::Items(items.into_iter().map(|(_, node)| node).collect())
+    }
+}
+
+/// The data of a tensor, with the float or the int element type of the precision settings.
+fn tensor_data<PS: PrecisionSettings>(tensor: &Tensor) -> candle_core::Result<TensorData> {
+    let shape = tensor.dims().to_vec();
+    let tensor = tensor.flatten_all()?;
+
+    let data = match tensor.dtype() {
+        DType::U8 | DType::U32 | DType::I64 => {
+            let values = tensor.to_dtype(DType::I64)?.to_vec1::<i64>()?;
+            TensorData::new(values, shape).convert::<PS::IntElem>()
+        }
+        DType::BF16 | DType::F16 | DType::F32 | DType::F64 => {
+            let values = tensor.to_dtype(DType::F64)?.to_vec1::<f64>()?;
+            TensorData::new(values, shape).convert::<PS::FloatElem>()
+        }
+    };
+
+    Ok(data)
+}
+
+fn save<PS: PrecisionSettings>(
+    record: RecordNode,
+    record_type: RecordType,
+    file: PathBuf,
+) -> Result<(), RecorderError> {
+    match record_type {
+        RecordType::PrettyJson => {
+            Recorder::<NdArray>::record(&PrettyJsonFileRecorder::<PS>::new(), record, file)
+        }
+        RecordType::NamedMpkGz => {
+            Recorder::<NdArray>::record(&NamedMpkGzFileRecorder::<PS>::new(), record, file)
+        }
+        RecordType::NamedMpk => {
+            Recorder::<NdArray>::record(&NamedMpkFileRecorder::<PS>::new(), record, file)
+        }
+        RecordType::Bincode => {
+            Recorder::<NdArray>::record(&BinFileRecorder::<PS>::new(), record, file)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::write_safetensors;
+
+    use burn::module::Module;
+    use burn::nn::Linear;
+    use burn::record::read_params;
+    use burn::tensor::DType as BurnDType;
+    use burn_import::pytorch::PyTorchFileRecorder;
+
+    #[derive(Module, Debug)]
+    struct Net<B: Backend> {
+        fc1: Linear<B>,
+        fc2: Linear<B>,
+    }
+
+    fn f32_bytes(values: &[f32]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn converts_the_linear_layers_of_a_pytorch_file() {
+        let file =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("pytorch-tests/tests/linear/linear.pt");
+        let out_file = std::env::temp_dir().join("burn_import_cli_linear");
+        let options = RecordOptions {
+            record_type: RecordType::NamedMpk,
+            transpose: vec![Regex::new(r"weight$").unwrap()],
+            ..Default::default()
+        };
+
+        let num_params = convert(&file, out_file.clone(), &options).unwrap();
+        assert_eq!(num_params, 3);
+
+        let device = Default::default();
+        let converted: NetRecord<NdArray> = NamedMpkFileRecorder::<FullPrecisionSettings>::new()
+            .load(out_file, &device)
+            .unwrap();
+        let expected: NetRecord<NdArray> = PyTorchFileRecorder::<FullPrecisionSettings>::default()
+            .load(file.into(), &device)
+            .unwrap();
+
+        for (converted, expected) in [(converted.fc1, expected.fc1), (converted.fc2, expected.fc2)]
+        {
+            converted
+                .weight
+                .to_data()
+                .assert_eq(&expected.weight.to_data(), true);
+            match (converted.bias, expected.bias) {
+                (Some(converted), Some(expected)) => {
+                    converted.to_data().assert_eq(&expected.to_data(), true)
+                }
+                (None, None) => {}
+                _ => panic!("Expected the same biases"),
+            }
+        }
+    }
+
+    #[test]
+    fn converts_the_lists_and_the_remapped_keys_of_a_safetensors_file() {
+        let file = write_safetensors(
+            "burn_import_cli_record.safetensors",
+            &[
+                ("blocks.1.weight", "F32", vec![2], f32_bytes(&[3.0, 4.0])),
+                ("blocks.0.weight", "F32", vec![2], f32_bytes(&[1.0, 2.0])),
+                ("head.weight", "F32", vec![1, 2], f32_bytes(&[5.0, 6.0])),
+                ("steps", "I64", vec![1], 7_i64.to_le_bytes().to_vec()),
+            ],
+        );
+        let out_file = std::env::temp_dir().join("burn_import_cli_record");
+        let options = RecordOptions {
+            record_type: RecordType::NamedMpk,
+            half_precision: true,
+            key_remap: vec![(Regex::new(r"^head\.").unwrap(), "output.".to_string())],
+            ..Default::default()
+        };
+
+        convert(&file, out_file.clone(), &options).unwrap();
+
+        let (_, params) = read_params(out_file).unwrap();
+        let mut paths = params.keys().cloned().collect::<Vec<_>>();
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                "blocks.0.weight",
+                "blocks.1.weight",
+                "output.weight",
+                "steps"
+            ]
+        );
+        assert_eq!(params["blocks.1.weight"].dtype, BurnDType::F16);
+        assert_eq!(params["steps"].dtype, BurnDType::I16);
+        params["blocks.1.weight"]
+            .assert_eq(&TensorData::from([3.0, 4.0]).convert::<half::f16>(), true);
+        params["output.weight"]
+            .assert_eq(&TensorData::from([[5.0, 6.0]]).convert::<half::f16>(), true);
+    }
+
+    #[test]
+    fn lists_the_fields_named_by_index() {
+        let tensors = [("layers.1.weight", 1.0), ("layers.0.weight", 0.0)]
+            .into_iter()
+            .map(|(key, value)| {
+                let tensor = Tensor::new(&[value as f32], &Device::Cpu).unwrap();
+                (key.to_string(), tensor)
+            })
+            .collect();
+
+        let record =
+            record_node::<FullPrecisionSettings>(tensors, &RecordOptions::default()).unwrap();
+
+        let RecordNode::Fields(fields) = record else {
+            panic!("Expected the fields of the module");
+        };
+        match &fields["layers"] {
+            RecordNode::Items(items) => assert_eq!(items.len(), 2),
+            node => panic!("Expected a list, got {node:?}"),
+        }
+    }
+
+    #[test]
+    fn rejects_a_key_which_is_both_a_parameter_and_a_module() {
+        let tensors = ["fc", "fc.weight"]
+            .into_iter()
+            .map(|key| {
+                let tensor = Tensor::new(&[0.0_f32], &Device::Cpu).unwrap();
+                (key.to_string(), tensor)
+            })
+            .collect();
+
+        let err =
+            record_node::<FullPrecisionSettings>(tensors, &RecordOptions::default()).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("fc is both a parameter and a module"));
+    }
+
+    #[test]
+    fn rejects_the_top_level_key_of_a_safetensors_file() {
+        let file = write_safetensors("burn_import_cli_top_level_key.safetensors", &[]);
+        let options = RecordOptions {
+            top_level_key: Some("state_dict".to_string()),
+            ..Default::default()
+        };
+
+        let out_file = std::env::temp_dir().join("burn_import_cli_top_level_key");
+        assert!(convert(&file, out_file, &options).is_err());
+    }
+}
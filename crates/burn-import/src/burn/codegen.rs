@@ -1,9 +1,10 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Literal, TokenStream};
 use quote::quote;
 
 use burn::nn::PaddingConfig1d;
 use burn::nn::PaddingConfig2d;
 use burn::nn::PaddingConfig3d;
+use burn::tensor::ScatterReduction;
 
 fn convert_primitive<T: ToString>(primitive: T) -> TokenStream {
     let value = primitive.to_string();
@@ -57,17 +58,19 @@ impl ToTokens for i64 {
     }
 }
 
-/// Prettier output for `f64`
+/// Prettier output for `f64`, the whole numbers keeping a decimal point to stay float literals
 impl ToTokens for f64 {
     fn to_tokens(&self) -> TokenStream {
-        convert_primitive(self)
+        let value = Literal::f64_unsuffixed(*self);
+        quote! { #value }
     }
 }
 
-/// Prettier output for `f32`
+/// Prettier output for `f32`, the whole numbers keeping a decimal point to stay float literals
 impl ToTokens for f32 {
     fn to_tokens(&self) -> TokenStream {
-        convert_primitive(self)
+        let value = Literal::f32_unsuffixed(*self);
+        quote! { #value }
     }
 }
 
@@ -115,3 +118,16 @@ impl ToTokens for PaddingConfig3d {
         }
     }
 }
+
+/// Scatter reduction
+impl ToTokens for ScatterReduction {
+    fn to_tokens(&self) -> TokenStream {
+        match self {
+            Self::None => quote! { ScatterReduction::None },
+            Self::Add => quote! { ScatterReduction::Add },
+            Self::Mul => quote! { ScatterReduction::Mul },
+            Self::Max => quote! { ScatterReduction::Max },
+            Self::Min => quote! { ScatterReduction::Min },
+        }
+    }
+}
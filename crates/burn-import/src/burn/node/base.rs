@@ -1,20 +1,24 @@
 use std::marker::PhantomData;
 
+#[cfg(feature = "onnx-runtime")]
+use super::onnx_runtime::OnnxRuntimeNode;
 use super::{
     argmax::ArgMaxNode, avg_pool1d::AvgPool1dNode, avg_pool2d::AvgPool2dNode,
     batch_norm::BatchNormNode, binary::BinaryNode, clip::ClipNode, concat::ConcatNode,
     constant::ConstantNode, constant_of_shape::ConstantOfShapeNode, conv1d::Conv1dNode,
     conv2d::Conv2dNode, conv3d::Conv3dNode, conv_transpose_1d::ConvTranspose1dNode,
     conv_transpose_2d::ConvTranspose2dNode, conv_transpose_3d::ConvTranspose3dNode,
-    dropout::DropoutNode, expand::ExpandNode, gather::GatherNode,
-    gather_elements::GatherElementsNode, global_avg_pool::GlobalAvgPoolNode,
-    layer_norm::LayerNormNode, linear::LinearNode, mask_where::WhereNode, matmul::MatmulNode,
-    max_pool1d::MaxPool1dNode, max_pool2d::MaxPool2dNode, mean::MeanNode, pad::PadNode,
-    prelu::PReluNode, random_normal::RandomNormalNode, random_normal_like::RandomNormalLikeNode,
-    random_uniform::RandomUniformNode, random_uniform_like::RandomUniformLikeNode,
-    range::RangeNode, reshape::ReshapeNode, resize::ResizeNode, slice::SliceNode,
-    squeeze::SqueezeNode, sum::SumNode, tile::TileNode, trilu::TriluNode, unary::UnaryNode,
-    unsqueeze::UnsqueezeNode,
+    dropout::DropoutNode, einsum::EinsumNode, expand::ExpandNode, gather::GatherNode,
+    gather_elements::GatherElementsNode, gather_nd::GatherNdNode,
+    global_avg_pool::GlobalAvgPoolNode, layer_norm::LayerNormNode, linear::LinearNode,
+    mask_where::WhereNode, matmul::MatmulNode, max_pool1d::MaxPool1dNode,
+    max_pool2d::MaxPool2dNode, mean::MeanNode, non_max_suppression::NonMaxSuppressionNode,
+    pad::PadNode, prelu::PReluNode, random_normal::RandomNormalNode,
+    random_normal_like::RandomNormalLikeNode, random_uniform::RandomUniformNode,
+    random_uniform_like::RandomUniformLikeNode, range::RangeNode, reshape::ReshapeNode,
+    resize::ResizeNode, rnn::GruNode, rnn::LstmNode, scatter_elements::ScatterElementsNode,
+    scatter_nd::ScatterNdNode, slice::SliceNode, squeeze::SqueezeNode, sum::SumNode,
+    tile::TileNode, topk::TopKNode, trilu::TriluNode, unary::UnaryNode, unsqueeze::UnsqueezeNode,
 };
 use crate::burn::{BurnImports, Scope, Type};
 use burn::backend::NdArray;
@@ -100,24 +104,34 @@ pub enum Node<PS: PrecisionSettings> {
     ConvTranspose3d(ConvTranspose3dNode),
     PRelu(PReluNode),
     Dropout(DropoutNode),
+    Einsum(EinsumNode),
     Expand(ExpandNode),
     Gather(GatherNode),
     GatherElements(GatherElementsNode),
+    GatherNd(GatherNdNode),
     GlobalAvgPool(GlobalAvgPoolNode),
+    Gru(GruNode),
     LayerNorm(LayerNormNode),
     Linear(LinearNode),
+    Lstm(LstmNode),
     Matmul(MatmulNode),
     MaxPool1d(MaxPool1dNode),
     MaxPool2d(MaxPool2dNode),
     Mean(MeanNode),
+    NonMaxSuppression(NonMaxSuppressionNode),
+    #[cfg(feature = "onnx-runtime")]
+    OnnxRuntime(OnnxRuntimeNode),
     Pad(PadNode),
     Range(RangeNode),
     Reshape(ReshapeNode),
     Resize(ResizeNode),
+    ScatterElements(ScatterElementsNode),
+    ScatterNd(ScatterNdNode),
     Slice(SliceNode),
     Squeeze(SqueezeNode),
     Sum(SumNode),
     Tile(TileNode),
+    TopK(TopKNode),
     Trilu(TriluNode),
     Unary(UnaryNode),
     Unsqueeze(UnsqueezeNode),
@@ -152,24 +166,34 @@ macro_rules! match_all {
             Node::ConvTranspose3d(node) => $func(node),
             Node::PRelu(node) => $func(node),
             Node::Dropout(node) => $func(node),
+            Node::Einsum(node) => $func(node),
             Node::Expand(node) => $func(node),
             Node::Gather(node) => $func(node),
             Node::GatherElements(node) => $func(node),
+            Node::GatherNd(node) => $func(node),
             Node::GlobalAvgPool(node) => $func(node),
+            Node::Gru(node) => $func(node),
             Node::LayerNorm(node) => $func(node),
             Node::Linear(node) => $func(node),
+            Node::Lstm(node) => $func(node),
             Node::Matmul(node) => $func(node),
             Node::MaxPool1d(node) => $func(node),
             Node::MaxPool2d(node) => $func(node),
             Node::Mean(node) => $func(node),
+            Node::NonMaxSuppression(node) => $func(node),
+            #[cfg(feature = "onnx-runtime")]
+            Node::OnnxRuntime(node) => $func(node),
             Node::Pad(node) => $func(node),
             Node::Range(node) => $func(node),
             Node::Reshape(node) => $func(node),
             Node::Resize(node) => $func(node),
+            Node::ScatterElements(node) => $func(node),
+            Node::ScatterNd(node) => $func(node),
             Node::Slice(node) => $func(node),
             Node::Squeeze(node) => $func(node),
             Node::Sum(node) => $func(node),
             Node::Tile(node) => $func(node),
+            Node::TopK(node) => $func(node),
             Node::Trilu(node) => $func(node),
             Node::Unary(node) => $func(node),
             Node::Unsqueeze(node) => $func(node),
@@ -212,24 +236,34 @@ impl<PS: PrecisionSettings> Node<PS> {
             Node::ConvTranspose3d(_) => "conv_transpose3d",
             Node::PRelu(_) => "prelu",
             Node::Dropout(_) => "dropout",
+            Node::Einsum(_) => "einsum",
             Node::Expand(_) => "expand",
             Node::Gather(_) => "gather",
             Node::GatherElements(_) => "gather_elements",
+            Node::GatherNd(_) => "gather_nd",
             Node::GlobalAvgPool(_) => "global_avg_pool",
+            Node::Gru(_) => "gru",
             Node::LayerNorm(_) => "layer_norm",
             Node::Linear(_) => "linear",
+            Node::Lstm(_) => "lstm",
             Node::Matmul(_) => "matmul",
             Node::MaxPool1d(_) => "max_pool1d",
             Node::MaxPool2d(_) => "max_pool2d",
             Node::Mean(_) => "mean",
+            Node::NonMaxSuppression(_) => "non_max_suppression",
+            #[cfg(feature = "onnx-runtime")]
+            Node::OnnxRuntime(_) => "onnx_runtime",
             Node::Pad(_) => "pad",
             Node::Range(_) => "range",
             Node::Reshape(_) => "reshape",
             Node::Resize(_) => "resize",
+            Node::ScatterElements(_) => "scatter_elements",
+            Node::ScatterNd(_) => "scatter_nd",
             Node::Slice(_) => "slice",
             Node::Squeeze(_) => "squeeze",
             Node::Sum(_) => "add",
             Node::Tile(_) => "tile",
+            Node::TopK(_) => "topk",
             Node::Trilu(_) => "trilu",
             Node::Unary(unary) => unary.kind.as_str(),
             Node::Unsqueeze(_) => "unsqueeze",
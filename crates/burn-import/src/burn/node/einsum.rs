@@ -0,0 +1,455 @@
+use super::{Node, NodeCodegen};
+use crate::burn::{Scope, TensorKind, TensorType, ToTokens, Type};
+use burn::config::Config;
+use burn::record::PrecisionSettings;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// The labels of the operands and of the output of an einsum equation, with the ellipsis
+/// expanded into labels and the implicit output made explicit.
+#[derive(Config, Debug)]
+pub struct EinsumConfig {
+    pub inputs: Vec<String>,
+    pub output: String,
+}
+
+/// Einsum node, lowered to a sequence of sums, permutations and batched matrix products
+/// contracting the operands two by two.
+#[derive(Debug, Clone)]
+pub struct EinsumNode {
+    pub inputs: Vec<TensorType>,
+    pub output: TensorType,
+    pub config: EinsumConfig,
+}
+
+impl EinsumNode {
+    pub fn new(inputs: Vec<TensorType>, output: TensorType, config: EinsumConfig) -> Self {
+        if inputs.iter().any(|input| input.kind != TensorKind::Float) {
+            panic!("Einsum is only implemented for float tensors");
+        }
+        if inputs.len() != config.inputs.len() {
+            panic!(
+                "Einsum: the equation has {} operands but the node has {} inputs",
+                config.inputs.len(),
+                inputs.len()
+            );
+        }
+
+        Self {
+            inputs,
+            output,
+            config,
+        }
+    }
+}
+
+/// An operand of the einsum, with the label of each of its dimensions.
+#[derive(Clone)]
+struct Operand {
+    tokens: TokenStream,
+    labels: Vec<char>,
+}
+
+/// The dimensions of a tensor with the given labels, as a list of indexing expressions.
+fn dims_of(dims: &TokenStream, labels: &[char], selected: &[char]) -> Vec<TokenStream> {
+    selected
+        .iter()
+        .map(|label| {
+            let index = labels.iter().position(|l| l == label).unwrap().to_tokens();
+            quote! { #dims[#index] }
+        })
+        .collect()
+}
+
+/// The product of the dimensions, one when there are none.
+fn product(dims: &[TokenStream]) -> TokenStream {
+    match dims.split_first() {
+        Some((first, rest)) => quote! { #first #(* #rest)* },
+        None => quote! { 1 },
+    }
+}
+
+/// Sums the dimensions with the given labels, keeping them with a size of one.
+fn sum_dims(tokens: TokenStream, labels: &[char], summed: &[char]) -> TokenStream {
+    summed.iter().fold(tokens, |tokens, label| {
+        let dim = labels.iter().position(|l| l == label).unwrap().to_tokens();
+        quote! { #tokens.sum_dim(#dim) }
+    })
+}
+
+/// The permutation moving the dimensions with the given labels in the given order.
+fn permute(tokens: TokenStream, labels: &[char], order: &[char]) -> TokenStream {
+    let axes = order
+        .iter()
+        .map(|label| labels.iter().position(|l| l == label).unwrap())
+        .collect::<Vec<_>>();
+
+    match axes.iter().enumerate().all(|(i, axis)| i == *axis) {
+        true => tokens,
+        false => {
+            let axes = axes.to_tokens();
+            quote! { #tokens.permute(#axes) }
+        }
+    }
+}
+
+/// The labels of an operand depending on whether they are shared with the other operand and
+/// kept for the output or the next operands.
+fn select(labels: &[char], other: &[char], keep: &[char], shared: bool, kept: bool) -> Vec<char> {
+    labels
+        .iter()
+        .filter(|label| other.contains(label) == shared && keep.contains(label) == kept)
+        .copied()
+        .collect()
+}
+
+/// Contracts two operands with a batched matrix product, keeping the labels needed by the
+/// output or by the next operands.
+fn contract(lhs: Operand, rhs: Operand, keep: &[char]) -> Operand {
+    let batch = select(&lhs.labels, &rhs.labels, keep, true, true);
+    let contracted = select(&lhs.labels, &rhs.labels, keep, true, false);
+    let left = select(&lhs.labels, &rhs.labels, keep, false, true);
+    let right = select(&rhs.labels, &lhs.labels, keep, false, true);
+    let lhs_summed = select(&lhs.labels, &rhs.labels, keep, false, false);
+    let rhs_summed = select(&rhs.labels, &lhs.labels, keep, false, false);
+
+    let labels = [batch.clone(), left.clone(), right.clone()].concat();
+    if labels.is_empty() {
+        panic!("Einsum: the intermediate scalar products are not supported");
+    }
+
+    // The summed dimensions have a size of one, so they are merged with the contracted ones.
+    let lhs_order = [
+        batch.clone(),
+        left.clone(),
+        contracted.clone(),
+        lhs_summed.clone(),
+    ]
+    .concat();
+    let rhs_order = [
+        batch.clone(),
+        contracted.clone(),
+        rhs_summed.clone(),
+        right.clone(),
+    ]
+    .concat();
+
+    let lhs_dims = quote! { lhs_dims };
+    let rhs_dims = quote! { rhs_dims };
+    // The batch dimensions are kept apart since they are broadcast by the matrix product when
+    // one of them has a size of one.
+    let lhs_shape = [
+        dims_of(&lhs_dims, &lhs.labels, &batch),
+        vec![
+            product(&dims_of(&lhs_dims, &lhs.labels, &left)),
+            product(&dims_of(&lhs_dims, &lhs.labels, &contracted)),
+        ],
+    ]
+    .concat();
+    let rhs_shape = [
+        dims_of(&rhs_dims, &rhs.labels, &batch),
+        vec![
+            product(&dims_of(&rhs_dims, &rhs.labels, &contracted)),
+            product(&dims_of(&rhs_dims, &rhs.labels, &right)),
+        ],
+    ]
+    .concat();
+    let batch_shape = dims_of(&lhs_dims, &lhs.labels, &batch)
+        .into_iter()
+        .zip(dims_of(&rhs_dims, &rhs.labels, &batch))
+        .map(|(lhs, rhs)| quote! { #lhs.max(#rhs) })
+        .collect::<Vec<_>>();
+    let output_shape = [
+        batch_shape,
+        dims_of(&lhs_dims, &lhs.labels, &left),
+        dims_of(&rhs_dims, &rhs.labels, &right),
+    ]
+    .concat();
+
+    // The reshapes aren't needed for the (batched) matrix products of two matrices.
+    let is_matrix = [&left, &contracted, &right]
+        .iter()
+        .all(|group| group.len() == 1)
+        && lhs_summed.is_empty()
+        && rhs_summed.is_empty();
+
+    let tokens = match is_matrix {
+        true => {
+            let lhs = permute(lhs.tokens, &lhs.labels, &lhs_order);
+            let rhs = permute(rhs.tokens, &rhs.labels, &rhs_order);
+
+            quote! { #lhs.matmul(#rhs) }
+        }
+        false => {
+            let lhs_tokens = sum_dims(lhs.tokens, &lhs.labels, &lhs_summed);
+            let rhs_tokens = sum_dims(rhs.tokens, &rhs.labels, &rhs_summed);
+            let lhs_permuted = permute(quote! { lhs }, &lhs.labels, &lhs_order);
+            let rhs_permuted = permute(quote! { rhs }, &rhs.labels, &rhs_order);
+
+            quote! {
+                {
+                    let lhs = #lhs_tokens;
+                    let rhs = #rhs_tokens;
+                    let lhs_dims = lhs.dims();
+                    let rhs_dims = rhs.dims();
+                    let lhs = #lhs_permuted.reshape([#(#lhs_shape),*]);
+                    let rhs = #rhs_permuted.reshape([#(#rhs_shape),*]);
+
+                    lhs.matmul(rhs).reshape([#(#output_shape),*])
+                }
+            }
+        }
+    };
+
+    Operand { tokens, labels }
+}
+
+impl<PS: PrecisionSettings> NodeCodegen<PS> for EinsumNode {
+    fn output_types(&self) -> Vec<Type> {
+        vec![Type::Tensor(self.output.clone())]
+    }
+
+    fn input_types(&self) -> Vec<Type> {
+        self.inputs
+            .iter()
+            .map(|input| Type::Tensor(input.clone()))
+            .collect()
+    }
+
+    fn forward(&self, scope: &mut Scope, node_position: usize) -> TokenStream {
+        let output = &self.output.name;
+        let output_labels = self.config.output.chars().collect::<Vec<_>>();
+
+        let mut operands = self
+            .inputs
+            .iter()
+            .zip(self.config.inputs.iter())
+            .map(|(input, labels)| Operand {
+                tokens: scope.tensor_use_owned(input, node_position),
+                labels: labels.chars().collect(),
+            })
+            .collect::<Vec<_>>();
+
+        // The operands are contracted from left to right.
+        let mut result = operands.remove(0);
+        for (index, operand) in operands.iter().enumerate() {
+            let keep = operands[index + 1..]
+                .iter()
+                .flat_map(|operand| operand.labels.iter())
+                .chain(output_labels.iter())
+                .copied()
+                .collect::<Vec<_>>();
+            result = contract(result, operand.clone(), &keep);
+        }
+
+        // The labels which aren't in the output are summed and moved last before being removed.
+        let summed = result
+            .labels
+            .iter()
+            .filter(|label| !output_labels.contains(label))
+            .copied()
+            .collect::<Vec<_>>();
+        let tokens = sum_dims(result.tokens, &result.labels, &summed);
+        let order = [output_labels.clone(), summed.clone()].concat();
+
+        let tokens = match summed.is_empty() {
+            true => permute(tokens, &result.labels, &order),
+            false => {
+                let permuted = permute(quote! { result }, &result.labels, &order);
+                let shape = dims_of(&quote! { dims }, &result.labels, &output_labels);
+                quote! {
+                    {
+                        let result = #tokens;
+                        let dims = result.dims();
+
+                        #permuted.reshape([#(#shape),*])
+                    }
+                }
+            }
+        };
+
+        quote! {
+            let #output = #tokens;
+        }
+    }
+
+    fn into_node(self) -> Node<PS> {
+        Node::Einsum(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::burn::{graph::BurnGraph, node::test::assert_tokens, TensorType};
+    use burn::record::FullPrecisionSettings;
+
+    #[test]
+    fn test_codegen_batched_matmul() {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+
+        graph.register(EinsumNode::new(
+            vec![
+                TensorType::new_float("query", 4),
+                TensorType::new_float("key", 4),
+            ],
+            TensorType::new_float("scores", 4),
+            EinsumConfig::new(
+                vec!["bhid".to_string(), "bhjd".to_string()],
+                "bhij".to_string(),
+            ),
+        ));
+
+        graph.register_input_output(
+            vec!["query".to_string(), "key".to_string()],
+            vec!["scores".to_string()],
+        );
+
+        let expected = quote! {
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+
+            #[derive(Module, Debug)]
+            pub struct Model<B: Backend> {
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model<B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    Self {
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(&self, query: Tensor<B, 4>, key: Tensor<B, 4>) -> Tensor<B, 4> {
+                    let scores = query.matmul(key.permute([0, 1, 3, 2]));
+
+                    scores
+                }
+            }
+        };
+
+        assert_tokens(graph.codegen(), expected);
+    }
+
+    #[test]
+    fn test_codegen_broadcast_contraction() {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+
+        graph.register(EinsumNode::new(
+            vec![
+                TensorType::new_float("lhs", 4),
+                TensorType::new_float("rhs", 3),
+            ],
+            TensorType::new_float("output", 4),
+            EinsumConfig::new(
+                vec!["bijk".to_string(), "bkl".to_string()],
+                "bijl".to_string(),
+            ),
+        ));
+
+        graph.register_input_output(
+            vec!["lhs".to_string(), "rhs".to_string()],
+            vec!["output".to_string()],
+        );
+
+        let expected = quote! {
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+
+            #[derive(Module, Debug)]
+            pub struct Model<B: Backend> {
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model<B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    Self {
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(&self, lhs: Tensor<B, 4>, rhs: Tensor<B, 3>) -> Tensor<B, 4> {
+                    let output = {
+                        let lhs = lhs;
+                        let rhs = rhs;
+                        let lhs_dims = lhs.dims();
+                        let rhs_dims = rhs.dims();
+                        let lhs = lhs.reshape([lhs_dims[0], lhs_dims[1] * lhs_dims[2], lhs_dims[3]]);
+                        let rhs = rhs.reshape([rhs_dims[0], rhs_dims[1], rhs_dims[2]]);
+
+                        lhs.matmul(rhs).reshape([
+                            lhs_dims[0].max(rhs_dims[0]),
+                            lhs_dims[1],
+                            lhs_dims[2],
+                            rhs_dims[2]
+                        ])
+                    };
+
+                    output
+                }
+            }
+        };
+
+        assert_tokens(graph.codegen(), expected);
+    }
+
+    #[test]
+    fn test_codegen_sum_and_transpose() {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+
+        graph.register(EinsumNode::new(
+            vec![TensorType::new_float("input", 3)],
+            TensorType::new_float("output", 2),
+            EinsumConfig::new(vec!["bij".to_string()], "jb".to_string()),
+        ));
+
+        graph.register_input_output(vec!["input".to_string()], vec!["output".to_string()]);
+
+        let expected = quote! {
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+
+            #[derive(Module, Debug)]
+            pub struct Model<B: Backend> {
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model<B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    Self {
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(&self, input: Tensor<B, 3>) -> Tensor<B, 2> {
+                    let output = {
+                        let result = input.sum_dim(1);
+                        let dims = result.dims();
+
+                        result.permute([2, 0, 1]).reshape([dims[2], dims[0]])
+                    };
+
+                    output
+                }
+            }
+        };
+
+        assert_tokens(graph.codegen(), expected);
+    }
+}
@@ -16,9 +16,11 @@ pub(crate) mod conv_transpose_1d;
 pub(crate) mod conv_transpose_2d;
 pub(crate) mod conv_transpose_3d;
 pub(crate) mod dropout;
+pub(crate) mod einsum;
 pub(crate) mod expand;
 pub(crate) mod gather;
 pub(crate) mod gather_elements;
+pub(crate) mod gather_nd;
 pub(crate) mod global_avg_pool;
 pub(crate) mod layer_norm;
 pub(crate) mod linear;
@@ -27,6 +29,9 @@ pub(crate) mod matmul;
 pub(crate) mod max_pool1d;
 pub(crate) mod max_pool2d;
 pub(crate) mod mean;
+pub(crate) mod non_max_suppression;
+#[cfg(feature = "onnx-runtime")]
+pub(crate) mod onnx_runtime;
 pub(crate) mod pad;
 pub(crate) mod prelu;
 pub(crate) mod random_normal;
@@ -36,10 +41,14 @@ pub(crate) mod random_uniform_like;
 pub(crate) mod range;
 pub(crate) mod reshape;
 pub(crate) mod resize;
+pub(crate) mod rnn;
+pub(crate) mod scatter_elements;
+pub(crate) mod scatter_nd;
 pub(crate) mod slice;
 pub(crate) mod squeeze;
 pub(crate) mod sum;
 pub(crate) mod tile;
+pub(crate) mod topk;
 pub(crate) mod trilu;
 pub(crate) mod unary;
 pub(crate) mod unsqueeze;
@@ -0,0 +1,142 @@
+use super::{Node, NodeCodegen};
+use crate::burn::{BurnImports, Scope, TensorType, ToTokens, Type};
+use burn::config::Config;
+use burn::record::PrecisionSettings;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+#[derive(Config, Debug)]
+pub struct NonMaxSuppressionConfig {
+    pub max_output_boxes_per_class: usize,
+    pub iou_threshold: f32,
+    pub score_threshold: Option<f32>,
+    pub center_point_box: bool,
+}
+
+#[derive(Debug, Clone, new)]
+pub struct NonMaxSuppressionNode {
+    pub boxes: TensorType,
+    pub scores: TensorType,
+    pub output: TensorType,
+    pub config: NonMaxSuppressionConfig,
+}
+
+impl<PS: PrecisionSettings> NodeCodegen<PS> for NonMaxSuppressionNode {
+    fn output_types(&self) -> Vec<Type> {
+        vec![Type::Tensor(self.output.clone())]
+    }
+
+    fn input_types(&self) -> Vec<Type> {
+        vec![
+            Type::Tensor(self.boxes.clone()),
+            Type::Tensor(self.scores.clone()),
+        ]
+    }
+
+    fn forward(&self, scope: &mut Scope, node_position: usize) -> TokenStream {
+        let boxes = scope.tensor_use_owned(&self.boxes, node_position);
+        let scores = scope.tensor_use_owned(&self.scores, node_position);
+        let output = &self.output.name;
+
+        let max_output_boxes_per_class = self.config.max_output_boxes_per_class.to_tokens();
+        let iou_threshold = self.config.iou_threshold.to_tokens();
+        let score_threshold = match self.config.score_threshold {
+            Some(threshold) => {
+                let threshold = threshold.to_tokens();
+                quote! { Some(#threshold) }
+            }
+            None => quote! { None },
+        };
+        let center_point_box = self.config.center_point_box;
+
+        // The number of selected boxes depends on the data, so it's only known at runtime.
+        quote! {
+            let #output = non_max_suppression(
+                #boxes,
+                #scores,
+                NmsOptions::new(
+                    #max_output_boxes_per_class,
+                    #iou_threshold,
+                    #score_threshold,
+                    #center_point_box,
+                ),
+            );
+        }
+    }
+
+    fn register_imports(&self, imports: &mut BurnImports) {
+        imports.register("burn::tensor::non_max_suppression");
+        imports.register("burn::tensor::NmsOptions");
+    }
+
+    fn into_node(self) -> Node<PS> {
+        Node::NonMaxSuppression(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use burn::record::FullPrecisionSettings;
+
+    use super::*;
+    use crate::burn::{graph::BurnGraph, node::test::assert_tokens, TensorType};
+
+    #[test]
+    fn test_codegen_non_max_suppression() {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+
+        graph.register(NonMaxSuppressionNode::new(
+            TensorType::new_float("boxes", 3),
+            TensorType::new_float("scores", 3),
+            TensorType::new_int("selected", 2),
+            NonMaxSuppressionConfig::new(10, 0.5, false).with_score_threshold(Some(0.25)),
+        ));
+
+        graph.register_input_output(
+            vec!["boxes".to_string(), "scores".to_string()],
+            vec!["selected".to_string()],
+        );
+
+        let expected = quote! {
+            use burn::tensor::Int;
+            use burn::tensor::NmsOptions;
+            use burn::tensor::non_max_suppression;
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+
+            #[derive(Module, Debug)]
+            pub struct Model<B: Backend> {
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model<B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    Self {
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(
+                    &self,
+                    boxes: Tensor<B, 3>,
+                    scores: Tensor<B, 3>,
+                ) -> Tensor<B, 2, Int> {
+                    let selected = non_max_suppression(
+                        boxes,
+                        scores,
+                        NmsOptions::new(10, 0.5, Some(0.25), false),
+                    );
+
+                    selected
+                }
+            }
+        };
+
+        assert_tokens(graph.codegen(), expected);
+    }
+}
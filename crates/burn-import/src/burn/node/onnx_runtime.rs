@@ -0,0 +1,197 @@
+use std::path::PathBuf;
+
+use super::{Node, NodeCodegen};
+use crate::burn::{BurnImports, Scope, TensorKind, TensorType, Type};
+use burn::record::PrecisionSettings;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// A node which is not supported natively, run with an ONNX Runtime session.
+///
+/// The node is exported as a standalone ONNX model, embedded in the generated code, whose session
+/// is created on the first forward pass. The generated code depends on the `ort` crate.
+#[derive(Debug, Clone, new)]
+pub struct OnnxRuntimeNode {
+    /// The ONNX operator of the node, e.g. `Det`.
+    pub op_type: String,
+    /// The inputs of the node without value, in the order of the inputs of the model.
+    pub inputs: Vec<TensorType>,
+    pub outputs: Vec<TensorType>,
+    /// The path of the exported model.
+    pub model: PathBuf,
+}
+
+impl<PS: PrecisionSettings> NodeCodegen<PS> for OnnxRuntimeNode {
+    fn output_types(&self) -> Vec<Type> {
+        self.outputs
+            .iter()
+            .map(|output| Type::Tensor(output.clone()))
+            .collect()
+    }
+
+    fn input_types(&self) -> Vec<Type> {
+        self.inputs
+            .iter()
+            .map(|input| Type::Tensor(input.clone()))
+            .collect()
+    }
+
+    fn forward(&self, scope: &mut Scope, node_position: usize) -> TokenStream {
+        let model = self.model.to_str().unwrap();
+        let error = format!("Should run the {} node with ONNX Runtime", self.op_type);
+
+        let inputs = self.inputs.iter().map(|input| {
+            let tensor = scope.tensor_use_owned(input, node_position);
+            let (elem, convert) = match input.kind {
+                TensorKind::Float => (quote! { f32 }, quote! { .convert::<f32>() }),
+                TensorKind::Int => (quote! { i64 }, quote! { .convert::<i64>() }),
+                TensorKind::Bool => (quote! { bool }, quote! {}),
+            };
+
+            quote! {
+                {
+                    let data = #tensor.into_data()#convert;
+                    let shape = data.shape.iter().map(|dim| *dim as i64).collect::<Vec<_>>();
+                    ort::value::Tensor::from_array((shape, data.to_vec::<#elem>().unwrap()))
+                        .expect(#error)
+                }
+            }
+        });
+
+        let outputs = self.outputs.iter().enumerate().map(|(i, output)| {
+            let name = &output.name;
+            let ty = output.ty();
+            let elem = match output.kind {
+                TensorKind::Float => quote! { f32 },
+                TensorKind::Int => quote! { i64 },
+                TensorKind::Bool => quote! { bool },
+            };
+
+            quote! {
+                let #name: #ty = {
+                    let (shape, values) = outputs[#i]
+                        .try_extract_raw_tensor::<#elem>()
+                        .expect(#error);
+                    let shape = shape.iter().map(|dim| *dim as usize).collect::<Vec<_>>();
+                    Tensor::from_data(TensorData::new(values.to_vec(), shape), &*self.device)
+                };
+            }
+        });
+
+        let names = self.outputs.iter().map(|output| &output.name);
+        let names = match self.outputs.len() {
+            1 => quote! { #(#names)* },
+            _ => quote! { (#(#names),*) },
+        };
+
+        // The session is shared by the forward passes of the model
+        quote! {
+            let #names = {
+                static SESSION: std::sync::OnceLock<ort::session::Session> =
+                    std::sync::OnceLock::new();
+                let session = SESSION.get_or_init(|| {
+                    ort::session::Session::builder()
+                        .and_then(|builder| builder.commit_from_memory(include_bytes!(#model)))
+                        .expect("Should create the ONNX Runtime session")
+                });
+                let outputs = session
+                    .run(ort::inputs![#(#inputs),*].expect(#error))
+                    .expect(#error);
+
+                #(#outputs)*
+
+                #names
+            };
+        }
+    }
+
+    fn register_imports(&self, imports: &mut BurnImports) {
+        imports.register("burn::tensor::TensorData");
+    }
+
+    fn into_node(self) -> Node<PS> {
+        Node::OnnxRuntime(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use burn::record::FullPrecisionSettings;
+
+    use super::*;
+    use crate::burn::{graph::BurnGraph, node::test::assert_tokens, TensorType};
+
+    #[test]
+    fn test_codegen_onnx_runtime() {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+
+        graph.register(OnnxRuntimeNode::new(
+            "Det".to_string(),
+            vec![TensorType::new_float("tensor1", 3)],
+            vec![TensorType::new_float("tensor2", 1)],
+            PathBuf::from("/tmp/model.det1.onnx"),
+        ));
+
+        graph.register_input_output(vec!["tensor1".to_string()], vec!["tensor2".to_string()]);
+
+        let expected = quote! {
+            use burn::tensor::TensorData;
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+
+            #[derive(Module, Debug)]
+            pub struct Model<B: Backend> {
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model<B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    Self {
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(&self, tensor1: Tensor<B, 3>) -> Tensor<B, 1> {
+                    let tensor2 = {
+                        static SESSION: std::sync::OnceLock<ort::session::Session> = std::sync::OnceLock::new();
+                        let session = SESSION.get_or_init(|| {
+                            ort::session::Session::builder()
+                                .and_then(|builder| builder.commit_from_memory(include_bytes!("/tmp/model.det1.onnx")))
+                                .expect("Should create the ONNX Runtime session")
+                        });
+                        let outputs = session
+                            .run(
+                                ort::inputs![{
+                                    let data = tensor1.into_data().convert::<f32>();
+                                    let shape = data.shape.iter().map(|dim| *dim as i64).collect::<Vec<_>>();
+                                    ort::value::Tensor::from_array((shape, data.to_vec::<f32>().unwrap()))
+                                        .expect("Should run the Det node with ONNX Runtime")
+                                }]
+                                .expect("Should run the Det node with ONNX Runtime"),
+                            )
+                            .expect("Should run the Det node with ONNX Runtime");
+
+                        let tensor2: Tensor<B, 1> = {
+                            let (shape, values) = outputs[0usize]
+                                .try_extract_raw_tensor::<f32>()
+                                .expect("Should run the Det node with ONNX Runtime");
+                            let shape = shape.iter().map(|dim| *dim as usize).collect::<Vec<_>>();
+                            Tensor::from_data(TensorData::new(values.to_vec(), shape), &*self.device)
+                        };
+
+                        tensor2
+                    };
+
+                    tensor2
+                }
+            }
+        };
+
+        assert_tokens(graph.codegen(), expected);
+    }
+}
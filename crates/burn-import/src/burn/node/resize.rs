@@ -4,6 +4,14 @@ use burn::record::PrecisionSettings;
 use proc_macro2::TokenStream;
 use quote::quote;
 
+/// Sampling of the input coordinates, with the names of the ONNX `Resize` attributes.
+#[derive(Debug, Clone, new)]
+pub struct ResizeSampling {
+    pub coordinate_transformation_mode: String,
+    pub nearest_mode: String,
+    pub cubic_coeff_a: f32,
+}
+
 #[derive(Debug, Clone)]
 pub struct ResizeNode {
     pub field: OtherType,
@@ -12,6 +20,7 @@ pub struct ResizeNode {
     mode: String,
     scales: Vec<f32>,
     sizes: Vec<usize>,
+    sampling: ResizeSampling,
 }
 
 impl ResizeNode {
@@ -22,6 +31,7 @@ impl ResizeNode {
         mode: String,
         scales: Vec<f32>,
         sizes: Vec<usize>,
+        sampling: ResizeSampling,
     ) -> Self {
         let ty = if input.dim == 3 {
             quote! {
@@ -42,7 +52,43 @@ impl ResizeNode {
             mode,
             scales,
             sizes,
+            sampling,
+        }
+    }
+
+    /// The builder calls of the sampling, only the options used by the mode being set.
+    fn sampling_tokens(&self) -> TokenStream {
+        let coordinate_transformation_mode =
+            match self.sampling.coordinate_transformation_mode.as_str() {
+                "half_pixel" => quote! { CoordinateTransformationMode::HalfPixel },
+                "pytorch_half_pixel" => quote! { CoordinateTransformationMode::PytorchHalfPixel },
+                "align_corners" => quote! { CoordinateTransformationMode::AlignCorners },
+                "asymmetric" => quote! { CoordinateTransformationMode::Asymmetric },
+                mode => panic!("Unsupported coordinate transformation mode {mode} for resize node"),
+            };
+        let mut tokens = quote! {
+            .with_coordinate_transformation_mode(Some(#coordinate_transformation_mode))
+        };
+
+        match self.mode.as_str() {
+            "nearest" => {
+                let nearest_mode = match self.sampling.nearest_mode.as_str() {
+                    "round_prefer_floor" => quote! { NearestMode::RoundPreferFloor },
+                    "round_prefer_ceil" => quote! { NearestMode::RoundPreferCeil },
+                    "floor" => quote! { NearestMode::Floor },
+                    "ceil" => quote! { NearestMode::Ceil },
+                    mode => panic!("Unsupported nearest mode {mode} for resize node"),
+                };
+                tokens.extend(quote! { .with_nearest_mode(#nearest_mode) });
+            }
+            "cubic" => {
+                let cubic_coeff_a = self.sampling.cubic_coeff_a.to_tokens();
+                tokens.extend(quote! { .with_cubic_coeff_a(#cubic_coeff_a) });
+            }
+            _ => {}
         }
+
+        tokens
     }
 }
 
@@ -68,6 +114,7 @@ impl<PS: PrecisionSettings> NodeCodegen<PS> for ResizeNode {
             "cubic" => quote! { InterpolateMode::Cubic },
             _ => panic!("Unsupported mode for resize node"),
         };
+        let sampling = self.sampling_tokens();
 
         let tokens = if self.input.dim == 3 {
             let size = if let Some(size) = self.sizes.first() {
@@ -89,6 +136,7 @@ impl<PS: PrecisionSettings> NodeCodegen<PS> for ResizeNode {
                     .with_output_size(#size)
                     .with_scale_factor(#scale_factor)
                     .with_mode(#mode)
+                    #sampling
                     .init();
             }
         } else if self.input.dim == 4 {
@@ -113,6 +161,7 @@ impl<PS: PrecisionSettings> NodeCodegen<PS> for ResizeNode {
                     .with_output_size(#size)
                     .with_scale_factor(#scale_factor)
                     .with_mode(#mode)
+                    #sampling
                     .init();
             }
         } else {
@@ -124,6 +173,10 @@ impl<PS: PrecisionSettings> NodeCodegen<PS> for ResizeNode {
 
     fn register_imports(&self, imports: &mut crate::burn::BurnImports) {
         imports.register("burn::nn::interpolate::InterpolateMode");
+        imports.register("burn::nn::interpolate::CoordinateTransformationMode");
+        if self.mode == "nearest" {
+            imports.register("burn::nn::interpolate::NearestMode");
+        }
         if self.input.dim == 3 {
             imports.register("burn::nn::interpolate::Interpolate1dConfig");
             imports.register("burn::nn::interpolate::Interpolate1d");
@@ -176,14 +229,17 @@ mod tests {
             "nearest".to_string(),
             vec![0.5, 0.5],
             vec![],
+            ResizeSampling::new("asymmetric".to_string(), "floor".to_string(), -0.75),
         ));
 
         graph.register_input_output(vec!["tensor1".to_string()], vec!["tensor2".to_string()]);
 
         let expected = quote! {
+            use burn::nn::interpolate::CoordinateTransformationMode;
             use burn::nn::interpolate::Interpolate2d;
             use burn::nn::interpolate::Interpolate2dConfig;
             use burn::nn::interpolate::InterpolateMode;
+            use burn::nn::interpolate::NearestMode;
             use burn::{
                 module::Module,
                 tensor::{backend::Backend, Tensor},
@@ -201,6 +257,10 @@ mod tests {
                         .with_output_size(None)
                         .with_scale_factor(Some([0.5, 0.5]))
                         .with_mode(InterpolateMode::Nearest)
+                        .with_coordinate_transformation_mode(Some(
+                            CoordinateTransformationMode::Asymmetric
+                        ))
+                        .with_nearest_mode(NearestMode::Floor)
                         .init();
                     Self {
                         resize,
@@ -230,11 +290,17 @@ mod tests {
             "cubic".to_string(),
             vec![],
             vec![20],
+            ResizeSampling::new(
+                "half_pixel".to_string(),
+                "round_prefer_floor".to_string(),
+                -0.5,
+            ),
         ));
 
         graph.register_input_output(vec!["tensor1".to_string()], vec!["tensor2".to_string()]);
 
         let expected = quote! {
+            use burn::nn::interpolate::CoordinateTransformationMode;
             use burn::nn::interpolate::Interpolate1d;
             use burn::nn::interpolate::Interpolate1dConfig;
             use burn::nn::interpolate::InterpolateMode;
@@ -255,6 +321,10 @@ mod tests {
                         .with_output_size(Some(20))
                         .with_scale_factor(None)
                         .with_mode(InterpolateMode::Cubic)
+                        .with_coordinate_transformation_mode(Some(
+                            CoordinateTransformationMode::HalfPixel
+                        ))
+                        .with_cubic_coeff_a(-0.5)
                         .init();
                     Self {
                         resize,
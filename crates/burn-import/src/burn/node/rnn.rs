@@ -0,0 +1,675 @@
+use super::{Node, NodeCodegen, SerializationBackend};
+use crate::burn::{BurnImports, OtherType, Scope, TensorType, ToTokens, Type};
+use burn::{
+    config::Config,
+    module::{ConstantRecord, Param, ParamId},
+    nn::{gru::GruRecord, BiLstmRecord, GateControllerRecord, LinearRecord, LstmRecord},
+    record::{PrecisionSettings, Record},
+    tensor::{Tensor, TensorData},
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use serde::Serialize;
+
+/// The direction of a recurrent node.
+#[derive(Config, Debug, PartialEq)]
+pub enum RnnDirection {
+    Forward,
+    Reverse,
+    Bidirectional,
+}
+
+#[derive(Config, Debug)]
+pub struct RnnConfig {
+    pub d_input: usize,
+    pub d_hidden: usize,
+    pub bias: bool,
+    pub direction: RnnDirection,
+    /// If the tensors have the batch as their first dimension, the ONNX `layout` 1.
+    pub batch_first: bool,
+    /// If the reset gate of a GRU is applied after the hidden transformation, the ONNX
+    /// `linear_before_reset`.
+    #[config(default = false)]
+    pub reset_after: bool,
+}
+
+/// The weights of an ONNX recurrent node, with the gates of each direction concatenated.
+#[derive(Debug, Clone)]
+pub struct RnnWeights {
+    /// The input weights `W`, `[num_directions, num_gates * d_hidden, d_input]`.
+    pub input: TensorData,
+    /// The hidden weights `R`, `[num_directions, num_gates * d_hidden, d_hidden]`.
+    pub hidden: TensorData,
+    /// The input and hidden biases `B`, `[num_directions, 2 * num_gates * d_hidden]`.
+    pub bias: Option<TensorData>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LstmNode {
+    pub field: OtherType,
+    pub input: TensorType,
+    pub initial_hidden: Option<TensorType>,
+    pub initial_cell: Option<TensorType>,
+    pub output: Option<TensorType>,
+    pub output_hidden: Option<TensorType>,
+    pub output_cell: Option<TensorType>,
+    pub weights: RnnWeights,
+    pub config: RnnConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct GruNode {
+    pub field: OtherType,
+    pub input: TensorType,
+    pub initial_hidden: Option<TensorType>,
+    pub output: Option<TensorType>,
+    pub output_hidden: Option<TensorType>,
+    pub weights: RnnWeights,
+    pub config: RnnConfig,
+}
+
+impl LstmNode {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new<S: AsRef<str>>(
+        name: S,
+        input: TensorType,
+        initial_hidden: Option<TensorType>,
+        initial_cell: Option<TensorType>,
+        output: Option<TensorType>,
+        output_hidden: Option<TensorType>,
+        output_cell: Option<TensorType>,
+        weights: RnnWeights,
+        config: RnnConfig,
+    ) -> Self {
+        let ty = match config.direction {
+            RnnDirection::Bidirectional => quote! { BiLstm<B> },
+            _ => quote! { Lstm<B> },
+        };
+
+        Self {
+            field: OtherType::new(name, ty),
+            input,
+            initial_hidden,
+            initial_cell,
+            output,
+            output_hidden,
+            output_cell,
+            weights,
+            config,
+        }
+    }
+}
+
+impl GruNode {
+    pub fn new<S: AsRef<str>>(
+        name: S,
+        input: TensorType,
+        initial_hidden: Option<TensorType>,
+        output: Option<TensorType>,
+        output_hidden: Option<TensorType>,
+        weights: RnnWeights,
+        config: RnnConfig,
+    ) -> Self {
+        Self {
+            field: OtherType::new(name, quote! { Gru<B> }),
+            input,
+            initial_hidden,
+            output,
+            output_hidden,
+            weights,
+            config,
+        }
+    }
+}
+
+impl RnnWeights {
+    /// The records of the gates of a direction, in the ONNX order of the gates.
+    fn gate_records<PS: PrecisionSettings, const G: usize>(
+        &self,
+        direction: usize,
+        d_hidden: usize,
+    ) -> [GateControllerRecord<SerializationBackend>; G] {
+        let device = Default::default();
+        let input = Tensor::<SerializationBackend, 3>::from_data(
+            self.input.clone().convert::<PS::FloatElem>(),
+            &device,
+        );
+        let hidden = Tensor::<SerializationBackend, 3>::from_data(
+            self.hidden.clone().convert::<PS::FloatElem>(),
+            &device,
+        );
+        let bias = self.bias.as_ref().map(|bias| {
+            Tensor::<SerializationBackend, 2>::from_data(
+                bias.clone().convert::<PS::FloatElem>(),
+                &device,
+            )
+        });
+
+        // The ONNX weights of a gate are [d_hidden, d_features], transposed by the linear layers.
+        let weight = |weights: &Tensor<SerializationBackend, 3>, gate: usize| {
+            let [_, _, d_features] = weights.dims();
+            let weight = weights
+                .clone()
+                .slice([
+                    direction..direction + 1,
+                    gate * d_hidden..(gate + 1) * d_hidden,
+                    0..d_features,
+                ])
+                .squeeze::<2>(0)
+                .transpose();
+
+            Param::initialized(ParamId::new(), weight)
+        };
+        // The hidden biases follow the input biases of all the gates.
+        let bias = |offset: usize, gate: usize| {
+            bias.as_ref().map(|bias| {
+                let start = offset + gate * d_hidden;
+                let bias = bias
+                    .clone()
+                    .slice([direction..direction + 1, start..start + d_hidden])
+                    .squeeze::<1>(0);
+
+                Param::initialized(ParamId::new(), bias)
+            })
+        };
+
+        core::array::from_fn(|gate| GateControllerRecord {
+            input_transform: LinearRecord {
+                weight: weight(&input, gate),
+                bias: bias(0, gate),
+            },
+            hidden_transform: LinearRecord {
+                weight: weight(&hidden, gate),
+                bias: bias(G * d_hidden, gate),
+            },
+        })
+    }
+
+    fn lstm_record<PS: PrecisionSettings>(
+        &self,
+        direction: usize,
+        d_hidden: usize,
+    ) -> LstmRecord<SerializationBackend> {
+        // The ONNX gates are ordered input, output, forget and cell.
+        let [input_gate, output_gate, forget_gate, cell_gate] =
+            self.gate_records::<PS, 4>(direction, d_hidden);
+
+        LstmRecord {
+            input_gate,
+            forget_gate,
+            output_gate,
+            cell_gate,
+            d_hidden: ConstantRecord::new(),
+        }
+    }
+}
+
+impl RnnConfig {
+    /// The input in the `[batch_size, sequence_length, d_input]` layout of the burn modules,
+    /// with the sequence reversed for the reverse direction.
+    fn input_tokens(&self, input: TokenStream) -> TokenStream {
+        let input = match self.batch_first {
+            true => input,
+            false => quote! { #input.swap_dims(0, 1) },
+        };
+
+        match self.direction {
+            RnnDirection::Reverse => quote! { #input.flip([1]) },
+            _ => input,
+        }
+    }
+
+    /// The ONNX state `[num_directions, batch_size, d_hidden]` in the layout of the burn modules.
+    fn state_input(&self, state: TokenStream) -> TokenStream {
+        match (&self.direction, self.batch_first) {
+            (RnnDirection::Bidirectional, false) => state,
+            (RnnDirection::Bidirectional, true) => quote! { #state.swap_dims(0, 1) },
+            (_, false) => quote! { #state.squeeze::<2>(0) },
+            (_, true) => quote! { #state.squeeze::<2>(1) },
+        }
+    }
+
+    /// The final state of the burn modules in the ONNX layout.
+    fn state_output(&self, state: TokenStream) -> TokenStream {
+        match (&self.direction, self.batch_first) {
+            (RnnDirection::Bidirectional, false) => state,
+            (RnnDirection::Bidirectional, true) => quote! { #state.swap_dims(0, 1) },
+            (_, false) => quote! { #state.unsqueeze_dim::<3>(0) },
+            (_, true) => quote! { #state.unsqueeze_dim::<3>(1) },
+        }
+    }
+
+    /// The output `[batch_size, sequence_length, num_directions * d_hidden]` of the burn modules
+    /// in the ONNX layout `[sequence_length, num_directions, batch_size, d_hidden]`.
+    fn output(&self, output: TokenStream) -> TokenStream {
+        let d_hidden = self.d_hidden.to_tokens();
+
+        match (&self.direction, self.batch_first) {
+            (RnnDirection::Bidirectional, batch_first) => {
+                let permute = match batch_first {
+                    true => quote! {},
+                    false => quote! { .permute([1, 2, 0, 3]) },
+                };
+                quote! {
+                    {
+                        let [batch_size, seq_length, _] = #output.dims();
+                        #output.reshape([batch_size, seq_length, 2, #d_hidden])#permute
+                    }
+                }
+            }
+            (direction, batch_first) => {
+                let output = match direction {
+                    RnnDirection::Reverse => quote! { #output.flip([1]) },
+                    _ => output,
+                };
+                match batch_first {
+                    true => quote! { #output.unsqueeze_dim::<4>(2) },
+                    false => quote! { #output.swap_dims(0, 1).unsqueeze_dim::<4>(1) },
+                }
+            }
+        }
+    }
+}
+
+/// Binds the outputs of a recurrent node, skipping the ones which aren't used.
+fn bind_outputs(
+    outputs: &[&Option<TensorType>],
+    body: TokenStream,
+    values: &[TokenStream],
+) -> TokenStream {
+    let (names, values): (Vec<_>, Vec<_>) = outputs
+        .iter()
+        .zip(values)
+        .filter_map(|(output, value)| output.as_ref().map(|output| (&output.name, value)))
+        .unzip();
+
+    quote! {
+        let (#(#names,)*) = {
+            #body
+
+            (#(#values,)*)
+        };
+    }
+}
+
+fn output_types(outputs: &[&Option<TensorType>]) -> Vec<Type> {
+    outputs
+        .iter()
+        .filter_map(|output| output.as_ref().map(|output| Type::Tensor(output.clone())))
+        .collect()
+}
+
+impl<PS: PrecisionSettings> NodeCodegen<PS> for LstmNode {
+    fn input_types(&self) -> Vec<Type> {
+        [
+            Some(&self.input),
+            self.initial_hidden.as_ref(),
+            self.initial_cell.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|input| Type::Tensor(input.clone()))
+        .collect()
+    }
+
+    fn output_types(&self) -> Vec<Type> {
+        output_types(&[&self.output, &self.output_hidden, &self.output_cell])
+    }
+
+    fn field_type(&self) -> Option<Type> {
+        Some(Type::Other(self.field.clone()))
+    }
+
+    fn field_init(&self) -> Option<TokenStream> {
+        let name = &self.field.name;
+        let d_input = self.config.d_input.to_tokens();
+        let d_hidden = self.config.d_hidden.to_tokens();
+        let bias = self.config.bias;
+        let config = match self.config.direction {
+            RnnDirection::Bidirectional => quote! { BiLstmConfig },
+            _ => quote! { LstmConfig },
+        };
+        let tokens = quote! {
+            let #name = #config::new(#d_input, #d_hidden, #bias)
+                .init(device);
+        };
+
+        Some(tokens)
+    }
+
+    fn field_serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let d_hidden = self.config.d_hidden;
+
+        match self.config.direction {
+            RnnDirection::Bidirectional => {
+                let record = BiLstmRecord::<SerializationBackend> {
+                    forward: self.weights.lstm_record::<PS>(0, d_hidden),
+                    reverse: self.weights.lstm_record::<PS>(1, d_hidden),
+                    d_hidden: ConstantRecord::new(),
+                };
+
+                Record::into_item::<PS>(record).serialize(serializer)
+            }
+            _ => {
+                let record = self.weights.lstm_record::<PS>(0, d_hidden);
+
+                Record::into_item::<PS>(record).serialize(serializer)
+            }
+        }
+    }
+
+    fn forward(&self, scope: &mut Scope, node_position: usize) -> TokenStream {
+        let input = scope.tensor_use_owned(&self.input, node_position);
+        let input = self.config.input_tokens(input);
+        let field = &self.field.name;
+
+        let mut initial_state = |state: &Option<TensorType>| {
+            state.as_ref().map(|state| {
+                let state = scope.tensor_use_owned(state, node_position);
+                self.config.state_input(state)
+            })
+        };
+        // A missing initial state is zeros, like when both are missing.
+        let state = match (
+            initial_state(&self.initial_hidden),
+            initial_state(&self.initial_cell),
+        ) {
+            (None, None) => quote! { None },
+            (Some(hidden), None) => quote! {{
+                let hidden = #hidden;
+                Some(LstmState::new(hidden.zeros_like(), hidden))
+            }},
+            (None, Some(cell)) => quote! {{
+                let cell = #cell;
+                let hidden = cell.zeros_like();
+                Some(LstmState::new(cell, hidden))
+            }},
+            (Some(hidden), Some(cell)) => quote! { Some(LstmState::new(#cell, #hidden)) },
+        };
+
+        let values = [
+            self.config.output(quote! { output }),
+            self.config.state_output(quote! { state.hidden }),
+            self.config.state_output(quote! { state.cell }),
+        ];
+        let output = match &self.output {
+            Some(_) => quote! { output },
+            None => quote! { _ },
+        };
+        let final_state = match self.output_hidden.is_some() || self.output_cell.is_some() {
+            true => quote! { state },
+            false => quote! { _ },
+        };
+        let body = quote! {
+            let (#output, #final_state) = self.#field.forward(#input, #state);
+        };
+
+        bind_outputs(
+            &[&self.output, &self.output_hidden, &self.output_cell],
+            body,
+            &values,
+        )
+    }
+
+    fn register_imports(&self, imports: &mut BurnImports) {
+        match self.config.direction {
+            RnnDirection::Bidirectional => {
+                imports.register("burn::nn::BiLstm");
+                imports.register("burn::nn::BiLstmConfig");
+            }
+            _ => {
+                imports.register("burn::nn::Lstm");
+                imports.register("burn::nn::LstmConfig");
+            }
+        }
+        if self.initial_hidden.is_some() || self.initial_cell.is_some() {
+            imports.register("burn::nn::LstmState");
+        }
+    }
+
+    fn into_node(self) -> Node<PS> {
+        Node::Lstm(self)
+    }
+}
+
+impl<PS: PrecisionSettings> NodeCodegen<PS> for GruNode {
+    fn input_types(&self) -> Vec<Type> {
+        [Some(&self.input), self.initial_hidden.as_ref()]
+            .into_iter()
+            .flatten()
+            .map(|input| Type::Tensor(input.clone()))
+            .collect()
+    }
+
+    fn output_types(&self) -> Vec<Type> {
+        output_types(&[&self.output, &self.output_hidden])
+    }
+
+    fn field_type(&self) -> Option<Type> {
+        Some(Type::Other(self.field.clone()))
+    }
+
+    fn field_init(&self) -> Option<TokenStream> {
+        let name = &self.field.name;
+        let d_input = self.config.d_input.to_tokens();
+        let d_hidden = self.config.d_hidden.to_tokens();
+        let bias = self.config.bias;
+        let reset_after = self.config.reset_after;
+        let tokens = quote! {
+            let #name = GruConfig::new(#d_input, #d_hidden, #bias)
+                .with_reset_after(#reset_after)
+                .init(device);
+        };
+
+        Some(tokens)
+    }
+
+    fn field_serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // The ONNX gates are ordered update, reset and new.
+        let [update_gate, reset_gate, new_gate] =
+            self.weights.gate_records::<PS, 3>(0, self.config.d_hidden);
+        let record = GruRecord::<SerializationBackend> {
+            update_gate,
+            reset_gate,
+            new_gate,
+            d_hidden: ConstantRecord::new(),
+            reset_after: ConstantRecord::new(),
+        };
+
+        let item = Record::into_item::<PS>(record);
+        item.serialize(serializer)
+    }
+
+    fn forward(&self, scope: &mut Scope, node_position: usize) -> TokenStream {
+        let input = scope.tensor_use_owned(&self.input, node_position);
+        let input = self.config.input_tokens(input);
+        let field = &self.field.name;
+
+        let state = match &self.initial_hidden {
+            Some(state) => {
+                let state = scope.tensor_use_owned(state, node_position);
+                let state = self.config.state_input(state);
+                quote! { Some(#state) }
+            }
+            None => quote! { None },
+        };
+
+        let values = [
+            self.config.output(quote! { output }),
+            self.config.state_output(quote! { state }),
+        ];
+        let output = match &self.output {
+            Some(_) => quote! { output },
+            None => quote! { _ },
+        };
+        let final_state = match &self.output_hidden {
+            Some(_) => quote! { state },
+            None => quote! { _ },
+        };
+        let body = quote! {
+            let (#output, #final_state) = self.#field.forward_with_state(#input, #state);
+        };
+
+        bind_outputs(&[&self.output, &self.output_hidden], body, &values)
+    }
+
+    fn register_imports(&self, imports: &mut BurnImports) {
+        imports.register("burn::nn::gru::Gru");
+        imports.register("burn::nn::gru::GruConfig");
+    }
+
+    fn into_node(self) -> Node<PS> {
+        Node::Gru(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::burn::{graph::BurnGraph, node::test::assert_tokens, TensorType};
+    use burn::record::FullPrecisionSettings;
+
+    fn weights() -> RnnWeights {
+        RnnWeights {
+            input: TensorData::from([2f32]),
+            hidden: TensorData::from([2f32]),
+            bias: None,
+        }
+    }
+
+    #[test]
+    fn test_codegen_lstm() {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+
+        graph.register(LstmNode::new(
+            "lstm",
+            TensorType::new_float("input", 3),
+            None,
+            None,
+            Some(TensorType::new_float("output", 4)),
+            Some(TensorType::new_float("output_hidden", 3)),
+            None,
+            weights(),
+            RnnConfig::new(2, 4, false, RnnDirection::Forward, false),
+        ));
+
+        graph.register_input_output(
+            vec!["input".to_string()],
+            vec!["output".to_string(), "output_hidden".to_string()],
+        );
+
+        let expected = quote! {
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+            use burn::nn::Lstm;
+            use burn::nn::LstmConfig;
+
+            #[derive(Module, Debug)]
+            pub struct Model <B: Backend> {
+                lstm: Lstm<B>,
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model <B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    let lstm = LstmConfig::new(2, 4, false)
+                        .init(device);
+
+                    Self {
+                        lstm,
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(&self, input: Tensor<B, 3>) -> (Tensor<B, 4>, Tensor<B, 3>) {
+                    let (output, output_hidden,) = {
+                        let (output, state) = self.lstm.forward(input.swap_dims(0, 1), None);
+
+                        (
+                            output.swap_dims(0, 1).unsqueeze_dim::<4>(1),
+                            state.hidden.unsqueeze_dim::<3>(0),
+                        )
+                    };
+
+                    (output, output_hidden)
+                }
+            }
+        };
+
+        assert_tokens(graph.codegen(), expected);
+    }
+
+    #[test]
+    fn test_codegen_gru_reverse_batch_first() {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+
+        graph.register(GruNode::new(
+            "gru",
+            TensorType::new_float("input", 3),
+            Some(TensorType::new_float("initial_hidden", 3)),
+            None,
+            Some(TensorType::new_float("output_hidden", 3)),
+            weights(),
+            RnnConfig::new(2, 4, true, RnnDirection::Reverse, true).with_reset_after(true),
+        ));
+
+        graph.register_input_output(
+            vec!["input".to_string(), "initial_hidden".to_string()],
+            vec!["output_hidden".to_string()],
+        );
+
+        let expected = quote! {
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+            use burn::nn::gru::Gru;
+            use burn::nn::gru::GruConfig;
+
+            #[derive(Module, Debug)]
+            pub struct Model <B: Backend> {
+                gru: Gru<B>,
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model <B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    let gru = GruConfig::new(2, 4, true)
+                        .with_reset_after(true)
+                        .init(device);
+
+                    Self {
+                        gru,
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(
+                    &self,
+                    input: Tensor<B, 3>,
+                    initial_hidden: Tensor<B, 3>,
+                ) -> Tensor<B, 3> {
+                    let (output_hidden,) = {
+                        let (_, state) = self
+                            .gru
+                            .forward_with_state(input.flip([1]), Some(initial_hidden.squeeze::<2>(1)));
+
+                        (state.unsqueeze_dim::<3>(1),)
+                    };
+
+                    output_hidden
+                }
+            }
+        };
+
+        assert_tokens(graph.codegen(), expected);
+    }
+}
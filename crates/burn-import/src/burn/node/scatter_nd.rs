@@ -0,0 +1,119 @@
+use super::{Node, NodeCodegen};
+use crate::burn::{BurnImports, Scope, TensorType, ToTokens, Type};
+use burn::record::PrecisionSettings;
+use burn::tensor::ScatterReduction;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+#[derive(Debug, Clone, new)]
+pub struct ScatterNdNode {
+    pub input: TensorType,
+    pub indices: TensorType,
+    pub updates: TensorType,
+    pub output: TensorType,
+    pub reduction: ScatterReduction,
+}
+
+impl<PS: PrecisionSettings> NodeCodegen<PS> for ScatterNdNode {
+    fn output_types(&self) -> Vec<Type> {
+        vec![Type::Tensor(self.output.clone())]
+    }
+
+    fn input_types(&self) -> Vec<Type> {
+        vec![
+            Type::Tensor(self.input.clone()),
+            Type::Tensor(self.indices.clone()),
+            Type::Tensor(self.updates.clone()),
+        ]
+    }
+
+    fn forward(&self, scope: &mut Scope, node_position: usize) -> TokenStream {
+        let input = scope.tensor_use_owned(&self.input, node_position);
+        let indices = scope.tensor_use_owned(&self.indices, node_position);
+        let updates = scope.tensor_use_owned(&self.updates, node_position);
+        let output = &self.output.name;
+        let reduction = self.reduction.to_tokens();
+
+        quote! {
+            let #output = scatter_nd(#input, #indices, #updates, #reduction);
+        }
+    }
+
+    fn register_imports(&self, imports: &mut BurnImports) {
+        imports.register("burn::tensor::scatter_nd");
+        imports.register("burn::tensor::ScatterReduction");
+    }
+
+    fn into_node(self) -> Node<PS> {
+        Node::ScatterNd(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use burn::record::FullPrecisionSettings;
+
+    use super::*;
+    use crate::burn::{graph::BurnGraph, node::test::assert_tokens, TensorType};
+
+    #[test]
+    fn test_codegen_scatter_nd() {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+
+        graph.register(ScatterNdNode::new(
+            TensorType::new_float("tensor1", 3),
+            TensorType::new_int("tensor2", 2),
+            TensorType::new_float("tensor3", 2),
+            TensorType::new_float("tensor4", 3),
+            ScatterReduction::Add,
+        ));
+
+        graph.register_input_output(
+            vec![
+                "tensor1".to_string(),
+                "tensor2".to_string(),
+                "tensor3".to_string(),
+            ],
+            vec!["tensor4".to_string()],
+        );
+
+        let expected = quote! {
+            use burn::tensor::Int;
+            use burn::tensor::ScatterReduction;
+            use burn::tensor::scatter_nd;
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+
+            #[derive(Module, Debug)]
+            pub struct Model<B: Backend> {
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model<B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    Self {
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(
+                    &self,
+                    tensor1: Tensor<B, 3>,
+                    tensor2: Tensor<B, 2, Int>,
+                    tensor3: Tensor<B, 2>,
+                ) -> Tensor<B, 3> {
+                    let tensor4 = scatter_nd(tensor1, tensor2, tensor3, ScatterReduction::Add);
+
+                    tensor4
+                }
+            }
+        };
+
+        assert_tokens(graph.codegen(), expected);
+    }
+}
@@ -0,0 +1,216 @@
+use super::{Node, NodeCodegen};
+use crate::burn::{BurnImports, Scope, TensorType, ToTokens, Type};
+use burn::config::Config;
+use burn::record::PrecisionSettings;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+#[derive(Config, Debug)]
+pub struct TopKConfig {
+    pub axis: usize,
+    /// If the largest elements are selected, the smallest ones otherwise.
+    pub largest: bool,
+}
+
+/// The number of elements selected by a TopK node.
+#[derive(Debug, Clone)]
+pub enum TopKSize {
+    Static(usize),
+    Runtime(Type),
+}
+
+#[derive(Debug, Clone, new)]
+pub struct TopKNode {
+    pub input: TensorType,
+    pub k: TopKSize,
+    pub values: Option<TensorType>,
+    pub indices: Option<TensorType>,
+    pub config: TopKConfig,
+}
+
+impl<PS: PrecisionSettings> NodeCodegen<PS> for TopKNode {
+    fn output_types(&self) -> Vec<Type> {
+        [&self.values, &self.indices]
+            .into_iter()
+            .flatten()
+            .map(|output| Type::Tensor(output.clone()))
+            .collect()
+    }
+
+    fn input_types(&self) -> Vec<Type> {
+        let input = Type::Tensor(self.input.clone());
+
+        match &self.k {
+            TopKSize::Static(_) => vec![input],
+            TopKSize::Runtime(k) => vec![input, k.clone()],
+        }
+    }
+
+    fn forward(&self, scope: &mut Scope, node_position: usize) -> TokenStream {
+        let input = scope.tensor_use_owned(&self.input, node_position);
+        let axis = self.config.axis.to_tokens();
+
+        let k = match &self.k {
+            TopKSize::Static(k) => k.to_tokens(),
+            TopKSize::Runtime(Type::Tensor(k)) => {
+                // The number of elements is a tensor with a single element read from the device.
+                let k = scope.tensor_use_owned(k, node_position);
+                quote! { #k.into_scalar().elem::<i64>() as usize }
+            }
+            TopKSize::Runtime(Type::Scalar(k)) => {
+                let k = &k.name;
+                quote! { #k as usize }
+            }
+            k => panic!("Invalid size source {:?}", k),
+        };
+
+        let output = |output: &Option<TensorType>| match output {
+            Some(output) => {
+                let name = &output.name;
+                quote! { #name }
+            }
+            None => quote! { _ },
+        };
+        let values = output(&self.values);
+        let indices = output(&self.indices);
+
+        match self.config.largest {
+            true => quote! {
+                let (#values, #indices) = #input.topk_with_indices(#k, #axis);
+            },
+            // The smallest elements are the first ones of the ascending sort.
+            false => quote! {
+                let (#values, #indices) = {
+                    let k = #k;
+                    let (values, indices) = #input.sort_with_indices(#axis);
+
+                    (values.narrow(#axis, 0, k), indices.narrow(#axis, 0, k))
+                };
+            },
+        }
+    }
+
+    fn register_imports(&self, imports: &mut BurnImports) {
+        if let TopKSize::Runtime(Type::Tensor(_)) = &self.k {
+            imports.register("burn::tensor::ElementConversion");
+        }
+    }
+
+    fn into_node(self) -> Node<PS> {
+        Node::TopK(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use burn::record::FullPrecisionSettings;
+
+    use super::*;
+    use crate::burn::{graph::BurnGraph, node::test::assert_tokens, TensorType};
+
+    #[test]
+    fn test_codegen_topk() {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+
+        graph.register(TopKNode::new(
+            TensorType::new_float("input", 2),
+            TopKSize::Static(3),
+            Some(TensorType::new_float("values", 2)),
+            Some(TensorType::new_int("indices", 2)),
+            TopKConfig::new(1, true),
+        ));
+
+        graph.register_input_output(
+            vec!["input".to_string()],
+            vec!["values".to_string(), "indices".to_string()],
+        );
+
+        let expected = quote! {
+            use burn::tensor::Int;
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+
+            #[derive(Module, Debug)]
+            pub struct Model<B: Backend> {
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model<B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    Self {
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(&self, input: Tensor<B, 2>) -> (Tensor<B, 2>, Tensor<B, 2, Int>) {
+                    let (values, indices) = input.topk_with_indices(3, 1);
+
+                    (values, indices)
+                }
+            }
+        };
+
+        assert_tokens(graph.codegen(), expected);
+    }
+
+    #[test]
+    fn test_codegen_topk_smallest_runtime_size() {
+        let mut graph = BurnGraph::<FullPrecisionSettings>::default();
+
+        graph.register(TopKNode::new(
+            TensorType::new_float("input", 2),
+            TopKSize::Runtime(Type::Tensor(TensorType::new_int("k", 1))),
+            Some(TensorType::new_float("values", 2)),
+            None,
+            TopKConfig::new(0, false),
+        ));
+
+        graph.register_input_output(
+            vec!["input".to_string(), "k".to_string()],
+            vec!["values".to_string()],
+        );
+
+        let expected = quote! {
+            use burn::tensor::ElementConversion;
+            use burn::tensor::Int;
+            use burn::{
+                module::Module,
+                tensor::{backend::Backend, Tensor},
+            };
+
+            #[derive(Module, Debug)]
+            pub struct Model<B: Backend> {
+                phantom: core::marker::PhantomData<B>,
+                device: burn::module::Ignored<B::Device>,
+            }
+
+            impl<B: Backend> Model<B> {
+                #[allow(unused_variables)]
+                pub fn new(device: &B::Device) -> Self {
+                    Self {
+                        phantom: core::marker::PhantomData,
+                        device: burn::module::Ignored(device.clone()),
+                    }
+                }
+                #[allow(clippy::let_and_return, clippy::approx_constant)]
+                pub fn forward(&self, input: Tensor<B, 2>, k: Tensor<B, 1, Int>) -> Tensor<B, 2> {
+                    let (values, _) = {
+                        let k = k.into_scalar().elem::<i64>() as usize;
+                        let (values, indices) = input.sort_with_indices(0);
+
+                        (values.narrow(0, 0, k), indices.narrow(0, 0, k))
+                    };
+
+                    values
+                }
+            }
+        };
+
+        assert_tokens(graph.codegen(), expected);
+    }
+}
@@ -0,0 +1,652 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::error::Error;
+use crate::pytorch::serialize_tensor_data;
+
+use burn::record::serde::{
+    adapter::DefaultAdapter,
+    data::{remap, unflatten, NestedValue, Serializable},
+    de::Deserializer,
+    error,
+    ser::Serializer,
+};
+use burn::{
+    module::ParamId,
+    record::PrecisionSettings,
+    tensor::{DType, Element, ElementConversion, TensorData},
+};
+
+use half::{bf16, f16};
+use regex::Regex;
+use rmpv::Value;
+use serde::de::DeserializeOwned;
+
+/// The msgpack extension type of the numpy arrays serialized by Flax.
+const EXT_NDARRAY: i8 = 1;
+
+/// The msgpack extension type of the numpy scalars serialized by Flax.
+const EXT_NPSCALAR: i8 = 3;
+
+/// The key marking the large arrays which Flax splits in chunks.
+const CHUNKED_ARRAY: &str = "__msgpack_chunked_array__";
+
+/// The variable collections loaded from a dictionary of variables.
+const COLLECTIONS: [&str; 2] = ["params", "batch_stats"];
+
+/// The names of the Flax parameters which differ from the names of the Burn parameters.
+const PARAM_NAMES: [(&str, &str); 5] = [
+    ("kernel", "weight"),
+    ("embedding", "weight"),
+    ("scale", "gamma"),
+    ("mean", "running_mean"),
+    ("var", "running_var"),
+];
+
+/// Deserializes a Flax msgpack checkpoint.
+///
+/// # Arguments
+///
+/// * `path` - A string slice that holds the path of the file to read.
+/// * `top_level_key` - The key of the variables in the checkpoint, if they are not at its root.
+/// * `key_remap` - A vector of tuples containing a regular expression and a replacement string.
+/// * `debug` - Whether to print the keys and the shapes of the tensors.
+pub fn from_file<PS, D>(
+    path: &Path,
+    top_level_key: Option<&str>,
+    key_remap: Vec<(Regex, String)>,
+    debug: bool,
+) -> Result<D, Error>
+where
+    D: DeserializeOwned,
+    PS: PrecisionSettings,
+{
+    let bytes = std::fs::read(path)?;
+    let mut root = rmpv::decode::read_value(&mut bytes.as_slice())?;
+
+    if let Some(key) = top_level_key {
+        root = entry(&root, key)
+            .cloned()
+            .ok_or_else(|| Error::Other(format!("Missing top-level key {key}")))?;
+    }
+
+    // The variables of a module, or a train state, hold their parameters by collection
+    let mut weights = HashMap::new();
+    match entry(&root, COLLECTIONS[0]) {
+        Some(_) => {
+            for collection in COLLECTIONS {
+                if let Some(value) = entry(&root, collection) {
+                    read_weights(value, "", &mut weights)?;
+                }
+            }
+        }
+        None => read_weights(&root, "", &mut weights)?,
+    }
+
+    // Convert the paths of the weights to the keys of the Burn parameters
+    let keys = weights
+        .keys()
+        .map(|path| (path.clone(), burn_key(path, &weights)))
+        .collect::<HashMap<_, _>>();
+    let tensors = weights
+        .into_iter()
+        .map(|(path, tensor)| {
+            let (key, layout) = keys[&path].clone();
+            (key, tensor.into_layout(layout))
+        })
+        .collect::<HashMap<_, _>>();
+
+    // Remap the keys (replace the keys in the map with the new keys)
+    let (tensors, remapped_keys) = remap(tensors, key_remap);
+
+    // Print the remapped keys if debug is enabled
+    if debug {
+        let mut remapped_keys = remapped_keys;
+        remapped_keys.sort();
+        println!("Debug information of keys and tensor shapes:\n---");
+        for (new_key, old_key) in remapped_keys {
+            if old_key != new_key {
+                println!("Original Key: {old_key}");
+                println!("Remapped Key: {new_key}");
+            } else {
+                println!("Key: {}", new_key);
+            }
+
+            let tensor = &tensors[&new_key];
+            println!("Shape: {:?}", tensor.shape);
+            println!("Dtype: {:?}", tensor.dtype);
+            println!("---");
+        }
+    }
+
+    // Convert the Flax tensors to a nested value data structure
+    let nested_value = unflatten::<PS, _>(tensors)?;
+
+    // The Flax layouts are converted to the Burn layouts when the tensors are read
+    let deserializer = Deserializer::<DefaultAdapter>::new(nested_value, true);
+
+    // Deserialize the nested value into a record type
+    let value = D::deserialize(deserializer)?;
+    Ok(value)
+}
+
+/// The value of a string key of a msgpack map.
+fn entry<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    value
+        .as_map()?
+        .iter()
+        .find(|(name, _)| name.as_str() == Some(key))
+        .map(|(_, value)| value)
+}
+
+/// Reads the arrays of a pytree recursively, by path.
+///
+/// The lists of modules are serialized by Flax as maps with the indices as keys, e.g. `layers.0`.
+fn read_weights(
+    value: &Value,
+    prefix: &str,
+    weights: &mut HashMap<String, FlaxTensor>,
+) -> Result<(), Error> {
+    match value {
+        Value::Ext(EXT_NDARRAY | EXT_NPSCALAR, data) => {
+            weights.insert(prefix.to_string(), FlaxTensor::from_bytes(data)?);
+        }
+        Value::Map(_) if entry(value, CHUNKED_ARRAY).is_some() => {
+            weights.insert(prefix.to_string(), FlaxTensor::from_chunks(value)?);
+        }
+        Value::Map(entries) => {
+            for (name, value) in entries {
+                let name = match name {
+                    Value::String(name) => name.as_str().unwrap_or_default().to_string(),
+                    name => name.to_string(),
+                };
+                let path = match prefix.is_empty() {
+                    true => name,
+                    false => format!("{prefix}.{name}"),
+                };
+                read_weights(value, &path, weights)?;
+            }
+        }
+        // The other leaves, e.g. the step of a train state, are not parameters
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// The conversion of the layout of a Flax array to the layout of its Burn parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    /// The layouts are the same, e.g. the `[in, out]` kernels of `Dense`.
+    Same,
+    /// The kernel of a `Conv`, `[spatial..., in, out]`, to `[out, in, spatial...]`.
+    Conv,
+    /// The kernel of a `ConvTranspose`, `[spatial..., in, out]`, to `[in, out, spatial...]`.
+    ///
+    /// The spatial axes are flipped, since Flax does not transpose the kernel by default.
+    ConvTranspose,
+    /// The kernel of a `DenseGeneral`, `[in..., out...]` with `output_rank` output axes, to the
+    /// `[in, out]` kernel of a `Linear`.
+    DenseGeneral { output_rank: usize },
+    /// The bias of a `DenseGeneral`, `[out...]`, to the `[out]` bias of a `Linear`.
+    Flatten,
+}
+
+/// The names of the projections of `MultiHeadDotProductAttention`, whose `DenseGeneral` kernels
+/// have one input axis.
+const ATTENTION_PROJECTIONS: [&str; 3] = ["query", "key", "value"];
+
+/// The key of the Burn parameter of a Flax array, and the conversion of its layout.
+///
+/// The `bias` of the normalization layers, which have a `scale`, is the `beta` of Burn.
+///
+/// The convolution kernels are recognized by the name of their module, e.g. `Conv_0` or
+/// `ConvTranspose_0`, since the kernels of `DenseGeneral` can have the same rank.
+fn burn_key(path: &str, weights: &HashMap<String, FlaxTensor>) -> (String, Layout) {
+    let (prefix, name) = match path.rsplit_once('.') {
+        Some((prefix, name)) => (Some(prefix), name),
+        None => (None, path),
+    };
+
+    let sibling = |sibling: &str| match prefix {
+        Some(prefix) => weights.get(&format!("{prefix}.{sibling}")),
+        None => weights.get(sibling),
+    };
+
+    let burn_name = match PARAM_NAMES.iter().find(|(flax_name, _)| *flax_name == name) {
+        Some((_, burn_name)) => *burn_name,
+        None if name == "bias" && sibling("scale").is_some() => "beta",
+        None => name,
+    };
+
+    let key = match prefix {
+        Some(prefix) => format!("{prefix}.{burn_name}"),
+        None => burn_name.to_string(),
+    };
+
+    let rank = weights.get(path).map_or(0, |tensor| tensor.shape.len());
+    let module = prefix
+        .map(|prefix| prefix.rsplit('.').next().unwrap_or(prefix))
+        .unwrap_or_default();
+    let module_lower = module.to_lowercase();
+    let is_conv = module_lower.contains("conv");
+    let is_transpose = module_lower.contains("transpose") || module_lower.contains("deconv");
+
+    let layout = match name {
+        "kernel" if rank >= 3 && is_conv && is_transpose => Layout::ConvTranspose,
+        "kernel" if rank >= 3 && is_conv => Layout::Conv,
+        "kernel" if rank >= 3 => {
+            // The output axes are the axes of the bias, or the features of the projections
+            let output_rank = match sibling("bias") {
+                Some(bias) => bias.shape.len(),
+                None if ATTENTION_PROJECTIONS.contains(&module) => rank - 1,
+                None => 1,
+            };
+            Layout::DenseGeneral { output_rank }
+        }
+        "bias" if rank >= 2 => Layout::Flatten,
+        _ => Layout::Same,
+    };
+
+    (key, layout)
+}
+
+/// An array of a Flax checkpoint, with its values as little-endian bytes.
+#[derive(Debug, Clone, new)]
+struct FlaxTensor {
+    shape: Vec<usize>,
+    dtype: DType,
+    bytes: Vec<u8>,
+}
+
+impl FlaxTensor {
+    /// Reads a numpy array serialized by Flax, as a msgpack `(shape, dtype, bytes)` tuple.
+    fn from_bytes(data: &[u8]) -> Result<Self, Error> {
+        let value = rmpv::decode::read_value(&mut &data[..])?;
+        let invalid = || Error::Other("Invalid Flax array".to_string());
+
+        let [shape, dtype, bytes] = value.as_array().map(Vec::as_slice).ok_or_else(invalid)? else {
+            return Err(invalid());
+        };
+        let shape = shape
+            .as_array()
+            .ok_or_else(invalid)?
+            .iter()
+            .map(|dim| dim.as_u64().map(|dim| dim as usize).ok_or_else(invalid))
+            .collect::<Result<Vec<_>, _>>()?;
+        let dtype = dtype.as_str().ok_or_else(invalid)?;
+        let bytes = bytes.as_slice().ok_or_else(invalid)?.to_vec();
+
+        Ok(Self::new(shape, numpy_dtype(dtype)?, bytes))
+    }
+
+    /// Reads an array which Flax splits in chunks, when larger than its maximum chunk size.
+    ///
+    /// The chunks are the flattened values of the array, by index.
+    fn from_chunks(value: &Value) -> Result<Self, Error> {
+        let invalid = || Error::Other("Invalid Flax chunked array".to_string());
+
+        let shape = indexed(entry(value, "shape").ok_or_else(invalid)?)
+            .ok_or_else(invalid)?
+            .into_iter()
+            .map(|dim| dim.as_u64().map(|dim| dim as usize).ok_or_else(invalid))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut chunks = indexed(entry(value, "chunks").ok_or_else(invalid)?)
+            .ok_or_else(invalid)?
+            .into_iter()
+            .map(|chunk| match chunk {
+                Value::Ext(EXT_NDARRAY, data) => Self::from_bytes(data),
+                _ => Err(invalid()),
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter();
+
+        let mut tensor = chunks.next().ok_or_else(invalid)?;
+        for chunk in chunks {
+            tensor.bytes.extend(chunk.bytes);
+        }
+        tensor.shape = shape;
+
+        Ok(tensor)
+    }
+
+    /// Converts the layout of the array to the layout of its Burn parameter.
+    fn into_layout(mut self, layout: Layout) -> Self {
+        let rank = self.shape.len();
+
+        match layout {
+            Layout::Same => self,
+            Layout::Conv => {
+                let axes = [rank - 1, rank - 2].into_iter().chain(0..rank - 2);
+                self.permute(&axes.collect::<Vec<_>>(), false)
+            }
+            Layout::ConvTranspose => {
+                let axes = [rank - 2, rank - 1].into_iter().chain(0..rank - 2);
+                self.permute(&axes.collect::<Vec<_>>(), true)
+            }
+            Layout::DenseGeneral { output_rank } => {
+                let (inputs, outputs) = self.shape.split_at(rank - output_rank.min(rank));
+                self.shape = vec![inputs.iter().product(), outputs.iter().product()];
+                self
+            }
+            Layout::Flatten => {
+                self.shape = vec![self.shape.iter().product()];
+                self
+            }
+        }
+    }
+
+    /// Permutes the axes of the array, the axis `i` of the result being the axis `axes[i]`.
+    ///
+    /// With `flip_spatial`, the axes after the first two of the result are reversed.
+    fn permute(self, axes: &[usize], flip_spatial: bool) -> Self {
+        let rank = self.shape.len();
+        let shape = axes
+            .iter()
+            .map(|axis| self.shape[*axis])
+            .collect::<Vec<_>>();
+
+        let mut strides = vec![1; rank];
+        for axis in (0..rank - 1).rev() {
+            strides[axis] = strides[axis + 1] * self.shape[axis + 1];
+        }
+
+        let size = self.dtype.size();
+        let num_elements = self.bytes.len() / size;
+        let mut bytes = Vec::with_capacity(self.bytes.len());
+        let mut index = vec![0; rank];
+        for _ in 0..num_elements {
+            let offset = axes
+                .iter()
+                .zip(index.iter())
+                .enumerate()
+                .map(|(dim, (axis, i))| match flip_spatial && dim >= 2 {
+                    true => strides[*axis] * (shape[dim] - 1 - i),
+                    false => strides[*axis] * i,
+                })
+                .sum::<usize>();
+            bytes.extend_from_slice(&self.bytes[offset * size..(offset + 1) * size]);
+
+            // Increment the index in the new layout
+            for axis in (0..rank).rev() {
+                index[axis] += 1;
+                if index[axis] < shape[axis] {
+                    break;
+                }
+                index[axis] = 0;
+            }
+        }
+
+        Self::new(shape, self.dtype, bytes)
+    }
+}
+
+/// The values of a tuple serialized by Flax, as a map with the indices as keys.
+fn indexed(value: &Value) -> Option<Vec<&Value>> {
+    let entries = value.as_map()?;
+
+    (0..entries.len())
+        .map(|i| entry(value, &i.to_string()))
+        .collect()
+}
+
+/// The Burn data type of a numpy data type name.
+fn numpy_dtype(name: &str) -> Result<DType, Error> {
+    let dtype = match name {
+        "float64" => DType::F64,
+        "float32" => DType::F32,
+        "float16" => DType::F16,
+        "bfloat16" => DType::BF16,
+        "int64" => DType::I64,
+        "int32" => DType::I32,
+        "int16" => DType::I16,
+        "int8" => DType::I8,
+        "uint64" => DType::U64,
+        "uint32" => DType::U32,
+        "uint16" => DType::U16,
+        "uint8" => DType::U8,
+        "bool" => DType::Bool,
+        _ => return Err(Error::Other(format!("Unsupported Flax dtype: {name}"))),
+    };
+
+    Ok(dtype)
+}
+
+/// Serializes a Flax tensor as a `FloatElem` or `IntElem` tensor, depending on its data type.
+impl Serializable for FlaxTensor {
+    fn serialize<PS>(&self, serializer: Serializer) -> Result<NestedValue, error::Error>
+    where
+        PS: PrecisionSettings,
+    {
+        let shape = self.shape.clone();
+        let bytes = self.bytes.as_slice();
+
+        let data = match self.dtype {
+            DType::F64 => decode::<f64, PS::FloatElem, _>(bytes, shape, f64::from_le_bytes),
+            DType::F32 => decode::<f32, PS::FloatElem, _>(bytes, shape, f32::from_le_bytes),
+            DType::F16 => decode::<f16, PS::FloatElem, _>(bytes, shape, f16::from_le_bytes),
+            DType::BF16 => decode::<bf16, PS::FloatElem, _>(bytes, shape, bf16::from_le_bytes),
+            DType::I64 => decode::<i64, PS::IntElem, _>(bytes, shape, i64::from_le_bytes),
+            DType::I32 => decode::<i32, PS::IntElem, _>(bytes, shape, i32::from_le_bytes),
+            DType::I16 => decode::<i16, PS::IntElem, _>(bytes, shape, i16::from_le_bytes),
+            DType::I8 => decode::<i8, PS::IntElem, _>(bytes, shape, i8::from_le_bytes),
+            DType::U64 => decode::<u64, PS::IntElem, _>(bytes, shape, u64::from_le_bytes),
+            DType::U32 => decode::<u32, PS::IntElem, _>(bytes, shape, u32::from_le_bytes),
+            DType::U16 => decode::<u16, PS::IntElem, _>(bytes, shape, u16::from_le_bytes),
+            DType::U8 => decode::<u8, PS::IntElem, _>(bytes, shape, u8::from_le_bytes),
+            DType::Bool => {
+                let values = bytes.iter().map(|value| *value != 0).collect::<Vec<_>>();
+                TensorData::new(values, shape)
+            }
+            DType::QFloat(_) => unreachable!("Flax arrays are not quantized"),
+        };
+
+        serialize_tensor_data(data, ParamId::new(), serializer)
+    }
+}
+
+/// Helper function to decode the little-endian bytes of a Flax array.
+fn decode<T, E, const N: usize>(
+    bytes: &[u8],
+    shape: Vec<usize>,
+    from_le_bytes: fn([u8; N]) -> T,
+) -> TensorData
+where
+    T: ElementConversion,
+    E: Element,
+{
+    let values: Vec<E> = bytes
+        .chunks_exact(N)
+        .map(|value| from_le_bytes(value.try_into().unwrap()).elem())
+        .collect();
+
+    TensorData::new(values, shape)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weights(tensors: &[(&str, &[usize])]) -> HashMap<String, FlaxTensor> {
+        tensors
+            .iter()
+            .map(|(path, shape)| {
+                let size = shape.iter().product::<usize>();
+                let tensor = FlaxTensor::new(shape.to_vec(), DType::U8, vec![0; size]);
+                (path.to_string(), tensor)
+            })
+            .collect()
+    }
+
+    /// Encodes a numpy array the way Flax does.
+    fn ndarray(shape: &[u64], dtype: &str, bytes: Vec<u8>) -> Value {
+        let shape = shape.iter().map(|dim| Value::from(*dim)).collect();
+        let value = Value::Array(vec![
+            Value::Array(shape),
+            Value::from(dtype),
+            Value::Binary(bytes),
+        ]);
+        let mut data = Vec::new();
+        rmpv::encode::write_value(&mut data, &value).unwrap();
+
+        Value::Ext(EXT_NDARRAY, data)
+    }
+
+    #[test]
+    fn should_convert_flax_paths_to_burn_keys() {
+        let weights = weights(&[
+            ("Dense_0.kernel", &[2, 3]),
+            ("Dense_0.bias", &[3]),
+            ("LayerNorm_0.scale", &[3]),
+            ("LayerNorm_0.bias", &[3]),
+        ]);
+
+        assert_eq!(
+            burn_key("Dense_0.kernel", &weights),
+            ("Dense_0.weight".to_string(), Layout::Same)
+        );
+        assert_eq!(
+            burn_key("Dense_0.bias", &weights),
+            ("Dense_0.bias".to_string(), Layout::Same)
+        );
+        assert_eq!(
+            burn_key("LayerNorm_0.bias", &weights),
+            ("LayerNorm_0.beta".to_string(), Layout::Same)
+        );
+    }
+
+    #[test]
+    fn should_recognize_the_conv_kernels_by_module_name() {
+        let weights = weights(&[
+            ("Conv_0.kernel", &[3, 3, 2, 4]),
+            ("encoder.conv1.kernel", &[3, 2, 4]),
+            ("ConvTranspose_0.kernel", &[3, 3, 4, 2]),
+            ("DenseGeneral_0.kernel", &[2, 3, 4]),
+        ]);
+
+        let layout = |path| burn_key(path, &weights).1;
+
+        assert_eq!(layout("Conv_0.kernel"), Layout::Conv);
+        assert_eq!(layout("encoder.conv1.kernel"), Layout::Conv);
+        assert_eq!(layout("ConvTranspose_0.kernel"), Layout::ConvTranspose);
+        assert_eq!(
+            layout("DenseGeneral_0.kernel"),
+            Layout::DenseGeneral { output_rank: 1 }
+        );
+    }
+
+    #[test]
+    fn should_infer_the_output_axes_of_the_dense_general_kernels() {
+        // The projections of an attention with 2 heads of 3 features, and an embedding of 6
+        let weights = weights(&[
+            ("attention.query.kernel", &[6, 2, 3]),
+            ("attention.query.bias", &[2, 3]),
+            ("attention.key.kernel", &[6, 2, 3]),
+            ("attention.out.kernel", &[2, 3, 6]),
+        ]);
+
+        let layout = |path| burn_key(path, &weights).1;
+
+        assert_eq!(
+            layout("attention.query.kernel"),
+            Layout::DenseGeneral { output_rank: 2 }
+        );
+        assert_eq!(layout("attention.query.bias"), Layout::Flatten);
+        assert_eq!(
+            layout("attention.key.kernel"),
+            Layout::DenseGeneral { output_rank: 2 }
+        );
+        assert_eq!(
+            layout("attention.out.kernel"),
+            Layout::DenseGeneral { output_rank: 1 }
+        );
+    }
+
+    #[test]
+    fn should_convert_conv_kernel_to_channels_first() {
+        // [kernel_size, in, out] = [2, 1, 3]
+        let bytes = [0u8, 1, 2, 3, 4, 5].to_vec();
+        let kernel = FlaxTensor::new(vec![2, 1, 3], DType::U8, bytes);
+
+        let kernel = kernel.into_layout(Layout::Conv);
+
+        assert_eq!(kernel.shape, vec![3, 1, 2]);
+        assert_eq!(kernel.bytes, vec![0, 3, 1, 4, 2, 5]);
+    }
+
+    #[test]
+    fn should_flip_conv_transpose_kernel() {
+        // [kernel_size, in, out] = [2, 1, 3]
+        let bytes = [0u8, 1, 2, 3, 4, 5].to_vec();
+        let kernel = FlaxTensor::new(vec![2, 1, 3], DType::U8, bytes);
+
+        let kernel = kernel.into_layout(Layout::ConvTranspose);
+
+        assert_eq!(kernel.shape, vec![1, 3, 2]);
+        assert_eq!(kernel.bytes, vec![3, 0, 4, 1, 5, 2]);
+    }
+
+    #[test]
+    fn should_reshape_dense_general_kernel_to_2d() {
+        let bytes = (0..24).collect::<Vec<u8>>();
+        let kernel = FlaxTensor::new(vec![2, 3, 4], DType::U8, bytes.clone());
+
+        let query = kernel
+            .clone()
+            .into_layout(Layout::DenseGeneral { output_rank: 2 });
+        let out = kernel.into_layout(Layout::DenseGeneral { output_rank: 1 });
+
+        assert_eq!(query.shape, vec![2, 12]);
+        assert_eq!(out.shape, vec![6, 4]);
+        assert_eq!(query.bytes, bytes);
+        assert_eq!(out.bytes, bytes);
+    }
+
+    #[test]
+    fn should_read_chunked_arrays() {
+        let chunk = |values: [f32; 2]| {
+            let bytes = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+            ndarray(&[2], "float32", bytes)
+        };
+        let value = Value::Map(vec![
+            (Value::from(CHUNKED_ARRAY), Value::from(true)),
+            (
+                Value::from("shape"),
+                Value::Map(vec![
+                    (Value::from("0"), Value::from(2)),
+                    (Value::from("1"), Value::from(2)),
+                ]),
+            ),
+            (
+                Value::from("chunks"),
+                Value::Map(vec![
+                    (Value::from("1"), chunk([3.0, 4.0])),
+                    (Value::from("0"), chunk([1.0, 2.0])),
+                ]),
+            ),
+        ]);
+
+        let mut weights = HashMap::new();
+        read_weights(&value, "kernel", &mut weights).unwrap();
+        let tensor = &weights["kernel"];
+
+        let values = tensor
+            .bytes
+            .chunks_exact(4)
+            .map(|value| f32::from_le_bytes(value.try_into().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(tensor.shape, vec![2, 2]);
+        assert_eq!(tensor.dtype, DType::F32);
+        assert_eq!(values, vec![1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn should_reject_unsupported_dtypes() {
+        let Value::Ext(_, data) = ndarray(&[1], "complex64", vec![0; 8]) else {
+            unreachable!()
+        };
+
+        assert!(FlaxTensor::from_bytes(&data).is_err());
+    }
+}
@@ -0,0 +1,323 @@
+use core::marker::PhantomData;
+use std::path::PathBuf;
+
+use burn::{
+    record::{PrecisionSettings, Record, Recorder, RecorderError},
+    tensor::backend::Backend,
+};
+
+use regex::Regex;
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::reader::from_file;
+
+/// A recorder that loads Flax msgpack checkpoints into Burn modules.
+///
+/// The checkpoints are the files written with `flax.serialization.to_bytes`, e.g. by
+/// `flax.training.checkpoints.save_checkpoint`, and the msgpack checkpoints of Orbax. The arrays
+/// saved with TensorStore by Orbax are not supported.
+///
+/// The recorder is enabled by the `flax` feature, which is not a default feature.
+///
+/// The parameter pytree is flattened into Burn keys, e.g. `params/Dense_0/kernel` to
+/// `Dense_0.weight`, and the `batch_stats` collection is loaded with the `params` collection.
+///
+/// LoadArgs can be used to map the module names of Flax to the module names of Burn.
+/// See [LoadArgs](struct.LoadArgs.html) for more information.
+#[derive(new, Debug, Default, Clone)]
+pub struct FlaxFileRecorder<PS: PrecisionSettings> {
+    _settings: PhantomData<PS>,
+}
+
+impl<PS: PrecisionSettings, B: Backend> Recorder<B> for FlaxFileRecorder<PS> {
+    type Settings = PS;
+    type RecordArgs = PathBuf;
+    type RecordOutput = ();
+    type LoadArgs = LoadArgs;
+
+    fn save_item<I: Serialize>(
+        &self,
+        _item: I,
+        _file: Self::RecordArgs,
+    ) -> Result<(), RecorderError> {
+        unimplemented!("save_item not implemented for FlaxFileRecorder")
+    }
+
+    fn load_item<I: DeserializeOwned>(&self, _file: Self::LoadArgs) -> Result<I, RecorderError> {
+        unimplemented!("load_item not implemented for FlaxFileRecorder")
+    }
+
+    fn load<R: Record<B>>(
+        &self,
+        args: Self::LoadArgs,
+        device: &B::Device,
+    ) -> Result<R, RecorderError> {
+        let item = from_file::<PS, R::Item<Self::Settings>>(
+            &args.file,
+            args.top_level_key.as_deref(),
+            args.key_remap,
+            args.debug,
+        )?;
+        Ok(R::from_item(item, device))
+    }
+}
+
+/// Arguments for loading a Flax msgpack checkpoint.
+///
+/// # Fields
+///
+/// * `file` - The path to the file to load.
+/// * `key_remap` - A vector of tuples containing a regular expression and a replacement string.
+///                See [regex::Regex::replace](https://docs.rs/regex/latest/regex/struct.Regex.html#method.replace)
+///                for more information.
+///
+/// # Examples
+///
+/// ```text
+/// use burn_import::flax::{FlaxFileRecorder, LoadArgs};
+/// use burn::record::FullPrecisionSettings;
+/// use burn::record::Recorder;
+///
+/// // Map the Flax module names to the Burn module names
+/// let args = LoadArgs::new("checkpoint_1000".into())
+///     .with_top_level_key("target")
+///     .with_key_remap("^Conv_([0-9]+)", "conv$1")
+///     .with_key_remap("^Dense_([0-9]+)", "fc$1");
+///
+/// let record = FlaxFileRecorder::<FullPrecisionSettings>::default()
+///     .load(args, &device)
+///     .expect("Should decode state successfully");
+/// ```
+#[derive(Debug, Clone)]
+pub struct LoadArgs {
+    /// The path to the file to load.
+    pub file: PathBuf,
+
+    /// A list of key remappings.
+    pub key_remap: Vec<(Regex, String)>,
+
+    /// Top-level key of the variables in the checkpoint.
+    /// The train states are saved under `target` by `flax.training.checkpoints`.
+    pub top_level_key: Option<String>,
+
+    /// Whether to print debug information.
+    pub debug: bool,
+}
+
+impl LoadArgs {
+    /// Creates a new `LoadArgs` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The path to the file to load.
+    pub fn new(file: PathBuf) -> Self {
+        Self {
+            file,
+            key_remap: Vec::new(),
+            top_level_key: None,
+            debug: false,
+        }
+    }
+
+    /// Sets key remapping.
+    ///
+    /// The keys are remapped after the conversion of the Flax paths, e.g. `Dense_0.weight`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The Regex pattern to be replaced.
+    /// * `replacement` - The pattern to replace with.
+    ///
+    /// See [Regex](https://docs.rs/regex/1.5.4/regex/#syntax) for the pattern syntax and
+    /// [Replacement](https://docs.rs/regex/latest/regex/struct.Regex.html#method.replace) for the
+    /// replacement syntax.
+    pub fn with_key_remap(mut self, pattern: &str, replacement: &str) -> Self {
+        let regex = Regex::new(pattern).expect("Valid regex");
+
+        self.key_remap.push((regex, replacement.into()));
+        self
+    }
+
+    /// Sets the top-level key of the variables in the checkpoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The top-level key, e.g. `target` for a train state.
+    pub fn with_top_level_key(mut self, key: &str) -> Self {
+        self.top_level_key = Some(key.into());
+        self
+    }
+
+    /// Sets printing debug information on.
+    pub fn with_debug_print(mut self) -> Self {
+        self.debug = true;
+        self
+    }
+}
+
+impl From<PathBuf> for LoadArgs {
+    fn from(val: PathBuf) -> Self {
+        LoadArgs::new(val)
+    }
+}
+
+impl From<String> for LoadArgs {
+    fn from(val: String) -> Self {
+        LoadArgs::new(val.into())
+    }
+}
+
+impl From<&str> for LoadArgs {
+    fn from(val: &str) -> Self {
+        LoadArgs::new(val.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burn::{
+        backend::NdArray,
+        module::Module,
+        nn::{
+            conv::{Conv1d, Conv1dConfig},
+            LayerNorm, LayerNormConfig, Linear, LinearConfig,
+        },
+        record::FullPrecisionSettings,
+        tensor::{Tensor, TensorData},
+    };
+    use half::f16;
+    use rmpv::Value;
+
+    type TestBackend = NdArray<f32>;
+
+    #[derive(Module, Debug)]
+    struct Model<B: Backend> {
+        fc: Linear<B>,
+        conv: Conv1d<B>,
+        norm: LayerNorm<B>,
+    }
+
+    /// Encodes a numpy array the way Flax does.
+    fn ndarray(shape: &[u64], dtype: &str, bytes: Vec<u8>) -> Value {
+        let shape = shape.iter().map(|dim| Value::from(*dim)).collect();
+        let value = Value::Array(vec![
+            Value::Array(shape),
+            Value::from(dtype),
+            Value::Binary(bytes),
+        ]);
+        let mut data = Vec::new();
+        rmpv::encode::write_value(&mut data, &value).unwrap();
+
+        Value::Ext(1, data)
+    }
+
+    fn f32_array(shape: &[u64], values: &[f32]) -> Value {
+        let bytes = values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        ndarray(shape, "float32", bytes)
+    }
+
+    fn map(entries: Vec<(&str, Value)>) -> Value {
+        Value::Map(
+            entries
+                .into_iter()
+                .map(|(key, value)| (Value::from(key), value))
+                .collect(),
+        )
+    }
+
+    /// Writes the train state of a model with a `Dense`, a `Conv` and a `LayerNorm`.
+    fn write_checkpoint(name: &str) -> PathBuf {
+        let kernel = [0.0f32, 1.0, 2.0, 3.0, 4.0, 5.0]
+            .iter()
+            .flat_map(|value| f16::from_f32(*value).to_le_bytes())
+            .collect();
+        let params = map(vec![
+            (
+                "Dense_0",
+                map(vec![
+                    ("kernel", ndarray(&[2, 3], "float16", kernel)),
+                    ("bias", f32_array(&[3], &[0.1, 0.2, 0.3])),
+                ]),
+            ),
+            (
+                "Conv_0",
+                map(vec![
+                    ("kernel", f32_array(&[2, 1, 2], &[0.0, 1.0, 2.0, 3.0])),
+                    ("bias", f32_array(&[2], &[-1.0, 1.0])),
+                ]),
+            ),
+            (
+                "LayerNorm_0",
+                map(vec![
+                    ("scale", f32_array(&[3], &[2.0, 2.0, 2.0])),
+                    ("bias", f32_array(&[3], &[0.5, 0.5, 0.5])),
+                ]),
+            ),
+        ]);
+        let state = map(vec![(
+            "target",
+            map(vec![("params", params), ("step", Value::from(100))]),
+        )]);
+
+        let path = std::env::temp_dir().join(name);
+        let mut bytes = Vec::new();
+        rmpv::encode::write_value(&mut bytes, &state).unwrap();
+        std::fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    #[test]
+    fn should_load_a_flax_train_state() {
+        let path = write_checkpoint("burn_import_flax_train_state.msgpack");
+        let device = Default::default();
+        let args = LoadArgs::new(path)
+            .with_top_level_key("target")
+            .with_key_remap("^Dense_0", "fc")
+            .with_key_remap("^Conv_0", "conv")
+            .with_key_remap("^LayerNorm_0", "norm");
+
+        let record = FlaxFileRecorder::<FullPrecisionSettings>::default()
+            .load(args, &device)
+            .expect("Should load the record");
+        let model = Model::<TestBackend> {
+            fc: LinearConfig::new(2, 3).init(&device),
+            conv: Conv1dConfig::new(1, 2, 2).init(&device),
+            norm: LayerNormConfig::new(3).init(&device),
+        }
+        .load_record(record);
+
+        let data = |tensor: Tensor<TestBackend, 1>| tensor.into_data();
+        model.fc.weight.val().into_data().assert_eq(
+            &TensorData::from([[0.0f32, 1.0, 2.0], [3.0, 4.0, 5.0]]),
+            true,
+        );
+        data(model.fc.bias.unwrap().val())
+            .assert_approx_eq(&TensorData::from([0.1f32, 0.2, 0.3]), 6);
+        // [kernel_size, in, out] to [out, in, kernel_size]
+        model
+            .conv
+            .weight
+            .val()
+            .into_data()
+            .assert_eq(&TensorData::from([[[0.0f32, 2.0]], [[1.0, 3.0]]]), true);
+        data(model.conv.bias.unwrap().val()).assert_eq(&TensorData::from([-1.0f32, 1.0]), true);
+        data(model.norm.gamma.val()).assert_eq(&TensorData::from([2.0f32, 2.0, 2.0]), true);
+        data(model.norm.beta.val()).assert_eq(&TensorData::from([0.5f32, 0.5, 0.5]), true);
+    }
+
+    #[test]
+    fn should_fail_on_missing_top_level_key() {
+        let path = write_checkpoint("burn_import_flax_missing_key.msgpack");
+        let device = Default::default();
+        let args = LoadArgs::new(path).with_top_level_key("state");
+
+        let result: Result<ModelRecord<TestBackend>, _> =
+            FlaxFileRecorder::<FullPrecisionSettings>::default().load(args, &device);
+
+        assert!(result.is_err());
+    }
+}
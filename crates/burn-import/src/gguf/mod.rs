@@ -0,0 +1,5 @@
+mod error;
+mod reader;
+mod recorder;
+pub use reader::config_from_file;
+pub use recorder::{GgufFileRecorder, LoadArgs};
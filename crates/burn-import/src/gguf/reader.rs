@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use super::error::Error;
+use crate::pytorch::{serialize_tensor_data, PyTorchAdapter};
+
+use burn::{
+    module::ParamId,
+    record::PrecisionSettings,
+    tensor::{ElementConversion, TensorData},
+};
+use burn::{
+    record::serde::{
+        adapter::DefaultAdapter,
+        data::{remap, unflatten, NestedValue, Serializable},
+        de::Deserializer,
+        error,
+        ser::Serializer,
+    },
+    tensor::backend::Backend,
+};
+
+use candle_core::{
+    quantized::{gguf_file, QTensor},
+    DType, Device,
+};
+use regex::Regex;
+use serde::de::DeserializeOwned;
+
+/// Deserializes a GGUF file.
+///
+/// # Arguments
+///
+/// * `path` - A string slice that holds the path of the file to read.
+/// * `key_remap` - A vector of tuples containing a regular expression and a replacement string.
+/// * `debug` - Whether to print the keys, shapes and formats of the tensors.
+pub fn from_file<PS, D, B>(
+    path: &Path,
+    key_remap: Vec<(Regex, String)>,
+    debug: bool,
+) -> Result<D, Error>
+where
+    D: DeserializeOwned,
+    PS: PrecisionSettings,
+    B: Backend,
+{
+    let mut reader = BufReader::new(File::open(path)?);
+    let content = gguf_file::Content::read(&mut reader)?;
+
+    let rotary_heads = rotary_heads(&content);
+
+    // Read the tensors in their GGUF format, they are only dequantized when serialized
+    let tensors = content
+        .tensor_infos
+        .keys()
+        .map(|name| {
+            let tensor = content.tensor(&mut reader, name, &Device::Cpu)?;
+            let num_heads =
+                rotary_heads.and_then(|(query_heads, key_heads)| match name.rsplit('.').nth(1) {
+                    Some("attn_q") => Some(query_heads),
+                    Some("attn_k") => Some(key_heads),
+                    _ => None,
+                });
+            Ok((name.clone(), GgufTensor::new(tensor, num_heads)))
+        })
+        .collect::<Result<HashMap<_, _>, Error>>()?;
+
+    // Remap the keys (replace the keys in the map with the new keys)
+    let (tensors, remapped_keys) = remap(tensors, key_remap);
+
+    // Print the remapped keys if debug is enabled
+    if debug {
+        let mut remapped_keys = remapped_keys;
+        remapped_keys.sort();
+        println!("Debug information of keys and tensor shapes:\n---");
+        for (new_key, old_key) in remapped_keys {
+            if old_key != new_key {
+                println!("Original Key: {old_key}");
+                println!("Remapped Key: {new_key}");
+            } else {
+                println!("Key: {}", new_key);
+            }
+
+            let tensor = &tensors[&new_key].tensor;
+            println!("Shape: {:?}", tensor.shape());
+            println!("Format: {:?}", tensor.dtype());
+            println!("---");
+        }
+    }
+
+    // Convert the GGUF tensors to a nested value data structure
+    let nested_value = unflatten::<PS, _>(tensors)?;
+
+    // The tensors of the GGUF files converted from PyTorch keep the PyTorch layout
+    let deserializer = Deserializer::<PyTorchAdapter<PS, B>>::new(nested_value, true);
+
+    // Deserialize the nested value into a record type
+    let value = D::deserialize(deserializer)?;
+    Ok(value)
+}
+
+/// Deserialize config values from the metadata of a GGUF file.
+///
+/// The metadata keys are namespaced by the architecture of the model, e.g.
+/// `llama.embedding_length`.
+///
+/// # Arguments
+///
+/// * `path` - The path to the `.gguf` file.
+/// * `prefix` - Optional namespace of the keys to retrieve, e.g. `llama`, which is removed from
+///              the keys.
+pub fn config_from_file<D, P>(path: P, prefix: Option<&str>) -> Result<D, Error>
+where
+    D: DeserializeOwned,
+    P: AsRef<Path>,
+{
+    let mut reader = BufReader::new(File::open(path)?);
+    let content = gguf_file::Content::read(&mut reader)?;
+
+    let map = content
+        .metadata
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let key = match prefix {
+                Some(prefix) => key.strip_prefix(prefix)?.strip_prefix('.')?.to_string(),
+                None => key,
+            };
+            Some((key, to_nested_value(value)))
+        })
+        .collect::<HashMap<_, _>>();
+
+    // Create a deserializer with the default adapter and nested value
+    let deserializer = Deserializer::<DefaultAdapter>::new(NestedValue::Map(map), true);
+
+    // Deserialize the nested value into a target type
+    let value = D::deserialize(deserializer)?;
+    Ok(value)
+}
+
+/// Convert a GGUF metadata value to a nested value recursively.
+fn to_nested_value(value: gguf_file::Value) -> NestedValue {
+    match value {
+        gguf_file::Value::U8(v) => NestedValue::I64(v.into()),
+        gguf_file::Value::I8(v) => NestedValue::I64(v.into()),
+        gguf_file::Value::U16(v) => NestedValue::I64(v.into()),
+        gguf_file::Value::I16(v) => NestedValue::I64(v.into()),
+        gguf_file::Value::U32(v) => NestedValue::I64(v.into()),
+        gguf_file::Value::I32(v) => NestedValue::I64(v.into()),
+        gguf_file::Value::U64(v) => NestedValue::U64(v),
+        gguf_file::Value::I64(v) => NestedValue::I64(v),
+        gguf_file::Value::F32(v) => NestedValue::F32(v),
+        gguf_file::Value::F64(v) => NestedValue::F64(v),
+        gguf_file::Value::Bool(v) => NestedValue::Bool(v),
+        gguf_file::Value::String(v) => NestedValue::String(v),
+        gguf_file::Value::Array(v) => {
+            NestedValue::Vec(v.into_iter().map(to_nested_value).collect())
+        }
+    }
+}
+
+/// The number of query and key heads of a llama model, whose query and key weights are permuted.
+///
+/// The llama.cpp conversion interleaves the two halves of the rotary dimensions of each head in
+/// the rows of the `attn_q` and `attn_k` weights. The permutation is undone when the tensors are
+/// read, so that they have the layout of the original checkpoint.
+fn rotary_heads(content: &gguf_file::Content) -> Option<(usize, usize)> {
+    let metadata = |key: &str| content.metadata.get(key);
+
+    match metadata("general.architecture")?.to_string().ok()?.as_str() {
+        "llama" => {
+            let query_heads = metadata("llama.attention.head_count")?.to_u32().ok()? as usize;
+            let key_heads = metadata("llama.attention.head_count_kv")
+                .and_then(|value| value.to_u32().ok())
+                .map_or(query_heads, |heads| heads as usize);
+
+            Some((query_heads, key_heads))
+        }
+        _ => None,
+    }
+}
+
+/// Undoes the rotary permutation of the rows of a query or key weight, in place.
+///
+/// The row `2 * i + j` of a head is moved back to the row `j * head_dim / 2 + i`, where `i` is
+/// the index of the rotary pair and `j` the index in the pair.
+fn unpermute_rotary(values: &mut [f32], shape: &[usize], num_heads: usize) {
+    let num_rows = shape[0];
+    let row_size = values.len() / num_rows;
+    let half_head_dim = num_rows / num_heads / 2;
+    let permuted = values.to_vec();
+
+    for head in 0..num_heads {
+        for i in 0..half_head_dim {
+            for j in 0..2 {
+                let source = (head * half_head_dim + i) * 2 + j;
+                let target = head * half_head_dim * 2 + j * half_head_dim + i;
+
+                values[target * row_size..(target + 1) * row_size]
+                    .copy_from_slice(&permuted[source * row_size..(source + 1) * row_size]);
+            }
+        }
+    }
+}
+
+/// A tensor of a GGUF file, in its GGUF format.
+#[derive(new)]
+struct GgufTensor {
+    tensor: QTensor,
+    /// The number of heads of a query or key weight whose rotary permutation is undone.
+    num_heads: Option<usize>,
+}
+
+/// Serializes a GGUF tensor, dequantized to `FloatElem` values.
+///
+/// The quantized formats of GGUF (e.g. `Q8_0` or `Q4_K`) use a scale per block of values, which
+/// the quantization of Burn does not support, so the values are not kept quantized.
+impl Serializable for GgufTensor {
+    fn serialize<PS>(&self, serializer: Serializer) -> Result<NestedValue, error::Error>
+    where
+        PS: PrecisionSettings,
+    {
+        let shape = self.tensor.shape().dims().to_vec();
+        let mut values = self
+            .tensor
+            .dequantize(&Device::Cpu)
+            .and_then(|tensor| tensor.flatten_all()?.to_dtype(DType::F32)?.to_vec1::<f32>())
+            .map_err(|err| error::Error::Other(format!("Candle dequantize error: {err}")))?;
+
+        if let Some(num_heads) = self.num_heads {
+            if shape[0] % (num_heads * 2) != 0 {
+                return Err(error::Error::Other(format!(
+                    "The {} rows of the query or key weight are not divisible in {num_heads} heads",
+                    shape[0]
+                )));
+            }
+            unpermute_rotary(&mut values, &shape, num_heads);
+        }
+
+        let values: Vec<PS::FloatElem> = values.into_iter().map(ElementConversion::elem).collect();
+
+        serialize_tensor_data(TensorData::new(values, shape), ParamId::new(), serializer)
+    }
+}
@@ -0,0 +1,347 @@
+use core::marker::PhantomData;
+use std::path::PathBuf;
+
+use burn::{
+    record::{PrecisionSettings, Record, Recorder, RecorderError},
+    tensor::backend::Backend,
+};
+
+use regex::Regex;
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::reader::from_file;
+
+/// A recorder that loads GGUF files (`.gguf`), as produced by the llama.cpp ecosystem, into Burn
+/// modules.
+///
+/// The quantized tensors (e.g. `Q8_0` or `Q4_K`) are dequantized to the float type of the
+/// precision settings, since Burn does not support their scales per block. The query and key
+/// weights of the llama models have the layout of the original checkpoint, their rotary
+/// permutation by the llama.cpp conversion is undone.
+///
+/// LoadArgs can be used to remap keys.
+/// See [LoadArgs](struct.LoadArgs.html) for more information.
+#[derive(new, Debug, Default, Clone)]
+pub struct GgufFileRecorder<PS: PrecisionSettings> {
+    _settings: PhantomData<PS>,
+}
+
+impl<PS: PrecisionSettings, B: Backend> Recorder<B> for GgufFileRecorder<PS> {
+    type Settings = PS;
+    type RecordArgs = PathBuf;
+    type RecordOutput = ();
+    type LoadArgs = LoadArgs;
+
+    fn save_item<I: Serialize>(
+        &self,
+        _item: I,
+        _file: Self::RecordArgs,
+    ) -> Result<(), RecorderError> {
+        unimplemented!("save_item not implemented for GgufFileRecorder")
+    }
+
+    fn load_item<I: DeserializeOwned>(&self, _file: Self::LoadArgs) -> Result<I, RecorderError> {
+        unimplemented!("load_item not implemented for GgufFileRecorder")
+    }
+
+    fn load<R: Record<B>>(
+        &self,
+        args: Self::LoadArgs,
+        device: &B::Device,
+    ) -> Result<R, RecorderError> {
+        let item =
+            from_file::<PS, R::Item<Self::Settings>, B>(&args.file, args.key_remap, args.debug)?;
+        Ok(R::from_item(item, device))
+    }
+}
+
+/// Arguments for loading a GGUF file.
+///
+/// # Fields
+///
+/// * `file` - The path to the file to load.
+/// * `key_remap` - A vector of tuples containing a regular expression and a replacement string.
+///                See [regex::Regex::replace](https://docs.rs/regex/latest/regex/struct.Regex.html#method.replace)
+///                for more information.
+///
+/// # Examples
+///
+/// ```text
+/// use burn_import::gguf::{GgufFileRecorder, LoadArgs};
+/// use burn::record::FullPrecisionSettings;
+/// use burn::record::Recorder;
+///
+/// // Map the llama.cpp names to the module names, e.g. "blk.0.attn_q" -> "layers.0.attention.wq"
+/// let args = LoadArgs::new("llama.gguf".into())
+///     .with_key_remap("blk\\.([0-9]+)\\.attn_q", "layers.$1.attention.wq")
+///     .with_key_remap("token_embd", "embedding");
+///
+/// let record = GgufFileRecorder::<FullPrecisionSettings>::default()
+///     .load(args, &device)
+///     .expect("Should decode state successfully");
+/// ```
+#[derive(Debug, Clone)]
+pub struct LoadArgs {
+    /// The path to the file to load.
+    pub file: PathBuf,
+
+    /// A list of key remappings.
+    pub key_remap: Vec<(Regex, String)>,
+
+    /// Whether to print debug information.
+    pub debug: bool,
+}
+
+impl LoadArgs {
+    /// Creates a new `LoadArgs` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The path to the file to load.
+    pub fn new(file: PathBuf) -> Self {
+        Self {
+            file,
+            key_remap: Vec::new(),
+            debug: false,
+        }
+    }
+
+    /// Sets key remapping.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The Regex pattern to be replaced.
+    /// * `replacement` - The pattern to replace with.
+    ///
+    /// See [Regex](https://docs.rs/regex/1.5.4/regex/#syntax) for the pattern syntax and
+    /// [Replacement](https://docs.rs/regex/latest/regex/struct.Regex.html#method.replace) for the
+    /// replacement syntax.
+    pub fn with_key_remap(mut self, pattern: &str, replacement: &str) -> Self {
+        let regex = Regex::new(pattern).expect("Valid regex");
+
+        self.key_remap.push((regex, replacement.into()));
+        self
+    }
+
+    /// Sets printing debug information on.
+    pub fn with_debug_print(mut self) -> Self {
+        self.debug = true;
+        self
+    }
+}
+
+impl From<PathBuf> for LoadArgs {
+    fn from(val: PathBuf) -> Self {
+        LoadArgs::new(val)
+    }
+}
+
+impl From<String> for LoadArgs {
+    fn from(val: String) -> Self {
+        LoadArgs::new(val.into())
+    }
+}
+
+impl From<&str> for LoadArgs {
+    fn from(val: &str) -> Self {
+        LoadArgs::new(val.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burn::{
+        backend::NdArray,
+        module::{Module, Param},
+        record::FullPrecisionSettings,
+        tensor::Tensor,
+    };
+    use candle_core::{
+        quantized::{gguf_file, GgmlDType, QTensor},
+        Device,
+    };
+
+    type TestBackend = NdArray<f32>;
+
+    #[derive(Module, Debug)]
+    struct Model<B: Backend> {
+        weight: Param<Tensor<B, 2>>,
+    }
+
+    #[derive(Module, Debug)]
+    struct Attention<B: Backend> {
+        wq: Param<Tensor<B, 2>>,
+        wk: Param<Tensor<B, 2>>,
+    }
+
+    /// Writes the tensors and the metadata to a GGUF file.
+    fn write_gguf(
+        name: &str,
+        metadata: &[(&str, gguf_file::Value)],
+        tensors: &[(&str, QTensor)],
+    ) -> PathBuf {
+        let path = std::env::temp_dir().join(name);
+        let mut file = std::fs::File::create(&path).unwrap();
+        let metadata = metadata
+            .iter()
+            .map(|(key, value)| (*key, value))
+            .collect::<Vec<_>>();
+        let tensors = tensors
+            .iter()
+            .map(|(key, tensor)| (*key, tensor))
+            .collect::<Vec<_>>();
+
+        gguf_file::write(&mut file, &metadata, &tensors).unwrap();
+        path
+    }
+
+    fn quantize(values: Vec<f32>, shape: [usize; 2], dtype: GgmlDType) -> QTensor {
+        let tensor = candle_core::Tensor::from_vec(values, &shape, &Device::Cpu).unwrap();
+        QTensor::quantize(&tensor, dtype).unwrap()
+    }
+
+    fn values(param: Param<Tensor<TestBackend, 2>>) -> Vec<f32> {
+        param.val().into_data().to_vec().unwrap()
+    }
+
+    #[test]
+    fn should_dequantize_each_format() {
+        // The k-quants have blocks of 256 values
+        let shape = [2, 256];
+        let range = 4.0;
+        let source = (0..512)
+            .map(|i| (i as f32 * 0.37).sin() * 2.0)
+            .collect::<Vec<_>>();
+
+        // The maximum error of the format, relatively to the range of the values
+        let formats = [
+            (GgmlDType::F32, 0.0),
+            (GgmlDType::F16, 0.001),
+            (GgmlDType::Q4_0, 0.15),
+            (GgmlDType::Q4_1, 0.15),
+            (GgmlDType::Q5_0, 0.08),
+            (GgmlDType::Q5_1, 0.08),
+            (GgmlDType::Q8_0, 0.01),
+            (GgmlDType::Q2K, 0.5),
+            (GgmlDType::Q3K, 0.3),
+            (GgmlDType::Q4K, 0.15),
+            (GgmlDType::Q5K, 0.08),
+            (GgmlDType::Q6K, 0.04),
+        ];
+
+        for (dtype, tolerance) in formats {
+            let tensor = quantize(source.clone(), shape, dtype);
+            let expected = tensor
+                .dequantize(&Device::Cpu)
+                .unwrap()
+                .flatten_all()
+                .unwrap()
+                .to_vec1::<f32>()
+                .unwrap();
+            let path = write_gguf(
+                &format!("burn_import_gguf_{dtype:?}.gguf"),
+                &[],
+                &[("weight", tensor)],
+            );
+
+            let device = Default::default();
+            let record = GgufFileRecorder::<FullPrecisionSettings>::default()
+                .load(LoadArgs::new(path), &device)
+                .expect("Should load the record");
+            let model = Model {
+                weight: Param::from_tensor(Tensor::zeros(shape, &device)),
+            }
+            .load_record(record);
+            let output = values(model.weight);
+
+            // The values are the dequantized values of the format
+            assert_eq!(output, expected, "{dtype:?}");
+            for (value, source) in output.iter().zip(source.iter()) {
+                assert!(
+                    (value - source).abs() <= tolerance * range,
+                    "{dtype:?}: {value} != {source}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn should_undo_the_rotary_permutation_of_llama() {
+        // 2 query heads and 1 key head of dimension 4, with a row per value
+        let rows = |count: usize| {
+            let values = (0..count).map(|i| i as f32).collect::<Vec<_>>();
+            quantize(values, [count, 1], GgmlDType::F32)
+        };
+        let metadata = |architecture: &str| {
+            vec![
+                (
+                    "general.architecture",
+                    gguf_file::Value::String(architecture.to_string()),
+                ),
+                ("llama.attention.head_count", gguf_file::Value::U32(2)),
+                ("llama.attention.head_count_kv", gguf_file::Value::U32(1)),
+            ]
+        };
+        let load = |architecture: &str| {
+            let path = write_gguf(
+                &format!("burn_import_gguf_rotary_{architecture}.gguf"),
+                &metadata(architecture),
+                &[
+                    ("blk.0.attn_q.weight", rows(8)),
+                    ("blk.0.attn_k.weight", rows(4)),
+                ],
+            );
+            let args = LoadArgs::new(path)
+                .with_key_remap("blk\\.0\\.attn_q\\.weight", "wq")
+                .with_key_remap("blk\\.0\\.attn_k\\.weight", "wk");
+
+            let device = Default::default();
+            let record = GgufFileRecorder::<FullPrecisionSettings>::default()
+                .load(args, &device)
+                .expect("Should load the record");
+            Attention {
+                wq: Param::from_tensor(Tensor::zeros([8, 1], &device)),
+                wk: Param::from_tensor(Tensor::zeros([4, 1], &device)),
+            }
+            .load_record(record)
+        };
+
+        let llama = load("llama");
+        let other = load("gpt2");
+
+        // The rotary pairs (2 * i, 2 * i + 1) of each head are split in two halves
+        assert_eq!(
+            values(llama.wq),
+            vec![0.0, 2.0, 1.0, 3.0, 4.0, 6.0, 5.0, 7.0]
+        );
+        assert_eq!(values(llama.wk), vec![0.0, 2.0, 1.0, 3.0]);
+        assert_eq!(
+            values(other.wq),
+            vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]
+        );
+    }
+
+    #[test]
+    fn should_read_the_config_from_the_metadata() {
+        #[derive(serde::Deserialize)]
+        struct Config {
+            embedding_length: i64,
+            vocab_size: Option<i64>,
+        }
+
+        let path = write_gguf(
+            "burn_import_gguf_config.gguf",
+            &[
+                ("llama.embedding_length", gguf_file::Value::U32(4096)),
+                ("general.name", gguf_file::Value::String("llama".into())),
+            ],
+            &[("weight", quantize(vec![0.0; 2], [1, 2], GgmlDType::F32))],
+        );
+
+        let config: Config = crate::gguf::config_from_file(path, Some("llama")).unwrap();
+
+        assert_eq!(config.embedding_length, 4096);
+        assert_eq!(config.vocab_size, None);
+    }
+}
@@ -0,0 +1,4 @@
+mod error;
+mod reader;
+mod recorder;
+pub use recorder::{KerasFileRecorder, LoadArgs};
@@ -0,0 +1,251 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::error::Error;
+use crate::pytorch::serialize_tensor_data;
+
+use burn::record::serde::{
+    adapter::DefaultAdapter,
+    data::{remap, unflatten, NestedValue, Serializable},
+    de::Deserializer,
+    error,
+    ser::Serializer,
+};
+use burn::{
+    module::ParamId,
+    record::PrecisionSettings,
+    tensor::{ElementConversion, TensorData},
+};
+
+use hdf5::Group;
+use regex::Regex;
+use serde::de::DeserializeOwned;
+
+/// The names of the Keras weights which differ from the names of the Burn parameters.
+const WEIGHT_NAMES: [(&str, &str); 4] = [
+    ("kernel", "weight"),
+    ("embeddings", "weight"),
+    ("moving_mean", "running_mean"),
+    ("moving_variance", "running_var"),
+];
+
+/// Deserializes a Keras HDF5 file.
+///
+/// # Arguments
+///
+/// * `path` - A string slice that holds the path of the file to read.
+/// * `key_remap` - A vector of tuples containing a regular expression and a replacement string.
+/// * `debug` - Whether to print the keys and the shapes of the tensors.
+pub fn from_file<PS, D>(
+    path: &Path,
+    key_remap: Vec<(Regex, String)>,
+    debug: bool,
+) -> Result<D, Error>
+where
+    D: DeserializeOwned,
+    PS: PrecisionSettings,
+{
+    let file = hdf5::File::open(path)?;
+
+    // The models saved with `model.save` keep their weights in a group, next to their config
+    let root = match file.link_exists("model_weights") {
+        true => file.group("model_weights")?,
+        false => file.group("/")?,
+    };
+
+    let mut weights = HashMap::new();
+    read_weights(&root, "", &mut weights)?;
+
+    // Convert the paths of the weights to the keys of the Burn parameters
+    let tensors = weights
+        .into_iter()
+        .map(|(path, tensor)| {
+            let (key, is_kernel) = burn_key(&path);
+            let tensor = match is_kernel {
+                true => tensor.into_channels_first(),
+                false => tensor,
+            };
+            (key, tensor)
+        })
+        .collect::<HashMap<_, _>>();
+
+    // Remap the keys (replace the keys in the map with the new keys)
+    let (tensors, remapped_keys) = remap(tensors, key_remap);
+
+    // Print the remapped keys if debug is enabled
+    if debug {
+        let mut remapped_keys = remapped_keys;
+        remapped_keys.sort();
+        println!("Debug information of keys and tensor shapes:\n---");
+        for (new_key, old_key) in remapped_keys {
+            if old_key != new_key {
+                println!("Original Key: {old_key}");
+                println!("Remapped Key: {new_key}");
+            } else {
+                println!("Key: {}", new_key);
+            }
+
+            println!("Shape: {:?}", tensors[&new_key].shape);
+            println!("---");
+        }
+    }
+
+    // Convert the Keras tensors to a nested value data structure
+    let nested_value = unflatten::<PS, _>(tensors)?;
+
+    // The Keras layouts are converted to the Burn layouts when the tensors are read
+    let deserializer = Deserializer::<DefaultAdapter>::new(nested_value, true);
+
+    // Deserialize the nested value into a record type
+    let value = D::deserialize(deserializer)?;
+    Ok(value)
+}
+
+/// Reads the datasets of a group recursively, by path.
+fn read_weights(
+    group: &Group,
+    prefix: &str,
+    weights: &mut HashMap<String, KerasTensor>,
+) -> Result<(), Error> {
+    for name in group.member_names()? {
+        let path = match prefix.is_empty() {
+            true => name.clone(),
+            false => format!("{prefix}/{name}"),
+        };
+
+        match group.dataset(&name) {
+            Ok(dataset) => {
+                let tensor = KerasTensor::new(dataset.shape(), dataset.read_raw::<f32>()?);
+                weights.insert(path, tensor);
+            }
+            Err(_) => read_weights(&group.group(&name)?, &path, weights)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// The key of the Burn parameter of a Keras weight, and whether the weight is a kernel whose
+/// layout differs.
+///
+/// The weights are stored by layer, under the scope of the layer, e.g. `dense_1/dense_1/kernel:0`
+/// for the `weight` of the `dense_1` layer.
+fn burn_key(path: &str) -> (String, bool) {
+    let path = path.split(':').next().unwrap();
+
+    let mut names = Vec::<&str>::new();
+    for name in path.split('/') {
+        if names.last() != Some(&name) {
+            names.push(name);
+        }
+    }
+
+    let is_kernel = names.last() == Some(&"kernel");
+    if let Some(last) = names.last_mut() {
+        if let Some((_, burn_name)) = WEIGHT_NAMES.iter().find(|(name, _)| *name == *last) {
+            *last = burn_name;
+        }
+    }
+
+    (names.join("."), is_kernel)
+}
+
+/// A weight of a Keras file.
+#[derive(new)]
+struct KerasTensor {
+    shape: Vec<usize>,
+    values: Vec<f32>,
+}
+
+impl KerasTensor {
+    /// Converts the layout of a convolution kernel, `[spatial..., in, out]`, to the layout of
+    /// Burn, `[out, in, spatial...]`.
+    ///
+    /// The dense kernels, `[in, out]`, already have the layout of Burn.
+    fn into_channels_first(self) -> Self {
+        let rank = self.shape.len();
+        if rank < 3 {
+            return self;
+        }
+
+        let axes = [rank - 1, rank - 2]
+            .into_iter()
+            .chain(0..rank - 2)
+            .collect::<Vec<_>>();
+        let shape = axes
+            .iter()
+            .map(|axis| self.shape[*axis])
+            .collect::<Vec<_>>();
+
+        let mut strides = vec![1; rank];
+        for axis in (0..rank - 1).rev() {
+            strides[axis] = strides[axis + 1] * self.shape[axis + 1];
+        }
+
+        let mut values = Vec::with_capacity(self.values.len());
+        let mut index = vec![0; rank];
+        for _ in 0..self.values.len() {
+            let offset = axes
+                .iter()
+                .zip(index.iter())
+                .map(|(axis, i)| strides[*axis] * i)
+                .sum::<usize>();
+            values.push(self.values[offset]);
+
+            // Increment the index in the new layout
+            for axis in (0..rank).rev() {
+                index[axis] += 1;
+                if index[axis] < shape[axis] {
+                    break;
+                }
+                index[axis] = 0;
+            }
+        }
+
+        Self { shape, values }
+    }
+}
+
+/// Serializes a Keras tensor as a `FloatElem` tensor.
+impl Serializable for KerasTensor {
+    fn serialize<PS>(&self, serializer: Serializer) -> Result<NestedValue, error::Error>
+    where
+        PS: PrecisionSettings,
+    {
+        let values: Vec<PS::FloatElem> = self.values.iter().map(|x| x.elem()).collect();
+
+        serialize_tensor_data(
+            TensorData::new(values, self.shape.clone()),
+            ParamId::new(),
+            serializer,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_convert_keras_paths_to_burn_keys() {
+        assert_eq!(
+            burn_key("dense_1/dense_1/kernel:0"),
+            ("dense_1.weight".to_string(), true)
+        );
+        assert_eq!(
+            burn_key("sequential/sequential/batch_norm/moving_variance:0"),
+            ("sequential.batch_norm.running_var".to_string(), false)
+        );
+    }
+
+    #[test]
+    fn should_convert_conv_kernel_to_channels_first() {
+        // [kernel_size, in, out] = [2, 1, 3]
+        let kernel = KerasTensor::new(vec![2, 1, 3], vec![0., 1., 2., 3., 4., 5.]);
+
+        let kernel = kernel.into_channels_first();
+
+        assert_eq!(kernel.shape, vec![3, 1, 2]);
+        assert_eq!(kernel.values, vec![0., 3., 1., 4., 2., 5.]);
+    }
+}
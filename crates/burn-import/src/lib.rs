@@ -30,5 +30,25 @@ pub mod burn;
 #[cfg(feature = "pytorch")]
 pub mod pytorch;
 
+/// The GGUF module for recorder.
+#[cfg(feature = "gguf")]
+pub mod gguf;
+
+/// The safetensors module for recorder.
+#[cfg(feature = "safetensors")]
+pub mod safetensors;
+
+/// The Flax module for recorder.
+#[cfg(feature = "flax")]
+pub mod flax;
+
+/// The Keras module for recorder.
+#[cfg(feature = "keras")]
+pub mod keras;
+
+// The TorchScript import, used by the ONNX model generation.
+#[cfg(feature = "torchscript")]
+mod torchscript;
+
 mod formatter;
 pub use formatter::*;
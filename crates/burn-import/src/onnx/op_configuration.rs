@@ -7,9 +7,18 @@ use burn::nn::{
     BatchNormConfig, DropoutConfig, LayerNormConfig, LinearConfig, PaddingConfig1d,
     PaddingConfig2d, PaddingConfig3d,
 };
+use burn::tensor::ScatterReduction;
 
 use crate::burn::node::{
-    expand::ExpandShape, pad::PadConfig, tile::TileConfig, trilu::TriluConfig,
+    einsum::EinsumConfig,
+    expand::ExpandShape,
+    non_max_suppression::NonMaxSuppressionConfig,
+    pad::PadConfig,
+    resize::ResizeSampling,
+    rnn::{RnnConfig, RnnDirection},
+    tile::TileConfig,
+    topk::{TopKConfig, TopKSize},
+    trilu::TriluConfig,
 };
 use onnx_ir::ir::{ArgType, AttributeValue, Data, ElementType, Node};
 
@@ -43,7 +52,7 @@ pub fn conv1d_config(curr: &Node) -> Conv1dConfig {
     }
 
     // the channels are inverted in the weight tensor
-    let shape = weight.shape.clone().unwrap();
+    let shape = weight.static_shape().unwrap();
     let channels_in = shape[1] * group;
     let channels_out = shape[0];
 
@@ -86,7 +95,7 @@ pub fn conv2d_config(curr: &Node) -> Conv2dConfig {
     }
 
     // the channels are inverted in the weight tensor
-    let shape = weight.shape.clone().unwrap();
+    let shape = weight.static_shape().unwrap();
     let channels: [usize; 2] = [shape[1] * group, shape[0]];
 
     let padding = padding_config_2d(&pads);
@@ -131,7 +140,7 @@ pub fn conv3d_config(curr: &Node) -> Conv3dConfig {
     }
 
     // the channels are inverted in the weight tensor
-    let shape = weight.shape.clone().unwrap();
+    let shape = weight.static_shape().unwrap();
     let channels: [usize; 2] = [shape[1] * group, shape[0]];
 
     let padding = padding_config_3d(&pads);
@@ -272,7 +281,7 @@ pub fn conv_transpose1d_config(curr: &Node) -> ConvTranspose1dConfig {
     let bias = curr.inputs.len() == 3;
 
     // Extract channels from the weight tensor shape [out_channels, in_channels]
-    let shape = weight.shape.clone().unwrap();
+    let shape = weight.static_shape().unwrap();
     let channels: [usize; 2] = [shape[1] * group, shape[0]];
 
     // Create the ConvTranspose1d configuration
@@ -334,7 +343,7 @@ pub fn conv_transpose2d_config(curr: &Node) -> ConvTranspose2dConfig {
     let bias = curr.inputs.len() == 3;
 
     // the channels are inverted in the weight tensor
-    let shape = weight.shape.clone().unwrap();
+    let shape = weight.static_shape().unwrap();
     let channels: [usize; 2] = [shape[1] * group, shape[0]];
 
     ConvTranspose2dConfig::new(
@@ -400,7 +409,7 @@ pub fn conv_transpose3d_config(curr: &Node) -> ConvTranspose3dConfig {
     let bias = curr.inputs.len() == 3;
 
     // the channels are inverted in the weight tensor
-    let shape = weight.shape.clone().unwrap();
+    let shape = weight.static_shape().unwrap();
     let channels: [usize; 2] = [shape[1] * group, shape[0]];
 
     ConvTranspose3dConfig::new(
@@ -495,7 +504,7 @@ pub fn expand_config(node: &Node) -> ExpandShape {
         ArgType::Tensor(tensor) => {
             assert_eq!(tensor.dim, 1, "Expand: shape tensor must be 1D");
             assert!(
-                tensor.shape.is_some(),
+                tensor.static_shape().is_some(),
                 "Expand: shape tensor shape must be known!"
             );
             assert!(
@@ -619,7 +628,7 @@ pub fn linear_config(node: &Node) -> LinearConfig {
         );
     }
 
-    let shape = weight.shape.clone().unwrap();
+    let shape = weight.static_shape().unwrap();
     let (in_size, out_size) = (shape[0], shape[1]);
 
     // check if the bias is present
@@ -814,7 +823,7 @@ pub fn batch_norm_config(node: &Node) -> BatchNormConfig {
         panic!("BatchNorm: weight tensor must be present");
     };
 
-    let num_features: usize = tensor_type.shape.clone().unwrap()[0];
+    let num_features: usize = tensor_type.static_shape().unwrap()[0];
 
     let mut epsilon = 0f32;
     let mut momentum = 0f32;
@@ -841,7 +850,7 @@ pub fn layer_norm_config(node: &Node) -> (LayerNormConfig, bool) {
         panic!("LayerNorm: weight tensor must be present");
     };
 
-    let num_features: usize = tensor_type.shape.clone().unwrap()[0];
+    let num_features: usize = tensor_type.static_shape().unwrap()[0];
 
     // When `stash_type` is `1` (default), perform operations in 32-bit float and
     // cast the results back to original dtype
@@ -934,9 +943,15 @@ pub fn pad_config(node: &Node) -> PadConfig {
         };
 
         //TODO : handle more possible attributes
+        // The pads of the older opsets are moved from the attributes to the input
         let mut pads: Vec<usize> = get_pads_input(node)
             .into_iter()
-            .map(|x| x as usize)
+            .map(|x| {
+                if x < 0 {
+                    panic!("Pad: Negative pad is not supported");
+                }
+                x as usize
+            })
             .collect();
 
         for (key, value) in node.attrs.iter() {
@@ -1212,8 +1227,11 @@ pub fn reshape_config(node: &Node) -> Vec<i64> {
     }
 }
 
-pub fn resize_config(node: &Node) -> (String, Vec<f32>, Vec<usize>) {
+pub fn resize_config(node: &Node) -> (String, Vec<f32>, Vec<usize>, ResizeSampling) {
     let mut mode: String = "".to_string();
+    let mut coordinate_transformation_mode = "half_pixel".to_string();
+    let mut nearest_mode = "round_prefer_floor".to_string();
+    let mut cubic_coeff_a = -0.75;
 
     let mut scales: Vec<f32>;
     let mut sizes: Vec<usize>;
@@ -1229,11 +1247,8 @@ pub fn resize_config(node: &Node) -> (String, Vec<f32>, Vec<usize>) {
         panic!("Resize: input must be a tensor")
     };
 
-    // Note: we are ignoring some attributes because results are approximately the same
-    // and we are not supporting all the attributes of the Resize operator.
-    // However, some attributes are important to be checked and we are checking
-    // against the default values of the attributes.
-    // TODO revisit this when we have more Resize operators in the model
+    // Note: we are not supporting all the attributes of the Resize operator,
+    // so the unsupported ones are checked against their default values.
     for (key, value) in node.attrs.iter() {
         match key.as_str() {
             "antialias" => assert_eq!(
@@ -1243,10 +1258,9 @@ pub fn resize_config(node: &Node) -> (String, Vec<f32>, Vec<usize>) {
             ),
             "axes" => panic!("Resize: custom axes attribute is not supported"),
             "coordinate_transformation_mode" => {
-                log::warn!("Resize: coordinate_transformation_mode is ignored")
+                coordinate_transformation_mode = value.clone().into_string().to_lowercase()
             }
-
-            "cubic_coeff_a" => log::warn!("Resize: cubic_coeff_a is ignored"),
+            "cubic_coeff_a" => cubic_coeff_a = value.clone().into_f32(),
             "exclude_outside" => assert_eq!(
                 value.clone().into_i32(),
                 0,
@@ -1265,24 +1279,13 @@ pub fn resize_config(node: &Node) -> (String, Vec<f32>, Vec<usize>) {
                 )
             }
             "mode" => mode = value.clone().into_string().to_lowercase(),
-            "nearest_mode" => log::warn!("Resize: nearest_mode is ignored"),
+            "nearest_mode" => nearest_mode = value.clone().into_string().to_lowercase(),
 
             _ => {}
         }
     }
 
-    let roi: Vec<f32> = node
-        .inputs
-        .get(1)
-        .map(|input| {
-            if let Some(data) = &input.value {
-                data.clone().into_f32s()
-            } else {
-                vec![]
-            }
-        })
-        .unwrap_or_default();
-
+    // The roi input is only used by the tf_crop_and_resize mode, which is not supported
     scales = node
         .inputs
         .get(2)
@@ -1315,8 +1318,14 @@ pub fn resize_config(node: &Node) -> (String, Vec<f32>, Vec<usize>) {
         panic!("Resize: mode attribute is required")
     }
 
-    if !roi.is_empty() {
-        panic!("Resize: roi input is not supported")
+    if !matches!(
+        coordinate_transformation_mode.as_str(),
+        "half_pixel" | "pytorch_half_pixel" | "align_corners" | "asymmetric"
+    ) {
+        panic!(
+            "Resize: coordinate_transformation_mode {} is not supported",
+            coordinate_transformation_mode
+        )
     }
 
     if scales.is_empty() && sizes.is_empty() {
@@ -1337,7 +1346,9 @@ pub fn resize_config(node: &Node) -> (String, Vec<f32>, Vec<usize>) {
         sizes = sizes.iter().skip(2).cloned().collect();
     }
 
-    (mode, scales, sizes)
+    let sampling = ResizeSampling::new(coordinate_transformation_mode, nearest_mode, cubic_coeff_a);
+
+    (mode, scales, sizes, sampling)
 }
 
 //Note this function should only execute if the second input is a constant
@@ -1393,11 +1404,12 @@ pub fn clip_config(node: &Node) -> (Option<f64>, Option<f64>) {
     // For Clip Opset 11+ , the min and max values are inputs
     // Get the min and max values from the input values
     if min_result.is_none() && max_result.is_none() {
-        let min = &node.inputs[1].value;
-        let max = &node.inputs[2].value;
+        // The optional inputs can be omitted, e.g. the max of an upgraded opset 6 node
+        let min = node.inputs.get(1).and_then(|input| input.value.as_ref());
+        let max = node.inputs.get(2).and_then(|input| input.value.as_ref());
 
-        if min_result.is_none() && min.is_some() {
-            let min = min.clone().unwrap().into_scalar();
+        if let Some(min) = min {
+            let min = min.clone().into_scalar();
             min_result = match min {
                 Data::Float16(min) => Some(f32::from(min) as f64),
                 Data::Float32(min) => Some(min as f64),
@@ -1406,8 +1418,8 @@ pub fn clip_config(node: &Node) -> (Option<f64>, Option<f64>) {
             };
         }
 
-        if max_result.is_none() && max.is_some() {
-            let max = max.clone().unwrap().into_scalar();
+        if let Some(max) = max {
+            let max = max.clone().into_scalar();
             max_result = match max {
                 Data::Float16(max) => Some(f32::from(max) as f64),
                 Data::Float32(max) => Some(max as f64),
@@ -1785,7 +1797,7 @@ pub fn transpose_config(curr: &Node) -> Vec<i64> {
 }
 
 pub fn squeeze_config(curr: &Node) -> Vec<i64> {
-    let axes = curr
+    let mut axes = curr
         .attrs
         .iter()
         .filter_map(|(key, value)| {
@@ -1798,6 +1810,11 @@ pub fn squeeze_config(curr: &Node) -> Vec<i64> {
         .next()
         .unwrap_or_else(Vec::new);
 
+    // Since opset 13, the axes are an input
+    if let Some(Data::Int64s(values)) = curr.inputs.get(1).and_then(|input| input.value.as_ref()) {
+        axes.clone_from(values);
+    }
+
     match curr.inputs.first().unwrap().clone().ty {
         ArgType::Tensor(tensor) => tensor,
         _ => panic!("Only tensor input is valid"),
@@ -1805,3 +1822,369 @@ pub fn squeeze_config(curr: &Node) -> Vec<i64> {
 
     axes
 }
+
+/// Create a RnnConfig from the attributes of the ONNX `LSTM` node
+pub fn lstm_config(node: &Node) -> RnnConfig {
+    recurrent_config(node, "LSTM", &["Sigmoid", "Tanh", "Tanh"], true)
+}
+
+/// Create a RnnConfig from the attributes of the ONNX `GRU` node
+pub fn gru_config(node: &Node) -> RnnConfig {
+    // There is no bidirectional GRU module.
+    let config = recurrent_config(node, "GRU", &["Sigmoid", "Tanh"], false);
+
+    let reset_after = node
+        .attrs
+        .get("linear_before_reset")
+        .map(|value| value.clone().into_i64() != 0)
+        .unwrap_or(false);
+
+    config.with_reset_after(reset_after)
+}
+
+fn recurrent_config(node: &Node, op: &str, activations: &[&str], bidirectional: bool) -> RnnConfig {
+    let mut direction = RnnDirection::Forward;
+    let mut batch_first = false;
+    let mut hidden_size = None;
+
+    for (key, value) in node.attrs.iter() {
+        match key.as_str() {
+            "hidden_size" => hidden_size = Some(value.clone().into_i64() as usize),
+            "direction" => {
+                direction = match value.clone().into_string().as_str() {
+                    "forward" => RnnDirection::Forward,
+                    "reverse" => RnnDirection::Reverse,
+                    "bidirectional" if bidirectional => RnnDirection::Bidirectional,
+                    "bidirectional" => panic!("{op}: the bidirectional direction is not supported"),
+                    direction => panic!("{op}: unknown direction {direction}"),
+                }
+            }
+            "layout" => batch_first = value.clone().into_i64() == 1,
+            "activations" => {
+                // The activations are repeated for the reverse direction.
+                let values = value.clone().into_strings();
+                for (index, activation) in values.iter().enumerate() {
+                    if !activation.eq_ignore_ascii_case(activations[index % activations.len()]) {
+                        panic!("{op}: only the default activations are supported (got {values:?})");
+                    }
+                }
+            }
+            "clip" => panic!("{op}: the clip attribute is not supported"),
+            "input_forget" if value.clone().into_i64() != 0 => {
+                panic!("{op}: the input_forget attribute is not supported")
+            }
+            _ => {}
+        }
+    }
+
+    let is_present = |index: usize| {
+        node.inputs
+            .get(index)
+            .is_some_and(|input| !input.name.is_empty())
+    };
+    if is_present(4) {
+        panic!("{op}: the sequence_lens input is not supported");
+    }
+    if is_present(7) {
+        panic!("{op}: the peephole weights are not supported");
+    }
+
+    // The input weights have the shape [num_directions, num_gates * hidden_size, input_size]
+    // and the hidden weights [num_directions, num_gates * hidden_size, hidden_size].
+    let weight_shape = |index: usize| match &node.inputs.get(index).map(|input| &input.ty) {
+        Some(ArgType::Tensor(tensor)) if tensor.static_shape().is_some() => {
+            tensor.static_shape().unwrap()
+        }
+        _ => panic!("{op}: the weight tensors must be present"),
+    };
+    let d_input = weight_shape(1)[2];
+    let d_hidden = hidden_size.unwrap_or_else(|| weight_shape(2)[2]);
+
+    // check if the bias is present
+    let bias = node.inputs.get(3).is_some_and(|bias| bias.value.is_some());
+
+    RnnConfig::new(d_input, d_hidden, bias, direction, batch_first)
+}
+
+/// Create an EinsumConfig from the equation of the node, the ellipsis being expanded into the
+/// labels unused by the equation
+pub fn einsum_config(node: &Node) -> EinsumConfig {
+    let equation = match node.attrs.get("equation") {
+        Some(equation) => equation.clone().into_string().replace(' ', ""),
+        None => panic!("Einsum: the equation is required"),
+    };
+    let (inputs, output) = match equation.split_once("->") {
+        Some((inputs, output)) => (inputs, Some(output)),
+        None => (equation.as_str(), None),
+    };
+
+    let ranks = node
+        .inputs
+        .iter()
+        .map(|input| match &input.ty {
+            ArgType::Tensor(tensor) => tensor.dim,
+            _ => panic!("Einsum: only tensor inputs are valid"),
+        })
+        .collect::<Vec<_>>();
+
+    // The broadcast dimensions are aligned to the right, like the ellipsis of the operands.
+    let ellipsis_dim = |labels: &str, rank: usize| match labels.contains("...") {
+        true => rank + 3 - labels.len(),
+        false => 0,
+    };
+    let num_ellipsis_dims = inputs
+        .split(',')
+        .zip(ranks.iter())
+        .map(|(labels, rank)| ellipsis_dim(labels, *rank))
+        .max()
+        .unwrap_or(0);
+    let ellipsis = ('A'..='Z')
+        .chain('a'..='z')
+        .filter(|label| !equation.contains(*label))
+        .take(num_ellipsis_dims)
+        .collect::<String>();
+    if ellipsis.len() < num_ellipsis_dims {
+        panic!("Einsum: not enough labels to expand the ellipsis of {equation}");
+    }
+
+    let inputs = inputs
+        .split(',')
+        .zip(ranks.iter())
+        .map(|(labels, rank)| {
+            let num_dims = ellipsis_dim(labels, *rank);
+            labels.replace("...", &ellipsis[num_ellipsis_dims - num_dims..])
+        })
+        .collect::<Vec<_>>();
+
+    let output = match output {
+        Some(output) => output.replace("...", &ellipsis),
+        // The implicit output has the labels appearing once in alphabetical order.
+        None => {
+            let labels = inputs.concat();
+            let mut once = labels
+                .chars()
+                .filter(|label| !ellipsis.contains(*label) && labels.matches(*label).count() == 1)
+                .collect::<Vec<_>>();
+            once.sort();
+
+            ellipsis.chars().chain(once).collect()
+        }
+    };
+
+    for (labels, rank) in inputs.iter().zip(ranks) {
+        if labels.len() != rank {
+            panic!("Einsum: the operand {labels} of {equation} should have {rank} dimensions");
+        }
+        if labels
+            .chars()
+            .any(|label| labels.matches(label).count() > 1)
+        {
+            panic!("Einsum: the repeated labels of {equation} are not supported");
+        }
+    }
+    if output.is_empty() {
+        panic!("Einsum: the scalar output of {equation} is not supported");
+    }
+    if output
+        .chars()
+        .any(|label| !inputs.iter().any(|labels| labels.contains(label)))
+    {
+        panic!("Einsum: the output of {equation} has labels missing from the operands");
+    }
+
+    EinsumConfig::new(inputs, output)
+}
+
+pub fn topk_config(node: &Node) -> (TopKConfig, TopKSize) {
+    let rank = match &node.inputs[0].ty {
+        ArgType::Tensor(tensor) => tensor.dim as i64,
+        _ => panic!("TopK: only tensor input is valid"),
+    };
+
+    let mut axis: i64 = -1;
+    let mut largest = true;
+    let mut k = None;
+    for (key, value) in node.attrs.iter() {
+        match key.as_str() {
+            "axis" => axis = value.clone().into_i64(),
+            // Before opset 10, K is an attribute
+            "k" => k = Some(value.clone().into_i64()),
+            "largest" => largest = value.clone().into_i64() != 0,
+            "sorted" => {
+                // The selected elements are always sorted
+                if value.clone().into_i64() == 0 {
+                    log::warn!("TopK: unsorted outputs are not supported, the outputs are sorted");
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // if axis is negative, it is counted from the end
+    if axis < 0 {
+        axis += rank;
+    }
+
+    let k = match k {
+        Some(k) => TopKSize::Static(k as usize),
+        None => topk_size(node),
+    };
+
+    (TopKConfig::new(axis as usize, largest), k)
+}
+
+fn topk_size(node: &Node) -> TopKSize {
+    match node.inputs[1].value.clone().map(Data::into_scalar) {
+        Some(Data::Int64(k)) => TopKSize::Static(k as usize),
+        // The number of elements is only known at runtime
+        None => TopKSize::Runtime(crate::burn::Type::from(&node.inputs[1])),
+        k => panic!("TopK: K must be int64, is {:?}", k),
+    }
+}
+
+pub fn non_max_suppression_config(node: &Node) -> NonMaxSuppressionConfig {
+    // The optional inputs are either omitted or constants
+    let input = |index: usize| match node.inputs.get(index) {
+        Some(input) if !input.name.is_empty() => match &input.value {
+            Some(value) => Some(value.clone().into_scalar()),
+            None => panic!(
+                "NonMaxSuppression: only constant {} is supported",
+                input.name
+            ),
+        },
+        _ => None,
+    };
+    let threshold = |data: Data| match data {
+        Data::Float32(threshold) => threshold,
+        Data::Float64(threshold) => threshold as f32,
+        _ => panic!("NonMaxSuppression: thresholds must be float, is {:?}", data),
+    };
+
+    // An omitted maximum number of boxes selects no boxes, as the ONNX default
+    let max_output_boxes_per_class = match input(2) {
+        Some(Data::Int64(max)) => max.max(0) as usize,
+        None => 0,
+        max => panic!(
+            "NonMaxSuppression: max_output_boxes_per_class must be int64, is {:?}",
+            max
+        ),
+    };
+    let iou_threshold = input(3).map(threshold).unwrap_or(0.0);
+    let score_threshold = input(4).map(threshold);
+
+    let center_point_box = match node.attrs.get("center_point_box") {
+        Some(value) => value.clone().into_i64() == 1,
+        None => false,
+    };
+
+    NonMaxSuppressionConfig::new(max_output_boxes_per_class, iou_threshold, center_point_box)
+        .with_score_threshold(score_threshold)
+}
+
+/// Check the attributes of a GatherND node, only the default batch_dims being supported
+pub fn gather_nd_config(node: &Node) {
+    if let Some(batch_dims) = node.attrs.get("batch_dims") {
+        if batch_dims.clone().into_i64() != 0 {
+            panic!("GatherND: only batch_dims=0 is supported");
+        }
+    }
+}
+
+pub fn scatter_nd_config(node: &Node) -> ScatterReduction {
+    scatter_reduction(node)
+}
+
+pub fn scatter_elements_config(node: &Node) -> (usize, ScatterReduction) {
+    let rank = match &node.inputs[0].ty {
+        ArgType::Tensor(tensor) => tensor.dim as i64,
+        _ => panic!("ScatterElements: only tensor input is valid"),
+    };
+
+    let mut axis = node
+        .attrs
+        .get("axis")
+        .map(|value| value.clone().into_i64())
+        .unwrap_or(0);
+
+    // if axis is negative, it is counted from the end
+    if axis < 0 {
+        axis += rank;
+    }
+
+    (axis as usize, scatter_reduction(node))
+}
+
+fn scatter_reduction(node: &Node) -> ScatterReduction {
+    let reduction = match node.attrs.get("reduction") {
+        Some(reduction) => reduction.clone().into_string(),
+        None => return ScatterReduction::None,
+    };
+
+    match reduction.as_str() {
+        "none" => ScatterReduction::None,
+        "add" => ScatterReduction::Add,
+        "mul" => ScatterReduction::Mul,
+        "max" => ScatterReduction::Max,
+        "min" => ScatterReduction::Min,
+        _ => panic!(
+            "{:?}: reduction {reduction} is not supported",
+            node.node_type
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use onnx_ir::ir::{Argument, NodeType, TensorType};
+
+    fn gru_node(direction: &str) -> Node {
+        let tensor = |name: &str, shape: Vec<usize>| Argument {
+            name: name.to_string(),
+            ty: ArgType::Tensor(TensorType {
+                elem_type: ElementType::Float32,
+                dim: shape.len(),
+                shape: Some(shape.into_iter().map(Some).collect()),
+            }),
+            value: None,
+            passed: true,
+        };
+        let attrs = [
+            ("hidden_size", AttributeValue::Int64(4)),
+            ("direction", AttributeValue::String(direction.to_string())),
+            ("linear_before_reset", AttributeValue::Int64(1)),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value))
+        .collect();
+
+        Node {
+            node_type: NodeType::GRU,
+            name: "gru".to_string(),
+            inputs: vec![
+                tensor("X", vec![5, 1, 2]),
+                tensor("W", vec![1, 12, 2]),
+                tensor("R", vec![1, 12, 4]),
+            ],
+            outputs: vec![tensor("Y", vec![5, 1, 1, 4])],
+            attrs,
+        }
+    }
+
+    #[test]
+    fn gru_config_reverse() {
+        let config = gru_config(&gru_node("reverse"));
+
+        assert_eq!(config.d_input, 2);
+        assert_eq!(config.d_hidden, 4);
+        assert!(!config.bias);
+        assert_eq!(config.direction, RnnDirection::Reverse);
+        assert!(config.reset_after);
+    }
+
+    #[test]
+    #[should_panic(expected = "GRU: the bidirectional direction is not supported")]
+    fn gru_config_rejects_bidirectional() {
+        gru_config(&gru_node("bidirectional"));
+    }
+}
@@ -31,9 +31,11 @@ use crate::{
             conv_transpose_2d::ConvTranspose2dNode,
             conv_transpose_3d::ConvTranspose3dNode,
             dropout::DropoutNode,
+            einsum::EinsumNode,
             expand::{ExpandNode, ExpandShape},
             gather::GatherNode,
             gather_elements::GatherElementsNode,
+            gather_nd::GatherNdNode,
             global_avg_pool::GlobalAvgPoolNode,
             layer_norm::LayerNormNode,
             linear::LinearNode,
@@ -41,6 +43,7 @@ use crate::{
             matmul::MatmulNode,
             max_pool1d::MaxPool1dNode,
             max_pool2d::MaxPool2dNode,
+            non_max_suppression::NonMaxSuppressionNode,
             pad::PadNode,
             prelu::PReluNode,
             random_normal::RandomNormalNode,
@@ -50,10 +53,14 @@ use crate::{
             range::RangeNode,
             reshape::ReshapeNode,
             resize::ResizeNode,
+            rnn::{GruNode, LstmNode, RnnWeights},
+            scatter_elements::ScatterElementsNode,
+            scatter_nd::ScatterNdNode,
             slice::SliceNode,
             squeeze::SqueezeNode,
             sum::SumNode,
             tile::TileNode,
+            topk::TopKNode,
             trilu::TriluNode,
             unary::UnaryNode,
             unsqueeze::UnsqueezeNode,
@@ -67,12 +74,14 @@ use crate::{
 use super::op_configuration::{
     argmax_config, avg_pool1d_config, avg_pool2d_config, batch_norm_config, clip_config,
     concat_config, conv1d_config, conv2d_config, conv3d_config, conv_transpose1d_config,
-    conv_transpose2d_config, conv_transpose3d_config, dropout_config, expand_config,
-    flatten_config, gather_config, hard_sigmoid_config, layer_norm_config, leaky_relu_config,
-    linear_config, log_softmax_config, max_pool1d_config, max_pool2d_config, pad_config,
+    conv_transpose2d_config, conv_transpose3d_config, dropout_config, einsum_config, expand_config,
+    flatten_config, gather_config, gather_nd_config, gru_config, hard_sigmoid_config,
+    layer_norm_config, leaky_relu_config, linear_config, log_softmax_config, lstm_config,
+    max_pool1d_config, max_pool2d_config, non_max_suppression_config, pad_config,
     reduce_max_config, reduce_mean_config, reduce_min_config, reduce_prod_config,
-    reduce_sum_config, reshape_config, resize_config, shape_config, slice_config, softmax_config,
-    squeeze_config, tile_config, transpose_config, trilu_config, unsqueeze_config,
+    reduce_sum_config, reshape_config, resize_config, scatter_elements_config, scatter_nd_config,
+    shape_config, slice_config, softmax_config, squeeze_config, tile_config, topk_config,
+    transpose_config, trilu_config, unsqueeze_config,
 };
 use onnx_ir::{
     convert_constant_value,
@@ -80,13 +89,20 @@ use onnx_ir::{
         ArgType, Argument as OnnxArgument, Data, ElementType, Node, NodeType, OnnxGraph,
         TensorType as OnnxTensorType,
     },
-    parse_onnx,
+    optimize_graph, parse_onnx,
 };
 
 pub use crate::burn::graph::RecordType;
 use crate::burn::node::mean::MeanNode;
-
-/// Generate code and states from `.onnx` files and save them to the `out_dir`.
+#[cfg(feature = "onnx-runtime")]
+use crate::burn::node::onnx_runtime::OnnxRuntimeNode;
+#[cfg(feature = "torchscript")]
+use crate::torchscript::parse_torchscript;
+#[cfg(feature = "onnx-runtime")]
+use onnx_ir::node_to_onnx;
+
+/// Generate code and states from `.onnx` files, or TorchScript `.pt` files, and save them to the
+/// `out_dir`.
 #[derive(Debug, Default)]
 pub struct ModelGen {
     out_dir: Option<PathBuf>,
@@ -96,6 +112,11 @@ pub struct ModelGen {
     half_precision: bool,
     record_type: RecordType,
     embed_states: bool,
+    optimize_graph: bool,
+    #[cfg(feature = "torchscript")]
+    input_ranks: Vec<usize>,
+    #[cfg(feature = "onnx-runtime")]
+    onnx_runtime_fallback: bool,
 }
 
 impl ModelGen {
@@ -171,6 +192,56 @@ impl ModelGen {
         self
     }
 
+    /// Specify whether to optimize the graph before generating the code.
+    ///
+    /// The Identity and Dropout nodes are removed, the consecutive transposes are merged, the batch
+    /// norms following a convolution are folded into it and the elementwise operations on
+    /// constants are computed, so the generated model is smaller and faster.
+    ///
+    /// # Arguments
+    ///
+    /// * `optimize_graph` - If true, the graph is optimized. Otherwise, each node of the graph is
+    ///    generated.
+    pub fn optimize_graph(&mut self, optimize_graph: bool) -> &mut Self {
+        self.optimize_graph = optimize_graph;
+        self
+    }
+
+    /// Specify the ranks of the inputs of the TorchScript models (`.pt`).
+    ///
+    /// The forward signature of TorchScript does not record the ranks of the tensors, so they
+    /// must be given for each input, e.g. `&[4]` for a batch of images.
+    ///
+    /// # Arguments
+    ///
+    /// * `input_ranks` - The rank of each input of the forward method.
+    #[cfg(feature = "torchscript")]
+    pub fn input_ranks(&mut self, input_ranks: &[usize]) -> &mut Self {
+        self.input_ranks = input_ranks.to_vec();
+        self
+    }
+
+    /// Specify whether to run the unsupported nodes with ONNX Runtime.
+    ///
+    /// Each unsupported node is exported as a standalone ONNX model, saved next to the generated
+    /// code and embedded in it, which is run with an ONNX Runtime session during the forward pass.
+    /// The crate of the generated code must then depend on the `ort` crate.
+    ///
+    /// The types of the outputs of the unsupported nodes are read from the value info of the
+    /// graph, which is filled by the ONNX shape inference. The operators unknown to the parser
+    /// require it, the outputs of the other ones are otherwise assumed to have the type of their
+    /// first input.
+    ///
+    /// # Arguments
+    ///
+    /// * `onnx_runtime_fallback` - If true, the unsupported nodes are run with ONNX Runtime.
+    ///    Otherwise, the code generation fails when a node is not supported.
+    #[cfg(feature = "onnx-runtime")]
+    pub fn onnx_runtime_fallback(&mut self, onnx_runtime_fallback: bool) -> &mut Self {
+        self.onnx_runtime_fallback = onnx_runtime_fallback;
+        self
+    }
+
     /// Run code generation.
     fn run(&self, is_build_script: bool) {
         log::info!("Starting to convert ONNX to Burn");
@@ -211,8 +282,20 @@ impl ModelGen {
         log::debug!("Development mode: {:?}", self.development);
         log::debug!("Output file: {:?}", out_file);
 
-        let graph = parse_onnx(input.as_ref());
-        let graph = ParsedOnnxGraph(graph);
+        let (mut graph, format) = match input.extension().and_then(|ext| ext.to_str()) {
+            #[cfg(feature = "torchscript")]
+            Some("pt") => (parse_torchscript(input, &self.input_ranks), "TorchScript"),
+            _ => (parse_onnx(input.as_ref()), "ONNX"),
+        };
+        if self.optimize_graph {
+            optimize_graph(&mut graph);
+        }
+
+        let graph = ParsedOnnxGraph {
+            graph,
+            #[cfg(feature = "onnx-runtime")]
+            onnx_runtime_fallback: self.onnx_runtime_fallback.then(|| out_file.clone()),
+        };
 
         if self.development {
             // export the graph
@@ -223,7 +306,7 @@ impl ModelGen {
         }
 
         let blank_space = true;
-        let top_comment = Some(format!("Generated from ONNX {input:?} by burn-import"));
+        let top_comment = Some(format!("Generated from {format} {input:?} by burn-import"));
 
         let code = if self.half_precision {
             graph
@@ -248,15 +331,48 @@ impl ModelGen {
     }
 }
 #[derive(Debug)]
-struct ParsedOnnxGraph(OnnxGraph);
+struct ParsedOnnxGraph {
+    graph: OnnxGraph,
+    /// The path of the generated code, next to which the unsupported nodes are exported, if they
+    /// are run with ONNX Runtime.
+    #[cfg(feature = "onnx-runtime")]
+    onnx_runtime_fallback: Option<PathBuf>,
+}
+
+/// The types of the nodes of an ONNX graph which are not supported by the code generation.
+pub fn unsupported_ops(graph: &OnnxGraph) -> Vec<NodeType> {
+    let graph = ParsedOnnxGraph {
+        graph: graph.clone(),
+        #[cfg(feature = "onnx-runtime")]
+        onnx_runtime_fallback: None,
+    };
+
+    match graph.try_into_burn::<FullPrecisionSettings>() {
+        Ok(_) => vec![],
+        Err(mut unsupported_ops) => {
+            unsupported_ops.sort_by_key(|node_type| node_type.to_string());
+            unsupported_ops.dedup();
+            unsupported_ops
+        }
+    }
+}
+
 impl ParsedOnnxGraph {
     /// Converts ONNX graph to Burn graph.
     pub fn into_burn<PS: PrecisionSettings + 'static>(self) -> BurnGraph<PS> {
+        self.try_into_burn()
+            .unwrap_or_else(|unsupported_ops| panic!("Unsupported ops: {:?}", unsupported_ops))
+    }
+
+    /// Converts ONNX graph to Burn graph, or returns the types of the unsupported nodes.
+    fn try_into_burn<PS: PrecisionSettings + 'static>(
+        self,
+    ) -> Result<BurnGraph<PS>, Vec<NodeType>> {
         let mut graph = BurnGraph::<PS>::default();
 
         let mut unsupported_ops = vec![];
 
-        for node in self.0.nodes {
+        for node in self.graph.nodes {
             match node.node_type {
                 NodeType::Add => graph.register(Self::add_conversion(node)),
                 NodeType::ArgMax => graph.register(Self::argmax_conversion(node)),
@@ -280,6 +396,7 @@ impl ParsedOnnxGraph {
                 NodeType::AveragePool1d => graph.register(Self::avg_pool_1d_conversion(node)),
                 NodeType::AveragePool2d => graph.register(Self::avg_pool_2d_conversion(node)),
                 NodeType::MatMul => graph.register(Self::matmul_conversion(node)),
+                NodeType::Einsum => graph.register(Self::einsum_conversion(node)),
                 NodeType::Neg => graph.register(Self::neg_conversion(node)),
                 NodeType::Not => graph.register(Self::not_conversion(node)),
                 NodeType::Greater => graph.register(Self::greater_conversion(node)),
@@ -290,6 +407,8 @@ impl ParsedOnnxGraph {
                     graph.register(Self::layer_norm_conversion::<PS>(node))
                 }
                 NodeType::Linear => graph.register(Self::linear_conversion::<PS>(node)),
+                NodeType::LSTM => graph.register(Self::lstm_conversion::<PS>(node)),
+                NodeType::GRU => graph.register(Self::gru_conversion::<PS>(node)),
                 NodeType::BatchNormalization => {
                     graph.register(Self::batch_norm_conversion::<PS>(node))
                 }
@@ -298,6 +417,7 @@ impl ParsedOnnxGraph {
                 NodeType::Flatten => graph.register(Self::flatten_conversion(node)),
                 NodeType::Gather => graph.register(Self::gather_conversion(node)),
                 NodeType::GatherElements => graph.register(Self::gather_elements_conversion(node)),
+                NodeType::GatherND => graph.register(Self::gather_nd_conversion(node)),
                 NodeType::HardSigmoid => graph.register(Self::hard_sigmoid_conversion(node)),
                 NodeType::Log => graph.register(Self::log_conversion(node)),
                 NodeType::LeakyRelu => graph.register(Self::leaky_relu_conversion(node)),
@@ -315,6 +435,10 @@ impl ParsedOnnxGraph {
                 NodeType::ReduceSum => graph.register(Self::reduce_sum_conversion(node)),
                 NodeType::Reshape => graph.register(Self::reshape_conversion(node)),
                 NodeType::Resize => graph.register(Self::resize_conversion(node)),
+                NodeType::ScatterElements => {
+                    graph.register(Self::scatter_elements_conversion(node))
+                }
+                NodeType::ScatterND => graph.register(Self::scatter_nd_conversion(node)),
                 NodeType::Reciprocal => graph.register(Self::reciprocal_conversion(node)),
                 NodeType::Shape => graph.register(Self::shape_conversion(node)),
                 NodeType::Sigmoid => graph.register(Self::sigmoid_conversion(node)),
@@ -349,6 +473,10 @@ impl ParsedOnnxGraph {
                 }
                 NodeType::Tile => graph.register(Self::tile_conversion(node)),
                 NodeType::Trilu => graph.register(Self::trilu_conversion(node)),
+                NodeType::TopK => graph.register(Self::topk_conversion(node)),
+                NodeType::NonMaxSuppression => {
+                    graph.register(Self::non_max_suppression_conversion(node))
+                }
                 NodeType::RandomNormal => graph.register(Self::random_normal_conversion(node)),
                 NodeType::RandomNormalLike => {
                     graph.register(Self::random_normal_like_conversion(node))
@@ -356,23 +484,31 @@ impl ParsedOnnxGraph {
                 NodeType::ConstantOfShape => {
                     graph.register(Self::constant_of_shape_conversion(node))
                 }
+                #[cfg(feature = "onnx-runtime")]
+                _ if self.onnx_runtime_fallback.is_some() => {
+                    graph.register(Self::onnx_runtime_conversion(
+                        node,
+                        self.graph.opset_version,
+                        self.onnx_runtime_fallback.as_ref().unwrap(),
+                    ))
+                }
                 node_type => unsupported_ops.push(node_type),
             }
         }
 
         if !unsupported_ops.is_empty() {
-            panic!("Unsupported ops: {:?}", unsupported_ops);
+            return Err(unsupported_ops);
         }
 
         // Get input and output names
         let input_names = self
-            .0
+            .graph
             .inputs
             .iter()
             .map(|input| input.name.clone())
             .collect::<Vec<_>>();
         let output_names = self
-            .0
+            .graph
             .outputs
             .iter()
             .map(|output| output.name.clone())
@@ -381,7 +517,29 @@ impl ParsedOnnxGraph {
         // Register inputs and outputs with the graph
         graph.register_input_output(input_names, output_names);
 
-        graph
+        Ok(graph)
+    }
+
+    #[cfg(feature = "onnx-runtime")]
+    fn onnx_runtime_conversion(node: Node, opset_version: i64, out_file: &Path) -> OnnxRuntimeNode {
+        warn!(
+            "Running the unsupported {} node {} with ONNX Runtime",
+            node.node_type, node.name
+        );
+
+        let model = out_file.with_extension(format!("{}.onnx", node.name));
+        fs::write(&model, node_to_onnx(&node, opset_version)).unwrap();
+
+        // The inputs with a value are initializers of the exported model
+        let inputs = node
+            .inputs
+            .iter()
+            .filter(|input| !input.name.is_empty() && input.value.is_none())
+            .map(TensorType::from)
+            .collect();
+        let outputs = node.outputs.iter().map(TensorType::from).collect();
+
+        OnnxRuntimeNode::new(node.node_type.to_string(), inputs, outputs, model)
     }
 
     fn constant_conversion<PS: PrecisionSettings>(node: Node) -> ConstantNode {
@@ -401,19 +559,19 @@ impl ParsedOnnxGraph {
                     let kind: TensorKind = tensor.elem_type.clone().into();
                     let dim = tensor.dim;
                     let name = node.name.clone();
-                    let shape = tensor.shape.clone();
+                    let shape = tensor.static_shape();
 
                     let tensor_data = match tensor.elem_type {
                         // TODO Review how double precision should be supported
                         ElementType::Float32 | ElementType::Float64 => {
                             serialize_data::<PS::FloatElem>(
                                 attr.value.unwrap(),
-                                tensor.shape.unwrap(),
+                                tensor.static_shape().unwrap(),
                             )
                         }
                         ElementType::Int32 | ElementType::Int64 => serialize_data::<PS::IntElem>(
                             attr.value.unwrap(),
-                            tensor.shape.unwrap(),
+                            tensor.static_shape().unwrap(),
                         ),
                         // TODO support Bool tensor when it is supported by Burn
                         _ => panic!("Unsupported constant tensor type: {:?} ", tensor.elem_type),
@@ -594,6 +752,32 @@ impl ParsedOnnxGraph {
         MatmulNode::new(lhs, rhs, output)
     }
 
+    fn einsum_conversion(node: Node) -> EinsumNode {
+        let inputs = node.inputs.iter().map(TensorType::from).collect();
+        let output = TensorType::from(node.outputs.first().unwrap());
+        let config = einsum_config(&node);
+
+        EinsumNode::new(inputs, output, config)
+    }
+
+    fn topk_conversion(node: Node) -> TopKNode {
+        let input = TensorType::from(node.inputs.first().unwrap());
+        let values = optional_output(&node, 0);
+        let indices = optional_output(&node, 1);
+        let (config, k) = topk_config(&node);
+
+        TopKNode::new(input, k, values, indices, config)
+    }
+
+    fn non_max_suppression_conversion(node: Node) -> NonMaxSuppressionNode {
+        let boxes = TensorType::from(node.inputs.first().unwrap());
+        let scores = TensorType::from(node.inputs.get(1).unwrap());
+        let output = TensorType::from(node.outputs.first().unwrap());
+        let config = non_max_suppression_config(&node);
+
+        NonMaxSuppressionNode::new(boxes, scores, output, config)
+    }
+
     fn equal_conversion(node: Node) -> BinaryNode {
         let lhs = Type::from(node.inputs.first().unwrap());
         let rhs = Type::from(node.inputs.get(1).unwrap());
@@ -680,6 +864,35 @@ impl ParsedOnnxGraph {
         GatherElementsNode::new(input, index, output, dim)
     }
 
+    fn gather_nd_conversion(node: Node) -> GatherNdNode {
+        let input = TensorType::from(node.inputs.first().unwrap());
+        let indices = TensorType::from(node.inputs.get(1).unwrap());
+        let output = TensorType::from(node.outputs.first().unwrap());
+        gather_nd_config(&node);
+
+        GatherNdNode::new(input, indices, output)
+    }
+
+    fn scatter_nd_conversion(node: Node) -> ScatterNdNode {
+        let input = TensorType::from(node.inputs.first().unwrap());
+        let indices = TensorType::from(node.inputs.get(1).unwrap());
+        let updates = TensorType::from(node.inputs.get(2).unwrap());
+        let output = TensorType::from(node.outputs.first().unwrap());
+        let reduction = scatter_nd_config(&node);
+
+        ScatterNdNode::new(input, indices, updates, output, reduction)
+    }
+
+    fn scatter_elements_conversion(node: Node) -> ScatterElementsNode {
+        let input = TensorType::from(node.inputs.first().unwrap());
+        let indices = TensorType::from(node.inputs.get(1).unwrap());
+        let updates = TensorType::from(node.inputs.get(2).unwrap());
+        let output = TensorType::from(node.outputs.first().unwrap());
+        let (dim, reduction) = scatter_elements_config(&node);
+
+        ScatterElementsNode::new(input, indices, updates, output, dim, reduction)
+    }
+
     fn transpose_conversion(node: Node) -> UnaryNode {
         let input = Type::from(node.inputs.first().unwrap());
         let output = Type::from(node.outputs.first().unwrap());
@@ -710,9 +923,9 @@ impl ParsedOnnxGraph {
 
         let output = TensorType::from(node.outputs.first().unwrap());
 
-        let (mode, scales, sizes) = resize_config(&node);
+        let (mode, scales, sizes, sampling) = resize_config(&node);
 
-        ResizeNode::new(name, input, output, mode, scales, sizes)
+        ResizeNode::new(name, input, output, mode, scales, sizes, sampling)
     }
 
     fn min_conversion(node: Node) -> BinaryNode {
@@ -952,6 +1165,40 @@ impl ParsedOnnxGraph {
         )
     }
 
+    fn lstm_conversion<PS: PrecisionSettings>(node: Node) -> LstmNode {
+        let name = &node.name;
+        let input = TensorType::from(node.inputs.first().unwrap());
+        let config = lstm_config(&node);
+
+        LstmNode::new(
+            name,
+            input,
+            recurrent_input(&node, 5),
+            recurrent_input(&node, 6),
+            optional_output(&node, 0),
+            optional_output(&node, 1),
+            optional_output(&node, 2),
+            recurrent_weights::<PS>(&node),
+            config,
+        )
+    }
+
+    fn gru_conversion<PS: PrecisionSettings>(node: Node) -> GruNode {
+        let name = &node.name;
+        let input = TensorType::from(node.inputs.first().unwrap());
+        let config = gru_config(&node);
+
+        GruNode::new(
+            name,
+            input,
+            recurrent_input(&node, 5),
+            optional_output(&node, 0),
+            optional_output(&node, 1),
+            recurrent_weights::<PS>(&node),
+            config,
+        )
+    }
+
     fn layer_norm_conversion<PS: PrecisionSettings>(node: Node) -> LayerNormNode {
         let (config, full_precision) = layer_norm_config(&node);
         let input = TensorType::from(node.inputs.first().unwrap());
@@ -1290,13 +1537,55 @@ fn extract_data_serialize<E: Element>(input_index: usize, node: &Node) -> Option
 
             Some(serialize_data::<E>(
                 value.clone(),
-                tensor_type.shape.unwrap().clone(),
+                tensor_type.static_shape().unwrap(),
             ))
         }
         _ => panic!("Unsupported serialization type"),
     }
 }
 
+/// The weights `W`, `R` and the optional bias `B` of the recurrent nodes.
+fn recurrent_weights<PS: PrecisionSettings>(node: &Node) -> RnnWeights {
+    RnnWeights {
+        input: extract_data_serialize::<PS::FloatElem>(1, node).expect("Weights are required"),
+        hidden: extract_data_serialize::<PS::FloatElem>(2, node)
+            .expect("Recurrence weights are required"),
+        bias: extract_data_serialize::<PS::FloatElem>(3, node),
+    }
+}
+
+/// The optional initial state of a recurrent node, the missing inputs having an empty name.
+fn recurrent_input(node: &Node, index: usize) -> Option<TensorType> {
+    let input = node
+        .inputs
+        .get(index)
+        .filter(|input| !input.name.is_empty())?;
+
+    // The constant initial states are only supported when they are zeros, the default state.
+    if let Some(value) = &input.value {
+        let zeros = match value {
+            Data::Float16s(values) => values.iter().all(|value| value.to_f32() == 0.0),
+            Data::Float32s(values) => values.iter().all(|value| *value == 0.0),
+            Data::Float64s(values) => values.iter().all(|value| *value == 0.0),
+            _ => false,
+        };
+        if !zeros {
+            panic!("{}: constant initial states are not supported", node.name);
+        }
+        return None;
+    }
+
+    Some(TensorType::from(input))
+}
+
+/// The optional output of a node, the unused outputs having an empty name.
+fn optional_output(node: &Node, index: usize) -> Option<TensorType> {
+    node.outputs
+        .get(index)
+        .filter(|output| !output.name.is_empty())
+        .map(TensorType::from)
+}
+
 /// Convert data to `TensorData`.
 fn serialize_data<E: Element>(data: Data, shape: Vec<usize>) -> TensorData {
     match data {
@@ -1313,24 +1602,27 @@ fn serialize_data<E: Element>(data: Data, shape: Vec<usize>) -> TensorData {
 impl From<&OnnxArgument> for TensorType {
     fn from(arg: &OnnxArgument) -> Self {
         match &arg.ty {
-            ArgType::Tensor(OnnxTensorType {
-                elem_type: ElementType::Float16 | ElementType::Float32 | ElementType::Float64,
-                dim,
-                shape,
-                ..
-            }) => TensorType::new_float_with_shape(arg.name.clone(), *dim, shape.clone()),
-            ArgType::Tensor(OnnxTensorType {
-                elem_type: ElementType::Int32 | ElementType::Int64,
-                dim,
-                shape,
-                ..
-            }) => TensorType::new_int_with_shape(arg.name.clone(), *dim, shape.clone()),
-            ArgType::Tensor(OnnxTensorType {
-                elem_type: ElementType::Bool,
-                dim,
-                shape,
-                ..
-            }) => TensorType::new_bool_with_shape(arg.name.clone(), *dim, shape.clone()),
+            ArgType::Tensor(
+                tensor @ OnnxTensorType {
+                    elem_type: ElementType::Float16 | ElementType::Float32 | ElementType::Float64,
+                    dim,
+                    ..
+                },
+            ) => TensorType::new_float_with_shape(arg.name.clone(), *dim, tensor.static_shape()),
+            ArgType::Tensor(
+                tensor @ OnnxTensorType {
+                    elem_type: ElementType::Int32 | ElementType::Int64,
+                    dim,
+                    ..
+                },
+            ) => TensorType::new_int_with_shape(arg.name.clone(), *dim, tensor.static_shape()),
+            ArgType::Tensor(
+                tensor @ OnnxTensorType {
+                    elem_type: ElementType::Bool,
+                    dim,
+                    ..
+                },
+            ) => TensorType::new_bool_with_shape(arg.name.clone(), *dim, tensor.static_shape()),
             _ => panic!("Can't transform {:?} to tensor.", arg.ty),
         }
     }
@@ -1349,7 +1641,7 @@ impl From<&OnnxArgument> for Type {
                     let kind: TensorKind = tensor.elem_type.clone().into();
                     let dim = tensor.dim;
                     let name = arg.name.clone();
-                    let shape = tensor.shape.clone();
+                    let shape = tensor.static_shape();
                     Type::Tensor(TensorType::new(name, dim, kind, shape))
                 }
             }
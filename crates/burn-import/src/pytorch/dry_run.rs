@@ -0,0 +1,215 @@
+use core::fmt;
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::error::Error;
+
+use burn::{
+    module::{Module, ModuleVisitor, ParamId},
+    record::serde::data::remap,
+    tensor::{backend::Backend, Bool, Int, Tensor},
+};
+
+use candle_core::pickle;
+use regex::Regex;
+
+/// A key of the file matched with a parameter of the module.
+#[derive(Debug, Clone)]
+pub struct KeyMatch {
+    /// The key in the file, before the remapping.
+    pub original_key: String,
+    /// The key in the file, after the remapping.
+    pub key: String,
+    /// The shape of the tensor in the file, after the transpositions.
+    pub shape: Vec<usize>,
+    /// The path of the parameter in the module.
+    pub module_key: String,
+    /// The shape of the parameter in the module.
+    pub module_shape: Vec<usize>,
+}
+
+/// The report of a dry run, comparing the keys of a PyTorch file with the parameters of a module
+/// without loading the tensors.
+#[derive(Debug, Clone, Default)]
+pub struct DryRunReport {
+    /// The keys loaded into the parameters of the module.
+    pub matched: Vec<KeyMatch>,
+    /// The keys whose parameter has a different shape, which fail to load.
+    pub mismatched: Vec<KeyMatch>,
+    /// The keys of the file without parameter in the module, which are ignored.
+    pub unused: Vec<(String, Vec<usize>)>,
+    /// The parameters of the module without key in the file, which fail to load.
+    pub missing: Vec<(String, Vec<usize>)>,
+}
+
+impl DryRunReport {
+    /// Whether all the parameters of the module would be loaded.
+    pub fn is_complete(&self) -> bool {
+        self.mismatched.is_empty() && self.missing.is_empty()
+    }
+}
+
+impl fmt::Display for DryRunReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Matched keys ({}):", self.matched.len())?;
+        for entry in self.matched.iter() {
+            write!(f, "  {} {:?}", entry.key, entry.shape)?;
+            if entry.original_key != entry.key {
+                write!(f, " (from {})", entry.original_key)?;
+            }
+            if entry.module_key != entry.key {
+                write!(f, " -> {}", entry.module_key)?;
+            }
+            writeln!(f)?;
+        }
+
+        writeln!(f, "Mismatched shapes ({}):", self.mismatched.len())?;
+        for entry in self.mismatched.iter() {
+            writeln!(
+                f,
+                "  {} {:?} -> {} {:?}",
+                entry.key, entry.shape, entry.module_key, entry.module_shape
+            )?;
+        }
+
+        writeln!(f, "Unused keys of the file ({}):", self.unused.len())?;
+        for (key, shape) in self.unused.iter() {
+            writeln!(f, "  {key} {shape:?}")?;
+        }
+
+        writeln!(f, "Missing keys of the module ({}):", self.missing.len())?;
+        for (key, shape) in self.missing.iter() {
+            writeln!(f, "  {key} {shape:?}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Compares the keys of a PyTorch file, remapped and transposed, with the parameters of a module.
+pub(crate) fn dry_run<B: Backend, M: Module<B>>(
+    module: &M,
+    path: &Path,
+    key_remap: Vec<(Regex, String)>,
+    transpose: &[Regex],
+    top_level_key: Option<&str>,
+) -> Result<DryRunReport, Error> {
+    // Only the shapes are read, not the tensors
+    let shapes = pickle::read_pth_tensor_info(path, false, top_level_key)?
+        .into_iter()
+        .map(|info| (info.name, info.layout.shape().dims().to_vec()))
+        .collect::<HashMap<_, _>>();
+
+    let (mut shapes, remapped_keys) = remap(shapes, key_remap);
+
+    for (key, shape) in shapes.iter_mut() {
+        if shape.len() >= 2 && transpose.iter().any(|pattern| pattern.is_match(key)) {
+            let rank = shape.len();
+            shape.swap(rank - 2, rank - 1);
+        }
+    }
+
+    let mut visitor = ParamShapes::default();
+    module.visit(&mut visitor);
+
+    let mut keys = remapped_keys
+        .into_iter()
+        .filter_map(|(key, original_key)| {
+            let shape = shapes.remove(&key)?;
+            Some((key, original_key, shape))
+        })
+        .collect::<Vec<_>>();
+    keys.sort();
+
+    Ok(compare(keys, visitor.shapes))
+}
+
+/// Matches the keys of a file with the parameters of a module, as the PyTorch adapter does when
+/// loading a record.
+fn compare(
+    keys: Vec<(String, String, Vec<usize>)>,
+    mut params: HashMap<String, Vec<usize>>,
+) -> DryRunReport {
+    let mut report = DryRunReport::default();
+
+    for (key, original_key, shape) in keys {
+        // The weight and the bias of the normalization layers are named gamma and beta in Burn
+        let module_key = [key.clone(), renamed_norm_key(&key)]
+            .into_iter()
+            .find(|key| params.contains_key(key));
+
+        let Some(module_key) = module_key else {
+            report.unused.push((key, shape));
+            continue;
+        };
+        let module_shape = params.remove(&module_key).unwrap();
+
+        // The weights of the linear layers are transposed by the PyTorch adapter
+        let reversed = shape.iter().rev().cloned().collect::<Vec<_>>();
+        let same_shape = shape == module_shape
+            || (shape.len() == 2 && key.ends_with("weight") && reversed == module_shape);
+
+        let entry = KeyMatch {
+            original_key,
+            key,
+            shape,
+            module_key,
+            module_shape,
+        };
+
+        match same_shape {
+            true => report.matched.push(entry),
+            false => report.mismatched.push(entry),
+        }
+    }
+
+    report.missing = params.into_iter().collect();
+    report.missing.sort();
+
+    report
+}
+
+fn renamed_norm_key(key: &str) -> String {
+    if let Some(path) = key.strip_suffix("weight") {
+        format!("{path}gamma")
+    } else if let Some(path) = key.strip_suffix("bias") {
+        format!("{path}beta")
+    } else {
+        key.to_string()
+    }
+}
+
+/// Collects the shapes of the parameters of a module by path.
+#[derive(Default)]
+struct ParamShapes {
+    path: Vec<String>,
+    shapes: HashMap<String, Vec<usize>>,
+}
+
+impl ParamShapes {
+    fn insert(&mut self, shape: Vec<usize>) {
+        self.shapes.insert(self.path.join("."), shape);
+    }
+}
+
+impl<B: Backend> ModuleVisitor<B> for ParamShapes {
+    fn enter_module(&mut self, name: &str) {
+        self.path.push(name.to_string());
+    }
+
+    fn exit_module(&mut self, _name: &str) {
+        self.path.pop();
+    }
+
+    fn visit_float<const D: usize>(&mut self, _id: ParamId, tensor: &Tensor<B, D>) {
+        self.insert(tensor.dims().to_vec());
+    }
+
+    fn visit_int<const D: usize>(&mut self, _id: ParamId, tensor: &Tensor<B, D, Int>) {
+        self.insert(tensor.dims().to_vec());
+    }
+
+    fn visit_bool<const D: usize>(&mut self, _id: ParamId, tensor: &Tensor<B, D, Bool>) {
+        self.insert(tensor.dims().to_vec());
+    }
+}
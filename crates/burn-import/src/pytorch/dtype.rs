@@ -0,0 +1,82 @@
+use burn::tensor::DType;
+use regex::Regex;
+
+/// The data types of the float tensors of a source model when they are read, used by the load
+/// arguments of the PyTorch and safetensors recorders.
+///
+/// The values are rounded to their data type when read, then converted to the float type of the
+/// backend when the record is loaded. A data type more precise than the backend float type keeps
+/// the values of the source model up to the precision of the backend.
+///
+/// # Example
+///
+/// ```text
+/// // Keep the normalization weights of a bf16 model in f32
+/// let float_dtypes = FloatDTypes::new()
+///     .with_dtype(DType::BF16)
+///     .with_override("norm|embed", DType::F32);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FloatDTypes {
+    dtype: Option<DType>,
+    overrides: Vec<(Regex, DType)>,
+}
+
+impl FloatDTypes {
+    /// Creates the data types of the precision settings of the recorder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads the float tensors as `dtype`, instead of the `FloatElem` of the precision settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `dtype` - The float data type, e.g. `DType::BF16`.
+    pub fn with_dtype(mut self, dtype: DType) -> Self {
+        assert!(dtype.is_float(), "{dtype:?} is not a float data type");
+
+        self.dtype = Some(dtype);
+        self
+    }
+
+    /// Reads the float tensors whose keys match the pattern as `dtype`, whatever the data type of
+    /// the other tensors.
+    ///
+    /// The pattern is matched against the remapped keys, the first matching override applies. It
+    /// is useful to keep the precision of the weights sensitive to rounding, such as the
+    /// normalization and embedding weights.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The Regex pattern of the keys.
+    /// * `dtype` - The float data type, e.g. `DType::F32`.
+    pub fn with_override(mut self, pattern: &str, dtype: DType) -> Self {
+        assert!(dtype.is_float(), "{dtype:?} is not a float data type");
+        let regex = Regex::new(pattern).expect("Valid regex");
+
+        self.overrides.push((regex, dtype));
+        self
+    }
+
+    /// Whether the tensors are read with the precision settings of the recorder.
+    pub(crate) fn is_default(&self) -> bool {
+        self.dtype.is_none() && self.overrides.is_empty()
+    }
+
+    /// Reads the float tensors as `dtype` unless another data type is set.
+    pub(crate) fn or_dtype(mut self, dtype: DType) -> Self {
+        self.dtype = self.dtype.or(Some(dtype));
+        self
+    }
+
+    /// The data type of the tensor of the key: the data type of the first override whose pattern
+    /// matches the key, or the data type of all the float tensors.
+    pub(crate) fn get(&self, key: &str) -> Option<DType> {
+        self.overrides
+            .iter()
+            .find(|(pattern, _)| pattern.is_match(key))
+            .map(|(_, dtype)| *dtype)
+            .or(self.dtype)
+    }
+}
@@ -1,7 +1,19 @@
 mod adapter;
 mod config;
+mod dry_run;
+mod dtype;
 mod error;
 mod reader;
 mod recorder;
 pub use config::config_from_file;
+pub use dry_run::{DryRunReport, KeyMatch};
+pub use dtype::FloatDTypes;
 pub use recorder::{LoadArgs, PyTorchFileRecorder};
+
+// The GGUF and safetensors tensors keep the PyTorch layout.
+#[cfg(any(feature = "gguf", feature = "safetensors"))]
+pub(crate) use adapter::PyTorchAdapter;
+#[cfg(any(feature = "gguf", feature = "keras", feature = "flax"))]
+pub(crate) use reader::serialize_tensor_data;
+#[cfg(feature = "safetensors")]
+pub(crate) use reader::CandleTensor;
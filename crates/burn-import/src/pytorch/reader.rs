@@ -2,12 +2,12 @@ use core::ops::Deref;
 use std::collections::HashMap;
 use std::path::Path;
 
-use super::{adapter::PyTorchAdapter, error::Error};
+use super::{adapter::PyTorchAdapter, dtype::FloatDTypes, error::Error};
 
 use burn::{
     module::ParamId,
     record::PrecisionSettings,
-    tensor::{Element, ElementConversion, TensorData},
+    tensor::{DType, Element, ElementConversion, TensorData},
 };
 use burn::{
     record::serde::{
@@ -30,11 +30,16 @@ use serde::{de::DeserializeOwned, Serialize};
 ///
 /// * `path` - A string slice that holds the path of the file to read.
 /// * `key_remap` - A vector of tuples containing a regular expression and a replacement string.
+/// * `transpose` - The patterns of the keys whose last two dimensions are transposed.
 /// * `top_level_key` - An optional top-level key to load state_dict from a dictionary.
+/// * `float_dtypes` - The data types of the float tensors when read.
+/// * `debug` - Whether to print the keys, shapes and data types of the tensors.
 pub fn from_file<PS, D, B>(
     path: &Path,
     key_remap: Vec<(Regex, String)>,
+    transpose: &[Regex],
     top_level_key: Option<&str>,
+    float_dtypes: &FloatDTypes,
     debug: bool,
 ) -> Result<D, Error>
 where
@@ -45,11 +50,20 @@ where
     // Read the pickle file and return a vector of Candle tensors
     let tensors: HashMap<String, CandleTensor> = pickle::read_all_with_key(path, top_level_key)?
         .into_iter()
-        .map(|(key, tensor)| (key, CandleTensor(tensor)))
+        .map(|(key, tensor)| (key, CandleTensor::new(tensor)))
         .collect();
 
     // Remap the keys (replace the keys in the map with the new keys)
-    let (tensors, remapped_keys) = remap(tensors, key_remap);
+    let (mut tensors, remapped_keys) = remap(tensors, key_remap);
+
+    // Transpose the tensors whose layout differs from the layout of the Burn modules
+    for (key, tensor) in tensors.iter_mut() {
+        if tensor.rank() >= 2 && transpose.iter().any(|pattern| pattern.is_match(key)) {
+            let rank = tensor.rank();
+            tensor.tensor = tensor.transpose(rank - 2, rank - 1)?.contiguous()?;
+        }
+        tensor.float_dtype = float_dtypes.get(key);
+    }
 
     // Print the remapped keys if debug is enabled
     if debug {
@@ -68,6 +82,9 @@ where
             let dtype = tensors[&new_key].dtype();
             println!("Shape: {shape:?}");
             println!("Dtype: {dtype:?}");
+            if let Some(float_dtype) = tensors[&new_key].float_dtype {
+                println!("Cast to: {float_dtype:?}");
+            }
             println!("---");
         }
     }
@@ -87,15 +104,17 @@ where
 ///
 /// Tensors are wrapped in a `Param` struct (learnable parameters) and serialized as a `TensorData` struct.
 ///
-/// Values are serialized as `FloatElem` or `IntElem` depending on the precision settings.
+/// Values are serialized as `FloatElem` or `IntElem` depending on the precision settings, unless
+/// the float data type of the tensor is overridden.
 impl Serializable for CandleTensor {
     fn serialize<PS>(&self, serializer: Serializer) -> Result<NestedValue, error::Error>
     where
         PS: PrecisionSettings,
     {
         let shape = self.shape().clone().into_dims();
-        let flatten = CandleTensor(self.flatten_all().expect("Failed to flatten the tensor"));
+        let flatten = CandleTensor::new(self.flatten_all().expect("Failed to flatten the tensor"));
         let param_id = ParamId::new();
+        let float_dtype = self.float_dtype;
 
         match self.dtype() {
             candle_core::DType::U8 => {
@@ -108,21 +127,46 @@ impl Serializable for CandleTensor {
                 serialize_data::<i64, PS::IntElem>(flatten, shape, param_id, serializer)
             }
             candle_core::DType::BF16 => {
-                serialize_data::<bf16, PS::FloatElem>(flatten, shape, param_id, serializer)
+                serialize_float::<bf16, PS>(flatten, shape, float_dtype, param_id, serializer)
             }
             candle_core::DType::F16 => {
-                serialize_data::<f16, PS::FloatElem>(flatten, shape, param_id, serializer)
+                serialize_float::<f16, PS>(flatten, shape, float_dtype, param_id, serializer)
             }
             candle_core::DType::F32 => {
-                serialize_data::<f32, PS::FloatElem>(flatten, shape, param_id, serializer)
+                serialize_float::<f32, PS>(flatten, shape, float_dtype, param_id, serializer)
             }
             candle_core::DType::F64 => {
-                serialize_data::<f64, PS::FloatElem>(flatten, shape, param_id, serializer)
+                serialize_float::<f64, PS>(flatten, shape, float_dtype, param_id, serializer)
             }
         }
     }
 }
 
+/// Helper function to serialize a candle tensor data of floats, as `FloatElem` unless the data
+/// type is overridden.
+fn serialize_float<T, PS>(
+    tensor: CandleTensor,
+    shape: Vec<usize>,
+    float_dtype: Option<DType>,
+    param_id: ParamId,
+    serializer: Serializer,
+) -> Result<NestedValue, error::Error>
+where
+    T: WithDType + ElementConversion,
+    PS: PrecisionSettings,
+{
+    match float_dtype {
+        None => serialize_data::<T, PS::FloatElem>(tensor, shape, param_id, serializer),
+        Some(DType::F64) => serialize_data::<T, f64>(tensor, shape, param_id, serializer),
+        Some(DType::F32) => serialize_data::<T, f32>(tensor, shape, param_id, serializer),
+        Some(DType::F16) => serialize_data::<T, f16>(tensor, shape, param_id, serializer),
+        Some(DType::BF16) => serialize_data::<T, bf16>(tensor, shape, param_id, serializer),
+        Some(dtype) => Err(error::Error::Other(format!(
+            "{dtype:?} is not a float data type"
+        ))),
+    }
+}
+
 /// Helper function to serialize a candle tensor data.
 fn serialize_data<T, E>(
     tensor: CandleTensor,
@@ -141,11 +185,20 @@ where
         .map(ElementConversion::elem)
         .collect();
 
+    serialize_tensor_data(TensorData::new(data, shape), param_id, serializer)
+}
+
+/// Helper function to serialize the tensor data of a param.
+pub(crate) fn serialize_tensor_data(
+    data: TensorData,
+    param_id: ParamId,
+    serializer: Serializer,
+) -> Result<NestedValue, error::Error> {
     let TensorData {
         bytes,
         shape,
         dtype,
-    } = TensorData::new(data, shape);
+    } = data;
 
     // Manually serialize the tensor instead of using the `ParamSerde` struct, such as:
     // ParamSerde::new(param_id, TensorData::new(data, shape)).serialize(serializer)
@@ -163,13 +216,26 @@ where
     Ok(NestedValue::Map(param))
 }
 
-/// New type struct for Candle tensors because we need to implement the `Serializable` trait for it.
-struct CandleTensor(candle_core::Tensor);
+/// Wrapper of Candle tensors because we need to implement the `Serializable` trait for it.
+pub(crate) struct CandleTensor {
+    pub(crate) tensor: candle_core::Tensor,
+    /// The data type of the float values, `FloatElem` of the precision settings if `None`.
+    pub(crate) float_dtype: Option<DType>,
+}
+
+impl CandleTensor {
+    pub(crate) fn new(tensor: candle_core::Tensor) -> Self {
+        Self {
+            tensor,
+            float_dtype: None,
+        }
+    }
+}
 
 impl Deref for CandleTensor {
     type Target = candle_core::Tensor;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.tensor
     }
 }
@@ -2,13 +2,16 @@ use core::marker::PhantomData;
 use std::path::PathBuf;
 
 use burn::{
-    record::{PrecisionSettings, Record, Recorder, RecorderError},
-    tensor::backend::Backend,
+    module::Module,
+    record::{DoublePrecisionSettings, PrecisionSettings, Record, Recorder, RecorderError},
+    tensor::{backend::Backend, Element},
 };
 
 use regex::Regex;
 use serde::{de::DeserializeOwned, Serialize};
 
+use super::dry_run::{dry_run, DryRunReport};
+use super::dtype::FloatDTypes;
 use super::reader::from_file;
 
 /// A recorder that that loads PyTorch files (`.pt`) into Burn modules.
@@ -44,16 +47,66 @@ impl<PS: PrecisionSettings, B: Backend> Recorder<B> for PyTorchFileRecorder<PS>
         args: Self::LoadArgs,
         device: &B::Device,
     ) -> Result<R, RecorderError> {
+        if !args.float_dtypes.is_default() {
+            // The values are rounded to their data type when read and kept in double precision
+            // until the record is converted to the float type of the backend
+            let float_dtypes = args.float_dtypes.or_dtype(PS::FloatElem::dtype());
+            let item = from_file::<DoublePrecisionSettings, R::Item<DoublePrecisionSettings>, B>(
+                &args.file,
+                args.key_remap,
+                &args.transpose,
+                args.top_level_key.as_deref(), // Convert Option<String> to Option<&str>
+                &float_dtypes,
+                args.debug,
+            )?;
+            return Ok(R::from_item(item, device));
+        }
+
         let item = from_file::<PS, R::Item<Self::Settings>, B>(
             &args.file,
             args.key_remap,
+            &args.transpose,
             args.top_level_key.as_deref(), // Convert Option<String> to Option<&str>
+            &FloatDTypes::new(),
             args.debug,
         )?;
         Ok(R::from_item(item, device))
     }
 }
 
+impl<PS: PrecisionSettings> PyTorchFileRecorder<PS> {
+    /// Compares the keys of the file, remapped and transposed with the load arguments, with the
+    /// parameters of a module, without loading the tensors.
+    ///
+    /// The report lists the matched keys with their shapes, the keys whose shapes differ, the
+    /// keys of the file unused by the module and the parameters of the module missing from the
+    /// file.
+    ///
+    /// # Examples
+    ///
+    /// ```text
+    /// let report = PyTorchFileRecorder::<FullPrecisionSettings>::default()
+    ///     .dry_run(args, &Net::<Backend>::init(&device))
+    ///     .expect("Should read the keys of the file");
+    ///
+    /// println!("{report}");
+    /// ```
+    pub fn dry_run<B: Backend, M: Module<B>>(
+        &self,
+        args: LoadArgs,
+        module: &M,
+    ) -> Result<DryRunReport, RecorderError> {
+        let report = dry_run(
+            module,
+            &args.file,
+            args.key_remap,
+            &args.transpose,
+            args.top_level_key.as_deref(),
+        )?;
+        Ok(report)
+    }
+}
+
 /// Arguments for loading a PyTorch file.
 ///
 /// # Fields
@@ -90,10 +143,16 @@ pub struct LoadArgs {
     /// A list of key remappings.
     pub key_remap: Vec<(Regex, String)>,
 
+    /// The patterns of the keys whose tensors are transposed, after the remapping.
+    pub transpose: Vec<Regex>,
+
     /// Top-level key to load state_dict from the file.
     /// Sometimes the state_dict is nested under a top-level key in a dict.
     pub top_level_key: Option<String>,
 
+    /// The data types of the float tensors, `FloatElem` of the precision settings by default.
+    pub float_dtypes: FloatDTypes,
+
     /// Whether to print debug information.
     pub debug: bool,
 }
@@ -108,7 +167,9 @@ impl LoadArgs {
         Self {
             file,
             key_remap: Vec::new(),
+            transpose: Vec::new(),
             top_level_key: None,
+            float_dtypes: FloatDTypes::new(),
             debug: false,
         }
     }
@@ -130,6 +191,22 @@ impl LoadArgs {
         self
     }
 
+    /// Transposes the last two dimensions of the tensors whose keys match the pattern.
+    ///
+    /// The pattern is matched against the remapped keys. It is useful when a checkpoint stores
+    /// its weights with another layout than PyTorch, e.g. the `Conv1D` layers of Hugging Face
+    /// store the linear weights as `[in, out]` instead of `[out, in]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The Regex pattern of the keys to transpose.
+    pub fn with_transpose(mut self, pattern: &str) -> Self {
+        let regex = Regex::new(pattern).expect("Valid regex");
+
+        self.transpose.push(regex);
+        self
+    }
+
     /// Sets the top-level key to load state_dict from the file.
     /// Sometimes the state_dict is nested under a top-level key in a dict.
     ///
@@ -141,6 +218,16 @@ impl LoadArgs {
         self
     }
 
+    /// Sets the data types of the float tensors when read.
+    ///
+    /// # Arguments
+    ///
+    /// * `float_dtypes` - The data types, e.g. `FloatDTypes::new().with_dtype(DType::BF16)`.
+    pub fn with_float_dtypes(mut self, float_dtypes: FloatDTypes) -> Self {
+        self.float_dtypes = float_dtypes;
+        self
+    }
+
     /// Sets printing debug information on.
     pub fn with_debug_print(mut self) -> Self {
         self.debug = true;
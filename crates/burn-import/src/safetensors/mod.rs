@@ -0,0 +1,5 @@
+mod error;
+mod reader;
+mod recorder;
+pub use crate::pytorch::FloatDTypes;
+pub use recorder::{LoadArgs, SafetensorsFileRecorder};
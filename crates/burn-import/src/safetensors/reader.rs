@@ -0,0 +1,197 @@
+use std::collections::{BTreeSet, HashMap};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::error::Error;
+use crate::pytorch::{CandleTensor, FloatDTypes, PyTorchAdapter};
+
+use burn::{
+    record::{
+        serde::{
+            data::{remap, unflatten, LazyValue, NestedValue, Serializable},
+            de::Deserializer,
+            error,
+            ser::Serializer,
+        },
+        PrecisionSettings,
+    },
+    tensor::{backend::Backend, DType},
+};
+
+use candle_core::{safetensors::MmapedSafetensors, Device};
+use regex::Regex;
+use serde::{de::DeserializeOwned, Deserialize};
+
+/// The index of a sharded checkpoint, e.g. `model.safetensors.index.json`.
+#[derive(Deserialize)]
+struct Index {
+    /// The shard file of each tensor, relative to the index.
+    weight_map: HashMap<String, String>,
+}
+
+/// Deserializes a safetensors file, or the shards of a sharded checkpoint.
+///
+/// # Arguments
+///
+/// * `path` - The path of the `.safetensors` file, or of the `.index.json` file of the shards.
+/// * `key_remap` - A vector of tuples containing a regular expression and a replacement string.
+/// * `float_dtypes` - The data types of the float tensors when read.
+/// * `debug` - Whether to print the keys, shapes and data types of the tensors.
+pub fn from_file<PS, D, B>(
+    path: &Path,
+    key_remap: Vec<(Regex, String)>,
+    float_dtypes: &FloatDTypes,
+    debug: bool,
+) -> Result<D, Error>
+where
+    D: DeserializeOwned,
+    PS: PrecisionSettings,
+    B: Backend,
+{
+    let files = match path.to_str().is_some_and(|path| path.ends_with(".json")) {
+        true => shard_files(path)?,
+        false => vec![path.to_path_buf()],
+    };
+
+    // SAFETY: the files are memory mapped, they must not be modified while the record is loaded.
+    let safetensors = Arc::new(unsafe { MmapedSafetensors::multi(&files)? });
+
+    // The tensors are only read from their shard when deserialized
+    let tensors = safetensors
+        .tensors()
+        .into_iter()
+        .map(|(name, _)| {
+            let tensor = SafetensorsTensor::new(name.clone(), safetensors.clone(), None);
+            (name, tensor)
+        })
+        .collect::<HashMap<_, _>>();
+
+    // Remap the keys (replace the keys in the map with the new keys)
+    let (mut tensors, remapped_keys) = remap(tensors, key_remap);
+
+    for (key, tensor) in tensors.iter_mut() {
+        tensor.float_dtype = float_dtypes.get(key);
+    }
+
+    // Print the remapped keys if debug is enabled
+    if debug {
+        let mut remapped_keys = remapped_keys;
+        remapped_keys.sort();
+        println!("Debug information of keys and tensor shapes:\n---");
+        for (new_key, old_key) in remapped_keys {
+            if old_key != new_key {
+                println!("Original Key: {old_key}");
+                println!("Remapped Key: {new_key}");
+            } else {
+                println!("Key: {}", new_key);
+            }
+
+            let view = safetensors.get(&tensors[&new_key].name)?;
+            println!("Shape: {:?}", view.shape());
+            println!("Dtype: {:?}", view.dtype());
+            if let Some(float_dtype) = tensors[&new_key].float_dtype {
+                println!("Cast to: {float_dtype:?}");
+            }
+            println!("---");
+        }
+    }
+
+    // Convert the safetensors tensors to a nested value data structure
+    let nested_value = unflatten::<PS, _>(tensors)?;
+
+    // The safetensors checkpoints of the HuggingFace ecosystem have the PyTorch layout
+    let deserializer = Deserializer::<PyTorchAdapter<PS, B>>::new(nested_value, true);
+
+    // Deserialize the nested value into a record type
+    let value = D::deserialize(deserializer)?;
+    Ok(value)
+}
+
+/// The shard files of a sharded checkpoint, from its index.
+fn shard_files(path: &Path) -> Result<Vec<PathBuf>, Error> {
+    let index: Index = serde_json::from_reader(File::open(path)?)?;
+    let base_dir = path.parent().unwrap_or(Path::new(""));
+
+    // Each shard holds many tensors
+    let files = index
+        .weight_map
+        .into_values()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|file| base_dir.join(file))
+        .collect::<Vec<_>>();
+
+    if files.is_empty() {
+        return Err(Error::Other(format!(
+            "No shard in the index {}",
+            path.display()
+        )));
+    }
+
+    Ok(files)
+}
+
+/// A tensor of a safetensors file, read from its memory mapped shard.
+#[derive(new, Clone)]
+struct SafetensorsTensor {
+    name: String,
+    safetensors: Arc<MmapedSafetensors>,
+    /// The data type of the float values, `FloatElem` of the precision settings if `None`.
+    float_dtype: Option<DType>,
+}
+
+impl SafetensorsTensor {
+    /// Copies the tensor out of its shard, as a candle tensor.
+    fn read<PS: PrecisionSettings>(&self) -> Result<NestedValue, error::Error> {
+        let tensor = self
+            .safetensors
+            .load(&self.name, &Device::Cpu)
+            .map_err(|err| error::Error::Other(format!("Candle safetensors error: {err}")))?;
+
+        let tensor = CandleTensor {
+            tensor,
+            float_dtype: self.float_dtype,
+        };
+        tensor.serialize::<PS>(Serializer::new())
+    }
+}
+
+/// Serializes a safetensors tensor as a lazy value, so that each tensor is only copied when its
+/// field of the record is deserialized, instead of copying the whole checkpoint up front.
+impl Serializable for SafetensorsTensor {
+    fn serialize<PS>(&self, _serializer: Serializer) -> Result<NestedValue, error::Error>
+    where
+        PS: PrecisionSettings,
+    {
+        // A function pointer does not borrow the precision settings type
+        let read: fn(&SafetensorsTensor) -> Result<NestedValue, error::Error> = Self::read::<PS>;
+        let tensor = self.clone();
+
+        Ok(NestedValue::Lazy(LazyValue::new(move || read(&tensor))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burn::record::FullPrecisionSettings;
+    use candle_core::Tensor;
+
+    #[test]
+    fn should_copy_the_tensors_when_deserialized() {
+        let path = std::env::temp_dir().join("burn_import_safetensors_lazy.safetensors");
+        let tensor = Tensor::new(&[1.0f32, 2.0, 3.0], &Device::Cpu).unwrap();
+        candle_core::safetensors::save(&HashMap::from([("weight", tensor)]), &path).unwrap();
+        let safetensors = Arc::new(unsafe { MmapedSafetensors::new(&path).unwrap() });
+
+        let value = SafetensorsTensor::new("weight".into(), safetensors, None)
+            .serialize::<FullPrecisionSettings>(Serializer::new())
+            .unwrap();
+
+        assert!(matches!(value, NestedValue::Lazy(_)));
+        let param = value.resolve().unwrap().as_map().unwrap();
+        let data = param["param"].clone().as_map().unwrap();
+        assert_eq!(data["bytes"].clone().as_bytes().unwrap().len(), 12);
+    }
+}
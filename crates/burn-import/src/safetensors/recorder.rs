@@ -0,0 +1,462 @@
+use core::marker::PhantomData;
+use std::path::PathBuf;
+
+use burn::{
+    record::{DoublePrecisionSettings, PrecisionSettings, Record, Recorder, RecorderError},
+    tensor::{backend::Backend, Element},
+};
+
+use regex::Regex;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::pytorch::FloatDTypes;
+
+use super::reader::from_file;
+
+/// A recorder that loads safetensors files (`.safetensors`) into Burn modules.
+///
+/// The sharded checkpoints of the HuggingFace ecosystem are loaded from their index, e.g.
+/// `model.safetensors.index.json`, without concatenating the shards: the shards are memory mapped
+/// and each tensor is only copied out of its shard when its field of the record is deserialized.
+///
+/// LoadArgs can be used to remap keys.
+/// See [LoadArgs](struct.LoadArgs.html) for more information.
+#[derive(new, Debug, Default, Clone)]
+pub struct SafetensorsFileRecorder<PS: PrecisionSettings> {
+    _settings: PhantomData<PS>,
+}
+
+impl<PS: PrecisionSettings, B: Backend> Recorder<B> for SafetensorsFileRecorder<PS> {
+    type Settings = PS;
+    type RecordArgs = PathBuf;
+    type RecordOutput = ();
+    type LoadArgs = LoadArgs;
+
+    fn save_item<I: Serialize>(
+        &self,
+        _item: I,
+        _file: Self::RecordArgs,
+    ) -> Result<(), RecorderError> {
+        unimplemented!("save_item not implemented for SafetensorsFileRecorder")
+    }
+
+    fn load_item<I: DeserializeOwned>(&self, _file: Self::LoadArgs) -> Result<I, RecorderError> {
+        unimplemented!("load_item not implemented for SafetensorsFileRecorder")
+    }
+
+    fn load<R: Record<B>>(
+        &self,
+        args: Self::LoadArgs,
+        device: &B::Device,
+    ) -> Result<R, RecorderError> {
+        if !args.float_dtypes.is_default() {
+            // The values are rounded to their data type when read and kept in double precision
+            // until the record is converted to the float type of the backend
+            let float_dtypes = args.float_dtypes.or_dtype(PS::FloatElem::dtype());
+            let item = from_file::<DoublePrecisionSettings, R::Item<DoublePrecisionSettings>, B>(
+                &args.file,
+                args.key_remap,
+                &float_dtypes,
+                args.debug,
+            )?;
+            return Ok(R::from_item(item, device));
+        }
+
+        let item = from_file::<PS, R::Item<Self::Settings>, B>(
+            &args.file,
+            args.key_remap,
+            &FloatDTypes::new(),
+            args.debug,
+        )?;
+        Ok(R::from_item(item, device))
+    }
+}
+
+/// Arguments for loading a safetensors file.
+///
+/// # Fields
+///
+/// * `file` - The path to the file to load, or to the index of a sharded checkpoint.
+/// * `key_remap` - A vector of tuples containing a regular expression and a replacement string.
+///                See [regex::Regex::replace](https://docs.rs/regex/latest/regex/struct.Regex.html#method.replace)
+///                for more information.
+///
+/// # Examples
+///
+/// ```text
+/// use burn_import::safetensors::{LoadArgs, SafetensorsFileRecorder};
+/// use burn::record::FullPrecisionSettings;
+/// use burn::record::Recorder;
+///
+/// // Load the shards listed by the index, e.g. "model-00001-of-00030.safetensors"
+/// let args = LoadArgs::new("model.safetensors.index.json".into())
+///     .with_key_remap("^model\\.", "");
+///
+/// let record = SafetensorsFileRecorder::<FullPrecisionSettings>::default()
+///     .load(args, &device)
+///     .expect("Should decode state successfully");
+/// ```
+#[derive(Debug, Clone)]
+pub struct LoadArgs {
+    /// The path to the file to load.
+    pub file: PathBuf,
+
+    /// A list of key remappings.
+    pub key_remap: Vec<(Regex, String)>,
+
+    /// The data types of the float tensors, `FloatElem` of the precision settings by default.
+    pub float_dtypes: FloatDTypes,
+
+    /// Whether to print debug information.
+    pub debug: bool,
+}
+
+impl LoadArgs {
+    /// Creates a new `LoadArgs` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `file` - The path to the `.safetensors` file, or to the `.index.json` file of the shards.
+    pub fn new(file: PathBuf) -> Self {
+        Self {
+            file,
+            key_remap: Vec::new(),
+            float_dtypes: FloatDTypes::new(),
+            debug: false,
+        }
+    }
+
+    /// Sets key remapping.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The Regex pattern to be replaced.
+    /// * `replacement` - The pattern to replace with.
+    ///
+    /// See [Regex](https://docs.rs/regex/1.5.4/regex/#syntax) for the pattern syntax and
+    /// [Replacement](https://docs.rs/regex/latest/regex/struct.Regex.html#method.replace) for the
+    /// replacement syntax.
+    pub fn with_key_remap(mut self, pattern: &str, replacement: &str) -> Self {
+        let regex = Regex::new(pattern).expect("Valid regex");
+
+        self.key_remap.push((regex, replacement.into()));
+        self
+    }
+
+    /// Sets the data types of the float tensors when read.
+    ///
+    /// # Arguments
+    ///
+    /// * `float_dtypes` - The data types, e.g. `FloatDTypes::new().with_dtype(DType::BF16)`.
+    pub fn with_float_dtypes(mut self, float_dtypes: FloatDTypes) -> Self {
+        self.float_dtypes = float_dtypes;
+        self
+    }
+
+    /// Sets printing debug information on.
+    pub fn with_debug_print(mut self) -> Self {
+        self.debug = true;
+        self
+    }
+}
+
+impl From<PathBuf> for LoadArgs {
+    fn from(val: PathBuf) -> Self {
+        LoadArgs::new(val)
+    }
+}
+
+impl From<String> for LoadArgs {
+    fn from(val: String) -> Self {
+        LoadArgs::new(val.into())
+    }
+}
+
+impl From<&str> for LoadArgs {
+    fn from(val: &str) -> Self {
+        LoadArgs::new(val.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burn::{
+        backend::NdArray,
+        module::{Module, Param, ParamId},
+        nn::{Linear, LinearConfig},
+        record::{FullPrecisionSettings, HalfPrecisionSettings},
+        tensor::{DType, Int, Tensor},
+    };
+    use half::{bf16, f16};
+
+    type TestBackend = NdArray<f32>;
+
+    #[derive(Module, Debug)]
+    struct Model<B: Backend> {
+        norm: Param<Tensor<B, 1>>,
+        weight: Param<Tensor<B, 1>>,
+        embed: Param<Tensor<B, 1>>,
+    }
+
+    #[derive(Module, Debug)]
+    struct LinearModel<B: Backend> {
+        linear: Linear<B>,
+        steps: Param<Tensor<B, 1, Int>>,
+    }
+
+    /// A tensor `(name, dtype, shape, bytes)` of a safetensors file.
+    type TensorEntry<K> = (K, &'static str, Vec<usize>, Vec<u8>);
+
+    /// Writes the tensors to a safetensors file.
+    fn write_safetensors<K: AsRef<str>>(name: &str, tensors: &[TensorEntry<K>]) -> PathBuf {
+        let mut header = serde_json::Map::new();
+        let mut data = Vec::new();
+        for (key, dtype, shape, bytes) in tensors {
+            header.insert(
+                key.as_ref().to_string(),
+                serde_json::json!({
+                    "dtype": dtype,
+                    "shape": shape,
+                    "data_offsets": [data.len(), data.len() + bytes.len()],
+                }),
+            );
+            data.extend_from_slice(bytes);
+        }
+        let header = serde_json::to_vec(&header).unwrap();
+
+        let path = std::env::temp_dir().join(name);
+        let mut file = (header.len() as u64).to_le_bytes().to_vec();
+        file.extend(header);
+        file.extend(data);
+        std::fs::write(&path, file).unwrap();
+        path
+    }
+
+    fn f32_bytes(values: &[f32]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect()
+    }
+
+    fn f16_bytes(values: &[f32]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| f16::from_f32(*value).to_le_bytes())
+            .collect()
+    }
+
+    fn bf16_bytes(values: &[f32]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| bf16::from_f32(*value).to_le_bytes())
+            .collect()
+    }
+
+    fn f64_bytes(values: &[f64]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect()
+    }
+
+    fn i64_bytes(values: &[i64]) -> Vec<u8> {
+        values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect()
+    }
+
+    fn load(args: LoadArgs) -> Model<TestBackend> {
+        let device = Default::default();
+        let record = SafetensorsFileRecorder::<HalfPrecisionSettings>::default()
+            .load(args, &device)
+            .expect("Should load the record");
+        let zeros = || Param::from_tensor(Tensor::zeros([2], &device));
+
+        Model {
+            norm: zeros(),
+            weight: zeros(),
+            embed: zeros(),
+        }
+        .load_record(record)
+    }
+
+    fn load_linear(args: LoadArgs) -> LinearModel<TestBackend> {
+        let device = Default::default();
+        let record = SafetensorsFileRecorder::<FullPrecisionSettings>::default()
+            .load(args, &device)
+            .expect("Should load the record");
+
+        LinearModel {
+            linear: LinearConfig::new(2, 3).init(&device),
+            steps: Param::initialized(ParamId::new(), Tensor::zeros([2], &device)),
+        }
+        .load_record(record)
+    }
+
+    fn values(param: Param<Tensor<TestBackend, 1>>) -> Vec<f32> {
+        param.val().into_data().to_vec().unwrap()
+    }
+
+    /// The tensors of a linear module in the PyTorch layout, whose weight is `[d_output, d_input]`.
+    fn linear_tensors(prefix: &str) -> Vec<TensorEntry<String>> {
+        let key = |name: &str| format!("{prefix}{name}");
+
+        vec![
+            (
+                key("linear.weight"),
+                "F32",
+                vec![3, 2],
+                f32_bytes(&[1.0, 4.0, 2.0, 5.0, 3.0, 6.0]),
+            ),
+            (
+                key("linear.bias"),
+                "F32",
+                vec![3],
+                f32_bytes(&[0.5, -0.5, 0.0]),
+            ),
+            (key("steps"), "I64", vec![2], i64_bytes(&[7, -3])),
+        ]
+    }
+
+    fn assert_linear(model: LinearModel<TestBackend>) {
+        let weight = model.linear.weight.val().into_data();
+        let bias = model.linear.bias.unwrap().val().into_data();
+        let steps = model.steps.val().into_data();
+
+        // The weight is transposed to the Burn layout `[d_input, d_output]`
+        assert_eq!(weight.shape, vec![2, 3]);
+        assert_eq!(
+            weight.to_vec::<f32>().unwrap(),
+            vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]
+        );
+        assert_eq!(bias.to_vec::<f32>().unwrap(), vec![0.5, -0.5, 0.0]);
+        assert_eq!(steps.to_vec::<i64>().unwrap(), vec![7, -3]);
+    }
+
+    #[test]
+    fn should_load_a_linear_module_with_the_pytorch_layout() {
+        let path = write_safetensors(
+            "burn_import_safetensors_linear.safetensors",
+            &linear_tensors(""),
+        );
+
+        assert_linear(load_linear(LoadArgs::new(path)));
+    }
+
+    #[test]
+    fn should_remap_the_keys() {
+        let path = write_safetensors(
+            "burn_import_safetensors_remap.safetensors",
+            &linear_tensors("model.layers.0."),
+        );
+
+        let args = LoadArgs::new(path).with_key_remap("^model\\.layers\\.0\\.", "");
+
+        assert_linear(load_linear(args));
+    }
+
+    #[test]
+    fn should_load_the_shards_of_an_index() {
+        let dir = std::env::temp_dir().join("burn_import_safetensors_shards");
+        std::fs::create_dir_all(&dir).unwrap();
+        let [weight, bias, steps]: [TensorEntry<String>; 3] =
+            linear_tensors("").try_into().unwrap();
+        let shard_1 = write_safetensors(
+            "burn_import_safetensors_shards/model-00001-of-00002.safetensors",
+            &[weight, bias],
+        );
+        let shard_2 = write_safetensors(
+            "burn_import_safetensors_shards/model-00002-of-00002.safetensors",
+            &[steps],
+        );
+        assert!(shard_1.exists() && shard_2.exists());
+
+        let index = dir.join("model.safetensors.index.json");
+        let weight_map = serde_json::json!({
+            "weight_map": {
+                "linear.weight": "model-00001-of-00002.safetensors",
+                "linear.bias": "model-00001-of-00002.safetensors",
+                "steps": "model-00002-of-00002.safetensors",
+            }
+        });
+        std::fs::write(&index, weight_map.to_string()).unwrap();
+
+        assert_linear(load_linear(LoadArgs::new(index)));
+    }
+
+    #[test]
+    fn should_convert_the_float_dtypes() {
+        let path = write_safetensors(
+            "burn_import_safetensors_dtypes.safetensors",
+            &[
+                ("norm", "BF16", vec![2], bf16_bytes(&[1.5, -2.0])),
+                ("weight", "F64", vec![2], f64_bytes(&[0.25, 3.0])),
+                ("embed", "F16", vec![2], f16_bytes(&[0.5, 4.0])),
+            ],
+        );
+
+        let model = load(LoadArgs::new(path));
+
+        assert_eq!(values(model.norm), vec![1.5, -2.0]);
+        assert_eq!(values(model.weight), vec![0.25, 3.0]);
+        assert_eq!(values(model.embed), vec![0.5, 4.0]);
+    }
+
+    #[test]
+    fn should_fail_on_a_missing_tensor() {
+        let path = write_safetensors(
+            "burn_import_safetensors_missing.safetensors",
+            &[("norm", "F32", vec![2], f32_bytes(&[1.0, 2.0]))],
+        );
+        let device = Default::default();
+
+        let result = SafetensorsFileRecorder::<FullPrecisionSettings>::default()
+            .load::<LinearModelRecord<TestBackend>>(LoadArgs::new(path), &device);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_keep_the_precision_of_the_overridden_tensors() {
+        let path = write_safetensors(
+            "burn_import_safetensors_dtype_override.safetensors",
+            &[
+                ("norm", "F32", vec![2], f32_bytes(&[1.0001, 2.0])),
+                ("weight", "F32", vec![2], f32_bytes(&[1.0001, 3.0])),
+                ("embed", "F16", vec![2], f16_bytes(&[0.1, 0.2])),
+            ],
+        );
+
+        let args = LoadArgs::new(path)
+            .with_float_dtypes(FloatDTypes::new().with_override("norm|embed", DType::F32));
+        let model = load(args);
+
+        // The other tensors are rounded to the half precision of the recorder settings
+        assert_eq!(values(model.norm), vec![1.0001, 2.0]);
+        assert_eq!(values(model.weight), vec![1.0, 3.0]);
+        assert_eq!(
+            values(model.embed),
+            vec![f16::from_f32(0.1).to_f32(), f16::from_f32(0.2).to_f32()]
+        );
+    }
+
+    #[test]
+    fn should_round_the_tensors_to_the_precision_settings_by_default() {
+        let path = write_safetensors(
+            "burn_import_safetensors_dtype_default.safetensors",
+            &[
+                ("norm", "F32", vec![2], f32_bytes(&[1.0001, 2.0])),
+                ("weight", "F32", vec![2], f32_bytes(&[1.0001, 3.0])),
+                ("embed", "F16", vec![2], f16_bytes(&[0.1, 0.2])),
+            ],
+        );
+
+        let model = load(LoadArgs::new(path));
+
+        assert_eq!(values(model.norm), vec![1.0, 2.0]);
+        assert_eq!(values(model.weight), vec![1.0, 3.0]);
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use candle_core::{
+    pickle::{Object, Stack, TensorInfo},
+    DType, Device, Tensor,
+};
+use zip::ZipArchive;
+
+use super::parser::{parse_forwards, Forward};
+
+/// The content of a TorchScript archive (`.pt`), as saved by `torch.jit.save`.
+///
+/// The archive contains the code of the modules, `code/**.py`, and the pickled modules with their
+/// attributes, `data.pkl`, whose tensors are stored in `data/`.
+pub(crate) struct Archive {
+    zip: ZipArchive<BufReader<File>>,
+    /// The forward methods by qualified class name, e.g.
+    /// `__torch__.torch.nn.modules.linear.Linear`.
+    forwards: HashMap<String, Forward>,
+    /// The qualified class names of the modules by path, the root module having an empty path.
+    modules: HashMap<String, String>,
+    /// The tensors by path, e.g. `fc.weight`.
+    tensors: HashMap<String, TensorInfo>,
+}
+
+impl Archive {
+    /// Reads the code and the modules of a TorchScript archive.
+    pub fn open(path: &Path) -> Self {
+        let file = File::open(path).unwrap_or_else(|err| panic!("Could not open {path:?}: {err}"));
+        let mut zip = ZipArchive::new(BufReader::new(file))
+            .unwrap_or_else(|err| panic!("Invalid TorchScript archive {path:?}: {err}"));
+
+        let names = zip.file_names().map(String::from).collect::<Vec<_>>();
+
+        // The code files are named after the namespace of their classes, e.g.
+        // `model/code/__torch__/torch/nn/modules/linear.py`
+        let mut forwards = HashMap::new();
+        for name in names.iter().filter(|name| name.ends_with(".py")) {
+            let Some((_, file)) = name.split_once("/code/") else {
+                continue;
+            };
+            let namespace = file.trim_end_matches(".py").replace('/', ".");
+            let source = read_to_string(&mut zip, name);
+
+            for (class, forward) in parse_forwards(&source) {
+                forwards.insert(format!("{namespace}.{class}"), forward);
+            }
+        }
+
+        let data_pkl = names
+            .iter()
+            .find(|name| name.ends_with("/data.pkl"))
+            .expect("data.pkl not found in the TorchScript archive")
+            .clone();
+
+        let object = {
+            let mut reader = BufReader::new(zip.by_name(&data_pkl).unwrap());
+            let mut stack = Stack::empty();
+            stack
+                .read_loop(&mut reader)
+                .and_then(|_| stack.finalize())
+                .unwrap_or_else(|err| panic!("Invalid {data_pkl}: {err}"))
+        };
+
+        // The storages of the tensors are stored next to data.pkl, in `data/`
+        let dir_name = Path::new(data_pkl.trim_end_matches(".pkl"));
+
+        let mut modules = HashMap::new();
+        let mut tensors = HashMap::new();
+        collect_modules(object, "", dir_name, &mut modules, &mut tensors);
+
+        Self {
+            zip,
+            forwards,
+            modules,
+            tensors,
+        }
+    }
+
+    /// The qualified class name of the module at the given path.
+    pub fn class(&self, path: &str) -> &str {
+        self.modules
+            .get(path)
+            .unwrap_or_else(|| panic!("TorchScript: module {path:?} not found"))
+    }
+
+    /// The forward method of the module at the given path.
+    pub fn forward(&self, path: &str) -> Forward {
+        let class = self.class(path);
+
+        self.forwards
+            .get(class)
+            .unwrap_or_else(|| panic!("TorchScript: the code of {class} was not found"))
+            .clone()
+    }
+
+    pub fn is_module(&self, path: &str) -> bool {
+        self.modules.contains_key(path)
+    }
+
+    pub fn is_tensor(&self, path: &str) -> bool {
+        self.tensors.contains_key(path)
+    }
+
+    /// The shape and the values, converted to `f32`, of the tensor at the given path.
+    pub fn tensor(&mut self, path: &str) -> Option<(Vec<usize>, Vec<f32>)> {
+        let info = self.tensors.get(path)?.clone();
+
+        if !info.layout.is_contiguous() {
+            panic!("TorchScript: the tensor {path} is not contiguous");
+        }
+
+        let mut bytes = Vec::new();
+        self.zip
+            .by_name(&info.path)
+            .and_then(|mut file| Ok(file.read_to_end(&mut bytes)?))
+            .unwrap_or_else(|err| panic!("Could not read the storage of {path}: {err}"));
+
+        let shape = info.layout.shape().dims().to_vec();
+        let size = info.dtype.size_in_bytes();
+        let start = info.layout.start_offset() * size;
+        let end = start + shape.iter().product::<usize>() * size;
+
+        let values = Tensor::from_raw_buffer(&bytes[start..end], info.dtype, &shape, &Device::Cpu)
+            .and_then(|tensor| tensor.flatten_all()?.to_dtype(DType::F32)?.to_vec1::<f32>())
+            .unwrap_or_else(|err| panic!("Could not read the tensor {path}: {err}"));
+
+        Some((shape, values))
+    }
+}
+
+fn read_to_string(zip: &mut ZipArchive<BufReader<File>>, name: &str) -> String {
+    let mut source = String::new();
+    zip.by_name(name)
+        .and_then(|mut file| Ok(file.read_to_string(&mut source)?))
+        .unwrap_or_else(|err| panic!("Could not read {name}: {err}"));
+
+    source
+}
+
+/// Collects the classes of the modules and the tensors of a pickled module recursively.
+///
+/// A module is pickled as an object of its class whose state is the dictionary of its attributes,
+/// which are either submodules, tensors or values.
+fn collect_modules(
+    object: Object,
+    path: &str,
+    dir_name: &Path,
+    modules: &mut HashMap<String, String>,
+    tensors: &mut HashMap<String, TensorInfo>,
+) {
+    let Object::Build { callable, args } = object else {
+        return;
+    };
+    let Some(class) = class_name(&callable) else {
+        return;
+    };
+    modules.insert(path.to_string(), class);
+
+    let Object::Dict(attributes) = *args else {
+        return;
+    };
+
+    for (name, value) in attributes {
+        let Object::Unicode(name) = name else {
+            continue;
+        };
+        let path = match path.is_empty() {
+            true => name,
+            false => format!("{path}.{name}"),
+        };
+
+        match value
+            .clone()
+            .into_tensor_info(Object::Unicode(path.clone()), dir_name)
+        {
+            Ok(Some(info)) => {
+                tensors.insert(path, info);
+            }
+            _ => collect_modules(value, &path, dir_name, modules, tensors),
+        }
+    }
+}
+
+/// The qualified name of the class of a pickled object, e.g. `__torch__.Net`.
+fn class_name(callable: &Object) -> Option<String> {
+    match callable {
+        Object::Class {
+            module_name,
+            class_name,
+        } => Some(format!("{module_name}.{class_name}")),
+        Object::Reduce { callable, .. } => class_name(callable),
+        _ => None,
+    }
+}
@@ -0,0 +1,1029 @@
+use std::collections::HashMap;
+
+use onnx_ir::{
+    dim_inference,
+    ir::{
+        partial_shape, ArgType, Argument, AttributeValue, Attributes, Data, ElementType, Node,
+        NodeType, OnnxGraph, Tensor, TensorType,
+    },
+};
+
+use super::{
+    archive::Archive,
+    parser::{Expr, Stmt},
+};
+
+/// A value of the interpreted TorchScript code.
+#[derive(Debug, Clone)]
+enum Value {
+    /// A tensor computed by the graph.
+    Tensor(Argument),
+    /// A parameter or a buffer of a module, by path.
+    Param(String),
+    /// A module, by path.
+    Module(String),
+    /// The forward method of a module, by path.
+    Forward(String),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Str(String),
+    None,
+    List(Vec<Value>),
+    /// A function or a type, e.g. `torch.relu` or `List`, by qualified name.
+    Function(String),
+}
+
+/// Builds the graph of a TorchScript module by interpreting its forward method.
+///
+/// The operations of the traced code are recorded as ONNX nodes, which are then converted by the
+/// ONNX pipeline.
+pub(crate) struct GraphBuilder {
+    archive: Archive,
+    nodes: Vec<Node>,
+    node_name_counter: HashMap<NodeType, usize>,
+}
+
+impl GraphBuilder {
+    pub fn new(archive: Archive) -> Self {
+        Self {
+            archive,
+            nodes: Vec::new(),
+            node_name_counter: HashMap::new(),
+        }
+    }
+
+    /// Builds the graph of the root module for float inputs of the given ranks.
+    pub fn build(mut self, input_ranks: &[usize]) -> OnnxGraph {
+        let inputs = input_ranks
+            .iter()
+            .enumerate()
+            .map(|(i, rank)| Argument {
+                name: format!("input{}", i + 1),
+                ty: ArgType::Tensor(TensorType {
+                    elem_type: ElementType::Float32,
+                    dim: *rank,
+                    shape: None,
+                }),
+                value: None,
+                passed: true,
+            })
+            .collect::<Vec<_>>();
+
+        let args = inputs.iter().cloned().map(Value::Tensor).collect();
+        let outputs = match self.call_module("", args) {
+            Value::List(values) => values,
+            value => vec![value],
+        };
+        let outputs = outputs
+            .iter()
+            .map(|value| self.tensor(value))
+            .collect::<Vec<_>>();
+
+        // The nodes follow the semantics of the operator set 16
+        OnnxGraph {
+            nodes: self.nodes,
+            inputs,
+            outputs,
+            opset_version: 16,
+        }
+    }
+
+    fn call_module(&mut self, path: &str, args: Vec<Value>) -> Value {
+        let forward = self.archive.forward(path);
+
+        if forward.params.len() != args.len() {
+            panic!(
+                "TorchScript: the forward method of {:?} expects {} inputs, got {}",
+                self.archive.class(path),
+                forward.params.len(),
+                args.len()
+            );
+        }
+
+        let mut scope = forward
+            .params
+            .into_iter()
+            .zip(args)
+            .collect::<HashMap<_, _>>();
+        scope.insert("self".to_string(), Value::Module(path.to_string()));
+
+        for stmt in forward.body.iter() {
+            match stmt {
+                Stmt::Assign(targets, expr) => {
+                    let value = self.eval(expr, &scope);
+
+                    match (targets.len(), value) {
+                        (0, _) => {}
+                        (1, value) => {
+                            scope.insert(targets[0].clone(), value);
+                        }
+                        (_, Value::List(values)) if values.len() == targets.len() => {
+                            scope.extend(targets.iter().cloned().zip(values));
+                        }
+                        (_, value) => panic!("TorchScript: cannot unpack {value:?} to {targets:?}"),
+                    }
+                }
+                Stmt::Return(expr) => return self.eval(expr, &scope),
+            }
+        }
+
+        Value::None
+    }
+
+    fn eval(&mut self, expr: &Expr, scope: &HashMap<String, Value>) -> Value {
+        match expr {
+            Expr::Name(name) => match name.as_str() {
+                "True" => Value::Bool(true),
+                "False" => Value::Bool(false),
+                "None" => Value::None,
+                // The names which are not variables are the namespaces and the builtins
+                _ => scope
+                    .get(name)
+                    .cloned()
+                    .unwrap_or_else(|| Value::Function(name.clone())),
+            },
+            Expr::Int(value) => Value::Int(*value),
+            Expr::Float(value) => Value::Float(*value),
+            Expr::Str(value) => Value::Str(value.clone()),
+            Expr::List(items) => {
+                Value::List(items.iter().map(|item| self.eval(item, scope)).collect())
+            }
+            Expr::Attr(object, name) => match self.eval(object, scope) {
+                Value::Module(path) => self.attribute(&path, name),
+                Value::Function(function) => Value::Function(format!("{function}.{name}")),
+                value => panic!("TorchScript: unsupported attribute {name} of {value:?}"),
+            },
+            Expr::Index(object, index) => match (self.eval(object, scope), self.eval(index, scope))
+            {
+                (Value::List(values), Value::Int(index)) => {
+                    let index = match index < 0 {
+                        true => values.len() as i64 + index,
+                        false => index,
+                    };
+                    values[index as usize].clone()
+                }
+                // The type parameters of the annotations, e.g. `List[Tensor]`
+                (Value::Function(name), _) => Value::Function(name),
+                (value, index) => panic!("TorchScript: cannot index {value:?} with {index:?}"),
+            },
+            Expr::Call {
+                callee,
+                args,
+                kwargs,
+            } => {
+                let callee = self.eval(callee, scope);
+                let args = args.iter().map(|arg| self.eval(arg, scope)).collect();
+                let kwargs = kwargs
+                    .iter()
+                    .map(|(name, arg)| (name.clone(), self.eval(arg, scope)))
+                    .collect();
+
+                self.call(callee, args, kwargs)
+            }
+        }
+    }
+
+    /// The attribute of a module: a submodule, a tensor or the forward method.
+    fn attribute(&self, path: &str, name: &str) -> Value {
+        let child = match path.is_empty() {
+            true => name.to_string(),
+            false => format!("{path}.{name}"),
+        };
+
+        if self.archive.is_module(&child) {
+            Value::Module(child)
+        } else if self.archive.is_tensor(&child) {
+            Value::Param(child)
+        } else if name == "forward" {
+            Value::Forward(path.to_string())
+        } else if name == "training" {
+            // The modules are imported for inference
+            Value::Bool(false)
+        } else {
+            panic!("TorchScript: attribute {child} not found")
+        }
+    }
+
+    fn call(&mut self, callee: Value, args: Vec<Value>, kwargs: Vec<(String, Value)>) -> Value {
+        let name = match callee {
+            Value::Forward(path) => return self.call_module(&path, args),
+            Value::Function(name) => name,
+            value => panic!("TorchScript: {value:?} is not callable"),
+        };
+
+        match name.as_str() {
+            "getattr" => match (&args[0], &args[1]) {
+                (Value::Module(path), Value::Str(name)) => self.attribute(path, name),
+                _ => panic!("TorchScript: unsupported getattr({args:?})"),
+            },
+            // Type annotations and conversions of scalars
+            "annotate" => args[1].clone(),
+            "int" | "float" => args[0].clone(),
+            _ => self.operation(Arguments { name, args, kwargs }),
+        }
+    }
+
+    /// Records an operation of the `torch` namespace as ONNX nodes.
+    fn operation(&mut self, args: Arguments) -> Value {
+        // The in-place variants, e.g. `torch.relu_`, are recorded as their out-of-place variants
+        let name = args.name.trim_start_matches("torch.").trim_end_matches('_');
+
+        let output = match name {
+            "relu" | "sigmoid" | "tanh" | "exp" | "neg" | "sqrt" => {
+                let node_type = match name {
+                    "relu" => NodeType::Relu,
+                    "sigmoid" => NodeType::Sigmoid,
+                    "tanh" => NodeType::Tanh,
+                    "exp" => NodeType::Exp,
+                    "neg" => NodeType::Neg,
+                    _ => NodeType::Sqrt,
+                };
+                let input = self.tensor(args.value(0, "input"));
+
+                self.node(node_type, vec![input], Attributes::new())
+            }
+            "gelu" => {
+                if !matches!(args.str(1, "approximate"), None | Some("none")) {
+                    panic!("TorchScript: only the exact gelu is supported");
+                }
+                let input = self.tensor(args.value(0, "input"));
+
+                self.node(NodeType::Gelu, vec![input], Attributes::new())
+            }
+            "softmax" | "log_softmax" => {
+                let node_type = match name {
+                    "softmax" => NodeType::Softmax,
+                    _ => NodeType::LogSoftmax,
+                };
+                let input = self.tensor(args.value(0, "input"));
+                let axis = args.int(1, "dim").expect("softmax dim");
+
+                self.node(
+                    node_type,
+                    vec![input],
+                    attrs([("axis", AttributeValue::Int64(axis))]),
+                )
+            }
+            "add" | "sub" | "mul" | "div" => {
+                let supported = match name {
+                    "add" | "sub" => args.float(2, "alpha").unwrap_or(1.0) == 1.0,
+                    "div" => args.get(2, "rounding_mode").is_none(),
+                    _ => true,
+                };
+                if !supported {
+                    panic!("TorchScript: {name} with alpha or rounding_mode is not supported");
+                }
+                let node_type = match name {
+                    "add" => NodeType::Add,
+                    "sub" => NodeType::Sub,
+                    "mul" => NodeType::Mul,
+                    _ => NodeType::Div,
+                };
+                let lhs = self.tensor(args.value(0, "input"));
+                let rhs = self.tensor(args.value(1, "other"));
+
+                self.node(node_type, vec![lhs, rhs], Attributes::new())
+            }
+            "matmul" => {
+                let lhs = self.tensor(args.value(0, "input"));
+                let rhs = self.tensor(args.value(1, "other"));
+
+                self.node(NodeType::MatMul, vec![lhs, rhs], Attributes::new())
+            }
+            "linear" => {
+                let input = self.tensor(args.value(0, "input"));
+                // The weight is stored as [out, in] by PyTorch and as [in, out] by Burn
+                let mut weight = self.weight(args.value(1, "weight"));
+                transpose_weight(&mut weight);
+
+                let mut inputs = vec![input, weight];
+                if let Some(bias) = args.get(2, "bias") {
+                    inputs.push(self.weight(bias));
+                }
+
+                self.node(NodeType::Linear, inputs, Attributes::new())
+            }
+            "_convolution" | "conv1d" | "conv2d" => {
+                if name == "_convolution" && args.bool(6, "transposed") == Some(true) {
+                    panic!("TorchScript: transposed convolutions are not supported");
+                }
+                let groups = match name {
+                    "_convolution" => args.int(8, "groups"),
+                    _ => args.int(6, "groups"),
+                };
+
+                let input = self.tensor(args.value(0, "input"));
+                let weight = self.weight(args.value(1, "weight"));
+                let shape = weight_shape(&weight);
+                let kernel_shape = shape[2..].iter().map(|k| *k as i64).collect::<Vec<_>>();
+                let rank = kernel_shape.len();
+
+                let node_type = match rank {
+                    1 => NodeType::Conv1d,
+                    2 => NodeType::Conv2d,
+                    _ => panic!("TorchScript: only 1d and 2d convolutions are supported"),
+                };
+
+                let padding = expand(args.ints(4, "padding"), rank, 0);
+                let attributes = attrs([
+                    ("kernel_shape", AttributeValue::Int64s(kernel_shape)),
+                    (
+                        "strides",
+                        AttributeValue::Int64s(expand(args.ints(3, "stride"), rank, 1)),
+                    ),
+                    (
+                        "pads",
+                        AttributeValue::Int64s([padding.clone(), padding].concat()),
+                    ),
+                    (
+                        "dilations",
+                        AttributeValue::Int64s(expand(args.ints(5, "dilation"), rank, 1)),
+                    ),
+                    ("group", AttributeValue::Int64(groups.unwrap_or(1))),
+                ]);
+
+                let mut inputs = vec![input, weight];
+                if let Some(bias) = args.get(2, "bias") {
+                    inputs.push(self.weight(bias));
+                }
+
+                self.node(node_type, inputs, attributes)
+            }
+            "max_pool2d" | "avg_pool2d" => {
+                let input = self.tensor(args.value(0, "input"));
+                let kernel_shape = expand(args.ints(1, "kernel_size"), 2, 1);
+                // The stride defaults to the kernel size
+                let strides = match args.ints(2, "stride") {
+                    Some(stride) if !stride.is_empty() => expand(Some(stride), 2, 1),
+                    _ => kernel_shape.clone(),
+                };
+                let padding = expand(args.ints(3, "padding"), 2, 0);
+
+                let mut attributes = attrs([
+                    ("kernel_shape", AttributeValue::Int64s(kernel_shape)),
+                    ("strides", AttributeValue::Int64s(strides)),
+                    (
+                        "pads",
+                        AttributeValue::Int64s([padding.clone(), padding].concat()),
+                    ),
+                ]);
+
+                let node_type = match name {
+                    "max_pool2d" => {
+                        if args.bool(5, "ceil_mode") == Some(true) {
+                            panic!("TorchScript: max_pool2d with ceil_mode is not supported");
+                        }
+                        attributes.insert(
+                            "dilations".to_string(),
+                            AttributeValue::Int64s(expand(args.ints(4, "dilation"), 2, 1)),
+                        );
+                        NodeType::MaxPool2d
+                    }
+                    _ => {
+                        let ceil_mode = args.bool(4, "ceil_mode").unwrap_or(false);
+                        let count_include_pad = args.bool(5, "count_include_pad").unwrap_or(true);
+                        attributes.insert(
+                            "ceil_mode".to_string(),
+                            AttributeValue::Int64(ceil_mode as i64),
+                        );
+                        attributes.insert(
+                            "count_include_pad".to_string(),
+                            AttributeValue::Int64(count_include_pad as i64),
+                        );
+                        NodeType::AveragePool2d
+                    }
+                };
+
+                self.node(node_type, vec![input], attributes)
+            }
+            "batch_norm" => {
+                let input = self.tensor(args.value(0, "input"));
+                let mut inputs = vec![input];
+                for (index, name) in [
+                    (1, "weight"),
+                    (2, "bias"),
+                    (3, "running_mean"),
+                    (4, "running_var"),
+                ] {
+                    inputs.push(self.weight(args.value(index, name)));
+                }
+                let attributes = attrs([
+                    (
+                        "momentum",
+                        AttributeValue::Float32(args.float(6, "momentum").unwrap_or(0.1) as f32),
+                    ),
+                    (
+                        "epsilon",
+                        AttributeValue::Float32(args.float(7, "eps").unwrap_or(1e-5) as f32),
+                    ),
+                ]);
+
+                self.node(NodeType::BatchNormalization, inputs, attributes)
+            }
+            "layer_norm" => {
+                if args.ints(1, "normalized_shape").map(|shape| shape.len()) != Some(1) {
+                    panic!("TorchScript: layer_norm is only supported on the last dimension");
+                }
+                let input = self.tensor(args.value(0, "input"));
+                let mut inputs = vec![input, self.weight(args.value(2, "weight"))];
+                if let Some(bias) = args.get(3, "bias") {
+                    inputs.push(self.weight(bias));
+                }
+                let attributes = attrs([
+                    ("axis", AttributeValue::Int64(-1)),
+                    (
+                        "epsilon",
+                        AttributeValue::Float32(args.float(4, "eps").unwrap_or(1e-5) as f32),
+                    ),
+                ]);
+
+                self.node(NodeType::LayerNormalization, inputs, attributes)
+            }
+            "flatten" => {
+                let input = self.tensor(args.value(0, "input"));
+                let rank = input.ty.rank() as i64;
+                let start = normalize_axis(args.int(1, "start_dim").unwrap_or(0), rank);
+                let end = normalize_axis(args.int(2, "end_dim").unwrap_or(-1), rank);
+
+                if end != rank - 1 {
+                    panic!("TorchScript: flatten is only supported up to the last dimension");
+                }
+
+                self.node(
+                    NodeType::Flatten,
+                    vec![input],
+                    attrs([("axis", AttributeValue::Int64(start))]),
+                )
+            }
+            "reshape" | "view" => {
+                let input = self.tensor(args.value(0, "input"));
+                let shape = args.ints(1, "shape").unwrap_or_else(|| {
+                    panic!("TorchScript: {name} is only supported with a constant shape")
+                });
+                let shape = Argument::from(AttributeValue::Int64s(shape));
+
+                self.node(NodeType::Reshape, vec![input, shape], Attributes::new())
+            }
+            "transpose" | "permute" => {
+                let input = self.tensor(args.value(0, "input"));
+                let rank = input.ty.rank() as i64;
+                let perm = match name {
+                    "transpose" => {
+                        let dim0 =
+                            normalize_axis(args.int(1, "dim0").expect("transpose dim0"), rank);
+                        let dim1 =
+                            normalize_axis(args.int(2, "dim1").expect("transpose dim1"), rank);
+                        let mut perm = (0..rank).collect::<Vec<_>>();
+                        perm.swap(dim0 as usize, dim1 as usize);
+                        perm
+                    }
+                    _ => args
+                        .ints(1, "dims")
+                        .expect("permute dims")
+                        .into_iter()
+                        .map(|axis| normalize_axis(axis, rank))
+                        .collect(),
+                };
+
+                self.node(
+                    NodeType::Transpose,
+                    vec![input],
+                    attrs([("perm", AttributeValue::Int64s(perm))]),
+                )
+            }
+            "cat" => {
+                let inputs = match args.value(0, "tensors") {
+                    Value::List(values) => values.iter().map(|value| self.tensor(value)).collect(),
+                    value => panic!("TorchScript: cat expects a list of tensors, got {value:?}"),
+                };
+                let axis = args.int(1, "dim").unwrap_or(0);
+
+                self.node(
+                    NodeType::Concat,
+                    inputs,
+                    attrs([("axis", AttributeValue::Int64(axis))]),
+                )
+            }
+            "dropout" | "feature_dropout" => {
+                if args.bool(2, "train") == Some(true) {
+                    panic!("TorchScript: the module must be traced in eval mode");
+                }
+                return args.value(0, "input").clone();
+            }
+            "contiguous" | "detach" | "clone" => return args.value(0, "input").clone(),
+            _ => panic!("TorchScript: unsupported operation {}", args.name),
+        };
+
+        Value::Tensor(output)
+    }
+
+    /// The tensor of a value, the parameters and the scalars being recorded as constants.
+    fn tensor(&mut self, value: &Value) -> Argument {
+        let value = match value {
+            Value::Tensor(argument) => return argument.clone(),
+            Value::Param(path) => {
+                let (shape, values) = self.archive.tensor(path).unwrap();
+
+                AttributeValue::Tensor(Tensor {
+                    elem_type: ElementType::Float32,
+                    dim: shape.len(),
+                    data: Some(Data::Float32s(values)),
+                    shape: Some(shape),
+                })
+            }
+            Value::Int(value) => AttributeValue::Float32(*value as f32),
+            Value::Float(value) => AttributeValue::Float32(*value as f32),
+            value => panic!("TorchScript: {value:?} is not a tensor"),
+        };
+
+        self.node(NodeType::Constant, Vec::new(), attrs([("value", value)]))
+    }
+
+    /// The argument of a weight, whose value is stored in the node.
+    fn weight(&mut self, value: &Value) -> Argument {
+        let Value::Param(path) = value else {
+            panic!("TorchScript: the weights must be parameters of the module, got {value:?}");
+        };
+        let (shape, values) = self.archive.tensor(path).unwrap();
+
+        Argument {
+            name: path.clone(),
+            ty: ArgType::Tensor(TensorType {
+                elem_type: ElementType::Float32,
+                dim: shape.len(),
+                shape: Some(partial_shape(shape)),
+            }),
+            value: Some(Data::Float32s(values)),
+            passed: false,
+        }
+    }
+
+    /// Records a node and returns its output, whose type is inferred.
+    fn node(&mut self, node_type: NodeType, inputs: Vec<Argument>, attrs: Attributes) -> Argument {
+        let counter = self.node_name_counter.entry(node_type.clone()).or_insert(0);
+        *counter += 1;
+        let name = format!("{node_type}{counter}").to_lowercase();
+
+        let mut node = Node {
+            node_type,
+            name: name.clone(),
+            inputs,
+            outputs: vec![Argument {
+                passed: true,
+                ..Argument::new(format!("{name}_out1"))
+            }],
+            attrs,
+        };
+        dim_inference(&mut node);
+
+        let output = node.outputs[0].clone();
+        self.nodes.push(node);
+        output
+    }
+}
+
+/// The arguments of an operation, passed by position or by name.
+struct Arguments {
+    name: String,
+    args: Vec<Value>,
+    kwargs: Vec<(String, Value)>,
+}
+
+impl Arguments {
+    /// The argument at the given position or with the given name, `None` values being missing.
+    fn get(&self, index: usize, name: &str) -> Option<&Value> {
+        self.args
+            .get(index)
+            .or_else(|| {
+                self.kwargs
+                    .iter()
+                    .find_map(|(key, value)| (key == name).then_some(value))
+            })
+            .filter(|value| !matches!(value, Value::None))
+    }
+
+    fn value(&self, index: usize, name: &str) -> &Value {
+        self.get(index, name)
+            .unwrap_or_else(|| panic!("TorchScript: {} requires the argument {name}", self.name))
+    }
+
+    fn int(&self, index: usize, name: &str) -> Option<i64> {
+        match self.get(index, name)? {
+            Value::Int(value) => Some(*value),
+            value => panic!("TorchScript: {name} must be an integer, got {value:?}"),
+        }
+    }
+
+    fn ints(&self, index: usize, name: &str) -> Option<Vec<i64>> {
+        match self.get(index, name)? {
+            Value::Int(value) => Some(vec![*value]),
+            Value::List(values) => Some(
+                values
+                    .iter()
+                    .map(|value| match value {
+                        Value::Int(value) => *value,
+                        value => {
+                            panic!("TorchScript: {name} must be constant integers, got {value:?}")
+                        }
+                    })
+                    .collect(),
+            ),
+            value => panic!("TorchScript: {name} must be integers, got {value:?}"),
+        }
+    }
+
+    fn float(&self, index: usize, name: &str) -> Option<f64> {
+        match self.get(index, name)? {
+            Value::Float(value) => Some(*value),
+            Value::Int(value) => Some(*value as f64),
+            value => panic!("TorchScript: {name} must be a number, got {value:?}"),
+        }
+    }
+
+    fn bool(&self, index: usize, name: &str) -> Option<bool> {
+        match self.get(index, name)? {
+            Value::Bool(value) => Some(*value),
+            value => panic!("TorchScript: {name} must be a boolean, got {value:?}"),
+        }
+    }
+
+    fn str(&self, index: usize, name: &str) -> Option<&str> {
+        match self.get(index, name)? {
+            Value::Str(value) => Some(value),
+            value => panic!("TorchScript: {name} must be a string, got {value:?}"),
+        }
+    }
+}
+
+fn attrs<const N: usize>(attrs: [(&str, AttributeValue); N]) -> Attributes {
+    attrs
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect()
+}
+
+/// Expands the values of a spatial argument, which can be given once for all the dimensions.
+fn expand(values: Option<Vec<i64>>, rank: usize, default: i64) -> Vec<i64> {
+    match values {
+        Some(values) if values.len() == 1 => vec![values[0]; rank],
+        Some(values) if values.len() == rank => values,
+        Some(values) => panic!("TorchScript: expected {rank} values, got {values:?}"),
+        None => vec![default; rank],
+    }
+}
+
+fn normalize_axis(axis: i64, rank: i64) -> i64 {
+    match axis < 0 {
+        true => axis + rank,
+        false => axis,
+    }
+}
+
+fn weight_shape(weight: &Argument) -> Vec<usize> {
+    match &weight.ty {
+        ArgType::Tensor(tensor) => tensor.static_shape().unwrap(),
+        _ => unreachable!("weights are tensors"),
+    }
+}
+
+/// Transposes a weight of shape [rows, cols] to [cols, rows].
+fn transpose_weight(weight: &mut Argument) {
+    let shape = weight_shape(weight);
+    if shape.len() != 2 {
+        panic!("TorchScript: the linear weight must be a 2D tensor, got {shape:?}");
+    }
+    let (rows, cols) = (shape[0], shape[1]);
+
+    if let Some(Data::Float32s(values)) = &weight.value {
+        let transposed = (0..rows * cols)
+            .map(|k| values[(k % rows) * cols + k / rows])
+            .collect();
+        weight.value = Some(Data::Float32s(transposed));
+    }
+
+    weight.ty = ArgType::Tensor(TensorType {
+        elem_type: ElementType::Float32,
+        dim: 2,
+        shape: Some(vec![Some(cols), Some(rows)]),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::path::PathBuf;
+
+    use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+    /// A pickled module of a TorchScript archive, written with the opcodes of `torch.jit.save`.
+    struct Pickle {
+        bytes: Vec<u8>,
+        storages: Vec<Vec<u8>>,
+    }
+
+    /// An attribute of a pickled module.
+    enum Attribute<'a> {
+        Module(&'a str, Vec<(&'a str, Attribute<'a>)>),
+        Tensor(Vec<usize>, Vec<f32>),
+    }
+
+    impl Pickle {
+        fn new(class: &str, attributes: Vec<(&str, Attribute)>) -> Self {
+            let mut pickle = Self {
+                bytes: vec![0x80, 2],
+                storages: Vec::new(),
+            };
+            pickle.module(class, attributes);
+            pickle.bytes.push(b'.');
+            pickle
+        }
+
+        fn global(&mut self, module: &str, name: &str) {
+            self.bytes.push(b'c');
+            self.bytes.extend(format!("{module}\n{name}\n").as_bytes());
+        }
+
+        fn str(&mut self, value: &str) {
+            self.bytes.push(b'X');
+            self.bytes.extend((value.len() as u32).to_le_bytes());
+            self.bytes.extend(value.as_bytes());
+        }
+
+        fn int(&mut self, value: usize) {
+            self.bytes.push(b'J');
+            self.bytes.extend((value as i32).to_le_bytes());
+        }
+
+        fn ints(&mut self, values: &[usize]) {
+            self.bytes.push(b'(');
+            values.iter().for_each(|value| self.int(*value));
+            self.bytes.push(b't');
+        }
+
+        fn module(&mut self, class: &str, attributes: Vec<(&str, Attribute)>) {
+            let (module, name) = class.rsplit_once('.').unwrap();
+            self.global(module, name);
+            // EMPTY_TUPLE NEWOBJ EMPTY_DICT MARK
+            self.bytes.extend([b')', 0x81, b'}', b'(']);
+
+            for (name, attribute) in attributes {
+                self.str(name);
+                match attribute {
+                    Attribute::Module(class, attributes) => self.module(class, attributes),
+                    Attribute::Tensor(shape, values) => self.tensor(shape, values),
+                }
+            }
+            // SETITEMS BUILD
+            self.bytes.extend([b'u', b'b']);
+        }
+
+        fn tensor(&mut self, shape: Vec<usize>, values: Vec<f32>) {
+            let key = self.storages.len().to_string();
+            let strides = (0..shape.len())
+                .map(|i| shape[i + 1..].iter().product())
+                .collect::<Vec<_>>();
+
+            self.global("torch._utils", "_rebuild_tensor_v2");
+            self.bytes.push(b'(');
+            // The persistent id of the storage
+            self.bytes.push(b'(');
+            self.str("storage");
+            self.global("torch", "FloatStorage");
+            self.str(&key);
+            self.str("cpu");
+            self.int(values.len());
+            self.bytes.extend([b't', b'Q']);
+            self.int(0);
+            self.ints(&shape);
+            self.ints(&strides);
+            self.bytes.push(0x89);
+            self.global("collections", "OrderedDict");
+            self.bytes.extend([b')', b'R', b't', b'R']);
+
+            let bytes = values.iter().flat_map(|value| value.to_le_bytes());
+            self.storages.push(bytes.collect());
+        }
+    }
+
+    /// Writes a TorchScript archive with the code files `(namespace, source)` and the module.
+    fn write_archive(name: &str, code: &[(&str, &str)], pickle: Pickle) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("{name}.pt"));
+        let mut zip = ZipWriter::new(std::fs::File::create(&path).unwrap());
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Stored);
+
+        let mut write = |file: String, bytes: &[u8]| {
+            zip.start_file(format!("{name}/{file}"), options).unwrap();
+            zip.write_all(bytes).unwrap();
+        };
+        for (namespace, source) in code {
+            write(
+                format!("code/{}.py", namespace.replace('.', "/")),
+                source.as_bytes(),
+            );
+        }
+        for (key, storage) in pickle.storages.iter().enumerate() {
+            write(format!("data/{key}"), storage);
+        }
+        write("data.pkl".to_string(), &pickle.bytes);
+
+        zip.finish().unwrap();
+        path
+    }
+
+    const LINEAR: &str = r#"
+class Linear(Module):
+  __parameters__ = ["weight", "bias", ]
+  __buffers__ = []
+  weight : Tensor
+  bias : Tensor
+  training : bool
+  def forward(self: __torch__.torch.nn.modules.linear.Linear,
+    input: Tensor) -> Tensor:
+    bias = self.bias
+    weight = self.weight
+    return torch.linear(input, weight, bias)
+"#;
+
+    fn build(
+        name: &str,
+        root: &str,
+        attributes: Vec<(&str, Attribute)>,
+        ranks: &[usize],
+    ) -> OnnxGraph {
+        let pickle = Pickle::new("__torch__.Net", attributes);
+        let path = write_archive(
+            name,
+            &[
+                ("__torch__", root),
+                ("__torch__.torch.nn.modules.linear", LINEAR),
+            ],
+            pickle,
+        );
+
+        GraphBuilder::new(Archive::open(&path)).build(ranks)
+    }
+
+    fn node_types(graph: &OnnxGraph) -> Vec<NodeType> {
+        graph
+            .nodes
+            .iter()
+            .map(|node| node.node_type.clone())
+            .collect()
+    }
+
+    fn linear(d_input: usize, d_output: usize) -> Attribute<'static> {
+        let weight = (0..d_input * d_output).map(|i| i as f32).collect();
+        let bias = (0..d_output).map(|i| -(i as f32)).collect();
+
+        Attribute::Module(
+            "__torch__.torch.nn.modules.linear.Linear",
+            vec![
+                ("weight", Attribute::Tensor(vec![d_output, d_input], weight)),
+                ("bias", Attribute::Tensor(vec![d_output], bias)),
+            ],
+        )
+    }
+
+    #[test]
+    fn should_lower_submodules_with_transposed_linear_weights() {
+        let root = r#"
+class Net(Module):
+  __parameters__ = []
+  __buffers__ = []
+  training : bool
+  fc : __torch__.torch.nn.modules.linear.Linear
+  def forward(self: __torch__.Net,
+    x: Tensor) -> Tensor:
+    fc = self.fc
+    _0 = torch.relu((fc).forward(x, ))
+    return _0
+"#;
+        let graph = build("torchscript_linear", root, vec![("fc", linear(2, 3))], &[2]);
+
+        assert_eq!(node_types(&graph), vec![NodeType::Linear, NodeType::Relu]);
+        assert_eq!(graph.inputs[0].name, "input1");
+        assert_eq!(graph.outputs[0].ty.rank(), 2);
+
+        // The weight [3, 2] of PyTorch is transposed to [2, 3]
+        let weight = &graph.nodes[0].inputs[1];
+        assert_eq!(weight.name, "fc.weight");
+        assert_eq!(weight_shape(weight), vec![2, 3]);
+        assert_eq!(
+            weight.value.clone().unwrap().into_f32s(),
+            vec![0.0, 2.0, 4.0, 1.0, 3.0, 5.0]
+        );
+        assert_eq!(graph.nodes[0].inputs.len(), 3);
+    }
+
+    #[test]
+    fn should_lower_tensor_operations() {
+        let root = r#"
+class Net(Module):
+  __parameters__ = ["scale", ]
+  __buffers__ = []
+  scale : Tensor
+  training : bool
+  def forward(self: __torch__.Net,
+    x: Tensor,
+    y: Tensor) -> Tuple[Tensor, Tensor]:
+    scale = self.scale
+    _0 = torch.add(torch.mul(x, scale), 1.5, alpha=1)
+    _1 = torch.dropout(_0, 0.5, False)
+    _2 = torch.transpose(_1, -1, 1)
+    _3 = torch.cat([_2, y], 1)
+    _4 = torch.flatten(torch.sigmoid_(_3), 1)
+    _5 = torch.softmax(torch.reshape(_4, [2, -1]), -1, None)
+    return (_5, _3)
+"#;
+        let graph = build(
+            "torchscript_ops",
+            root,
+            vec![("scale", Attribute::Tensor(vec![1], vec![2.0]))],
+            &[3, 3],
+        );
+
+        assert_eq!(
+            node_types(&graph),
+            vec![
+                NodeType::Constant,
+                NodeType::Mul,
+                NodeType::Constant,
+                NodeType::Add,
+                NodeType::Transpose,
+                NodeType::Concat,
+                NodeType::Sigmoid,
+                NodeType::Flatten,
+                NodeType::Reshape,
+                NodeType::Softmax,
+            ]
+        );
+        let attr = |index: usize, name: &str| graph.nodes[index].attrs[name].clone();
+        assert_eq!(attr(4, "perm").into_i64s(), vec![0, 2, 1]);
+        assert_eq!(attr(5, "axis").into_i64(), 1);
+        assert_eq!(attr(7, "axis").into_i64(), 1);
+        assert_eq!(attr(9, "axis").into_i64(), -1);
+
+        // The tuple is returned as several outputs
+        let ranks = graph
+            .outputs
+            .iter()
+            .map(|output| output.ty.rank())
+            .collect::<Vec<_>>();
+        assert_eq!(ranks, vec![2, 3]);
+    }
+
+    #[test]
+    fn should_lower_convolutions_and_pooling() {
+        let root = r#"
+class Net(Module):
+  __parameters__ = ["weight", ]
+  __buffers__ = []
+  weight : Tensor
+  training : bool
+  def forward(self: __torch__.Net,
+    x: Tensor) -> Tensor:
+    weight = self.weight
+    _0 = torch._convolution(x, weight, None, [2, 1], [1, 1], [1, 1], False, [0, 0], 1, False, False, True, True)
+    _1 = torch.max_pool2d(_0, [2, 2], annotate(List[int], []), [0, 0], [1, 1], False)
+    return torch.avg_pool2d(_1, [3, 3], [1, 1], [1, 1], True, False, None)
+"#;
+        let weight = Attribute::Tensor(vec![4, 2, 3, 3], vec![0.5; 72]);
+        let graph = build("torchscript_conv", root, vec![("weight", weight)], &[4]);
+
+        assert_eq!(
+            node_types(&graph),
+            vec![
+                NodeType::Conv2d,
+                NodeType::MaxPool2d,
+                NodeType::AveragePool2d
+            ]
+        );
+        let attr = |index: usize, name: &str| graph.nodes[index].attrs[name].clone();
+        assert_eq!(attr(0, "kernel_shape").into_i64s(), vec![3, 3]);
+        assert_eq!(attr(0, "strides").into_i64s(), vec![2, 1]);
+        assert_eq!(attr(0, "pads").into_i64s(), vec![1, 1, 1, 1]);
+        assert_eq!(attr(0, "group").into_i64(), 1);
+        assert_eq!(graph.nodes[0].inputs.len(), 2);
+        // The stride of the pooling defaults to its kernel size
+        assert_eq!(attr(1, "strides").into_i64s(), vec![2, 2]);
+        assert_eq!(attr(2, "ceil_mode").into_i64(), 1);
+        assert_eq!(attr(2, "count_include_pad").into_i64(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "unsupported operation torch.lstm")]
+    fn should_reject_unsupported_operations() {
+        let root = r#"
+class Net(Module):
+  __parameters__ = []
+  __buffers__ = []
+  training : bool
+  def forward(self: __torch__.Net,
+    x: Tensor) -> Tensor:
+    return torch.lstm(x)
+"#;
+        build("torchscript_unsupported", root, Vec::new(), &[3]);
+    }
+}
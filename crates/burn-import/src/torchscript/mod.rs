@@ -0,0 +1,23 @@
+mod archive;
+mod graph;
+mod parser;
+
+use std::path::Path;
+
+use onnx_ir::OnnxGraph;
+
+use archive::Archive;
+use graph::GraphBuilder;
+
+/// Parses a TorchScript archive (`.pt`) into the graph of its forward method.
+///
+/// The forward signature of TorchScript does not record the ranks of the tensors, which are
+/// given for each input.
+pub(crate) fn parse_torchscript(path: &Path, input_ranks: &[usize]) -> OnnxGraph {
+    log::info!("Parsing TorchScript file: {}", path.display());
+
+    let graph = GraphBuilder::new(Archive::open(path)).build(input_ranks);
+
+    log::info!("Finished parsing TorchScript file: {}", path.display());
+    graph
+}
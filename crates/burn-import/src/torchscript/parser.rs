@@ -0,0 +1,454 @@
+use std::collections::HashMap;
+
+/// The forward method of a TorchScript class.
+#[derive(Debug, Clone)]
+pub(crate) struct Forward {
+    /// The names of the parameters, `self` excluded.
+    pub params: Vec<String>,
+    /// The statements of the method.
+    pub body: Vec<Stmt>,
+}
+
+/// A statement of a forward method.
+#[derive(Debug, Clone)]
+pub(crate) enum Stmt {
+    /// An assignment to a name, several names unpacking a tuple, or no name for an expression
+    /// whose value is discarded.
+    Assign(Vec<String>, Expr),
+    /// The return of the method.
+    Return(Expr),
+}
+
+/// An expression of a forward method.
+#[derive(Debug, Clone)]
+pub(crate) enum Expr {
+    Name(String),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    /// A list or a tuple.
+    List(Vec<Expr>),
+    Attr(Box<Expr>, String),
+    Index(Box<Expr>, Box<Expr>),
+    Call {
+        callee: Box<Expr>,
+        args: Vec<Expr>,
+        kwargs: Vec<(String, Expr)>,
+    },
+}
+
+/// Parses the forward methods of the classes of a TorchScript source file, by class name.
+///
+/// Only the straight-line code produced by tracing is supported, the control flow of scripted
+/// modules is not.
+pub(crate) fn parse_forwards(source: &str) -> HashMap<String, Forward> {
+    let lines = logical_lines(source);
+    let mut forwards = HashMap::new();
+    let mut class = None;
+
+    let mut i = 0;
+    while i < lines.len() {
+        let (indent, text) = &lines[i];
+        let indent = *indent;
+
+        if indent == 0 {
+            class = text.strip_prefix("class ").map(|declaration| {
+                declaration
+                    .split(['(', ':'])
+                    .next()
+                    .unwrap()
+                    .trim()
+                    .to_string()
+            });
+        } else if let (Some(class), Some(signature)) = (&class, text.strip_prefix("def forward(")) {
+            let params = parse_params(signature);
+            let mut body = Vec::new();
+
+            i += 1;
+            while i < lines.len() && lines[i].0 > indent {
+                body.push(parse_stmt(&lines[i].1));
+                i += 1;
+            }
+
+            forwards.insert(class.clone(), Forward { params, body });
+            continue;
+        }
+
+        i += 1;
+    }
+
+    forwards
+}
+
+/// The indentation and the content of the lines, the lines with unclosed brackets being joined
+/// with the following ones.
+fn logical_lines(source: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+    let mut depth = 0;
+
+    for line in source.lines() {
+        let text = line.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+
+        match depth > 0 {
+            true => {
+                let last = lines.last_mut().unwrap();
+                last.1.push(' ');
+                last.1.push_str(text);
+            }
+            false => lines.push((line.len() - line.trim_start().len(), text.to_string())),
+        }
+
+        depth += bracket_depth(text);
+    }
+
+    lines
+}
+
+/// The difference between the opening and the closing brackets outside of the strings.
+fn bracket_depth(text: &str) -> i32 {
+    let mut depth = 0;
+    let mut quote = None;
+    let mut escaped = false;
+
+    for c in text.chars() {
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '"' | '\'' => quote = Some(c),
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                _ => {}
+            },
+        }
+    }
+
+    depth
+}
+
+/// The names of the parameters of a method signature, e.g. `self: Net, x: Tensor) -> Tensor:`.
+fn parse_params(signature: &str) -> Vec<String> {
+    let mut params = Vec::new();
+    let mut depth = 0;
+    let mut param = String::new();
+
+    for c in signature.chars() {
+        match c {
+            '(' | '[' => depth += 1,
+            ']' => depth -= 1,
+            ')' if depth == 0 => break,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                params.push(param.clone());
+                param.clear();
+                continue;
+            }
+            _ => {}
+        }
+        param.push(c);
+    }
+    params.push(param);
+
+    params
+        .iter()
+        .map(|param| param.split([':', '=']).next().unwrap().trim().to_string())
+        .filter(|name| !name.is_empty() && name != "self")
+        .collect()
+}
+
+fn parse_stmt(text: &str) -> Stmt {
+    if text == "return" {
+        return Stmt::Return(Expr::Name("None".to_string()));
+    }
+    if let Some(value) = text.strip_prefix("return ") {
+        return Stmt::Return(parse_expr(value));
+    }
+    if text.ends_with(':') {
+        panic!("TorchScript: control flow is not supported ({text})");
+    }
+
+    match assignment(text) {
+        Some(position) => {
+            let targets = text[..position]
+                .split(',')
+                .map(|target| target.trim().to_string())
+                .filter(|target| !target.is_empty())
+                .collect();
+
+            Stmt::Assign(targets, parse_expr(&text[position + 1..]))
+        }
+        None => Stmt::Assign(Vec::new(), parse_expr(text)),
+    }
+}
+
+/// The position of the `=` of an assignment, outside of the brackets (keyword arguments) and
+/// which is not part of a comparison.
+fn assignment(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+
+    text.char_indices().find_map(|(i, c)| {
+        let is_assignment = c == '='
+            && bracket_depth(&text[..i]) == 0
+            && !matches!(bytes.get(i + 1), Some(b'='))
+            && !matches!(
+                i.checked_sub(1).map(|j| bytes[j]),
+                Some(b'=' | b'!' | b'<' | b'>')
+            );
+
+        is_assignment.then_some(i)
+    })
+}
+
+fn parse_expr(text: &str) -> Expr {
+    let mut parser = Parser {
+        tokens: tokenize(text),
+        position: 0,
+    };
+    let expr = parser.expr();
+
+    if parser.position != parser.tokens.len() {
+        panic!("TorchScript: unsupported expression {text}");
+    }
+
+    expr
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Punct(char),
+}
+
+fn tokenize(text: &str) -> Vec<Token> {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_alphabetic() || c == '_' {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else if c.is_ascii_digit() {
+            // The exponent of a float can be signed, e.g. `1e-05`
+            while i < chars.len()
+                && (chars[i].is_ascii_alphanumeric()
+                    || chars[i] == '.'
+                    || (matches!(chars[i], '-' | '+') && matches!(chars[i - 1], 'e' | 'E')))
+            {
+                i += 1;
+            }
+            let number = chars[start..i].iter().collect::<String>();
+            tokens.push(match number.parse::<i64>() {
+                Ok(value) => Token::Int(value),
+                Err(_) => Token::Float(
+                    number
+                        .parse()
+                        .unwrap_or_else(|_| panic!("TorchScript: invalid number {number}")),
+                ),
+            });
+        } else if c == '"' || c == '\'' {
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                if chars[i] == '\\' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            tokens.push(Token::Str(
+                chars[start + 1..i.min(chars.len())].iter().collect(),
+            ));
+            i += 1;
+        } else {
+            tokens.push(Token::Punct(c));
+            i += 1;
+        }
+    }
+
+    tokens
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.tokens.get(self.position) == Some(&Token::Punct(c));
+        if found {
+            self.position += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, c: char) {
+        if !self.eat(c) {
+            panic!(
+                "TorchScript: expected `{c}`, found {:?}",
+                self.tokens.get(self.position)
+            );
+        }
+    }
+
+    fn expr(&mut self) -> Expr {
+        if !self.eat('-') {
+            return self.postfix();
+        }
+
+        match self.postfix() {
+            Expr::Int(value) => Expr::Int(-value),
+            Expr::Float(value) => Expr::Float(-value),
+            expr => Expr::Call {
+                callee: Box::new(Expr::Name("torch.neg".to_string())),
+                args: vec![expr],
+                kwargs: Vec::new(),
+            },
+        }
+    }
+
+    fn postfix(&mut self) -> Expr {
+        let mut expr = self.primary();
+
+        loop {
+            if self.eat('.') {
+                match self.next() {
+                    Some(Token::Ident(name)) => expr = Expr::Attr(Box::new(expr), name),
+                    token => panic!("TorchScript: expected an attribute, found {token:?}"),
+                }
+            } else if self.eat('(') {
+                let (args, kwargs) = self.arguments();
+                expr = Expr::Call {
+                    callee: Box::new(expr),
+                    args,
+                    kwargs,
+                };
+            } else if self.eat('[') {
+                let index = self.expr();
+                self.expect(']');
+                expr = Expr::Index(Box::new(expr), Box::new(index));
+            } else {
+                return expr;
+            }
+        }
+    }
+
+    fn primary(&mut self) -> Expr {
+        match self.next() {
+            Some(Token::Ident(name)) => Expr::Name(name),
+            Some(Token::Int(value)) => Expr::Int(value),
+            Some(Token::Float(value)) => Expr::Float(value),
+            Some(Token::Str(value)) => Expr::Str(value),
+            Some(Token::Punct('(')) => {
+                // A parenthesized expression, or a tuple when the items are separated by commas
+                let (mut items, tuple) = self.sequence(')');
+                match tuple || items.len() != 1 {
+                    true => Expr::List(items),
+                    false => items.remove(0),
+                }
+            }
+            Some(Token::Punct('[')) => Expr::List(self.sequence(']').0),
+            token => panic!("TorchScript: unexpected token {token:?}"),
+        }
+    }
+
+    /// The items until the closing bracket, and whether they are separated by commas.
+    fn sequence(&mut self, close: char) -> (Vec<Expr>, bool) {
+        let mut items = Vec::new();
+        let mut comma = false;
+
+        while !self.eat(close) {
+            items.push(self.expr());
+
+            if self.eat(',') {
+                comma = true;
+            } else {
+                self.expect(close);
+                break;
+            }
+        }
+
+        (items, comma)
+    }
+
+    fn arguments(&mut self) -> (Vec<Expr>, Vec<(String, Expr)>) {
+        let mut args = Vec::new();
+        let mut kwargs = Vec::new();
+
+        while !self.eat(')') {
+            // A keyword argument is a name followed by `=`
+            match (
+                self.tokens.get(self.position),
+                self.tokens.get(self.position + 1),
+            ) {
+                (Some(Token::Ident(name)), Some(Token::Punct('='))) => {
+                    let name = name.clone();
+                    self.position += 2;
+                    kwargs.push((name, self.expr()));
+                }
+                _ => args.push(self.expr()),
+            }
+
+            if !self.eat(',') {
+                self.expect(')');
+                break;
+            }
+        }
+
+        (args, kwargs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_traced_forward() {
+        let source = r#"
+class Net(Module):
+  __parameters__ = []
+  __buffers__ = []
+  training : bool
+  fc : __torch__.torch.nn.modules.linear.Linear
+  def forward(self: __torch__.Net,
+    x: Tensor) -> Tensor:
+    fc = self.fc
+    _0 = getattr(self, "0")
+    _1 = torch.softmax((_0).forward(x, ), -1, None)
+    return (fc).forward(torch.flatten(_1, 1), )
+"#;
+
+        let forwards = parse_forwards(source);
+        let forward = &forwards["Net"];
+
+        assert_eq!(forward.params, vec!["x".to_string()]);
+        assert_eq!(forward.body.len(), 4);
+        assert!(matches!(
+            &forward.body[2],
+            Stmt::Assign(targets, Expr::Call { args, .. })
+                if targets == &["_1".to_string()] && matches!(args[1], Expr::Int(-1))
+        ));
+        assert!(matches!(&forward.body[3], Stmt::Return(Expr::Call { .. })));
+    }
+}
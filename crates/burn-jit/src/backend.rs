@@ -2,7 +2,7 @@ use crate::{
     tensor::{JitTensor, QJitTensor},
     FloatElement, IntElement, JitRuntime, PrecisionBridge,
 };
-use burn_tensor::backend::{Backend, DeviceOps};
+use burn_tensor::backend::{Backend, DeviceOps, MemoryUsage};
 use cubecl::server::ComputeServer;
 use rand::{rngs::StdRng, SeedableRng};
 use std::{marker::PhantomData, sync::Mutex};
@@ -62,6 +62,15 @@ where
         let client = R::client(device);
         futures_lite::future::block_on(client.sync());
     }
+
+    fn memory_usage(device: &Self::Device) -> Option<MemoryUsage> {
+        let usage = R::client(device).memory_usage();
+
+        Some(MemoryUsage {
+            bytes_in_use: usage.bytes_in_use,
+            bytes_reserved: usage.bytes_reserved,
+        })
+    }
 }
 
 impl<R: JitRuntime, F: FloatElement, I: IntElement> core::fmt::Debug for JitBackend<R, F, I> {
@@ -0,0 +1,310 @@
+use cubecl::{calculate_cube_count_elemwise, prelude::*};
+
+use burn_tensor::{ops::attention::ATTENTION_MASK_VALUE, ElementConversion, Shape};
+
+use crate::{
+    ops::numeric::{empty_device, zeros_device},
+    tensor::JitTensor,
+    FloatElement, JitRuntime,
+};
+
+/// The number of queries of a block, and of keys and values of a tile.
+const BLOCK_SIZE: u32 = 32;
+
+/// The maximum number of floats of the tiles of a cube in shared memory, 32 KiB of `f32`.
+const MAX_SHARED_ELEMS: u32 = 8192;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+struct AttentionConfig {
+    block_size: u32,
+    d_k: u32,
+    d_v: u32,
+    masked: bool,
+}
+
+/// Computes the attention of a block of queries, one query per unit, going through the keys and
+/// the values by tiles loaded in shared memory by all the units of the cube.
+///
+/// Each query goes through the keys once with an online softmax: the running sum of the
+/// exponentials and the weighted sum of the values are rescaled each time the maximum score
+/// increases, so the score matrix is never materialized.
+#[cube(launch)]
+fn flash_attention_kernel<F: Float>(
+    query: &Tensor<F>,
+    key: &Tensor<F>,
+    value: &Tensor<F>,
+    mask: &Tensor<u32>,
+    output: &mut Tensor<F>,
+    scale: F,
+    #[comptime] config: AttentionConfig,
+) {
+    let block_size = config.block_size;
+    let d_k = config.d_k;
+    let d_v = config.d_v;
+
+    let n_heads = query.shape(1);
+    let seq_length_q = query.shape(2);
+    let seq_length_k = key.shape(2);
+
+    let num_blocks_q = (seq_length_q + block_size - 1) / block_size;
+    let num_blocks_k = (seq_length_k + block_size - 1) / block_size;
+
+    // The cubes beyond the blocks of queries exit together, before any synchronization
+    if CUBE_POS >= query.shape(0) * n_heads * num_blocks_q {
+        return;
+    }
+
+    let batch = CUBE_POS / num_blocks_q / n_heads;
+    let head = CUBE_POS / num_blocks_q % n_heads;
+    let row = (CUBE_POS % num_blocks_q) * block_size + UNIT_POS;
+    let valid_row = row < seq_length_q;
+
+    let mut smem_query = SharedMemory::<F>::new(block_size * d_k);
+    let mut smem_key = SharedMemory::<F>::new(block_size * d_k);
+    let mut smem_value = SharedMemory::<F>::new(block_size * d_v);
+    let mut smem_acc = SharedMemory::<F>::new(block_size * d_v);
+
+    let offset_query = UNIT_POS * d_k;
+    let offset_acc = UNIT_POS * d_v;
+
+    // Each unit loads its scaled query and clears the weighted sum of its values
+    let index_query = batch * query.stride(0) + head * query.stride(1) + row * query.stride(2);
+    for d in 0..d_k {
+        let mut value_query = F::new(0.0);
+        if valid_row {
+            value_query = query[index_query + d * query.stride(3)] * scale;
+        }
+        smem_query[offset_query + d] = value_query;
+    }
+    for d in 0..d_v {
+        smem_acc[offset_acc + d] = F::new(0.0);
+    }
+
+    let index_key = batch * key.stride(0) + head * key.stride(1);
+    let index_value = batch * value.stride(0) + head * value.stride(1);
+    let index_mask = batch * mask.stride(0) + head * mask.stride(1) + row * mask.stride(2);
+
+    let mut max = F::MIN;
+    let mut sum = F::new(0.0);
+
+    for block_k in 0..num_blocks_k {
+        let start_k = block_k * block_size;
+
+        // Each unit loads a key and a value of the tile
+        let row_k = start_k + UNIT_POS;
+        let valid_row_k = row_k < seq_length_k;
+        for d in 0..d_k {
+            let mut value_key = F::new(0.0);
+            if valid_row_k {
+                value_key = key[index_key + row_k * key.stride(2) + d * key.stride(3)];
+            }
+            smem_key[offset_query + d] = value_key;
+        }
+        for d in 0..d_v {
+            let mut value_value = F::new(0.0);
+            if valid_row_k {
+                value_value = value[index_value + row_k * value.stride(2) + d * value.stride(3)];
+            }
+            smem_value[offset_acc + d] = value_value;
+        }
+
+        sync_units();
+
+        if valid_row {
+            for j in 0..block_size {
+                let k = start_k + j;
+
+                if k < seq_length_k {
+                    let mut score = F::new(0.0);
+                    for d in 0..d_k {
+                        score += smem_query[offset_query + d] * smem_key[j * d_k + d];
+                    }
+
+                    if config.masked {
+                        if mask[index_mask + k * mask.stride(3)] != 0 {
+                            score = F::new(ATTENTION_MASK_VALUE);
+                        }
+                    }
+
+                    if score > max {
+                        let correction = F::exp(max - score);
+                        sum *= correction;
+                        for d in 0..d_v {
+                            smem_acc[offset_acc + d] *= correction;
+                        }
+                        max = score;
+                    }
+
+                    let weight = F::exp(score - max);
+                    sum += weight;
+                    for d in 0..d_v {
+                        smem_acc[offset_acc + d] += weight * smem_value[j * d_v + d];
+                    }
+                }
+            }
+        }
+
+        // The tile is read by all the units before being replaced
+        sync_units();
+    }
+
+    if valid_row {
+        let index_output =
+            batch * output.stride(0) + head * output.stride(1) + row * output.stride(2);
+        for d in 0..d_v {
+            output[index_output + d * output.stride(3)] = smem_acc[offset_acc + d] / sum;
+        }
+    }
+}
+
+/// Computes an output value of the attention, going through the keys once with an online
+/// softmax, for the heads too large for the tiles of the [flash attention](flash_attention_kernel).
+#[cube(launch)]
+fn attention_kernel<F: Float>(
+    query: &Tensor<F>,
+    key: &Tensor<F>,
+    value: &Tensor<F>,
+    mask: &Tensor<u32>,
+    output: &mut Tensor<F>,
+    scale: F,
+    #[comptime] masked: bool,
+) {
+    if ABSOLUTE_POS >= output.len() {
+        return;
+    }
+
+    let batch = ABSOLUTE_POS / output.stride(0) % output.shape(0);
+    let head = ABSOLUTE_POS / output.stride(1) % output.shape(1);
+    let row = ABSOLUTE_POS / output.stride(2) % output.shape(2);
+    let col = ABSOLUTE_POS / output.stride(3) % output.shape(3);
+
+    let d_k = query.shape(3);
+    let seq_length_k = key.shape(2);
+
+    let index_query = batch * query.stride(0) + head * query.stride(1) + row * query.stride(2);
+    let index_key = batch * key.stride(0) + head * key.stride(1);
+    let index_value = batch * value.stride(0) + head * value.stride(1) + col * value.stride(3);
+    let index_mask = batch * mask.stride(0) + head * mask.stride(1) + row * mask.stride(2);
+
+    let query_stride_3 = query.stride(3);
+    let key_stride_2 = key.stride(2);
+    let key_stride_3 = key.stride(3);
+    let value_stride_2 = value.stride(2);
+    let mask_stride_3 = mask.stride(3);
+
+    let mut max = F::MIN;
+    let mut sum = F::new(0.0);
+    let mut acc = F::new(0.0);
+
+    for k in 0..seq_length_k {
+        let index_key_k = index_key + k * key_stride_2;
+        let mut score = F::new(0.0);
+
+        for d in 0..d_k {
+            score += query[index_query + d * query_stride_3] * key[index_key_k + d * key_stride_3];
+        }
+        score *= scale;
+
+        if masked {
+            if mask[index_mask + k * mask_stride_3] != 0 {
+                score = F::new(ATTENTION_MASK_VALUE);
+            }
+        }
+
+        if score > max {
+            let correction = F::exp(max - score);
+            sum *= correction;
+            acc *= correction;
+            max = score;
+        }
+
+        let weight = F::exp(score - max);
+        sum += weight;
+        acc += weight * value[index_value + k * value_stride_2];
+    }
+
+    output[ABSOLUTE_POS] = acc / sum;
+}
+
+/// Scaled dot-product attention without materializing the score matrix.
+///
+/// The queries are processed by blocks, going through the keys and the values by tiles in shared
+/// memory. The heads too large for the tiles fall back to a kernel computing each output value
+/// from the global memory.
+pub(crate) fn attention<R: JitRuntime, E: FloatElement>(
+    query: JitTensor<R, E>,
+    key: JitTensor<R, E>,
+    value: JitTensor<R, E>,
+    mask: Option<JitTensor<R, u32>>,
+) -> JitTensor<R, E> {
+    let [batch_size, n_heads, seq_length_q, d_k] = query.shape.dims();
+    let [_, _, _, d_v] = value.shape.dims();
+    let scale = (d_k as f64).sqrt().recip();
+
+    let output = empty_device::<R, E>(
+        query.client.clone(),
+        query.device.clone(),
+        Shape::new([batch_size, n_heads, seq_length_q, d_v]),
+    );
+
+    // The kernels always take a mask, which is only read if there is one
+    let masked = mask.is_some();
+    let mask = mask.unwrap_or_else(|| {
+        zeros_device::<R, u32>(
+            query.client.clone(),
+            query.device.clone(),
+            Shape::new([1, 1, 1, 1]),
+        )
+    });
+
+    // The query, key, value and output tiles of a block
+    let num_shared_elems = 2 * BLOCK_SIZE * (d_k + d_v) as u32;
+    if num_shared_elems > MAX_SHARED_ELEMS {
+        let cube_dim = CubeDim::default();
+        let cube_count = calculate_cube_count_elemwise(output.shape.num_elements(), cube_dim);
+
+        attention_kernel::launch::<E, R>(
+            &query.client,
+            cube_count,
+            cube_dim,
+            query.as_tensor_arg(1),
+            key.as_tensor_arg(1),
+            value.as_tensor_arg(1),
+            mask.as_tensor_arg(1),
+            output.as_tensor_arg(1),
+            ScalarArg::new(scale.elem::<E>()),
+            masked,
+        );
+
+        return output;
+    }
+
+    let config = AttentionConfig {
+        block_size: BLOCK_SIZE,
+        d_k: d_k as u32,
+        d_v: d_v as u32,
+        masked,
+    };
+
+    // One cube per block of queries of a head, over two dimensions for the large batches
+    let num_cubes = (batch_size * n_heads * seq_length_q.div_ceil(BLOCK_SIZE as usize)) as f32;
+    let cube_count_x = f32::ceil(f32::sqrt(num_cubes));
+    let cube_count_y = f32::ceil(num_cubes / cube_count_x);
+    let cube_count = CubeCount::Static(cube_count_x as u32, cube_count_y as u32, 1);
+    let cube_dim = CubeDim::new(BLOCK_SIZE, 1, 1);
+
+    flash_attention_kernel::launch::<E, R>(
+        &query.client,
+        cube_count,
+        cube_dim,
+        query.as_tensor_arg(1),
+        key.as_tensor_arg(1),
+        value.as_tensor_arg(1),
+        mask.as_tensor_arg(1),
+        output.as_tensor_arg(1),
+        ScalarArg::new(scale.elem::<E>()),
+        config,
+    );
+
+    output
+}
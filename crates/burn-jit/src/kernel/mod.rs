@@ -15,6 +15,8 @@ pub(crate) use unary::*;
 
 pub use cubecl::{Kernel, SUBCUBE_DIM_APPROX};
 
+/// Attention kernels
+pub mod attention;
 /// Convolution kernels
 pub mod conv;
 /// Interpolation kernels
@@ -0,0 +1,183 @@
+use crate::ops::numeric::{empty_device, zeros_device};
+use crate::tensor::{JitTensor, QJitTensor};
+use crate::FloatElement;
+use crate::{IntElement, JitRuntime};
+use burn_tensor::quantization::{QuantizationScheme, QuantizationType};
+use burn_tensor::Shape;
+use cubecl::prelude::*;
+
+use super::extract_i8;
+
+#[cube]
+fn read_i8(tensor: &Tensor<u32>, index: u32) -> i32 {
+    // Four 8-bit values are packed in each u32, the first one in the most significant bits
+    extract_i8(tensor[index / 4], (3 - index % 4) * 8)
+}
+
+/// The number of rows and columns of the output tiles, and of the values of `k` loaded at once.
+const TILE_SIZE: u32 = 16;
+
+/// Each cube computes a tile of the output, one element per unit, going through `k` by tiles
+/// of the quantized values loaded in shared memory by all the units of the cube.
+///
+/// The products are accumulated in 32-bit integers and the sum is dequantized once.
+#[cube(launch_unchecked)]
+fn q_matmul_per_tensor_int8_kernel<F: Float>(
+    lhs: &Tensor<u32>,
+    lhs_scale: &Tensor<F>,
+    lhs_offset: &Tensor<i32>,
+    rhs: &Tensor<u32>,
+    rhs_scale: &Tensor<F>,
+    rhs_offset: &Tensor<i32>,
+    output: &mut Tensor<F>,
+    #[comptime] tile_size: u32,
+) {
+    let rank = output.rank();
+    let n_rows = output.shape(rank - 2);
+    let n_cols = output.shape(rank - 1);
+    let k = lhs.shape(rank - 1);
+
+    let row = CUBE_POS_Y * tile_size + UNIT_POS_Y;
+    let col = CUBE_POS_X * tile_size + UNIT_POS_X;
+    let valid_row = row < n_rows;
+    let valid_col = col < n_cols;
+
+    // The output is contiguous, each cube along z computes a matrix of the batch
+    let offset_output = n_rows * n_cols * CUBE_POS_Z;
+    let mut index_lhs = row * lhs.stride(rank - 2);
+    let mut index_rhs = col * rhs.stride(rank - 1);
+
+    for i in 0..rank - 2 {
+        let index = offset_output / output.stride(i) % output.shape(i);
+        // Broadcast the batch dimensions of size 1
+        index_lhs += index % lhs.shape(i) * lhs.stride(i);
+        index_rhs += index % rhs.shape(i) * rhs.stride(i);
+    }
+
+    let lhs_stride = lhs.stride(rank - 1);
+    let rhs_stride = rhs.stride(rank - 2);
+    let lhs_offset = lhs_offset[0];
+    let rhs_offset = rhs_offset[0];
+
+    let mut smem_lhs = SharedMemory::<i32>::new(tile_size * tile_size);
+    let mut smem_rhs = SharedMemory::<i32>::new(tile_size * tile_size);
+    let tile_index = UNIT_POS_Y * tile_size + UNIT_POS_X;
+
+    // x = scale * (x_q - offset), so the sum of the products is scaled once at the end
+    let mut sum = i32::new(0);
+    let num_tiles = (k + tile_size - 1) / tile_size;
+
+    for tile in 0..num_tiles {
+        let start = tile * tile_size;
+
+        // Each unit loads a value of both tiles, the values beyond `k` are zeros
+        let k_lhs = start + UNIT_POS_X;
+        let mut a = i32::new(0);
+        if valid_row && k_lhs < k {
+            a = read_i8(lhs, index_lhs + k_lhs * lhs_stride) - lhs_offset;
+        }
+        smem_lhs[tile_index] = a;
+
+        let k_rhs = start + UNIT_POS_Y;
+        let mut b = i32::new(0);
+        if valid_col && k_rhs < k {
+            b = read_i8(rhs, index_rhs + k_rhs * rhs_stride) - rhs_offset;
+        }
+        smem_rhs[tile_index] = b;
+
+        sync_units();
+
+        #[unroll]
+        for i in 0..tile_size {
+            sum += smem_lhs[UNIT_POS_Y * tile_size + i] * smem_rhs[i * tile_size + UNIT_POS_X];
+        }
+
+        // The tiles are read by all the units before being replaced
+        sync_units();
+    }
+
+    if valid_row && valid_col {
+        output[offset_output + row * n_cols + col] =
+            F::cast_from(sum) * lhs_scale[0] * rhs_scale[0];
+    }
+}
+
+/// Multiplies two quantized tensors without dequantizing them, returning the result in
+/// floating point.
+///
+/// The symmetric tensors have no offset, a zero offset is used for them.
+///
+/// Only the matmul runs on the quantized values: there is no quantized convolution, the
+/// convolutions of the quantized modules run on the dequantized weights.
+pub fn q_matmul<R, F, I>(lhs: QJitTensor<R, F, I>, rhs: QJitTensor<R, F, I>) -> JitTensor<R, F>
+where
+    R: JitRuntime,
+    F: FloatElement,
+    I: IntElement,
+{
+    match (lhs.scheme, rhs.scheme) {
+        (
+            QuantizationScheme::PerTensorAffine(QuantizationType::QInt8)
+            | QuantizationScheme::PerTensorSymmetric(QuantizationType::QInt8),
+            QuantizationScheme::PerTensorAffine(QuantizationType::QInt8)
+            | QuantizationScheme::PerTensorSymmetric(QuantizationType::QInt8),
+        ) => q_matmul_per_tensor_int8(lhs, rhs),
+    }
+}
+
+fn q_matmul_per_tensor_int8<R, F, I>(
+    lhs: QJitTensor<R, F, I>,
+    rhs: QJitTensor<R, F, I>,
+) -> JitTensor<R, F>
+where
+    R: JitRuntime,
+    F: FloatElement,
+    I: IntElement,
+{
+    lhs.qtensor.assert_is_on_same_device(&rhs.qtensor);
+
+    let ndims = lhs.qtensor.shape.num_dims();
+    let mut shape_out = vec![0; ndims];
+    for i in 0..ndims - 2 {
+        shape_out[i] = usize::max(lhs.qtensor.shape.dims[i], rhs.qtensor.shape.dims[i]);
+    }
+    shape_out[ndims - 2] = lhs.qtensor.shape.dims[ndims - 2];
+    shape_out[ndims - 1] = rhs.qtensor.shape.dims[ndims - 1];
+
+    let client = lhs.qtensor.client.clone();
+    let device = lhs.qtensor.device.clone();
+    let output = empty_device::<R, F>(client.clone(), device.clone(), Shape::from(shape_out));
+
+    let offset = |offset: Option<JitTensor<R, I>>| {
+        offset.unwrap_or_else(|| zeros_device::<R, I>(client.clone(), device.clone(), [1].into()))
+    };
+    let lhs_offset = offset(lhs.qparams.offset);
+    let rhs_offset = offset(rhs.qparams.offset);
+
+    let num_tiles = |size: usize| (size as u32).div_ceil(TILE_SIZE);
+    let num_batches = output.shape.dims[..ndims - 2].iter().product::<usize>();
+    let cube_dim = CubeDim::new(TILE_SIZE, TILE_SIZE, 1);
+    let cube_count = CubeCount::Static(
+        num_tiles(output.shape.dims[ndims - 1]),
+        num_tiles(output.shape.dims[ndims - 2]),
+        num_batches as u32,
+    );
+
+    unsafe {
+        q_matmul_per_tensor_int8_kernel::launch_unchecked::<F, R>(
+            &client,
+            cube_count,
+            cube_dim,
+            lhs.qtensor.as_tensor_arg(1),
+            lhs.qparams.scale.as_tensor_arg(1),
+            lhs_offset.as_tensor_arg(1),
+            rhs.qtensor.as_tensor_arg(1),
+            rhs.qparams.scale.as_tensor_arg(1),
+            rhs_offset.as_tensor_arg(1),
+            output.as_tensor_arg(1),
+            TILE_SIZE,
+        )
+    };
+
+    output
+}
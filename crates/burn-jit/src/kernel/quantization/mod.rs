@@ -1,5 +1,7 @@
 mod dequantize;
+mod matmul;
 mod quantize;
 
 pub use dequantize::*;
+pub use matmul::*;
 pub use quantize::*;
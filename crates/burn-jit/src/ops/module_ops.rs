@@ -5,11 +5,11 @@ use crate::{
     },
     FloatElement, IntElement, JitBackend, JitRuntime,
 };
+use burn_tensor::ops::{BoolTensor, FloatTensor, IntTensor};
 use burn_tensor::ops::{
     ConvOptions, ConvTransposeOptions, DeformConv2dBackward, DeformConvOptions, InterpolateOptions,
     MaxPool2dBackward, MaxPool2dWithIndices, ModuleOps,
 };
-use burn_tensor::ops::{FloatTensor, IntTensor};
 
 impl<R, F, I> ModuleOps<Self> for JitBackend<R, F, I>
 where
@@ -173,4 +173,13 @@ where
     ) -> FloatTensor<Self> {
         kernel::interpolate::interpolate_backward(x, grad, output_size, options)
     }
+
+    fn attention(
+        query: FloatTensor<Self>,
+        key: FloatTensor<Self>,
+        value: FloatTensor<Self>,
+        mask: Option<BoolTensor<Self>>,
+    ) -> FloatTensor<Self> {
+        kernel::attention::attention(query, key, value, mask)
+    }
 }
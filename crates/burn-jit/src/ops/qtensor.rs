@@ -92,6 +92,14 @@ where
         kernel::quantization::dequantize(tensor)
     }
 
+    fn q_matmul(lhs: QuantizedTensor<Self>, rhs: QuantizedTensor<Self>) -> QuantizedTensor<Self> {
+        // Heuristic: prioritize lhs scheme
+        let scheme = lhs.scheme.clone();
+        let output = kernel::quantization::q_matmul(lhs, rhs);
+
+        Self::quantize_dynamic(output, &scheme)
+    }
+
     fn q_shape(tensor: &QuantizedTensor<Self>) -> Shape {
         tensor.qtensor.shape.clone()
     }
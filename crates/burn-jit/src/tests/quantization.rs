@@ -3,7 +3,7 @@ mod tests {
     use super::*;
     use burn_tensor::{
         quantization::{QuantizationScheme, QuantizationType},
-        Tensor,
+        Tensor, TensorData,
     };
 
     #[test]
@@ -79,4 +79,70 @@ mod tests {
 
         output.to_data().assert_approx_eq(&output_ref.to_data(), 3);
     }
+
+    #[test]
+    fn should_matmul_quantized_tensors() {
+        // The values are on the quantization grid (scale of 1), so both products are exact
+        let scheme = QuantizationScheme::PerTensorSymmetric(QuantizationType::QInt8);
+        let device = Default::default();
+        let lhs = Tensor::<TestBackend, 3>::from_floats(
+            [
+                [[1.0, -2.0, 127.0], [5.0, 40.0, -10.0]],
+                [[-30.0, 25.0, 0.0], [15.0, -5.0, 20.0]],
+            ],
+            &device,
+        );
+        let rhs = Tensor::<TestBackend, 3>::from_floats(
+            [[[20.0, -10.0], [5.0, 30.0], [-127.0, 10.0]]],
+            &device,
+        );
+        let lhs_ref = Tensor::<ReferenceBackend, 3>::from_data(lhs.to_data(), &Default::default());
+        let rhs_ref = Tensor::<ReferenceBackend, 3>::from_data(rhs.to_data(), &Default::default());
+
+        let output = lhs
+            .quantize_dynamic(&scheme)
+            .matmul(rhs.quantize_dynamic(&scheme));
+        let output_ref = lhs_ref
+            .quantize_dynamic(&scheme)
+            .matmul(rhs_ref.quantize_dynamic(&scheme));
+
+        output.to_data().assert_eq(&output_ref.to_data(), false);
+        output
+            .dequantize()
+            .to_data()
+            .assert_approx_eq(&output_ref.dequantize().to_data(), 3);
+    }
+
+    #[test]
+    fn should_matmul_quantized_tensors_across_tiles() {
+        // The shapes aren't multiples of the tiles, and the batch of rhs is broadcast
+        let scheme = QuantizationScheme::PerTensorAffine(QuantizationType::QInt8);
+        let device = Default::default();
+        let values = |shape: [usize; 3]| {
+            let values = (0..shape.iter().product::<usize>())
+                .map(|i| ((i * 7) % 255) as f32 / 10.0 - 12.7)
+                .collect::<Vec<_>>();
+            TensorData::new(values, shape)
+        };
+        let lhs = Tensor::<TestBackend, 3>::from_data(values([2, 19, 37]), &device);
+        let rhs = Tensor::<TestBackend, 3>::from_data(values([1, 37, 21]), &device);
+        let lhs_ref = Tensor::<ReferenceBackend, 3>::from_data(lhs.to_data(), &Default::default());
+        let rhs_ref = Tensor::<ReferenceBackend, 3>::from_data(rhs.to_data(), &Default::default());
+
+        let output = lhs
+            .quantize_dynamic(&scheme)
+            .matmul(rhs.quantize_dynamic(&scheme))
+            .dequantize();
+        let output_ref = lhs_ref
+            .quantize_dynamic(&scheme)
+            .matmul(rhs_ref.quantize_dynamic(&scheme))
+            .dequantize();
+
+        // The outputs can only differ by the rounding of their quantization, a step of the range
+        let range = output_ref.clone().max().into_scalar() - output_ref.clone().min().into_scalar();
+        let step = range as f64 / 255.0;
+        output
+            .to_data()
+            .assert_approx_eq_diff(&output_ref.to_data(), step * 1.01);
+    }
 }
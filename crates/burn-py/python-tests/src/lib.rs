@@ -0,0 +1,32 @@
+//! A linear model exposed to the Python tests of burn-py.
+
+use std::path::Path;
+
+use burn::nn::{Linear, LinearConfig};
+use burn::prelude::*;
+use burn::record::{FullPrecisionSettings, NamedMpkFileRecorder, RecorderError};
+use burn_py::PythonModel;
+
+#[derive(Module, Debug)]
+struct Model<B: Backend> {
+    linear: Linear<B>,
+}
+
+impl<B: Backend> PythonModel<B> for Model<B> {
+    fn load(path: &Path, device: &B::Device) -> Result<Self, RecorderError> {
+        let model = Model {
+            linear: LinearConfig::new(2, 3).init(device),
+        };
+        let recorder = NamedMpkFileRecorder::<FullPrecisionSettings>::new();
+
+        model.load_file(path, &recorder, device)
+    }
+
+    fn forward(&self, inputs: Vec<TensorData>, device: &B::Device) -> Vec<TensorData> {
+        let input = Tensor::<B, 2>::from_data(inputs[0].clone(), device);
+
+        vec![self.linear.forward(input).into_data()]
+    }
+}
+
+burn_py::python_module!(burn_py_tests, Model);
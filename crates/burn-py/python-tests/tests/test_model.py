@@ -0,0 +1,33 @@
+from pathlib import Path
+
+import numpy as np
+import pytest
+
+import burn_py_tests
+
+# Linear(2, 3) record with the weight [[1, 2, 3], [4, 5, 6]] and the bias [0.5, -0.5, 0]
+RECORD = Path(__file__).parent / "model.mpk"
+
+
+def test_forward():
+    model = burn_py_tests.Model(str(RECORD), device="cpu")
+    inputs = np.array([[1.0, 2.0], [0.0, -1.0]], dtype=np.float32)
+
+    [output] = model.forward([inputs])
+
+    expected = np.array([[9.5, 11.5, 15.0], [-3.5, -5.5, -6.0]], dtype=np.float32)
+    np.testing.assert_allclose(output, expected)
+
+
+def test_devices():
+    assert "cpu" in burn_py_tests.Model.devices()
+
+
+def test_unknown_device():
+    with pytest.raises(ValueError, match="Unknown device"):
+        burn_py_tests.Model(str(RECORD), device="tpu")
+
+
+def test_missing_record():
+    with pytest.raises(ValueError, match="Could not load the record"):
+        burn_py_tests.Model(str(RECORD.with_name("missing.mpk")))
@@ -0,0 +1,99 @@
+#![warn(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_auto_cfg))]
+
+//! Python bindings to load and run Burn models.
+//!
+//! A model implementing [PythonModel] is exposed as a Python extension module with the
+//! [python_module] macro: the record of the model is loaded on the device selected from Python,
+//! and its forward pass runs on NumPy arrays.
+//!
+//! ```python
+//! import numpy as np
+//! import my_model
+//!
+//! model = my_model.Model("model.mpk", device="wgpu")
+//! [output] = model.forward([np.zeros((1, 3, 224, 224), dtype=np.float32)])
+//! ```
+
+#[cfg(not(any(feature = "ndarray", feature = "wgpu", feature = "cuda-jit")))]
+compile_error!("At least one backend feature of burn-py must be enabled");
+
+mod model;
+mod tensor;
+
+pub use model::*;
+pub use tensor::{from_numpy, to_numpy};
+
+pub use burn;
+pub use numpy;
+pub use pyo3;
+
+/// Declares a Python extension module running a model implementing [PythonModel].
+///
+/// The module exposes a `Model` class, created from the path of the record and the device, e.g.
+/// `Model("model.mpk", device="cuda:0")`, whose `forward` method takes and returns a list of
+/// NumPy arrays. The crate must be built as a `cdylib` depending on `pyo3` with its
+/// `extension-module` feature, e.g. with [maturin](https://www.maturin.rs).
+///
+/// # Example
+///
+/// ```rust, ignore
+/// // Declares the `my_model` module for `Model<B>`
+/// burn_py::python_module!(my_model, Model);
+/// ```
+#[macro_export]
+macro_rules! python_module {
+    ($module:ident, $model:ident) => {
+        struct __BurnPyFamily;
+
+        impl $crate::ModelFamily for __BurnPyFamily {
+            type Model<B: $crate::burn::tensor::backend::Backend> = $model<B>;
+        }
+
+        /// A Burn model loaded on a device.
+        #[::pyo3::pyclass(name = "Model")]
+        struct __BurnPyModel($crate::ModelRunner<__BurnPyFamily>);
+
+        #[::pyo3::pymethods]
+        impl __BurnPyModel {
+            #[new]
+            #[pyo3(signature = (path, device = "cpu"))]
+            fn new(path: ::std::path::PathBuf, device: &str) -> ::pyo3::PyResult<Self> {
+                $crate::ModelRunner::load(&path, device)
+                    .map(Self)
+                    .map_err(|err| ::pyo3::exceptions::PyValueError::new_err(err.to_string()))
+            }
+
+            /// Runs the forward pass on a list of NumPy arrays.
+            fn forward(
+                &self,
+                py: ::pyo3::Python<'_>,
+                inputs: Vec<::pyo3::Bound<'_, $crate::numpy::PyUntypedArray>>,
+            ) -> ::pyo3::PyResult<Vec<::pyo3::PyObject>> {
+                let inputs = inputs
+                    .iter()
+                    .map($crate::from_numpy)
+                    .collect::<::pyo3::PyResult<Vec<_>>>()?;
+
+                self.0
+                    .forward(inputs)
+                    .into_iter()
+                    .map(|data| $crate::to_numpy(py, data))
+                    .collect()
+            }
+
+            /// The devices of the enabled backends.
+            #[staticmethod]
+            fn devices() -> Vec<&'static str> {
+                $crate::available_devices()
+            }
+        }
+
+        #[::pyo3::pymodule]
+        fn $module(module: &::pyo3::Bound<'_, ::pyo3::types::PyModule>) -> ::pyo3::PyResult<()> {
+            use ::pyo3::types::PyModuleMethods;
+
+            module.add_class::<__BurnPyModel>()
+        }
+    };
+}
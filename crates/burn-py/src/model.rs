@@ -0,0 +1,184 @@
+use std::path::Path;
+
+use burn::{record::RecorderError, tensor::backend::Backend, tensor::TensorData};
+
+/// A model which can be loaded and run from Python.
+///
+/// The inputs and outputs are exchanged as tensor data, converted from and to NumPy arrays.
+///
+/// # Example
+///
+/// ```rust, ignore
+/// impl<B: Backend> PythonModel<B> for Model<B> {
+///     fn load(path: &Path, device: &B::Device) -> Result<Self, RecorderError> {
+///         let recorder = NamedMpkFileRecorder::<FullPrecisionSettings>::new();
+///         Model::new(device).load_file(path, &recorder, device)
+///     }
+///
+///     fn forward(&self, inputs: Vec<TensorData>, device: &B::Device) -> Vec<TensorData> {
+///         let input = Tensor::<B, 4>::from_data(inputs[0].clone(), device);
+///         vec![self.forward(input).into_data()]
+///     }
+/// }
+/// ```
+pub trait PythonModel<B: Backend>: Sized + Send + 'static {
+    /// Creates the model on the device and loads its record from the file.
+    fn load(path: &Path, device: &B::Device) -> Result<Self, RecorderError>;
+
+    /// Runs the forward pass of the model on the inputs.
+    fn forward(&self, inputs: Vec<TensorData>, device: &B::Device) -> Vec<TensorData>;
+}
+
+/// The family of a model generic over the backend, which is instantiated for the backend of the
+/// device selected from Python.
+///
+/// It is implemented by the [python_module](crate::python_module) macro.
+pub trait ModelFamily: 'static {
+    /// The model for the backend `B`.
+    type Model<B: Backend>: PythonModel<B>;
+}
+
+/// The error of a model run from Python.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// The device is unknown or its backend is not enabled.
+    #[error("Unknown device {0}, the available devices are {1}")]
+    UnknownDevice(String, String),
+
+    /// The record of the model could not be loaded.
+    #[error("Could not load the record: {0}")]
+    Record(#[from] RecorderError),
+}
+
+/// A model loaded on the device of one of the enabled backends.
+pub enum ModelRunner<F: ModelFamily> {
+    /// The model on the CPU, with the ndarray backend.
+    #[cfg(feature = "ndarray")]
+    NdArray(
+        F::Model<burn::backend::NdArray>,
+        burn::backend::ndarray::NdArrayDevice,
+    ),
+    /// The model on a GPU, with the wgpu backend.
+    #[cfg(feature = "wgpu")]
+    Wgpu(
+        F::Model<burn::backend::Wgpu>,
+        burn::backend::wgpu::WgpuDevice,
+    ),
+    /// The model on an NVIDIA GPU, with the CUDA backend.
+    #[cfg(feature = "cuda-jit")]
+    Cuda(
+        F::Model<burn::backend::CudaJit>,
+        burn::backend::cuda_jit::CudaDevice,
+    ),
+}
+
+impl<F: ModelFamily> ModelRunner<F> {
+    /// Loads the model on a device, given as `cpu`, `wgpu`, `wgpu:<index>` or `cuda:<index>`.
+    pub fn load(path: &Path, device: &str) -> Result<Self, Error> {
+        let (backend, index) = match device.split_once(':') {
+            Some((backend, index)) => (backend, index.parse::<usize>().ok()),
+            None => (device, None),
+        };
+
+        match (backend, index) {
+            #[cfg(feature = "ndarray")]
+            ("cpu" | "ndarray", None) => {
+                let device = burn::backend::ndarray::NdArrayDevice::Cpu;
+                Ok(Self::NdArray(F::Model::load(path, &device)?, device))
+            }
+            #[cfg(feature = "wgpu")]
+            ("wgpu", index) => {
+                let device = match index {
+                    Some(index) => burn::backend::wgpu::WgpuDevice::DiscreteGpu(index),
+                    None => burn::backend::wgpu::WgpuDevice::default(),
+                };
+                Ok(Self::Wgpu(F::Model::load(path, &device)?, device))
+            }
+            #[cfg(feature = "cuda-jit")]
+            ("cuda", index) => {
+                let device = burn::backend::cuda_jit::CudaDevice::new(index.unwrap_or(0));
+                Ok(Self::Cuda(F::Model::load(path, &device)?, device))
+            }
+            _ => Err(Error::UnknownDevice(
+                device.to_string(),
+                available_devices().join(", "),
+            )),
+        }
+    }
+
+    /// Runs the forward pass of the model on the inputs.
+    pub fn forward(&self, inputs: Vec<TensorData>) -> Vec<TensorData> {
+        match self {
+            #[cfg(feature = "ndarray")]
+            Self::NdArray(model, device) => model.forward(inputs, device),
+            #[cfg(feature = "wgpu")]
+            Self::Wgpu(model, device) => model.forward(inputs, device),
+            #[cfg(feature = "cuda-jit")]
+            Self::Cuda(model, device) => model.forward(inputs, device),
+        }
+    }
+}
+
+/// The devices of the enabled backends.
+pub fn available_devices() -> Vec<&'static str> {
+    let mut devices = Vec::new();
+    #[cfg(feature = "ndarray")]
+    devices.push("cpu");
+    #[cfg(feature = "wgpu")]
+    devices.push("wgpu");
+    #[cfg(feature = "cuda-jit")]
+    devices.push("cuda");
+
+    devices
+}
+
+#[cfg(all(test, feature = "ndarray"))]
+mod tests {
+    use super::*;
+
+    /// Doubles its input, and fails to load from an empty path.
+    struct Double;
+
+    impl<B: Backend> PythonModel<B> for Double {
+        fn load(path: &Path, _device: &B::Device) -> Result<Self, RecorderError> {
+            match path.as_os_str().is_empty() {
+                true => Err(RecorderError::FileNotFound("empty path".to_string())),
+                false => Ok(Self),
+            }
+        }
+
+        fn forward(&self, inputs: Vec<TensorData>, device: &B::Device) -> Vec<TensorData> {
+            let input = burn::tensor::Tensor::<B, 1>::from_data(inputs[0].clone(), device);
+            vec![(input * 2).into_data()]
+        }
+    }
+
+    struct DoubleFamily;
+
+    impl ModelFamily for DoubleFamily {
+        type Model<B: Backend> = Double;
+    }
+
+    #[test]
+    fn should_run_the_model_on_the_cpu() {
+        let runner = ModelRunner::<DoubleFamily>::load(Path::new("model.mpk"), "cpu").unwrap();
+
+        let outputs = runner.forward(vec![TensorData::new(vec![1.0f32, -2.0], [2])]);
+
+        outputs[0].assert_eq(&TensorData::new(vec![2.0f32, -4.0], [2]), false);
+    }
+
+    #[test]
+    fn should_reject_unknown_devices() {
+        let result = ModelRunner::<DoubleFamily>::load(Path::new("model.mpk"), "tpu:0");
+
+        assert!(matches!(result, Err(Error::UnknownDevice(device, _)) if device == "tpu:0"));
+    }
+
+    #[test]
+    fn should_forward_the_record_errors() {
+        let result = ModelRunner::<DoubleFamily>::load(Path::new(""), "cpu");
+
+        assert!(matches!(result, Err(Error::Record(_))));
+    }
+}
@@ -0,0 +1,144 @@
+use burn::tensor::{DType, Element, TensorData};
+use numpy::{PyArray1, PyArrayDyn, PyArrayMethods, PyUntypedArray, PyUntypedArrayMethods};
+use pyo3::{exceptions::PyTypeError, prelude::*};
+
+/// Converts a NumPy array to tensor data.
+///
+/// The arrays of `float64`, `float32`, `int64`, `int32`, `uint8` and `bool` values are supported,
+/// whatever their memory layout.
+pub fn from_numpy(array: &Bound<'_, PyUntypedArray>) -> PyResult<TensorData> {
+    if let Some(data) = try_from_numpy::<f64>(array) {
+        return Ok(data);
+    }
+    if let Some(data) = try_from_numpy::<f32>(array) {
+        return Ok(data);
+    }
+    if let Some(data) = try_from_numpy::<i64>(array) {
+        return Ok(data);
+    }
+    if let Some(data) = try_from_numpy::<i32>(array) {
+        return Ok(data);
+    }
+    if let Some(data) = try_from_numpy::<u8>(array) {
+        return Ok(data);
+    }
+    if let Some(data) = try_from_numpy::<bool>(array) {
+        return Ok(data);
+    }
+
+    Err(PyTypeError::new_err(format!(
+        "Unsupported array data type {}",
+        array.dtype()
+    )))
+}
+
+/// Converts tensor data to a NumPy array with the same shape and data type.
+pub fn to_numpy(py: Python<'_>, data: TensorData) -> PyResult<PyObject> {
+    match data.dtype {
+        DType::F64 => to_array::<f64>(py, data),
+        DType::F32 => to_array::<f32>(py, data),
+        DType::I64 => to_array::<i64>(py, data),
+        DType::I32 => to_array::<i32>(py, data),
+        DType::U8 => to_array::<u8>(py, data),
+        DType::Bool => to_array::<bool>(py, data),
+        // NumPy has no half precision type without extensions
+        DType::F16 | DType::BF16 => to_array::<f32>(py, data.convert::<f32>()),
+        dtype => Err(PyTypeError::new_err(format!(
+            "Unsupported tensor data type {dtype:?}"
+        ))),
+    }
+}
+
+fn try_from_numpy<E>(array: &Bound<'_, PyUntypedArray>) -> Option<TensorData>
+where
+    E: Element + numpy::Element,
+{
+    let array = array.downcast::<PyArrayDyn<E>>().ok()?.readonly();
+    let view = array.as_array();
+
+    // The values are copied in the logical order, so any strides are supported
+    let values = view.iter().copied().collect::<Vec<_>>();
+    Some(TensorData::new(values, view.shape()))
+}
+
+fn to_array<E>(py: Python<'_>, data: TensorData) -> PyResult<PyObject>
+where
+    E: Element + numpy::Element,
+{
+    let shape = data.shape.clone();
+    let values = data
+        .into_vec::<E>()
+        .map_err(|err| PyTypeError::new_err(format!("Invalid tensor data: {err:?}")))?;
+
+    let array = PyArray1::from_vec_bound(py, values).reshape(shape)?;
+    Ok(array.into_any().unbind())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use numpy::{PyArray2, PyArrayMethods};
+
+    fn roundtrip(py: Python<'_>, data: TensorData) -> TensorData {
+        let array = to_numpy(py, data).unwrap();
+        let array = array.bind(py).downcast::<PyUntypedArray>().unwrap();
+
+        from_numpy(array).unwrap()
+    }
+
+    #[test]
+    fn should_convert_the_supported_data_types() {
+        Python::with_gil(|py| {
+            let data = [
+                TensorData::new(vec![1.5f64, -2.0, 3.25, 0.0], [2, 2]),
+                TensorData::new(vec![1.5f32, -2.0, 3.25, 0.0], [4]),
+                TensorData::new(vec![1i64, -2, 3], [3, 1]),
+                TensorData::new(vec![1i32, -2, 3], [1, 3]),
+                TensorData::new(vec![0u8, 255], [2]),
+                TensorData::new(vec![true, false, true, true], [2, 1, 2]),
+            ];
+
+            for data in data {
+                roundtrip(py, data.clone()).assert_eq(&data, true);
+            }
+        });
+    }
+
+    #[test]
+    fn should_convert_half_precision_to_f32() {
+        Python::with_gil(|py| {
+            let data = TensorData::new(vec![1.5f32, -2.0], [2]);
+
+            let output = roundtrip(py, data.clone().convert::<burn::tensor::f16>());
+
+            output.assert_eq(&data, true);
+        });
+    }
+
+    #[test]
+    fn should_read_non_contiguous_arrays_in_logical_order() {
+        Python::with_gil(|py| {
+            let array =
+                PyArray2::from_vec2_bound(py, &[vec![1.0f32, 2.0, 3.0], vec![4.0, 5.0, 6.0]])
+                    .unwrap();
+            let transposed = array.transpose().unwrap();
+
+            let data = from_numpy(transposed.as_untyped()).unwrap();
+
+            data.assert_eq(
+                &TensorData::new(vec![1.0f32, 4.0, 2.0, 5.0, 3.0, 6.0], [3, 2]),
+                true,
+            );
+        });
+    }
+
+    #[test]
+    fn should_reject_unsupported_data_types() {
+        Python::with_gil(|py| {
+            let array = PyArray1::from_vec_bound(py, vec![1u16, 2]);
+
+            assert!(from_numpy(array.as_untyped()).is_err());
+            assert!(to_numpy(py, TensorData::new(vec![1u16, 2], [2])).is_err());
+        });
+    }
+}
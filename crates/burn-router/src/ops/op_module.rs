@@ -4,8 +4,8 @@ use burn_tensor::ops::conv::{
     calculate_conv_output_size, calculate_conv_transpose_output_size, calculate_pool_output_size,
 };
 use burn_tensor::ops::{
-    ConvOptions, ConvTransposeOptions, DeformConv2dBackward, DeformConvOptions, FloatTensor,
-    IntElem, ModuleOps,
+    BoolTensor, ConvOptions, ConvTransposeOptions, DeformConv2dBackward, DeformConvOptions,
+    FloatTensor, IntElem, ModuleOps,
 };
 use burn_tensor::ops::{
     IntTensor, InterpolateOptions, MaxPool1dBackward, MaxPool1dWithIndices, MaxPool2dBackward,
@@ -13,7 +13,7 @@ use burn_tensor::ops::{
 };
 use burn_tensor::repr::{
     AdaptiveAvgPool1dBackwardDescription, AdaptiveAvgPool1dDescription,
-    AdaptiveAvgPool2dBackwardDescription, AdaptiveAvgPool2dDescription,
+    AdaptiveAvgPool2dBackwardDescription, AdaptiveAvgPool2dDescription, AttentionDescription,
     AvgPool1dBackwardDescription, AvgPool1dDescription, AvgPool2dBackwardDescription,
     AvgPool2dDescription, Conv1dDescription, Conv2dDescription, Conv3dDescription,
     ConvTranspose1dDescription, ConvTranspose2dDescription, ConvTranspose3dDescription,
@@ -728,6 +728,37 @@ impl<R: RunnerChannel> ModuleOps<Self> for BackendRouter<R> {
         out
     }
 
+    fn attention(
+        query: FloatTensor<Self>,
+        key: FloatTensor<Self>,
+        value: FloatTensor<Self>,
+        mask: Option<BoolTensor<Self>>,
+    ) -> FloatTensor<Self> {
+        let shape = vec![
+            query.shape[0],
+            query.shape[1],
+            query.shape[2],
+            value.shape[3],
+        ];
+
+        let client = query.client.clone();
+        let out = client.register_empty_tensor(shape, query.dtype);
+
+        let desc = AttentionDescription {
+            query: query.into_description(),
+            key: key.into_description(),
+            value: value.into_description(),
+            mask: mask.map(|mask| mask.into_description()),
+            out: out.to_description_out(),
+        };
+
+        client.register(OperationDescription::Module(
+            ModuleOperationDescription::Attention(desc),
+        ));
+
+        out
+    }
+
     fn deform_conv2d(
         x: FloatTensor<Self>,
         offset: FloatTensor<Self>,
@@ -1195,6 +1195,18 @@ where
                     );
                     handles.register_float_tensor::<B>(&desc.out.id, output);
                 }
+                ModuleOperationDescription::Attention(desc) => {
+                    let query = handles.get_float_tensor::<B>(&desc.query);
+                    let key = handles.get_float_tensor::<B>(&desc.key);
+                    let value = handles.get_float_tensor::<B>(&desc.value);
+                    let mask = desc
+                        .mask
+                        .as_ref()
+                        .map(|mask| handles.get_bool_tensor::<B>(mask));
+
+                    let output = B::attention(query, key, value, mask);
+                    handles.register_float_tensor::<B>(&desc.out.id, output);
+                }
             },
         }
     }
@@ -294,14 +294,47 @@ impl<E: tch::kind::Element + Default + Element> TchTensor<E> {
     /// A new tensor.
     pub fn from_data(data: TensorData, device: tch::Device) -> Self {
         let shape_tch = TchShape::from(data.shape.as_slice());
-        let tensor =
-            tch::Tensor::from_slice(data.convert::<E>().as_slice::<E>().unwrap()).to(device);
-        let tensor = tensor.reshape(shape_tch.dims).to_kind(E::KIND);
+        let data = data.convert::<E>();
+        let tensor = match device {
+            // The data is copied once, to a pinned buffer of the caching host allocator, which
+            // only reuses the buffer once the copy to the device is done. The copy is queued on
+            // the current stream without blocking the host, which can prepare the next batch.
+            #[cfg(feature = "pinned-memory")]
+            tch::Device::Cuda(_) => {
+                let strides = contiguous_strides(&shape_tch.dims);
+                // SAFETY: The data outlives the tensor, which is only read by `pin_memory`.
+                let tensor = unsafe {
+                    tch::Tensor::from_blob(
+                        data.as_bytes().as_ptr(),
+                        &shape_tch.dims,
+                        &strides,
+                        E::KIND,
+                        tch::Device::Cpu,
+                    )
+                };
+                tensor
+                    .pin_memory(device)
+                    .to_device_(device, E::KIND, true, false)
+            }
+            _ => tch::Tensor::from_slice(data.as_slice::<E>().unwrap())
+                .to(device)
+                .reshape(shape_tch.dims),
+        };
 
         Self::new(tensor)
     }
 }
 
+/// The strides of a contiguous tensor of the given shape.
+#[cfg(feature = "pinned-memory")]
+fn contiguous_strides(dims: &[i64]) -> Vec<i64> {
+    let mut strides = vec![1; dims.len()];
+    for i in (0..dims.len().saturating_sub(1)).rev() {
+        strides[i] = strides[i + 1] * dims[i + 1];
+    }
+    strides
+}
+
 impl<E: tch::kind::Element + Default + Copy + std::fmt::Debug> TchTensor<E> {
     /// Creates an empty tensor from a shape and a device.
     ///
@@ -400,6 +433,25 @@ mod tests {
         assert_eq!(data_expected, data_actual);
     }
 
+    #[cfg(feature = "pinned-memory")]
+    #[test]
+    fn should_upload_the_same_data_through_pinned_memory() {
+        if !tch::Cuda::is_available() {
+            return;
+        }
+        let data_expected = TensorData::random::<f32, _, _>(
+            Shape::new([2, 3, 4]),
+            Distribution::Default,
+            &mut StdRng::from_entropy(),
+        );
+        let tensor = TchTensor::<f32>::from_data(data_expected.clone(), tch::Device::Cuda(0));
+
+        let data_actual =
+            Tensor::<LibTorch<f32>, 3>::from_primitive(TensorPrimitive::Float(tensor)).into_data();
+
+        assert_eq!(data_expected, data_actual);
+    }
+
     #[test]
     fn should_not_update_inplace_after_reshape() {
         let tensor_1 = Tensor::<LibTorch<f32>, 1>::from_floats([4.0, 4.0], &Default::default());
@@ -146,6 +146,8 @@ pub enum ModuleOperationDescription {
     Interpolate(InterpolateDescription),
     /// Operation corresponding to [interpolate backward](crate::ops::ModuleOps::interpolate_backward).
     InterpolateBackward(InterpolateBackwardDescription),
+    /// Operation corresponding to [attention](crate::ops::ModuleOps::attention).
+    Attention(AttentionDescription),
 }
 
 /// Basic operations that can be done on any tensor type.
@@ -1195,6 +1197,16 @@ pub struct InterpolateOptionsDescription {
     pub mode: InterpolateModeDescription,
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Serialize, Deserialize)]
+#[allow(missing_docs)]
+pub struct AttentionDescription {
+    pub query: TensorDescription,
+    pub key: TensorDescription,
+    pub value: TensorDescription,
+    pub mask: Option<TensorDescription>,
+    pub out: TensorDescription,
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Serialize, Deserialize)]
 #[allow(missing_docs)]
 pub struct InterpolateDescription {
@@ -1620,6 +1632,10 @@ impl ModuleOperationDescription {
             ModuleOperationDescription::InterpolateBackward(desc) => {
                 vec![&desc.x, &desc.out, &desc.grad]
             }
+            ModuleOperationDescription::Attention(desc) => match &desc.mask {
+                Some(mask) => vec![&desc.query, &desc.key, &desc.value, mask, &desc.out],
+                None => vec![&desc.query, &desc.key, &desc.value, &desc.out],
+            },
         }
     }
 }
@@ -0,0 +1,257 @@
+use crate::{backend::Backend, Int, Shape, Tensor, TensorData};
+use alloc::{collections::BTreeMap, vec::Vec};
+
+/// The reduction applied to the values scattered to the same position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScatterReduction {
+    /// The values replace the elements of the tensor, the indices being expected to be unique.
+    None,
+    /// The values are added to the elements of the tensor.
+    Add,
+    /// The elements of the tensor are multiplied by the values.
+    Mul,
+    /// The elements of the tensor are the maximum of themselves and the values.
+    Max,
+    /// The elements of the tensor are the minimum of themselves and the values.
+    Min,
+}
+
+/// Gathers the slices of the tensor indexed by the last dimension of the indices, as the ONNX
+/// `GatherND` operator without batch dimensions.
+///
+/// # Arguments
+///
+/// * `tensor` - The tensor to gather from.
+/// * `indices` - The indices of shape `[..., k]`, indexing the `k` first dimensions of the tensor.
+///
+/// # Returns
+///
+/// A tensor of shape `[...indices.dims()[..DI - 1], ...tensor.dims()[k..]]`.
+///
+/// # Remarks
+///
+/// The negative indices are counted from the end of their dimension.
+pub fn gather_nd<B: Backend, const D: usize, const DI: usize, const DO: usize>(
+    tensor: Tensor<B, D>,
+    indices: Tensor<B, DI, Int>,
+) -> Tensor<B, DO> {
+    let dims = tensor.dims();
+    let indices_dims = indices.dims();
+    let k = indices_dims[DI - 1];
+    assert_eq!(
+        DO,
+        DI - 1 + D - k,
+        "Gather ND: the output should have {} dimensions",
+        DI - 1 + D - k
+    );
+
+    let (rows, row_size) = rows_of(&dims, k);
+    let indices = linear_indices(indices, &dims);
+
+    let shape = indices_dims[..DI - 1]
+        .iter()
+        .chain(&dims[k..])
+        .copied()
+        .collect::<Vec<_>>();
+
+    tensor
+        .reshape([rows, row_size])
+        .select(0, indices)
+        .reshape(Shape::from(shape))
+}
+
+/// Scatters the values to the slices of the tensor indexed by the last dimension of the indices,
+/// as the ONNX `ScatterND` operator.
+///
+/// # Arguments
+///
+/// * `tensor` - The tensor to scatter into.
+/// * `indices` - The indices of shape `[..., k]`, indexing the `k` first dimensions of the tensor.
+/// * `values` - The values of shape `[...indices.dims()[..DI - 1], ...tensor.dims()[k..]]`.
+/// * `reduction` - The reduction of the values with the elements of the tensor.
+///
+/// # Remarks
+///
+/// The negative indices are counted from the end of their dimension.
+pub fn scatter_nd<B: Backend, const D: usize, const DI: usize, const DV: usize>(
+    tensor: Tensor<B, D>,
+    indices: Tensor<B, DI, Int>,
+    values: Tensor<B, DV>,
+    reduction: ScatterReduction,
+) -> Tensor<B, D> {
+    let dims = tensor.dims();
+    let indices_dims = indices.dims();
+    let k = indices_dims[DI - 1];
+
+    let (rows, row_size) = rows_of(&dims, k);
+    let num_values = indices_dims[..DI - 1].iter().product::<usize>();
+    let indices = linear_indices(indices, &dims);
+
+    scatter_rows(
+        tensor.reshape([rows, row_size]),
+        indices,
+        values.reshape([num_values, row_size]),
+        reduction,
+    )
+    .reshape(dims)
+}
+
+/// Scatters the values along the given dimension, as the ONNX `ScatterElements` operator.
+///
+/// `tensor[i][indices[i][j]] = values[i][j]; // dim = 1`
+///
+/// # Arguments
+///
+/// * `tensor` - The tensor to scatter into.
+/// * `dim` - The dimension indexed by the indices.
+/// * `indices` - The indices, which can be smaller than the tensor in all dimensions.
+/// * `values` - The values, of the same shape as the indices.
+/// * `reduction` - The reduction of the values with the elements of the tensor.
+///
+/// # Remarks
+///
+/// The negative indices are counted from the end of the dimension.
+pub fn scatter_elements<B: Backend, const D: usize>(
+    tensor: Tensor<B, D>,
+    dim: usize,
+    indices: Tensor<B, D, Int>,
+    values: Tensor<B, D>,
+    reduction: ScatterReduction,
+) -> Tensor<B, D> {
+    let dims = tensor.dims();
+    let indices_dims = indices.dims();
+    let device = indices.device();
+    let num_values = indices_dims.iter().product::<usize>();
+
+    // The position of each value is its own position, except along the scattered dimension.
+    let mut position = Tensor::<B, D, Int>::zeros(indices_dims, &device);
+    for (axis, size) in dims.iter().enumerate() {
+        let coordinate = match axis == dim {
+            true => normalize(indices.clone(), *size),
+            false => {
+                let mut shape = [1; D];
+                shape[axis] = indices_dims[axis];
+
+                Tensor::<B, 1, Int>::arange(0..indices_dims[axis] as i64, &device)
+                    .reshape(shape)
+                    .expand(indices_dims)
+            }
+        };
+        position = position.mul_scalar(*size as i64).add(coordinate);
+    }
+
+    scatter_rows(
+        tensor.reshape([num_elements(&dims), 1]),
+        position.reshape([num_values]),
+        values.reshape([num_values, 1]),
+        reduction,
+    )
+    .reshape(dims)
+}
+
+/// The number of rows indexed by the `k` first dimensions and the size of each row.
+fn rows_of(dims: &[usize], k: usize) -> (usize, usize) {
+    (num_elements(&dims[..k]), num_elements(&dims[k..]))
+}
+
+fn num_elements(dims: &[usize]) -> usize {
+    dims.iter().product()
+}
+
+/// The negative indices are counted from the end of the dimension.
+fn normalize<B: Backend, const D: usize>(
+    indices: Tensor<B, D, Int>,
+    size: usize,
+) -> Tensor<B, D, Int> {
+    let negative = indices.clone().lower_elem(0).int();
+    indices.add(negative.mul_scalar(size as i64))
+}
+
+/// The row index of the `k` first dimensions for each index of shape `[..., k]`.
+fn linear_indices<B: Backend, const DI: usize>(
+    indices: Tensor<B, DI, Int>,
+    dims: &[usize],
+) -> Tensor<B, 1, Int> {
+    let indices_dims = indices.dims();
+    let k = indices_dims[DI - 1];
+    let num_indices = num_elements(&indices_dims[..DI - 1]);
+    let indices = indices.reshape([num_indices, k]);
+
+    let mut linear = Tensor::<B, 1, Int>::zeros([num_indices], &indices.device());
+    for (axis, size) in dims[..k].iter().enumerate() {
+        let index = indices.clone().narrow(1, axis, 1).reshape([num_indices]);
+        linear = linear.mul_scalar(*size as i64).add(normalize(index, *size));
+    }
+
+    linear
+}
+
+/// Scatters the rows of the values to the rows of the tensor given by the indices.
+fn scatter_rows<B: Backend>(
+    tensor: Tensor<B, 2>,
+    indices: Tensor<B, 1, Int>,
+    values: Tensor<B, 2>,
+    reduction: ScatterReduction,
+) -> Tensor<B, 2> {
+    if reduction == ScatterReduction::Add {
+        return tensor.select_assign(0, indices, values);
+    }
+
+    // The duplicated indices are reduced in rounds, each round scattering a single value per row.
+    let device = tensor.device();
+    let mut counts = BTreeMap::<i64, usize>::new();
+    let mut rounds = Vec::<(Vec<i64>, Vec<i64>)>::new();
+    for (position, row) in indices.into_data().iter::<i64>().enumerate() {
+        let round = counts.entry(row).or_insert(0);
+        if *round == rounds.len() {
+            rounds.push((Vec::new(), Vec::new()));
+        }
+        rounds[*round].0.push(position as i64);
+        rounds[*round].1.push(row);
+        *round += 1;
+    }
+
+    rounds
+        .into_iter()
+        .fold(tensor, |tensor, (positions, rows)| {
+            let shape = [rows.len()];
+            let rows = Tensor::<B, 1, Int>::from_data(TensorData::new(rows, shape), &device);
+            let values = values.clone().select(
+                0,
+                Tensor::from_data(TensorData::new(positions, shape), &device),
+            );
+            let replaced = || tensor.clone().select(0, rows.clone());
+
+            let values = match reduction {
+                ScatterReduction::Mul => replaced().mul(values),
+                ScatterReduction::Max => replaced().max_pair(values),
+                ScatterReduction::Min => replaced().min_pair(values),
+                _ => values,
+            };
+
+            assign_rows(tensor, rows, values)
+        })
+}
+
+/// Replaces the rows of the tensor given by the unique indices.
+///
+/// The rows are selected with a mask rather than with the difference of the values, which would
+/// be `NaN` for the infinite elements.
+fn assign_rows<B: Backend>(
+    tensor: Tensor<B, 2>,
+    rows: Tensor<B, 1, Int>,
+    values: Tensor<B, 2>,
+) -> Tensor<B, 2> {
+    let [num_rows, row_size] = tensor.dims();
+    let [num_values, _] = values.dims();
+    let device = tensor.device();
+
+    let mask = Tensor::<B, 1, Int>::zeros([num_rows], &device)
+        .select_assign(0, rows.clone(), Tensor::ones([num_values], &device))
+        .equal_elem(1)
+        .reshape([num_rows, 1])
+        .expand([num_rows, row_size]);
+    let scattered = Tensor::zeros([num_rows, row_size], &device).select_assign(0, rows, values);
+
+    tensor.mask_where(mask, scattered)
+}
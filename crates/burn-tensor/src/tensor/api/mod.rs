@@ -7,9 +7,11 @@ mod bool;
 mod cartesian_grid;
 mod chunk;
 mod float;
+mod gather_scatter;
 mod int;
 mod kind;
 mod narrow;
+mod nms;
 mod numeric;
 mod sort;
 
@@ -18,7 +20,9 @@ pub use autodiff::*;
 pub use base::*;
 pub use cartesian_grid::cartesian_grid;
 pub use chunk::chunk;
+pub use gather_scatter::{gather_nd, scatter_elements, scatter_nd, ScatterReduction};
 pub use kind::*;
 pub use narrow::narrow;
+pub use nms::{non_max_suppression, NmsOptions};
 pub use numeric::*;
 pub use sort::{argsort, sort, sort_with_indices};
@@ -0,0 +1,135 @@
+use crate::{backend::Backend, ElementConversion, Int, Shape, Tensor, TensorData};
+use alloc::vec::Vec;
+
+/// Options of the [non-maximum suppression](non_max_suppression).
+#[derive(new, Debug, Clone, PartialEq)]
+pub struct NmsOptions {
+    /// The maximum number of boxes selected for each batch and class.
+    pub max_output_boxes_per_class: usize,
+
+    /// The boxes overlapping a selected box with an intersection over union above the threshold
+    /// are suppressed.
+    pub iou_threshold: f32,
+
+    /// The boxes with a score below or equal to the threshold are ignored.
+    pub score_threshold: Option<f32>,
+
+    /// If the boxes are given by their center and their size `[x_center, y_center, width, height]`
+    /// instead of two opposite corners `[y1, x1, y2, x2]`.
+    pub center_point_box: bool,
+}
+
+/// The corners `[y_min, x_min, y_max, x_max]` of a box.
+fn corners(values: &[f32], center_point_box: bool) -> [f32; 4] {
+    match center_point_box {
+        true => {
+            let [x, y, width, height] = [values[0], values[1], values[2], values[3]];
+            [
+                y - height / 2.0,
+                x - width / 2.0,
+                y + height / 2.0,
+                x + width / 2.0,
+            ]
+        }
+        // The corners can be any pair of opposite corners.
+        false => [
+            values[0].min(values[2]),
+            values[1].min(values[3]),
+            values[0].max(values[2]),
+            values[1].max(values[3]),
+        ],
+    }
+}
+
+fn intersection_over_union(lhs: &[f32; 4], rhs: &[f32; 4]) -> f32 {
+    let area = |b: &[f32; 4]| (b[2] - b[0]) * (b[3] - b[1]);
+    let height = (lhs[2].min(rhs[2]) - lhs[0].max(rhs[0])).max(0.0);
+    let width = (lhs[3].min(rhs[3]) - lhs[1].max(rhs[1])).max(0.0);
+    let intersection = height * width;
+    let union = area(lhs) + area(rhs) - intersection;
+
+    match union > 0.0 {
+        true => intersection / union,
+        false => 0.0,
+    }
+}
+
+/// Selects the boxes with the highest scores of each class, suppressing the boxes overlapping
+/// a selected box, as the ONNX `NonMaxSuppression` operator.
+///
+/// # Arguments
+///
+/// * `boxes` - The boxes of shape `[batch_size, num_boxes, 4]`.
+/// * `scores` - The scores of each class of shape `[batch_size, num_classes, num_boxes]`.
+/// * `options` - The options of the suppression.
+///
+/// # Returns
+///
+/// A 2D tensor of shape `[num_selected, 3]` where each row contains the batch index, the class
+/// index and the box index of a selected box, ordered by batch, by class and by decreasing score.
+///
+/// # Remarks
+///
+/// The number of selected boxes depends on the data, so the boxes and the scores are read
+/// synchronously and the suppression runs on the CPU.
+pub fn non_max_suppression<B: Backend>(
+    boxes: Tensor<B, 3>,
+    scores: Tensor<B, 3>,
+    options: NmsOptions,
+) -> Tensor<B, 2, Int> {
+    let device = boxes.device();
+    let [batch_size, num_boxes, _] = boxes.dims();
+    let [_, num_classes, _] = scores.dims();
+
+    let boxes = boxes.into_data().iter::<f32>().collect::<Vec<_>>();
+    let scores = scores.into_data().iter::<f32>().collect::<Vec<_>>();
+
+    let mut selected = Vec::new();
+    for batch in 0..batch_size {
+        let corners = boxes[batch * num_boxes * 4..(batch + 1) * num_boxes * 4]
+            .chunks(4)
+            .map(|values| corners(values, options.center_point_box))
+            .collect::<Vec<_>>();
+
+        for class in 0..num_classes {
+            let start = (batch * num_classes + class) * num_boxes;
+            let class_scores = &scores[start..start + num_boxes];
+
+            let mut candidates = (0..num_boxes)
+                .filter(|index| match options.score_threshold {
+                    Some(threshold) => class_scores[*index] > threshold,
+                    None => true,
+                })
+                .collect::<Vec<_>>();
+            // The sort is stable, so the boxes with the same score keep their order.
+            candidates.sort_by(|a, b| class_scores[*b].total_cmp(&class_scores[*a]));
+
+            let mut kept: Vec<usize> = Vec::new();
+            for candidate in candidates {
+                if kept.len() >= options.max_output_boxes_per_class {
+                    break;
+                }
+                let suppressed = kept.iter().any(|index| {
+                    intersection_over_union(&corners[*index], &corners[candidate])
+                        > options.iou_threshold
+                });
+                if !suppressed {
+                    kept.push(candidate);
+                }
+            }
+
+            selected.extend(kept.into_iter().flat_map(|index| [batch, class, index]));
+        }
+    }
+
+    let num_selected = selected.len() / 3;
+    let selected = selected
+        .into_iter()
+        .map(|index| (index as i64).elem::<B::IntElem>())
+        .collect::<Vec<_>>();
+
+    Tensor::from_data(
+        TensorData::new(selected, Shape::new([num_selected, 3])),
+        &device,
+    )
+}
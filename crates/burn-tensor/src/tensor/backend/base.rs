@@ -3,7 +3,7 @@ use alloc::string::String;
 use crate::tensor::Element;
 use crate::{ops::*, quantization::QTensorPrimitive};
 
-use super::{BackendBridge, DeviceOps};
+use super::{BackendBridge, DeviceOps, MemoryUsage};
 
 /// This trait defines all types and functions needed for a backend to be used with burn.
 ///
@@ -108,6 +108,11 @@ pub trait Backend:
 
     /// Sync the backend, ensure that all computation are finished.
     fn sync(_device: &Self::Device) {}
+
+    /// The memory used on the device, for the backends managing their memory with pools.
+    fn memory_usage(_device: &Self::Device) -> Option<MemoryUsage> {
+        None
+    }
 }
 
 /// Trait that allows a backend to support autodiff.
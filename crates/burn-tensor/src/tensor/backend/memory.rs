@@ -0,0 +1,10 @@
+/// The memory of a device, as reported by the memory management of the
+/// [backend](crate::backend::Backend::memory_usage).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// The number of bytes allocated to the tensors.
+    pub bytes_in_use: u64,
+    /// The number of bytes reserved by the memory pools, including the free space kept for the
+    /// future allocations.
+    pub bytes_reserved: u64,
+}
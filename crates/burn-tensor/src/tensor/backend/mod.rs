@@ -1,10 +1,12 @@
 mod base;
 mod bridge;
 mod device;
+mod memory;
 
 pub use base::*;
 pub use bridge::*;
 pub use device::*;
+pub use memory::*;
 
 // Not needed for now, useful for different tensor memory layout
 // pub mod conversion;
@@ -1,7 +1,7 @@
 use crate::{
     backend::Backend,
     ops::{ConvOptions, ConvTransposeOptions, InterpolateOptions, UnfoldOptions},
-    Int, Tensor, TensorPrimitive,
+    Bool, Int, Tensor, TensorPrimitive,
 };
 
 use super::ops::DeformConvOptions;
@@ -316,3 +316,21 @@ where
         options,
     )))
 }
+
+/// Applies the [scaled dot-product attention](crate::ops::ModuleOps::attention).
+pub fn attention<B>(
+    query: Tensor<B, 4>,
+    key: Tensor<B, 4>,
+    value: Tensor<B, 4>,
+    mask: Option<Tensor<B, 4, Bool>>,
+) -> Tensor<B, 4>
+where
+    B: Backend,
+{
+    Tensor::new(TensorPrimitive::Float(B::attention(
+        query.primitive.tensor(),
+        key.primitive.tensor(),
+        value.primitive.tensor(),
+        mask.map(|mask| mask.primitive),
+    )))
+}
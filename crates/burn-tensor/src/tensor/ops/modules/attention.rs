@@ -0,0 +1,39 @@
+use crate::{
+    backend::Backend,
+    ops::{BoolTensor, FloatTensor},
+    ElementConversion,
+};
+
+/// The attention score of the masked positions, low enough for their weight to vanish after the
+/// softmax without producing NaNs in the rows where every position is masked.
+pub const ATTENTION_MASK_VALUE: f32 = -1.0e4;
+
+/// Computes the attention by materializing the score matrix, with the matrix multiplication and
+/// softmax of the backend.
+///
+/// This is the default implementation of the [attention](crate::ops::ModuleOps::attention), and the
+/// reference of the fused kernels.
+pub fn attention_from_matmul<B: Backend>(
+    query: FloatTensor<B>,
+    key: FloatTensor<B>,
+    value: FloatTensor<B>,
+    mask: Option<BoolTensor<B>>,
+) -> FloatTensor<B> {
+    let [_, _, _, d_k] = B::float_shape(&query).dims();
+    let scale = (d_k as f64).sqrt().recip();
+
+    let scores = B::float_matmul(query, B::float_swap_dims(key, 2, 3));
+    let scores = B::float_mul_scalar(scores, scale.elem());
+    let scores = match mask {
+        Some(mask) => B::float_mask_fill(scores, mask, ATTENTION_MASK_VALUE.elem()),
+        None => scores,
+    };
+
+    // Softmax over the keys, shifted by the maximum score for stability
+    let max = B::float_max_dim(scores.clone(), 3);
+    let weights = B::float_exp(B::float_sub(scores, max));
+    let sum = B::float_sum_dim(weights.clone(), 3);
+    let weights = B::float_div(weights, sum);
+
+    B::float_matmul(weights, value)
+}
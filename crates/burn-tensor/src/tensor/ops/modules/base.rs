@@ -1,7 +1,7 @@
-use super::{conv, pool, unfold::unfold4d_using_conv2d};
+use super::{attention, conv, pool, unfold::unfold4d_using_conv2d};
 use crate::{
     backend::Backend,
-    ops::{FloatTensor, IntTensor},
+    ops::{BoolTensor, FloatTensor, IntTensor},
     Shape,
 };
 
@@ -689,4 +689,28 @@ pub trait ModuleOps<B: Backend> {
         output_size: [usize; 2],
         options: InterpolateOptions,
     ) -> FloatTensor<B>;
+
+    /// Scaled dot-product attention, `softmax(query * key^T / sqrt(d_k)) * value`.
+    ///
+    /// The positions where the mask is `true` are ignored, their score being replaced by
+    /// [ATTENTION_MASK_VALUE](attention::ATTENTION_MASK_VALUE).
+    ///
+    /// The default implementation materializes the score matrix, the backends can compute the
+    /// attention without it.
+    ///
+    /// # Shapes
+    ///
+    /// query: `[batch_size, n_heads, seq_length_q, d_k]`,
+    /// key:   `[batch_size, n_heads, seq_length_k, d_k]`,
+    /// value: `[batch_size, n_heads, seq_length_k, d_v]`,
+    /// mask:  `[batch_size, n_heads, seq_length_q, seq_length_k]`,
+    /// output: `[batch_size, n_heads, seq_length_q, d_v]`
+    fn attention(
+        query: FloatTensor<B>,
+        key: FloatTensor<B>,
+        value: FloatTensor<B>,
+        mask: Option<BoolTensor<B>>,
+    ) -> FloatTensor<B> {
+        attention::attention_from_matmul::<B>(query, key, value, mask)
+    }
 }
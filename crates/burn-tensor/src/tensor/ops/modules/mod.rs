@@ -1,3 +1,5 @@
+/// Module with attention operations.
+pub mod attention;
 /// Module with convolution operations.
 pub mod conv;
 
@@ -152,6 +152,7 @@ macro_rules! testgen_with_float_param {
         burn_tensor::testgen_module_nearest_interpolate!();
         burn_tensor::testgen_module_bilinear_interpolate!();
         burn_tensor::testgen_module_bicubic_interpolate!();
+        burn_tensor::testgen_module_attention!();
 
         // test ops
         burn_tensor::testgen_gather_scatter!();
@@ -213,6 +214,8 @@ macro_rules! testgen_with_float_param {
         burn_tensor::testgen_tri_mask!();
         burn_tensor::testgen_sort_argsort!();
         burn_tensor::testgen_topk!();
+        burn_tensor::testgen_non_max_suppression!();
+        burn_tensor::testgen_gather_scatter_nd!();
         burn_tensor::testgen_remainder!();
         burn_tensor::testgen_cartesian_grid!();
         burn_tensor::testgen_nan!();
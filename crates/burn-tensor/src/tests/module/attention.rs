@@ -0,0 +1,115 @@
+#[burn_tensor_testgen::testgen(module_attention)]
+mod tests {
+    use super::*;
+    use burn_tensor::module::attention;
+    use burn_tensor::ops::attention::attention_from_matmul;
+    use burn_tensor::{Distribution, TensorPrimitive};
+
+    #[test]
+    fn test_attention_matches_the_reference() {
+        let device = Default::default();
+        let query = TestTensor::<4>::random([2, 3, 5, 4], Distribution::Default, &device);
+        let key = TestTensor::<4>::random([2, 3, 7, 4], Distribution::Default, &device);
+        let value = TestTensor::<4>::random([2, 3, 7, 6], Distribution::Default, &device);
+
+        let output = attention(query.clone(), key.clone(), value.clone(), None);
+
+        reference(query, key, value, None)
+            .into_data()
+            .assert_approx_eq(&output.into_data(), 3);
+    }
+
+    #[test]
+    fn test_attention_ignores_the_masked_positions() {
+        let device = Default::default();
+        let query = TestTensor::<4>::random([1, 2, 3, 4], Distribution::Default, &device);
+        let key = TestTensor::<4>::random([1, 2, 4, 4], Distribution::Default, &device);
+        let value = TestTensor::<4>::random([1, 2, 4, 2], Distribution::Default, &device);
+        // Causal mask, the last key is never attended to
+        let mask = TestTensorBool::<4>::from([[
+            [
+                [false, true, true, true],
+                [false, false, true, true],
+                [false, false, false, true],
+            ],
+            [
+                [false, true, true, true],
+                [false, false, true, true],
+                [false, false, false, true],
+            ],
+        ]]);
+
+        let output = attention(
+            query.clone(),
+            key.clone(),
+            value.clone(),
+            Some(mask.clone()),
+        );
+
+        reference(query, key, value, Some(mask))
+            .into_data()
+            .assert_approx_eq(&output.into_data(), 3);
+    }
+
+    #[test]
+    fn test_attention_matches_the_reference_over_several_tiles() {
+        let device = Default::default();
+        // More queries and keys than the 32 of a block of the fused kernels
+        let query = TestTensor::<4>::random([2, 2, 70, 8], Distribution::Default, &device);
+        let key = TestTensor::<4>::random([2, 2, 45, 8], Distribution::Default, &device);
+        let value = TestTensor::<4>::random([2, 2, 45, 4], Distribution::Default, &device);
+        let mask =
+            TestTensor::<4>::random([2, 2, 70, 45], Distribution::Default, &device).lower_elem(0.3);
+
+        let output = attention(
+            query.clone(),
+            key.clone(),
+            value.clone(),
+            Some(mask.clone()),
+        );
+
+        reference(query, key, value, Some(mask))
+            .into_data()
+            .assert_approx_eq(&output.into_data(), 3);
+    }
+
+    #[test]
+    fn test_attention_matches_the_reference_for_large_heads() {
+        let device = Default::default();
+        // Heads too large for the tiles of the fused kernels in shared memory
+        let query = TestTensor::<4>::random([1, 2, 40, 64], Distribution::Default, &device);
+        let key = TestTensor::<4>::random([1, 2, 36, 64], Distribution::Default, &device);
+        let value = TestTensor::<4>::random([1, 2, 36, 80], Distribution::Default, &device);
+        // The same mask for all the heads, through a view with a stride of 0
+        let mask = TestTensor::<4>::random([1, 1, 40, 36], Distribution::Default, &device)
+            .lower_elem(0.3)
+            .expand([1, 2, 40, 36]);
+
+        let output = attention(
+            query.clone(),
+            key.clone(),
+            value.clone(),
+            Some(mask.clone()),
+        );
+
+        reference(query, key, value, Some(mask))
+            .into_data()
+            .assert_approx_eq(&output.into_data(), 3);
+    }
+
+    fn reference(
+        query: TestTensor<4>,
+        key: TestTensor<4>,
+        value: TestTensor<4>,
+        mask: Option<TestTensorBool<4>>,
+    ) -> TestTensor<4> {
+        TestTensor::from_primitive(TensorPrimitive::Float(
+            attention_from_matmul::<TestBackend>(
+                query.into_primitive().tensor(),
+                key.into_primitive().tensor(),
+                value.into_primitive().tensor(),
+                mask.map(|mask| mask.into_primitive()),
+            ),
+        ))
+    }
+}
@@ -0,0 +1,128 @@
+#[burn_tensor_testgen::testgen(gather_scatter_nd)]
+mod tests {
+    use super::*;
+    use burn_tensor::{gather_nd, scatter_elements, scatter_nd, ScatterReduction, TensorData};
+
+    #[test]
+    fn should_gather_nd_elements() {
+        let device = Default::default();
+        let tensor = TestTensor::<2>::from_floats([[0.0, 1.0], [2.0, 3.0]], &device);
+        let indices = TestTensorInt::<2>::from_ints([[0, 0], [1, -1]], &device);
+
+        let output: TestTensor<1> = gather_nd(tensor, indices);
+
+        output
+            .into_data()
+            .assert_eq(&TensorData::from([0.0, 3.0]), false);
+    }
+
+    #[test]
+    fn should_gather_nd_slices() {
+        let device = Default::default();
+        let tensor = TestTensor::<2>::from_floats([[0.0, 1.0], [2.0, 3.0]], &device);
+        let indices = TestTensorInt::<2>::from_ints([[1], [0]], &device);
+
+        let output: TestTensor<2> = gather_nd(tensor, indices);
+
+        output
+            .into_data()
+            .assert_eq(&TensorData::from([[2.0, 3.0], [0.0, 1.0]]), false);
+    }
+
+    #[test]
+    fn should_scatter_nd_replacing_the_elements() {
+        let device = Default::default();
+        let tensor =
+            TestTensor::<1>::from_floats([1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0], &device);
+        let indices = TestTensorInt::<2>::from_ints([[4], [3], [1], [7]], &device);
+        let values = TestTensor::<1>::from_floats([9.0, 10.0, 11.0, 12.0], &device);
+
+        let output = scatter_nd(tensor, indices, values, ScatterReduction::None);
+
+        output.into_data().assert_eq(
+            &TensorData::from([1.0, 11.0, 3.0, 10.0, 9.0, 6.0, 7.0, 12.0]),
+            false,
+        );
+    }
+
+    #[test]
+    fn should_scatter_nd_multiplying_the_duplicated_indices() {
+        let device = Default::default();
+        let tensor = TestTensor::<2>::from_floats([[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]], &device);
+        let indices = TestTensorInt::<2>::from_ints([[1], [1], [-1]], &device);
+        let values = TestTensor::<2>::from_floats([[2.0, 2.0], [3.0, 0.5], [-1.0, 1.0]], &device);
+
+        let output = scatter_nd(tensor, indices, values, ScatterReduction::Mul);
+
+        output.into_data().assert_eq(
+            &TensorData::from([[1.0, 2.0], [18.0, 4.0], [-5.0, 6.0]]),
+            false,
+        );
+    }
+
+    #[test]
+    fn should_scatter_nd_keeping_the_infinite_elements() {
+        let device = Default::default();
+        let tensor =
+            TestTensor::<1>::from_floats([f32::INFINITY, 2.0, f32::NEG_INFINITY, 4.0], &device);
+        let indices = TestTensorInt::<2>::from_ints([[0], [3]], &device);
+        let values = TestTensor::<1>::from_floats([5.0, f32::INFINITY], &device);
+
+        let output = scatter_nd(tensor, indices, values, ScatterReduction::None);
+
+        output.into_data().assert_eq(
+            &TensorData::from([5.0, 2.0, f32::NEG_INFINITY, f32::INFINITY]),
+            false,
+        );
+    }
+
+    #[test]
+    fn should_scatter_nd_with_the_minimum_of_the_infinite_values() {
+        let device = Default::default();
+        let tensor = TestTensor::<2>::from_floats([[1.0, f32::INFINITY], [3.0, 4.0]], &device);
+        let indices = TestTensorInt::<2>::from_ints([[0], [0], [1]], &device);
+        let values = TestTensor::<2>::from_floats(
+            [[f32::NEG_INFINITY, 2.0], [0.0, f32::INFINITY], [5.0, 1.0]],
+            &device,
+        );
+
+        let output = scatter_nd(tensor, indices, values, ScatterReduction::Min);
+
+        output.into_data().assert_eq(
+            &TensorData::from([[f32::NEG_INFINITY, 2.0], [3.0, 1.0]]),
+            false,
+        );
+    }
+
+    #[test]
+    fn should_scatter_elements_into_a_larger_tensor() {
+        let device = Default::default();
+        let tensor = TestTensor::<2>::from_floats(
+            [[1.0, 2.0, 3.0, 4.0, 5.0], [6.0, 7.0, 8.0, 9.0, 10.0]],
+            &device,
+        );
+        let indices = TestTensorInt::<2>::from_ints([[1, 3]], &device);
+        let values = TestTensor::<2>::from_floats([[1.1, 2.1]], &device);
+
+        let output = scatter_elements(tensor, 1, indices, values, ScatterReduction::None);
+
+        output.into_data().assert_approx_eq(
+            &TensorData::from([[1.0, 1.1, 3.0, 2.1, 5.0], [6.0, 7.0, 8.0, 9.0, 10.0]]),
+            3,
+        );
+    }
+
+    #[test]
+    fn should_scatter_elements_with_the_maximum() {
+        let device = Default::default();
+        let tensor = TestTensor::<2>::from_floats([[1.0, 2.0, 3.0, 4.0, 5.0]], &device);
+        let indices = TestTensorInt::<2>::from_ints([[1, 1, -1]], &device);
+        let values = TestTensor::<2>::from_floats([[1.5, 2.5, 0.5]], &device);
+
+        let output = scatter_elements(tensor, 1, indices, values, ScatterReduction::Max);
+
+        output
+            .into_data()
+            .assert_eq(&TensorData::from([[1.0, 2.5, 3.0, 4.0, 5.0]]), false);
+    }
+}
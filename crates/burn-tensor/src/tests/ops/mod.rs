@@ -26,6 +26,7 @@ mod flip;
 mod floor;
 mod full;
 mod gather_scatter;
+mod gather_scatter_nd;
 mod init;
 mod iter_dim;
 mod log;
@@ -39,6 +40,7 @@ mod mul;
 mod nan;
 mod narrow;
 mod neg;
+mod non_max_suppression;
 mod one_hot;
 mod padding;
 mod permute;
@@ -0,0 +1,46 @@
+#[burn_tensor_testgen::testgen(non_max_suppression)]
+mod tests {
+    use super::*;
+    use burn_tensor::{non_max_suppression, NmsOptions, TensorData};
+
+    fn scores() -> TestTensor<3> {
+        TestTensor::from([[[0.9, 0.75, 0.6, 0.95, 0.5, 0.3]]])
+    }
+
+    #[test]
+    fn should_suppress_the_overlapping_boxes() {
+        let boxes = TestTensor::from([[
+            [0.0, 0.0, 1.0, 1.0],
+            [0.0, 0.1, 1.0, 1.1],
+            [0.0, -0.1, 1.0, 0.9],
+            [0.0, 10.0, 1.0, 11.0],
+            [0.0, 10.1, 1.0, 11.1],
+            [0.0, 100.0, 1.0, 101.0],
+        ]]);
+
+        let selected = non_max_suppression(boxes, scores(), NmsOptions::new(3, 0.5, None, false));
+
+        selected
+            .into_data()
+            .assert_eq(&TensorData::from([[0, 0, 3], [0, 0, 0], [0, 0, 5]]), false);
+    }
+
+    #[test]
+    fn should_suppress_the_center_point_boxes_below_the_score_threshold() {
+        let boxes = TestTensor::from([[
+            [0.5, 0.5, 1.0, 1.0],
+            [0.5, 0.6, 1.0, 1.0],
+            [0.5, 0.4, 1.0, 1.0],
+            [0.5, 10.5, 1.0, 1.0],
+            [0.5, 10.6, 1.0, 1.0],
+            [0.5, 100.5, 1.0, 1.0],
+        ]]);
+
+        let selected =
+            non_max_suppression(boxes, scores(), NmsOptions::new(3, 0.5, Some(0.4), true));
+
+        selected
+            .into_data()
+            .assert_eq(&TensorData::from([[0, 0, 3], [0, 0, 0]]), false);
+    }
+}
@@ -17,6 +17,7 @@ enum Message<R, B: Backend> {
 struct CheckpointerThread<C, R, B: Backend> {
     checkpointer: C,
     receiver: mpsc::Receiver<Message<R, B>>,
+    slots: mpsc::Receiver<()>,
 }
 
 impl<C, R, B> CheckpointerThread<C, R, B>
@@ -34,10 +35,13 @@ where
                         .send(record)
                         .expect("Can send response through callback channel.");
                 }
-                Message::Save(epoch, state) => self
-                    .checkpointer
-                    .save(epoch, state)
-                    .expect("Can save the state."),
+                Message::Save(epoch, state) => {
+                    self.checkpointer
+                        .save(epoch, state)
+                        .expect("Can save the state.");
+                    // Frees the slot taken by the record
+                    self.slots.recv().ok();
+                }
                 Message::Delete(epoch) => self
                     .checkpointer
                     .delete(epoch)
@@ -50,9 +54,15 @@ where
     }
 }
 
-/// Async checkpointer.
+/// Async checkpointer, saving the records on a background thread so the training isn't stalled
+/// while they are written.
+///
+/// The records are moved to the thread as they are. Since the tensor operations don't modify
+/// their inputs, a record is a snapshot of the parameters which the next training steps don't
+/// change, without copying them.
 pub struct AsyncCheckpointer<Record, B: Backend> {
-    sender: mpsc::SyncSender<Message<Record, B>>,
+    sender: mpsc::Sender<Message<Record, B>>,
+    slots: mpsc::SyncSender<()>,
     handler: Option<std::thread::JoinHandle<()>>,
 }
 
@@ -74,12 +84,36 @@ where
     where
         C: Checkpointer<R, B> + Send + 'static,
     {
-        // Only on checkpoint can be done in advance.
-        let (sender, receiver) = mpsc::sync_channel(0);
-        let thread = CheckpointerThread::new(checkpointer, receiver);
+        // Only one checkpoint can be done in advance.
+        Self::with_max_pending(checkpointer, 1)
+    }
+
+    /// Create a new async checkpointer, writing or waiting to write at most `max_pending`
+    /// records.
+    ///
+    /// Saving a record blocks until the oldest pending record is written when there are already
+    /// `max_pending` of them, each pending record keeping its tensors alive. Deleting or
+    /// restoring a record never waits for a slot.
+    ///
+    /// # Panics
+    ///
+    /// If `max_pending` is 0.
+    pub fn with_max_pending<C>(checkpointer: C, max_pending: usize) -> Self
+    where
+        C: Checkpointer<R, B> + Send + 'static,
+    {
+        assert!(max_pending > 0, "At least one record should be pending");
+
+        let (sender, receiver) = mpsc::channel();
+        let (slots, slots_receiver) = mpsc::sync_channel(max_pending);
+        let thread = CheckpointerThread::new(checkpointer, receiver, slots_receiver);
         let handler = Some(std::thread::spawn(move || thread.run()));
 
-        Self { sender, handler }
+        Self {
+            sender,
+            slots,
+            handler,
+        }
     }
 }
 
@@ -89,6 +123,10 @@ where
     B: Backend,
 {
     fn save(&self, epoch: usize, record: R) -> Result<(), CheckpointerError> {
+        // Waits for a slot, the thread frees one for each record written
+        self.slots
+            .send(())
+            .expect("Can reserve a slot for the record.");
         self.sender
             .send(Message::Save(epoch, record))
             .expect("Can send message to checkpointer thread.");
@@ -135,3 +173,63 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default, Clone)]
+    struct LogCheckpointer {
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Checkpointer<(), TestBackend> for LogCheckpointer {
+        fn save(&self, epoch: usize, _record: ()) -> Result<(), CheckpointerError> {
+            self.log.lock().unwrap().push(format!("save {epoch}"));
+            Ok(())
+        }
+
+        fn delete(&self, epoch: usize) -> Result<(), CheckpointerError> {
+            self.log.lock().unwrap().push(format!("delete {epoch}"));
+            Ok(())
+        }
+
+        fn restore(
+            &self,
+            epoch: usize,
+            _device: &<TestBackend as Backend>::Device,
+        ) -> Result<(), CheckpointerError> {
+            self.log.lock().unwrap().push(format!("restore {epoch}"));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_messages_are_handled_in_order() {
+        let checkpointer = LogCheckpointer::default();
+        let log = checkpointer.log.clone();
+        let async_checkpointer = AsyncCheckpointer::with_max_pending(checkpointer, 2);
+
+        for epoch in 1..=3 {
+            async_checkpointer.save(epoch, ()).unwrap();
+            async_checkpointer.delete(epoch - 1).unwrap();
+        }
+        async_checkpointer.restore(3, &Default::default()).unwrap();
+        drop(async_checkpointer);
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                "save 1",
+                "delete 0",
+                "save 2",
+                "delete 1",
+                "save 3",
+                "delete 2",
+                "restore 3"
+            ]
+        );
+    }
+}
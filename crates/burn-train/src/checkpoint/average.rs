@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+
+use super::{Checkpointer, CheckpointerError};
+use burn_core::module::{Module, ModuleMapper, ModuleVisitor, ParamId};
+use burn_core::tensor::backend::Backend;
+use burn_core::tensor::{Shape, Tensor};
+
+/// Averages the parameters of the models saved at the given epochs into a single model.
+///
+/// Each checkpoint is loaded into the module, so the precision of the saved records is converted
+/// to the precision of the backend by the recorder. The checkpoints must contain the same
+/// parameters with the same shapes, otherwise an error is returned. The integer and boolean
+/// tensors aren't averaged and are taken from the last checkpoint.
+///
+/// Averaging the last checkpoints of a training, as listed by
+/// [FileCheckpointer::epochs](super::FileCheckpointer::epochs), often improves the final model.
+pub fn average_checkpoints<B, M, C>(
+    module: M,
+    checkpointer: &C,
+    epochs: &[usize],
+    device: &B::Device,
+) -> Result<M, CheckpointerError>
+where
+    B: Backend,
+    M: Module<B>,
+    C: Checkpointer<M::Record, B>,
+{
+    let Some((last, others)) = epochs.split_last() else {
+        return Err(CheckpointerError::Unknown(
+            "At least one checkpoint is required to compute an average".to_string(),
+        ));
+    };
+
+    let mut sum = ParamsSum::<B>::default();
+    for epoch in others {
+        let record = checkpointer.restore(*epoch, device)?;
+        module.clone().load_record(record).visit(&mut sum);
+        sum.end_module(*epoch)?;
+    }
+
+    let record = checkpointer.restore(*last, device)?;
+    let module = module.load_record(record);
+    module.visit(&mut sum);
+    sum.end_module(*last)?;
+
+    let mut average = ParamsAverage {
+        sums: sum.sums,
+        path: Vec::new(),
+        num_modules: epochs.len(),
+    };
+
+    Ok(module.map(&mut average))
+}
+
+/// Sums the float parameters of the modules by path.
+struct ParamsSum<B: Backend> {
+    path: Vec<String>,
+    sums: HashMap<String, ParamSum<B>>,
+    num_modules: usize,
+    error: Option<String>,
+}
+
+struct ParamSum<B: Backend> {
+    shape: Shape,
+    sum: Tensor<B, 1>,
+    count: usize,
+}
+
+impl<B: Backend> Default for ParamsSum<B> {
+    fn default() -> Self {
+        Self {
+            path: Vec::new(),
+            sums: HashMap::new(),
+            num_modules: 0,
+            error: None,
+        }
+    }
+}
+
+impl<B: Backend> ParamsSum<B> {
+    /// Checks that the visited module contains the same parameters as the previous ones.
+    fn end_module(&mut self, epoch: usize) -> Result<(), CheckpointerError> {
+        self.num_modules += 1;
+
+        if let Some(error) = self.error.take() {
+            return Err(CheckpointerError::Unknown(format!(
+                "Invalid checkpoint {epoch}: {error}"
+            )));
+        }
+
+        let missing = self
+            .sums
+            .iter()
+            .filter(|(_, param)| param.count != self.num_modules)
+            .map(|(path, _)| path.as_str())
+            .collect::<Vec<_>>();
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(CheckpointerError::Unknown(format!(
+                "The parameters {missing:?} aren't in all the checkpoints, up to checkpoint {epoch}"
+            )))
+        }
+    }
+}
+
+impl<B: Backend> ModuleVisitor<B> for ParamsSum<B> {
+    fn enter_module(&mut self, name: &str) {
+        self.path.push(name.into());
+    }
+
+    fn exit_module(&mut self, _name: &str) {
+        self.path.pop();
+    }
+
+    fn visit_float<const D: usize>(&mut self, _id: ParamId, tensor: &Tensor<B, D>) {
+        let path = self.path.join(".");
+        let shape = tensor.shape();
+        let flat = tensor.clone().reshape([shape.num_elements()]);
+
+        match self.sums.get_mut(&path) {
+            Some(param) if param.shape != shape => {
+                self.error = Some(format!(
+                    "The parameter {path} has the shape {:?} instead of {:?}",
+                    shape.dims, param.shape.dims
+                ));
+            }
+            Some(param) => {
+                param.sum = param.sum.clone() + flat;
+                param.count += 1;
+            }
+            None => {
+                let param = ParamSum {
+                    shape,
+                    sum: flat,
+                    count: 1,
+                };
+                self.sums.insert(path, param);
+            }
+        }
+    }
+}
+
+/// Replaces the float parameters of the module by their average.
+struct ParamsAverage<B: Backend> {
+    sums: HashMap<String, ParamSum<B>>,
+    path: Vec<String>,
+    num_modules: usize,
+}
+
+impl<B: Backend> ModuleMapper<B> for ParamsAverage<B> {
+    fn enter_module(&mut self, name: &str) {
+        self.path.push(name.into());
+    }
+
+    fn exit_module(&mut self, _name: &str) {
+        self.path.pop();
+    }
+
+    fn map_float<const D: usize>(&mut self, _id: ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
+        let Some(param) = self.sums.remove(&self.path.join(".")) else {
+            return tensor;
+        };
+        let require_grad = tensor.is_require_grad();
+
+        param
+            .sum
+            .div_scalar(self.num_modules as f64)
+            .reshape(tensor.dims())
+            .set_require_grad(require_grad)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkpoint::FileCheckpointer;
+    use crate::TestBackend;
+    use burn_core::nn::{Linear, LinearConfig};
+    use burn_core::record::{FullPrecisionSettings, NamedMpkFileRecorder};
+    use burn_core::tensor::TensorData;
+
+    fn checkpointer(name: &str) -> FileCheckpointer<NamedMpkFileRecorder<FullPrecisionSettings>> {
+        let directory = std::env::temp_dir().join("burn-average-checkpoints");
+        FileCheckpointer::new(NamedMpkFileRecorder::new(), directory, name)
+    }
+
+    fn linear(value: f32, bias: bool) -> Linear<TestBackend> {
+        let device = Default::default();
+        let linear = LinearConfig::new(2, 2)
+            .with_bias(bias)
+            .init::<TestBackend>(&device);
+
+        linear.map(&mut Fill(value))
+    }
+
+    struct Fill(f32);
+
+    impl ModuleMapper<TestBackend> for Fill {
+        fn map_float<const D: usize>(
+            &mut self,
+            _id: ParamId,
+            tensor: Tensor<TestBackend, D>,
+        ) -> Tensor<TestBackend, D> {
+            tensor.ones_like().mul_scalar(self.0)
+        }
+    }
+
+    #[test]
+    fn should_average_the_parameters_of_the_checkpoints() {
+        let checkpointer = checkpointer("average");
+        for (epoch, value) in [(1, 1.0), (2, 2.0), (3, 6.0)] {
+            Checkpointer::<_, TestBackend>::save(
+                &checkpointer,
+                epoch,
+                linear(value, true).into_record(),
+            )
+            .unwrap();
+        }
+
+        let epochs = checkpointer.epochs();
+        let module = average_checkpoints(
+            linear(0.0, true),
+            &checkpointer,
+            &epochs,
+            &Default::default(),
+        )
+        .unwrap();
+
+        assert_eq!(epochs, vec![1, 2, 3]);
+        module
+            .weight
+            .val()
+            .into_data()
+            .assert_eq(&TensorData::from([[3.0f32, 3.0], [3.0, 3.0]]), false);
+        module
+            .bias
+            .unwrap()
+            .val()
+            .into_data()
+            .assert_eq(&TensorData::from([3.0f32, 3.0]), false);
+    }
+
+    #[test]
+    fn should_fail_when_a_parameter_is_missing() {
+        let checkpointer = checkpointer("missing");
+        Checkpointer::<_, TestBackend>::save(&checkpointer, 1, linear(1.0, true).into_record())
+            .unwrap();
+        Checkpointer::<_, TestBackend>::save(&checkpointer, 2, linear(1.0, false).into_record())
+            .unwrap();
+
+        let result = average_checkpoints(
+            linear(0.0, true),
+            &checkpointer,
+            &[1, 2],
+            &Default::default(),
+        );
+
+        assert!(matches!(result, Err(CheckpointerError::Unknown(_))));
+    }
+}
@@ -32,9 +32,31 @@ impl<FR> FileCheckpointer<FR> {
         }
     }
 
-    fn path_for_epoch(&self, epoch: usize) -> PathBuf {
+    pub(crate) fn path_for_epoch(&self, epoch: usize) -> PathBuf {
         self.directory.join(format!("{}-{}", self.name, epoch))
     }
+
+    /// The epochs of the checkpoints saved in the directory, in increasing order.
+    pub fn epochs(&self) -> Vec<usize> {
+        let prefix = format!("{}-", self.name);
+        let Ok(entries) = std::fs::read_dir(&self.directory) else {
+            return Vec::new();
+        };
+
+        let mut epochs = entries
+            .filter_map(|entry| {
+                let file_name = entry.ok()?.file_name();
+                let file_name = file_name.to_str()?.strip_prefix(&prefix)?;
+                // The epoch is followed by the extension of the recorder.
+                let epoch = file_name.split('.').next()?;
+
+                epoch.parse::<usize>().ok()
+            })
+            .collect::<Vec<_>>();
+        epochs.sort();
+
+        epochs
+    }
 }
 
 impl<FR, R, B> Checkpointer<R, B> for FileCheckpointer<FR>
@@ -47,10 +69,32 @@ where
         let file_path = self.path_for_epoch(epoch);
         log::info!("Saving checkpoint {} to {}", epoch, file_path.display());
 
+        // The record is written to a temporary directory whose files are moved once complete, so
+        // an interrupted training never leaves a partial checkpoint. The file reported by the
+        // recorder, such as the index of the shards, is moved last.
+        let partial_dir = self
+            .directory
+            .join(format!("{}-{}-partial", self.name, epoch));
+        std::fs::remove_dir_all(&partial_dir).ok();
+        std::fs::create_dir_all(&partial_dir).map_err(CheckpointerError::IOError)?;
+
+        let partial_path = partial_dir.join(file_path.file_name().unwrap());
         self.recorder
-            .record(record, file_path)
+            .record(record, partial_path.clone())
             .map_err(CheckpointerError::RecorderError)?;
 
+        let partial_file = with_extension::<FR, B>(&partial_path);
+        for entry in std::fs::read_dir(&partial_dir).map_err(CheckpointerError::IOError)? {
+            let path = entry.map_err(CheckpointerError::IOError)?.path();
+            if path != partial_file {
+                std::fs::rename(&path, self.directory.join(path.file_name().unwrap()))
+                    .map_err(CheckpointerError::IOError)?;
+            }
+        }
+        std::fs::rename(partial_file, with_extension::<FR, B>(&file_path))
+            .map_err(CheckpointerError::IOError)?;
+        std::fs::remove_dir(&partial_dir).map_err(CheckpointerError::IOError)?;
+
         Ok(())
     }
 
@@ -70,17 +114,113 @@ where
     }
 
     fn delete(&self, epoch: usize) -> Result<(), CheckpointerError> {
-        let file_to_remove = format!(
-            "{}.{}",
-            self.path_for_epoch(epoch).display(),
-            FR::file_extension(),
-        );
+        let file_to_remove = with_extension::<FR, B>(&self.path_for_epoch(epoch));
 
-        if std::path::Path::new(&file_to_remove).exists() {
-            log::info!("Removing checkpoint {}", file_to_remove);
+        if file_to_remove.exists() {
+            log::info!("Removing checkpoint {}", file_to_remove.display());
             std::fs::remove_file(file_to_remove).map_err(CheckpointerError::IOError)?;
         }
 
+        // The other files of the checkpoint, such as the shards, are named after it.
+        let prefix = format!("{}-{}-", self.name, epoch);
+        let Ok(entries) = std::fs::read_dir(&self.directory) else {
+            return Ok(());
+        };
+        for entry in entries {
+            let path = entry.map_err(CheckpointerError::IOError)?.path();
+            let is_part = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix));
+            if is_part && path.is_file() {
+                std::fs::remove_file(path).map_err(CheckpointerError::IOError)?;
+            }
+        }
+
         Ok(())
     }
 }
+
+/// The path of the file written by the recorder for the path without extension.
+fn with_extension<FR: FileRecorder<B>, B: Backend>(path: &Path) -> PathBuf {
+    PathBuf::from(format!("{}.{}", path.display(), FR::file_extension()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use burn_core::module::Module;
+    use burn_core::nn::{Linear, LinearConfig, LinearRecord};
+    use burn_core::record::{FullPrecisionSettings, ShardedMpkFileRecorder};
+
+    type Recorder = ShardedMpkFileRecorder<FullPrecisionSettings>;
+
+    fn files(directory: &Path) -> Vec<String> {
+        let mut files = std::fs::read_dir(directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn file_checkpointer_should_move_all_the_files_of_a_sharded_record() {
+        let directory = std::env::temp_dir().join("burn-sharded-checkpoints");
+        std::fs::remove_dir_all(&directory).ok();
+        // A shard for each value of the record.
+        let checkpointer =
+            FileCheckpointer::new(Recorder::new().with_max_shard_size(1), &directory, "model");
+        let device = Default::default();
+        let linear: Linear<TestBackend> = LinearConfig::new(2, 2).init(&device);
+
+        Checkpointer::<_, TestBackend>::save(&checkpointer, 1, linear.clone().into_record())
+            .unwrap();
+
+        let saved = files(&directory);
+        assert!(saved.len() > 2, "Expected several shards, got {saved:?}");
+        let (index, shards) = saved.split_last().unwrap();
+        assert_eq!(index, "model-1.mpk.index.json");
+        for (i, shard) in shards.iter().enumerate() {
+            assert_eq!(
+                *shard,
+                format!("model-1-{:05}-of-{:05}.mpk", i + 1, shards.len())
+            );
+        }
+        assert_eq!(checkpointer.epochs(), [1]);
+
+        let restored: LinearRecord<TestBackend> =
+            Checkpointer::<_, TestBackend>::restore(&checkpointer, 1, &device).unwrap();
+        restored
+            .weight
+            .val()
+            .into_data()
+            .assert_eq(&linear.weight.val().into_data(), true);
+
+        Checkpointer::<LinearRecord<TestBackend>, TestBackend>::delete(&checkpointer, 1).unwrap();
+        assert!(files(&directory).is_empty());
+    }
+
+    #[test]
+    fn file_checkpointer_should_replace_an_interrupted_checkpoint() {
+        let directory = std::env::temp_dir().join("burn-interrupted-checkpoints");
+        std::fs::remove_dir_all(&directory).ok();
+        let checkpointer = FileCheckpointer::new(Recorder::new(), &directory, "model");
+        // The files of a checkpoint whose saving was interrupted.
+        let partial_dir = directory.join("model-2-partial");
+        std::fs::create_dir_all(&partial_dir).unwrap();
+        std::fs::write(partial_dir.join("model-2-00001-of-00002.mpk"), b"partial").unwrap();
+        assert!(checkpointer.epochs().is_empty());
+
+        let device = Default::default();
+        let linear: Linear<TestBackend> = LinearConfig::new(2, 2).init(&device);
+        Checkpointer::<_, TestBackend>::save(&checkpointer, 2, linear.into_record()).unwrap();
+
+        assert_eq!(
+            files(&directory),
+            ["model-2-00001-of-00001.mpk", "model-2.mpk.index.json"]
+        );
+        assert_eq!(checkpointer.epochs(), [2]);
+    }
+}
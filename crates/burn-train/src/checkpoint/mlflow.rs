@@ -0,0 +1,63 @@
+use super::{Checkpointer, CheckpointerError, FileCheckpointer};
+use crate::logger::MlflowRun;
+use burn_core::{
+    record::{FileRecorder, Record},
+    tensor::backend::Backend,
+};
+
+/// Checkpointer saving the records to files, like the [file checkpointer](FileCheckpointer),
+/// and uploading them as artifacts of an [MLflow run](MlflowRun).
+///
+/// Checkpoints deleted locally are kept in the run.
+pub struct MlflowCheckpointer<FR> {
+    checkpointer: FileCheckpointer<FR>,
+    run: MlflowRun,
+    artifact_dir: String,
+}
+
+impl<FR> MlflowCheckpointer<FR> {
+    /// Creates a new MLflow checkpointer.
+    ///
+    /// # Arguments
+    ///
+    /// * `checkpointer` - The file checkpointer saving the records locally.
+    /// * `run` - The run receiving the checkpoints.
+    /// * `artifact_dir` - The directory of the checkpoints in the run artifacts.
+    pub fn new(checkpointer: FileCheckpointer<FR>, run: MlflowRun, artifact_dir: &str) -> Self {
+        Self {
+            checkpointer,
+            run,
+            artifact_dir: artifact_dir.to_string(),
+        }
+    }
+}
+
+impl<FR, R, B> Checkpointer<R, B> for MlflowCheckpointer<FR>
+where
+    R: Record<B>,
+    FR: FileRecorder<B>,
+    B: Backend,
+{
+    fn save(&self, epoch: usize, record: R) -> Result<(), CheckpointerError> {
+        Checkpointer::<R, B>::save(&self.checkpointer, epoch, record)?;
+
+        let file_path = format!(
+            "{}.{}",
+            self.checkpointer.path_for_epoch(epoch).display(),
+            FR::file_extension(),
+        );
+        log::info!("Uploading checkpoint {} to MLflow", file_path);
+
+        self.run
+            .log_artifact(&file_path, &self.artifact_dir)
+            .map_err(|err| CheckpointerError::Unknown(err.to_string()))
+    }
+
+    fn restore(&self, epoch: usize, device: &B::Device) -> Result<R, CheckpointerError> {
+        self.checkpointer.restore(epoch, device)
+    }
+
+    fn delete(&self, epoch: usize) -> Result<(), CheckpointerError> {
+        Checkpointer::<R, B>::delete(&self.checkpointer, epoch)
+    }
+}
@@ -1,9 +1,17 @@
 mod async_checkpoint;
+mod average;
 mod base;
 mod file;
+#[cfg(feature = "mlflow")]
+mod mlflow;
+mod state;
 mod strategy;
 
 pub use async_checkpoint::*;
+pub use average::*;
 pub use base::*;
 pub use file::*;
+#[cfg(feature = "mlflow")]
+pub use mlflow::*;
+pub use state::*;
 pub use strategy::*;
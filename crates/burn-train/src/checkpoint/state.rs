@@ -0,0 +1,75 @@
+use burn_core::record::{PrecisionSettings, Record};
+use burn_core::tensor::backend::Backend;
+use serde::{Deserialize, Serialize};
+
+/// The position of the training loop, saved with each checkpoint so that a resumed training
+/// continues where the checkpoint was saved.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TrainingState {
+    /// The next epoch to train.
+    pub epoch: usize,
+    /// The number of iterations of the epoch already trained, when the checkpoint is saved
+    /// during the epoch.
+    pub iteration: usize,
+    /// The number of training steps since the start of the training.
+    pub num_steps: usize,
+    /// The number of validations done every given number of steps.
+    pub num_rounds: usize,
+    /// The factor applied to the learning rate after the detection of non-finite values.
+    pub lr_factor: f64,
+}
+
+impl Default for TrainingState {
+    fn default() -> Self {
+        Self {
+            epoch: 1,
+            iteration: 0,
+            num_steps: 0,
+            num_rounds: 0,
+            lr_factor: 1.0,
+        }
+    }
+}
+
+impl<B: Backend> Record<B> for TrainingState {
+    type Item<S: PrecisionSettings> = Self;
+
+    fn into_item<S: PrecisionSettings>(self) -> Self::Item<S> {
+        self
+    }
+
+    fn from_item<S: PrecisionSettings>(item: Self::Item<S>, _device: &B::Device) -> Self {
+        item
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkpoint::{Checkpointer, FileCheckpointer};
+    use crate::TestBackend;
+    use burn_core::record::{FullPrecisionSettings, NamedMpkFileRecorder};
+
+    #[test]
+    fn training_state_should_be_restored_from_checkpoint() {
+        let directory = std::env::temp_dir().join("burn-training-state");
+        let checkpointer = FileCheckpointer::new(
+            NamedMpkFileRecorder::<FullPrecisionSettings>::new(),
+            directory,
+            "state",
+        );
+        let state = TrainingState {
+            epoch: 3,
+            iteration: 42,
+            num_steps: 242,
+            num_rounds: 4,
+            lr_factor: 0.25,
+        };
+
+        Checkpointer::<_, TestBackend>::save(&checkpointer, 4, state.clone()).unwrap();
+        let restored: TrainingState =
+            Checkpointer::<_, TestBackend>::restore(&checkpointer, 4, &Default::default()).unwrap();
+
+        assert_eq!(restored, state);
+    }
+}
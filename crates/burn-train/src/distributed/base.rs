@@ -0,0 +1,129 @@
+use std::net::SocketAddr;
+
+/// The error type for the [collective operations](Collective).
+#[derive(Debug)]
+pub enum CollectiveError {
+    /// IO error, such as a peer disconnecting.
+    IOError(std::io::Error),
+
+    /// The configuration of the process group is invalid.
+    InvalidConfig(String),
+
+    /// Other errors.
+    Unknown(String),
+}
+
+impl core::fmt::Display for CollectiveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::IOError(err) => write!(f, "IO error: {err}"),
+            Self::InvalidConfig(message) => write!(f, "Invalid configuration: {message}"),
+            Self::Unknown(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for CollectiveError {}
+
+impl From<std::io::Error> for CollectiveError {
+    fn from(err: std::io::Error) -> Self {
+        Self::IOError(err)
+    }
+}
+
+/// Collective communication between the processes of a distributed training.
+///
+/// Every process of the group must call the same operations in the same order, with buffers of
+/// the same length, otherwise the processes block or fail.
+///
+/// The [TCP collective](super::TcpCollective) is the only implementation, working with any
+/// backend through the host memory. No NCCL collective is provided for the CUDA devices, faster
+/// implementations can be provided by implementing this trait.
+pub trait Collective {
+    /// The rank of the current process, from 0 to the [world size](Collective::world_size).
+    fn rank(&self) -> usize;
+
+    /// The number of processes in the group.
+    fn world_size(&self) -> usize;
+
+    /// Replaces the values with their element-wise sum over all the processes.
+    fn all_reduce_sum(&mut self, values: &mut [f32]) -> Result<(), CollectiveError>;
+
+    /// Replaces the values with the values of the `root` process.
+    fn broadcast(&mut self, values: &mut [f32], root: usize) -> Result<(), CollectiveError>;
+
+    /// Blocks until all the processes reach the barrier.
+    fn barrier(&mut self) -> Result<(), CollectiveError>;
+
+    /// If the current process is the main one, responsible for the checkpoints.
+    fn is_main_process(&self) -> bool {
+        self.rank() == 0
+    }
+}
+
+/// The configuration of the current process in a distributed training.
+#[derive(Debug, Clone)]
+pub struct DistributedConfig {
+    /// The rank of the current process.
+    pub rank: usize,
+    /// The number of processes.
+    pub world_size: usize,
+    /// The address of the main process, used to establish the connections.
+    pub master_addr: SocketAddr,
+}
+
+impl DistributedConfig {
+    /// Create the configuration of the current process.
+    pub fn new(rank: usize, world_size: usize, master_addr: SocketAddr) -> Self {
+        Self {
+            rank,
+            world_size,
+            master_addr,
+        }
+    }
+
+    /// Read the configuration from the `RANK`, `WORLD_SIZE`, `MASTER_ADDR` and `MASTER_PORT`
+    /// environment variables, as set by the [launcher](super::LocalLauncher) or by a cluster
+    /// scheduler.
+    pub fn from_env() -> Result<Self, CollectiveError> {
+        let var = |name: &str| {
+            std::env::var(name).map_err(|_| {
+                CollectiveError::InvalidConfig(format!("The variable {name} isn't set"))
+            })
+        };
+        let parse = |name: &str| {
+            var(name)?
+                .parse::<usize>()
+                .map_err(|err| CollectiveError::InvalidConfig(format!("Invalid {name}: {err}")))
+        };
+
+        let rank = parse("RANK")?;
+        let world_size = parse("WORLD_SIZE")?;
+        let master_addr = format!("{}:{}", var("MASTER_ADDR")?, var("MASTER_PORT")?);
+        let master_addr = std::net::ToSocketAddrs::to_socket_addrs(&master_addr)?
+            .next()
+            .ok_or_else(|| {
+                CollectiveError::InvalidConfig(format!("Can't resolve {master_addr}"))
+            })?;
+
+        if rank >= world_size {
+            return Err(CollectiveError::InvalidConfig(format!(
+                "The rank {rank} should be lower than the world size {world_size}"
+            )));
+        }
+
+        Ok(Self::new(rank, world_size, master_addr))
+    }
+
+    /// The range of the items of a dataset of the given length processed by the current process.
+    ///
+    /// The ranges of the processes have the same length, so that every process executes the same
+    /// number of steps. The last items are dropped when the length isn't a multiple of the world
+    /// size.
+    pub fn shard_range(&self, len: usize) -> core::ops::Range<usize> {
+        let shard_len = len / self.world_size;
+        let start = self.rank * shard_len;
+
+        start..start + shard_len
+    }
+}
@@ -0,0 +1,62 @@
+use super::CollectiveError;
+use std::net::SocketAddr;
+use std::process::Command;
+
+/// Launches a distributed training on the current machine, with one process per rank.
+///
+/// Each process runs the current executable with the same arguments, and the environment
+/// variables read by [DistributedConfig::from_env](super::DistributedConfig::from_env). On a
+/// cluster, the processes are usually launched by the scheduler, which sets the same variables.
+pub struct LocalLauncher {
+    world_size: usize,
+    master_addr: SocketAddr,
+}
+
+impl LocalLauncher {
+    /// Create a launcher for the given number of processes.
+    pub fn new(world_size: usize) -> Self {
+        Self {
+            world_size,
+            master_addr: SocketAddr::from(([127, 0, 0, 1], 29500)),
+        }
+    }
+
+    /// Set the address on which the main process listens, `127.0.0.1:29500` by default.
+    pub fn with_master_addr(mut self, master_addr: SocketAddr) -> Self {
+        self.master_addr = master_addr;
+        self
+    }
+
+    /// Spawn the processes and wait for all of them to exit.
+    pub fn launch(&self) -> Result<(), CollectiveError> {
+        let executable = std::env::current_exe()?;
+        let args = std::env::args_os().skip(1).collect::<Vec<_>>();
+
+        let children = (0..self.world_size)
+            .map(|rank| {
+                Command::new(&executable)
+                    .args(&args)
+                    .env("RANK", rank.to_string())
+                    .env("WORLD_SIZE", self.world_size.to_string())
+                    .env("MASTER_ADDR", self.master_addr.ip().to_string())
+                    .env("MASTER_PORT", self.master_addr.port().to_string())
+                    .spawn()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut failed = Vec::new();
+        for (rank, mut child) in children.into_iter().enumerate() {
+            if !child.wait()?.success() {
+                failed.push(rank);
+            }
+        }
+
+        if !failed.is_empty() {
+            return Err(CollectiveError::Unknown(format!(
+                "The processes of ranks {failed:?} failed"
+            )));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,9 @@
+mod base;
+mod launcher;
+mod sync;
+mod tcp;
+
+pub use base::*;
+pub use launcher::*;
+pub(crate) use sync::*;
+pub use tcp::*;
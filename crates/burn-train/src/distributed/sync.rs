@@ -0,0 +1,303 @@
+use super::Collective;
+use crate::learner::TrainingInterrupter;
+use burn_core::module::{AutodiffModule, Module, ModuleMapper, ModuleVisitor, ParamId};
+use burn_core::optim::GradientsParams;
+use burn_core::tensor::backend::{AutodiffBackend, Backend};
+use burn_core::tensor::{Tensor, TensorData};
+use core::marker::PhantomData;
+use std::collections::HashSet;
+
+/// Averages the gradients of the module over all the processes.
+///
+/// Only the parameters with gradients in every process are reduced, such as the parameters which
+/// aren't frozen. The processes first agree on the set of these parameters, so that all of them
+/// reduce the same tensors in the same order. The gradients of the parameters missing in some
+/// processes are dropped, keeping the models of the processes identical.
+pub(crate) fn all_reduce_grads<B, M>(
+    collective: &mut dyn Collective,
+    module: &M,
+    grads: GradientsParams,
+) -> GradientsParams
+where
+    B: AutodiffBackend,
+    M: AutodiffModule<B>,
+{
+    let mut presence = GradientsPresence::<B, M> {
+        grads: &grads,
+        ids: Vec::new(),
+        counts: Vec::new(),
+        _p: PhantomData,
+    };
+    module.visit(&mut presence);
+
+    // The parameters are visited in the same order by all the processes.
+    let GradientsPresence {
+        ids, mut counts, ..
+    } = presence;
+    collective
+        .all_reduce_sum(&mut counts)
+        .expect("Can all-reduce the parameters with gradients.");
+
+    let world_size = collective.world_size() as f32;
+    let shared = ids
+        .into_iter()
+        .zip(counts)
+        .filter_map(|(id, count)| {
+            if count > 0.0 && count < world_size {
+                log::warn!("The gradients of the parameter {id} are missing in some processes.");
+            }
+            (count == world_size).then_some(id)
+        })
+        .collect();
+
+    let mut visitor = GradientsAllReduce::<B, M> {
+        collective,
+        grads,
+        shared,
+        _p: PhantomData,
+    };
+    module.visit(&mut visitor);
+
+    visitor.grads
+}
+
+/// Replaces the parameters of the module with the ones of the main process, so that all the
+/// processes start the training with the same model.
+pub(crate) fn broadcast_module<B, M>(collective: &mut dyn Collective, module: M) -> M
+where
+    B: Backend,
+    M: Module<B>,
+{
+    let mut mapper = ModuleBroadcast { collective };
+    module.map(&mut mapper)
+}
+
+/// Returns the flag of the main process.
+pub(crate) fn broadcast_flag(collective: &mut dyn Collective, flag: bool) -> bool {
+    let mut values = [flag as u8 as f32];
+    collective
+        .broadcast(&mut values, 0)
+        .expect("Can broadcast the flag.");
+
+    values[0] != 0.0
+}
+
+/// Returns if the flag is set in any of the processes.
+pub(crate) fn all_reduce_flag(collective: &mut dyn Collective, flag: bool) -> bool {
+    let mut values = [flag as u8 as f32];
+    collective
+        .all_reduce_sum(&mut values)
+        .expect("Can all-reduce the flag.");
+
+    values[0] != 0.0
+}
+
+/// Returns if the training should stop, stopping every process when the training of any of them
+/// is interrupted, e.g. by a callback or an early stopping strategy.
+///
+/// All the processes must check it at the same steps, so that they leave the training together
+/// instead of waiting for the gradients of a stopped process.
+pub(crate) fn synchronize_stop(
+    collective: &mut Option<Box<dyn Collective>>,
+    interrupter: &TrainingInterrupter,
+) -> bool {
+    let Some(collective) = collective else {
+        return interrupter.should_stop();
+    };
+
+    let should_stop = all_reduce_flag(collective.as_mut(), interrupter.should_stop());
+    if should_stop {
+        interrupter.stop();
+    }
+
+    should_stop
+}
+
+/// Lists the parameters of the module, with 1 if they have gradients in the current process.
+struct GradientsPresence<'a, B, M> {
+    grads: &'a GradientsParams,
+    ids: Vec<ParamId>,
+    counts: Vec<f32>,
+    _p: PhantomData<(B, M)>,
+}
+
+impl<B, M> ModuleVisitor<B> for GradientsPresence<'_, B, M>
+where
+    B: AutodiffBackend,
+    M: AutodiffModule<B>,
+{
+    fn visit_float<const D: usize>(&mut self, id: ParamId, _tensor: &Tensor<B, D>) {
+        let present = self.grads.get::<B::InnerBackend, D>(id).is_some();
+
+        self.ids.push(id);
+        self.counts.push(present as u8 as f32);
+    }
+}
+
+struct GradientsAllReduce<'a, B, M> {
+    collective: &'a mut dyn Collective,
+    grads: GradientsParams,
+    /// The parameters with gradients in every process.
+    shared: HashSet<ParamId>,
+    _p: PhantomData<(B, M)>,
+}
+
+impl<B, M> ModuleVisitor<B> for GradientsAllReduce<'_, B, M>
+where
+    B: AutodiffBackend,
+    M: AutodiffModule<B>,
+{
+    fn visit_float<const D: usize>(&mut self, id: ParamId, tensor: &Tensor<B, D>) {
+        let Some(grad) = self.grads.remove::<B::InnerBackend, D>(id) else {
+            return;
+        };
+        if !self.shared.contains(&id) {
+            return;
+        }
+
+        let data = grad.into_data();
+        let shape = data.shape.clone();
+        let mut values = data
+            .convert::<f32>()
+            .to_vec::<f32>()
+            .expect("Can read the gradients as floats.");
+
+        self.collective
+            .all_reduce_sum(&mut values)
+            .expect("Can all-reduce the gradients.");
+
+        let world_size = self.collective.world_size() as f32;
+        values.iter_mut().for_each(|value| *value /= world_size);
+
+        let grad = Tensor::from_data(TensorData::new(values, shape), &tensor.device());
+        self.grads.register::<B::InnerBackend, D>(id, grad);
+    }
+}
+
+struct ModuleBroadcast<'a> {
+    collective: &'a mut dyn Collective,
+}
+
+impl<B: Backend> ModuleMapper<B> for ModuleBroadcast<'_> {
+    fn map_float<const D: usize>(&mut self, _id: ParamId, tensor: Tensor<B, D>) -> Tensor<B, D> {
+        let device = tensor.device();
+        let require_grad = tensor.is_require_grad();
+
+        let data = tensor.into_data();
+        let shape = data.shape.clone();
+        let mut values = data
+            .convert::<f32>()
+            .to_vec::<f32>()
+            .expect("Can read the parameters as floats.");
+
+        self.collective
+            .broadcast(&mut values, 0)
+            .expect("Can broadcast the parameters.");
+
+        Tensor::from_data(TensorData::new(values, shape), &device).set_require_grad(require_grad)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributed::{DistributedConfig, TcpCollective};
+    use crate::TestBackend;
+    use burn_autodiff::Autodiff;
+    use burn_core::nn::{Initializer, Linear, LinearConfig};
+    use std::net::{SocketAddr, TcpListener};
+
+    type B = Autodiff<TestBackend>;
+
+    /// Runs the function in a process group of the given size, with one thread per rank.
+    fn run_group<T, F>(world_size: usize, func: F) -> Vec<T>
+    where
+        T: Send + 'static,
+        F: Fn(usize, Option<Box<dyn Collective>>) -> T + Send + Sync + Copy + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let master_addr: SocketAddr = listener.local_addr().unwrap();
+        drop(listener);
+
+        let handles = (0..world_size)
+            .map(|rank| {
+                std::thread::spawn(move || {
+                    let config = DistributedConfig::new(rank, world_size, master_addr);
+                    let collective = TcpCollective::new(&config).unwrap();
+                    func(rank, Some(Box::new(collective)))
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_all_reduce_grads_skips_the_frozen_and_missing_parameters() {
+        let results = run_group(2, |rank, mut collective| {
+            let device = Default::default();
+            let layer = LinearConfig::new(2, 1).with_initializer(Initializer::Ones);
+            let model: Vec<Linear<B>> = vec![
+                layer.init(&device),
+                layer.init::<B>(&device).no_grad(),
+                layer.init(&device),
+            ];
+
+            // The gradients of the weights of the first layer are the input.
+            let input = Tensor::<B, 2>::from_floats([[rank as f32, 1.0]], &device);
+            let mut output = model[0].forward(input.clone()) + model[1].forward(input.clone());
+            // The last layer is only used by the main process.
+            if rank == 0 {
+                output = output + model[2].forward(input);
+            }
+            let grads = GradientsParams::from_grads(output.sum().backward(), &model);
+
+            let grads = all_reduce_grads(collective.as_deref_mut().unwrap(), &model, grads);
+            let grad = |index: usize| {
+                grads
+                    .get::<TestBackend, 2>(model[index].weight.id)
+                    .map(|grad| grad.into_data().to_vec::<f32>().unwrap())
+            };
+
+            (grad(0), grad(1), grad(2))
+        });
+
+        for (averaged, frozen, missing) in results {
+            assert_eq!(averaged, Some(vec![0.5, 1.0]));
+            assert_eq!(frozen, None);
+            assert_eq!(missing, None);
+        }
+    }
+
+    #[test]
+    fn test_synchronize_stop_stops_all_the_processes() {
+        let results = run_group(2, |rank, mut collective| {
+            let interrupter = TrainingInterrupter::new();
+
+            for step in 1..10 {
+                // Every step reduces values, as the gradients of a training step.
+                let mut values = [1.0];
+                collective
+                    .as_deref_mut()
+                    .unwrap()
+                    .all_reduce_sum(&mut values)
+                    .unwrap();
+
+                // The main process stops early, as with an early stopping strategy.
+                if rank == 0 && step == 3 {
+                    interrupter.stop();
+                }
+                if synchronize_stop(&mut collective, &interrupter) {
+                    return (step, interrupter.should_stop());
+                }
+            }
+
+            (10, interrupter.should_stop())
+        });
+
+        assert_eq!(results, vec![(3, true), (3, true)]);
+    }
+}
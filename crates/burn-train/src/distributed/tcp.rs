@@ -0,0 +1,236 @@
+use super::{Collective, CollectiveError, DistributedConfig};
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::{Duration, Instant};
+
+/// The time allowed to the processes to join the group.
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// [Collective](Collective) operations over TCP, working with any backend and any number of
+/// machines.
+///
+/// The main process is connected to every other process and reduces the values before sending
+/// them back. The values are transferred through the host memory, which makes it a portable
+/// fallback rather than the fastest option.
+pub struct TcpCollective {
+    rank: usize,
+    world_size: usize,
+    /// The connections to the other processes, by rank, on the main process only.
+    peers: Vec<Option<TcpStream>>,
+    /// The connection to the main process, on the other processes.
+    main: Option<TcpStream>,
+}
+
+impl TcpCollective {
+    /// Join the process group, blocking until all the processes are connected.
+    pub fn new(config: &DistributedConfig) -> Result<Self, CollectiveError> {
+        if config.rank >= config.world_size {
+            return Err(CollectiveError::InvalidConfig(format!(
+                "The rank {} should be lower than the world size {}",
+                config.rank, config.world_size
+            )));
+        }
+
+        let mut collective = Self {
+            rank: config.rank,
+            world_size: config.world_size,
+            peers: (0..config.world_size).map(|_| None).collect(),
+            main: None,
+        };
+
+        if config.rank == 0 {
+            let listener = TcpListener::bind(config.master_addr)?;
+
+            for _ in 1..config.world_size {
+                let (mut stream, _) = listener.accept()?;
+                stream.set_nodelay(true)?;
+                let rank = read_u64(&mut stream)? as usize;
+
+                if rank == 0 || rank >= config.world_size || collective.peers[rank].is_some() {
+                    return Err(CollectiveError::InvalidConfig(format!(
+                        "Invalid or duplicated rank {rank}"
+                    )));
+                }
+                collective.peers[rank] = Some(stream);
+            }
+        } else {
+            let mut stream = connect(config)?;
+            stream.set_nodelay(true)?;
+            write_u64(&mut stream, config.rank as u64)?;
+            collective.main = Some(stream);
+        }
+
+        log::info!(
+            "Joined the process group with rank {} of {}",
+            config.rank,
+            config.world_size
+        );
+
+        Ok(collective)
+    }
+
+    fn peers(&mut self) -> impl Iterator<Item = (usize, &mut TcpStream)> {
+        self.peers
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(rank, peer)| peer.as_mut().map(|peer| (rank, peer)))
+    }
+
+    fn main(&mut self) -> &mut TcpStream {
+        self.main
+            .as_mut()
+            .expect("The processes other than the main one are connected to it.")
+    }
+}
+
+impl Collective for TcpCollective {
+    fn rank(&self) -> usize {
+        self.rank
+    }
+
+    fn world_size(&self) -> usize {
+        self.world_size
+    }
+
+    fn all_reduce_sum(&mut self, values: &mut [f32]) -> Result<(), CollectiveError> {
+        if self.rank != 0 {
+            let main = self.main();
+            write_values(main, values)?;
+            return read_values(main, values);
+        }
+
+        let mut buffer = vec![0.0; values.len()];
+        for (_, peer) in self.peers() {
+            read_values(peer, &mut buffer)?;
+            for (value, other) in values.iter_mut().zip(buffer.iter()) {
+                *value += other;
+            }
+        }
+        for (_, peer) in self.peers() {
+            write_values(peer, values)?;
+        }
+
+        Ok(())
+    }
+
+    fn broadcast(&mut self, values: &mut [f32], root: usize) -> Result<(), CollectiveError> {
+        if root >= self.world_size {
+            return Err(CollectiveError::InvalidConfig(format!(
+                "Invalid root rank {root}"
+            )));
+        }
+
+        if self.rank != 0 {
+            let is_root = self.rank == root;
+            let main = self.main();
+
+            return if is_root {
+                write_values(main, values)
+            } else {
+                read_values(main, values)
+            };
+        }
+
+        if root != 0 {
+            let peer = self.peers[root]
+                .as_mut()
+                .expect("The main process is connected to every other process.");
+            read_values(peer, values)?;
+        }
+        for (rank, peer) in self.peers() {
+            if rank != root {
+                write_values(peer, values)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn barrier(&mut self) -> Result<(), CollectiveError> {
+        self.all_reduce_sum(&mut [0.0])
+    }
+}
+
+fn connect(config: &DistributedConfig) -> Result<TcpStream, CollectiveError> {
+    let start = Instant::now();
+
+    loop {
+        match TcpStream::connect(config.master_addr) {
+            Ok(stream) => return Ok(stream),
+            // The main process may not be listening yet.
+            Err(err) if start.elapsed() < CONNECTION_TIMEOUT => {
+                log::debug!("Waiting for the main process: {err}");
+                std::thread::sleep(Duration::from_millis(100));
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
+fn write_u64(stream: &mut TcpStream, value: u64) -> Result<(), CollectiveError> {
+    stream.write_all(&value.to_le_bytes())?;
+    Ok(())
+}
+
+fn read_u64(stream: &mut TcpStream) -> Result<u64, CollectiveError> {
+    let mut bytes = [0; 8];
+    stream.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn write_values(stream: &mut TcpStream, values: &[f32]) -> Result<(), CollectiveError> {
+    let bytes = values
+        .iter()
+        .flat_map(|value| value.to_le_bytes())
+        .collect::<Vec<_>>();
+    stream.write_all(&bytes)?;
+    Ok(())
+}
+
+fn read_values(stream: &mut TcpStream, values: &mut [f32]) -> Result<(), CollectiveError> {
+    let mut bytes = vec![0; values.len() * 4];
+    stream.read_exact(&mut bytes)?;
+
+    for (value, bytes) in values.iter_mut().zip(bytes.chunks_exact(4)) {
+        *value = f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::SocketAddr;
+
+    #[test]
+    fn tcp_collective_should_reduce_and_broadcast() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let master_addr: SocketAddr = listener.local_addr().unwrap();
+        drop(listener);
+        let world_size = 3;
+
+        let handles = (0..world_size)
+            .map(|rank| {
+                std::thread::spawn(move || {
+                    let config = DistributedConfig::new(rank, world_size, master_addr);
+                    let mut collective = TcpCollective::new(&config).unwrap();
+
+                    let mut values = [rank as f32, 1.0];
+                    collective.all_reduce_sum(&mut values).unwrap();
+
+                    let mut broadcast = [rank as f32 * 10.0];
+                    collective.broadcast(&mut broadcast, 2).unwrap();
+                    collective.barrier().unwrap();
+
+                    (values, broadcast)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for handle in handles {
+            let (values, broadcast) = handle.join().unwrap();
+            assert_eq!(values, [3.0, 3.0]);
+            assert_eq!(broadcast, [20.0]);
+        }
+    }
+}
@@ -1,6 +1,14 @@
-use crate::checkpoint::{Checkpointer, CheckpointingAction, CheckpointingStrategy};
+use crate::checkpoint::{
+    AsyncCheckpointer, Checkpointer, CheckpointerError, CheckpointingAction, CheckpointingStrategy,
+    TrainingState,
+};
 use crate::components::LearnerComponents;
-use crate::learner::EarlyStoppingStrategy;
+use crate::distributed::Collective;
+use crate::learner::{
+    EarlyStoppingStrategy, LearnerCallbacks, LrStepping, NonFiniteGuard, NormMonitoring,
+    StepProfiler,
+};
+use crate::metric::processor::EventProcessor;
 use crate::metric::store::EventStoreClient;
 use crate::LearnerSummaryConfig;
 use burn_core::lr_scheduler::LrScheduler;
@@ -22,12 +30,25 @@ pub struct Learner<LC: LearnerComponents> {
     pub(crate) num_epochs: usize,
     pub(crate) checkpoint: Option<usize>,
     pub(crate) grad_accumulation: Option<usize>,
+    pub(crate) lr_stepping: LrStepping,
+    pub(crate) validation_interval: Option<usize>,
+    pub(crate) seed: Option<u64>,
+    pub(crate) restore_data_position: bool,
+    pub(crate) profiler: Option<StepProfiler>,
+    pub(crate) norms: Option<NormMonitoring>,
+    pub(crate) valid_metrics: Vec<String>,
+    /// The number of evaluations already logged, each as its own validation epoch.
+    pub(crate) num_evaluations: usize,
     pub(crate) checkpointer: Option<LearnerCheckpointer<LC>>,
     pub(crate) devices: Vec<<LC::Backend as Backend>::Device>,
     pub(crate) interrupter: TrainingInterrupter,
     pub(crate) early_stopping: Option<Box<dyn EarlyStoppingStrategy>>,
     pub(crate) event_processor: LC::EventProcessor,
     pub(crate) event_store: Rc<EventStoreClient>,
+    pub(crate) callbacks: LearnerCallbacks<LC::Model>,
+    pub(crate) collective: Option<Box<dyn Collective>>,
+    pub(crate) non_finite:
+        Option<NonFiniteGuard<<LC::EventProcessor as EventProcessor>::ItemTrain>>,
     pub(crate) summary: Option<LearnerSummaryConfig>,
 }
 
@@ -36,19 +57,23 @@ pub(crate) struct LearnerCheckpointer<LC: LearnerComponents> {
     model: LC::CheckpointerModel,
     optim: LC::CheckpointerOptimizer,
     lr_scheduler: LC::CheckpointerLrScheduler,
+    state: AsyncCheckpointer<TrainingState, LC::Backend>,
     strategy: LC::CheckpointerStrategy,
 }
 
 impl<LC: LearnerComponents> LearnerCheckpointer<LC> {
+    /// Applies the checkpointing strategy, returning if a checkpoint is saved for the epoch.
     pub(crate) fn checkpoint(
         &mut self,
         model: &LC::Model,
         optim: &LC::Optimizer,
         scheduler: &LC::LrScheduler,
+        state: &TrainingState,
         epoch: usize,
         store: &EventStoreClient,
-    ) {
+    ) -> bool {
         let actions = self.strategy.checkpointing(epoch, store);
+        let mut saved = false;
 
         for action in actions {
             match action {
@@ -62,6 +87,9 @@ impl<LC: LearnerComponents> LearnerCheckpointer<LC> {
                     self.lr_scheduler
                         .delete(epoch)
                         .expect("Can delete learning rate scheduler checkpoint.");
+                    self.state
+                        .delete(epoch)
+                        .expect("Can delete training state checkpoint.");
                 }
                 CheckpointingAction::Save => {
                     self.model
@@ -73,9 +101,15 @@ impl<LC: LearnerComponents> LearnerCheckpointer<LC> {
                     self.lr_scheduler
                         .save(epoch, scheduler.to_record())
                         .expect("Can save learning rate scheduler checkpoint.");
+                    self.state
+                        .save(epoch, state.clone())
+                        .expect("Can save training state checkpoint.");
+                    saved = true;
                 }
             }
         }
+
+        saved
     }
 
     pub(crate) fn load_checkpoint(
@@ -85,7 +119,12 @@ impl<LC: LearnerComponents> LearnerCheckpointer<LC> {
         scheduler: LC::LrScheduler,
         device: &Device<LC::Backend>,
         epoch: usize,
-    ) -> (LC::Model, LC::Optimizer, LC::LrScheduler) {
+    ) -> (
+        LC::Model,
+        LC::Optimizer,
+        LC::LrScheduler,
+        Option<TrainingState>,
+    ) {
         let record = self
             .model
             .restore(epoch, device)
@@ -104,7 +143,28 @@ impl<LC: LearnerComponents> LearnerCheckpointer<LC> {
             .expect("Can load learning rate scheduler checkpoint.");
         let scheduler = scheduler.load_record(record);
 
-        (model, optim, scheduler)
+        // The checkpoints saved before the training state was recorded don't include it.
+        let state = match self.state.restore(epoch, device) {
+            Ok(state) => Some(state),
+            Err(err) => {
+                log::warn!("Can't load the training state of checkpoint {epoch}: {err:?}");
+                None
+            }
+        };
+
+        (model, optim, scheduler, state)
+    }
+
+    /// Restores the model saved at the given epoch.
+    pub(crate) fn restore_model(
+        &self,
+        model: LC::Model,
+        device: &Device<LC::Backend>,
+        epoch: usize,
+    ) -> Result<LC::Model, CheckpointerError> {
+        let record = self.model.restore(epoch, device)?;
+
+        Ok(model.load_record(record))
     }
 }
 
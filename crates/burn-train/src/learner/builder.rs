@@ -5,16 +5,24 @@ use std::rc::Rc;
 use super::Learner;
 use crate::checkpoint::{
     AsyncCheckpointer, CheckpointingStrategy, ComposedCheckpointingStrategy, FileCheckpointer,
-    KeepLastNCheckpoints, MetricCheckpointingStrategy,
+    KeepLastNCheckpoints, MetricCheckpointingStrategy, TrainingState,
 };
 use crate::components::LearnerComponentsMarker;
+use crate::distributed::Collective;
 use crate::learner::base::TrainingInterrupter;
-use crate::learner::EarlyStoppingStrategy;
+use crate::learner::{
+    EarlyStoppingStrategy, LearnerCallbacks, LrStepping, NonFiniteDetection, NonFiniteGuard,
+    NormMonitoring, StepPhase, StepProfiler, TrainCallback,
+};
 use crate::logger::{FileMetricLogger, MetricLogger};
 use crate::metric::processor::{FullEventProcessor, Metrics};
 use crate::metric::store::{Aggregate, Direction, EventStoreClient, LogEventStore, Split};
-use crate::metric::{Adaptor, LossMetric, Metric};
-use crate::renderer::{default_renderer, MetricsRenderer};
+use crate::metric::{
+    Adaptor, GradientNormMetric, LossInput, LossMetric, Metric, ParameterNormMetric, StepTimeMetric,
+};
+use crate::renderer::{default_renderer, MetricsRenderer, TuiConfig};
+#[cfg(feature = "mlflow")]
+use crate::{checkpoint::MlflowCheckpointer, logger::MlflowRun};
 use crate::{
     ApplicationLoggerInstaller, FileApplicationLoggerInstaller, LearnerCheckpointer,
     LearnerSummaryConfig,
@@ -43,13 +51,21 @@ where
         AsyncCheckpointer<M::Record, B>,
         AsyncCheckpointer<O::Record, B>,
         AsyncCheckpointer<S::Record<B>, B>,
+        AsyncCheckpointer<TrainingState, B>,
     )>,
     num_epochs: usize,
     checkpoint: Option<usize>,
     directory: PathBuf,
     grad_accumulation: Option<usize>,
+    lr_stepping: LrStepping,
+    validation_interval: Option<usize>,
+    seed: Option<u64>,
+    restore_data_position: bool,
+    profile: bool,
+    norms: Option<NormMonitoring>,
     devices: Vec<B::Device>,
     renderer: Option<Box<dyn MetricsRenderer + 'static>>,
+    tui: TuiConfig,
     metrics: Metrics<T, V>,
     event_store: LogEventStore,
     interrupter: TrainingInterrupter,
@@ -57,7 +73,11 @@ where
     num_loggers: usize,
     checkpointer_strategy: Box<dyn CheckpointingStrategy>,
     early_stopping: Option<Box<dyn EarlyStoppingStrategy>>,
+    callbacks: Vec<Box<dyn TrainCallback<M>>>,
+    collective: Option<Box<dyn Collective>>,
+    non_finite: Option<NonFiniteGuard<T>>,
     summary_metrics: HashSet<String>,
+    valid_metrics: Vec<String>,
     summary: bool,
 }
 
@@ -84,10 +104,17 @@ where
             checkpointers: None,
             directory,
             grad_accumulation: None,
+            lr_stepping: LrStepping::Batch,
+            validation_interval: None,
+            seed: None,
+            restore_data_position: false,
+            profile: false,
+            norms: None,
             devices: vec![B::Device::default()],
             metrics: Metrics::default(),
             event_store: LogEventStore::default(),
             renderer: None,
+            tui: TuiConfig::default(),
             interrupter: TrainingInterrupter::new(),
             tracing_logger: Some(Box::new(FileApplicationLoggerInstaller::new(
                 experiment_log_file,
@@ -104,7 +131,11 @@ where
                     .build(),
             ),
             early_stopping: None,
+            callbacks: Vec::new(),
+            collective: None,
+            non_finite: None,
             summary_metrics: HashSet::new(),
+            valid_metrics: Vec::new(),
             summary: false,
         }
     }
@@ -148,6 +179,15 @@ where
         self
     }
 
+    /// Configure the terminal UI, with the refresh rate, the plotted metrics and custom panels.
+    ///
+    /// The configuration is ignored when a [custom renderer](Self::renderer) is set, without the
+    /// `tui` feature or when the standard output isn't a terminal.
+    pub fn tui(mut self, config: TuiConfig) -> Self {
+        self.tui = config;
+        self
+    }
+
     /// Register a training metric.
     pub fn metric_train<Me: Metric + 'static>(mut self, metric: Me) -> Self
     where
@@ -181,6 +221,123 @@ where
         self
     }
 
+    /// Step the learning rate scheduler for each batch, for each optimizer step or for each
+    /// epoch, the scheduler being stepped for each batch by default.
+    pub fn lr_stepping(mut self, lr_stepping: LrStepping) -> Self {
+        self.lr_stepping = lr_stepping;
+        self
+    }
+
+    /// The number of learning rate scheduler steps over the whole training, given the number of
+    /// batches of the training dataloader per epoch.
+    ///
+    /// The number accounts for the [number of epochs](Self::num_epochs), the
+    /// [devices](Self::devices), the [gradient accumulation](Self::grads_accumulation) and the
+    /// [stepping](Self::lr_stepping), so those should be set first. It is used to configure
+    /// schedules over the total training steps, such as a cosine decay after a warmup:
+    ///
+    /// ```rust,ignore
+    /// let builder = LearnerBuilder::new(directory)
+    ///     .num_epochs(10)
+    ///     .grads_accumulation(4)
+    ///     .lr_stepping(LrStepping::Optimizer);
+    /// let total_steps = builder.num_lr_steps(num_batches);
+    /// let scheduler = WarmupLrSchedulerConfig::new(warmup_steps)
+    ///     .init(CosineAnnealingLrSchedulerConfig::new(1e-3, total_steps - warmup_steps).init());
+    /// ```
+    pub fn num_lr_steps(&self, num_batches: usize) -> usize {
+        self.lr_stepping.num_steps(
+            num_batches,
+            self.num_epochs,
+            self.devices.len(),
+            self.grad_accumulation,
+        )
+    }
+
+    /// Run the validation, the checkpointing and the early stopping every given number of
+    /// training steps, instead of at the end of each epoch.
+    ///
+    /// # Notes
+    ///
+    /// The validation metrics, the checkpoints and the early stopping strategy are then indexed by
+    /// validation round rather than by epoch, so the training is [resumed](Self::checkpoint) from
+    /// a validation round. With distributed training, only the main process validates the model,
+    /// the other processes following its early stopping decision.
+    pub fn validate_every(mut self, num_steps: usize) -> Self {
+        assert!(
+            num_steps > 0,
+            "The validation interval should be at least one step"
+        );
+        self.validation_interval = Some(num_steps);
+        self
+    }
+
+    /// Seed the backend at the start of each epoch with the given seed and the epoch number, so
+    /// that a training resumed at the start of an epoch draws the same random numbers, for
+    /// instance for the dropout, as an uninterrupted one.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Restore the order of the training items when [resuming](Self::checkpoint) the training.
+    ///
+    /// # Notes
+    ///
+    /// The training dataloader is iterated once for each epoch already trained, so that a
+    /// dataloader shuffling with a fixed seed yields the same items as in an uninterrupted
+    /// training. Creating the iterators may start loading the batches with a multi-threaded
+    /// dataloader, which is why it isn't done by default.
+    pub fn restore_dataloader_position(mut self) -> Self {
+        self.restore_data_position = true;
+        self
+    }
+
+    /// Measure the time spent loading the data, in the [train step](crate::TrainStep::step) and
+    /// in the optimizer for each training step, reported by [step time metrics](StepTimeMetric).
+    ///
+    /// # Notes
+    ///
+    /// The devices are synchronized after each phase, which slows down the training. The
+    /// transfer of the batch and the forward and backward passes can be measured in the train
+    /// step with [profile_phase](crate::profile_phase), registering the
+    /// [step time metrics](StepTimeMetric) of these phases.
+    pub fn profile(mut self) -> Self
+    where
+        T: Adaptor<()>,
+    {
+        self.profile = true;
+        for phase in [
+            StepPhase::DataLoading,
+            StepPhase::Step,
+            StepPhase::Optimizer,
+        ] {
+            self.metrics
+                .register_train_metric_numeric(StepTimeMetric::new(phase));
+        }
+        self
+    }
+
+    /// Compute the norms of the gradients and of the parameters during training, reported by the
+    /// [gradient norm](GradientNormMetric) and the [parameter norm](ParameterNormMetric) metrics.
+    ///
+    /// # Notes
+    ///
+    /// The norms are computed before the optimizer step, from the gradients of the batch, or of
+    /// the batches of all the devices with multi-device training, before the gradient
+    /// accumulation.
+    pub fn monitor_norms(mut self, monitoring: NormMonitoring) -> Self
+    where
+        T: Adaptor<()>,
+    {
+        self.norms = Some(monitoring);
+        self.metrics
+            .register_train_metric_numeric(GradientNormMetric::new());
+        self.metrics
+            .register_train_metric_numeric(ParameterNormMetric::new());
+        self
+    }
+
     /// Register a [numeric](crate::metric::Numeric) training [metric](Metric).
     pub fn metric_train_numeric<Me>(mut self, metric: Me) -> Self
     where
@@ -201,6 +358,7 @@ where
         V: Adaptor<Me::Input>,
     {
         self.summary_metrics.insert(Me::NAME.to_string());
+        self.valid_metrics.push(Me::NAME.to_string());
         self.metrics.register_valid_metric_numeric(metric);
         self
     }
@@ -212,12 +370,52 @@ where
     }
 
     /// Run the training loop on multiple devices.
+    ///
+    /// With more than one device, the training is data parallel: the model is replicated on each
+    /// device and each replica trains on its own batch, then the gradients are averaged on the
+    /// first device before each optimizer step. The effective batch size is the batch size of
+    /// the dataloader multiplied by the number of devices.
     pub fn devices(mut self, devices: Vec<B::Device>) -> Self {
         self.devices = devices;
         self
     }
 
+    /// Train the model with multiple processes, possibly on different machines, using the given
+    /// [collective](Collective) to communicate.
+    ///
+    /// The main process broadcasts the initial model, then the gradients are averaged over the
+    /// processes before each optimizer step, and only the main process saves the checkpoints.
+    ///
+    /// # Notes
+    ///
+    /// Each process should train on a different shard of the dataset with the same number of
+    /// batches, for instance using [DistributedConfig::shard_range](crate::distributed::DistributedConfig::shard_range),
+    /// since the processes wait for each other at every step.
+    pub fn distributed<C>(mut self, collective: C) -> Self
+    where
+        C: Collective + 'static,
+    {
+        self.collective = Some(Box::new(collective));
+        self
+    }
+
+    /// Check the loss, and optionally the gradients, after each training step, applying the
+    /// [policy](crate::NonFinitePolicy) of the detection when a NaN or an infinity is found.
+    ///
+    /// The diagnostic reports are written in the artifact directory.
+    pub fn detect_non_finite(mut self, detection: NonFiniteDetection<T>) -> Self
+    where
+        T: Adaptor<LossInput<B>>,
+    {
+        self.non_finite = Some(NonFiniteGuard::new(detection, self.directory.clone()));
+        self
+    }
+
     /// The epoch from which the training must resume.
+    ///
+    /// The model, the optimizer, the learning rate scheduler and the position of the training
+    /// loop are restored from the checkpoint. A checkpoint saved during an epoch, with
+    /// [step validation](Self::validate_every), resumes after the last trained step of the epoch.
     pub fn checkpoint(mut self, checkpoint: usize) -> Self {
         self.checkpoint = Some(checkpoint);
         self
@@ -238,6 +436,17 @@ where
         self
     }
 
+    /// Register a [callback](TrainCallback) invoked at different points of the training loop.
+    ///
+    /// The callbacks are invoked in the order of registration.
+    pub fn callback<C>(mut self, callback: C) -> Self
+    where
+        C: TrainCallback<M> + 'static,
+    {
+        self.callbacks.push(Box::new(callback));
+        self
+    }
+
     /// By default, Rust logs are captured and written into
     /// `experiment.log`. If disabled, standard Rust log handling
     /// will apply.
@@ -264,12 +473,50 @@ where
         let checkpointer_optimizer =
             FileCheckpointer::new(recorder.clone(), &checkpoint_dir, "optim");
         let checkpointer_scheduler: FileCheckpointer<FR> =
-            FileCheckpointer::new(recorder, &checkpoint_dir, "scheduler");
+            FileCheckpointer::new(recorder.clone(), &checkpoint_dir, "scheduler");
+        let checkpointer_state: FileCheckpointer<FR> =
+            FileCheckpointer::new(recorder, &checkpoint_dir, "state");
 
         self.checkpointers = Some((
             AsyncCheckpointer::new(checkpointer_model),
             AsyncCheckpointer::new(checkpointer_optimizer),
             AsyncCheckpointer::new(checkpointer_scheduler),
+            AsyncCheckpointer::new(checkpointer_state),
+        ));
+
+        self
+    }
+
+    /// Register a checkpointer that will save the [optimizer](Optimizer), the
+    /// [model](AutodiffModule) and the [scheduler](LrScheduler) to different files, and upload
+    /// them as artifacts of the given [MLflow run](MlflowRun).
+    #[cfg(feature = "mlflow")]
+    pub fn with_mlflow_checkpointer<FR>(mut self, recorder: FR, run: &MlflowRun) -> Self
+    where
+        FR: FileRecorder<B> + 'static,
+        FR: FileRecorder<B::InnerBackend> + 'static,
+        O::Record: 'static,
+        M::Record: 'static,
+        S::Record<B>: 'static,
+    {
+        let checkpoint_dir = self.directory.join("checkpoint");
+        let checkpointer = |recorder: FR, name: &str| {
+            MlflowCheckpointer::new(
+                FileCheckpointer::new(recorder, &checkpoint_dir, name),
+                run.clone(),
+                "checkpoint",
+            )
+        };
+        let checkpointer_model = checkpointer(recorder.clone(), "model");
+        let checkpointer_optimizer = checkpointer(recorder.clone(), "optim");
+        let checkpointer_scheduler = checkpointer(recorder.clone(), "scheduler");
+        let checkpointer_state = checkpointer(recorder, "state");
+
+        self.checkpointers = Some((
+            AsyncCheckpointer::new(checkpointer_model),
+            AsyncCheckpointer::new(checkpointer_optimizer),
+            AsyncCheckpointer::new(checkpointer_scheduler),
+            AsyncCheckpointer::new(checkpointer_state),
         ));
 
         self
@@ -316,9 +563,9 @@ where
                 log::warn!("Failed to install the experiment logger: {}", e);
             }
         }
-        let renderer = self
-            .renderer
-            .unwrap_or_else(|| default_renderer(self.interrupter.clone(), self.checkpoint));
+        let renderer = self.renderer.unwrap_or_else(|| {
+            default_renderer(self.interrupter.clone(), self.checkpoint, self.tui)
+        });
 
         if self.num_loggers == 0 {
             self.event_store
@@ -330,8 +577,16 @@ where
         let event_store = Rc::new(EventStoreClient::new(self.event_store));
         let event_processor = FullEventProcessor::new(self.metrics, renderer, event_store.clone());
 
-        let checkpointer = self.checkpointers.map(|(model, optim, scheduler)| {
-            LearnerCheckpointer::new(model, optim, scheduler, self.checkpointer_strategy)
+        let callbacks = LearnerCallbacks::new(self.callbacks, event_store.clone());
+
+        let profiler = if self.profile {
+            Some(StepProfiler::new::<B>(self.devices.clone()))
+        } else {
+            None
+        };
+
+        let checkpointer = self.checkpointers.map(|(model, optim, scheduler, state)| {
+            LearnerCheckpointer::new(model, optim, scheduler, state, self.checkpointer_strategy)
         });
 
         let summary = if self.summary {
@@ -353,9 +608,20 @@ where
             event_store,
             checkpoint: self.checkpoint,
             grad_accumulation: self.grad_accumulation,
+            lr_stepping: self.lr_stepping,
+            validation_interval: self.validation_interval,
+            seed: self.seed,
+            restore_data_position: self.restore_data_position,
+            profiler,
+            norms: self.norms,
+            valid_metrics: self.valid_metrics,
+            num_evaluations: 0,
             devices: self.devices,
             interrupter: self.interrupter,
             early_stopping: self.early_stopping,
+            callbacks,
+            collective: self.collective,
+            non_finite: self.non_finite,
             summary,
         }
     }
@@ -0,0 +1,215 @@
+use crate::metric::store::EventStoreClient;
+use burn_core::LearningRate;
+use std::rc::Rc;
+
+/// A callback invoked by the [learner](crate::Learner) at different points of the training loop.
+///
+/// All the hooks do nothing by default, so only the relevant ones need to be implemented. This
+/// can be used to add custom behaviors to the training loop, such as periodically generating
+/// samples with the model or adjusting the weights of the losses during training.
+///
+/// Callbacks are registered with the [learner builder](crate::LearnerBuilder::callback) and are
+/// invoked in the order of registration.
+pub trait TrainCallback<M> {
+    /// Called once before the first epoch.
+    fn on_train_start(&mut self, _context: &mut CallbackContext<M>) {}
+
+    /// Called before the training of each epoch.
+    fn on_epoch_start(&mut self, _context: &mut CallbackContext<M>) {}
+
+    /// Called after each training step, once the model is optimized.
+    fn on_batch_end(&mut self, _context: &mut CallbackContext<M>) {}
+
+    /// Called at the end of each epoch, after the validation and the checkpointing.
+    fn on_epoch_end(&mut self, _context: &mut CallbackContext<M>) {}
+
+    /// Called when a checkpoint is saved for the current epoch.
+    fn on_checkpoint(&mut self, _context: &mut CallbackContext<M>) {}
+
+    /// Called once after the last epoch, or when the training is stopped.
+    fn on_train_end(&mut self, _context: &mut CallbackContext<M>) {}
+}
+
+/// The state of the training loop given to the [callbacks](TrainCallback).
+pub struct CallbackContext<'a, M> {
+    /// The model being trained.
+    pub model: &'a mut M,
+    /// The current epoch.
+    pub epoch: usize,
+    /// The total number of epochs.
+    pub epoch_total: usize,
+    /// The current iteration in the epoch, or 0 outside of the training steps.
+    pub iteration: usize,
+    /// The learning rate of the current training step.
+    pub lr: Option<LearningRate>,
+    /// The metrics collected during training, aggregated per epoch.
+    pub store: &'a EventStoreClient,
+    control: TrainingControl,
+}
+
+/// The actions requested by the [callbacks](TrainCallback) to the training loop.
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub(crate) struct TrainingControl {
+    pub(crate) stop: bool,
+    pub(crate) skip_epoch: bool,
+}
+
+impl<M> CallbackContext<'_, M> {
+    /// Stops the training after the current hook. The remaining epochs aren't executed.
+    pub fn stop(&mut self) {
+        self.control.stop = true;
+    }
+
+    /// Skips the rest of the current epoch.
+    ///
+    /// When requested at the start of an epoch, the epoch is neither trained nor validated.
+    /// During the training steps, the remaining batches are skipped and the epoch continues with
+    /// the validation.
+    pub fn skip_epoch(&mut self) {
+        self.control.skip_epoch = true;
+    }
+}
+
+/// The [callbacks](TrainCallback) registered on the [learner](crate::Learner).
+pub struct LearnerCallbacks<M> {
+    callbacks: Vec<Box<dyn TrainCallback<M>>>,
+    store: Rc<EventStoreClient>,
+    pub(crate) epoch: usize,
+    pub(crate) epoch_total: usize,
+}
+
+impl<M> LearnerCallbacks<M> {
+    pub(crate) fn new(
+        callbacks: Vec<Box<dyn TrainCallback<M>>>,
+        store: Rc<EventStoreClient>,
+    ) -> Self {
+        Self {
+            callbacks,
+            store,
+            epoch: 0,
+            epoch_total: 0,
+        }
+    }
+
+    pub(crate) fn on_train_start(&mut self, model: &mut M) -> TrainingControl {
+        self.invoke(model, 0, None, |callback, context| {
+            callback.on_train_start(context)
+        })
+    }
+
+    pub(crate) fn on_epoch_start(&mut self, model: &mut M) -> TrainingControl {
+        self.invoke(model, 0, None, |callback, context| {
+            callback.on_epoch_start(context)
+        })
+    }
+
+    pub(crate) fn on_batch_end(
+        &mut self,
+        model: &mut M,
+        iteration: usize,
+        lr: LearningRate,
+    ) -> TrainingControl {
+        self.invoke(model, iteration, Some(lr), |callback, context| {
+            callback.on_batch_end(context)
+        })
+    }
+
+    pub(crate) fn on_epoch_end(&mut self, model: &mut M) -> TrainingControl {
+        self.invoke(model, 0, None, |callback, context| {
+            callback.on_epoch_end(context)
+        })
+    }
+
+    pub(crate) fn on_checkpoint(&mut self, model: &mut M) -> TrainingControl {
+        self.invoke(model, 0, None, |callback, context| {
+            callback.on_checkpoint(context)
+        })
+    }
+
+    pub(crate) fn on_train_end(&mut self, model: &mut M) {
+        self.invoke(model, 0, None, |callback, context| {
+            callback.on_train_end(context)
+        });
+    }
+
+    fn invoke<F>(
+        &mut self,
+        model: &mut M,
+        iteration: usize,
+        lr: Option<LearningRate>,
+        hook: F,
+    ) -> TrainingControl
+    where
+        F: Fn(&mut dyn TrainCallback<M>, &mut CallbackContext<M>),
+    {
+        let mut context = CallbackContext {
+            model,
+            epoch: self.epoch,
+            epoch_total: self.epoch_total,
+            iteration,
+            lr,
+            store: &self.store,
+            control: TrainingControl::default(),
+        };
+
+        for callback in self.callbacks.iter_mut() {
+            hook(callback.as_mut(), &mut context);
+        }
+
+        context.control
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metric::store::LogEventStore;
+
+    struct StopAfter {
+        num_batches: usize,
+        calls: Rc<std::cell::RefCell<Vec<String>>>,
+    }
+
+    impl TrainCallback<usize> for StopAfter {
+        fn on_epoch_start(&mut self, context: &mut CallbackContext<usize>) {
+            self.calls
+                .borrow_mut()
+                .push(format!("epoch start {}", context.epoch));
+        }
+
+        fn on_batch_end(&mut self, context: &mut CallbackContext<usize>) {
+            *context.model += 1;
+            if context.iteration >= self.num_batches {
+                context.stop();
+            }
+        }
+    }
+
+    #[test]
+    fn callbacks_should_share_the_context_and_return_the_control() {
+        let calls = Rc::new(std::cell::RefCell::new(Vec::new()));
+        let first = StopAfter {
+            num_batches: 2,
+            calls: calls.clone(),
+        };
+        let second = StopAfter {
+            num_batches: 5,
+            calls: calls.clone(),
+        };
+        let store = Rc::new(EventStoreClient::new(LogEventStore::default()));
+        let callbacks: Vec<Box<dyn TrainCallback<usize>>> = vec![Box::new(first), Box::new(second)];
+        let mut callbacks = LearnerCallbacks::new(callbacks, store);
+        callbacks.epoch = 3;
+        let mut model = 0;
+
+        assert_eq!(
+            callbacks.on_epoch_start(&mut model),
+            TrainingControl::default()
+        );
+        assert_eq!(*calls.borrow(), vec!["epoch start 3", "epoch start 3"]);
+
+        assert!(!callbacks.on_batch_end(&mut model, 1, 0.1).stop);
+        assert!(callbacks.on_batch_end(&mut model, 2, 0.1).stop);
+        assert_eq!(model, 4);
+    }
+}
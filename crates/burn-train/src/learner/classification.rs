@@ -1,4 +1,6 @@
-use crate::metric::{AccuracyInput, Adaptor, HammingScoreInput, LossInput};
+use crate::metric::{
+    AccuracyInput, Adaptor, ClassificationInput, HammingScoreInput, LossInput, PerplexityInput,
+};
 use burn_core::tensor::backend::Backend;
 use burn_core::tensor::{Int, Tensor};
 
@@ -21,6 +23,18 @@ impl<B: Backend> Adaptor<AccuracyInput<B>> for ClassificationOutput<B> {
     }
 }
 
+impl<B: Backend> Adaptor<ClassificationInput<B>> for ClassificationOutput<B> {
+    fn adapt(&self) -> ClassificationInput<B> {
+        ClassificationInput::new(self.output.clone(), self.targets.clone())
+    }
+}
+
+impl<B: Backend> Adaptor<PerplexityInput<B>> for ClassificationOutput<B> {
+    fn adapt(&self) -> PerplexityInput<B> {
+        PerplexityInput::new(self.output.clone(), self.targets.clone())
+    }
+}
+
 impl<B: Backend> Adaptor<LossInput<B>> for ClassificationOutput<B> {
     fn adapt(&self) -> LossInput<B> {
         LossInput::new(self.loss.clone())
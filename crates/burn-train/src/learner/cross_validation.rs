@@ -0,0 +1,270 @@
+use burn_core::data::dataset::transform::{ComposedDataset, PartialDataset, ShuffledDataset};
+use burn_core::data::dataset::Dataset;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Display;
+use std::sync::{Arc, Mutex};
+
+/// K-fold cross-validation, splitting a dataset into `K` folds and training once per fold, with
+/// the fold as the validation set and the other folds as the training set.
+///
+/// The training of each fold is provided as a closure, which typically builds the dataloaders,
+/// a fresh model and a [learner](crate::Learner) with its own artifact directory, then returns
+/// the metrics to aggregate, such as the last validation metrics of the
+/// [learner summary](crate::LearnerSummary).
+#[derive(Clone, Debug)]
+pub struct CrossValidator {
+    num_folds: usize,
+    seed: Option<u64>,
+}
+
+/// A fold of the [cross-validation](CrossValidator).
+pub struct Fold<I> {
+    /// The index of the fold, from 0 to the number of folds.
+    pub index: usize,
+    /// The training items, from all the other folds.
+    pub train: Arc<dyn Dataset<I>>,
+    /// The validation items.
+    pub valid: Arc<dyn Dataset<I>>,
+}
+
+/// The metrics of each fold of the [cross-validation](CrossValidator).
+pub type FoldMetrics = HashMap<String, f64>;
+
+/// The metrics of all the folds of a [cross-validation](CrossValidator).
+#[derive(Clone, Debug)]
+pub struct CrossValidationResult {
+    /// The metrics of each fold, in the order of the folds.
+    pub folds: Vec<FoldMetrics>,
+}
+
+impl CrossValidator {
+    /// Create a cross-validation with the given number of folds.
+    pub fn new(num_folds: usize) -> Self {
+        assert!(
+            num_folds >= 2,
+            "The cross-validation needs at least 2 folds"
+        );
+
+        Self {
+            num_folds,
+            seed: None,
+        }
+    }
+
+    /// Shuffle the items with the given seed before splitting the folds, which is required when
+    /// the dataset is sorted, for instance by class.
+    pub fn with_shuffle(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Split the dataset into the folds.
+    ///
+    /// The folds have the same number of items, except the last one which also contains the
+    /// remaining items.
+    pub fn folds<D, I>(&self, dataset: D) -> Vec<Fold<I>>
+    where
+        D: Dataset<I> + 'static,
+        I: Clone + Send + Sync + 'static,
+    {
+        assert!(
+            dataset.len() >= self.num_folds,
+            "The dataset should contain at least one item per fold"
+        );
+
+        let dataset: Arc<dyn Dataset<I>> = match self.seed {
+            Some(seed) => Arc::new(ShuffledDataset::with_seed(dataset, seed)),
+            None => Arc::new(dataset),
+        };
+        let partitions = PartialDataset::<_, I>::split(dataset, self.num_folds)
+            .into_iter()
+            .map(Arc::new)
+            .collect::<Vec<_>>();
+
+        (0..self.num_folds)
+            .map(|index| {
+                let train = partitions
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| *i != index)
+                    .map(|(_, partition)| partition.clone())
+                    .collect();
+
+                Fold {
+                    index,
+                    train: Arc::new(ComposedDataset::new(train)),
+                    valid: Arc::new(partitions[index].clone()),
+                }
+            })
+            .collect()
+    }
+
+    /// Run the training of each fold sequentially.
+    pub fn run<D, I, F>(&self, dataset: D, mut train: F) -> CrossValidationResult
+    where
+        D: Dataset<I> + 'static,
+        I: Clone + Send + Sync + 'static,
+        F: FnMut(Fold<I>) -> FoldMetrics,
+    {
+        let folds = self
+            .folds(dataset)
+            .into_iter()
+            .map(|fold| {
+                log::info!("Training fold {}/{}", fold.index + 1, self.num_folds);
+                train(fold)
+            })
+            .collect();
+
+        CrossValidationResult { folds }
+    }
+
+    /// Run the training of the folds in parallel, one thread per device, each thread training
+    /// the next remaining fold on its device.
+    pub fn run_parallel<D, I, Dev, F>(
+        &self,
+        dataset: D,
+        devices: Vec<Dev>,
+        train: F,
+    ) -> CrossValidationResult
+    where
+        D: Dataset<I> + 'static,
+        I: Clone + Send + Sync + 'static,
+        Dev: Send,
+        F: Fn(Fold<I>, &Dev) -> FoldMetrics + Sync,
+    {
+        assert!(!devices.is_empty(), "At least one device is required");
+
+        let folds = Mutex::new(self.folds(dataset).into_iter());
+        let results = Mutex::new(Vec::with_capacity(self.num_folds));
+
+        std::thread::scope(|scope| {
+            for device in devices {
+                let (folds, results, train) = (&folds, &results, &train);
+
+                scope.spawn(move || loop {
+                    let Some(fold) = folds.lock().unwrap().next() else {
+                        break;
+                    };
+                    let index = fold.index;
+
+                    log::info!("Training fold {}/{}", index + 1, self.num_folds);
+                    let metrics = train(fold, &device);
+                    results.lock().unwrap().push((index, metrics));
+                });
+            }
+        });
+
+        let mut results = results.into_inner().unwrap();
+        results.sort_by_key(|(index, _)| *index);
+
+        CrossValidationResult {
+            folds: results.into_iter().map(|(_, metrics)| metrics).collect(),
+        }
+    }
+}
+
+impl CrossValidationResult {
+    /// The mean of the metric over the folds reporting it.
+    pub fn mean(&self, metric: &str) -> Option<f64> {
+        let values = self.values(metric);
+        if values.is_empty() {
+            return None;
+        }
+
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+
+    /// The standard deviation of the metric over the folds reporting it.
+    pub fn std(&self, metric: &str) -> Option<f64> {
+        let values = self.values(metric);
+        let mean = self.mean(metric)?;
+        let variance = values
+            .iter()
+            .map(|value| (value - mean).powi(2))
+            .sum::<f64>()
+            / values.len() as f64;
+
+        Some(variance.sqrt())
+    }
+
+    fn values(&self, metric: &str) -> Vec<f64> {
+        self.folds
+            .iter()
+            .filter_map(|metrics| metrics.get(metric).copied())
+            .collect()
+    }
+}
+
+impl Display for CrossValidationResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Sorted by name for a stable output.
+        let names = self
+            .folds
+            .iter()
+            .flat_map(|metrics| metrics.keys())
+            .collect::<BTreeSet<_>>();
+
+        writeln!(f, "Cross-validation ({} folds)", self.folds.len())?;
+        for name in names {
+            let (mean, std) = (self.mean(name).unwrap(), self.std(name).unwrap());
+            writeln!(f, "{name}: {mean:.4} ± {std:.4}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burn_core::data::dataset::InMemDataset;
+
+    fn dataset() -> InMemDataset<usize> {
+        InMemDataset::new((0..10).collect())
+    }
+
+    fn valid_items(fold: &Fold<usize>) -> Vec<usize> {
+        fold.valid.iter().collect()
+    }
+
+    #[test]
+    fn folds_should_partition_the_dataset() {
+        let folds = CrossValidator::new(3).folds(dataset());
+
+        assert_eq!(valid_items(&folds[0]), vec![0, 1, 2]);
+        assert_eq!(valid_items(&folds[2]), vec![6, 7, 8, 9]);
+        assert_eq!(
+            folds[1].train.iter().collect::<Vec<_>>(),
+            vec![0, 1, 2, 6, 7, 8, 9]
+        );
+    }
+
+    #[test]
+    fn shuffled_folds_should_contain_all_the_items() {
+        let folds = CrossValidator::new(3).with_shuffle(42).folds(dataset());
+
+        let mut items = folds.iter().flat_map(valid_items).collect::<Vec<_>>();
+        items.sort();
+
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn parallel_run_should_aggregate_the_folds_in_order() {
+        let validator = CrossValidator::new(4);
+
+        let result = validator.run_parallel(dataset(), vec![0, 1], |fold, _device| {
+            let mut metrics = FoldMetrics::new();
+            metrics.insert("Items".to_string(), fold.valid.len() as f64);
+            metrics.insert("Index".to_string(), fold.index as f64);
+            metrics
+        });
+
+        let indices = result.values("Index");
+        assert_eq!(indices, vec![0.0, 1.0, 2.0, 3.0]);
+        // Folds of 2, 2, 2 and 4 items.
+        assert_eq!(result.mean("Items"), Some(2.5));
+        assert!((result.std("Items").unwrap() - 0.75f64.sqrt()).abs() < 1e-9);
+        assert_eq!(result.mean("Accuracy"), None);
+    }
+}
@@ -0,0 +1,101 @@
+use burn_core::data::dataloader::DataLoader;
+use std::sync::Arc;
+
+/// Provides the training dataloader of each epoch to
+/// [fit with a curriculum](crate::Learner::fit_curriculum), such as a dataloader with longer
+/// sequences, harder samples or stronger augmentations as the training progresses.
+///
+/// Any function taking the epoch, starting at 1, and returning the dataloader implements the
+/// trait.
+pub trait Curriculum<I> {
+    /// The training dataloader of the epoch.
+    ///
+    /// The function is called once at the start of each epoch, including the epochs preceding a
+    /// resumed checkpoint, in order.
+    fn dataloader(&mut self, epoch: usize) -> Arc<dyn DataLoader<I>>;
+}
+
+impl<I, F> Curriculum<I> for F
+where
+    F: FnMut(usize) -> Arc<dyn DataLoader<I>>,
+{
+    fn dataloader(&mut self, epoch: usize) -> Arc<dyn DataLoader<I>> {
+        self(epoch)
+    }
+}
+
+/// A [curriculum](Curriculum) switching to the dataloader of the next stage at given epochs.
+pub struct CurriculumStages<I> {
+    /// Sorted by starting epoch.
+    stages: Vec<(usize, Arc<dyn DataLoader<I>>)>,
+}
+
+impl<I> CurriculumStages<I> {
+    /// Create the curriculum with the dataloader of the first stage, starting at the first epoch.
+    pub fn new(dataloader: Arc<dyn DataLoader<I>>) -> Self {
+        Self {
+            stages: vec![(1, dataloader)],
+        }
+    }
+
+    /// Adds a stage using the dataloader from the given epoch.
+    pub fn with_stage(mut self, epoch: usize, dataloader: Arc<dyn DataLoader<I>>) -> Self {
+        let last = self.stages.last().map(|(start, _)| *start).unwrap_or(0);
+        assert!(
+            epoch > last,
+            "The stages should start at increasing epochs, but epoch {epoch} follows epoch {last}"
+        );
+
+        self.stages.push((epoch, dataloader));
+        self
+    }
+}
+
+impl<I> Curriculum<I> for CurriculumStages<I> {
+    fn dataloader(&mut self, epoch: usize) -> Arc<dyn DataLoader<I>> {
+        let (_, dataloader) = self
+            .stages
+            .iter()
+            .rev()
+            .find(|(start, _)| *start <= epoch)
+            .unwrap_or(&self.stages[0]);
+
+        dataloader.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burn_core::data::dataloader::batcher::Batcher;
+    use burn_core::data::dataloader::DataLoaderBuilder;
+    use burn_core::data::dataset::InMemDataset;
+
+    #[derive(Clone)]
+    struct VecBatcher;
+
+    impl Batcher<usize, Vec<usize>> for VecBatcher {
+        fn batch(&self, items: Vec<usize>) -> Vec<usize> {
+            items
+        }
+    }
+
+    fn dataloader(num_items: usize) -> Arc<dyn DataLoader<Vec<usize>>> {
+        DataLoaderBuilder::new(VecBatcher)
+            .batch_size(2)
+            .build(InMemDataset::new((0..num_items).collect()))
+    }
+
+    #[test]
+    fn stages_should_switch_dataloader_at_their_epoch() {
+        let mut curriculum = CurriculumStages::new(dataloader(4))
+            .with_stage(3, dataloader(8))
+            .with_stage(5, dataloader(16));
+
+        let num_items = (1..=6)
+            .map(|epoch| curriculum.dataloader(epoch).num_items())
+            .collect::<Vec<_>>();
+
+        assert_eq!(num_items, [4, 4, 8, 8, 16, 16]);
+    }
+}
@@ -16,6 +16,11 @@ pub enum StoppingCondition {
 pub trait EarlyStoppingStrategy {
     /// Update its current state and returns if the training should be stopped.
     fn should_stop(&mut self, epoch: usize, store: &EventStoreClient) -> bool;
+
+    /// The epoch whose checkpoint should be restored at the end of the training, if any.
+    fn restore_epoch(&self) -> Option<usize> {
+        None
+    }
 }
 
 /// An [early stopping strategy](EarlyStoppingStrategy) based on a metrics collected
@@ -26,12 +31,19 @@ pub struct MetricEarlyStoppingStrategy {
     aggregate: Aggregate,
     direction: Direction,
     split: Split,
+    min_delta: f64,
+    warmup_epochs: usize,
+    restore_best: bool,
     best_epoch: usize,
     best_value: f64,
 }
 
 impl EarlyStoppingStrategy for MetricEarlyStoppingStrategy {
     fn should_stop(&mut self, epoch: usize, store: &EventStoreClient) -> bool {
+        if epoch <= self.warmup_epochs {
+            return false;
+        }
+
         let current_value =
             match store.find_metric(&self.metric_name, epoch, self.aggregate, self.split) {
                 Some(value) => value,
@@ -42,8 +54,8 @@ impl EarlyStoppingStrategy for MetricEarlyStoppingStrategy {
             };
 
         let is_best = match self.direction {
-            Direction::Lowest => current_value < self.best_value,
-            Direction::Highest => current_value > self.best_value,
+            Direction::Lowest => current_value < self.best_value - self.min_delta,
+            Direction::Highest => current_value > self.best_value + self.min_delta,
         };
 
         if is_best {
@@ -79,6 +91,12 @@ impl EarlyStoppingStrategy for MetricEarlyStoppingStrategy {
             }
         }
     }
+
+    fn restore_epoch(&self) -> Option<usize> {
+        let found_best = self.best_value != Self::initial_value(&self.direction);
+
+        (self.restore_best && found_best).then_some(self.best_epoch)
+    }
 }
 
 impl MetricEarlyStoppingStrategy {
@@ -94,19 +112,51 @@ impl MetricEarlyStoppingStrategy {
         split: Split,
         condition: StoppingCondition,
     ) -> Self {
-        let init_value = match direction {
-            Direction::Lowest => f64::MAX,
-            Direction::Highest => f64::MIN,
-        };
-
         Self {
             metric_name: Me::NAME.to_string(),
             condition,
             aggregate,
+            best_value: Self::initial_value(&direction),
             direction,
             split,
+            min_delta: 0.0,
+            warmup_epochs: 0,
+            restore_best: false,
             best_epoch: 1,
-            best_value: init_value,
+        }
+    }
+
+    /// Sets the minimum change of the metric in the right [direction](Direction) to be
+    /// considered as an improvement, which avoids stopping late on noisy metrics.
+    pub fn with_min_delta(mut self, min_delta: f64) -> Self {
+        self.min_delta = min_delta;
+        self
+    }
+
+    /// Sets the number of epochs at the start of the training during which the metric isn't
+    /// monitored.
+    pub fn with_warmup_epochs(mut self, warmup_epochs: usize) -> Self {
+        self.warmup_epochs = warmup_epochs;
+        self
+    }
+
+    /// Restores the checkpoint of the best epoch at the end of the training.
+    ///
+    /// # Notes
+    ///
+    /// The [checkpointing strategy](crate::checkpoint::CheckpointingStrategy) should keep the
+    /// checkpoint of the best epoch, for instance by using a
+    /// [metric checkpointing strategy](crate::checkpoint::MetricCheckpointingStrategy) with the
+    /// same metric.
+    pub fn with_restore_best(mut self, restore_best: bool) -> Self {
+        self.restore_best = restore_best;
+        self
+    }
+
+    fn initial_value(direction: &Direction) -> f64 {
+        match direction {
+            Direction::Lowest => f64::MAX,
+            Direction::Highest => f64::MIN,
         }
     }
 }
@@ -184,13 +234,59 @@ mod tests {
         );
     }
 
-    fn test_early_stopping(n_epochs: usize, data: &[(&[f64], bool, &str)]) {
-        let mut early_stopping = MetricEarlyStoppingStrategy::new::<LossMetric<TestBackend>>(
+    #[test]
+    fn early_stop_when_improvement_is_below_min_delta() {
+        test_early_stopping_with(
+            strategy(1).with_min_delta(0.1),
+            &[
+                (&[0.5], false, "Should not stop first epoch"),
+                (
+                    &[0.45],
+                    true,
+                    "Should stop when the improvement is too small",
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn never_early_stop_during_warmup() {
+        let mut early_stopping = test_early_stopping_with(
+            strategy(1).with_warmup_epochs(2).with_restore_best(true),
+            &[
+                (&[0.1], false, "Should not stop during warmup"),
+                (&[0.5], false, "Should not stop during warmup"),
+                (&[0.6], false, "Should not stop first epoch after warmup"),
+                (&[0.7], true, "Should stop when no improvement after warmup"),
+            ],
+        );
+
+        assert_eq!(
+            early_stopping.restore_epoch(),
+            Some(3),
+            "The best epoch should ignore the warmup"
+        );
+        early_stopping.restore_best = false;
+        assert_eq!(early_stopping.restore_epoch(), None);
+    }
+
+    fn strategy(n_epochs: usize) -> MetricEarlyStoppingStrategy {
+        MetricEarlyStoppingStrategy::new::<LossMetric<TestBackend>>(
             Aggregate::Mean,
             Direction::Lowest,
             Split::Train,
             StoppingCondition::NoImprovementSince { n_epochs },
-        );
+        )
+    }
+
+    fn test_early_stopping(n_epochs: usize, data: &[(&[f64], bool, &str)]) {
+        test_early_stopping_with(strategy(n_epochs), data);
+    }
+
+    fn test_early_stopping_with(
+        mut early_stopping: MetricEarlyStoppingStrategy,
+        data: &[(&[f64], bool, &str)],
+    ) -> MetricEarlyStoppingStrategy {
         let mut store = LogEventStore::default();
         let mut metrics = Metrics::<f64, f64>::default();
 
@@ -214,5 +310,7 @@ mod tests {
             );
             epoch += 1;
         }
+
+        early_stopping
     }
 }
@@ -1,13 +1,39 @@
 use burn_core::{
-    data::dataloader::DataLoader, lr_scheduler::LrScheduler, module::AutodiffModule,
-    optim::GradientsAccumulator, tensor::backend::Backend,
+    data::dataloader::DataLoader,
+    lr_scheduler::LrScheduler,
+    module::AutodiffModule,
+    optim::{GradientsAccumulator, GradientsParams},
+    tensor::backend::{AutodiffBackend, Backend},
+    LearningRate,
 };
 use std::sync::Arc;
 
+use crate::distributed::{all_reduce_grads, synchronize_stop, Collective};
+use crate::learner::{
+    LrStepping, NonFiniteAction, NonFiniteGuard, NormMonitoring, StepPhase, StepProfiler,
+};
 use crate::metric::processor::{Event, EventProcessor, LearnerItem};
+use crate::LearnerCallbacks;
 use crate::{components::LearnerComponents, learner::base::TrainingInterrupter};
 use crate::{MultiDevicesTrainStep, TrainStep, ValidStep};
 
+/// A hook invoked by the training epochs after each step, with the optimized model, returning if
+/// a checkpoint is saved.
+pub type StepHook<'a, LC> = dyn FnMut(StepState<'_, LC>) -> bool + 'a;
+
+/// The state of the training after a step, given to the [step hook](StepHook).
+pub struct StepState<'a, LC: LearnerComponents> {
+    /// The number of iterations of the epoch already trained.
+    pub(crate) iteration: usize,
+    /// The factor applied to the learning rate after the detection of non-finite values.
+    pub(crate) lr_factor: f64,
+    pub(crate) model: &'a LC::Model,
+    pub(crate) optim: &'a LC::Optimizer,
+    pub(crate) scheduler: &'a LC::LrScheduler,
+    pub(crate) processor: &'a mut LC::EventProcessor,
+    pub(crate) collective: &'a mut Option<Box<dyn Collective>>,
+}
+
 /// A validation epoch.
 #[derive(new)]
 pub struct ValidEpoch<VI> {
@@ -23,6 +49,14 @@ pub struct TrainEpoch<TI> {
     epoch: usize,
     epoch_total: usize,
     grad_accumulation: Option<usize>,
+    #[new(default)]
+    skip: usize,
+    #[new(default)]
+    profiler: Option<StepProfiler>,
+    #[new(default)]
+    norms: Option<NormMonitoring>,
+    #[new(default)]
+    lr_stepping: LrStepping,
 }
 
 impl<VI> ValidEpoch<VI> {
@@ -73,6 +107,54 @@ impl<VI> ValidEpoch<VI> {
 }
 
 impl<TI> TrainEpoch<TI> {
+    /// Skip the given number of iterations, already trained before resuming the training.
+    pub(crate) fn skip(mut self, num_iterations: usize) -> Self {
+        self.skip = num_iterations;
+        self
+    }
+
+    /// Measure the phases of each training step with the given profiler.
+    pub(crate) fn profile(mut self, profiler: Option<StepProfiler>) -> Self {
+        self.profiler = profiler;
+        self
+    }
+
+    /// Compute the norms of the gradients and of the parameters with the given monitoring.
+    pub(crate) fn monitor_norms(mut self, norms: Option<NormMonitoring>) -> Self {
+        self.norms = norms;
+        self
+    }
+
+    /// Step the learning rate scheduler at the given frequency.
+    pub(crate) fn lr_stepping(mut self, lr_stepping: LrStepping) -> Self {
+        self.lr_stepping = lr_stepping;
+        self
+    }
+
+    /// Steps the scheduler when needed, returning the learning rate of the batch.
+    fn lr<S: LrScheduler>(
+        &self,
+        scheduler: &mut S,
+        current: &mut Option<LearningRate>,
+        accumulation_start: bool,
+    ) -> LearningRate {
+        let lr = match *current {
+            Some(lr) if !self.lr_stepping.should_step(accumulation_start) => lr,
+            _ => scheduler.step(),
+        };
+        *current = Some(lr);
+
+        lr
+    }
+
+    /// Measures a phase of the current step, when profiling.
+    fn measure<T>(&self, phase: StepPhase, func: impl FnOnce() -> T) -> T {
+        match &self.profiler {
+            Some(profiler) => profiler.measure(phase, func),
+            None => func(),
+        }
+    }
+
     /// Runs the training epoch.
     ///
     /// # Arguments
@@ -81,16 +163,26 @@ impl<TI> TrainEpoch<TI> {
     /// * `optim` - The optimizer to use.
     /// * `scheduler` - The learning rate scheduler to use.
     /// * `processor` - The event processor to use.
+    /// * `callbacks` - The callbacks invoked after each step.
+    /// * `collective` - The collective used to average the gradients over the processes, for
+    ///   distributed training.
+    /// * `non_finite` - The detection of the non-finite losses and gradients.
+    /// * `step_hook` - The hook invoked after each step, to validate the model during the epoch.
     ///
     /// # Returns
     ///
     /// The trained model and the optimizer.
+    #[allow(clippy::too_many_arguments)]
     pub fn run<LC: LearnerComponents, TO>(
         &self,
         mut model: LC::Model,
         mut optim: LC::Optimizer,
         scheduler: &mut LC::LrScheduler,
         processor: &mut LC::EventProcessor,
+        callbacks: &mut LearnerCallbacks<LC::Model>,
+        collective: &mut Option<Box<dyn Collective>>,
+        non_finite: &mut Option<NonFiniteGuard<TO>>,
+        mut step_hook: Option<&mut StepHook<'_, LC>>,
         interrupter: &TrainingInterrupter,
     ) -> (LC::Model, LC::Optimizer)
     where
@@ -100,33 +192,81 @@ impl<TI> TrainEpoch<TI> {
         log::info!("Executing training step for epoch {}", self.epoch,);
 
         let mut iterator = self.dataloader.iter();
-        let mut iteration = 0;
+        let mut iteration = skip_iterations(&mut iterator, self.skip);
         let mut accumulator = GradientsAccumulator::new();
         let mut accumulation_current = 0;
+        let mut current_lr = None;
 
-        while let Some(item) = iterator.next() {
+        if let Some(profiler) = &self.profiler {
+            profiler.start();
+        }
+
+        while let Some(item) = self.measure(StepPhase::DataLoading, || iterator.next()) {
             iteration += 1;
-            let lr = scheduler.step();
+            let lr = self.lr(scheduler, &mut current_lr, accumulation_current == 0);
+            let lr = non_finite.as_ref().map_or(lr, |guard| guard.lr(lr));
             log::info!("Iteration {}", iteration);
 
             let progress = iterator.progress();
-            let item = model.step(item);
+            let item = self.measure(StepPhase::Step, || model.step(item));
+
+            let action = inspect(
+                non_finite,
+                collective,
+                &model,
+                std::slice::from_ref(&item.item),
+                &item.grads,
+                (self.epoch, iteration, lr),
+            );
+            if action == Some(NonFiniteAction::Abort) {
+                interrupter.stop();
+                break;
+            }
+            let skip = action == Some(NonFiniteAction::Skip);
+            let norms = self
+                .norms
+                .as_ref()
+                .filter(|_| !skip)
+                .and_then(|norms| norms.compute(iteration, &model, &item.grads));
 
             match self.grad_accumulation {
                 Some(accumulation) => {
-                    accumulator.accumulate(&model, item.grads);
+                    // A skipped batch still counts, so that all the processes optimize the
+                    // model at the same steps.
+                    if !skip {
+                        accumulator.accumulate(&model, item.grads);
+                    }
                     accumulation_current += 1;
 
                     if accumulation <= accumulation_current {
-                        let grads = accumulator.grads();
-                        model = model.optimize(&mut optim, lr, grads);
+                        let grads = synchronize(collective, &model, accumulator.grads());
+                        model = self.measure(StepPhase::Optimizer, || {
+                            model.optimize(&mut optim, lr, grads)
+                        });
                         accumulation_current = 0;
                     }
                 }
-                None => model = model.optimize(&mut optim, lr, item.grads),
+                None => {
+                    if !skip {
+                        let grads = synchronize(collective, &model, item.grads);
+                        model = self.measure(StepPhase::Optimizer, || {
+                            model.optimize(&mut optim, lr, grads)
+                        });
+                    }
+                }
             }
 
-            let item = LearnerItem::new(
+            let profile = self.profiler.as_ref().map(StepProfiler::end_step);
+
+            if skip {
+                if synchronize_stop(collective, interrupter) {
+                    log::info!("Training interrupted.");
+                    break;
+                }
+                continue;
+            }
+
+            let mut item = LearnerItem::new(
                 item.item,
                 progress,
                 self.epoch,
@@ -134,13 +274,43 @@ impl<TI> TrainEpoch<TI> {
                 iteration,
                 Some(lr),
             );
+            item.profile = profile;
+            item.norms = norms;
 
             processor.process_train(Event::ProcessedItem(item));
 
-            if interrupter.should_stop() {
+            let control = callbacks.on_batch_end(&mut model, iteration, lr);
+            if control.stop {
+                interrupter.stop();
+            }
+
+            if let Some(hook) = step_hook.as_mut() {
+                let saved = hook(StepState {
+                    iteration,
+                    lr_factor: non_finite.as_ref().map_or(1.0, |guard| guard.lr_factor()),
+                    model: &model,
+                    optim: &optim,
+                    scheduler,
+                    processor,
+                    collective,
+                });
+                if saved && callbacks.on_checkpoint(&mut model).stop {
+                    interrupter.stop();
+                }
+            }
+
+            if synchronize_stop(collective, interrupter) {
                 log::info!("Training interrupted.");
                 break;
             }
+            if control.skip_epoch {
+                log::info!("Skipping the remaining training steps of the epoch.");
+                break;
+            }
+        }
+
+        if let Some(profiler) = &self.profiler {
+            profiler.stop();
         }
         processor.process_train(Event::EndEpoch(self.epoch));
 
@@ -149,7 +319,12 @@ impl<TI> TrainEpoch<TI> {
 }
 
 impl<TI> TrainEpoch<TI> {
-    /// Runs the training epoch on multiple devices.
+    /// Runs the training epoch on multiple devices with data parallelism.
+    ///
+    /// The model is replicated on each device, and each replica trains on its own batch, so the
+    /// effective batch size is multiplied by the number of devices. The gradients of the replicas
+    /// are averaged on the first device before each optimizer step, keeping the replicas
+    /// synchronized.
     ///
     /// # Arguments
     ///
@@ -157,17 +332,27 @@ impl<TI> TrainEpoch<TI> {
     /// * `optim` - The optimizer to use.
     /// * `lr_scheduler` - The learning rate scheduler to use.
     /// * `processor` - The event processor to use.
+    /// * `callbacks` - The callbacks invoked after each step.
+    /// * `collective` - The collective used to average the gradients over the processes, for
+    ///   distributed training.
+    /// * `non_finite` - The detection of the non-finite losses and gradients.
+    /// * `step_hook` - The hook invoked after each step, to validate the model during the epoch.
     /// * `devices` - The devices to use.
     ///
     /// # Returns
     ///
     /// The trained model and the optimizer.
+    #[allow(clippy::too_many_arguments)]
     pub fn run_multi_device<LC: LearnerComponents, TO>(
         &self,
         mut model: LC::Model,
         mut optim: LC::Optimizer,
         lr_scheduler: &mut LC::LrScheduler,
         processor: &mut LC::EventProcessor,
+        callbacks: &mut LearnerCallbacks<LC::Model>,
+        collective: &mut Option<Box<dyn Collective>>,
+        non_finite: &mut Option<NonFiniteGuard<TO>>,
+        mut step_hook: Option<&mut StepHook<'_, LC>>,
         devices: Vec<<LC::Backend as Backend>::Device>,
         interrupter: &TrainingInterrupter,
     ) -> (LC::Model, LC::Optimizer)
@@ -184,55 +369,135 @@ impl<TI> TrainEpoch<TI> {
         );
 
         let mut iterator = self.dataloader.iter();
-        let mut iteration = 0;
+        let mut iteration = skip_iterations(&mut iterator, self.skip);
         let mut accumulator = GradientsAccumulator::new();
         let mut accumulation_current = 0;
+        let mut current_lr = None;
 
-        let accumulation = self.grad_accumulation.unwrap_or(1) * devices.len();
+        let accumulation = self.grad_accumulation.unwrap_or(1);
         let step = MultiDevicesTrainStep::new(&devices);
 
         // The main device is always the first in the list.
         let device_main = devices.first().expect("A minimum of one device.").clone();
         let mut interrupted = false;
 
+        if let Some(profiler) = &self.profiler {
+            profiler.start();
+        }
+
         loop {
-            let items = step.step(&mut iterator, &model);
+            // The batches are loaded by the threads of the devices, during the step.
+            let items = self.measure(StepPhase::Step, || step.step(&mut iterator, &model));
             if items.is_empty() {
                 break;
             }
 
-            for item in items {
-                iteration += 1;
-                let lr = lr_scheduler.step();
-                let progress = iterator.progress();
+            // The replicas are synchronized: their gradients are averaged on the main device
+            // before a single optimizer step, like a batch split over the devices.
+            let lr = self.lr(lr_scheduler, &mut current_lr, accumulation_current == 0);
+            let lr = non_finite.as_ref().map_or(lr, |guard| guard.lr(lr));
+            let num_replicas = items.len();
+            let mut replicas = GradientsAccumulator::new();
+            let mut outputs = Vec::with_capacity(num_replicas);
 
+            for item in items {
                 let grads = item.grads.to_device(&device_main, &model);
+                replicas.accumulate(&model, grads);
+                outputs.push(item.item);
+            }
 
+            let grads = replicas.grads().scale(&model, 1.0 / num_replicas as f64);
+
+            let action = inspect(
+                non_finite,
+                collective,
+                &model,
+                &outputs,
+                &grads,
+                (self.epoch, iteration + num_replicas, lr),
+            );
+            if action == Some(NonFiniteAction::Abort) {
+                interrupter.stop();
+                break;
+            }
+            let skip = action == Some(NonFiniteAction::Skip);
+            let norms = self
+                .norms
+                .as_ref()
+                .filter(|_| !skip)
+                .and_then(|norms| norms.compute(iteration + num_replicas, &model, &grads));
+
+            if !skip {
                 accumulator.accumulate(&model, grads);
-                accumulation_current += 1;
+            }
+            accumulation_current += 1;
+
+            if accumulation <= accumulation_current {
+                let grads = synchronize(collective, &model, accumulator.grads());
+                model = self.measure(StepPhase::Optimizer, || {
+                    model.optimize(&mut optim, lr, grads)
+                });
+                accumulation_current = 0;
+            }
 
-                if accumulation <= accumulation_current {
-                    let grads = accumulator.grads();
-                    model = model.optimize(&mut optim, lr, grads);
-                    accumulation_current = 0;
+            let profile = self.profiler.as_ref().map(StepProfiler::end_step);
+
+            if skip {
+                iteration += num_replicas;
+                if synchronize_stop(collective, interrupter) {
+                    log::info!("Training interrupted.");
+                    break;
                 }
+                continue;
+            }
 
-                let item = LearnerItem::new(
-                    item.item,
+            for item in outputs {
+                iteration += 1;
+                let progress = iterator.progress();
+
+                let mut item = LearnerItem::new(
+                    item,
                     progress,
                     self.epoch,
                     self.epoch_total,
                     iteration,
                     Some(lr),
                 );
+                item.profile = profile.clone();
+                item.norms = norms.clone();
 
                 processor.process_train(Event::ProcessedItem(item));
 
-                if interrupter.should_stop() {
+                let control = callbacks.on_batch_end(&mut model, iteration, lr);
+                if control.stop {
+                    interrupter.stop();
+                }
+
+                if let Some(hook) = step_hook.as_mut() {
+                    let saved = hook(StepState {
+                        iteration,
+                        lr_factor: non_finite.as_ref().map_or(1.0, |guard| guard.lr_factor()),
+                        model: &model,
+                        optim: &optim,
+                        scheduler: lr_scheduler,
+                        processor,
+                        collective,
+                    });
+                    if saved && callbacks.on_checkpoint(&mut model).stop {
+                        interrupter.stop();
+                    }
+                }
+
+                if synchronize_stop(collective, interrupter) {
                     log::info!("Training interrupted.");
                     interrupted = true;
                     break;
                 }
+                if control.skip_epoch {
+                    log::info!("Skipping the remaining training steps of the epoch.");
+                    interrupted = true;
+                    break;
+                }
             }
 
             if interrupted {
@@ -240,8 +505,59 @@ impl<TI> TrainEpoch<TI> {
             }
         }
 
+        if let Some(profiler) = &self.profiler {
+            profiler.stop();
+        }
         processor.process_train(Event::EndEpoch(self.epoch));
 
         (model, optim)
     }
 }
+
+/// Skips the iterations already trained, returning the number of skipped iterations.
+fn skip_iterations<I>(iterator: &mut dyn Iterator<Item = I>, num: usize) -> usize {
+    let mut iteration = 0;
+    while iteration < num && iterator.next().is_some() {
+        iteration += 1;
+    }
+
+    if iteration > 0 {
+        log::info!("Skipped {iteration} iterations already trained.");
+    }
+    iteration
+}
+
+/// Averages the gradients over the processes of a distributed training.
+fn synchronize<B, M>(
+    collective: &mut Option<Box<dyn Collective>>,
+    model: &M,
+    grads: GradientsParams,
+) -> GradientsParams
+where
+    B: AutodiffBackend,
+    M: AutodiffModule<B>,
+{
+    match collective {
+        Some(collective) => all_reduce_grads(collective.as_mut(), model, grads),
+        None => grads,
+    }
+}
+
+/// Checks a training step for non-finite values, returning the action to take.
+fn inspect<B, M, TO>(
+    non_finite: &mut Option<NonFiniteGuard<TO>>,
+    collective: &mut Option<Box<dyn Collective>>,
+    model: &M,
+    items: &[TO],
+    grads: &GradientsParams,
+    (epoch, iteration, lr): (usize, usize, LearningRate),
+) -> Option<NonFiniteAction>
+where
+    B: AutodiffBackend,
+    M: AutodiffModule<B>,
+{
+    let guard = non_finite.as_mut()?;
+    let report = guard.check(model, items, grads, collective)?;
+
+    Some(guard.handle(report, items, epoch, iteration, lr))
+}
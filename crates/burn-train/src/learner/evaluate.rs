@@ -0,0 +1,225 @@
+use crate::components::LearnerComponents;
+use crate::metric::processor::{Event, EventProcessor, LearnerItem};
+use crate::metric::store::{Aggregate, Split};
+use crate::{Learner, TestTimeAugmentation, ValidStep};
+use burn_core::data::dataloader::DataLoader;
+use burn_core::module::{AutodiffModule, Module};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+impl<LC: LearnerComponents> Learner<LC> {
+    /// Evaluates the model on a dataset, without optimization.
+    ///
+    /// The [valid step](ValidStep) is run on each batch, with the validation metrics and the
+    /// progress displayed and logged like a validation epoch of the training.
+    ///
+    /// # Arguments
+    ///
+    /// * `dataloader` - The dataloader of the evaluated dataset, such as the test set.
+    ///
+    /// # Returns
+    ///
+    /// The mean of each numeric validation metric over the dataset.
+    ///
+    /// # Notes
+    ///
+    /// Each evaluation is logged as the next validation epoch, starting from the first one, so the
+    /// learner should use its own directory to keep the logs of the training.
+    pub fn evaluate<InputValid, OutputValid>(
+        &mut self,
+        dataloader: Arc<dyn DataLoader<InputValid>>,
+    ) -> HashMap<String, f64>
+    where
+        <LC::Model as AutodiffModule<LC::Backend>>::InnerModule: ValidStep<InputValid, OutputValid>,
+        LC::EventProcessor: EventProcessor<ItemValid = OutputValid>,
+    {
+        self.evaluate_steps(dataloader, |model, item| model.step(item))
+    }
+
+    /// Evaluates the model on a dataset with [test-time augmentation](TestTimeAugmentation),
+    /// without optimization.
+    ///
+    /// The outputs of the augmentations of each batch are merged before being given to the
+    /// validation metrics, otherwise the evaluation is the same as [evaluate](Self::evaluate).
+    pub fn evaluate_with_augmentation<InputValid, OutputValid>(
+        &mut self,
+        dataloader: Arc<dyn DataLoader<InputValid>>,
+        augmentation: &TestTimeAugmentation<InputValid, OutputValid>,
+    ) -> HashMap<String, f64>
+    where
+        <LC::Model as AutodiffModule<LC::Backend>>::InnerModule: ValidStep<InputValid, OutputValid>,
+        LC::EventProcessor: EventProcessor<ItemValid = OutputValid>,
+    {
+        self.evaluate_steps(dataloader, |model, item| augmentation.step(model, item))
+    }
+
+    fn evaluate_steps<InputValid, OutputValid, F>(
+        &mut self,
+        dataloader: Arc<dyn DataLoader<InputValid>>,
+        step: F,
+    ) -> HashMap<String, f64>
+    where
+        LC::EventProcessor: EventProcessor<ItemValid = OutputValid>,
+        F: Fn(&<LC::Model as AutodiffModule<LC::Backend>>::InnerModule, InputValid) -> OutputValid,
+    {
+        let mut model = self.model.valid();
+        if let Some(device) = self.devices.first() {
+            model = model.fork(device);
+        }
+
+        // The metrics of the previous evaluations are kept by the loggers and the store under
+        // their own epochs.
+        self.num_evaluations += 1;
+        let epoch = self.num_evaluations;
+        let mut iterator = dataloader.iter();
+        let mut iteration = 0;
+
+        while let Some(item) = iterator.next() {
+            let progress = iterator.progress();
+            iteration += 1;
+
+            let item = step(&model, item);
+            let item = LearnerItem::new(item, progress, epoch, epoch, iteration, None);
+            self.event_processor
+                .process_valid(Event::ProcessedItem(item));
+
+            if self.interrupter.should_stop() {
+                log::info!("Evaluation interrupted.");
+                break;
+            }
+        }
+        self.event_processor.process_valid(Event::EndEpoch(epoch));
+
+        self.valid_metrics
+            .iter()
+            .filter_map(|name| {
+                let value =
+                    self.event_store
+                        .find_metric(name, epoch, Aggregate::Mean, Split::Valid)?;
+                Some((name.clone(), value))
+            })
+            .collect()
+    }
+
+    /// Runs the inference of the model on a dataset, returning the outputs of the
+    /// [valid step](ValidStep) for each batch, in order.
+    ///
+    /// No metric is computed, the outputs being returned instead.
+    pub fn predict<Input, Output>(&self, dataloader: Arc<dyn DataLoader<Input>>) -> Vec<Output>
+    where
+        <LC::Model as AutodiffModule<LC::Backend>>::InnerModule: ValidStep<Input, Output>,
+    {
+        let mut model = self.model.valid();
+        if let Some(device) = self.devices.first() {
+            model = model.fork(device);
+        }
+
+        let mut iterator = dataloader.iter();
+        let mut outputs = Vec::new();
+
+        while let Some(item) = iterator.next() {
+            outputs.push(model.step(item));
+
+            let progress = iterator.progress();
+            log::debug!(
+                "Predicted {}/{} items",
+                progress.items_processed,
+                progress.items_total
+            );
+
+            if self.interrupter.should_stop() {
+                log::info!("Inference interrupted.");
+                break;
+            }
+        }
+
+        outputs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metric::LossMetric;
+    use crate::renderer::{MetricState, MetricsRenderer, TrainingProgress};
+    use crate::{LearnerBuilder, RegressionOutput, TestBackend};
+    use burn_core::data::dataloader::batcher::Batcher;
+    use burn_core::data::dataloader::DataLoaderBuilder;
+    use burn_core::data::dataset::InMemDataset;
+    use burn_core::nn::loss::{MseLoss, Reduction};
+    use burn_core::nn::{Initializer, Linear, LinearConfig};
+    use burn_core::optim::SgdConfig;
+    use burn_core::tensor::{Tensor, TensorData};
+
+    type TestAutodiffBackend = burn_autodiff::Autodiff<TestBackend>;
+    type Batch = [Tensor<TestBackend, 2>; 2];
+
+    impl ValidStep<Batch, RegressionOutput<TestBackend>> for Linear<TestBackend> {
+        fn step(&self, [inputs, targets]: Batch) -> RegressionOutput<TestBackend> {
+            let output = self.forward(inputs);
+            let loss = MseLoss::new().forward(output.clone(), targets.clone(), Reduction::Mean);
+
+            RegressionOutput::new(loss, output, targets)
+        }
+    }
+
+    #[derive(Clone)]
+    struct PairBatcher;
+
+    impl Batcher<[f32; 2], Batch> for PairBatcher {
+        fn batch(&self, items: Vec<[f32; 2]>) -> Batch {
+            let column = |index: usize| {
+                let values = items.iter().map(|item| item[index]).collect::<Vec<_>>();
+                let data = TensorData::new(values, [items.len(), 1]);
+                Tensor::from_data(data, &Default::default())
+            };
+
+            [column(0), column(1)]
+        }
+    }
+
+    struct NoRenderer;
+
+    impl MetricsRenderer for NoRenderer {
+        fn update_train(&mut self, _state: MetricState) {}
+        fn update_valid(&mut self, _state: MetricState) {}
+        fn render_train(&mut self, _item: TrainingProgress) {}
+        fn render_valid(&mut self, _item: TrainingProgress) {}
+    }
+
+    fn dataloader(pairs: Vec<[f32; 2]>) -> Arc<dyn DataLoader<Batch>> {
+        DataLoaderBuilder::new(PairBatcher)
+            .batch_size(2)
+            .build(InMemDataset::new(pairs))
+    }
+
+    #[test]
+    fn evaluations_should_not_reuse_the_previous_metrics() {
+        let directory = "/tmp/test-learner-evaluate";
+        // The identity model, the loss being the squared error of the inputs.
+        let model = LinearConfig::new(1, 1)
+            .with_bias(false)
+            .with_initializer(Initializer::Ones)
+            .init::<TestAutodiffBackend>(&Default::default());
+        let mut learner = LearnerBuilder::<
+            TestAutodiffBackend,
+            RegressionOutput<TestAutodiffBackend>,
+            RegressionOutput<TestBackend>,
+            _,
+            _,
+            _,
+        >::new(directory)
+        .metric_valid_numeric(LossMetric::new())
+        .renderer(NoRenderer)
+        .with_application_logger(None)
+        .build(model, SgdConfig::new().init(), 1e-2);
+
+        let first = learner.evaluate(dataloader(vec![[1.0, 1.0], [2.0, 2.0]]));
+        let second = learner.evaluate(dataloader(vec![[1.0, 2.0], [2.0, 3.0]]));
+
+        assert_eq!(first.get("Loss"), Some(&0.0));
+        assert_eq!(second.get("Loss"), Some(&1.0));
+
+        std::fs::remove_dir_all(directory).ok();
+    }
+}
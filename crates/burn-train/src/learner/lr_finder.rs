@@ -0,0 +1,305 @@
+use crate::metric::{Adaptor, LossInput};
+use crate::TrainStep;
+use burn_core::data::dataloader::DataLoader;
+use burn_core::module::AutodiffModule;
+use burn_core::optim::Optimizer;
+use burn_core::tensor::backend::AutodiffBackend;
+use burn_core::LearningRate;
+use std::fmt::Display;
+use std::sync::Arc;
+
+/// Learning rate finder, running a short training with an exponentially increasing learning
+/// rate and recording the loss for each one.
+///
+/// The sweep stops after `num_iters` iterations, or earlier when the loss diverges. The
+/// resulting [curve](LrFinderResult) can be used to pick a learning rate range, typically a bit
+/// before the loss reaches its minimum.
+///
+/// The model and optimizer given to [run](LrFinder::run) are modified by the sweep, so a fresh
+/// model should be used for the actual training.
+#[derive(Clone, Debug)]
+pub struct LrFinder {
+    start_lr: LearningRate,
+    end_lr: LearningRate,
+    num_iters: usize,
+    smoothing: f64,
+    divergence_threshold: f64,
+}
+
+impl Default for LrFinder {
+    fn default() -> Self {
+        Self {
+            start_lr: 1e-7,
+            end_lr: 1.0,
+            num_iters: 100,
+            smoothing: 0.98,
+            divergence_threshold: 4.0,
+        }
+    }
+}
+
+impl LrFinder {
+    /// Create a learning rate finder with the default settings, sweeping from 1e-7 to 1 in 100
+    /// iterations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The learning rates range of the sweep.
+    pub fn with_range(mut self, start_lr: LearningRate, end_lr: LearningRate) -> Self {
+        assert!(
+            start_lr > 0. && start_lr < end_lr,
+            "The start learning rate must be greater than 0 and lower than the end learning rate"
+        );
+        self.start_lr = start_lr;
+        self.end_lr = end_lr;
+        self
+    }
+
+    /// The number of iterations of the sweep.
+    pub fn with_num_iters(mut self, num_iters: usize) -> Self {
+        assert!(num_iters > 1, "The sweep needs at least 2 iterations");
+        self.num_iters = num_iters;
+        self
+    }
+
+    /// The exponential moving average factor used to smooth the recorded losses.
+    pub fn with_smoothing(mut self, smoothing: f64) -> Self {
+        assert!(
+            (0.0..1.0).contains(&smoothing),
+            "The smoothing factor must be at least 0 and lower than 1"
+        );
+        self.smoothing = smoothing;
+        self
+    }
+
+    /// The sweep stops when the smoothed loss exceeds the best loss times this factor.
+    pub fn with_divergence_threshold(mut self, divergence_threshold: f64) -> Self {
+        self.divergence_threshold = divergence_threshold;
+        self
+    }
+
+    /// Runs the learning rate sweep over the given dataloader, iterating over it multiple times
+    /// if it has fewer items than the number of iterations.
+    pub fn run<B, M, O, TI, TO>(
+        &self,
+        mut model: M,
+        mut optim: O,
+        dataloader: Arc<dyn DataLoader<TI>>,
+    ) -> LrFinderResult
+    where
+        B: AutodiffBackend,
+        M: AutodiffModule<B> + TrainStep<TI, TO>,
+        O: Optimizer<M, B>,
+        TO: Adaptor<LossInput<B>>,
+    {
+        let mut result = LrFinderResult::default();
+        let mut iteration = 0;
+        let mut loss_avg = 0.0;
+        let mut best_loss = f64::INFINITY;
+
+        'sweep: loop {
+            let mut is_empty = true;
+
+            for item in dataloader.iter() {
+                is_empty = false;
+                let lr = self.lr(iteration);
+
+                let output = model.step(item);
+                let loss = output.item.adapt().value();
+                model = model.optimize(&mut optim, lr, output.grads);
+
+                loss_avg = self.smoothing * loss_avg + (1.0 - self.smoothing) * loss;
+                let loss_smoothed = loss_avg / (1.0 - self.smoothing.powi(iteration as i32 + 1));
+
+                log::info!("Learning rate finder iteration {iteration}: lr {lr:e}, loss {loss}");
+                result.lrs.push(lr);
+                result.losses.push(loss_smoothed);
+                iteration += 1;
+
+                if !loss_smoothed.is_finite()
+                    || loss_smoothed > self.divergence_threshold * best_loss
+                {
+                    log::info!("Learning rate finder stopped, the loss diverged.");
+                    break 'sweep;
+                }
+                best_loss = best_loss.min(loss_smoothed);
+
+                if iteration >= self.num_iters {
+                    break 'sweep;
+                }
+            }
+
+            if is_empty {
+                break;
+            }
+        }
+
+        result
+    }
+
+    fn lr(&self, iteration: usize) -> LearningRate {
+        let progress = iteration as f64 / (self.num_iters - 1) as f64;
+        self.start_lr * (self.end_lr / self.start_lr).powf(progress)
+    }
+}
+
+/// The loss curve recorded by the [learning rate finder](LrFinder).
+#[derive(Clone, Debug, Default)]
+pub struct LrFinderResult {
+    /// The learning rate of each iteration.
+    pub lrs: Vec<LearningRate>,
+    /// The smoothed loss of each iteration.
+    pub losses: Vec<f64>,
+}
+
+impl LrFinderResult {
+    /// The learning rate where the loss decreases the fastest, which is usually a good
+    /// learning rate to start training with.
+    pub fn suggestion(&self) -> Option<LearningRate> {
+        let (index, _) = self
+            .lrs
+            .windows(2)
+            .zip(self.losses.windows(2))
+            .map(|(lrs, losses)| (losses[1] - losses[0]) / (lrs[1].ln() - lrs[0].ln()))
+            .enumerate()
+            .filter(|(_, slope)| slope.is_finite())
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))?;
+
+        Some(self.lrs[index])
+    }
+
+    /// The learning rate reaching the lowest loss. Training usually diverges with learning rates
+    /// this high, it's mostly useful as an upper bound.
+    pub fn min_loss_lr(&self) -> Option<LearningRate> {
+        self.losses
+            .iter()
+            .enumerate()
+            .filter(|(_, loss)| loss.is_finite())
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(index, _)| self.lrs[index])
+    }
+
+    /// The suggested learning rate range, from the [suggestion](LrFinderResult::suggestion) to
+    /// a tenth of the [learning rate reaching the lowest loss](LrFinderResult::min_loss_lr).
+    pub fn suggested_range(&self) -> Option<(LearningRate, LearningRate)> {
+        let start = self.suggestion()?;
+        let end = (self.min_loss_lr()? / 10.0).max(start);
+
+        Some((start, end))
+    }
+
+    /// Plots the loss against the learning rate (on a log scale) as text.
+    pub fn plot(&self, width: usize, height: usize) -> String {
+        let points = self
+            .lrs
+            .iter()
+            .zip(self.losses.iter())
+            .filter(|(_, loss)| loss.is_finite())
+            .map(|(lr, loss)| (lr.log10(), *loss))
+            .collect::<Vec<_>>();
+
+        if points.is_empty() || width == 0 || height == 0 {
+            return String::new();
+        }
+
+        let bounds = |values: &mut dyn Iterator<Item = f64>| {
+            values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+                (min.min(value), max.max(value))
+            })
+        };
+        let (x_min, x_max) = bounds(&mut points.iter().map(|(x, _)| *x));
+        let (y_min, y_max) = bounds(&mut points.iter().map(|(_, y)| *y));
+        let scale = |value: f64, min: f64, max: f64, size: usize| {
+            if max > min {
+                ((value - min) / (max - min) * (size - 1) as f64).round() as usize
+            } else {
+                0
+            }
+        };
+
+        let mut grid = vec![vec![' '; width]; height];
+        for (x, y) in points {
+            let column = scale(x, x_min, x_max, width);
+            let row = height - 1 - scale(y, y_min, y_max, height);
+            grid[row][column] = '*';
+        }
+
+        let mut plot = format!("loss {y_max:.4}\n");
+        for row in grid {
+            plot += "|";
+            plot.extend(row);
+            plot += "\n";
+        }
+        plot += &format!("loss {y_min:.4}\n");
+        plot += &format!(
+            "lr {:e} {} lr {:e}\n",
+            10f64.powf(x_min),
+            "-".repeat(width.saturating_sub(24)),
+            10f64.powf(x_max)
+        );
+        plot
+    }
+}
+
+impl Display for LrFinderResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Learning rate finder ({} iterations)", self.lrs.len())?;
+        match self.suggested_range() {
+            Some((start, end)) => {
+                writeln!(f, "Suggested learning rate range: {start:e} - {end:e}")?
+            }
+            None => writeln!(f, "Not enough iterations to suggest a learning rate.")?,
+        }
+        write!(f, "{}", self.plot(60, 15))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result() -> LrFinderResult {
+        LrFinderResult {
+            lrs: vec![1e-5, 1e-4, 1e-3, 1e-2, 1e-1, 1.0],
+            losses: vec![2.0, 1.9, 1.2, 0.8, 0.7, 5.0],
+        }
+    }
+
+    #[test]
+    fn should_suggest_steepest_decrease() {
+        assert_eq!(result().suggestion(), Some(1e-4));
+    }
+
+    #[test]
+    fn should_find_min_loss_lr() {
+        assert_eq!(result().min_loss_lr(), Some(1e-1));
+    }
+
+    #[test]
+    fn should_suggest_range() {
+        let (start, end) = result().suggested_range().unwrap();
+
+        assert_eq!(start, 1e-4);
+        assert!((end - 1e-2).abs() < 1e-12, "{end} != 1e-2");
+    }
+
+    #[test]
+    fn should_not_suggest_without_enough_iterations() {
+        let result = LrFinderResult {
+            lrs: vec![1e-5],
+            losses: vec![2.0],
+        };
+        assert_eq!(result.suggestion(), None);
+    }
+
+    #[test]
+    fn should_sweep_exponentially() {
+        let finder = LrFinder::new().with_range(1e-4, 1.0).with_num_iters(5);
+        let lrs = (0..5).map(|i| finder.lr(i)).collect::<Vec<_>>();
+
+        for (lr, expected) in lrs.iter().zip([1e-4, 1e-3, 1e-2, 1e-1, 1.0]) {
+            assert!((lr - expected).abs() < 1e-12, "{lr} != {expected}");
+        }
+    }
+}
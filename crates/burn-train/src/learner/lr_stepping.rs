@@ -0,0 +1,67 @@
+/// When the learning rate scheduler is stepped during training, set with the
+/// [learner builder](crate::LearnerBuilder::lr_stepping).
+///
+/// Schedules defined in steps, such as the warmup and the decay of transformers, usually count
+/// the optimizer steps, which differ from the batches with gradient accumulation. The total
+/// number of scheduler steps of a training is given by
+/// [the learner builder](crate::LearnerBuilder::num_lr_steps) to configure these schedules.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LrStepping {
+    /// Step the scheduler for each batch, or each group of batches with multiple devices.
+    #[default]
+    Batch,
+    /// Step the scheduler for each optimizer step, the batches of a gradient accumulation
+    /// sharing the same learning rate.
+    Optimizer,
+    /// Step the scheduler once at the start of each epoch.
+    ///
+    /// When resuming the training in the middle of an epoch, the scheduler is stepped again for
+    /// that epoch.
+    Epoch,
+}
+
+impl LrStepping {
+    /// Whether the scheduler is stepped for a batch after the first one of the epoch.
+    pub(crate) fn should_step(&self, accumulation_start: bool) -> bool {
+        match self {
+            LrStepping::Batch => true,
+            LrStepping::Optimizer => accumulation_start,
+            LrStepping::Epoch => false,
+        }
+    }
+
+    /// The number of scheduler steps over the training.
+    pub(crate) fn num_steps(
+        &self,
+        num_batches: usize,
+        num_epochs: usize,
+        num_devices: usize,
+        grad_accumulation: Option<usize>,
+    ) -> usize {
+        // The batches of the devices are trained together.
+        let iterations = num_batches.div_ceil(num_devices.max(1));
+
+        let steps_per_epoch = match self {
+            LrStepping::Batch => iterations,
+            LrStepping::Optimizer => iterations.div_ceil(grad_accumulation.unwrap_or(1)),
+            LrStepping::Epoch => 1,
+        };
+
+        steps_per_epoch * num_epochs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn num_steps_should_count_the_scheduler_steps_of_the_training() {
+        assert_eq!(LrStepping::Batch.num_steps(10, 3, 1, Some(4)), 30);
+        assert_eq!(LrStepping::Batch.num_steps(10, 3, 4, None), 9);
+        // The last accumulation of each epoch is incomplete.
+        assert_eq!(LrStepping::Optimizer.num_steps(10, 3, 1, Some(4)), 9);
+        assert_eq!(LrStepping::Optimizer.num_steps(10, 3, 2, Some(2)), 9);
+        assert_eq!(LrStepping::Epoch.num_steps(10, 3, 2, Some(4)), 3);
+    }
+}
@@ -1,22 +1,43 @@
 mod application_logger;
 mod base;
 mod builder;
+mod callback;
 mod classification;
+mod cross_validation;
+mod curriculum;
 mod early_stopping;
 mod epoch;
+mod evaluate;
+mod lr_finder;
+mod lr_stepping;
+mod non_finite;
+mod norms;
+mod profiler;
 mod regression;
+mod segmentation;
 mod step;
 mod summary;
 mod train_val;
+mod tta;
 
 pub use application_logger::*;
 pub use base::*;
 pub use builder::*;
+pub use callback::*;
 pub use classification::*;
+pub use cross_validation::*;
+pub use curriculum::*;
 pub use early_stopping::*;
 pub use epoch::*;
+pub use lr_finder::*;
+pub use lr_stepping::*;
+pub use non_finite::*;
+pub use norms::*;
+pub use profiler::*;
 pub use regression::*;
+pub use segmentation::*;
 pub use step::*;
 pub use summary::*;
 pub use train::*;
 pub use train_val::*;
+pub use tta::*;
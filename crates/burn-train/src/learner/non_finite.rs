@@ -0,0 +1,342 @@
+use crate::distributed::{all_reduce_flag, Collective};
+use crate::metric::{Adaptor, LossInput};
+use burn_core::module::{AutodiffModule, ModuleVisitor, ParamId};
+use burn_core::optim::GradientsParams;
+use burn_core::tensor::backend::{AutodiffBackend, Backend};
+use burn_core::tensor::{ElementConversion, Tensor};
+use burn_core::LearningRate;
+use core::marker::PhantomData;
+use std::path::PathBuf;
+
+/// The action taken by the [learner](crate::Learner) when a non-finite loss or gradient is
+/// detected.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NonFinitePolicy {
+    /// Skip the batch: its gradients aren't applied to the model and its metrics aren't
+    /// collected.
+    SkipBatch,
+    /// Skip the batch and multiply the learning rate by the given factor for the rest of the
+    /// training.
+    ReduceLr {
+        /// The factor applied to the learning rate each time a non-finite value is detected.
+        factor: f64,
+    },
+    /// Stop the training, writing a diagnostic report of the offending batch in the artifact
+    /// directory.
+    Abort,
+}
+
+type DumpFn<T> = Box<dyn Fn(&T) -> String>;
+
+/// Detection of non-finite losses and gradients during training, registered with the
+/// [learner builder](crate::LearnerBuilder::detect_non_finite).
+///
+/// The loss is checked after each training step, before the optimizer step, so that a NaN or an
+/// infinity never reaches the weights of the model.
+pub struct NonFiniteDetection<T> {
+    policy: NonFinitePolicy,
+    check_gradients: bool,
+    max_grad_norm: Option<f64>,
+    dump: Option<DumpFn<T>>,
+}
+
+impl<T> NonFiniteDetection<T> {
+    /// Create the detection with the given policy, checking the loss only.
+    pub fn new(policy: NonFinitePolicy) -> Self {
+        if let NonFinitePolicy::ReduceLr { factor } = policy {
+            assert!(
+                factor > 0.0 && factor < 1.0,
+                "The learning rate factor should be between 0 and 1"
+            );
+        }
+
+        Self {
+            policy,
+            check_gradients: false,
+            max_grad_norm: None,
+            dump: None,
+        }
+    }
+
+    /// Also check that the norm of the gradients is finite, which requires reading all the
+    /// gradients at each step.
+    pub fn with_gradients(mut self, check_gradients: bool) -> Self {
+        self.check_gradients = check_gradients;
+        self
+    }
+
+    /// Also consider the steps with a gradient norm above the given value as diverging.
+    pub fn with_max_grad_norm(mut self, max_grad_norm: f64) -> Self {
+        self.check_gradients = true;
+        self.max_grad_norm = Some(max_grad_norm);
+        self
+    }
+
+    /// Format the training outputs of the offending batch in the diagnostic report.
+    pub fn with_dump<F>(mut self, dump: F) -> Self
+    where
+        F: Fn(&T) -> String + 'static,
+    {
+        self.dump = Some(Box::new(dump));
+        self
+    }
+}
+
+/// What was detected during a training step.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct NonFiniteReport {
+    /// The losses of the items of the step.
+    losses: Vec<f64>,
+    /// The norm of the gradients, if checked.
+    grad_norm: Option<f64>,
+    /// The parameters with non-finite gradients.
+    params: Vec<String>,
+}
+
+/// The action to take for the current step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum NonFiniteAction {
+    Skip,
+    Abort,
+}
+
+/// Applies the [non-finite detection](NonFiniteDetection) during training.
+pub struct NonFiniteGuard<T> {
+    detection: NonFiniteDetection<T>,
+    loss: Box<dyn Fn(&T) -> f64>,
+    directory: PathBuf,
+    lr_factor: f64,
+}
+
+impl<T> NonFiniteGuard<T> {
+    pub(crate) fn new<B>(detection: NonFiniteDetection<T>, directory: PathBuf) -> Self
+    where
+        B: Backend,
+        T: Adaptor<LossInput<B>> + 'static,
+    {
+        Self {
+            detection,
+            loss: Box::new(|item: &T| item.adapt().value()),
+            directory,
+            lr_factor: 1.0,
+        }
+    }
+
+    /// The learning rate to use, reduced after each divergence with the
+    /// [reduce policy](NonFinitePolicy::ReduceLr).
+    pub(crate) fn lr(&self, lr: LearningRate) -> LearningRate {
+        lr * self.lr_factor
+    }
+
+    /// The factor applied to the learning rate, saved with the checkpoints.
+    pub(crate) fn lr_factor(&self) -> f64 {
+        self.lr_factor
+    }
+
+    /// Restores the factor applied to the learning rate when resuming the training.
+    pub(crate) fn set_lr_factor(&mut self, lr_factor: f64) {
+        self.lr_factor = lr_factor;
+    }
+
+    /// Checks the losses of the items and the gradients of the step.
+    ///
+    /// With distributed training, the result is shared with the other processes, so that they
+    /// all skip or abort the same steps.
+    pub(crate) fn check<B, M>(
+        &self,
+        model: &M,
+        items: &[T],
+        grads: &GradientsParams,
+        collective: &mut Option<Box<dyn Collective>>,
+    ) -> Option<NonFiniteReport>
+    where
+        B: AutodiffBackend,
+        M: AutodiffModule<B>,
+    {
+        let mut report = NonFiniteReport {
+            losses: items.iter().map(|item| (self.loss)(item)).collect(),
+            ..Default::default()
+        };
+        let mut diverged = report.losses.iter().any(|loss| !loss.is_finite());
+
+        if self.detection.check_gradients {
+            let mut visitor = GradientsNorm::<B, M> {
+                grads,
+                path: Vec::new(),
+                sum_squares: 0.0,
+                params: Vec::new(),
+                _p: PhantomData,
+            };
+            model.visit(&mut visitor);
+
+            let grad_norm = visitor.sum_squares.sqrt();
+            let max_grad_norm = self.detection.max_grad_norm.unwrap_or(f64::INFINITY);
+            diverged |= !grad_norm.is_finite() || grad_norm > max_grad_norm;
+            report.grad_norm = Some(grad_norm);
+            report.params = visitor.params;
+        }
+
+        if let Some(collective) = collective {
+            diverged = all_reduce_flag(collective.as_mut(), diverged);
+        }
+
+        if diverged {
+            Some(report)
+        } else {
+            None
+        }
+    }
+
+    /// Applies the policy to a diverging step.
+    pub(crate) fn handle(
+        &mut self,
+        report: NonFiniteReport,
+        items: &[T],
+        epoch: usize,
+        iteration: usize,
+        lr: LearningRate,
+    ) -> NonFiniteAction {
+        let summary = format!(
+            "Non-finite values detected at epoch {epoch}, iteration {iteration}: losses {:?}, \
+             gradient norm {:?}, learning rate {lr}",
+            report.losses, report.grad_norm
+        );
+
+        match self.detection.policy {
+            NonFinitePolicy::SkipBatch => {
+                log::warn!("{summary}, skipping the batch.");
+                NonFiniteAction::Skip
+            }
+            NonFinitePolicy::ReduceLr { factor } => {
+                self.lr_factor *= factor;
+                log::warn!(
+                    "{summary}, skipping the batch and reducing the learning rate by a factor {}.",
+                    self.lr_factor
+                );
+                NonFiniteAction::Skip
+            }
+            NonFinitePolicy::Abort => {
+                log::error!("{summary}, aborting the training.");
+                self.write_report(&summary, &report, items, epoch, iteration);
+                NonFiniteAction::Abort
+            }
+        }
+    }
+
+    fn write_report(
+        &self,
+        summary: &str,
+        report: &NonFiniteReport,
+        items: &[T],
+        epoch: usize,
+        iteration: usize,
+    ) {
+        let mut content = format!("{summary}\n");
+
+        if !report.params.is_empty() {
+            content += "\nParameters with non-finite gradients:\n";
+            for param in report.params.iter() {
+                content += &format!("  {param}\n");
+            }
+        }
+
+        if let Some(dump) = &self.detection.dump {
+            for (i, item) in items.iter().enumerate() {
+                content += &format!("\nOutput {i}:\n{}\n", dump(item));
+            }
+        }
+
+        let path = self.directory.join(format!(
+            "non-finite-epoch-{epoch}-iteration-{iteration}.log"
+        ));
+
+        match std::fs::create_dir_all(&self.directory).and_then(|_| std::fs::write(&path, content))
+        {
+            Ok(()) => log::error!("Diagnostic report written to {}", path.display()),
+            Err(err) => log::error!("Failed to write the diagnostic report: {err}"),
+        }
+    }
+}
+
+struct GradientsNorm<'a, B, M> {
+    grads: &'a GradientsParams,
+    path: Vec<String>,
+    sum_squares: f64,
+    params: Vec<String>,
+    _p: PhantomData<(B, M)>,
+}
+
+impl<B, M> ModuleVisitor<B> for GradientsNorm<'_, B, M>
+where
+    B: AutodiffBackend,
+    M: AutodiffModule<B>,
+{
+    fn enter_module(&mut self, name: &str) {
+        self.path.push(name.into());
+    }
+
+    fn exit_module(&mut self, _name: &str) {
+        self.path.pop();
+    }
+
+    fn visit_float<const D: usize>(&mut self, id: ParamId, _tensor: &Tensor<B, D>) {
+        let Some(grad) = self.grads.get::<B::InnerBackend, D>(id) else {
+            return;
+        };
+
+        let sum_squares = grad.powf_scalar(2.0).sum().into_scalar().elem::<f64>();
+        if !sum_squares.is_finite() {
+            self.params.push(self.path.join("."));
+        }
+        self.sum_squares += sum_squares;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+
+    struct Output(f32);
+
+    impl Adaptor<LossInput<TestBackend>> for Output {
+        fn adapt(&self) -> LossInput<TestBackend> {
+            LossInput::new(Tensor::from_floats([self.0], &Default::default()))
+        }
+    }
+
+    fn guard(detection: NonFiniteDetection<Output>, directory: &str) -> NonFiniteGuard<Output> {
+        NonFiniteGuard::new::<TestBackend>(detection, std::env::temp_dir().join(directory))
+    }
+
+    #[test]
+    fn reduce_lr_policy_should_skip_and_reduce_the_learning_rate() {
+        let detection = NonFiniteDetection::new(NonFinitePolicy::ReduceLr { factor: 0.5 });
+        let mut guard = guard(detection, "burn-non-finite-reduce");
+
+        let action = guard.handle(NonFiniteReport::default(), &[], 1, 3, 0.1);
+
+        assert_eq!(action, NonFiniteAction::Skip);
+        assert_eq!(guard.lr(0.1), 0.05);
+    }
+
+    #[test]
+    fn abort_policy_should_write_a_diagnostic_report() {
+        let detection = NonFiniteDetection::new(NonFinitePolicy::Abort)
+            .with_dump(|output: &Output| format!("loss {}", output.0));
+        let mut guard = guard(detection, "burn-non-finite-abort");
+        let items = [Output(f32::NAN)];
+        let report = NonFiniteReport {
+            losses: items.iter().map(|item| (guard.loss)(item)).collect(),
+            ..Default::default()
+        };
+
+        let action = guard.handle(report, &items, 2, 7, 0.1);
+
+        assert_eq!(action, NonFiniteAction::Abort);
+        let path = guard.directory.join("non-finite-epoch-2-iteration-7.log");
+        let content = std::fs::read_to_string(path).unwrap();
+        assert!(content.contains("losses [NaN]"));
+        assert!(content.contains("Output 0:\nloss NaN"));
+    }
+}
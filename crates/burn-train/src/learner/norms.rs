@@ -0,0 +1,231 @@
+use burn_core::module::{AutodiffModule, ModuleVisitor, ParamId};
+use burn_core::optim::GradientsParams;
+use burn_core::tensor::backend::{AutodiffBackend, Backend};
+use burn_core::tensor::{ElementConversion, Tensor};
+use core::marker::PhantomData;
+use std::collections::BTreeMap;
+
+/// Monitoring of the norms of the gradients and of the parameters during training, registered
+/// with the [learner builder](crate::LearnerBuilder::monitor_norms).
+///
+/// Computing the norms reads all the gradients and the parameters of the model, so they are
+/// only computed every given number of steps.
+#[derive(Clone, Debug)]
+pub struct NormMonitoring {
+    frequency: usize,
+    per_layer: bool,
+}
+
+/// The norms computed at a training step.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StepNorms {
+    /// The global norm of the gradients.
+    pub gradients: f64,
+    /// The global norm of the parameters.
+    pub parameters: f64,
+    /// The norms of each layer, when computed per layer.
+    pub layers: Vec<LayerNorms>,
+}
+
+/// The norms of the parameters of a layer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayerNorms {
+    /// The path of the layer in the model.
+    pub name: String,
+    /// The norm of the gradients of the layer.
+    pub gradients: f64,
+    /// The norm of the parameters of the layer.
+    pub parameters: f64,
+}
+
+impl Default for NormMonitoring {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NormMonitoring {
+    /// Compute the global norms at every step.
+    pub fn new() -> Self {
+        Self {
+            frequency: 1,
+            per_layer: false,
+        }
+    }
+
+    /// Compute the norms every given number of steps.
+    pub fn with_frequency(mut self, frequency: usize) -> Self {
+        assert!(frequency > 0, "The frequency should be at least one step");
+        self.frequency = frequency;
+        self
+    }
+
+    /// Also compute the norms of each layer.
+    pub fn with_per_layer(mut self, per_layer: bool) -> Self {
+        self.per_layer = per_layer;
+        self
+    }
+
+    /// Computes the norms of the gradients of the step, if sampled at this iteration.
+    pub(crate) fn compute<B, M>(
+        &self,
+        iteration: usize,
+        model: &M,
+        grads: &GradientsParams,
+    ) -> Option<StepNorms>
+    where
+        B: AutodiffBackend,
+        M: AutodiffModule<B>,
+    {
+        if iteration % self.frequency != 0 {
+            return None;
+        }
+
+        let mut visitor = NormsVisitor::<B, M> {
+            grads,
+            path: Vec::new(),
+            per_layer: self.per_layer,
+            global: SumSquares::default(),
+            layers: BTreeMap::new(),
+            _p: PhantomData,
+        };
+        model.visit(&mut visitor);
+
+        Some(StepNorms {
+            gradients: visitor.global.gradients.sqrt(),
+            parameters: visitor.global.parameters.sqrt(),
+            layers: visitor
+                .layers
+                .into_iter()
+                .map(|(name, sum)| LayerNorms {
+                    name,
+                    gradients: sum.gradients.sqrt(),
+                    parameters: sum.parameters.sqrt(),
+                })
+                .collect(),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct SumSquares {
+    gradients: f64,
+    parameters: f64,
+}
+
+struct NormsVisitor<'a, B, M> {
+    grads: &'a GradientsParams,
+    path: Vec<String>,
+    per_layer: bool,
+    global: SumSquares,
+    /// Sorted by path for a stable order.
+    layers: BTreeMap<String, SumSquares>,
+    _p: PhantomData<(B, M)>,
+}
+
+impl<B, M> ModuleVisitor<B> for NormsVisitor<'_, B, M>
+where
+    B: AutodiffBackend,
+    M: AutodiffModule<B>,
+{
+    fn enter_module(&mut self, name: &str) {
+        self.path.push(name.into());
+    }
+
+    fn exit_module(&mut self, _name: &str) {
+        self.path.pop();
+    }
+
+    fn visit_float<const D: usize>(&mut self, id: ParamId, tensor: &Tensor<B, D>) {
+        let parameters = sum_squares(tensor.clone().inner());
+        let gradients = self
+            .grads
+            .get::<B::InnerBackend, D>(id)
+            .map_or(0.0, sum_squares);
+
+        self.global.gradients += gradients;
+        self.global.parameters += parameters;
+
+        if self.per_layer {
+            // The layer is the module containing the parameter.
+            let layer = self.path[..self.path.len().saturating_sub(1)].join(".");
+            let sum = self.layers.entry(layer).or_default();
+            sum.gradients += gradients;
+            sum.parameters += parameters;
+        }
+    }
+}
+
+fn sum_squares<B: Backend, const D: usize>(tensor: Tensor<B, D>) -> f64 {
+    tensor.powf_scalar(2.0).sum().into_scalar().elem::<f64>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use burn_autodiff::Autodiff;
+    use burn_core::nn::{Initializer, Linear, LinearConfig};
+
+    type B = Autodiff<TestBackend>;
+
+    /// Two layers with ones as parameters, the gradients of the first one being the input.
+    fn model_and_grads() -> (Vec<Linear<B>>, GradientsParams) {
+        let device = Default::default();
+        let layer = LinearConfig::new(2, 1).with_initializer(Initializer::Ones);
+        let model = vec![layer.init::<B>(&device), layer.init::<B>(&device)];
+
+        let input = Tensor::<B, 2>::from_floats([[1.0, 2.0]], &device);
+        let grads = model[0].forward(input).sum().backward();
+        let grads = GradientsParams::from_grads(grads, &model);
+
+        (model, grads)
+    }
+
+    #[test]
+    fn test_norms_are_computed_every_given_number_of_steps() {
+        let (model, grads) = model_and_grads();
+        let monitoring = NormMonitoring::new().with_frequency(2);
+
+        assert_eq!(monitoring.compute(1, &model, &grads), None);
+        assert!(monitoring.compute(2, &model, &grads).is_some());
+    }
+
+    #[test]
+    fn test_global_norms() {
+        let (model, grads) = model_and_grads();
+
+        let norms = NormMonitoring::new().compute(0, &model, &grads).unwrap();
+
+        // The gradients of the weights are the input, and the gradient of the bias is one.
+        assert_eq!(norms.gradients, 6.0_f64.sqrt());
+        assert_eq!(norms.parameters, 6.0_f64.sqrt());
+        assert!(norms.layers.is_empty());
+    }
+
+    #[test]
+    fn test_norms_per_layer() {
+        let (model, grads) = model_and_grads();
+
+        let norms = NormMonitoring::new()
+            .with_per_layer(true)
+            .compute(0, &model, &grads)
+            .unwrap();
+
+        assert_eq!(
+            norms.layers,
+            vec![
+                LayerNorms {
+                    name: "0".to_string(),
+                    gradients: 6.0_f64.sqrt(),
+                    parameters: 3.0_f64.sqrt(),
+                },
+                LayerNorms {
+                    name: "1".to_string(),
+                    gradients: 0.0,
+                    parameters: 3.0_f64.sqrt(),
+                },
+            ]
+        );
+    }
+}
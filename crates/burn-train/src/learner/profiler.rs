@@ -0,0 +1,182 @@
+use burn_core::tensor::backend::Backend;
+use std::cell::RefCell;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// A phase of a training step measured by the [profiler](crate::LearnerBuilder::profile).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StepPhase {
+    /// Loading the next batch from the dataloader.
+    DataLoading,
+    /// Moving the batch to the device, recorded with [profile_phase].
+    Transfer,
+    /// The forward pass, recorded with [profile_phase].
+    Forward,
+    /// The backward pass, recorded with [profile_phase].
+    Backward,
+    /// The whole [train step](crate::TrainStep::step), including the phases recorded in it.
+    Step,
+    /// The optimizer step.
+    Optimizer,
+}
+
+impl StepPhase {
+    const ALL: [StepPhase; 6] = [
+        StepPhase::DataLoading,
+        StepPhase::Transfer,
+        StepPhase::Forward,
+        StepPhase::Backward,
+        StepPhase::Step,
+        StepPhase::Optimizer,
+    ];
+
+    /// The name of the phase.
+    pub fn name(&self) -> &'static str {
+        match self {
+            StepPhase::DataLoading => "Data Loading",
+            StepPhase::Transfer => "Transfer",
+            StepPhase::Forward => "Forward",
+            StepPhase::Backward => "Backward",
+            StepPhase::Step => "Step",
+            StepPhase::Optimizer => "Optimizer",
+        }
+    }
+
+    fn index(&self) -> usize {
+        Self::ALL.iter().position(|phase| phase == self).unwrap()
+    }
+}
+
+/// The durations of the phases of a training step.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StepProfile {
+    durations: [Option<Duration>; StepPhase::ALL.len()],
+}
+
+impl StepProfile {
+    /// The duration of the phase, if measured during the step.
+    pub fn duration(&self, phase: StepPhase) -> Option<Duration> {
+        self.durations[phase.index()]
+    }
+
+    /// Adds the duration to the phase, which may be measured multiple times in a step.
+    pub(crate) fn record(&mut self, phase: StepPhase, duration: Duration) {
+        let total = self.durations[phase.index()].get_or_insert(Duration::ZERO);
+        *total += duration;
+    }
+}
+
+thread_local! {
+    /// The profile of the current step, when profiling.
+    static PROFILE: RefCell<Option<StepProfile>> = const { RefCell::new(None) };
+}
+
+fn record(phase: StepPhase, duration: Duration) {
+    PROFILE.with(|profile| {
+        if let Some(profile) = profile.borrow_mut().as_mut() {
+            profile.record(phase, duration);
+        }
+    });
+}
+
+fn is_profiling() -> bool {
+    PROFILE.with(|profile| profile.borrow().is_some())
+}
+
+/// Measures a phase of the [train step](crate::TrainStep::step), such as the transfer of the
+/// batch or the forward and backward passes, when the training is
+/// [profiled](crate::LearnerBuilder::profile).
+///
+/// The device is synchronized after the phase so that the asynchronous computations are
+/// included in its duration. Without profiling, the function is only called.
+///
+/// # Notes
+///
+/// The phase is recorded on the thread running the step, which isn't the training thread with
+/// multiple devices, so the phases of the step are only recorded on a single device.
+pub fn profile_phase<B: Backend, T>(
+    phase: StepPhase,
+    device: &B::Device,
+    func: impl FnOnce() -> T,
+) -> T {
+    if !is_profiling() {
+        return func();
+    }
+
+    let start = Instant::now();
+    let output = func();
+    B::sync(device);
+    record(phase, start.elapsed());
+
+    output
+}
+
+/// Measures the phases of the training steps.
+#[derive(Clone)]
+pub(crate) struct StepProfiler {
+    sync: Arc<dyn Fn() + Send + Sync>,
+}
+
+impl StepProfiler {
+    /// Create a profiler synchronizing the given devices after each measured phase.
+    pub(crate) fn new<B: Backend>(devices: Vec<B::Device>) -> Self {
+        Self {
+            sync: Arc::new(move || devices.iter().for_each(B::sync)),
+        }
+    }
+
+    /// Starts profiling the steps on the current thread.
+    pub(crate) fn start(&self) {
+        PROFILE.with(|profile| *profile.borrow_mut() = Some(StepProfile::default()));
+    }
+
+    /// Stops profiling the steps on the current thread.
+    pub(crate) fn stop(&self) {
+        PROFILE.with(|profile| *profile.borrow_mut() = None);
+    }
+
+    /// Measures a phase of the current step.
+    pub(crate) fn measure<T>(&self, phase: StepPhase, func: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let output = func();
+        (self.sync)();
+        record(phase, start.elapsed());
+
+        output
+    }
+
+    /// Returns the profile of the current step, starting the profile of the next one.
+    pub(crate) fn end_step(&self) -> StepProfile {
+        PROFILE
+            .with(|profile| profile.borrow_mut().replace(StepProfile::default()))
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+
+    #[test]
+    fn profiler_should_record_the_phases_of_each_step() {
+        let profiler = StepProfiler::new::<TestBackend>(vec![Default::default()]);
+        let device = Default::default();
+
+        profiler.start();
+        profiler.measure(StepPhase::DataLoading, || {
+            std::thread::sleep(Duration::from_millis(2))
+        });
+        profile_phase::<TestBackend, _>(StepPhase::Forward, &device, || ());
+        profile_phase::<TestBackend, _>(StepPhase::Forward, &device, || ());
+        let first = profiler.end_step();
+        let second = profiler.end_step();
+        profiler.stop();
+
+        assert!(first.duration(StepPhase::DataLoading).unwrap() >= Duration::from_millis(2));
+        assert!(first.duration(StepPhase::Forward).is_some());
+        assert_eq!(first.duration(StepPhase::Optimizer), None);
+        assert_eq!(second, StepProfile::default());
+        assert!(!is_profiling());
+    }
+}
@@ -0,0 +1,28 @@
+use crate::metric::{Adaptor, IouInput, LossInput};
+use burn_core::tensor::backend::Backend;
+use burn_core::tensor::{Int, Tensor};
+
+/// Semantic segmentation output adapted for multiple metrics.
+#[derive(new)]
+pub struct SegmentationOutput<B: Backend> {
+    /// The loss.
+    pub loss: Tensor<B, 1>,
+
+    /// The output, with shape `[batch_size, num_classes, height, width]`.
+    pub output: Tensor<B, 4>,
+
+    /// The targets, with shape `[batch_size, height, width]`.
+    pub targets: Tensor<B, 3, Int>,
+}
+
+impl<B: Backend> Adaptor<IouInput<B>> for SegmentationOutput<B> {
+    fn adapt(&self) -> IouInput<B> {
+        IouInput::new(self.output.clone(), self.targets.clone())
+    }
+}
+
+impl<B: Backend> Adaptor<LossInput<B>> for SegmentationOutput<B> {
+    fn adapt(&self) -> LossInput<B> {
+        LossInput::new(self.loss.clone())
+    }
+}
@@ -1,10 +1,16 @@
+use crate::checkpoint::TrainingState;
 use crate::components::LearnerComponents;
+use crate::distributed::{broadcast_flag, broadcast_module, synchronize_stop, Collective};
+use crate::learner::base::{LearnerCheckpointer, TrainingInterrupter};
 use crate::metric::processor::EventProcessor;
-use crate::{Learner, TrainEpoch, ValidEpoch};
+use crate::metric::store::EventStoreClient;
+use crate::{
+    Curriculum, EarlyStoppingStrategy, Learner, StepHook, StepState, TrainEpoch, ValidEpoch,
+};
 use burn_core::data::dataloader::DataLoader;
 use burn_core::module::{AutodiffModule, Module};
 use burn_core::optim::{GradientsParams, Optimizer};
-use burn_core::tensor::backend::AutodiffBackend;
+use burn_core::tensor::backend::{AutodiffBackend, Backend};
 use std::sync::Arc;
 
 /// A training output.
@@ -109,7 +115,7 @@ impl<LC: LearnerComponents> Learner<LC> {
     ///
     /// The fitted model.
     pub fn fit<InputTrain, InputValid, OutputTrain, OutputValid>(
-        mut self,
+        self,
         dataloader_train: Arc<dyn DataLoader<InputTrain>>,
         dataloader_valid: Arc<dyn DataLoader<InputValid>>,
     ) -> LC::Model
@@ -121,6 +127,38 @@ impl<LC: LearnerComponents> Learner<LC> {
         LC::Model: TrainStep<InputTrain, OutputTrain>,
         <LC::Model as AutodiffModule<LC::Backend>>::InnerModule: ValidStep<InputValid, OutputValid>,
         LC::EventProcessor: EventProcessor<ItemTrain = OutputTrain, ItemValid = OutputValid>,
+    {
+        self.fit_curriculum::<_, InputTrain, InputValid, OutputTrain, OutputValid>(
+            move |_: usize| dataloader_train.clone(),
+            dataloader_valid,
+        )
+    }
+
+    /// Fits the model with a training dataloader changing between the epochs.
+    ///
+    /// # Arguments
+    ///
+    /// * `curriculum` - The [curriculum](Curriculum) providing the training dataloader of each
+    ///   epoch.
+    /// * `dataloader_valid` - The validation dataloader.
+    ///
+    /// # Returns
+    ///
+    /// The fitted model.
+    pub fn fit_curriculum<C, InputTrain, InputValid, OutputTrain, OutputValid>(
+        mut self,
+        mut curriculum: C,
+        dataloader_valid: Arc<dyn DataLoader<InputValid>>,
+    ) -> LC::Model
+    where
+        C: Curriculum<InputTrain>,
+        InputTrain: Send + 'static,
+        InputValid: Send,
+        OutputTrain: Send + 'static,
+        OutputValid: Send,
+        LC::Model: TrainStep<InputTrain, OutputTrain>,
+        <LC::Model as AutodiffModule<LC::Backend>>::InnerModule: ValidStep<InputValid, OutputValid>,
+        LC::EventProcessor: EventProcessor<ItemTrain = OutputTrain, ItemValid = OutputValid>,
     {
         log::info!("Fitting the model:\n {}", self.model.to_string());
         // The reference model is always on the first device provided.
@@ -128,29 +166,144 @@ impl<LC: LearnerComponents> Learner<LC> {
             self.model = self.model.fork(device);
         }
 
-        let starting_epoch = match self.checkpoint {
+        let state = match self.checkpoint {
             Some(checkpoint) => {
+                let mut state = None;
                 if let Some(checkpointer) = &mut self.checkpointer {
-                    (self.model, self.optim, self.lr_scheduler) = checkpointer.load_checkpoint(
-                        self.model,
-                        self.optim,
-                        self.lr_scheduler,
-                        &Default::default(), // Load the checkpoint on the default device.
-                        checkpoint,
-                    );
+                    (self.model, self.optim, self.lr_scheduler, state) = checkpointer
+                        .load_checkpoint(
+                            self.model,
+                            self.optim,
+                            self.lr_scheduler,
+                            &Default::default(), // Load the checkpoint on the default device.
+                            checkpoint,
+                        );
                 }
-                checkpoint + 1
+                // Without training state, the checkpoint is saved at the end of an epoch.
+                state.unwrap_or(TrainingState {
+                    epoch: checkpoint + 1,
+                    ..Default::default()
+                })
             }
-            None => 1,
+            None => TrainingState::default(),
         };
 
-        for epoch in starting_epoch..self.num_epochs + 1 {
+        if let Some(non_finite) = &mut self.non_finite {
+            non_finite.set_lr_factor(state.lr_factor);
+        }
+        // The curriculum goes through the previous epochs of a resumed training.
+        for epoch in 1..state.epoch {
+            let dataloader_train = curriculum.dataloader(epoch);
+            if self.restore_data_position {
+                // Each iterator advances the random state of a shuffling dataloader.
+                drop(dataloader_train.iter());
+            }
+        }
+
+        if let Some(collective) = &mut self.collective {
+            // All the processes start from the model of the main process.
+            self.model = broadcast_module(collective.as_mut(), self.model);
+        }
+
+        self.callbacks.epoch_total = self.num_epochs;
+        if self.callbacks.on_train_start(&mut self.model).stop {
+            self.interrupter.stop();
+        }
+
+        // The number of training steps and of validations, when validating every given number
+        // of steps.
+        let mut num_steps = state.num_steps;
+        let mut num_rounds = state.num_rounds;
+
+        for epoch in state.epoch..self.num_epochs + 1 {
+            // The processes of a distributed training stop together.
+            if synchronize_stop(&mut self.collective, &self.interrupter) {
+                break;
+            }
+
+            if let Some(seed) = self.seed {
+                LC::Backend::seed(seed.wrapping_add(epoch as u64));
+            }
+
+            let dataloader_train = curriculum.dataloader(epoch);
+
+            self.callbacks.epoch = epoch;
+            let control = self.callbacks.on_epoch_start(&mut self.model);
+            if control.stop {
+                self.interrupter.stop();
+            }
+            if synchronize_stop(&mut self.collective, &self.interrupter) {
+                break;
+            }
+            if control.skip_epoch {
+                log::info!("Skipping epoch {}", epoch);
+                continue;
+            }
+
             let epoch_train = TrainEpoch::new(
-                dataloader_train.clone(),
+                dataloader_train,
                 epoch,
                 self.num_epochs,
                 self.grad_accumulation,
             );
+            let epoch_train = epoch_train
+                .profile(self.profiler.clone())
+                .monitor_norms(self.norms.clone())
+                .lr_stepping(self.lr_stepping);
+            let epoch_train = if epoch == state.epoch {
+                epoch_train.skip(state.iteration)
+            } else {
+                epoch_train
+            };
+            let mut evaluation = Evaluation::<LC, InputValid> {
+                dataloader: dataloader_valid.clone(),
+                checkpointer: &mut self.checkpointer,
+                early_stopping: &mut self.early_stopping,
+                store: &self.event_store,
+                interrupter: &self.interrupter,
+            };
+
+            let interval = self.validation_interval;
+            let mut validate_steps = |step: StepState<'_, LC>| {
+                num_steps += 1;
+                if interval.map_or(true, |interval| num_steps % interval != 0) {
+                    return false;
+                }
+
+                num_rounds += 1;
+                let is_main_process = step
+                    .collective
+                    .as_ref()
+                    .map_or(true, |collective| collective.is_main_process());
+                if !is_main_process {
+                    if evaluation.follow(step.collective) {
+                        evaluation.interrupter.stop();
+                    }
+                    return false;
+                }
+
+                let state = TrainingState {
+                    epoch,
+                    iteration: step.iteration,
+                    num_steps,
+                    num_rounds,
+                    lr_factor: step.lr_factor,
+                };
+                let outcome = evaluation.run::<OutputValid>(
+                    step.model,
+                    step.optim,
+                    step.scheduler,
+                    step.processor,
+                    step.collective,
+                    (num_rounds, num_rounds),
+                    &state,
+                );
+                if outcome.stop {
+                    evaluation.interrupter.stop();
+                }
+                outcome.saved
+            };
+            let step_hook = interval.map(|_| &mut validate_steps as &mut StepHook<'_, LC>);
 
             if self.devices.len() > 1 {
                 (self.model, self.optim) = epoch_train.run_multi_device::<LC, OutputTrain>(
@@ -158,6 +311,10 @@ impl<LC: LearnerComponents> Learner<LC> {
                     self.optim,
                     &mut self.lr_scheduler,
                     &mut self.event_processor,
+                    &mut self.callbacks,
+                    &mut self.collective,
+                    &mut self.non_finite,
+                    step_hook,
                     self.devices.clone(),
                     &self.interrupter,
                 )
@@ -167,38 +324,67 @@ impl<LC: LearnerComponents> Learner<LC> {
                     self.optim,
                     &mut self.lr_scheduler,
                     &mut self.event_processor,
+                    &mut self.callbacks,
+                    &mut self.collective,
+                    &mut self.non_finite,
+                    step_hook,
                     &self.interrupter,
                 );
             }
 
-            if self.interrupter.should_stop() {
+            if synchronize_stop(&mut self.collective, &self.interrupter) {
                 break;
             }
 
-            let epoch_valid = ValidEpoch::new(dataloader_valid.clone(), epoch, self.num_epochs);
-            epoch_valid.run::<LC, OutputValid>(
-                &self.model,
-                &mut self.event_processor,
-                &self.interrupter,
-            );
-
-            if let Some(checkpointer) = &mut self.checkpointer {
-                checkpointer.checkpoint(
+            // With step validation, the model is already validated during the epoch.
+            let outcome = if interval.is_none() {
+                let state = TrainingState {
+                    epoch: epoch + 1,
+                    iteration: 0,
+                    num_steps,
+                    num_rounds,
+                    lr_factor: self
+                        .non_finite
+                        .as_ref()
+                        .map_or(1.0, |guard| guard.lr_factor()),
+                };
+                evaluation.run::<OutputValid>(
                     &self.model,
                     &self.optim,
                     &self.lr_scheduler,
-                    epoch,
-                    &self.event_store,
-                );
+                    &mut self.event_processor,
+                    &mut self.collective,
+                    (epoch, self.num_epochs),
+                    &state,
+                )
+            } else {
+                EvaluationOutcome::default()
+            };
+
+            if outcome.saved && self.callbacks.on_checkpoint(&mut self.model).stop {
+                self.interrupter.stop();
             }
 
-            if let Some(early_stopping) = &mut self.early_stopping {
-                if early_stopping.should_stop(epoch, &self.event_store) {
-                    break;
-                }
+            // The stop is synchronized at the start of the next epoch.
+            if self.callbacks.on_epoch_end(&mut self.model).stop {
+                self.interrupter.stop();
+            }
+
+            if outcome.stop {
+                break;
             }
         }
 
+        let restore_epoch = self
+            .early_stopping
+            .as_ref()
+            .and_then(|early_stopping| early_stopping.restore_epoch());
+        if let Some(epoch) = restore_epoch {
+            self.restore_best_model(epoch);
+        }
+
+        self.callbacks.on_train_end(&mut self.model);
+
         // Display learner summary
         if let Some(summary) = self.summary {
             match summary.init() {
@@ -214,4 +400,92 @@ impl<LC: LearnerComponents> Learner<LC> {
 
         self.model
     }
+
+    fn restore_best_model(&mut self, epoch: usize) {
+        let Some(checkpointer) = &self.checkpointer else {
+            log::warn!("Can't restore the best model of epoch {epoch} without a checkpointer.");
+            return;
+        };
+
+        let device = self.devices.first().cloned().unwrap_or_default();
+        match checkpointer.restore_model(self.model.clone(), &device, epoch) {
+            Ok(model) => {
+                log::info!("Restored the best model of epoch {epoch}.");
+                self.model = model;
+            }
+            Err(err) => log::warn!("Can't restore the best model of epoch {epoch}: {err:?}"),
+        }
+    }
+}
+
+/// Validates the model, then applies the checkpointing and the early stopping strategies, at the
+/// end of the epochs or every given number of training steps.
+struct Evaluation<'a, LC: LearnerComponents, VI> {
+    dataloader: Arc<dyn DataLoader<VI>>,
+    checkpointer: &'a mut Option<LearnerCheckpointer<LC>>,
+    early_stopping: &'a mut Option<Box<dyn EarlyStoppingStrategy>>,
+    store: &'a EventStoreClient,
+    interrupter: &'a TrainingInterrupter,
+}
+
+#[derive(Default)]
+struct EvaluationOutcome {
+    /// If a checkpoint is saved.
+    saved: bool,
+    /// If the training should stop.
+    stop: bool,
+}
+
+impl<LC: LearnerComponents, VI> Evaluation<'_, LC, VI> {
+    /// Evaluates the model for the given epoch, or validation round, and the total number of
+    /// epochs, saving the training state with the checkpoint.
+    #[allow(clippy::too_many_arguments)]
+    fn run<VO>(
+        &mut self,
+        model: &LC::Model,
+        optim: &LC::Optimizer,
+        scheduler: &LC::LrScheduler,
+        processor: &mut LC::EventProcessor,
+        collective: &mut Option<Box<dyn Collective>>,
+        (epoch, epoch_total): (usize, usize),
+        state: &TrainingState,
+    ) -> EvaluationOutcome
+    where
+        LC::EventProcessor: EventProcessor<ItemValid = VO>,
+        <LC::Model as AutodiffModule<LC::Backend>>::InnerModule: ValidStep<VI, VO>,
+    {
+        let epoch_valid = ValidEpoch::new(self.dataloader.clone(), epoch, epoch_total);
+        epoch_valid.run::<LC, VO>(model, processor, self.interrupter);
+
+        let mut outcome = EvaluationOutcome::default();
+        let is_main_process = collective
+            .as_ref()
+            .map_or(true, |collective| collective.is_main_process());
+
+        // Only the main process saves the checkpoints, the models being the same.
+        let checkpointer = self.checkpointer.as_mut().filter(|_| is_main_process);
+        if let Some(checkpointer) = checkpointer {
+            outcome.saved =
+                checkpointer.checkpoint(model, optim, scheduler, state, epoch, self.store);
+        }
+
+        if let Some(early_stopping) = self.early_stopping.as_mut() {
+            outcome.stop = early_stopping.should_stop(epoch, self.store);
+            if let Some(collective) = collective {
+                // The processes stop together, following the main process.
+                outcome.stop = broadcast_flag(collective.as_mut(), outcome.stop);
+            }
+        }
+
+        outcome
+    }
+
+    /// Follows the early stopping decision of the main process, the other processes not
+    /// validating the model during the epochs.
+    fn follow(&mut self, collective: &mut Option<Box<dyn Collective>>) -> bool {
+        match (self.early_stopping.as_ref(), collective) {
+            (Some(_), Some(collective)) => broadcast_flag(collective.as_mut(), false),
+            _ => false,
+        }
+    }
 }
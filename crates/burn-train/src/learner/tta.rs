@@ -0,0 +1,184 @@
+use crate::{ClassificationOutput, MultiLabelClassificationOutput, RegressionOutput, ValidStep};
+use burn_core::tensor::backend::Backend;
+use burn_core::tensor::module::interpolate;
+use burn_core::tensor::ops::{InterpolateMode, InterpolateOptions};
+use burn_core::tensor::Tensor;
+
+type AugmentationFn<I> = Box<dyn Fn(&I) -> I>;
+
+/// Test-time augmentation, running the [valid step](ValidStep) on augmented versions of each
+/// batch and merging the outputs, used by the
+/// [learner evaluation](crate::Learner::evaluate_with_augmentation).
+///
+/// The original batch is always evaluated, followed by each augmentation. The merged output is
+/// then given to the metrics like the output of a single step.
+pub struct TestTimeAugmentation<I, O> {
+    augmentations: Vec<AugmentationFn<I>>,
+    merge: Box<dyn Fn(Vec<O>) -> O>,
+}
+
+impl<I, O> TestTimeAugmentation<I, O> {
+    /// Create the test-time augmentation with the function merging the outputs, such as
+    /// [ClassificationOutput::merge].
+    pub fn new<F>(merge: F) -> Self
+    where
+        F: Fn(Vec<O>) -> O + 'static,
+    {
+        Self {
+            augmentations: Vec::new(),
+            merge: Box::new(merge),
+        }
+    }
+
+    /// Add an augmentation of the batches, for instance with [horizontal_flip].
+    pub fn with_augmentation<F>(mut self, augmentation: F) -> Self
+    where
+        F: Fn(&I) -> I + 'static,
+    {
+        self.augmentations.push(Box::new(augmentation));
+        self
+    }
+
+    /// Runs the valid step on the batch and its augmentations, returning the merged output.
+    pub(crate) fn step<M: ValidStep<I, O>>(&self, model: &M, item: I) -> O {
+        let mut outputs = Vec::with_capacity(self.augmentations.len() + 1);
+
+        for augmentation in self.augmentations.iter() {
+            outputs.push(model.step(augmentation(&item)));
+        }
+        outputs.insert(0, model.step(item));
+
+        (self.merge)(outputs)
+    }
+}
+
+/// Flips a batch of images of shape `[batch_size, channels, height, width]` horizontally.
+pub fn horizontal_flip<B: Backend>(images: Tensor<B, 4>) -> Tensor<B, 4> {
+    images.flip([3])
+}
+
+/// Flips a batch of images of shape `[batch_size, channels, height, width]` vertically.
+pub fn vertical_flip<B: Backend>(images: Tensor<B, 4>) -> Tensor<B, 4> {
+    images.flip([2])
+}
+
+/// Crops a batch of images of shape `[batch_size, channels, height, width]` at the given
+/// position, then resizes the crops to the size of the images, so the model sees a zoomed view
+/// with the usual input size.
+pub fn crop_resized<B: Backend>(
+    images: Tensor<B, 4>,
+    [top, left]: [usize; 2],
+    [height, width]: [usize; 2],
+) -> Tensor<B, 4> {
+    let [batch_size, channels, image_height, image_width] = images.dims();
+    assert!(
+        top + height <= image_height && left + width <= image_width,
+        "The crop should be inside the images"
+    );
+
+    let crops = images.slice([
+        0..batch_size,
+        0..channels,
+        top..top + height,
+        left..left + width,
+    ]);
+
+    interpolate(
+        crops,
+        [image_height, image_width],
+        InterpolateOptions::new(InterpolateMode::Bilinear),
+    )
+}
+
+/// Averages the tensors element-wise.
+fn mean<B: Backend, const D: usize>(tensors: Vec<Tensor<B, D>>) -> Tensor<B, D> {
+    let count = tensors.len();
+    let sum = tensors
+        .into_iter()
+        .reduce(|sum, tensor| sum + tensor)
+        .expect("At least one output to merge");
+
+    sum.div_scalar(count as f64)
+}
+
+impl<B: Backend> ClassificationOutput<B> {
+    /// Merges the outputs of the augmentations of a batch, averaging the logits and the losses.
+    pub fn merge(outputs: Vec<Self>) -> Self {
+        let targets = outputs[0].targets.clone();
+        let (loss, output) = outputs
+            .into_iter()
+            .map(|output| (output.loss, output.output))
+            .unzip();
+
+        Self::new(mean(loss), mean(output), targets)
+    }
+}
+
+impl<B: Backend> MultiLabelClassificationOutput<B> {
+    /// Merges the outputs of the augmentations of a batch, averaging the logits and the losses.
+    pub fn merge(outputs: Vec<Self>) -> Self {
+        let targets = outputs[0].targets.clone();
+        let (loss, output) = outputs
+            .into_iter()
+            .map(|output| (output.loss, output.output))
+            .unzip();
+
+        Self::new(mean(loss), mean(output), targets)
+    }
+}
+
+impl<B: Backend> RegressionOutput<B> {
+    /// Merges the outputs of the augmentations of a batch, averaging the predictions and the
+    /// losses.
+    pub fn merge(outputs: Vec<Self>) -> Self {
+        let targets = outputs[0].targets.clone();
+        let (loss, output) = outputs
+            .into_iter()
+            .map(|output| (output.loss, output.output))
+            .unzip();
+
+        Self::new(mean(loss), mean(output), targets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use burn_core::tensor::{Int, TensorData};
+
+    struct Model;
+
+    impl ValidStep<Tensor<TestBackend, 4>, ClassificationOutput<TestBackend>> for Model {
+        fn step(&self, images: Tensor<TestBackend, 4>) -> ClassificationOutput<TestBackend> {
+            let device = images.device();
+            // The logits are the pixels of the first row.
+            let output = images.slice([0..1, 0..1, 0..1, 0..2]).reshape([1, 2]);
+
+            ClassificationOutput::new(
+                Tensor::zeros([1], &device),
+                output,
+                Tensor::<TestBackend, 1, Int>::zeros([1], &device),
+            )
+        }
+    }
+
+    #[test]
+    fn tta_should_average_the_outputs_of_the_augmentations() {
+        let images = Tensor::<TestBackend, 4>::from_floats(
+            [[[[1.0, 3.0], [5.0, 7.0]]]],
+            &Default::default(),
+        );
+        let tta = TestTimeAugmentation::new(ClassificationOutput::merge)
+            .with_augmentation(|images: &Tensor<TestBackend, 4>| horizontal_flip(images.clone()))
+            .with_augmentation(|images: &Tensor<TestBackend, 4>| vertical_flip(images.clone()));
+
+        let output = tta.step(&Model, images);
+
+        // Mean of [1, 3], [3, 1] and [5, 7].
+        output
+            .output
+            .into_data()
+            .assert_approx_eq(&TensorData::from([[3.0f32, 11.0 / 3.0]]), 3);
+    }
+}
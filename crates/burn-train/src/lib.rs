@@ -11,6 +11,9 @@ pub mod checkpoint;
 
 pub(crate) mod components;
 
+/// Distributed training over multiple processes.
+pub mod distributed;
+
 /// Renderer modules to display metrics and training information.
 pub mod renderer;
 
@@ -20,6 +23,9 @@ pub mod logger;
 /// The metric module.
 pub mod metric;
 
+/// Hyperparameter search over multiple training runs.
+pub mod tuner;
+
 mod learner;
 
 pub use learner::*;
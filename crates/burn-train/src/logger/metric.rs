@@ -17,6 +17,19 @@ pub trait MetricLogger: Send {
     /// * `item` - The item.
     fn log(&mut self, item: &MetricEntry);
 
+    /// Logs all the items of a training or validation step.
+    ///
+    /// Loggers can rely on the default implementation, which logs the items one at a time.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - The items.
+    fn log_step(&mut self, items: &[&MetricEntry]) {
+        for item in items {
+            self.log(item);
+        }
+    }
+
     /// Logs an epoch.
     ///
     /// # Arguments
@@ -0,0 +1,424 @@
+use super::{InMemoryMetricLogger, MetricLogger};
+use crate::metric::{MetricEntry, NumericEntry};
+use burn_core::config::Config;
+use reqwest::{Client, RequestBuilder};
+use serde_json::{json, Value};
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::runtime::Runtime;
+
+/// The maximum number of metrics sent in one request, as limited by MLflow.
+const MAX_METRICS_PER_BATCH: usize = 1000;
+/// The maximum number of params sent in one request, as limited by MLflow.
+const MAX_PARAMS_PER_BATCH: usize = 100;
+
+/// The error type for the MLflow tracking client.
+#[derive(Debug)]
+pub enum MlflowError {
+    /// The request couldn't be sent.
+    Http(reqwest::Error),
+
+    /// The tracking server answered with an error.
+    Api {
+        /// The HTTP status code.
+        status: u16,
+        /// The body of the response.
+        message: String,
+    },
+
+    /// The response couldn't be parsed.
+    Json(serde_json::Error),
+
+    /// IO error.
+    IOError(std::io::Error),
+
+    /// Other errors.
+    Unknown(String),
+}
+
+impl core::fmt::Display for MlflowError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Http(err) => write!(f, "MLflow request failed: {err}"),
+            Self::Api { status, message } => write!(f, "MLflow error {status}: {message}"),
+            Self::Json(err) => write!(f, "Invalid MLflow response: {err}"),
+            Self::IOError(err) => write!(f, "IO error: {err}"),
+            Self::Unknown(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl From<reqwest::Error> for MlflowError {
+    fn from(err: reqwest::Error) -> Self {
+        Self::Http(err)
+    }
+}
+
+impl From<serde_json::Error> for MlflowError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+/// A run on an [MLflow](https://mlflow.org) tracking server, accessed through its REST API.
+///
+/// The run can be shared between the [metric loggers](MlflowRun::metric_logger) given to the
+/// learner and the [checkpointer](crate::checkpoint::MlflowCheckpointer), so the metrics and
+/// checkpoints of a training end up in the same run.
+#[derive(Clone)]
+pub struct MlflowRun {
+    inner: Arc<MlflowRunInner>,
+}
+
+struct MlflowRunInner {
+    client: MlflowClient,
+    run_id: String,
+    artifact_uri: String,
+}
+
+impl MlflowRun {
+    /// Start a new run in the given experiment, which is created if it doesn't exist yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `tracking_uri` - The address of the tracking server (e.g. `http://localhost:5000`).
+    /// * `experiment_name` - The name of the experiment.
+    /// * `run_name` - The name of the run, a random one is chosen by MLflow if not provided.
+    ///
+    /// # Returns
+    ///
+    /// The run.
+    pub fn start(
+        tracking_uri: &str,
+        experiment_name: &str,
+        run_name: Option<&str>,
+    ) -> Result<Self, MlflowError> {
+        let client = MlflowClient::new(tracking_uri)?;
+
+        let experiment_id = match client.get(
+            "mlflow/experiments/get-by-name",
+            &[("experiment_name", experiment_name)],
+        ) {
+            Ok(response) => response["experiment"]["experiment_id"].clone(),
+            Err(MlflowError::Api { status: 404, .. }) => client.post(
+                "mlflow/experiments/create",
+                json!({ "name": experiment_name }),
+            )?["experiment_id"]
+                .clone(),
+            Err(err) => return Err(err),
+        };
+
+        let mut request = json!({
+            "experiment_id": experiment_id,
+            "start_time": timestamp(),
+        });
+        if let Some(run_name) = run_name {
+            request["run_name"] = run_name.into();
+        }
+        let response = client.post("mlflow/runs/create", request)?;
+        let info = &response["run"]["info"];
+
+        let (Some(run_id), Some(artifact_uri)) =
+            (info["run_id"].as_str(), info["artifact_uri"].as_str())
+        else {
+            return Err(MlflowError::Unknown(format!(
+                "Unexpected run creation response: {response}"
+            )));
+        };
+        log::info!("Started MLflow run {run_id}");
+
+        Ok(Self {
+            inner: Arc::new(MlflowRunInner {
+                run_id: run_id.to_string(),
+                artifact_uri: artifact_uri.to_string(),
+                client,
+            }),
+        })
+    }
+
+    /// The id of the run.
+    pub fn id(&self) -> &str {
+        &self.inner.run_id
+    }
+
+    /// Logs the fields of a configuration as params, nested fields being named with their
+    /// path (e.g. `optimizer.beta_1`).
+    pub fn log_config<C: Config>(&self, config: &C) -> Result<(), MlflowError> {
+        let mut params = Vec::new();
+        flatten_params("", serde_json::to_value(config)?, &mut params);
+
+        self.log_params(params)
+    }
+
+    /// Logs params, given as `(key, value)` pairs.
+    pub fn log_params(
+        &self,
+        params: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<(), MlflowError> {
+        let params = params
+            .into_iter()
+            .map(|(key, value)| json!({ "key": key, "value": value }))
+            .collect::<Vec<_>>();
+
+        for params in params.chunks(MAX_PARAMS_PER_BATCH) {
+            self.log_batch(json!({ "params": params }))?;
+        }
+        Ok(())
+    }
+
+    /// Uploads a file as an artifact of the run.
+    ///
+    /// This requires the tracking server to serve the artifacts, which is the default since
+    /// MLflow 2.0.
+    ///
+    /// # Arguments
+    ///
+    /// * `local_path` - The path of the file to upload.
+    /// * `artifact_dir` - The directory of the artifact in the run (e.g. `checkpoints`).
+    pub fn log_artifact(
+        &self,
+        local_path: impl AsRef<Path>,
+        artifact_dir: &str,
+    ) -> Result<(), MlflowError> {
+        let local_path = local_path.as_ref();
+        let Some(root) = self.inner.artifact_uri.strip_prefix("mlflow-artifacts:/") else {
+            return Err(MlflowError::Unknown(format!(
+                "The artifacts of the run aren't served by the tracking server: {}",
+                self.inner.artifact_uri
+            )));
+        };
+        let file_name = local_path
+            .file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_default();
+        let path = [root, artifact_dir, file_name.as_ref()]
+            .iter()
+            .map(|part| part.trim_matches('/'))
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let content = std::fs::read(local_path).map_err(MlflowError::IOError)?;
+        self.inner.client.put_artifact(&path, content)
+    }
+
+    /// Creates a metric logger sending the numeric metrics to the run, with their name prefixed
+    /// by the given split (e.g. `train/Loss`).
+    pub fn metric_logger(&self, split: &str) -> MlflowMetricLogger {
+        MlflowMetricLogger {
+            run: self.clone(),
+            split: split.to_string(),
+            steps: HashMap::new(),
+            pending: Vec::new(),
+            values: InMemoryMetricLogger::new(),
+        }
+    }
+
+    /// Marks the run as finished.
+    pub fn end(&self) -> Result<(), MlflowError> {
+        self.inner.client.post(
+            "mlflow/runs/update",
+            json!({
+                "run_id": self.id(),
+                "status": "FINISHED",
+                "end_time": timestamp(),
+            }),
+        )?;
+        Ok(())
+    }
+
+    fn log_batch(&self, mut batch: Value) -> Result<(), MlflowError> {
+        batch["run_id"] = self.id().into();
+        self.inner.client.post("mlflow/runs/log-batch", batch)?;
+        Ok(())
+    }
+}
+
+/// Metric logger sending the numeric metrics to an [MLflow run](MlflowRun).
+///
+/// Metrics are sent in batches at the end of each epoch, using the number of values already
+/// logged for the metric as the step.
+pub struct MlflowMetricLogger {
+    run: MlflowRun,
+    split: String,
+    steps: HashMap<String, usize>,
+    pending: Vec<Value>,
+    values: InMemoryMetricLogger,
+}
+
+impl MlflowMetricLogger {
+    fn flush(&mut self) {
+        for metrics in self.pending.chunks(MAX_METRICS_PER_BATCH) {
+            if let Err(err) = self.run.log_batch(json!({ "metrics": metrics })) {
+                log::warn!("Failed to send the metrics to MLflow: {err}");
+            }
+        }
+        self.pending.clear();
+    }
+}
+
+impl MetricLogger for MlflowMetricLogger {
+    fn log(&mut self, item: &MetricEntry) {
+        self.values.log(item);
+
+        let value = match NumericEntry::deserialize(&item.serialize) {
+            Ok(entry) => entry.value(),
+            Err(_) => return,
+        };
+        let step = self.steps.entry(item.name.clone()).or_default();
+        *step += 1;
+
+        self.pending.push(json!({
+            "key": format!("{}/{}", self.split, item.name),
+            "value": value,
+            "timestamp": timestamp(),
+            "step": *step,
+        }));
+
+        if self.pending.len() >= MAX_METRICS_PER_BATCH {
+            self.flush();
+        }
+    }
+
+    fn end_epoch(&mut self, epoch: usize) {
+        self.values.end_epoch(epoch);
+        self.flush();
+    }
+
+    fn read_numeric(&mut self, name: &str, epoch: usize) -> Result<Vec<NumericEntry>, String> {
+        self.values.read_numeric(name, epoch)
+    }
+}
+
+impl Drop for MlflowMetricLogger {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+struct MlflowClient {
+    api: String,
+    http: Client,
+    runtime: Runtime,
+}
+
+impl MlflowClient {
+    fn new(tracking_uri: &str) -> Result<Self, MlflowError> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(MlflowError::IOError)?;
+
+        Ok(Self {
+            api: format!("{}/api/2.0", tracking_uri.trim_end_matches('/')),
+            http: Client::new(),
+            runtime,
+        })
+    }
+
+    fn get(&self, endpoint: &str, query: &[(&str, &str)]) -> Result<Value, MlflowError> {
+        let request = self
+            .http
+            .get(format!("{}/{endpoint}", self.api))
+            .query(query);
+        self.send(request)
+    }
+
+    fn post(&self, endpoint: &str, body: Value) -> Result<Value, MlflowError> {
+        let request = self
+            .http
+            .post(format!("{}/{endpoint}", self.api))
+            .header("Content-Type", "application/json")
+            .body(body.to_string());
+        self.send(request)
+    }
+
+    fn put_artifact(&self, path: &str, content: Vec<u8>) -> Result<(), MlflowError> {
+        let request = self
+            .http
+            .put(format!("{}/mlflow-artifacts/artifacts/{path}", self.api))
+            .body(content);
+        self.send(request)?;
+        Ok(())
+    }
+
+    fn send(&self, request: RequestBuilder) -> Result<Value, MlflowError> {
+        self.runtime.block_on(async {
+            let response = request.send().await?;
+            let status = response.status();
+            let body = response.text().await?;
+
+            if !status.is_success() {
+                return Err(MlflowError::Api {
+                    status: status.as_u16(),
+                    message: body,
+                });
+            }
+
+            if body.is_empty() {
+                Ok(Value::Null)
+            } else {
+                Ok(serde_json::from_str(&body)?)
+            }
+        })
+    }
+}
+
+/// Milliseconds since the Unix epoch, as expected by MLflow.
+fn timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+fn flatten_params(key: &str, value: Value, params: &mut Vec<(String, String)>) {
+    let child_key = |child: &str| {
+        if key.is_empty() {
+            child.to_string()
+        } else {
+            format!("{key}.{child}")
+        }
+    };
+
+    match value {
+        Value::Object(fields) => {
+            for (name, value) in fields {
+                flatten_params(&child_key(&name), value, params);
+            }
+        }
+        Value::String(value) => params.push((key.to_string(), value)),
+        value => params.push((key.to_string(), value.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_flatten_nested_config_fields() {
+        let config = json!({
+            "lr": 0.01,
+            "optimizer": { "name": "adam", "beta_1": 0.9 },
+            "layers": [2, 4],
+        });
+        let mut params = Vec::new();
+        flatten_params("", config, &mut params);
+        params.sort();
+
+        assert_eq!(
+            params,
+            [
+                ("layers".to_string(), "[2,4]".to_string()),
+                ("lr".to_string(), "0.01".to_string()),
+                ("optimizer.beta_1".to_string(), "0.9".to_string()),
+                ("optimizer.name".to_string(), "adam".to_string()),
+            ]
+        );
+    }
+}
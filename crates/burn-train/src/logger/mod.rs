@@ -3,9 +3,19 @@ mod base;
 mod file;
 mod in_memory;
 mod metric;
+#[cfg(feature = "mlflow")]
+mod mlflow;
+mod structured;
+#[cfg(feature = "tensorboard")]
+mod tensorboard;
 
 pub use async_logger::*;
 pub use base::*;
 pub use file::*;
 pub use in_memory::*;
 pub use metric::*;
+#[cfg(feature = "mlflow")]
+pub use mlflow::*;
+pub use structured::*;
+#[cfg(feature = "tensorboard")]
+pub use tensorboard::*;
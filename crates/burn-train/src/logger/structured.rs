@@ -0,0 +1,226 @@
+use super::{InMemoryMetricLogger, MetricLogger};
+use crate::metric::{MetricEntry, NumericEntry};
+use serde_json::{Map, Value};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+/// The file format of the [structured metric logger](StructuredMetricLogger).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructuredFormat {
+    /// Comma separated values, with a header row.
+    Csv,
+    /// One JSON object per line.
+    JsonLines,
+}
+
+/// Metric logger writing one row per step, with the values of all the metrics of the step.
+///
+/// Each row starts with the epoch, the step in the epoch and the [tags](StructuredMetricLogger::with_tag)
+/// of the logger, followed by the metric values. Numeric metrics are written as numbers, the
+/// others as their serialized string. This layout can be loaded directly as a data frame.
+///
+/// With the [CSV](StructuredFormat::Csv) format, the columns are set by the first step, and
+/// metrics only appearing later are ignored.
+pub struct StructuredMetricLogger {
+    writer: BufWriter<File>,
+    format: StructuredFormat,
+    tags: Vec<(String, String)>,
+    columns: Option<Vec<String>>,
+    epoch: usize,
+    step: usize,
+    values: InMemoryMetricLogger,
+}
+
+impl StructuredMetricLogger {
+    /// Create a new structured metric logger.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file, which is overwritten if it exists.
+    /// * `format` - The file format.
+    ///
+    /// # Returns
+    ///
+    /// The structured metric logger.
+    pub fn new(path: impl AsRef<Path>, format: StructuredFormat) -> Self {
+        let path = path.as_ref();
+        if let Some(directory) = path.parent() {
+            std::fs::create_dir_all(directory).ok();
+        }
+        let file = File::create(path).unwrap_or_else(|err| {
+            panic!(
+                "Should be able to create the new file '{}': {}",
+                path.display(),
+                err
+            )
+        });
+
+        Self {
+            writer: BufWriter::new(file),
+            format,
+            tags: Vec::new(),
+            columns: None,
+            epoch: 1,
+            step: 0,
+            values: InMemoryMetricLogger::new(),
+        }
+    }
+
+    /// Adds a tag written in every row, such as the run name or the git commit hash.
+    pub fn with_tag(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.tags.push((name.into(), value.into()));
+        self
+    }
+
+    fn write_json(&mut self, metrics: Vec<(&str, Value)>) {
+        let mut row = Map::new();
+        row.insert("epoch".into(), self.epoch.into());
+        row.insert("step".into(), self.step.into());
+        for (name, value) in self.tags.iter() {
+            row.insert(name.clone(), value.clone().into());
+        }
+        for (name, value) in metrics {
+            row.insert(name.to_string(), value);
+        }
+
+        writeln!(self.writer, "{}", Value::Object(row)).expect("Can log a row.");
+    }
+
+    fn write_csv(&mut self, metrics: Vec<(&str, Value)>) {
+        if self.columns.is_none() {
+            let header = ["epoch", "step"]
+                .into_iter()
+                .chain(self.tags.iter().map(|(name, _)| name.as_str()))
+                .chain(metrics.iter().map(|(name, _)| *name))
+                .map(csv_field)
+                .collect::<Vec<_>>();
+            writeln!(self.writer, "{}", header.join(",")).expect("Can log a row.");
+
+            self.columns = Some(metrics.iter().map(|(name, _)| name.to_string()).collect());
+        }
+        let columns = self.columns.as_ref().expect("The columns are set.");
+
+        let row = [self.epoch.to_string(), self.step.to_string()]
+            .into_iter()
+            .chain(self.tags.iter().map(|(_, value)| csv_field(value)))
+            .chain(columns.iter().map(|column| {
+                match metrics.iter().find(|(name, _)| *name == column.as_str()) {
+                    Some((_, Value::String(value))) => csv_field(value),
+                    Some((_, value)) => value.to_string(),
+                    None => String::new(),
+                }
+            }))
+            .collect::<Vec<_>>();
+
+        writeln!(self.writer, "{}", row.join(",")).expect("Can log a row.");
+    }
+}
+
+impl MetricLogger for StructuredMetricLogger {
+    fn log(&mut self, item: &MetricEntry) {
+        self.log_step(&[item]);
+    }
+
+    fn log_step(&mut self, items: &[&MetricEntry]) {
+        self.step += 1;
+
+        let metrics = items
+            .iter()
+            .map(|item| {
+                self.values.log(item);
+
+                let value = match NumericEntry::deserialize(&item.serialize) {
+                    Ok(entry) => entry.value().into(),
+                    Err(_) => item.serialize.clone().into(),
+                };
+                (item.name.as_str(), value)
+            })
+            .collect::<Vec<_>>();
+
+        match self.format {
+            StructuredFormat::Csv => self.write_csv(metrics),
+            StructuredFormat::JsonLines => self.write_json(metrics),
+        }
+    }
+
+    fn end_epoch(&mut self, epoch: usize) {
+        self.values.end_epoch(epoch);
+        self.epoch = epoch + 1;
+        self.step = 0;
+        self.writer.flush().expect("Can flush the metrics file.");
+    }
+
+    fn read_numeric(&mut self, name: &str, epoch: usize) -> Result<Vec<NumericEntry>, String> {
+        self.values.read_numeric(name, epoch)
+    }
+}
+
+impl Drop for StructuredMetricLogger {
+    fn drop(&mut self) {
+        self.writer.flush().ok();
+    }
+}
+
+/// Quotes the field if it contains a character reserved by the CSV format.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, value: &str) -> MetricEntry {
+        MetricEntry::new(name.into(), value.into(), value.into())
+    }
+
+    fn log_epoch(logger: &mut StructuredMetricLogger) {
+        let (loss, accuracy) = (entry("Loss", "0.5"), entry("Accuracy", "80,32"));
+        logger.log_step(&[&loss, &accuracy]);
+        let (loss, accuracy) = (entry("Loss", "0.25"), entry("Accuracy", "90,32"));
+        logger.log_step(&[&loss, &accuracy]);
+        logger.end_epoch(1);
+    }
+
+    #[test]
+    fn should_write_one_csv_row_per_step() {
+        let path = std::env::temp_dir().join("burn-structured-logger-test.csv");
+        let mut logger =
+            StructuredMetricLogger::new(&path, StructuredFormat::Csv).with_tag("run", "a,b");
+        log_epoch(&mut logger);
+        drop(logger);
+
+        assert_eq!(
+            std::fs::read_to_string(path).unwrap(),
+            "epoch,step,run,Loss,Accuracy\n1,1,\"a,b\",0.5,80.0\n1,2,\"a,b\",0.25,90.0\n"
+        );
+    }
+
+    #[test]
+    fn should_write_one_json_line_per_step() {
+        let path = std::env::temp_dir().join("burn-structured-logger-test.jsonl");
+        let mut logger =
+            StructuredMetricLogger::new(&path, StructuredFormat::JsonLines).with_tag("run", "a");
+        log_epoch(&mut logger);
+        drop(logger);
+
+        let rows = std::fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1]["epoch"], 1);
+        assert_eq!(rows[1]["step"], 2);
+        assert_eq!(rows[1]["run"], "a");
+        assert_eq!(rows[1]["Loss"], 0.25);
+        assert_eq!(rows[1]["Accuracy"], 90.0);
+    }
+}
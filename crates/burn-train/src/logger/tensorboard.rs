@@ -0,0 +1,427 @@
+use super::{InMemoryMetricLogger, MetricLogger};
+use crate::metric::{MetricEntry, NumericEntry};
+use burn_core::tensor::TensorData;
+use flate2::{write::ZlibEncoder, Compression, Crc};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const NUM_HISTOGRAM_BUCKETS: usize = 30;
+
+/// Metric logger writing [TensorBoard](https://www.tensorflow.org/tensorboard) event files.
+///
+/// Numeric metrics logged by the learner are written as scalars, using the number of values
+/// already logged for the metric as the step. Histograms and images can also be written with
+/// [log_histogram](TensorBoardLogger::log_histogram) and [log_image](TensorBoardLogger::log_image).
+///
+/// The learner logs training and validation metrics separately, so two loggers writing in
+/// sibling directories (e.g. `train` and `valid`) should be used, which TensorBoard displays as
+/// two runs.
+pub struct TensorBoardLogger {
+    writer: BufWriter<File>,
+    steps: HashMap<String, usize>,
+    values: InMemoryMetricLogger,
+}
+
+impl TensorBoardLogger {
+    /// Create a new TensorBoard logger.
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory where the event file is created.
+    ///
+    /// # Returns
+    ///
+    /// The TensorBoard logger.
+    pub fn new(directory: impl AsRef<Path>) -> Self {
+        let directory = directory.as_ref();
+        std::fs::create_dir_all(directory).ok();
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        let path = directory.join(format!("events.out.tfevents.{timestamp}.burn"));
+        let file = File::create(&path).unwrap_or_else(|err| {
+            panic!(
+                "Should be able to create the new file '{}': {}",
+                path.display(),
+                err
+            )
+        });
+
+        let mut logger = Self {
+            writer: BufWriter::new(file),
+            steps: HashMap::new(),
+            values: InMemoryMetricLogger::new(),
+        };
+        let mut event = event_header(0);
+        proto::string(&mut event, 3, "brain.Event:2");
+        logger.write_record(&event);
+        logger
+    }
+
+    /// Logs a scalar.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The name of the scalar.
+    /// * `step` - The step of the value.
+    /// * `value` - The value.
+    pub fn log_scalar(&mut self, tag: &str, step: usize, value: f64) {
+        let mut summary_value = Vec::new();
+        proto::string(&mut summary_value, 1, tag);
+        proto::float(&mut summary_value, 2, value as f32);
+
+        self.write_summary(step, &summary_value);
+    }
+
+    /// Logs the distribution of the given values as a histogram.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The name of the histogram.
+    /// * `step` - The step of the values.
+    /// * `values` - The values, which can be obtained from a tensor with
+    ///   `tensor.into_data().iter::<f64>()`.
+    pub fn log_histogram(&mut self, tag: &str, step: usize, values: impl IntoIterator<Item = f64>) {
+        let values = values
+            .into_iter()
+            .filter(|value| value.is_finite())
+            .collect::<Vec<_>>();
+        if values.is_empty() {
+            return;
+        }
+
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let width = (max - min) / NUM_HISTOGRAM_BUCKETS as f64;
+        let num_buckets = if width > 0.0 {
+            NUM_HISTOGRAM_BUCKETS
+        } else {
+            1
+        };
+
+        let mut buckets = vec![0.0; num_buckets];
+        for value in values.iter() {
+            let index = if width > 0.0 {
+                (((value - min) / width) as usize).min(num_buckets - 1)
+            } else {
+                0
+            };
+            buckets[index] += 1.0;
+        }
+        let limits = (1..=num_buckets)
+            .map(|i| {
+                if i == num_buckets {
+                    max
+                } else {
+                    min + width * i as f64
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut histogram = Vec::new();
+        proto::double(&mut histogram, 1, min);
+        proto::double(&mut histogram, 2, max);
+        proto::double(&mut histogram, 3, values.len() as f64);
+        proto::double(&mut histogram, 4, values.iter().sum());
+        proto::double(&mut histogram, 5, values.iter().map(|v| v * v).sum());
+        proto::doubles(&mut histogram, 6, &limits);
+        proto::doubles(&mut histogram, 7, &buckets);
+
+        let mut summary_value = Vec::new();
+        proto::string(&mut summary_value, 1, tag);
+        proto::bytes(&mut summary_value, 5, &histogram);
+
+        self.write_summary(step, &summary_value);
+    }
+
+    /// Logs an image.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The name of the image.
+    /// * `step` - The step of the image.
+    /// * `image` - The pixels of shape `[height, width, channels]`, with 1 (grayscale), 3 (RGB)
+    ///   or 4 (RGBA) channels and values between 0 and 1.
+    ///
+    /// # Panics
+    ///
+    /// If the image doesn't have a supported shape.
+    pub fn log_image(&mut self, tag: &str, step: usize, image: &TensorData) {
+        let [height, width, channels] = image.shape[..] else {
+            panic!("The image should have 3 dimensions, got {:?}", image.shape);
+        };
+        let pixels = image
+            .iter::<f32>()
+            .map(|value| (value.clamp(0.0, 1.0) * 255.0).round() as u8)
+            .collect::<Vec<_>>();
+        let encoded = encode_png(&pixels, width, height, channels);
+
+        let mut image = Vec::new();
+        proto::varint_field(&mut image, 1, height as u64);
+        proto::varint_field(&mut image, 2, width as u64);
+        proto::varint_field(&mut image, 3, channels as u64);
+        proto::bytes(&mut image, 4, &encoded);
+
+        let mut summary_value = Vec::new();
+        proto::string(&mut summary_value, 1, tag);
+        proto::bytes(&mut summary_value, 4, &image);
+
+        self.write_summary(step, &summary_value);
+    }
+
+    fn write_summary(&mut self, step: usize, summary_value: &[u8]) {
+        let mut summary = Vec::new();
+        proto::bytes(&mut summary, 1, summary_value);
+
+        let mut event = event_header(step);
+        proto::bytes(&mut event, 5, &summary);
+        self.write_record(&event);
+    }
+
+    /// Writes the data with the TFRecord framing expected in event files.
+    fn write_record(&mut self, data: &[u8]) {
+        let length = (data.len() as u64).to_le_bytes();
+
+        let mut record = Vec::with_capacity(data.len() + 16);
+        record.extend(length);
+        record.extend(masked_crc32c(&length).to_le_bytes());
+        record.extend(data);
+        record.extend(masked_crc32c(data).to_le_bytes());
+
+        self.writer
+            .write_all(&record)
+            .expect("Can write a TensorBoard event.");
+    }
+}
+
+impl MetricLogger for TensorBoardLogger {
+    fn log(&mut self, item: &MetricEntry) {
+        self.values.log(item);
+
+        let value = match NumericEntry::deserialize(&item.serialize) {
+            Ok(entry) => entry.value(),
+            Err(_) => return,
+        };
+        let step = self.steps.entry(item.name.clone()).or_default();
+        *step += 1;
+        let step = *step;
+
+        self.log_scalar(&item.name, step, value);
+    }
+
+    fn end_epoch(&mut self, epoch: usize) {
+        self.values.end_epoch(epoch);
+        self.writer
+            .flush()
+            .expect("Can flush the TensorBoard events.");
+    }
+
+    fn read_numeric(&mut self, name: &str, epoch: usize) -> Result<Vec<NumericEntry>, String> {
+        self.values.read_numeric(name, epoch)
+    }
+}
+
+impl Drop for TensorBoardLogger {
+    fn drop(&mut self) {
+        self.writer.flush().ok();
+    }
+}
+
+fn event_header(step: usize) -> Vec<u8> {
+    let wall_time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs_f64())
+        .unwrap_or_default();
+
+    let mut event = Vec::new();
+    proto::double(&mut event, 1, wall_time);
+    proto::varint_field(&mut event, 2, step as u64);
+    event
+}
+
+/// The CRC-32C (Castagnoli) checksum, masked as done by TFRecord.
+fn masked_crc32c(data: &[u8]) -> u32 {
+    let crc = crc32c(data);
+    crc.rotate_right(15).wrapping_add(0xa282ead8)
+}
+
+fn crc32c(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = match crc & 1 {
+                1 => (crc >> 1) ^ 0x82f63b78,
+                _ => crc >> 1,
+            };
+        }
+    }
+    !crc
+}
+
+/// Encodes 8-bit pixels as an unfiltered PNG image.
+fn encode_png(pixels: &[u8], width: usize, height: usize, channels: usize) -> Vec<u8> {
+    let color_type = match channels {
+        1 => 0,
+        3 => 2,
+        4 => 6,
+        _ => panic!("The image should have 1, 3 or 4 channels, got {channels}"),
+    };
+
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    for row in pixels.chunks(width * channels).take(height) {
+        encoder
+            .write_all(&[0])
+            .and_then(|_| encoder.write_all(row))
+            .expect("Can compress the image.");
+    }
+    let compressed = encoder.finish().expect("Can compress the image.");
+
+    let mut header = Vec::new();
+    header.extend((width as u32).to_be_bytes());
+    header.extend((height as u32).to_be_bytes());
+    header.extend([8, color_type, 0, 0, 0]);
+
+    let mut png = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+    for (kind, data) in [
+        (b"IHDR", header.as_slice()),
+        (b"IDAT", compressed.as_slice()),
+        (b"IEND", [].as_slice()),
+    ] {
+        let mut crc = Crc::new();
+        crc.update(kind);
+        crc.update(data);
+
+        png.extend((data.len() as u32).to_be_bytes());
+        png.extend(kind);
+        png.extend(data);
+        png.extend(crc.sum().to_be_bytes());
+    }
+    png
+}
+
+/// Minimal protobuf encoding of the TensorBoard event messages.
+mod proto {
+    pub fn varint(buffer: &mut Vec<u8>, mut value: u64) {
+        while value >= 0x80 {
+            buffer.push((value as u8 & 0x7f) | 0x80);
+            value >>= 7;
+        }
+        buffer.push(value as u8);
+    }
+
+    fn key(buffer: &mut Vec<u8>, field: u64, wire_type: u64) {
+        varint(buffer, (field << 3) | wire_type);
+    }
+
+    pub fn varint_field(buffer: &mut Vec<u8>, field: u64, value: u64) {
+        key(buffer, field, 0);
+        varint(buffer, value);
+    }
+
+    pub fn double(buffer: &mut Vec<u8>, field: u64, value: f64) {
+        key(buffer, field, 1);
+        buffer.extend(value.to_le_bytes());
+    }
+
+    pub fn float(buffer: &mut Vec<u8>, field: u64, value: f32) {
+        key(buffer, field, 5);
+        buffer.extend(value.to_le_bytes());
+    }
+
+    pub fn bytes(buffer: &mut Vec<u8>, field: u64, value: &[u8]) {
+        key(buffer, field, 2);
+        varint(buffer, value.len() as u64);
+        buffer.extend(value);
+    }
+
+    pub fn string(buffer: &mut Vec<u8>, field: u64, value: &str) {
+        bytes(buffer, field, value.as_bytes());
+    }
+
+    /// Packed repeated doubles.
+    pub fn doubles(buffer: &mut Vec<u8>, field: u64, values: &[f64]) {
+        let packed = values
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect::<Vec<_>>();
+        bytes(buffer, field, &packed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc32c_should_match_reference() {
+        assert_eq!(crc32c(b"123456789"), 0xe3069283);
+    }
+
+    #[test]
+    fn varint_should_encode_multiple_bytes() {
+        let mut buffer = Vec::new();
+        proto::varint(&mut buffer, 300);
+        assert_eq!(buffer, [0xac, 0x02]);
+    }
+
+    #[test]
+    fn should_write_framed_records() {
+        let directory = std::env::temp_dir().join("burn-tensorboard-logger-test");
+        std::fs::remove_dir_all(&directory).ok();
+
+        let mut logger = TensorBoardLogger::new(&directory);
+        logger.log(&MetricEntry::new("Loss".into(), "0.5".into(), "0.5".into()));
+        logger.log_histogram("weights", 1, [0.0, 0.5, 1.0]);
+        logger.log_image("image", 1, &TensorData::new(vec![0.0f32, 1.0], [1, 2, 1]));
+        drop(logger);
+
+        let path = std::fs::read_dir(&directory)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path();
+        let content = std::fs::read(path).unwrap();
+
+        let mut offset = 0;
+        let mut num_records = 0;
+        while offset < content.len() {
+            let length = u64::from_le_bytes(content[offset..offset + 8].try_into().unwrap());
+            let data = &content[offset + 12..offset + 12 + length as usize];
+            let crc = &content[offset + 12 + length as usize..offset + 16 + length as usize];
+
+            assert_eq!(crc, masked_crc32c(data).to_le_bytes());
+            offset += 16 + length as usize;
+            num_records += 1;
+        }
+
+        assert_eq!(offset, content.len());
+        assert_eq!(num_records, 4);
+    }
+
+    #[test]
+    fn should_read_logged_numeric_values() {
+        let directory = std::env::temp_dir().join("burn-tensorboard-logger-read-test");
+        std::fs::remove_dir_all(&directory).ok();
+
+        let mut logger = TensorBoardLogger::new(&directory);
+        logger.log(&MetricEntry::new("Loss".into(), "0.5".into(), "0.5".into()));
+        logger.log(&MetricEntry::new(
+            "Loss".into(),
+            "0.25".into(),
+            "0.25".into(),
+        ));
+
+        let values = logger.read_numeric("Loss", 1).unwrap();
+        assert_eq!(values.len(), 2);
+        assert_eq!(logger.steps["Loss"], 2);
+    }
+}
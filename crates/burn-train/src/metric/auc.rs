@@ -0,0 +1,473 @@
+use core::marker::PhantomData;
+
+use super::{format_float, ClassificationInput, MetricEntry, MetricMetadata, NumericEntry};
+use crate::metric::{Metric, Numeric};
+use burn_core::tensor::activation::{sigmoid, softmax};
+use burn_core::tensor::backend::Backend;
+
+const DEFAULT_NUM_BUCKETS: usize = 1000;
+
+/// The area under the receiver operating characteristic curve (AUROC).
+///
+/// The metric is computed over all the items seen since the start of the epoch. To keep the
+/// memory bounded, the scores are accumulated in histograms, so scores falling in the same bucket
+/// are considered tied.
+///
+/// With a single output per item, the classification is binary and the targets are `1` for the
+/// positive items. With more outputs, the metric is computed for each class against the others,
+/// then averaged over the classes. The number of outputs can't change until the metric is cleared.
+///
+/// The outputs are expected to be logits, converted to probabilities with a sigmoid for binary
+/// classification or a softmax otherwise, unless they are
+/// [already probabilities](AurocMetric::with_probabilities).
+pub struct AurocMetric<B: Backend> {
+    histograms: ScoreHistograms,
+    current: Option<f64>,
+    _b: PhantomData<B>,
+}
+
+/// The average precision, summarizing the area under the precision-recall curve (PR-AUC).
+///
+/// The metric is accumulated over the epoch in bounded memory like the [AUROC](AurocMetric), and
+/// is more informative with strongly imbalanced classes.
+pub struct AveragePrecisionMetric<B: Backend> {
+    histograms: ScoreHistograms,
+    current: Option<f64>,
+    _b: PhantomData<B>,
+}
+
+impl<B: Backend> AurocMetric<B> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of buckets of the score histograms, trading memory for precision.
+    pub fn with_num_buckets(mut self, num_buckets: usize) -> Self {
+        self.histograms = ScoreHistograms::new(num_buckets, self.histograms.probabilities);
+        self
+    }
+
+    /// Uses the outputs as probabilities between 0 and 1 instead of logits.
+    pub fn with_probabilities(mut self) -> Self {
+        self.histograms.probabilities = true;
+        self
+    }
+}
+
+impl<B: Backend> AveragePrecisionMetric<B> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of buckets of the score histograms, trading memory for precision.
+    pub fn with_num_buckets(mut self, num_buckets: usize) -> Self {
+        self.histograms = ScoreHistograms::new(num_buckets, self.histograms.probabilities);
+        self
+    }
+
+    /// Uses the outputs as probabilities between 0 and 1 instead of logits.
+    pub fn with_probabilities(mut self) -> Self {
+        self.histograms.probabilities = true;
+        self
+    }
+}
+
+impl<B: Backend> Default for AurocMetric<B> {
+    fn default() -> Self {
+        Self {
+            histograms: ScoreHistograms::new(DEFAULT_NUM_BUCKETS, false),
+            current: None,
+            _b: PhantomData,
+        }
+    }
+}
+
+impl<B: Backend> Default for AveragePrecisionMetric<B> {
+    fn default() -> Self {
+        Self {
+            histograms: ScoreHistograms::new(DEFAULT_NUM_BUCKETS, false),
+            current: None,
+            _b: PhantomData,
+        }
+    }
+}
+
+impl<B: Backend> Metric for AurocMetric<B> {
+    const NAME: &'static str = "AUROC";
+
+    type Input = ClassificationInput<B>;
+
+    fn update(
+        &mut self,
+        input: &ClassificationInput<B>,
+        _metadata: &MetricMetadata,
+    ) -> MetricEntry {
+        self.histograms.update(input);
+        self.current = self.histograms.average(ScoreHistogram::auroc);
+
+        entry(Self::NAME, self.current)
+    }
+
+    fn clear(&mut self) {
+        self.histograms.reset();
+        self.current = None;
+    }
+}
+
+impl<B: Backend> Metric for AveragePrecisionMetric<B> {
+    const NAME: &'static str = "Average Precision";
+
+    type Input = ClassificationInput<B>;
+
+    fn update(
+        &mut self,
+        input: &ClassificationInput<B>,
+        _metadata: &MetricMetadata,
+    ) -> MetricEntry {
+        self.histograms.update(input);
+        self.current = self.histograms.average(ScoreHistogram::average_precision);
+
+        entry(Self::NAME, self.current)
+    }
+
+    fn clear(&mut self) {
+        self.histograms.reset();
+        self.current = None;
+    }
+}
+
+impl<B: Backend> Numeric for AurocMetric<B> {
+    fn value(&self) -> f64 {
+        self.current.unwrap_or(f64::NAN)
+    }
+
+    fn try_value(&self) -> Option<f64> {
+        self.current
+    }
+}
+
+impl<B: Backend> Numeric for AveragePrecisionMetric<B> {
+    fn value(&self) -> f64 {
+        self.current.unwrap_or(f64::NAN)
+    }
+
+    fn try_value(&self) -> Option<f64> {
+        self.current
+    }
+}
+
+/// The entry of the metric, without value until a class has both positive and negative items.
+fn entry(name: &str, value: Option<f64>) -> MetricEntry {
+    let Some(value) = value else {
+        let formatted = "no positive and negative items".to_string();
+        return MetricEntry::new(name.to_string(), formatted.clone(), formatted);
+    };
+
+    MetricEntry::new(
+        name.to_string(),
+        format!("epoch {}", format_float(value, 4)),
+        NumericEntry::Epoch(value).serialize(),
+    )
+}
+
+/// The score histograms of each class.
+struct ScoreHistograms {
+    num_buckets: usize,
+    probabilities: bool,
+    classes: Vec<ScoreHistogram>,
+}
+
+impl ScoreHistograms {
+    fn new(num_buckets: usize, probabilities: bool) -> Self {
+        assert!(num_buckets > 0, "The number of buckets must be at least 1");
+
+        Self {
+            num_buckets,
+            probabilities,
+            classes: Vec::new(),
+        }
+    }
+
+    fn reset(&mut self) {
+        self.classes.clear();
+    }
+
+    fn update<B: Backend>(&mut self, input: &ClassificationInput<B>) {
+        let [_batch_size, num_outputs] = input.outputs.dims();
+        if self.classes.is_empty() {
+            self.classes = (0..num_outputs)
+                .map(|_| ScoreHistogram::new(self.num_buckets))
+                .collect();
+        }
+        assert_eq!(
+            self.classes.len(),
+            num_outputs,
+            "The number of outputs changed during the epoch"
+        );
+
+        let scores = match (self.probabilities, num_outputs) {
+            (true, _) => input.outputs.clone(),
+            (false, 1) => sigmoid(input.outputs.clone()),
+            (false, _) => softmax(input.outputs.clone(), 1),
+        };
+        let scores = scores.into_data().iter::<f64>().collect::<Vec<_>>();
+        if self.probabilities {
+            assert!(
+                scores.iter().all(|score| (0.0..=1.0).contains(score)),
+                "The scores should be probabilities between 0 and 1"
+            );
+        }
+        let targets = input.targets.clone().into_data();
+
+        for (scores, target) in scores.chunks(num_outputs).zip(targets.iter::<i64>()) {
+            if num_outputs == 1 {
+                self.classes[0].add(scores[0], target == 1);
+                continue;
+            }
+
+            for (class, (histogram, score)) in self.classes.iter_mut().zip(scores).enumerate() {
+                histogram.add(*score, target == class as i64);
+            }
+        }
+    }
+
+    /// Averages the metric over the classes having both positive and negative items.
+    fn average(&self, metric: fn(&ScoreHistogram) -> f64) -> Option<f64> {
+        let values = self
+            .classes
+            .iter()
+            .map(metric)
+            .filter(|value| !value.is_nan())
+            .collect::<Vec<_>>();
+
+        if values.is_empty() {
+            return None;
+        }
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+/// Number of positive and negative items for each score bucket, from 0 to 1.
+struct ScoreHistogram {
+    positives: Vec<u64>,
+    negatives: Vec<u64>,
+}
+
+impl ScoreHistogram {
+    fn new(num_buckets: usize) -> Self {
+        Self {
+            positives: vec![0; num_buckets],
+            negatives: vec![0; num_buckets],
+        }
+    }
+
+    fn add(&mut self, score: f64, positive: bool) {
+        let num_buckets = self.positives.len();
+        let bucket = ((score.clamp(0.0, 1.0) * num_buckets as f64) as usize).min(num_buckets - 1);
+
+        if positive {
+            self.positives[bucket] += 1;
+        } else {
+            self.negatives[bucket] += 1;
+        }
+    }
+
+    /// Iterates over the buckets from the highest scores, with the number of positive and
+    /// negative items in the bucket and in the buckets with higher scores.
+    fn buckets_descending(&self) -> impl Iterator<Item = (f64, f64, f64, f64)> + '_ {
+        self.positives.iter().zip(self.negatives.iter()).rev().scan(
+            (0.0, 0.0),
+            |(positives_above, negatives_above), (pos, neg)| {
+                let (pos, neg) = (*pos as f64, *neg as f64);
+                let bucket = (pos, neg, *positives_above, *negatives_above);
+                *positives_above += pos;
+                *negatives_above += neg;
+                Some(bucket)
+            },
+        )
+    }
+
+    fn totals(&self) -> (f64, f64) {
+        (
+            self.positives.iter().sum::<u64>() as f64,
+            self.negatives.iter().sum::<u64>() as f64,
+        )
+    }
+
+    /// The probability that a positive item scores higher than a negative one, counting ties as
+    /// one half.
+    fn auroc(&self) -> f64 {
+        let (num_positives, num_negatives) = self.totals();
+        if num_positives == 0.0 || num_negatives == 0.0 {
+            return f64::NAN;
+        }
+
+        let pairs = self
+            .buckets_descending()
+            .map(|(pos, neg, positives_above, _)| neg * (positives_above + pos / 2.0))
+            .sum::<f64>();
+
+        pairs / (num_positives * num_negatives)
+    }
+
+    /// The precision at each threshold, weighted by the increase in recall.
+    fn average_precision(&self) -> f64 {
+        let (num_positives, _) = self.totals();
+        if num_positives == 0.0 {
+            return f64::NAN;
+        }
+
+        self.buckets_descending()
+            .filter(|(pos, ..)| *pos > 0.0)
+            .map(|(pos, neg, positives_above, negatives_above)| {
+                let true_positives = positives_above + pos;
+                let precision = true_positives / (true_positives + negatives_above + neg);
+                precision * pos / num_positives
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use burn_core::tensor::Tensor;
+
+    fn binary_input(scores: [f32; 6], targets: [i64; 6]) -> ClassificationInput<TestBackend> {
+        let device = Default::default();
+        ClassificationInput::new(
+            Tensor::<TestBackend, 1>::from_floats(scores, &device).reshape([6, 1]),
+            Tensor::from_ints(targets, &device),
+        )
+    }
+
+    #[test]
+    fn test_auroc_binary() {
+        let mut metric = AurocMetric::<TestBackend>::new().with_probabilities();
+        let input = binary_input([0.1, 0.4, 0.35, 0.8, 0.7, 0.2], [0, 0, 1, 1, 1, 0]);
+
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+
+        // 8 of the 9 positive-negative pairs are ranked correctly.
+        assert!((metric.value() - 8.0 / 9.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_auroc_should_accumulate_over_batches() {
+        let mut metric = AurocMetric::<TestBackend>::new().with_probabilities();
+        let input = binary_input([0.9, 0.8, 0.7, 0.1, 0.2, 0.3], [1, 1, 1, 0, 0, 0]);
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        assert_eq!(metric.value(), 1.0);
+
+        let input = binary_input([0.1, 0.2, 0.3, 0.9, 0.8, 0.7], [1, 1, 1, 0, 0, 0]);
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        assert!((metric.value() - 0.5).abs() < 1e-6);
+
+        metric.clear();
+        assert_eq!(metric.try_value(), None);
+    }
+
+    #[test]
+    fn test_average_precision_binary() {
+        let mut metric = AveragePrecisionMetric::<TestBackend>::new().with_probabilities();
+        let input = binary_input([0.1, 0.4, 0.35, 0.8, 0.7, 0.2], [0, 0, 1, 1, 1, 0]);
+
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+
+        // Positives ranked 1st, 2nd and 4th.
+        let expected = (1.0 + 1.0 + 3.0 / 4.0) / 3.0;
+        assert!((metric.value() - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_auroc_multiclass_should_average_classes() {
+        let device = Default::default();
+        let mut metric = AurocMetric::<TestBackend>::new().with_probabilities();
+        let input = ClassificationInput::new(
+            Tensor::<TestBackend, 2>::from_floats(
+                [[0.7, 0.3], [0.6, 0.4], [0.2, 0.8], [0.45, 0.55]],
+                &device,
+            ),
+            Tensor::from_ints([0, 1, 1, 0], &device),
+        );
+
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+
+        // Each class ranks 3 of its 4 positive-negative pairs correctly.
+        assert!((metric.value() - 0.75).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_auroc_should_convert_the_logits() {
+        let mut metric = AurocMetric::<TestBackend>::new();
+        // Outside of [0, 1], the logits would fall in the first and the last buckets.
+        let input = binary_input([-3.0, 2.0, 1.0, 4.0, 3.0, -2.0], [0, 0, 1, 1, 1, 0]);
+
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+
+        // 8 of the 9 positive-negative pairs are ranked correctly.
+        assert!((metric.value() - 8.0 / 9.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_auroc_multiclass_should_convert_the_logits() {
+        let device = Default::default();
+        let mut metric = AurocMetric::<TestBackend>::new();
+        // The logits of the second class would all fall in the last bucket.
+        let input = ClassificationInput::new(
+            Tensor::<TestBackend, 2>::from_floats(
+                [[5.0, 1.0], [0.0, 2.0], [3.0, 4.0], [2.0, 1.0]],
+                &device,
+            ),
+            Tensor::from_ints([0, 1, 1, 0], &device),
+        );
+
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+
+        assert_eq!(metric.value(), 1.0);
+    }
+
+    #[test]
+    #[should_panic = "The scores should be probabilities between 0 and 1"]
+    fn test_auroc_should_reject_logits_as_probabilities() {
+        let mut metric = AurocMetric::<TestBackend>::new().with_probabilities();
+        let input = binary_input([-3.0, 2.0, 1.0, 4.0, 3.0, -2.0], [0, 0, 1, 1, 1, 0]);
+
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+    }
+
+    #[test]
+    #[should_panic = "The number of outputs changed during the epoch"]
+    fn test_auroc_should_reject_a_change_of_the_number_of_outputs() {
+        let device = Default::default();
+        let mut metric = AurocMetric::<TestBackend>::new();
+        let input = binary_input([-3.0, 2.0, 1.0, 4.0, 3.0, -2.0], [0, 0, 1, 1, 1, 0]);
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+
+        let input = ClassificationInput::new(
+            Tensor::<TestBackend, 2>::from_floats([[5.0, 1.0], [0.0, 2.0]], &device),
+            Tensor::from_ints([0, 1], &device),
+        );
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+    }
+
+    #[test]
+    fn test_auroc_should_accept_another_number_of_outputs_after_clear() {
+        let device = Default::default();
+        let mut metric = AurocMetric::<TestBackend>::new();
+        let input = binary_input([-3.0, 2.0, 1.0, 4.0, 3.0, -2.0], [0, 0, 1, 1, 1, 0]);
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        metric.clear();
+
+        let input = ClassificationInput::new(
+            Tensor::<TestBackend, 2>::from_floats([[5.0, 1.0], [0.0, 2.0]], &device),
+            Tensor::from_ints([0, 1], &device),
+        );
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+
+        assert_eq!(metric.value(), 1.0);
+    }
+}
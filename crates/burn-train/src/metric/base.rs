@@ -1,3 +1,4 @@
+use crate::learner::{StepNorms, StepProfile};
 use burn_core::{data::dataloader::Progress, LearningRate};
 
 /// Metric metadata that can be used when computing metrics.
@@ -16,6 +17,12 @@ pub struct MetricMetadata {
 
     /// The current learning rate.
     pub lr: Option<LearningRate>,
+
+    /// The durations of the phases of the current step, when profiling.
+    pub profile: Option<StepProfile>,
+
+    /// The norms of the gradients and of the parameters, at the steps where they are computed.
+    pub norms: Option<StepNorms>,
 }
 
 impl MetricMetadata {
@@ -30,6 +37,8 @@ impl MetricMetadata {
             epoch_total: 1,
             iteration: 0,
             lr: None,
+            profile: None,
+            norms: None,
         }
     }
 }
@@ -71,6 +80,14 @@ pub trait Adaptor<T> {
 pub trait Numeric {
     /// Returns the numeric value of the metric.
     fn value(&self) -> f64;
+
+    /// Returns the numeric value of the metric, `None` when the metric has no value at the
+    /// current step, e.g. before its first measure.
+    ///
+    /// The entries of the steps without value are neither logged nor plotted.
+    fn try_value(&self) -> Option<f64> {
+        Some(self.value())
+    }
 }
 
 /// Data type that contains the current state of a metric at a given time.
@@ -90,13 +107,29 @@ pub enum NumericEntry {
     Value(f64),
     /// Aggregated numeric (value, number of elements).
     Aggregated(f64, usize),
+    /// Numeric value computed over all the items of the epoch so far, such as the metrics which
+    /// can't be averaged over the batches.
+    ///
+    /// The last entry of an epoch is the value of the epoch, so the entries aren't aggregated.
+    Epoch(f64),
 }
 
+/// The marker of the serialized [epoch entries](NumericEntry::Epoch).
+const EPOCH_MARKER: &str = "epoch";
+
 impl NumericEntry {
     pub(crate) fn serialize(&self) -> String {
         match self {
             Self::Value(v) => v.to_string(),
             Self::Aggregated(v, n) => format!("{v},{n}"),
+            Self::Epoch(v) => format!("{v},{EPOCH_MARKER}"),
+        }
+    }
+
+    /// The value of the entry.
+    pub(crate) fn value(&self) -> f64 {
+        match self {
+            Self::Value(v) | Self::Aggregated(v, _) | Self::Epoch(v) => *v,
         }
     }
 
@@ -112,9 +145,10 @@ impl NumericEntry {
                 Err(err) => Err(err.to_string()),
             }
         } else if num_values == 2 {
-            // Aggregated numeric (value, number of elements)
+            // Aggregated numeric (value, number of elements) or epoch numeric (value, marker)
             let (value, numel) = (values[0], values[1]);
             match value.parse::<f64>() {
+                Ok(value) if numel == EPOCH_MARKER => Ok(NumericEntry::Epoch(value)),
                 Ok(value) => match numel.parse::<usize>() {
                     Ok(numel) => Ok(NumericEntry::Aggregated(value, numel)),
                     Err(err) => Err(err.to_string()),
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+
+use super::{format_float, MetricEntry, MetricMetadata, NumericEntry, TextGenerationInput};
+use crate::metric::{Metric, Numeric};
+
+const DEFAULT_MAX_ORDER: usize = 4;
+
+/// The corpus-level BLEU score, in percent.
+///
+/// The clipped n-gram matches and the lengths are accumulated over all the sentences seen since
+/// the start of the epoch, so the score is the one of the whole corpus rather than an average of
+/// sentence scores.
+pub struct BleuMetric {
+    max_order: usize,
+    matches: Vec<usize>,
+    totals: Vec<usize>,
+    hypothesis_length: usize,
+    reference_length: usize,
+}
+
+impl BleuMetric {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum n-gram order, 4 by default.
+    pub fn with_max_order(mut self, max_order: usize) -> Self {
+        assert!(max_order > 0, "The maximum n-gram order must be at least 1");
+        self.max_order = max_order;
+        self.clear();
+        self
+    }
+
+    fn add(&mut self, hypothesis: &[usize], reference: &[usize]) {
+        self.hypothesis_length += hypothesis.len();
+        self.reference_length += reference.len();
+
+        for order in 1..=self.max_order {
+            let reference_counts = ngram_counts(reference, order);
+
+            for (ngram, count) in ngram_counts(hypothesis, order) {
+                let reference_count = reference_counts.get(ngram).copied().unwrap_or(0);
+                self.matches[order - 1] += count.min(reference_count);
+                self.totals[order - 1] += count;
+            }
+        }
+    }
+}
+
+impl Default for BleuMetric {
+    fn default() -> Self {
+        Self {
+            max_order: DEFAULT_MAX_ORDER,
+            matches: vec![0; DEFAULT_MAX_ORDER],
+            totals: vec![0; DEFAULT_MAX_ORDER],
+            hypothesis_length: 0,
+            reference_length: 0,
+        }
+    }
+}
+
+impl Metric for BleuMetric {
+    const NAME: &'static str = "BLEU";
+
+    type Input = TextGenerationInput;
+
+    fn update(&mut self, input: &TextGenerationInput, _metadata: &MetricMetadata) -> MetricEntry {
+        for (hypothesis, reference) in input.pairs() {
+            self.add(hypothesis, reference);
+        }
+
+        let value = self.value();
+        MetricEntry::new(
+            Self::NAME.to_string(),
+            format!("epoch {} %", format_float(value, 2)),
+            NumericEntry::Epoch(value).serialize(),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.matches = vec![0; self.max_order];
+        self.totals = vec![0; self.max_order];
+        self.hypothesis_length = 0;
+        self.reference_length = 0;
+    }
+}
+
+impl Numeric for BleuMetric {
+    fn value(&self) -> f64 {
+        if self.hypothesis_length == 0 {
+            return 0.0;
+        }
+
+        let mut log_precision = 0.0;
+        for (matches, total) in self.matches.iter().zip(self.totals.iter()) {
+            if *matches == 0 {
+                return 0.0;
+            }
+            log_precision += (*matches as f64 / *total as f64).ln();
+        }
+        log_precision /= self.max_order as f64;
+
+        let log_brevity_penalty = if self.hypothesis_length > self.reference_length {
+            0.0
+        } else {
+            1.0 - self.reference_length as f64 / self.hypothesis_length as f64
+        };
+
+        100.0 * (log_brevity_penalty + log_precision).exp()
+    }
+}
+
+/// Counts the occurrences of each n-gram of the sequence.
+fn ngram_counts(tokens: &[usize], order: usize) -> HashMap<&[usize], usize> {
+    let mut counts = HashMap::new();
+    for ngram in tokens.windows(order) {
+        *counts.entry(ngram).or_insert(0) += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bleu(mut metric: BleuMetric, hypothesis: Vec<usize>, reference: Vec<usize>) -> f64 {
+        let input = TextGenerationInput::new(vec![hypothesis], vec![reference]);
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        metric.value()
+    }
+
+    #[test]
+    fn test_bleu_identical_sentences() {
+        let value = bleu(BleuMetric::new(), vec![1, 2, 3, 4, 5], vec![1, 2, 3, 4, 5]);
+
+        assert!((value - 100.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_bleu_geometric_mean_of_precisions() {
+        let metric = BleuMetric::new().with_max_order(2);
+        let value = bleu(metric, vec![1, 2, 3, 4], vec![1, 2, 3, 5]);
+
+        // Unigram precision 3/4 and bigram precision 2/3.
+        assert!((value - 100.0 * 0.5f64.sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_bleu_should_clip_matches_and_penalize_short_hypotheses() {
+        let metric = BleuMetric::new().with_max_order(1);
+        assert!((bleu(metric, vec![1, 1, 1, 1], vec![1, 2, 3, 4]) - 25.0).abs() < 1e-6);
+
+        let metric = BleuMetric::new().with_max_order(1);
+        let value = bleu(metric, vec![1, 2], vec![1, 2, 3, 4]);
+        assert!((value - 100.0 * (-1.0f64).exp()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_bleu_should_accumulate_over_the_corpus() {
+        let mut metric = BleuMetric::new().with_max_order(1);
+        let input =
+            TextGenerationInput::new(vec![vec![1, 2], vec![5, 6]], vec![vec![1, 2], vec![7, 8]]);
+
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+
+        assert!((metric.value() - 50.0).abs() < 1e-6);
+    }
+}
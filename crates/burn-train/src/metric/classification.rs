@@ -0,0 +1,397 @@
+use super::{format_float, MetricEntry, NumericEntry};
+use burn_core::tensor::{activation::sigmoid, backend::Backend, Int, Tensor};
+
+/// The averaging method of the classification metrics when there are more than two classes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ClassAverage {
+    /// The metric is computed from the counts of all the classes together.
+    Micro,
+    /// The metric is computed for each class, then averaged.
+    #[default]
+    Macro,
+    /// The metric is computed for each class, then averaged weighted by the number of targets of
+    /// each class.
+    Weighted,
+}
+
+/// The input type of the classification metrics, such as [precision](super::PrecisionMetric).
+///
+/// With more than one class, the predicted class is the one with the highest output. With a
+/// single output per item, the classification is binary and the item is predicted as positive
+/// (class `1`) when its probability exceeds the threshold of the metric.
+///
+/// As for the [AUROC](super::AurocMetric), the outputs are expected to be logits, converted to
+/// probabilities with a sigmoid for binary classification, unless the metric is set to take
+/// probabilities. The targets must be the indices of the classes, `0` or `1` when binary.
+#[derive(new)]
+pub struct ClassificationInput<B: Backend> {
+    pub(crate) outputs: Tensor<B, 2>,
+    pub(crate) targets: Tensor<B, 1, Int>,
+}
+
+/// The settings shared by the classification metrics.
+#[derive(Clone, Copy)]
+pub(crate) struct ClassificationConfig {
+    pub(crate) threshold: f64,
+    pub(crate) average: ClassAverage,
+    pub(crate) probabilities: bool,
+}
+
+impl Default for ClassificationConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 0.5,
+            average: ClassAverage::default(),
+            probabilities: false,
+        }
+    }
+}
+
+/// The confusion counts accumulated since the start of the epoch, the state of the
+/// classification metrics.
+///
+/// The metrics are computed from the counts of the whole epoch, since averaging the metrics of
+/// the batches would weight the classes differently in each batch.
+#[derive(Default)]
+pub(crate) struct ConfusionStats {
+    pub(crate) config: ClassificationConfig,
+    counts: Option<ConfusionCounts>,
+}
+
+impl ConfusionStats {
+    pub(crate) fn update<B: Backend>(&mut self, input: &ClassificationInput<B>) {
+        let counts = ConfusionCounts::new(input, &self.config);
+
+        match &mut self.counts {
+            Some(total) => total.add(counts),
+            None => self.counts = Some(counts),
+        }
+    }
+
+    /// The metric of the epoch in percent, `None` before the first item.
+    pub(crate) fn value(&self, metric: fn(&ConfusionCounts, ClassAverage) -> f64) -> Option<f64> {
+        let counts = self.counts.as_ref()?;
+        Some(100.0 * metric(counts, self.config.average))
+    }
+
+    pub(crate) fn entry(
+        &self,
+        name: &str,
+        metric: fn(&ConfusionCounts, ClassAverage) -> f64,
+    ) -> MetricEntry {
+        let Some(value) = self.value(metric) else {
+            let formatted = "no item".to_string();
+            return MetricEntry::new(name.to_string(), formatted.clone(), formatted);
+        };
+
+        MetricEntry::new(
+            name.to_string(),
+            format!("epoch {} %", format_float(value, 2)),
+            NumericEntry::Epoch(value).serialize(),
+        )
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.counts = None;
+    }
+}
+
+/// Per class confusion counts.
+pub(crate) struct ConfusionCounts {
+    pub(crate) batch_size: usize,
+    true_positives: Vec<f64>,
+    false_positives: Vec<f64>,
+    false_negatives: Vec<f64>,
+    binary: bool,
+}
+
+impl ConfusionCounts {
+    pub(crate) fn new<B: Backend>(
+        input: &ClassificationInput<B>,
+        config: &ClassificationConfig,
+    ) -> Self {
+        let [batch_size, num_outputs] = input.outputs.dims();
+        let binary = num_outputs == 1;
+        let num_classes = num_outputs.max(2);
+
+        let predictions = if binary {
+            let probabilities = match config.probabilities {
+                true => input.outputs.clone(),
+                false => sigmoid(input.outputs.clone()),
+            };
+            probabilities
+                .into_data()
+                .iter::<f64>()
+                .map(|output| (output > config.threshold) as usize)
+                .collect::<Vec<_>>()
+        } else {
+            input
+                .outputs
+                .clone()
+                .argmax(1)
+                .into_data()
+                .iter::<i64>()
+                .map(|class| class as usize)
+                .collect::<Vec<_>>()
+        };
+
+        let mut counts = Self {
+            batch_size,
+            true_positives: vec![0.0; num_classes],
+            false_positives: vec![0.0; num_classes],
+            false_negatives: vec![0.0; num_classes],
+            binary,
+        };
+
+        for (prediction, target) in predictions
+            .into_iter()
+            .zip(input.targets.clone().into_data().iter::<i64>())
+        {
+            assert!(
+                (0..num_classes as i64).contains(&target),
+                "The target {target} is not a class of the {num_classes} classes"
+            );
+            let target = target as usize;
+            if prediction == target {
+                counts.true_positives[target] += 1.0;
+            } else {
+                counts.false_positives[prediction] += 1.0;
+                counts.false_negatives[target] += 1.0;
+            }
+        }
+
+        counts
+    }
+
+    /// Adds the counts of another batch.
+    fn add(&mut self, other: Self) {
+        let num_classes = self.true_positives.len().max(other.true_positives.len());
+        for counts in [
+            &mut self.true_positives,
+            &mut self.false_positives,
+            &mut self.false_negatives,
+        ] {
+            counts.resize(num_classes, 0.0);
+        }
+
+        let sums = [
+            (&mut self.true_positives, other.true_positives),
+            (&mut self.false_positives, other.false_positives),
+            (&mut self.false_negatives, other.false_negatives),
+        ];
+        for (total, counts) in sums {
+            total
+                .iter_mut()
+                .zip(counts)
+                .for_each(|(total, count)| *total += count);
+        }
+        self.batch_size += other.batch_size;
+        self.binary &= other.binary;
+    }
+
+    pub(crate) fn precision(&self, average: ClassAverage) -> f64 {
+        self.average(average, |tp, fp, _fn| ratio(tp, tp + fp))
+    }
+
+    pub(crate) fn recall(&self, average: ClassAverage) -> f64 {
+        self.average(average, |tp, _fp, fn_| ratio(tp, tp + fn_))
+    }
+
+    pub(crate) fn f1_score(&self, average: ClassAverage) -> f64 {
+        self.average(average, |tp, fp, fn_| ratio(2.0 * tp, 2.0 * tp + fp + fn_))
+    }
+
+    /// Averages the metric computed from the true positives, false positives and false
+    /// negatives counts. Binary classification only reports the metric of the positive class.
+    fn average(&self, average: ClassAverage, metric: impl Fn(f64, f64, f64) -> f64) -> f64 {
+        let class_metric = |class: usize| {
+            metric(
+                self.true_positives[class],
+                self.false_positives[class],
+                self.false_negatives[class],
+            )
+        };
+
+        if self.binary {
+            return class_metric(1);
+        }
+
+        let classes = 0..self.true_positives.len();
+        let support = |class: usize| self.true_positives[class] + self.false_negatives[class];
+        // Classes neither predicted nor targeted are ignored.
+        let is_present = |class: &usize| support(*class) + self.false_positives[*class] > 0.0;
+
+        match average {
+            ClassAverage::Micro => metric(
+                self.true_positives.iter().sum(),
+                self.false_positives.iter().sum(),
+                self.false_negatives.iter().sum(),
+            ),
+            ClassAverage::Macro => {
+                let (sum, count) = classes
+                    .filter(is_present)
+                    .fold((0.0, 0.0), |(sum, count), class| {
+                        (sum + class_metric(class), count + 1.0)
+                    });
+                ratio(sum, count)
+            }
+            ClassAverage::Weighted => {
+                let (sum, total) = classes.fold((0.0, 0.0), |(sum, total), class| {
+                    (
+                        sum + class_metric(class) * support(class),
+                        total + support(class),
+                    )
+                });
+                ratio(sum, total)
+            }
+        }
+    }
+}
+
+/// Division returning 0 when the denominator is 0, for instance the precision of a class that
+/// is never predicted.
+fn ratio(numerator: f64, denominator: f64) -> f64 {
+    if denominator > 0.0 {
+        numerator / denominator
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::TestBackend;
+
+    /// Targets `[0, 1, 2, 2, 1, 0]` with predictions `[0, 2, 2, 2, 1, 1]`.
+    pub(crate) fn multiclass_input() -> ClassificationInput<TestBackend> {
+        let device = Default::default();
+        ClassificationInput::new(
+            Tensor::from_data(
+                [
+                    [0.8, 0.1, 0.1],
+                    [0.1, 0.2, 0.7],
+                    [0.2, 0.2, 0.6],
+                    [0.1, 0.1, 0.8],
+                    [0.3, 0.6, 0.1],
+                    [0.4, 0.5, 0.1],
+                ],
+                &device,
+            ),
+            Tensor::from_data([0, 1, 2, 2, 1, 0], &device),
+        )
+    }
+
+    fn counts(input: &ClassificationInput<TestBackend>) -> ConfusionCounts {
+        ConfusionCounts::new(input, &ClassificationConfig::default())
+    }
+
+    fn assert_close(value: f64, expected: f64) {
+        assert!((value - expected).abs() < 1e-6, "{value} != {expected}");
+    }
+
+    #[test]
+    fn micro_average_should_pool_all_classes() {
+        let counts = counts(&multiclass_input());
+
+        assert_close(counts.precision(ClassAverage::Micro), 4.0 / 6.0);
+        assert_close(counts.recall(ClassAverage::Micro), 4.0 / 6.0);
+        assert_close(counts.f1_score(ClassAverage::Micro), 4.0 / 6.0);
+    }
+
+    #[test]
+    fn macro_average_should_average_classes() {
+        let counts = counts(&multiclass_input());
+
+        assert_close(
+            counts.precision(ClassAverage::Macro),
+            (1.0 + 0.5 + 2.0 / 3.0) / 3.0,
+        );
+        assert_close(counts.recall(ClassAverage::Macro), (0.5 + 0.5 + 1.0) / 3.0);
+        assert_close(
+            counts.f1_score(ClassAverage::Macro),
+            (2.0 / 3.0 + 0.5 + 0.8) / 3.0,
+        );
+    }
+
+    #[test]
+    fn weighted_average_should_weight_classes_by_support() {
+        let device = Default::default();
+        // Targets [0, 0, 0, 1] with predictions [0, 0, 1, 1].
+        let input = ClassificationInput::<TestBackend>::new(
+            Tensor::from_data([[0.9, 0.1], [0.8, 0.2], [0.3, 0.7], [0.2, 0.8]], &device),
+            Tensor::from_data([0, 0, 0, 1], &device),
+        );
+        let counts = counts(&input);
+
+        assert_close(
+            counts.recall(ClassAverage::Weighted),
+            (2.0 / 3.0 * 3.0 + 1.0) / 4.0,
+        );
+    }
+
+    #[test]
+    fn binary_should_use_threshold_and_positive_class() {
+        let device = Default::default();
+        let input = ClassificationInput::<TestBackend>::new(
+            Tensor::from_data([[0.9], [0.4], [0.6], [0.2]], &device),
+            Tensor::from_data([1, 1, 0, 0], &device),
+        );
+        let config = ClassificationConfig {
+            threshold: 0.5,
+            average: ClassAverage::Macro,
+            probabilities: true,
+        };
+        let counts = ConfusionCounts::new(&input, &config);
+
+        assert_close(counts.precision(ClassAverage::Macro), 0.5);
+        assert_close(counts.recall(ClassAverage::Macro), 0.5);
+
+        let config = ClassificationConfig {
+            threshold: 0.3,
+            ..config
+        };
+        let counts = ConfusionCounts::new(&input, &config);
+
+        assert_close(counts.precision(ClassAverage::Macro), 2.0 / 3.0);
+        assert_close(counts.recall(ClassAverage::Macro), 1.0);
+    }
+
+    #[test]
+    fn binary_should_convert_the_logits() {
+        let device = Default::default();
+        // The logits are predicted as positive when above 0, with a probability above 0.5.
+        let input = ClassificationInput::<TestBackend>::new(
+            Tensor::from_data([[2.0], [-1.0], [0.3], [-3.0]], &device),
+            Tensor::from_data([1, 1, 0, 0], &device),
+        );
+        let counts = counts(&input);
+
+        assert_close(counts.precision(ClassAverage::Macro), 0.5);
+        assert_close(counts.recall(ClassAverage::Macro), 0.5);
+    }
+
+    #[test]
+    #[should_panic = "The target 3 is not a class of the 3 classes"]
+    fn should_reject_a_target_out_of_the_classes() {
+        let device = Default::default();
+        let input = ClassificationInput::<TestBackend>::new(
+            Tensor::from_data([[2.0, 0.5, -1.0], [0.1, 0.2, 3.0]], &device),
+            Tensor::from_data([0, 3], &device),
+        );
+
+        let _counts = counts(&input);
+    }
+
+    #[test]
+    #[should_panic = "The target -1 is not a class of the 2 classes"]
+    fn binary_should_reject_a_negative_target() {
+        let device = Default::default();
+        let input = ClassificationInput::<TestBackend>::new(
+            Tensor::from_data([[2.0], [-1.0]], &device),
+            Tensor::from_data([1, -1], &device),
+        );
+
+        let _counts = counts(&input);
+    }
+}
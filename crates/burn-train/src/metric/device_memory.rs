@@ -0,0 +1,84 @@
+use super::{MetricMetadata, Numeric};
+use crate::metric::{Metric, MetricEntry};
+use burn_core::tensor::backend::{Backend, MemoryUsage};
+use core::marker::PhantomData;
+
+/// The memory used on a device, as reported by the memory pools of the backend.
+///
+/// The memory allocated to the tensors and the memory reserved by the pools are reported after
+/// each step, which shows the memory kept by the pools, invisible to the system tools. The
+/// backends without memory pools, such as the CPU backends, don't report their memory and the
+/// metric has no value.
+pub struct DeviceMemoryUse<B: Backend> {
+    device: B::Device,
+    usage: Option<MemoryUsage>,
+    _backend: PhantomData<B>,
+}
+
+impl<B: Backend> DeviceMemoryUse<B> {
+    /// Creates the metric of the given device.
+    pub fn new(device: B::Device) -> Self {
+        Self {
+            device,
+            usage: None,
+            _backend: PhantomData,
+        }
+    }
+}
+
+impl<B: Backend> Metric for DeviceMemoryUse<B> {
+    const NAME: &'static str = "Device Memory";
+
+    type Input = ();
+
+    fn update(&mut self, _item: &Self::Input, _metadata: &MetricMetadata) -> MetricEntry {
+        let name = format!("{} {:?}", Self::NAME, self.device);
+        self.usage = B::memory_usage(&self.device);
+
+        let Some(usage) = self.usage else {
+            let formatted = "Not reported".to_string();
+            return MetricEntry::new(name, formatted.clone(), formatted);
+        };
+
+        let in_use = bytes2gb(usage.bytes_in_use);
+        let formatted = format!(
+            "In Use: {:.2} Gb - Reserved: {:.2} Gb",
+            in_use,
+            bytes2gb(usage.bytes_reserved),
+        );
+
+        MetricEntry::new(name, formatted, in_use.to_string())
+    }
+
+    fn clear(&mut self) {}
+}
+
+impl<B: Backend> Numeric for DeviceMemoryUse<B> {
+    fn value(&self) -> f64 {
+        self.try_value().unwrap_or(f64::NAN)
+    }
+
+    fn try_value(&self) -> Option<f64> {
+        self.usage.map(|usage| bytes2gb(usage.bytes_in_use))
+    }
+}
+
+fn bytes2gb(bytes: u64) -> f64 {
+    bytes as f64 / 1e9
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+
+    #[test]
+    fn test_device_memory_not_reported() {
+        let mut metric = DeviceMemoryUse::<TestBackend>::new(Default::default());
+
+        let entry = metric.update(&(), &MetricMetadata::fake());
+
+        assert_eq!(entry.formatted, "Not reported");
+        assert_eq!(metric.try_value(), None);
+    }
+}
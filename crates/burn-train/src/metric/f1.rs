@@ -0,0 +1,93 @@
+use core::marker::PhantomData;
+
+use super::classification::{ConfusionCounts, ConfusionStats};
+use super::{ClassAverage, ClassificationInput, MetricEntry, MetricMetadata};
+use crate::metric::{Metric, Numeric};
+use burn_core::tensor::backend::Backend;
+
+/// The F1 score metric, the harmonic mean of the [precision](super::PrecisionMetric) and the
+/// [recall](super::RecallMetric), computed from the counts since the start of the epoch.
+#[derive(Default)]
+pub struct F1ScoreMetric<B: Backend> {
+    stats: ConfusionStats,
+    _b: PhantomData<B>,
+}
+
+impl<B: Backend> F1ScoreMetric<B> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the averaging method used with more than two classes.
+    pub fn with_average(mut self, average: ClassAverage) -> Self {
+        self.stats.config.average = average;
+        self
+    }
+
+    /// Sets the probability above which an output is predicted as positive, for binary
+    /// classification.
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.stats.config.threshold = threshold;
+        self
+    }
+
+    /// Uses the outputs as probabilities between 0 and 1 instead of logits, for binary
+    /// classification.
+    pub fn with_probabilities(mut self) -> Self {
+        self.stats.config.probabilities = true;
+        self
+    }
+}
+
+impl<B: Backend> Metric for F1ScoreMetric<B> {
+    const NAME: &'static str = "F1 Score";
+
+    type Input = ClassificationInput<B>;
+
+    fn update(
+        &mut self,
+        input: &ClassificationInput<B>,
+        _metadata: &MetricMetadata,
+    ) -> MetricEntry {
+        self.stats.update(input);
+        self.stats.entry(Self::NAME, ConfusionCounts::f1_score)
+    }
+
+    fn clear(&mut self) {
+        self.stats.reset()
+    }
+}
+
+impl<B: Backend> Numeric for F1ScoreMetric<B> {
+    fn value(&self) -> f64 {
+        self.try_value().unwrap_or(f64::NAN)
+    }
+
+    fn try_value(&self) -> Option<f64> {
+        self.stats.value(ConfusionCounts::f1_score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::classification::tests::multiclass_input;
+    use super::*;
+    use crate::TestBackend;
+
+    #[test]
+    fn test_f1_score_micro() {
+        let mut metric = F1ScoreMetric::<TestBackend>::new().with_average(ClassAverage::Micro);
+        let _entry = metric.update(&multiclass_input(), &MetricMetadata::fake());
+
+        assert!((metric.value() - 100.0 * 4.0 / 6.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_f1_score_macro() {
+        let mut metric = F1ScoreMetric::<TestBackend>::new();
+        let _entry = metric.update(&multiclass_input(), &MetricMetadata::fake());
+
+        assert!((metric.value() - 100.0 * (2.0 / 3.0 + 0.5 + 0.8) / 3.0).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,214 @@
+use core::marker::PhantomData;
+
+use super::{MetricEntry, MetricMetadata, NumericEntry};
+use crate::metric::{Metric, Numeric};
+use burn_core::tensor::backend::Backend;
+use burn_core::tensor::{Int, Tensor};
+
+/// The mean intersection over union (mIoU) metric for semantic segmentation.
+///
+/// The intersection and union of each class are accumulated over all the pixels seen since the
+/// start of the epoch, then the IoU of each class is averaged over the classes present in the
+/// predictions or the targets. The metric has no value until a pixel isn't ignored.
+#[derive(Default)]
+pub struct IouMetric<B: Backend> {
+    intersections: Vec<u64>,
+    unions: Vec<u64>,
+    ignore_index: Option<usize>,
+    current: Option<f64>,
+    _b: PhantomData<B>,
+}
+
+/// The [IoU metric](IouMetric) input type.
+#[derive(new)]
+pub struct IouInput<B: Backend> {
+    /// The class scores of each pixel, with shape `[batch_size, num_classes, height, width]`.
+    outputs: Tensor<B, 4>,
+    /// The class of each pixel, with shape `[batch_size, height, width]`.
+    targets: Tensor<B, 3, Int>,
+}
+
+impl<B: Backend> IouMetric<B> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the target class of the pixels to ignore, such as unlabeled or boundary pixels.
+    pub fn with_ignore_index(mut self, index: usize) -> Self {
+        self.ignore_index = Some(index);
+        self
+    }
+
+    /// The IoU of each class in percent, or NaN for the classes neither predicted nor targeted
+    /// since the start of the epoch.
+    pub fn class_iou(&self) -> Vec<f64> {
+        self.intersections
+            .iter()
+            .zip(self.unions.iter())
+            .map(|(intersection, union)| {
+                if *union == 0 {
+                    f64::NAN
+                } else {
+                    100.0 * *intersection as f64 / *union as f64
+                }
+            })
+            .collect()
+    }
+
+    fn mean_iou(&self) -> Option<f64> {
+        let values = self
+            .class_iou()
+            .into_iter()
+            .filter(|value| !value.is_nan())
+            .collect::<Vec<_>>();
+
+        if values.is_empty() {
+            return None;
+        }
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+impl<B: Backend> Metric for IouMetric<B> {
+    const NAME: &'static str = "mIoU";
+
+    type Input = IouInput<B>;
+
+    fn update(&mut self, input: &IouInput<B>, _metadata: &MetricMetadata) -> MetricEntry {
+        let [_batch_size, num_classes, _height, _width] = input.outputs.dims();
+        if self.unions.len() != num_classes {
+            self.intersections = vec![0; num_classes];
+            self.unions = vec![0; num_classes];
+        }
+
+        let predictions = input.outputs.clone().argmax(1).into_data();
+        let targets = input.targets.clone().into_data();
+
+        for (prediction, target) in predictions.iter::<i64>().zip(targets.iter::<i64>()) {
+            let (prediction, target) = (prediction as usize, target as usize);
+            if self.ignore_index == Some(target) {
+                continue;
+            }
+            assert!(
+                target < num_classes,
+                "The target class {} is out of range, there are {num_classes} classes",
+                target as i64,
+            );
+
+            if prediction == target {
+                self.intersections[target] += 1;
+                self.unions[target] += 1;
+            } else {
+                self.unions[prediction] += 1;
+                self.unions[target] += 1;
+            }
+        }
+
+        self.current = self.mean_iou();
+
+        let Some(current) = self.current else {
+            let formatted = "no pixel".to_string();
+            return MetricEntry::new(Self::NAME.to_string(), formatted.clone(), formatted);
+        };
+
+        MetricEntry::new(
+            Self::NAME.to_string(),
+            format!("epoch {current:.2} %"),
+            NumericEntry::Epoch(current).serialize(),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.intersections.clear();
+        self.unions.clear();
+        self.current = None;
+    }
+}
+
+impl<B: Backend> Numeric for IouMetric<B> {
+    fn value(&self) -> f64 {
+        self.current.unwrap_or(f64::NAN)
+    }
+
+    fn try_value(&self) -> Option<f64> {
+        self.current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+
+    /// Predictions `[[0, 0], [1, 1]]`.
+    fn input(targets: [[i64; 2]; 2]) -> IouInput<TestBackend> {
+        let device = Default::default();
+        IouInput::new(
+            Tensor::from_data(
+                [[[[1.0, 1.0], [0.0, 0.0]], [[0.0, 0.0], [1.0, 1.0]]]],
+                &device,
+            ),
+            Tensor::from_data([targets], &device),
+        )
+    }
+
+    #[test]
+    fn test_iou_per_class_and_mean() {
+        let mut metric = IouMetric::<TestBackend>::new();
+
+        let _entry = metric.update(&input([[0, 1], [1, 1]]), &MetricMetadata::fake());
+
+        let class_iou = metric.class_iou();
+        assert!((class_iou[0] - 50.0).abs() < 1e-6);
+        assert!((class_iou[1] - 200.0 / 3.0).abs() < 1e-6);
+        assert!((metric.value() - (50.0 + 200.0 / 3.0) / 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_iou_with_ignore_index() {
+        let mut metric = IouMetric::<TestBackend>::new().with_ignore_index(255);
+
+        let _entry = metric.update(&input([[0, 255], [1, 1]]), &MetricMetadata::fake());
+
+        assert_eq!(metric.value(), 100.0);
+    }
+
+    #[test]
+    fn test_iou_should_accumulate_over_the_epoch() {
+        let mut metric = IouMetric::<TestBackend>::new();
+        let _entry = metric.update(&input([[0, 0], [1, 1]]), &MetricMetadata::fake());
+        assert_eq!(metric.value(), 100.0);
+
+        let _entry = metric.update(&input([[1, 1], [0, 0]]), &MetricMetadata::fake());
+        assert_eq!(metric.value(), 100.0 * 2.0 / 6.0);
+
+        metric.clear();
+        assert_eq!(metric.try_value(), None);
+    }
+
+    #[test]
+    fn test_iou_with_only_ignored_pixels_has_no_value() {
+        let mut metric = IouMetric::<TestBackend>::new().with_ignore_index(255);
+
+        let _entry = metric.update(&input([[255, 255], [255, 255]]), &MetricMetadata::fake());
+
+        assert_eq!(metric.try_value(), None);
+    }
+
+    #[test]
+    #[should_panic = "The target class 2 is out of range, there are 2 classes"]
+    fn test_iou_should_reject_out_of_range_targets() {
+        let mut metric = IouMetric::<TestBackend>::new();
+
+        let _entry = metric.update(&input([[0, 2], [1, 1]]), &MetricMetadata::fake());
+    }
+
+    #[test]
+    #[should_panic = "The target class -1 is out of range, there are 2 classes"]
+    fn test_iou_should_reject_negative_targets() {
+        let mut metric = IouMetric::<TestBackend>::new().with_ignore_index(255);
+
+        let _entry = metric.update(&input([[0, -1], [1, 1]]), &MetricMetadata::fake());
+    }
+}
@@ -0,0 +1,74 @@
+use core::marker::PhantomData;
+
+use super::state::{FormatOptions, NumericMetricState};
+use super::{MetricEntry, MetricMetadata, RegressionInput};
+use crate::metric::{Metric, Numeric};
+use burn_core::tensor::backend::Backend;
+use burn_core::tensor::ElementConversion;
+
+/// The mean absolute error metric.
+#[derive(Default)]
+pub struct MaeMetric<B: Backend> {
+    state: NumericMetricState,
+    _b: PhantomData<B>,
+}
+
+impl<B: Backend> MaeMetric<B> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<B: Backend> Metric for MaeMetric<B> {
+    const NAME: &'static str = "Mean Absolute Error";
+
+    type Input = RegressionInput<B>;
+
+    fn update(&mut self, input: &RegressionInput<B>, _metadata: &MetricMetadata) -> MetricEntry {
+        let [batch_size, _num_targets] = input.outputs.dims();
+
+        let error = (input.outputs.clone() - input.targets.clone())
+            .abs()
+            .mean()
+            .into_scalar()
+            .elem::<f64>();
+
+        self.state.update(
+            error,
+            batch_size,
+            FormatOptions::new(Self::NAME).precision(4),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.state.reset()
+    }
+}
+
+impl<B: Backend> Numeric for MaeMetric<B> {
+    fn value(&self) -> f64 {
+        self.state.value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use burn_core::tensor::Tensor;
+
+    #[test]
+    fn test_mae() {
+        let device = Default::default();
+        let mut metric = MaeMetric::<TestBackend>::new();
+        let input = RegressionInput::new(
+            Tensor::from_data([[1.0, 2.0], [3.0, 4.0]], &device),
+            Tensor::from_data([[1.5, 2.0], [2.0, 6.0]], &device),
+        );
+
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+
+        assert!((metric.value() - 3.5 / 4.0).abs() < 1e-6);
+    }
+}
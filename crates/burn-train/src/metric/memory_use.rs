@@ -2,7 +2,7 @@
 use super::{MetricMetadata, Numeric};
 use crate::metric::{Metric, MetricEntry};
 use std::time::{Duration, Instant};
-use sysinfo::System;
+use sysinfo::{Pid, System};
 
 /// Memory information
 pub struct CpuMemory {
@@ -74,6 +74,75 @@ impl Numeric for CpuMemory {
     }
 }
 
+/// Resident memory of the training process
+pub struct ProcessMemory {
+    last_refresh: Instant,
+    refresh_frequency: Duration,
+    sys: System,
+    pid: Option<Pid>,
+    rss_bytes: u64,
+}
+
+impl ProcessMemory {
+    /// Creates a new process memory metric
+    pub fn new() -> Self {
+        let mut metric = Self {
+            last_refresh: Instant::now(),
+            refresh_frequency: Duration::from_millis(200),
+            sys: System::new(),
+            pid: sysinfo::get_current_pid().ok(),
+            rss_bytes: 0,
+        };
+        metric.refresh();
+        metric
+    }
+
+    fn refresh(&mut self) {
+        self.last_refresh = Instant::now();
+
+        let Some(pid) = self.pid else {
+            return;
+        };
+        self.sys.refresh_process(pid);
+
+        // bytes of RAM resident in memory for the process
+        if let Some(process) = self.sys.process(pid) {
+            self.rss_bytes = process.memory();
+        }
+    }
+}
+
+impl Default for ProcessMemory {
+    fn default() -> Self {
+        ProcessMemory::new()
+    }
+}
+
+impl Metric for ProcessMemory {
+    const NAME: &'static str = "Process Memory";
+
+    type Input = ();
+
+    fn update(&mut self, _item: &Self::Input, _metadata: &MetricMetadata) -> MetricEntry {
+        if self.last_refresh.elapsed() >= self.refresh_frequency {
+            self.refresh();
+        }
+
+        let raw = bytes2gb(self.rss_bytes);
+        let formatted = format!("RSS: {:.2} Gb", raw);
+
+        MetricEntry::new(Self::NAME.to_string(), formatted, raw.to_string())
+    }
+
+    fn clear(&mut self) {}
+}
+
+impl Numeric for ProcessMemory {
+    fn value(&self) -> f64 {
+        bytes2gb(self.rss_bytes)
+    }
+}
+
 fn bytes2gb(bytes: u64) -> f64 {
     bytes as f64 / 1e9
 }
@@ -2,35 +2,71 @@
 pub mod state;
 
 mod acc;
+mod auc;
 mod base;
+mod bleu;
+mod classification;
 #[cfg(feature = "metrics")]
 mod cpu_temp;
 #[cfg(feature = "metrics")]
 mod cpu_use;
 #[cfg(feature = "metrics")]
 mod cuda;
+mod device_memory;
+mod f1;
 mod hamming;
+mod iou;
 mod learning_rate;
 mod loss;
+mod mae;
 #[cfg(feature = "metrics")]
 mod memory_use;
+mod norm;
+mod perplexity;
+mod precision;
+mod r2;
+mod recall;
+mod regression;
+mod rmse;
+mod rouge;
+mod step_time;
+mod text;
+mod throughput;
 
 #[cfg(feature = "metrics")]
 mod top_k_acc;
 
 pub use acc::*;
+pub use auc::*;
 pub use base::*;
+pub use bleu::*;
+pub use classification::{ClassAverage, ClassificationInput};
 #[cfg(feature = "metrics")]
 pub use cpu_temp::*;
 #[cfg(feature = "metrics")]
 pub use cpu_use::*;
 #[cfg(feature = "metrics")]
 pub use cuda::*;
+pub use device_memory::*;
+pub use f1::*;
 pub use hamming::*;
+pub use iou::*;
 pub use learning_rate::*;
 pub use loss::*;
+pub use mae::*;
 #[cfg(feature = "metrics")]
 pub use memory_use::*;
+pub use norm::*;
+pub use perplexity::*;
+pub use precision::*;
+pub use r2::*;
+pub use recall::*;
+pub use regression::RegressionInput;
+pub use rmse::*;
+pub use rouge::*;
+pub use step_time::*;
+pub use text::TextGenerationInput;
+pub use throughput::*;
 #[cfg(feature = "metrics")]
 pub use top_k_acc::*;
 
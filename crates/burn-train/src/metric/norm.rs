@@ -0,0 +1,164 @@
+use super::{format_float, MetricMetadata, Numeric, NumericEntry};
+use crate::learner::StepNorms;
+use crate::metric::{Metric, MetricEntry};
+
+/// The global norm of the gradients, computed by the
+/// [norm monitoring](crate::NormMonitoring) of the learner.
+///
+/// A steadily growing norm usually announces exploding gradients, while a norm close to zero
+/// shows vanishing gradients. With the norms computed per layer, the layer with the largest
+/// norm is also displayed.
+#[derive(Default)]
+pub struct GradientNormMetric {
+    norms: Option<StepNorms>,
+}
+
+/// The global norm of the parameters, computed by the
+/// [norm monitoring](crate::NormMonitoring) of the learner.
+#[derive(Default)]
+pub struct ParameterNormMetric {
+    norms: Option<StepNorms>,
+}
+
+impl GradientNormMetric {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ParameterNormMetric {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Updates the norms, which are only computed at the sampled steps, and formats the entry.
+fn norm_entry(
+    name: &str,
+    norms: &mut Option<StepNorms>,
+    metadata: &MetricMetadata,
+    value: impl Fn(&StepNorms) -> (f64, Vec<(&str, f64)>),
+) -> MetricEntry {
+    if let Some(step) = &metadata.norms {
+        *norms = Some(step.clone());
+    }
+
+    let Some(norms) = norms.as_ref() else {
+        let formatted = "not computed".to_string();
+        return MetricEntry::new(name.to_string(), formatted.clone(), formatted);
+    };
+
+    let (global, layers) = value(norms);
+    let mut formatted = format!("global {}", format_float(global, 4));
+    let largest = layers.into_iter().max_by(|(_, a), (_, b)| a.total_cmp(b));
+    if let Some((layer, norm)) = largest {
+        formatted += &format!(" - largest {layer} {}", format_float(norm, 4));
+    }
+    let serialized = NumericEntry::Value(global).serialize();
+
+    MetricEntry::new(name.to_string(), formatted, serialized)
+}
+
+impl Metric for GradientNormMetric {
+    const NAME: &'static str = "Gradient Norm";
+
+    type Input = ();
+
+    fn update(&mut self, _item: &(), metadata: &MetricMetadata) -> MetricEntry {
+        norm_entry(Self::NAME, &mut self.norms, metadata, |norms| {
+            let layers = norms.layers.iter();
+            let layers = layers.map(|layer| (layer.name.as_str(), layer.gradients));
+            (norms.gradients, layers.collect())
+        })
+    }
+
+    fn clear(&mut self) {
+        self.norms = None;
+    }
+}
+
+impl Numeric for GradientNormMetric {
+    fn value(&self) -> f64 {
+        self.try_value().unwrap_or(f64::NAN)
+    }
+
+    fn try_value(&self) -> Option<f64> {
+        self.norms.as_ref().map(|norms| norms.gradients)
+    }
+}
+
+impl Metric for ParameterNormMetric {
+    const NAME: &'static str = "Parameter Norm";
+
+    type Input = ();
+
+    fn update(&mut self, _item: &(), metadata: &MetricMetadata) -> MetricEntry {
+        norm_entry(Self::NAME, &mut self.norms, metadata, |norms| {
+            let layers = norms.layers.iter();
+            let layers = layers.map(|layer| (layer.name.as_str(), layer.parameters));
+            (norms.parameters, layers.collect())
+        })
+    }
+
+    fn clear(&mut self) {
+        self.norms = None;
+    }
+}
+
+impl Numeric for ParameterNormMetric {
+    fn value(&self) -> f64 {
+        self.try_value().unwrap_or(f64::NAN)
+    }
+
+    fn try_value(&self) -> Option<f64> {
+        self.norms.as_ref().map(|norms| norms.parameters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::learner::LayerNorms;
+
+    #[test]
+    fn test_gradient_norm_keeps_the_last_computed_norms() {
+        let mut metric = GradientNormMetric::new();
+        let mut metadata = MetricMetadata::fake();
+        metadata.norms = Some(StepNorms {
+            gradients: 5.0,
+            parameters: 10.0,
+            layers: vec![
+                LayerNorms {
+                    name: "linear1".to_string(),
+                    gradients: 3.0,
+                    parameters: 8.0,
+                },
+                LayerNorms {
+                    name: "linear2".to_string(),
+                    gradients: 4.0,
+                    parameters: 6.0,
+                },
+            ],
+        });
+
+        let entry = metric.update(&(), &metadata);
+        assert_eq!(entry.formatted, "global 5.0000 - largest linear2 4.0000");
+
+        // The norms aren't computed at this step.
+        metadata.norms = None;
+        metric.update(&(), &metadata);
+        assert_eq!(metric.value(), 5.0);
+    }
+
+    #[test]
+    fn test_parameter_norm_without_computed_norms_has_no_value() {
+        let mut metric = ParameterNormMetric::new();
+
+        let entry = metric.update(&(), &MetricMetadata::fake());
+
+        assert_eq!(entry.formatted, "not computed");
+        assert_eq!(metric.try_value(), None);
+    }
+}
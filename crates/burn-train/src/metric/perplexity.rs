@@ -0,0 +1,143 @@
+use core::marker::PhantomData;
+
+use super::{format_float, MetricEntry, MetricMetadata, NumericEntry};
+use crate::metric::{Metric, Numeric};
+use burn_core::tensor::activation::log_softmax;
+use burn_core::tensor::backend::Backend;
+use burn_core::tensor::{ElementConversion, Int, Tensor};
+
+/// The perplexity metric for language modeling.
+///
+/// The token-level cross-entropy is summed over all the tokens seen since the start of the
+/// epoch, and the perplexity is the exponential of its mean over the tokens. Padding tokens
+/// are excluded from both the sum and the token count, the metric having no value until a token
+/// isn't padding.
+#[derive(Default)]
+pub struct PerplexityMetric<B: Backend> {
+    sum_cross_entropy: f64,
+    num_tokens: usize,
+    pad_token: Option<usize>,
+    _b: PhantomData<B>,
+}
+
+/// The [perplexity metric](PerplexityMetric) input type.
+#[derive(new)]
+pub struct PerplexityInput<B: Backend> {
+    /// The logits of each token, with shape `[num_tokens, vocab_size]`.
+    outputs: Tensor<B, 2>,
+    /// The target of each token, with shape `[num_tokens]`.
+    targets: Tensor<B, 1, Int>,
+}
+
+impl<B: Backend> PerplexityMetric<B> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the pad token.
+    pub fn with_pad_token(mut self, index: usize) -> Self {
+        self.pad_token = Some(index);
+        self
+    }
+}
+
+impl<B: Backend> Metric for PerplexityMetric<B> {
+    const NAME: &'static str = "Perplexity";
+
+    type Input = PerplexityInput<B>;
+
+    fn update(&mut self, input: &PerplexityInput<B>, _metadata: &MetricMetadata) -> MetricEntry {
+        let [num_tokens, _vocab_size] = input.outputs.dims();
+
+        let targets = input.targets.clone().reshape([num_tokens, 1]);
+        let cross_entropy = log_softmax(input.outputs.clone(), 1)
+            .gather(1, targets.clone())
+            .neg();
+
+        let (cross_entropy, num_pad) = match self.pad_token {
+            Some(pad_token) => {
+                let mask = targets.equal_elem(pad_token as i64);
+                let num_pad = mask.clone().int().sum().into_scalar().elem::<i64>();
+
+                (cross_entropy.mask_fill(mask, 0.0), num_pad as usize)
+            }
+            None => (cross_entropy, 0),
+        };
+
+        self.sum_cross_entropy += cross_entropy.sum().into_scalar().elem::<f64>();
+        self.num_tokens += num_tokens - num_pad;
+
+        let Some(value) = self.try_value() else {
+            let formatted = "no token".to_string();
+            return MetricEntry::new(Self::NAME.to_string(), formatted.clone(), formatted);
+        };
+
+        MetricEntry::new(
+            Self::NAME.to_string(),
+            format!("epoch {}", format_float(value, 2)),
+            NumericEntry::Epoch(value).serialize(),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.sum_cross_entropy = 0.0;
+        self.num_tokens = 0;
+    }
+}
+
+impl<B: Backend> Numeric for PerplexityMetric<B> {
+    fn value(&self) -> f64 {
+        self.try_value().unwrap_or(f64::NAN)
+    }
+
+    fn try_value(&self) -> Option<f64> {
+        if self.num_tokens == 0 {
+            return None;
+        }
+        Some((self.sum_cross_entropy / self.num_tokens as f64).exp())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+
+    #[test]
+    fn test_perplexity_of_uniform_predictions_is_vocab_size() {
+        let device = Default::default();
+        let mut metric = PerplexityMetric::<TestBackend>::new();
+        let input = PerplexityInput::new(
+            Tensor::zeros([3, 4], &device),
+            Tensor::from_data([0, 2, 3], &device),
+        );
+
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+
+        assert!((metric.value() - 4.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_perplexity_with_padding() {
+        let device = Default::default();
+        let mut metric = PerplexityMetric::<TestBackend>::new().with_pad_token(3);
+        let input = PerplexityInput::new(
+            Tensor::from_data(
+                [
+                    [0.0, 0.0, 0.0, 0.0],
+                    [0.0, 0.0, 0.0, 0.0],
+                    [10.0, -10.0, -10.0, -10.0], // Error on padding should not count
+                ],
+                &device,
+            ),
+            Tensor::from_data([1, 2, 3], &device),
+        );
+
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        assert!((metric.value() - 4.0).abs() < 1e-4);
+
+        metric.clear();
+        assert_eq!(metric.try_value(), None);
+    }
+}
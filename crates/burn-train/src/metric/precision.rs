@@ -0,0 +1,121 @@
+use core::marker::PhantomData;
+
+use super::classification::{ConfusionCounts, ConfusionStats};
+use super::{ClassAverage, ClassificationInput, MetricEntry, MetricMetadata};
+use crate::metric::{Metric, Numeric};
+use burn_core::tensor::backend::Backend;
+
+/// The precision metric, the fraction of the predictions of a class that are correct.
+///
+/// The metric is computed from the true and false positives counted since the start of the
+/// epoch.
+#[derive(Default)]
+pub struct PrecisionMetric<B: Backend> {
+    stats: ConfusionStats,
+    _b: PhantomData<B>,
+}
+
+impl<B: Backend> PrecisionMetric<B> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the averaging method used with more than two classes.
+    pub fn with_average(mut self, average: ClassAverage) -> Self {
+        self.stats.config.average = average;
+        self
+    }
+
+    /// Sets the probability above which an output is predicted as positive, for binary
+    /// classification.
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.stats.config.threshold = threshold;
+        self
+    }
+
+    /// Uses the outputs as probabilities between 0 and 1 instead of logits, for binary
+    /// classification.
+    pub fn with_probabilities(mut self) -> Self {
+        self.stats.config.probabilities = true;
+        self
+    }
+}
+
+impl<B: Backend> Metric for PrecisionMetric<B> {
+    const NAME: &'static str = "Precision";
+
+    type Input = ClassificationInput<B>;
+
+    fn update(
+        &mut self,
+        input: &ClassificationInput<B>,
+        _metadata: &MetricMetadata,
+    ) -> MetricEntry {
+        self.stats.update(input);
+        self.stats.entry(Self::NAME, ConfusionCounts::precision)
+    }
+
+    fn clear(&mut self) {
+        self.stats.reset()
+    }
+}
+
+impl<B: Backend> Numeric for PrecisionMetric<B> {
+    fn value(&self) -> f64 {
+        self.try_value().unwrap_or(f64::NAN)
+    }
+
+    fn try_value(&self) -> Option<f64> {
+        self.stats.value(ConfusionCounts::precision)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::classification::tests::multiclass_input;
+    use super::*;
+    use crate::TestBackend;
+    use burn_core::tensor::Tensor;
+
+    #[test]
+    fn test_precision_micro() {
+        let mut metric = PrecisionMetric::<TestBackend>::new().with_average(ClassAverage::Micro);
+        let _entry = metric.update(&multiclass_input(), &MetricMetadata::fake());
+
+        assert!((metric.value() - 100.0 * 4.0 / 6.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_precision_macro() {
+        let mut metric = PrecisionMetric::<TestBackend>::new();
+        let _entry = metric.update(&multiclass_input(), &MetricMetadata::fake());
+
+        assert!((metric.value() - 100.0 * (1.0 + 0.5 + 2.0 / 3.0) / 3.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_precision_should_accumulate_the_counts_over_the_epoch() {
+        let device = Default::default();
+        let mut metric = PrecisionMetric::<TestBackend>::new().with_probabilities();
+
+        let input = ClassificationInput::new(
+            Tensor::from_data([[0.9]], &device),
+            Tensor::from_data([1], &device),
+        );
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        assert_eq!(metric.value(), 100.0);
+
+        // The batch precision is 0, with one true positive and two false positives over the
+        // epoch, instead of the average 25 % weighted by the batch sizes.
+        let input = ClassificationInput::new(
+            Tensor::from_data([[0.9], [0.9], [0.1]], &device),
+            Tensor::from_data([0, 0, 0], &device),
+        );
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        assert!((metric.value() - 100.0 / 3.0).abs() < 1e-4);
+
+        metric.clear();
+        assert_eq!(metric.try_value(), None);
+    }
+}
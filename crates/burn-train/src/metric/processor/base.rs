@@ -1,3 +1,4 @@
+use crate::learner::{StepNorms, StepProfile};
 use burn_core::data::dataloader::Progress;
 use burn_core::LearningRate;
 
@@ -42,4 +43,12 @@ pub struct LearnerItem<T> {
 
     /// The learning rate.
     pub lr: Option<LearningRate>,
+
+    /// The durations of the phases of the step, when profiling.
+    #[new(default)]
+    pub profile: Option<StepProfile>,
+
+    /// The norms of the gradients and of the parameters, at the steps where they are computed.
+    #[new(default)]
+    pub norms: Option<StepNorms>,
 }
@@ -78,8 +78,9 @@ impl<T, V> Metrics<T, V> {
         }
 
         for metric in self.train_numeric.iter_mut() {
-            let (state, value) = metric.update(item, metadata);
-            entries_numeric.push((state, value));
+            if let Some((state, value)) = metric.update(item, metadata) {
+                entries_numeric.push((state, value));
+            }
         }
 
         MetricsUpdate::new(entries, entries_numeric)
@@ -100,8 +101,9 @@ impl<T, V> Metrics<T, V> {
         }
 
         for metric in self.valid_numeric.iter_mut() {
-            let (state, value) = metric.update(item, metadata);
-            entries_numeric.push((state, value));
+            if let Some((state, value)) = metric.update(item, metadata) {
+                entries_numeric.push((state, value));
+            }
         }
 
         MetricsUpdate::new(entries, entries_numeric)
@@ -147,12 +149,19 @@ impl<T> From<&LearnerItem<T>> for MetricMetadata {
             epoch_total: item.epoch_total,
             iteration: item.iteration,
             lr: item.lr,
+            profile: item.profile.clone(),
+            norms: item.norms.clone(),
         }
     }
 }
 
 trait NumericMetricUpdater<T>: Send + Sync {
-    fn update(&mut self, item: &LearnerItem<T>, metadata: &MetricMetadata) -> (MetricEntry, f64);
+    /// The entry of the metric, `None` when the metric has no value at this step.
+    fn update(
+        &mut self,
+        item: &LearnerItem<T>,
+        metadata: &MetricMetadata,
+    ) -> Option<(MetricEntry, f64)>;
     fn clear(&mut self);
 }
 
@@ -172,11 +181,15 @@ where
     M: Metric + Numeric + 'static,
     T: Adaptor<M::Input>,
 {
-    fn update(&mut self, item: &LearnerItem<T>, metadata: &MetricMetadata) -> (MetricEntry, f64) {
+    fn update(
+        &mut self,
+        item: &LearnerItem<T>,
+        metadata: &MetricMetadata,
+    ) -> Option<(MetricEntry, f64)> {
         let update = self.metric.update(&item.item.adapt(), metadata);
-        let numeric = self.metric.value();
+        let numeric = self.metric.try_value()?;
 
-        (update, numeric)
+        Some((update, numeric))
     }
 
     fn clear(&mut self) {
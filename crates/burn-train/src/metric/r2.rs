@@ -0,0 +1,147 @@
+use core::marker::PhantomData;
+
+use super::{format_float, MetricEntry, MetricMetadata, NumericEntry, RegressionInput};
+use crate::metric::{Metric, Numeric};
+use burn_core::tensor::backend::Backend;
+
+/// The coefficient of determination (R²) metric.
+///
+/// The sums of squares are accumulated over all the items seen since the start of the epoch.
+/// With multiple targets, the R² of each target is computed separately, then averaged over the
+/// targets with a variance. The metric has no value until a target varies.
+#[derive(Default)]
+pub struct R2Metric<B: Backend> {
+    sum_targets: Vec<f64>,
+    sum_squared_targets: Vec<f64>,
+    sum_squared_errors: Vec<f64>,
+    count: usize,
+    _b: PhantomData<B>,
+}
+
+impl<B: Backend> R2Metric<B> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<B: Backend> Metric for R2Metric<B> {
+    const NAME: &'static str = "R²";
+
+    type Input = RegressionInput<B>;
+
+    fn update(&mut self, input: &RegressionInput<B>, _metadata: &MetricMetadata) -> MetricEntry {
+        let [batch_size, num_targets] = input.outputs.dims();
+        if self.sum_targets.len() != num_targets {
+            self.sum_targets = vec![0.0; num_targets];
+            self.sum_squared_targets = vec![0.0; num_targets];
+            self.sum_squared_errors = vec![0.0; num_targets];
+        }
+
+        let outputs = input.outputs.clone().into_data();
+        let targets = input.targets.clone().into_data();
+
+        for (index, (output, target)) in
+            outputs.iter::<f64>().zip(targets.iter::<f64>()).enumerate()
+        {
+            let column = index % num_targets;
+            self.sum_targets[column] += target;
+            self.sum_squared_targets[column] += target * target;
+            self.sum_squared_errors[column] += (target - output) * (target - output);
+        }
+        self.count += batch_size;
+
+        let Some(value) = self.try_value() else {
+            let formatted = "no target variance".to_string();
+            return MetricEntry::new(Self::NAME.to_string(), formatted.clone(), formatted);
+        };
+
+        MetricEntry::new(
+            Self::NAME.to_string(),
+            format!("epoch {}", format_float(value, 4)),
+            NumericEntry::Epoch(value).serialize(),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.sum_targets.clear();
+        self.sum_squared_targets.clear();
+        self.sum_squared_errors.clear();
+        self.count = 0;
+    }
+}
+
+impl<B: Backend> Numeric for R2Metric<B> {
+    fn value(&self) -> f64 {
+        self.try_value().unwrap_or(f64::NAN)
+    }
+
+    fn try_value(&self) -> Option<f64> {
+        let count = self.count as f64;
+
+        // Targets without variance are ignored, their R² being undefined.
+        let values = self
+            .sum_targets
+            .iter()
+            .zip(self.sum_squared_targets.iter())
+            .zip(self.sum_squared_errors.iter())
+            .filter_map(|((sum, sum_squared), sum_squared_errors)| {
+                let total_sum_squares = sum_squared - sum * sum / count;
+                if total_sum_squares > 0.0 {
+                    Some(1.0 - sum_squared_errors / total_sum_squares)
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if values.is_empty() {
+            return None;
+        }
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use burn_core::tensor::Tensor;
+
+    #[test]
+    fn test_r2_should_accumulate_over_the_epoch() {
+        let device = Default::default();
+        let mut metric = R2Metric::<TestBackend>::new();
+
+        let input = RegressionInput::new(
+            Tensor::from_data([[1.0], [2.0]], &device),
+            Tensor::from_data([[1.0], [2.0]], &device),
+        );
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        assert_eq!(metric.value(), 1.0);
+
+        let input = RegressionInput::new(
+            Tensor::from_data([[3.0], [5.0]], &device),
+            Tensor::from_data([[3.0], [4.0]], &device),
+        );
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+
+        // A squared error of 1 for a total sum of squares of 5.
+        assert!((metric.value() - 0.8).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_r2_without_target_variance_has_no_value() {
+        let device = Default::default();
+        let mut metric = R2Metric::<TestBackend>::new();
+        let input = RegressionInput::new(
+            Tensor::from_data([[1.0], [2.0]], &device),
+            Tensor::from_data([[3.0], [3.0]], &device),
+        );
+
+        let entry = metric.update(&input, &MetricMetadata::fake());
+
+        assert_eq!(entry.formatted, "no target variance");
+        assert_eq!(metric.try_value(), None);
+    }
+}
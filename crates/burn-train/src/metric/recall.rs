@@ -0,0 +1,95 @@
+use core::marker::PhantomData;
+
+use super::classification::{ConfusionCounts, ConfusionStats};
+use super::{ClassAverage, ClassificationInput, MetricEntry, MetricMetadata};
+use crate::metric::{Metric, Numeric};
+use burn_core::tensor::backend::Backend;
+
+/// The recall metric, the fraction of the items of a class that are predicted correctly.
+///
+/// The metric is computed from the true positives and false negatives counted since the start
+/// of the epoch.
+#[derive(Default)]
+pub struct RecallMetric<B: Backend> {
+    stats: ConfusionStats,
+    _b: PhantomData<B>,
+}
+
+impl<B: Backend> RecallMetric<B> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the averaging method used with more than two classes.
+    pub fn with_average(mut self, average: ClassAverage) -> Self {
+        self.stats.config.average = average;
+        self
+    }
+
+    /// Sets the probability above which an output is predicted as positive, for binary
+    /// classification.
+    pub fn with_threshold(mut self, threshold: f64) -> Self {
+        self.stats.config.threshold = threshold;
+        self
+    }
+
+    /// Uses the outputs as probabilities between 0 and 1 instead of logits, for binary
+    /// classification.
+    pub fn with_probabilities(mut self) -> Self {
+        self.stats.config.probabilities = true;
+        self
+    }
+}
+
+impl<B: Backend> Metric for RecallMetric<B> {
+    const NAME: &'static str = "Recall";
+
+    type Input = ClassificationInput<B>;
+
+    fn update(
+        &mut self,
+        input: &ClassificationInput<B>,
+        _metadata: &MetricMetadata,
+    ) -> MetricEntry {
+        self.stats.update(input);
+        self.stats.entry(Self::NAME, ConfusionCounts::recall)
+    }
+
+    fn clear(&mut self) {
+        self.stats.reset()
+    }
+}
+
+impl<B: Backend> Numeric for RecallMetric<B> {
+    fn value(&self) -> f64 {
+        self.try_value().unwrap_or(f64::NAN)
+    }
+
+    fn try_value(&self) -> Option<f64> {
+        self.stats.value(ConfusionCounts::recall)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::classification::tests::multiclass_input;
+    use super::*;
+    use crate::TestBackend;
+
+    #[test]
+    fn test_recall_micro() {
+        let mut metric = RecallMetric::<TestBackend>::new().with_average(ClassAverage::Micro);
+        let _entry = metric.update(&multiclass_input(), &MetricMetadata::fake());
+
+        assert!((metric.value() - 100.0 * 4.0 / 6.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_recall_macro() {
+        let mut metric = RecallMetric::<TestBackend>::new();
+        let _entry = metric.update(&multiclass_input(), &MetricMetadata::fake());
+
+        assert!((metric.value() - 100.0 * (0.5 + 0.5 + 1.0) / 3.0).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,10 @@
+use burn_core::tensor::{backend::Backend, Tensor};
+
+/// The input type of the regression metrics, such as the [mean absolute error](super::MaeMetric).
+#[derive(new)]
+pub struct RegressionInput<B: Backend> {
+    /// The predictions, with shape `[batch_size, num_targets]`.
+    pub(crate) outputs: Tensor<B, 2>,
+    /// The targets, with shape `[batch_size, num_targets]`.
+    pub(crate) targets: Tensor<B, 2>,
+}
@@ -0,0 +1,101 @@
+use core::marker::PhantomData;
+
+use super::{format_float, MetricEntry, MetricMetadata, NumericEntry, RegressionInput};
+use crate::metric::{Metric, Numeric};
+use burn_core::tensor::backend::Backend;
+use burn_core::tensor::ElementConversion;
+
+/// The root mean squared error metric.
+///
+/// The squared errors are summed over all the items seen since the start of the epoch, so the
+/// value is the RMSE of the whole epoch rather than the mean of the RMSE of each batch. The
+/// metric has no value before the first item.
+#[derive(Default)]
+pub struct RmseMetric<B: Backend> {
+    sum_squared_error: f64,
+    num_elements: usize,
+    _b: PhantomData<B>,
+}
+
+impl<B: Backend> RmseMetric<B> {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<B: Backend> Metric for RmseMetric<B> {
+    const NAME: &'static str = "Root Mean Squared Error";
+
+    type Input = RegressionInput<B>;
+
+    fn update(&mut self, input: &RegressionInput<B>, _metadata: &MetricMetadata) -> MetricEntry {
+        let [batch_size, num_targets] = input.outputs.dims();
+
+        self.sum_squared_error += (input.outputs.clone() - input.targets.clone())
+            .powf_scalar(2.0)
+            .sum()
+            .into_scalar()
+            .elem::<f64>();
+        self.num_elements += batch_size * num_targets;
+
+        let Some(value) = self.try_value() else {
+            let formatted = "no item".to_string();
+            return MetricEntry::new(Self::NAME.to_string(), formatted.clone(), formatted);
+        };
+
+        MetricEntry::new(
+            Self::NAME.to_string(),
+            format!("epoch {}", format_float(value, 4)),
+            NumericEntry::Epoch(value).serialize(),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.sum_squared_error = 0.0;
+        self.num_elements = 0;
+    }
+}
+
+impl<B: Backend> Numeric for RmseMetric<B> {
+    fn value(&self) -> f64 {
+        self.try_value().unwrap_or(f64::NAN)
+    }
+
+    fn try_value(&self) -> Option<f64> {
+        if self.num_elements == 0 {
+            return None;
+        }
+        Some((self.sum_squared_error / self.num_elements as f64).sqrt())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+    use burn_core::tensor::Tensor;
+
+    #[test]
+    fn test_rmse_should_accumulate_over_the_epoch() {
+        let device = Default::default();
+        let mut metric = RmseMetric::<TestBackend>::new();
+
+        let input = RegressionInput::new(
+            Tensor::from_data([[1.0], [2.0]], &device),
+            Tensor::from_data([[1.0], [2.0]], &device),
+        );
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        assert_eq!(metric.value(), 0.0);
+
+        let input = RegressionInput::new(
+            Tensor::from_data([[1.0], [2.0]], &device),
+            Tensor::from_data([[3.0], [4.0]], &device),
+        );
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+        assert!((metric.value() - 2.0f64.sqrt()).abs() < 1e-6);
+
+        metric.clear();
+        assert_eq!(metric.try_value(), None);
+    }
+}
@@ -0,0 +1,104 @@
+use super::state::{FormatOptions, NumericMetricState};
+use super::{MetricEntry, MetricMetadata, TextGenerationInput};
+use crate::metric::{Metric, Numeric};
+
+/// The ROUGE-L score, the F-measure of the longest common subsequence between each hypothesis
+/// and its reference, averaged over the sentences, in percent.
+#[derive(Default)]
+pub struct RougeLMetric {
+    state: NumericMetricState,
+}
+
+impl RougeLMetric {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Metric for RougeLMetric {
+    const NAME: &'static str = "ROUGE-L";
+
+    type Input = TextGenerationInput;
+
+    fn update(&mut self, input: &TextGenerationInput, _metadata: &MetricMetadata) -> MetricEntry {
+        let scores = input
+            .pairs()
+            .map(|(hypothesis, reference)| {
+                let lcs = longest_common_subsequence(hypothesis, reference) as f64;
+                if lcs == 0.0 {
+                    return 0.0;
+                }
+
+                let precision = lcs / hypothesis.len() as f64;
+                let recall = lcs / reference.len() as f64;
+                2.0 * precision * recall / (precision + recall)
+            })
+            .collect::<Vec<_>>();
+        let batch_size = scores.len();
+        let score = scores.iter().sum::<f64>() / batch_size.max(1) as f64;
+
+        self.state.update(
+            100.0 * score,
+            batch_size,
+            FormatOptions::new(Self::NAME).unit("%").precision(2),
+        )
+    }
+
+    fn clear(&mut self) {
+        self.state.reset()
+    }
+}
+
+impl Numeric for RougeLMetric {
+    fn value(&self) -> f64 {
+        self.state.value()
+    }
+}
+
+/// Length of the longest common subsequence, computed keeping a single row of the dynamic
+/// programming table.
+fn longest_common_subsequence(a: &[usize], b: &[usize]) -> usize {
+    let mut row = vec![0; b.len() + 1];
+
+    for token_a in a {
+        let mut diagonal = 0;
+        for (j, token_b) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if token_a == token_b {
+                diagonal + 1
+            } else {
+                above.max(row[j])
+            };
+            diagonal = above;
+        }
+    }
+
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_longest_common_subsequence() {
+        assert_eq!(longest_common_subsequence(&[1, 2, 3, 4], &[1, 3, 5, 4]), 3);
+        assert_eq!(longest_common_subsequence(&[1, 2], &[3, 4]), 0);
+        assert_eq!(longest_common_subsequence(&[], &[3, 4]), 0);
+    }
+
+    #[test]
+    fn test_rouge_l_should_average_sentences() {
+        let mut metric = RougeLMetric::new();
+        let input = TextGenerationInput::new(
+            vec![vec![1, 2, 3, 4], vec![1, 2]],
+            vec![vec![1, 3, 5, 4], vec![1, 2, 3, 4]],
+        );
+
+        let _entry = metric.update(&input, &MetricMetadata::fake());
+
+        // F-measures of 3/4 and 2/3.
+        assert!((metric.value() - 100.0 * (0.75 + 2.0 / 3.0) / 2.0).abs() < 1e-6);
+    }
+}
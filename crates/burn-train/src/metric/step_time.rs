@@ -0,0 +1,141 @@
+use std::collections::VecDeque;
+
+use super::{format_float, MetricMetadata, Numeric, NumericEntry};
+use crate::learner::StepPhase;
+use crate::metric::{Metric, MetricEntry};
+
+/// The default number of steps used to compute the percentiles.
+const DEFAULT_WINDOW: usize = 1000;
+
+/// The duration of a phase of the training steps, in milliseconds, measured by the
+/// [profiler](crate::LearnerBuilder::profile).
+///
+/// The median, the 90th and the 99th percentiles are computed over the last steps, which tells
+/// whether the training is limited by the data loading or by the computations. The steps where
+/// the phase isn't measured have no value.
+pub struct StepTimeMetric {
+    phase: StepPhase,
+    window: usize,
+    durations: VecDeque<f64>,
+    current: Option<f64>,
+}
+
+impl StepTimeMetric {
+    /// Creates the metric of the given phase.
+    pub fn new(phase: StepPhase) -> Self {
+        Self {
+            phase,
+            window: DEFAULT_WINDOW,
+            durations: VecDeque::new(),
+            current: None,
+        }
+    }
+
+    /// Sets the number of steps used to compute the percentiles.
+    pub fn with_window(mut self, window: usize) -> Self {
+        assert!(window > 0, "The window should contain at least one step");
+        self.window = window;
+        self
+    }
+
+    /// The percentile of the durations of the window, between 0 and 100.
+    fn percentile(&self, percentile: f64) -> f64 {
+        let mut durations = self.durations.iter().copied().collect::<Vec<_>>();
+        durations.sort_by(|a, b| a.total_cmp(b));
+
+        let rank = (percentile / 100.0 * (durations.len() - 1) as f64).round() as usize;
+        durations[rank]
+    }
+}
+
+impl Metric for StepTimeMetric {
+    const NAME: &'static str = "Step Time";
+
+    type Input = ();
+
+    fn update(&mut self, _item: &(), metadata: &MetricMetadata) -> MetricEntry {
+        let name = format!("{} ({})", Self::NAME, self.phase.name());
+        let duration = metadata
+            .profile
+            .as_ref()
+            .and_then(|profile| profile.duration(self.phase));
+
+        let Some(duration) = duration else {
+            self.current = None;
+            let formatted = "not measured".to_string();
+            return MetricEntry::new(name, formatted.clone(), formatted);
+        };
+
+        let current = duration.as_secs_f64() * 1000.0;
+        self.current = Some(current);
+        if self.durations.len() == self.window {
+            self.durations.pop_front();
+        }
+        self.durations.push_back(current);
+
+        let formatted = format!(
+            "p50 {} ms - p90 {} ms - p99 {} ms - batch {} ms",
+            format_float(self.percentile(50.0), 2),
+            format_float(self.percentile(90.0), 2),
+            format_float(self.percentile(99.0), 2),
+            format_float(current, 2),
+        );
+        let serialized = NumericEntry::Aggregated(current, 1).serialize();
+
+        MetricEntry::new(name, formatted, serialized)
+    }
+
+    fn clear(&mut self) {
+        self.durations.clear();
+        self.current = None;
+    }
+}
+
+impl Numeric for StepTimeMetric {
+    fn value(&self) -> f64 {
+        self.current.unwrap_or(f64::NAN)
+    }
+
+    fn try_value(&self) -> Option<f64> {
+        self.current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::learner::StepProfile;
+
+    #[test]
+    fn test_step_time_percentiles() {
+        let mut metric = StepTimeMetric::new(StepPhase::DataLoading).with_window(4);
+        let mut metadata = MetricMetadata::fake();
+
+        let mut entry = None;
+        for millis in [100, 1, 2, 3, 4] {
+            let mut profile = StepProfile::default();
+            profile.record(
+                StepPhase::DataLoading,
+                std::time::Duration::from_millis(millis),
+            );
+            metadata.profile = Some(profile);
+            entry = Some(metric.update(&(), &metadata));
+        }
+
+        // The first duration is out of the window.
+        assert_eq!(metric.percentile(0.0), 1.0);
+        assert_eq!(metric.percentile(100.0), 4.0);
+        assert_eq!(metric.value(), 4.0);
+        assert_eq!(entry.unwrap().name, "Step Time (Data Loading)");
+    }
+
+    #[test]
+    fn test_step_time_without_measure_has_no_value() {
+        let mut metric = StepTimeMetric::new(StepPhase::DataLoading);
+
+        let entry = metric.update(&(), &MetricMetadata::fake());
+
+        assert_eq!(entry.formatted, "not measured");
+        assert_eq!(metric.try_value(), None);
+    }
+}
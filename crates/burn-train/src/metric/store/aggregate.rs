@@ -48,12 +48,18 @@ impl NumericMetricsAggregate {
             return None;
         }
 
+        // The last epoch entry is computed over the whole epoch, whatever the aggregate.
+        if let Some(NumericEntry::Epoch(value)) = points.last() {
+            self.value_for_each_epoch.insert(key, *value);
+            return Some(*value);
+        }
+
         // Accurately compute the aggregated value based on the *actual* number of points
         // since not all mini-batches are guaranteed to have the specified batch size
         let (sum, num_points) = points
             .into_iter()
             .map(|entry| match entry {
-                NumericEntry::Value(v) => (v, 1),
+                NumericEntry::Value(v) | NumericEntry::Epoch(v) => (v, 1),
                 // Right now the mean is the only aggregate available, so we can assume that the sum
                 // of an entry corresponds to (value * number of elements)
                 NumericEntry::Aggregated(v, n) => (v * n as f64, n),
@@ -202,4 +208,27 @@ mod tests {
         // Average should be (0.5 + 1.25 * 2) / 3 = 1.0, not (0.5 + 1.25) / 2 = 0.875
         assert_eq!(value, 1.0);
     }
+
+    #[test]
+    fn should_not_average_epoch_entries() {
+        let mut logger = InMemoryMetricLogger::default();
+        let mut aggregate = NumericMetricsAggregate::default();
+        let metric_name = "mIoU";
+
+        for value in [20.0, 50.0, 40.0] {
+            let entry = MetricEntry::new(
+                metric_name.to_string(),
+                value.to_string(),
+                NumericEntry::Epoch(value).serialize(),
+            );
+            logger.log(&entry);
+        }
+
+        let value = aggregate
+            .aggregate(metric_name, 1, Aggregate::Mean, &mut [Box::new(logger)])
+            .unwrap();
+
+        // The last entry is computed over the whole epoch.
+        assert_eq!(value, 40.0);
+    }
 }
@@ -17,6 +17,16 @@ pub struct MetricsUpdate {
     pub entries_numeric: Vec<(MetricEntry, f64)>,
 }
 
+impl MetricsUpdate {
+    /// All the metric entries, the non-numeric ones first.
+    pub(crate) fn entries(&self) -> Vec<&MetricEntry> {
+        self.entries
+            .iter()
+            .chain(self.entries_numeric.iter().map(|(entry, _value)| entry))
+            .collect()
+    }
+}
+
 /// Defines how training and validation events are collected and searched.
 ///
 /// This trait also exposes methods that uses the collected data to compute useful information.
@@ -50,7 +60,7 @@ pub enum Aggregate {
     Mean,
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 /// The split to use.
 pub enum Split {
     /// The training split.
@@ -59,7 +69,7 @@ pub enum Split {
     Valid,
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 /// The direction of the query.
 pub enum Direction {
     /// Lower is better.
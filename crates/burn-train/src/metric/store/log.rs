@@ -14,26 +14,16 @@ impl EventStore for LogEventStore {
         match event {
             Event::MetricsUpdate(update) => match split {
                 Split::Train => {
-                    update
-                        .entries
-                        .iter()
-                        .chain(update.entries_numeric.iter().map(|(entry, _value)| entry))
-                        .for_each(|entry| {
-                            self.loggers_train
-                                .iter_mut()
-                                .for_each(|logger| logger.log(entry));
-                        });
+                    let entries = update.entries();
+                    self.loggers_train
+                        .iter_mut()
+                        .for_each(|logger| logger.log_step(&entries));
                 }
                 Split::Valid => {
-                    update
-                        .entries
-                        .iter()
-                        .chain(update.entries_numeric.iter().map(|(entry, _value)| entry))
-                        .for_each(|entry| {
-                            self.loggers_valid
-                                .iter_mut()
-                                .for_each(|logger| logger.log(entry));
-                        });
+                    let entries = update.entries();
+                    self.loggers_valid
+                        .iter_mut()
+                        .for_each(|logger| logger.log_step(&entries));
                 }
             },
             Event::EndEpoch(epoch) => match split {
@@ -0,0 +1,77 @@
+use burn_core::tensor::{backend::Backend, Int, Tensor};
+
+/// The input type of the text generation metrics, such as [BLEU](super::BleuMetric), with one
+/// tokenized reference for each tokenized hypothesis.
+#[derive(new)]
+pub struct TextGenerationInput {
+    pub(crate) hypotheses: Vec<Vec<usize>>,
+    pub(crate) references: Vec<Vec<usize>>,
+}
+
+impl TextGenerationInput {
+    /// Creates the input from batches of token sequences with shape `[batch_size, seq_length]`,
+    /// removing the pad tokens.
+    pub fn from_tokens<B: Backend>(
+        hypotheses: Tensor<B, 2, Int>,
+        references: Tensor<B, 2, Int>,
+        pad_token: Option<usize>,
+    ) -> Self {
+        Self::new(
+            sequences(hypotheses, pad_token),
+            sequences(references, pad_token),
+        )
+    }
+
+    /// Iterates over the hypotheses with their reference.
+    pub(crate) fn pairs(&self) -> impl Iterator<Item = (&[usize], &[usize])> {
+        assert_eq!(
+            self.hypotheses.len(),
+            self.references.len(),
+            "Each hypothesis should have a reference"
+        );
+
+        self.hypotheses
+            .iter()
+            .zip(self.references.iter())
+            .map(|(hypothesis, reference)| (hypothesis.as_slice(), reference.as_slice()))
+    }
+}
+
+fn sequences<B: Backend>(tokens: Tensor<B, 2, Int>, pad_token: Option<usize>) -> Vec<Vec<usize>> {
+    let [_batch_size, seq_length] = tokens.dims();
+    let tokens = tokens
+        .into_data()
+        .iter::<i64>()
+        .map(|token| token as usize)
+        .collect::<Vec<_>>();
+
+    tokens
+        .chunks(seq_length.max(1))
+        .map(|sequence| {
+            sequence
+                .iter()
+                .copied()
+                .filter(|token| Some(*token) != pad_token)
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TestBackend;
+
+    #[test]
+    fn from_tokens_should_remove_padding() {
+        let device = Default::default();
+        let input = TextGenerationInput::from_tokens(
+            Tensor::<TestBackend, 2, Int>::from_data([[1, 2, 0], [3, 0, 0]], &device),
+            Tensor::from_data([[1, 2, 4], [3, 5, 0]], &device),
+            Some(0),
+        );
+
+        assert_eq!(input.hypotheses, vec![vec![1, 2], vec![3]]);
+        assert_eq!(input.references, vec![vec![1, 2, 4], vec![3, 5]]);
+    }
+}
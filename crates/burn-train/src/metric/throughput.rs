@@ -0,0 +1,204 @@
+use std::time::Instant;
+
+use super::{format_float, MetricMetadata, Numeric, NumericEntry};
+use crate::metric::{Metric, MetricEntry};
+
+/// The number of processed samples per second during the epoch.
+///
+/// The samples are counted from the progress of the dataloader, so the throughput accounts for
+/// all the devices with multi-device training and isn't affected by the gradient accumulation,
+/// each accumulated batch being processed like any other one.
+pub struct SamplesPerSecondMetric {
+    state: ThroughputState,
+    items_processed: Option<usize>,
+}
+
+/// The number of processed tokens per second during the epoch.
+///
+/// The tokens of each batch are provided by the model output through the
+/// [tokens input](TokensInput).
+pub struct TokensPerSecondMetric {
+    state: ThroughputState,
+}
+
+/// The [tokens per second metric](TokensPerSecondMetric) input type.
+#[derive(new)]
+pub struct TokensInput {
+    /// The number of tokens of the batch, usually excluding the padding.
+    num_tokens: usize,
+}
+
+/// Throughput over the epoch, measured from the first update.
+struct ThroughputState {
+    start: Option<Instant>,
+    count: usize,
+    current: Option<f64>,
+}
+
+impl ThroughputState {
+    fn new() -> Self {
+        Self {
+            start: None,
+            count: 0,
+            current: None,
+        }
+    }
+
+    /// Adds the processed quantity, updating the throughput.
+    ///
+    /// The first update only starts the measure, since the time spent before it is unknown.
+    fn update(&mut self, count: usize) {
+        let Some(start) = self.start else {
+            self.start = Some(Instant::now());
+            return;
+        };
+
+        self.count += count;
+        let elapsed = start.elapsed().as_secs_f64();
+        if elapsed > 0.0 {
+            self.current = Some(self.count as f64 / elapsed);
+        }
+    }
+
+    fn entry(&self, name: &str, unit: &str) -> MetricEntry {
+        let Some(current) = self.current else {
+            let formatted = "measuring".to_string();
+            return MetricEntry::new(name.to_string(), formatted.clone(), formatted);
+        };
+
+        let formatted = format!("epoch {} {unit}", format_float(current, 2));
+        let serialized = NumericEntry::Epoch(current).serialize();
+
+        MetricEntry::new(name.to_string(), formatted, serialized)
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+impl SamplesPerSecondMetric {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self {
+            state: ThroughputState::new(),
+            items_processed: None,
+        }
+    }
+}
+
+impl Default for SamplesPerSecondMetric {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metric for SamplesPerSecondMetric {
+    const NAME: &'static str = "Samples Per Second";
+
+    type Input = ();
+
+    fn update(&mut self, _item: &(), metadata: &MetricMetadata) -> MetricEntry {
+        let items_processed = metadata.progress.items_processed;
+        let count = items_processed.saturating_sub(self.items_processed.unwrap_or(items_processed));
+        self.items_processed = Some(items_processed);
+
+        self.state.update(count);
+        self.state.entry(Self::NAME, "samples/s")
+    }
+
+    fn clear(&mut self) {
+        self.state.reset();
+        self.items_processed = None;
+    }
+}
+
+impl Numeric for SamplesPerSecondMetric {
+    fn value(&self) -> f64 {
+        self.state.current.unwrap_or(f64::NAN)
+    }
+
+    fn try_value(&self) -> Option<f64> {
+        self.state.current
+    }
+}
+
+impl TokensPerSecondMetric {
+    /// Creates the metric.
+    pub fn new() -> Self {
+        Self {
+            state: ThroughputState::new(),
+        }
+    }
+}
+
+impl Default for TokensPerSecondMetric {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metric for TokensPerSecondMetric {
+    const NAME: &'static str = "Tokens Per Second";
+
+    type Input = TokensInput;
+
+    fn update(&mut self, item: &TokensInput, _metadata: &MetricMetadata) -> MetricEntry {
+        self.state.update(item.num_tokens);
+        self.state.entry(Self::NAME, "tokens/s")
+    }
+
+    fn clear(&mut self) {
+        self.state.reset();
+    }
+}
+
+impl Numeric for TokensPerSecondMetric {
+    fn value(&self) -> f64 {
+        self.state.current.unwrap_or(f64::NAN)
+    }
+
+    fn try_value(&self) -> Option<f64> {
+        self.state.current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_samples_per_second() {
+        let mut metric = SamplesPerSecondMetric::new();
+        let mut metadata = MetricMetadata::fake();
+
+        metadata.progress.items_processed = 32;
+        let entry = metric.update(&(), &metadata);
+        assert_eq!(entry.formatted, "measuring");
+        assert_eq!(metric.try_value(), None);
+
+        std::thread::sleep(Duration::from_millis(10));
+        metadata.progress.items_processed = 96;
+        metric.update(&(), &metadata);
+
+        // 64 samples in at least 10 ms.
+        assert!(metric.value() > 0.0 && metric.value() <= 6400.0);
+
+        metric.clear();
+        assert_eq!(metric.try_value(), None);
+    }
+
+    #[test]
+    fn test_tokens_per_second() {
+        let mut metric = TokensPerSecondMetric::new();
+        let metadata = MetricMetadata::fake();
+
+        metric.update(&TokensInput::new(100), &metadata);
+        std::thread::sleep(Duration::from_millis(10));
+        metric.update(&TokensInput::new(50), &metadata);
+
+        // 50 tokens in at least 10 ms, the tokens of the first batch being excluded.
+        assert!(metric.value() > 0.0 && metric.value() <= 5000.0);
+    }
+}
@@ -0,0 +1,383 @@
+use crate::metric::store::Split;
+use crate::metric::{format_float, MetricEntry};
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// The default number of points kept by the panels.
+const DEFAULT_WINDOW: usize = 1000;
+
+/// The configuration of the terminal UI, set with the
+/// [learner builder](crate::LearnerBuilder::tui).
+///
+/// Only used by the terminal UI renderer, with the `tui` feature, when the standard output is a
+/// terminal.
+#[cfg_attr(not(feature = "tui"), allow(dead_code))]
+pub struct TuiConfig {
+    pub(crate) refresh_rate: Duration,
+    pub(crate) plotted_metrics: Option<Vec<String>>,
+    pub(crate) panels: Vec<Box<dyn TuiPanel>>,
+}
+
+impl Default for TuiConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TuiConfig {
+    /// Create the default configuration, plotting all the numeric metrics and refreshing the
+    /// interface every 100 ms.
+    pub fn new() -> Self {
+        Self {
+            refresh_rate: Duration::from_millis(100),
+            plotted_metrics: None,
+            panels: Vec::new(),
+        }
+    }
+
+    /// Sets the minimum duration between two refreshes of the interface.
+    pub fn with_refresh_rate(mut self, refresh_rate: Duration) -> Self {
+        self.refresh_rate = refresh_rate;
+        self
+    }
+
+    /// Only plot the numeric metrics with the given names, the other ones being still displayed
+    /// as text.
+    pub fn with_plotted_metrics<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.plotted_metrics = Some(names.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Adds a panel, displayed next to the plots.
+    pub fn with_panel<P: TuiPanel + 'static>(mut self, panel: P) -> Self {
+        self.panels.push(Box::new(panel));
+        self
+    }
+}
+
+/// A custom panel of the terminal UI, updated with the metric entries.
+pub trait TuiPanel: Send + Sync {
+    /// The title of the panel.
+    fn title(&self) -> String;
+
+    /// Updates the panel with a metric entry, with its value for numeric metrics.
+    fn update(&mut self, entry: &MetricEntry, value: Option<f64>, split: Split);
+
+    /// The content to display.
+    fn content(&self) -> PanelContent;
+}
+
+/// The content of a [panel](TuiPanel).
+#[derive(Clone, Debug, PartialEq)]
+pub enum PanelContent {
+    /// Lines of text.
+    Text(Vec<String>),
+    /// Line plots sharing the same axes.
+    Plot(Vec<PlotSeries>),
+    /// The counts of the bins of a histogram, with their labels.
+    Histogram(Vec<(String, u64)>),
+}
+
+/// A line of a [plot](PanelContent::Plot).
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlotSeries {
+    /// The name of the line.
+    pub name: String,
+    /// The points of the line.
+    pub points: Vec<(f64, f64)>,
+}
+
+/// A panel plotting multiple numeric metrics together, such as the losses of the generator and
+/// of the discriminator of a GAN.
+pub struct MetricPlotPanel {
+    title: String,
+    metrics: Vec<String>,
+    window: usize,
+    series: Vec<MetricSeries>,
+}
+
+struct MetricSeries {
+    metric: String,
+    split: Split,
+    num_points: usize,
+    points: VecDeque<(f64, f64)>,
+}
+
+impl MetricPlotPanel {
+    /// Create the panel plotting the metrics with the given names.
+    pub fn new<I, S>(title: &str, metrics: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            title: title.to_string(),
+            metrics: metrics.into_iter().map(Into::into).collect(),
+            window: DEFAULT_WINDOW,
+            series: Vec::new(),
+        }
+    }
+
+    /// Sets the number of recent points plotted for each metric.
+    pub fn with_window(mut self, window: usize) -> Self {
+        assert!(window > 0, "The window should contain at least one point");
+        self.window = window;
+        self
+    }
+}
+
+impl TuiPanel for MetricPlotPanel {
+    fn title(&self) -> String {
+        self.title.clone()
+    }
+
+    fn update(&mut self, entry: &MetricEntry, value: Option<f64>, split: Split) {
+        let Some(value) = value else {
+            return;
+        };
+        if !self.metrics.contains(&entry.name) {
+            return;
+        }
+
+        let index = match self
+            .series
+            .iter()
+            .position(|series| series.metric == entry.name && series.split == split)
+        {
+            Some(index) => index,
+            None => {
+                self.series.push(MetricSeries {
+                    metric: entry.name.clone(),
+                    split,
+                    num_points: 0,
+                    points: VecDeque::new(),
+                });
+                self.series.len() - 1
+            }
+        };
+
+        let series = &mut self.series[index];
+        if series.points.len() == self.window {
+            series.points.pop_front();
+        }
+        series.points.push_back((series.num_points as f64, value));
+        series.num_points += 1;
+    }
+
+    fn content(&self) -> PanelContent {
+        let series = self
+            .series
+            .iter()
+            .map(|series| PlotSeries {
+                name: match series.split {
+                    Split::Train => format!("{} (Train)", series.metric),
+                    Split::Valid => format!("{} (Valid)", series.metric),
+                },
+                points: series.points.iter().copied().collect(),
+            })
+            .collect();
+
+        PanelContent::Plot(series)
+    }
+}
+
+/// A panel displaying the whole formatted value of a metric, such as samples generated by the
+/// model, which are too long for the metrics panel.
+pub struct TextPanel {
+    metric: String,
+    train: Option<String>,
+    valid: Option<String>,
+}
+
+impl TextPanel {
+    /// Create the panel displaying the metric with the given name.
+    pub fn new(metric: &str) -> Self {
+        Self {
+            metric: metric.to_string(),
+            train: None,
+            valid: None,
+        }
+    }
+}
+
+impl TuiPanel for TextPanel {
+    fn title(&self) -> String {
+        self.metric.clone()
+    }
+
+    fn update(&mut self, entry: &MetricEntry, _value: Option<f64>, split: Split) {
+        if entry.name != self.metric {
+            return;
+        }
+
+        match split {
+            Split::Train => self.train = Some(entry.formatted.clone()),
+            Split::Valid => self.valid = Some(entry.formatted.clone()),
+        }
+    }
+
+    fn content(&self) -> PanelContent {
+        let mut lines = Vec::new();
+
+        for (name, formatted) in [("Train", &self.train), ("Valid", &self.valid)] {
+            if let Some(formatted) = formatted {
+                lines.push(name.to_string());
+                lines.extend(formatted.lines().map(|line| format!("  {line}")));
+            }
+        }
+
+        PanelContent::Text(lines)
+    }
+}
+
+/// A panel displaying the distribution of the recent training values of a numeric metric.
+pub struct HistogramPanel {
+    metric: String,
+    num_bins: usize,
+    window: usize,
+    values: VecDeque<f64>,
+}
+
+impl HistogramPanel {
+    /// Create the panel of the metric with the given name, with 10 bins.
+    pub fn new(metric: &str) -> Self {
+        Self {
+            metric: metric.to_string(),
+            num_bins: 10,
+            window: DEFAULT_WINDOW,
+            values: VecDeque::new(),
+        }
+    }
+
+    /// Sets the number of bins.
+    pub fn with_num_bins(mut self, num_bins: usize) -> Self {
+        assert!(num_bins > 0, "The histogram should have at least one bin");
+        self.num_bins = num_bins;
+        self
+    }
+
+    /// Sets the number of recent values in the histogram.
+    pub fn with_window(mut self, window: usize) -> Self {
+        assert!(window > 0, "The window should contain at least one value");
+        self.window = window;
+        self
+    }
+}
+
+impl TuiPanel for HistogramPanel {
+    fn title(&self) -> String {
+        format!("{} (Histogram)", self.metric)
+    }
+
+    fn update(&mut self, entry: &MetricEntry, value: Option<f64>, split: Split) {
+        let Some(value) = value.filter(|value| value.is_finite()) else {
+            return;
+        };
+        if entry.name != self.metric || split != Split::Train {
+            return;
+        }
+
+        if self.values.len() == self.window {
+            self.values.pop_front();
+        }
+        self.values.push_back(value);
+    }
+
+    fn content(&self) -> PanelContent {
+        let Some(min) = self.values.iter().copied().reduce(f64::min) else {
+            return PanelContent::Histogram(Vec::new());
+        };
+        let max = self.values.iter().copied().fold(min, f64::max);
+        let width = (max - min) / self.num_bins as f64;
+
+        let mut counts = vec![0; self.num_bins];
+        for value in self.values.iter() {
+            let bin = match width > 0.0 {
+                true => ((value - min) / width) as usize,
+                false => 0,
+            };
+            // The maximum is included in the last bin.
+            counts[bin.min(self.num_bins - 1)] += 1;
+        }
+
+        let bins = counts
+            .into_iter()
+            .enumerate()
+            .map(|(bin, count)| (format_float(min + bin as f64 * width, 2), count))
+            .collect();
+
+        PanelContent::Histogram(bins)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, formatted: &str) -> MetricEntry {
+        MetricEntry::new(name.to_string(), formatted.to_string(), String::new())
+    }
+
+    #[test]
+    fn plot_panel_should_keep_the_recent_points_of_its_metrics() {
+        let mut panel = MetricPlotPanel::new("Losses", ["Loss G", "Loss D"]).with_window(2);
+
+        for value in [1.0, 2.0, 3.0] {
+            panel.update(&entry("Loss G", ""), Some(value), Split::Train);
+            panel.update(&entry("Loss D", ""), Some(-value), Split::Train);
+            panel.update(&entry("Accuracy", ""), Some(value), Split::Train);
+        }
+        panel.update(&entry("Loss G", ""), Some(5.0), Split::Valid);
+
+        let PanelContent::Plot(series) = panel.content() else {
+            panic!("Expected a plot");
+        };
+        let names = series.iter().map(|s| s.name.as_str()).collect::<Vec<_>>();
+
+        assert_eq!(
+            names,
+            ["Loss G (Train)", "Loss D (Train)", "Loss G (Valid)"]
+        );
+        assert_eq!(series[0].points, [(1.0, 2.0), (2.0, 3.0)]);
+        assert_eq!(series[2].points, [(0.0, 5.0)]);
+    }
+
+    #[test]
+    fn text_panel_should_display_the_latest_entries() {
+        let mut panel = TextPanel::new("Samples");
+
+        panel.update(&entry("Samples", "old"), None, Split::Train);
+        panel.update(&entry("Samples", "first\nsecond"), None, Split::Train);
+        panel.update(&entry("Loss", "1.0"), Some(1.0), Split::Train);
+
+        assert_eq!(
+            panel.content(),
+            PanelContent::Text(vec![
+                "Train".to_string(),
+                "  first".to_string(),
+                "  second".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn histogram_panel_should_count_the_values_per_bin() {
+        let mut panel = HistogramPanel::new("Loss").with_num_bins(2);
+
+        for value in [0.0, 1.0, 2.0, 3.0, 4.0] {
+            panel.update(&entry("Loss", ""), Some(value), Split::Train);
+        }
+        panel.update(&entry("Loss", ""), Some(10.0), Split::Valid);
+
+        let PanelContent::Histogram(bins) = panel.content() else {
+            panic!("Expected a histogram");
+        };
+
+        assert_eq!(bins[0].1, 2);
+        assert_eq!(bins[1].1, 3);
+    }
+}
@@ -4,6 +4,9 @@ use std::io::IsTerminal;
 mod base;
 pub use base::*;
 
+mod dashboard;
+pub use dashboard::*;
+
 mod cli;
 
 #[cfg(feature = "tui")]
@@ -21,10 +24,11 @@ use crate::TrainingInterrupter;
 pub(crate) fn default_renderer(
     interuptor: TrainingInterrupter,
     checkpoint: Option<usize>,
+    config: TuiConfig,
 ) -> Box<dyn MetricsRenderer> {
     #[cfg(feature = "tui")]
     if std::io::stdout().is_terminal() {
-        return Box::new(tui::TuiMetricsRenderer::new(interuptor, checkpoint));
+        return Box::new(tui::TuiMetricsRenderer::new(interuptor, checkpoint, config));
     }
 
     Box::new(cli::CliMetricsRenderer::new())
@@ -1,5 +1,6 @@
 use super::{
-    ControlsView, NumericMetricView, ProgressBarView, StatusView, TerminalFrame, TextMetricView,
+    ControlsView, NumericMetricView, PanelsView, ProgressBarView, StatusView, TerminalFrame,
+    TextMetricView,
 };
 use ratatui::prelude::{Constraint, Direction, Layout, Rect};
 
@@ -10,6 +11,7 @@ pub(crate) struct MetricsView<'a> {
     progress: ProgressBarView,
     controls: ControlsView,
     status: StatusView,
+    panels: PanelsView,
 }
 
 impl<'a> MetricsView<'a> {
@@ -28,6 +30,25 @@ impl<'a> MetricsView<'a> {
         let size_other = chunks[0];
         let size_metric_numeric = chunks[1];
 
+        // The custom panels share the space of the plots.
+        let size_metric_numeric = match self.panels.len() {
+            0 => size_metric_numeric,
+            num_panels => {
+                let chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints(
+                        [
+                            Constraint::Ratio(1, num_panels as u32 + 1),
+                            Constraint::Ratio(num_panels as u32, num_panels as u32 + 1),
+                        ]
+                        .as_ref(),
+                    )
+                    .split(size_metric_numeric);
+                self.panels.render(frame, chunks[1]);
+                chunks[0]
+            }
+        };
+
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Max(5), Constraint::Min(6), Constraint::Max(6)].as_ref())
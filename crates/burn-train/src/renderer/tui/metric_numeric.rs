@@ -25,6 +25,8 @@ pub(crate) struct NumericMetricsState {
     kind: PlotKind,
     num_samples_train: Option<usize>,
     num_samples_valid: Option<usize>,
+    /// The names of the plotted metrics, all of them when not set.
+    plotted: Option<Vec<String>>,
 }
 
 /// The kind of plot to display.
@@ -38,8 +40,26 @@ pub(crate) enum PlotKind {
 }
 
 impl NumericMetricsState {
+    /// Create the state plotting the metrics with the given names, or all of them.
+    pub(crate) fn new(plotted: Option<Vec<String>>) -> Self {
+        Self {
+            plotted,
+            ..Default::default()
+        }
+    }
+
+    fn is_plotted(&self, name: &str) -> bool {
+        self.plotted.as_ref().map_or(true, |plotted| {
+            plotted.iter().any(|plotted| plotted == name)
+        })
+    }
+
     /// Register a new training value for the metric with the given name.
     pub(crate) fn push_train(&mut self, name: String, data: f64) {
+        if !self.is_plotted(&name) {
+            return;
+        }
+
         if let Some((recent, full)) = self.data.get_mut(&name) {
             recent.push_train(data);
             full.push_train(data);
@@ -57,6 +77,10 @@ impl NumericMetricsState {
 
     /// Register a new validation value for the metric with the given name.
     pub(crate) fn push_valid(&mut self, key: String, data: f64) {
+        if !self.is_plotted(&key) {
+            return;
+        }
+
         if let Some((recent, full)) = self.data.get_mut(&key) {
             recent.push_valid(data);
             full.push_valid(data);
@@ -3,6 +3,7 @@ mod controls;
 mod full_history;
 mod metric_numeric;
 mod metric_text;
+mod panel;
 mod plot_utils;
 mod popup;
 mod progress;
@@ -15,6 +16,7 @@ pub(crate) use controls::*;
 pub(crate) use full_history::*;
 pub(crate) use metric_numeric::*;
 pub(crate) use metric_text::*;
+pub(crate) use panel::*;
 pub(crate) use plot_utils::*;
 pub(crate) use popup::*;
 pub(crate) use progress::*;
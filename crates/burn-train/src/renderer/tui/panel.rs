@@ -0,0 +1,142 @@
+use super::TerminalFrame;
+use crate::metric::format_float;
+use crate::renderer::{PanelContent, PlotSeries, TuiPanel};
+use ratatui::{
+    prelude::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    symbols,
+    text::Line,
+    widgets::{Axis, BarChart, Block, Borders, Chart, Dataset, GraphType, Paragraph, Wrap},
+};
+
+const AXIS_TITLE_PRECISION: usize = 2;
+const COLORS: [Color; 6] = [
+    Color::LightRed,
+    Color::LightBlue,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightMagenta,
+    Color::LightCyan,
+];
+
+/// The custom panels view.
+pub(crate) struct PanelsView {
+    panels: Vec<(String, PanelContent)>,
+}
+
+impl PanelsView {
+    pub(crate) fn new(panels: &[Box<dyn TuiPanel>]) -> Self {
+        Self {
+            panels: panels
+                .iter()
+                .map(|panel| (panel.title(), panel.content()))
+                .collect(),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.panels.len()
+    }
+
+    /// Render the panels stacked vertically in the given area.
+    pub(crate) fn render(self, frame: &mut TerminalFrame<'_>, size: Rect) {
+        let num_panels = self.panels.len() as u32;
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints((0..num_panels).map(|_| Constraint::Ratio(1, num_panels)))
+            .split(size);
+
+        for ((title, content), size) in self.panels.into_iter().zip(chunks.iter()) {
+            render_panel(frame, *size, title, content);
+        }
+    }
+}
+
+fn render_panel(frame: &mut TerminalFrame<'_>, size: Rect, title: String, content: PanelContent) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .title_alignment(Alignment::Left)
+        .style(Style::default().fg(Color::Gray));
+
+    match content {
+        PanelContent::Text(lines) => {
+            let paragraph = Paragraph::new(lines.into_iter().map(Line::from).collect::<Vec<_>>())
+                .alignment(Alignment::Left)
+                .wrap(Wrap { trim: false })
+                .block(block);
+
+            frame.render_widget(paragraph, size);
+        }
+        PanelContent::Plot(series) => {
+            let size_inner = block.inner(size);
+            frame.render_widget(block, size);
+            frame.render_widget(chart(&series), size_inner);
+        }
+        PanelContent::Histogram(bins) => {
+            let bars = bins
+                .iter()
+                .map(|(label, count)| (label.as_str(), *count))
+                .collect::<Vec<_>>();
+            let num_bars = bars.len().max(1) as u16;
+            let bar_width = (size.width.saturating_sub(2) / num_bars)
+                .saturating_sub(1)
+                .max(1);
+
+            let histogram = BarChart::default()
+                .block(block)
+                .data(bars.as_slice())
+                .bar_width(bar_width)
+                .bar_gap(1)
+                .bar_style(Style::default().fg(Color::LightBlue));
+
+            frame.render_widget(histogram, size);
+        }
+    }
+}
+
+fn chart(series: &[PlotSeries]) -> Chart<'_> {
+    let points = || series.iter().flat_map(|series| series.points.iter());
+    let (min_x, max_x) = bounds(points().map(|(x, _)| *x));
+    let (min_y, max_y) = bounds(points().map(|(_, y)| *y));
+
+    let datasets = series
+        .iter()
+        .zip(COLORS.iter().cycle())
+        .map(|(series, color)| {
+            Dataset::default()
+                .name(series.name.clone())
+                .marker(symbols::Marker::Braille)
+                .style(Style::default().fg(*color).bold())
+                .graph_type(GraphType::Line)
+                .data(&series.points)
+        })
+        .collect();
+
+    Chart::new(datasets)
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::DarkGray))
+                .labels([format!("{min_x}").bold(), format!("{max_x}").bold()])
+                .bounds([min_x, max_x]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::DarkGray))
+                .labels([
+                    format_float(min_y, AXIS_TITLE_PRECISION).bold(),
+                    format_float(max_y, AXIS_TITLE_PRECISION).bold(),
+                ])
+                .bounds([min_y, max_y]),
+        )
+}
+
+/// The minimum and the maximum of the values, or zeros without values.
+fn bounds(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values
+        .fold(None, |bounds, value| match bounds {
+            None => Some((value, value)),
+            Some((min, max)) => Some((f64::min(min, value), f64::max(max, value))),
+        })
+        .unwrap_or((0.0, 0.0))
+}
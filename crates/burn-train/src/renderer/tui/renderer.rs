@@ -1,5 +1,6 @@
+use crate::metric::store::Split;
 use crate::renderer::{tui::NumericMetricsState, MetricsRenderer};
-use crate::renderer::{MetricState, TrainingProgress};
+use crate::renderer::{MetricState, TrainingProgress, TuiConfig, TuiPanel};
 use crate::TrainingInterrupter;
 use ratatui::{
     crossterm::{
@@ -19,8 +20,8 @@ use std::{
 };
 
 use super::{
-    Callback, CallbackFn, ControlsView, MetricsView, PopupState, ProgressBarState, StatusState,
-    TextMetricsState,
+    Callback, CallbackFn, ControlsView, MetricsView, PanelsView, PopupState, ProgressBarState,
+    StatusState, TextMetricsState,
 };
 
 /// The current terminal backend.
@@ -31,23 +32,25 @@ pub(crate) type TerminalFrame<'a> = ratatui::Frame<'a>;
 #[allow(deprecated)] // `PanicInfo` type is renamed to `PanicHookInfo` in Rust 1.82
 type PanicHook = Box<dyn Fn(&std::panic::PanicInfo<'_>) + 'static + Sync + Send>;
 
-const MAX_REFRESH_RATE_MILLIS: u64 = 100;
-
 /// The terminal UI metrics renderer.
 pub struct TuiMetricsRenderer {
     terminal: Terminal<TerminalBackend>,
     last_update: std::time::Instant,
+    refresh_rate: Duration,
     progress: ProgressBarState,
     metrics_numeric: NumericMetricsState,
     metrics_text: TextMetricsState,
     status: StatusState,
     interuptor: TrainingInterrupter,
     popup: PopupState,
+    panels: Vec<Box<dyn TuiPanel>>,
     previous_panic_hook: Option<Arc<PanicHook>>,
 }
 
 impl MetricsRenderer for TuiMetricsRenderer {
     fn update_train(&mut self, state: MetricState) {
+        self.update_panels(&state, Split::Train);
+
         match state {
             MetricState::Generic(entry) => {
                 self.metrics_text.update_train(entry);
@@ -60,6 +63,8 @@ impl MetricsRenderer for TuiMetricsRenderer {
     }
 
     fn update_valid(&mut self, state: MetricState) {
+        self.update_panels(&state, Split::Valid);
+
         match state {
             MetricState::Generic(entry) => {
                 self.metrics_text.update_valid(entry);
@@ -88,7 +93,11 @@ impl MetricsRenderer for TuiMetricsRenderer {
 
 impl TuiMetricsRenderer {
     /// Create a new terminal UI renderer.
-    pub fn new(interuptor: TrainingInterrupter, checkpoint: Option<usize>) -> Self {
+    pub fn new(
+        interuptor: TrainingInterrupter,
+        checkpoint: Option<usize>,
+        config: TuiConfig,
+    ) -> Self {
         let mut stdout = io::stdout();
         execute!(stdout, EnterAlternateScreen).unwrap();
         enable_raw_mode().unwrap();
@@ -109,19 +118,31 @@ impl TuiMetricsRenderer {
         Self {
             terminal,
             last_update: Instant::now(),
+            refresh_rate: config.refresh_rate,
             progress: ProgressBarState::new(checkpoint),
-            metrics_numeric: NumericMetricsState::default(),
+            metrics_numeric: NumericMetricsState::new(config.plotted_metrics),
             metrics_text: TextMetricsState::default(),
             status: StatusState::default(),
             interuptor,
             popup: PopupState::Empty,
+            panels: config.panels,
             previous_panic_hook: Some(previous_panic_hook),
         }
     }
 
+    fn update_panels(&mut self, state: &MetricState, split: Split) {
+        let (entry, value) = match state {
+            MetricState::Generic(entry) => (entry, None),
+            MetricState::Numeric(entry, value) => (entry, Some(*value)),
+        };
+
+        for panel in self.panels.iter_mut() {
+            panel.update(entry, value, split);
+        }
+    }
+
     fn render(&mut self) -> Result<(), Box<dyn Error>> {
-        let tick_rate = Duration::from_millis(MAX_REFRESH_RATE_MILLIS);
-        if self.last_update.elapsed() < tick_rate {
+        if self.last_update.elapsed() < self.refresh_rate {
             return Ok(());
         }
 
@@ -146,6 +167,7 @@ impl TuiMetricsRenderer {
                         self.progress.view(),
                         ControlsView,
                         self.status.view(),
+                        PanelsView::new(&self.panels),
                     );
 
                     view.render(frame, size);
@@ -0,0 +1,97 @@
+use crate::metric::store::Direction;
+use std::collections::BTreeMap;
+
+/// Asynchronous successive halving (ASHA), terminating the trials whose metric is worse than
+/// most of the previous trials at the same epoch, used with the
+/// [tuner](crate::tuner::Tuner::with_early_termination).
+///
+/// The trials are compared at the rungs `min_epochs * reduction_factor^k`, and only the best
+/// `1 / reduction_factor` of the trials reaching a rung continue their training. The trials
+/// being compared to those already reported, the first trials are never terminated.
+#[derive(Clone, Debug)]
+pub struct Asha {
+    min_epochs: usize,
+    max_epochs: usize,
+    reduction_factor: usize,
+}
+
+impl Asha {
+    /// Create the early termination, comparing the trials from the minimum number of epochs and
+    /// stopping them at the maximum number of epochs.
+    pub fn new(min_epochs: usize, max_epochs: usize) -> Self {
+        assert!(
+            0 < min_epochs && min_epochs <= max_epochs,
+            "The minimum number of epochs should be positive and at most the maximum"
+        );
+
+        Self {
+            min_epochs,
+            max_epochs,
+            reduction_factor: 3,
+        }
+    }
+
+    /// Sets the reduction factor, the trials continuing at each rung being the best
+    /// `1 / reduction_factor` of them.
+    pub fn with_reduction_factor(mut self, reduction_factor: usize) -> Self {
+        assert!(
+            reduction_factor >= 2,
+            "The reduction factor should be at least 2"
+        );
+        self.reduction_factor = reduction_factor;
+        self
+    }
+
+    /// Whether the trials are compared at the given epoch.
+    fn is_rung(&self, epoch: usize) -> bool {
+        let mut rung = self.min_epochs;
+        while rung < epoch {
+            rung *= self.reduction_factor;
+        }
+
+        rung == epoch
+    }
+}
+
+/// The values reported at each rung by all the trials.
+pub(crate) struct AshaState {
+    asha: Asha,
+    rungs: BTreeMap<usize, Vec<f64>>,
+}
+
+impl AshaState {
+    pub(crate) fn new(asha: Asha) -> Self {
+        Self {
+            asha,
+            rungs: BTreeMap::new(),
+        }
+    }
+
+    pub(crate) fn max_epochs(&self) -> usize {
+        self.asha.max_epochs
+    }
+
+    /// Records the value of a trial at the epoch, returning whether the trial should continue.
+    pub(crate) fn report(&mut self, epoch: usize, value: f64, direction: &Direction) -> bool {
+        if epoch >= self.asha.max_epochs {
+            return false;
+        }
+        if !self.asha.is_rung(epoch) {
+            return true;
+        }
+
+        let values = self.rungs.entry(epoch).or_default();
+        values.push(value);
+
+        let better = values
+            .iter()
+            .filter(|other| match direction {
+                Direction::Lowest => **other < value,
+                Direction::Highest => **other > value,
+            })
+            .count();
+        let promoted = values.len().div_ceil(self.asha.reduction_factor);
+
+        better < promoted
+    }
+}
@@ -0,0 +1,359 @@
+use super::{Asha, AshaState, SearchSpace, Trial, TrialParams, TrialReporter};
+use crate::metric::store::Direction;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::sync::{Arc, Mutex};
+
+/// The error type for the [tuner](Tuner).
+#[derive(Debug)]
+pub enum TunerError {
+    /// A hyperparameter doesn't exist in the configuration.
+    UnknownParam(String),
+
+    /// The configuration can't be built from the hyperparameters of a trial.
+    InvalidConfig(String),
+
+    /// Other errors, such as a failed training.
+    Unknown(String),
+}
+
+impl core::fmt::Display for TunerError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::UnknownParam(path) => write!(f, "Unknown hyperparameter: {path}"),
+            Self::InvalidConfig(message) => write!(f, "Invalid configuration: {message}"),
+            Self::Unknown(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for TunerError {}
+
+/// How the [tuner](Tuner) picks the hyperparameters of the trials.
+#[derive(Clone, Debug)]
+pub enum SearchStrategy {
+    /// Every combination of the values of the hyperparameters, which should all be discrete.
+    Grid,
+    /// Hyperparameters sampled independently for each trial.
+    Random {
+        /// The number of trials.
+        num_trials: usize,
+        /// The seed of the sampling.
+        seed: u64,
+    },
+}
+
+/// Hyperparameter search, launching a training for each trial of a [search space](SearchSpace).
+///
+/// Each trial is given to a function building its configuration with [Trial::config] and
+/// training the learner, with the [trial callback](Trial::callback) reporting the tuned metric
+/// at the end of each epoch. The trials are run one after the other.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let space = SearchSpace::new()
+///     .with_log_uniform("optimizer.learning_rate", 1e-5, 1e-2)
+///     .with_choice("model.hidden_size", [64, 128, 256]);
+/// let tuner = Tuner::new(space, SearchStrategy::Random { num_trials: 20, seed: 42 })
+///     .with_metric("Loss", Direction::Lowest)
+///     .with_early_termination(Asha::new(1, 27));
+///
+/// let result = tuner.run(|trial| {
+///     let config = trial.config(&base_config)?;
+///     let learner = LearnerBuilder::new(format!("/tmp/tuner/{}", trial.id))
+///         .metric_valid_numeric(LossMetric::new())
+///         .callback(trial.callback())
+///         .num_epochs(27)
+///         .build(model, optim, lr);
+///     learner.fit(dataloader_train, dataloader_valid);
+///     Ok(())
+/// })?;
+/// println!("{result}");
+/// ```
+pub struct Tuner {
+    space: SearchSpace,
+    strategy: SearchStrategy,
+    metric: String,
+    direction: Direction,
+    early_termination: Option<Asha>,
+}
+
+/// The outcome of a [trial](Trial).
+#[derive(Clone, Debug)]
+pub struct TrialResult {
+    /// The index of the trial.
+    pub id: usize,
+    /// The values of the hyperparameters of the trial.
+    pub params: TrialParams,
+    /// The last reported value of the tuned metric.
+    pub value: Option<f64>,
+    /// The number of epochs reported.
+    pub epochs: usize,
+    /// Whether the trial was terminated early.
+    pub pruned: bool,
+}
+
+/// The outcome of the [tuner](Tuner).
+#[derive(Clone, Debug)]
+pub struct TuningResult {
+    /// The results of the trials, in their order of execution.
+    pub trials: Vec<TrialResult>,
+    direction: Direction,
+}
+
+impl Tuner {
+    /// Create the tuner, minimizing the `Loss` metric by default.
+    pub fn new(space: SearchSpace, strategy: SearchStrategy) -> Self {
+        Self {
+            space,
+            strategy,
+            metric: "Loss".to_string(),
+            direction: Direction::Lowest,
+            early_termination: None,
+        }
+    }
+
+    /// Sets the numeric validation metric to optimize and whether lower or higher is better.
+    pub fn with_metric(mut self, name: &str, direction: Direction) -> Self {
+        self.metric = name.to_string();
+        self.direction = direction;
+        self
+    }
+
+    /// Terminates the bad trials early with [successive halving](Asha).
+    pub fn with_early_termination(mut self, asha: Asha) -> Self {
+        self.early_termination = Some(asha);
+        self
+    }
+
+    /// Runs the trials with the function training the learner for each of them.
+    ///
+    /// The tuning stops at the first error returned by the function.
+    pub fn run<F>(&self, mut train: F) -> Result<TuningResult, TunerError>
+    where
+        F: FnMut(&Trial) -> Result<(), TunerError>,
+    {
+        let params = match &self.strategy {
+            SearchStrategy::Grid => self.space.grid(),
+            SearchStrategy::Random { num_trials, seed } => {
+                let mut rng = StdRng::seed_from_u64(*seed);
+                (0..*num_trials)
+                    .map(|_| self.space.sample(&mut rng))
+                    .collect()
+            }
+        };
+
+        let reporter = Arc::new(Mutex::new(TrialReporter {
+            metric: self.metric.clone(),
+            direction: self.direction,
+            asha: self.early_termination.clone().map(AshaState::new),
+            value: None,
+            epochs: 0,
+            pruned: false,
+        }));
+        let mut trials = Vec::with_capacity(params.len());
+
+        for (id, params) in params.into_iter().enumerate() {
+            log::info!("Starting the trial {id} with {params:?}");
+            reporter.lock().unwrap().reset();
+
+            let trial = Trial {
+                id,
+                params,
+                reporter: reporter.clone(),
+            };
+            train(&trial)?;
+
+            let reporter = reporter.lock().unwrap();
+            trials.push(TrialResult {
+                id,
+                params: trial.params,
+                value: reporter.value,
+                epochs: reporter.epochs,
+                pruned: reporter.pruned,
+            });
+        }
+
+        Ok(TuningResult {
+            trials,
+            direction: self.direction,
+        })
+    }
+}
+
+impl TuningResult {
+    /// The trial with the best value of the tuned metric.
+    pub fn best(&self) -> Option<&TrialResult> {
+        self.trials
+            .iter()
+            .filter(|trial| trial.value.is_some_and(|value| !value.is_nan()))
+            .min_by(|a, b| {
+                let (a, b) = (a.value.unwrap(), b.value.unwrap());
+                match self.direction {
+                    Direction::Lowest => a.total_cmp(&b),
+                    Direction::Highest => b.total_cmp(&a),
+                }
+            })
+    }
+}
+
+impl core::fmt::Display for TuningResult {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let best = self.best().map(|trial| trial.id);
+
+        for trial in self.trials.iter() {
+            let value = match trial.value {
+                Some(value) => format!("{value:.4}"),
+                None => "-".to_string(),
+            };
+            let status = if trial.pruned { " (terminated)" } else { "" };
+            let marker = if Some(trial.id) == best { "*" } else { " " };
+            let params = trial
+                .params
+                .iter()
+                .map(|(path, value)| format!("{path}={value}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            writeln!(
+                f,
+                "{marker} Trial {} - {value} after {} epochs{status} - {params}",
+                trial.id, trial.epochs
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use burn_core::config::Config;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct OptimizerConfig {
+        learning_rate: f64,
+        momentum: bool,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    struct TrainingConfig {
+        hidden_size: usize,
+        optimizer: OptimizerConfig,
+    }
+
+    impl Config for TrainingConfig {}
+
+    fn base_config() -> TrainingConfig {
+        TrainingConfig {
+            hidden_size: 32,
+            optimizer: OptimizerConfig {
+                learning_rate: 1e-3,
+                momentum: false,
+            },
+        }
+    }
+
+    #[test]
+    fn grid_search_should_run_every_combination() {
+        let space = SearchSpace::new()
+            .with_choice("hidden_size", [64, 128])
+            .with_choice("optimizer.momentum", [false, true]);
+        let tuner = Tuner::new(space, SearchStrategy::Grid);
+
+        let mut configs = Vec::new();
+        let result = tuner
+            .run(|trial| {
+                let config = trial.config(&base_config())?;
+                trial.report(
+                    1,
+                    config.hidden_size as f64 - config.optimizer.momentum as u8 as f64,
+                );
+                configs.push(config);
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(configs.len(), 4);
+        assert_eq!(configs[3].hidden_size, 128);
+        assert!(configs[3].optimizer.momentum);
+        assert_eq!(configs[3].optimizer.learning_rate, 1e-3);
+        assert_eq!(result.best().unwrap().id, 1);
+    }
+
+    #[test]
+    fn trial_config_should_fail_with_unknown_param() {
+        let space = SearchSpace::new().with_choice("optimizer.beta", [0.9]);
+        let tuner = Tuner::new(space, SearchStrategy::Grid);
+
+        let result = tuner.run(|trial| trial.config(&base_config()).map(|_| ()));
+
+        assert!(matches!(result, Err(TunerError::UnknownParam(path)) if path == "optimizer.beta"));
+    }
+
+    #[test]
+    fn random_search_should_be_reproducible() {
+        let space = SearchSpace::new()
+            .with_log_uniform("optimizer.learning_rate", 1e-5, 1e-1)
+            .with_int("hidden_size", 16, 256);
+        let strategy = SearchStrategy::Random {
+            num_trials: 5,
+            seed: 42,
+        };
+
+        let run = || {
+            Tuner::new(space.clone(), strategy.clone())
+                .run(|trial| {
+                    let config = trial.config(&base_config())?;
+                    assert!((1e-5..=1e-1).contains(&config.optimizer.learning_rate));
+                    assert!((16..=256).contains(&config.hidden_size));
+                    Ok(())
+                })
+                .unwrap()
+        };
+        let (first, second) = (run(), run());
+
+        assert_eq!(first.trials.len(), 5);
+        for (a, b) in first.trials.iter().zip(second.trials.iter()) {
+            assert_eq!(a.params, b.params);
+        }
+    }
+
+    #[test]
+    fn asha_should_terminate_the_worst_trials() {
+        let space = SearchSpace::new().with_choice("hidden_size", [1, 2, 3, 4]);
+        let tuner = Tuner::new(space, SearchStrategy::Grid)
+            .with_metric("Accuracy", Direction::Highest)
+            .with_early_termination(Asha::new(1, 4).with_reduction_factor(2));
+
+        let result = tuner
+            .run(|trial| {
+                let score = [5.0, 8.0, 3.0, 9.0][trial.id];
+                for epoch in 1..=4 {
+                    if !trial.report(epoch, score + epoch as f64) {
+                        break;
+                    }
+                }
+                Ok(())
+            })
+            .unwrap();
+
+        let epochs = result
+            .trials
+            .iter()
+            .map(|trial| trial.epochs)
+            .collect::<Vec<_>>();
+        let pruned = result
+            .trials
+            .iter()
+            .map(|trial| trial.pruned)
+            .collect::<Vec<_>>();
+        // The third trial is behind two of the three trials at the first rung.
+        assert_eq!(epochs, [4, 4, 1, 4]);
+        assert_eq!(pruned, [false, false, true, false]);
+        assert_eq!(result.best().unwrap().id, 3);
+    }
+}
@@ -0,0 +1,9 @@
+mod asha;
+mod base;
+mod space;
+mod trial;
+
+pub use asha::*;
+pub use base::*;
+pub use space::*;
+pub use trial::*;
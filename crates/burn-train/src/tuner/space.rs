@@ -0,0 +1,138 @@
+use rand::rngs::StdRng;
+use rand::Rng;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// The values of the hyperparameters of a trial, by path in the configuration, such as
+/// `optimizer.weight_decay` for a nested configuration.
+pub type TrialParams = Map<String, Value>;
+
+/// The values a hyperparameter can take.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Domain {
+    /// One of the given values.
+    Choice(Vec<Value>),
+    /// An integer between the bounds, inclusive.
+    Int {
+        /// The lowest value.
+        low: i64,
+        /// The highest value.
+        high: i64,
+    },
+    /// A float sampled uniformly between the bounds.
+    Uniform {
+        /// The lowest value.
+        low: f64,
+        /// The highest value.
+        high: f64,
+    },
+    /// A positive float whose logarithm is sampled uniformly between the logarithms of the
+    /// bounds, typically for learning rates.
+    LogUniform {
+        /// The lowest value.
+        low: f64,
+        /// The highest value.
+        high: f64,
+    },
+}
+
+/// The hyperparameters to search, with their [domains](Domain).
+#[derive(Clone, Debug, Default)]
+pub struct SearchSpace {
+    params: Vec<(String, Domain)>,
+}
+
+impl SearchSpace {
+    /// Create an empty search space.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a hyperparameter taking one of the given values.
+    pub fn with_choice<T, I>(self, path: &str, values: I) -> Self
+    where
+        T: Serialize,
+        I: IntoIterator<Item = T>,
+    {
+        let values = values
+            .into_iter()
+            .map(|value| serde_json::to_value(value).expect("Can serialize the value"))
+            .collect::<Vec<_>>();
+        assert!(!values.is_empty(), "The choice of {path} has no value");
+
+        self.with(path, Domain::Choice(values))
+    }
+
+    /// Add an integer hyperparameter between the bounds, inclusive.
+    pub fn with_int(self, path: &str, low: i64, high: i64) -> Self {
+        assert!(low <= high, "The bounds of {path} are inverted");
+        self.with(path, Domain::Int { low, high })
+    }
+
+    /// Add a float hyperparameter sampled uniformly between the bounds.
+    pub fn with_uniform(self, path: &str, low: f64, high: f64) -> Self {
+        assert!(low <= high, "The bounds of {path} are inverted");
+        self.with(path, Domain::Uniform { low, high })
+    }
+
+    /// Add a float hyperparameter sampled uniformly on a logarithmic scale between the bounds.
+    pub fn with_log_uniform(self, path: &str, low: f64, high: f64) -> Self {
+        assert!(
+            0.0 < low && low <= high,
+            "The bounds of {path} should be positive and ordered"
+        );
+        self.with(path, Domain::LogUniform { low, high })
+    }
+
+    fn with(mut self, path: &str, domain: Domain) -> Self {
+        self.params.push((path.to_string(), domain));
+        self
+    }
+
+    /// All the combinations of the values of the hyperparameters, which should be discrete.
+    pub(crate) fn grid(&self) -> Vec<TrialParams> {
+        let mut grid = vec![TrialParams::new()];
+
+        for (path, domain) in self.params.iter() {
+            let values = match domain {
+                Domain::Choice(values) => values.clone(),
+                Domain::Int { low, high } => (*low..=*high).map(Value::from).collect(),
+                Domain::Uniform { .. } | Domain::LogUniform { .. } => {
+                    panic!("The grid search requires discrete values, but {path} is continuous")
+                }
+            };
+
+            grid = grid
+                .into_iter()
+                .flat_map(|params| {
+                    values.iter().map(move |value| {
+                        let mut params = params.clone();
+                        params.insert(path.clone(), value.clone());
+                        params
+                    })
+                })
+                .collect();
+        }
+
+        grid
+    }
+
+    /// Samples a value for each hyperparameter.
+    pub(crate) fn sample(&self, rng: &mut StdRng) -> TrialParams {
+        self.params
+            .iter()
+            .map(|(path, domain)| {
+                let value = match domain {
+                    Domain::Choice(values) => values[rng.gen_range(0..values.len())].clone(),
+                    Domain::Int { low, high } => Value::from(rng.gen_range(*low..=*high)),
+                    Domain::Uniform { low, high } => Value::from(rng.gen_range(*low..=*high)),
+                    Domain::LogUniform { low, high } => {
+                        Value::from(rng.gen_range(low.ln()..=high.ln()).exp())
+                    }
+                };
+
+                (path.clone(), value)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,128 @@
+use super::{AshaState, TrialParams, TunerError};
+use crate::metric::store::{Aggregate, Direction, Split};
+use crate::{CallbackContext, TrainCallback};
+use burn_core::config::Config;
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+
+/// A run of the [tuner](crate::tuner::Tuner) with values of the hyperparameters.
+pub struct Trial {
+    /// The index of the trial.
+    pub id: usize,
+    /// The values of the hyperparameters of the trial.
+    pub params: TrialParams,
+    pub(crate) reporter: Arc<Mutex<TrialReporter>>,
+}
+
+/// The metric reported by the current trial, shared with its [callback](TrialCallback) and
+/// with the following trials for the early termination.
+pub(crate) struct TrialReporter {
+    pub(crate) metric: String,
+    pub(crate) direction: Direction,
+    pub(crate) asha: Option<AshaState>,
+    pub(crate) value: Option<f64>,
+    pub(crate) epochs: usize,
+    pub(crate) pruned: bool,
+}
+
+impl TrialReporter {
+    fn report(&mut self, epoch: usize, value: f64) -> bool {
+        self.value = Some(value);
+        self.epochs = epoch;
+
+        let proceed = match self.asha.as_mut() {
+            Some(asha) => asha.report(epoch, value, &self.direction),
+            None => true,
+        };
+        // Reaching the maximum number of epochs isn't a termination.
+        self.pruned = !proceed && !self.is_last(epoch);
+
+        proceed
+    }
+
+    /// Starts reporting a new trial.
+    pub(crate) fn reset(&mut self) {
+        self.value = None;
+        self.epochs = 0;
+        self.pruned = false;
+    }
+
+    fn is_last(&self, epoch: usize) -> bool {
+        self.asha
+            .as_ref()
+            .is_some_and(|asha| epoch >= asha.max_epochs())
+    }
+}
+
+impl Trial {
+    /// Applies the hyperparameters of the trial to the base configuration.
+    ///
+    /// The hyperparameters are set by their path in the serialized configuration, with nested
+    /// configurations separated by dots, such as `optimizer.weight_decay`.
+    pub fn config<C: Config>(&self, base: &C) -> Result<C, TunerError> {
+        let mut value =
+            serde_json::to_value(base).map_err(|err| TunerError::InvalidConfig(err.to_string()))?;
+
+        for (path, param) in self.params.iter() {
+            let mut field = &mut value;
+            for key in path.split('.') {
+                field = field
+                    .as_object_mut()
+                    .and_then(|object| object.get_mut(key))
+                    .ok_or_else(|| TunerError::UnknownParam(path.clone()))?;
+            }
+            *field = param.clone();
+        }
+
+        serde_json::from_value(value).map_err(|err| TunerError::InvalidConfig(err.to_string()))
+    }
+
+    /// Reports the value of the tuned metric at the end of an epoch, returning whether the
+    /// training should continue.
+    ///
+    /// The [callback](Self::callback) reports the metric automatically when registered on the
+    /// learner.
+    pub fn report(&self, epoch: usize, value: f64) -> bool {
+        self.reporter.lock().unwrap().report(epoch, value)
+    }
+
+    /// The callback reporting the mean of the tuned metric on the validation split at the end of
+    /// each epoch and stopping the training when the trial is terminated, to be registered with
+    /// the [learner builder](crate::LearnerBuilder::callback).
+    pub fn callback(&self) -> TrialCallback {
+        TrialCallback {
+            reporter: self.reporter.clone(),
+        }
+    }
+
+    /// The value of a hyperparameter of the trial.
+    pub fn param(&self, path: &str) -> Option<&Value> {
+        self.params.get(path)
+    }
+}
+
+/// Reports the tuned metric of a [trial](Trial) from the training loop.
+pub struct TrialCallback {
+    reporter: Arc<Mutex<TrialReporter>>,
+}
+
+impl<M> TrainCallback<M> for TrialCallback {
+    fn on_epoch_end(&mut self, context: &mut CallbackContext<M>) {
+        let mut reporter = self.reporter.lock().unwrap();
+        let value = context.store.find_metric(
+            &reporter.metric,
+            context.epoch,
+            Aggregate::Mean,
+            Split::Valid,
+        );
+
+        let Some(value) = value else {
+            log::warn!("The tuned metric {} isn't logged", reporter.metric);
+            return;
+        };
+
+        if !reporter.report(context.epoch, value) {
+            context.stop();
+        }
+    }
+}
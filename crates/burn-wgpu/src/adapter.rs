@@ -0,0 +1,208 @@
+use alloc::{string::String, vec::Vec};
+use cubecl::wgpu::{GraphicsApi, WgpuDevice};
+use wgpu::{Backend, DeviceType, Limits, PowerPreference};
+
+/// An adapter available to the wgpu backend, listed by [enumerate_adapters].
+#[derive(Debug, Clone)]
+pub struct AdapterInfo {
+    /// The name of the adapter, e.g. `NVIDIA GeForce RTX 3080`.
+    pub name: String,
+    /// The graphics API of the adapter.
+    pub backend: Backend,
+    /// The type of the adapter.
+    pub device_type: DeviceType,
+    /// The limits supported by the adapter.
+    pub limits: Limits,
+    /// The device to use the adapter, `None` for the adapters of an unknown type.
+    pub device: Option<WgpuDevice>,
+}
+
+/// Lists the adapters of the graphics API, in the order used by the [devices](WgpuDevice)
+/// indexes.
+///
+/// # Example
+///
+/// ```rust, ignore
+/// for adapter in enumerate_adapters::<AutoGraphicsApi>() {
+///     println!("{} ({:?}, {:?})", adapter.name, adapter.backend, adapter.device_type);
+/// }
+/// ```
+#[cfg(not(target_family = "wasm"))]
+pub fn enumerate_adapters<G: GraphicsApi>() -> Vec<AdapterInfo> {
+    let instance = wgpu::Instance::default();
+    let mut counts = [0; 3];
+
+    instance
+        .enumerate_adapters(G::backend().into())
+        .into_iter()
+        .map(|adapter| {
+            let info = adapter.get_info();
+            // The devices are indexed among the adapters of the same type
+            let mut next_index = |i: usize| {
+                counts[i] += 1;
+                counts[i] - 1
+            };
+            let device = match info.device_type {
+                DeviceType::DiscreteGpu => Some(WgpuDevice::DiscreteGpu(next_index(0))),
+                DeviceType::IntegratedGpu => Some(WgpuDevice::IntegratedGpu(next_index(1))),
+                DeviceType::VirtualGpu => Some(WgpuDevice::VirtualGpu(next_index(2))),
+                DeviceType::Cpu => Some(WgpuDevice::Cpu),
+                DeviceType::Other => None,
+            };
+
+            AdapterInfo {
+                name: info.name,
+                backend: info.backend,
+                device_type: info.device_type,
+                limits: adapter.limits(),
+                device,
+            }
+        })
+        .collect()
+}
+
+/// Selects the [device](WgpuDevice) of an adapter by criteria.
+///
+/// # Example
+///
+/// ```rust, ignore
+/// let device = AdapterSelector::new()
+///     .with_name("nvidia")
+///     .with_power_preference(PowerPreference::HighPerformance)
+///     .select::<AutoGraphicsApi>()
+///     .unwrap_or_default();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AdapterSelector {
+    device_type: Option<DeviceType>,
+    name: Option<String>,
+    power_preference: PowerPreference,
+}
+
+impl AdapterSelector {
+    /// Creates a selector accepting all the adapters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only accepts the adapters of the type, e.g. [DeviceType::DiscreteGpu].
+    pub fn with_device_type(mut self, device_type: DeviceType) -> Self {
+        self.device_type = Some(device_type);
+        self
+    }
+
+    /// Only accepts the adapters whose name contains `name`, ignoring the case.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into().to_lowercase());
+        self
+    }
+
+    /// Prefers the discrete GPUs with [PowerPreference::HighPerformance] or the integrated GPUs
+    /// with [PowerPreference::LowPower]. Without preference, the first accepted adapter is
+    /// selected.
+    pub fn with_power_preference(mut self, power_preference: PowerPreference) -> Self {
+        self.power_preference = power_preference;
+        self
+    }
+
+    /// Selects the device of an adapter of the graphics API, `None` if no adapter is accepted.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn select<G: GraphicsApi>(&self) -> Option<WgpuDevice> {
+        self.select_from(&enumerate_adapters::<G>())
+    }
+
+    /// Selects the device of one of the adapters, `None` if no adapter is accepted.
+    pub fn select_from(&self, adapters: &[AdapterInfo]) -> Option<WgpuDevice> {
+        adapters
+            .iter()
+            .filter(|adapter| adapter.device.is_some())
+            .filter(|adapter| {
+                self.device_type
+                    .map_or(true, |device_type| adapter.device_type == device_type)
+            })
+            .filter(|adapter| {
+                self.name
+                    .as_ref()
+                    .map_or(true, |name| adapter.name.to_lowercase().contains(name))
+            })
+            // The first adapter is kept between the ones with the same rank
+            .min_by_key(|adapter| self.rank(adapter.device_type))
+            .and_then(|adapter| adapter.device.clone())
+    }
+
+    fn rank(&self, device_type: DeviceType) -> usize {
+        let order = match self.power_preference {
+            PowerPreference::HighPerformance => [
+                DeviceType::DiscreteGpu,
+                DeviceType::IntegratedGpu,
+                DeviceType::VirtualGpu,
+                DeviceType::Cpu,
+            ],
+            PowerPreference::LowPower => [
+                DeviceType::IntegratedGpu,
+                DeviceType::DiscreteGpu,
+                DeviceType::VirtualGpu,
+                DeviceType::Cpu,
+            ],
+            PowerPreference::None => return 0,
+        };
+
+        order
+            .iter()
+            .position(|ty| *ty == device_type)
+            .unwrap_or(order.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{string::ToString, vec};
+
+    fn adapter(name: &str, device_type: DeviceType, device: WgpuDevice) -> AdapterInfo {
+        AdapterInfo {
+            name: name.to_string(),
+            backend: Backend::Vulkan,
+            device_type,
+            limits: Limits::default(),
+            device: Some(device),
+        }
+    }
+
+    #[test]
+    fn test_select_from_applies_the_criteria() {
+        let adapters = vec![
+            adapter(
+                "Intel UHD",
+                DeviceType::IntegratedGpu,
+                WgpuDevice::IntegratedGpu(0),
+            ),
+            adapter(
+                "NVIDIA RTX",
+                DeviceType::DiscreteGpu,
+                WgpuDevice::DiscreteGpu(0),
+            ),
+            adapter(
+                "AMD Radeon",
+                DeviceType::DiscreteGpu,
+                WgpuDevice::DiscreteGpu(1),
+            ),
+        ];
+
+        let first = AdapterSelector::new().select_from(&adapters);
+        let fastest = AdapterSelector::new()
+            .with_power_preference(PowerPreference::HighPerformance)
+            .select_from(&adapters);
+        let named = AdapterSelector::new()
+            .with_name("radeon")
+            .select_from(&adapters);
+        let missing = AdapterSelector::new()
+            .with_device_type(DeviceType::Cpu)
+            .select_from(&adapters);
+
+        assert_eq!(first, Some(WgpuDevice::IntegratedGpu(0)));
+        assert_eq!(fastest, Some(WgpuDevice::DiscreteGpu(0)));
+        assert_eq!(named, Some(WgpuDevice::DiscreteGpu(1)));
+        assert_eq!(missing, None);
+    }
+}
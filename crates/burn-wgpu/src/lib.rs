@@ -2,6 +2,10 @@
 
 extern crate alloc;
 
+mod adapter;
+
+pub use adapter::*;
+
 #[cfg(feature = "template")]
 pub use burn_jit::{
     kernel::{into_contiguous, Kernel},
@@ -49,6 +53,9 @@ type Compiler = Wgsl;
 /// will mean the given device (in this case the default) will be initialized to use Vulkan as the graphics API.
 /// It's also possible to use an existing wgpu device, by using `init_existing_device`.
 ///
+/// To select the device of an adapter, e.g. the discrete GPU of a given vendor, use an
+/// [AdapterSelector].
+///
 /// # Notes
 ///
 /// This version of the wgpu backend uses [burn_fusion] to compile and optimize streams of tensor
@@ -84,6 +91,9 @@ pub type Wgpu<F = f32, I = i32, C = Compiler> =
 /// will mean the given device (in this case the default) will be initialized to use Vulkan as the graphics API.
 /// It's also possible to use an existing wgpu device, by using `init_existing_device`.
 ///
+/// To select the device of an adapter, e.g. the discrete GPU of a given vendor, use an
+/// [AdapterSelector].
+///
 /// # Notes
 ///
 /// This version of the wgpu backend doesn't use [burn_fusion] to compile and optimize streams of tensor
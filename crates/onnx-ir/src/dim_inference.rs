@@ -5,7 +5,10 @@ use log::debug;
 use protobuf::Enum;
 
 use crate::{
-    ir::{ArgType, AttributeValue, Data, ElementType, Node, NodeType, TensorType},
+    ir::{
+        partial_shape, ArgType, Argument, AttributeValue, Data, ElementType, Node, NodeType,
+        TensorType,
+    },
     protos::tensor_proto::DataType,
     util::{flatten_config, shape_config},
 };
@@ -28,6 +31,7 @@ pub fn dim_inference(node: &mut Node) {
         NodeType::Cos => same_as_input(node),
         NodeType::Div => same_as_input_broadcast(node),
         NodeType::Dropout => same_as_input(node),
+        NodeType::Einsum => einsum_update_outputs(node),
         NodeType::Equal => elementwise_comparison_outputs(node),
         NodeType::Erf => same_as_input(node),
         NodeType::Exp => same_as_input(node),
@@ -36,8 +40,10 @@ pub fn dim_inference(node: &mut Node) {
         NodeType::Gelu => same_as_input(node),
         NodeType::Gather => gather_update_outputs(node),
         NodeType::GatherElements => same_as_input(node),
+        NodeType::GatherND => gather_nd_update_outputs(node),
         NodeType::Greater => elementwise_comparison_outputs(node),
         NodeType::GreaterOrEqual => elementwise_comparison_outputs(node),
+        NodeType::GRU => recurrent_update_outputs(node),
         NodeType::HardSigmoid => same_as_input(node),
         NodeType::GlobalAveragePool => same_as_input(node),
         NodeType::ConvTranspose1d => conv_transpose1d_update_outputs(node),
@@ -49,6 +55,7 @@ pub fn dim_inference(node: &mut Node) {
         NodeType::Linear => linear_update_outputs(node),
         NodeType::Log => same_as_input(node),
         NodeType::LogSoftmax => same_as_input(node),
+        NodeType::LSTM => recurrent_update_outputs(node),
         NodeType::MatMul => matmul_update_outputs(node),
         NodeType::Max => same_as_input_broadcast(node),
         NodeType::MaxPool1d => same_as_input(node),
@@ -56,6 +63,7 @@ pub fn dim_inference(node: &mut Node) {
         NodeType::Min => same_as_input_broadcast(node),
         NodeType::Mul => same_as_input(node),
         NodeType::Neg => same_as_input(node),
+        NodeType::NonMaxSuppression => non_max_suppression_update_outputs(node),
         NodeType::Not => same_as_input(node),
         NodeType::Pad => same_as_input(node),
         NodeType::PRelu => same_as_input_broadcast(node),
@@ -74,6 +82,8 @@ pub fn dim_inference(node: &mut Node) {
         NodeType::Relu => same_as_input(node),
         NodeType::Reshape => reshape_update_outputs(node),
         NodeType::Resize => same_as_input(node),
+        NodeType::ScatterElements => same_as_input(node),
+        NodeType::ScatterND => same_as_input(node),
         NodeType::Shape => shape_update_outputs(node),
         NodeType::Sigmoid => same_as_input(node),
         NodeType::Sign => same_as_input(node),
@@ -85,6 +95,7 @@ pub fn dim_inference(node: &mut Node) {
         NodeType::Sub => same_as_input_broadcast(node),
         NodeType::Sum => same_as_input_broadcast(node),
         NodeType::Tanh => same_as_input(node),
+        NodeType::TopK => topk_update_outputs(node),
         NodeType::Transpose => same_as_input(node),
         NodeType::Trilu => same_as_input(node),
         NodeType::Unsqueeze => unsqueeze_update_output(node),
@@ -119,19 +130,19 @@ fn constant_update_outputs(node: &mut Node) {
             AttributeValue::Tensor(tensor) => ArgType::Tensor(TensorType {
                 elem_type: tensor.elem_type.clone(),
                 dim: tensor.dim,
-                shape: tensor.shape.clone(),
+                shape: tensor.shape.clone().map(partial_shape),
             }),
             AttributeValue::Float32(_) => ArgType::Scalar(ElementType::Float32),
             AttributeValue::Float32s(value) => ArgType::Tensor(TensorType {
                 elem_type: ElementType::Float32,
                 dim: 1,
-                shape: Some(vec![value.len()]),
+                shape: Some(vec![Some(value.len())]),
             }),
             AttributeValue::Int64(_) => ArgType::Scalar(ElementType::Int64),
             AttributeValue::Int64s(value) => ArgType::Tensor(TensorType {
                 elem_type: ElementType::Int64,
                 dim: 1,
-                shape: Some(vec![value.len()]),
+                shape: Some(vec![Some(value.len())]),
             }),
             ty => panic!("Constant value of {:?} is not supported", ty),
         },
@@ -151,8 +162,7 @@ fn constant_of_shape_update_output(node: &mut Node) {
         ArgType::Tensor(tensor_type) => tensor_type
             .shape
             .as_ref()
-            .and_then(|shape| shape.first())
-            .copied()
+            .and_then(|shape| shape.first().copied().flatten())
             .expect("ConstantOfShape node must have a Tensor with a non-empty shape"),
         _ => panic!("ConstantOfShape node must have a Tensor or Shape type input"),
     };
@@ -199,8 +209,8 @@ fn random_update_output(node: &mut Node) {
         shape: Some(
             shape
                 .drain(..)
-                .map(usize::try_from)
-                .collect::<Result<Vec<usize>, _>>()
+                .map(|dim| usize::try_from(dim).map(Some))
+                .collect::<Result<Vec<_>, _>>()
                 .unwrap(),
         ),
     })
@@ -223,13 +233,12 @@ fn random_like_update_output(node: &mut Node) {
     };
 
     if let ArgType::Tensor(tensor) = &node.inputs[0].clone().ty {
-        if let Some(shape) = tensor.shape.clone() {
-            node.outputs[0].ty = ArgType::Tensor(TensorType {
-                elem_type,
-                dim: shape.len(),
-                shape: Some(shape),
-            })
-        }
+        // The shape is unknown when the input has symbolic dimensions, but the rank is not
+        node.outputs[0].ty = ArgType::Tensor(TensorType {
+            elem_type,
+            dim: tensor.dim,
+            shape: tensor.shape.clone(),
+        })
     } else {
         panic!("Only tensor input is valid");
     }
@@ -264,6 +273,101 @@ fn linear_update_outputs(node: &mut Node) {
     }
 }
 
+/// Sets the outputs of the recurrent operators: the hidden states of all the steps `Y` with 4
+/// dimensions, followed by the final hidden state `Y_h` and the final cell state `Y_c` with 3
+/// dimensions.
+fn recurrent_update_outputs(node: &mut Node) {
+    let elem_type = match &node.inputs[0].ty {
+        ArgType::Tensor(tensor) => tensor.elem_type.clone(),
+        _ => panic!("{:?}: only tensor input is valid", node.node_type),
+    };
+
+    for (index, output) in node.outputs.iter_mut().enumerate() {
+        output.ty = ArgType::Tensor(TensorType {
+            elem_type: elem_type.clone(),
+            dim: if index == 0 { 4 } else { 3 },
+            shape: None,
+        });
+    }
+}
+
+/// Infers the rank of the output of an Einsum node from its equation, the ellipsis covering
+/// the broadcast dimensions of the operands.
+fn einsum_update_outputs(node: &mut Node) {
+    let equation = match node.attrs.get("equation") {
+        Some(equation) => equation.clone().into_string().replace(' ', ""),
+        None => panic!("Einsum: the equation is required"),
+    };
+    let (inputs, output) = match equation.split_once("->") {
+        Some((inputs, output)) => (inputs, Some(output)),
+        None => (equation.as_str(), None),
+    };
+
+    let elem_type = match &node.inputs[0].ty {
+        ArgType::Tensor(tensor) => tensor.elem_type.clone(),
+        _ => panic!("Einsum: only tensor inputs are valid"),
+    };
+    let ellipsis_dim = inputs
+        .split(',')
+        .zip(node.inputs.iter())
+        .filter(|(labels, _)| labels.contains("..."))
+        .map(|(labels, input)| match &input.ty {
+            ArgType::Tensor(tensor) => tensor.dim + 3 - labels.len(),
+            _ => panic!("Einsum: only tensor inputs are valid"),
+        })
+        .max()
+        .unwrap_or(0);
+
+    let dim = match output {
+        Some(output) if output.contains("...") => output.len() - 3 + ellipsis_dim,
+        Some(output) => output.len(),
+        // The implicit output has the labels appearing once, after the broadcast dimensions.
+        None => {
+            let labels = inputs.replace([',', '.'], "");
+            let once = labels
+                .chars()
+                .filter(|label| labels.matches(*label).count() == 1);
+
+            once.count() + ellipsis_dim
+        }
+    };
+
+    node.outputs[0].ty = ArgType::Tensor(TensorType {
+        elem_type,
+        dim,
+        shape: None,
+    });
+}
+
+/// The values have the rank of the input, the indices are integers of the same rank.
+fn topk_update_outputs(node: &mut Node) {
+    let tensor = match &node.inputs[0].ty {
+        ArgType::Tensor(tensor) => tensor.clone(),
+        _ => panic!("TopK: only tensor input is valid"),
+    };
+
+    node.outputs[0].ty = ArgType::Tensor(TensorType {
+        shape: None,
+        ..tensor.clone()
+    });
+    if let Some(indices) = node.outputs.get_mut(1) {
+        indices.ty = ArgType::Tensor(TensorType {
+            elem_type: ElementType::Int64,
+            dim: tensor.dim,
+            shape: None,
+        });
+    }
+}
+
+/// The selected indices have a data-dependent number of rows `[batch, class, box]`.
+fn non_max_suppression_update_outputs(node: &mut Node) {
+    node.outputs[0].ty = ArgType::Tensor(TensorType {
+        elem_type: ElementType::Int64,
+        dim: 2,
+        shape: None,
+    });
+}
+
 /// Update the output type using "to" attribute
 fn cast_update_outputs(node: &mut Node) {
     if node.inputs.len() != 1 {
@@ -512,6 +616,24 @@ fn same_as_input(node: &mut Node) {
 
 /// Temporary pass-through stub for dimension inference so that we can export the IR model.
 fn temporary_pass_through_stub(node: &mut Node) {
+    // The outputs keep the types recorded in the value info of the graph, if any. The rank 0
+    // tensors are scalars, so it is the default type of the outputs without value info.
+    let untyped = |output: &Argument| {
+        !output.name.is_empty() && matches!(&output.ty, ArgType::Tensor(tensor) if tensor.dim == 0)
+    };
+    if !node.outputs.iter().any(untyped) {
+        return;
+    }
+
+    // Nothing is known about the outputs of the unknown operators
+    if let NodeType::Unknown(op_type) = &node.node_type {
+        panic!(
+            "The output types of the unknown {op_type} node {} can't be inferred, they must be \
+            recorded in the value info of the graph, e.g. with the ONNX shape inference",
+            node.name
+        );
+    }
+
     log::warn!("Must implement dimension inference for {:?}", node);
     log::warn!("Temporarily setting the output type to the input type.");
     node.outputs[0].ty = node.inputs[0].ty.clone();
@@ -837,6 +959,33 @@ fn where_update_outputs(node: &mut Node) {
     }
 }
 
+/// The output of rank `q - 1 + r - k - b`, where `q` is the rank of the indices, `r` the rank of
+/// the data, `k` the size of the last dimension of the indices and `b` the batch dimensions.
+fn gather_nd_update_outputs(node: &mut Node) {
+    let (data, indices) = match (&node.inputs[0].ty, &node.inputs[1].ty) {
+        (ArgType::Tensor(data), ArgType::Tensor(indices)) => (data, indices),
+        _ => panic!("GatherND: only tensor inputs are valid"),
+    };
+    // The other dimensions of the indices may be symbolic, but the last one sets the rank.
+    let k = match indices.shape.as_ref().and_then(|shape| shape.last()) {
+        Some(Some(k)) => *k,
+        Some(None) | None => {
+            panic!("GatherND: the last dimension of the indices must be known")
+        }
+    };
+    let batch_dims = node
+        .attrs
+        .get("batch_dims")
+        .map(|value| value.clone().into_i64() as usize)
+        .unwrap_or(0);
+
+    node.outputs[0].ty = ArgType::Tensor(TensorType {
+        elem_type: data.elem_type.clone(),
+        dim: indices.dim - 1 + data.dim - k - batch_dims,
+        shape: None,
+    });
+}
+
 fn gather_update_outputs(node: &mut Node) {
     if node.inputs.len() != 2 {
         panic!("Gather requires two inputs: data and indices");
@@ -882,7 +1031,7 @@ fn set_broadcasting_output_shape(node: &mut Node) {
     for (idx, input_type) in node.inputs.iter().enumerate() {
         match &input_type.ty {
             ArgType::Tensor(t) => {
-                if let Some(shape) = &t.shape {
+                if let Some(shape) = &t.static_shape() {
                     for (rev_idx, dimension) in shape.iter().rev().enumerate() {
                         if let Some(current_out_dim) = reverse_out_shape.get_mut(rev_idx) {
                             if *dimension == 1 {
@@ -923,7 +1072,7 @@ fn set_broadcasting_output_shape(node: &mut Node) {
     match &mut node.outputs[0].ty {
         ArgType::Tensor(t) => {
             t.dim = out_shape.len();
-            t.shape = Some(out_shape);
+            t.shape = Some(partial_shape(out_shape.clone()));
         }
         ArgType::Scalar(_) => {
             if out_shape.len() > 1 || out_shape[0] > 1 {
@@ -938,3 +1087,52 @@ fn set_broadcasting_output_shape(node: &mut Node) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::PartialShape;
+
+    fn tensor(name: &str, elem_type: ElementType, shape: PartialShape) -> Argument {
+        let mut arg = Argument::new(name.to_string());
+        arg.ty = ArgType::Tensor(TensorType {
+            elem_type,
+            dim: shape.len(),
+            shape: Some(shape),
+        });
+        arg
+    }
+
+    fn gather_nd(indices_shape: PartialShape) -> Node {
+        Node {
+            node_type: NodeType::GatherND,
+            name: "gather_nd".to_string(),
+            inputs: vec![
+                tensor("data", ElementType::Float32, vec![None, Some(4), Some(5)]),
+                tensor("indices", ElementType::Int64, indices_shape),
+            ],
+            outputs: vec![Argument::new("output".to_string())],
+            attrs: Default::default(),
+        }
+    }
+
+    #[test]
+    fn gather_nd_with_symbolic_dimensions() {
+        let mut node = gather_nd(vec![None, Some(2)]);
+
+        dim_inference(&mut node);
+
+        match &node.outputs[0].ty {
+            ArgType::Tensor(tensor) => assert_eq!(tensor.dim, 2),
+            ty => panic!("Expected a tensor, got {ty:?}"),
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "GatherND: the last dimension of the indices must be known")]
+    fn gather_nd_with_symbolic_index_size() {
+        let mut node = gather_nd(vec![Some(3), None]);
+
+        dim_inference(&mut node);
+    }
+}
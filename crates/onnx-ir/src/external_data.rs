@@ -0,0 +1,95 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    path::{Component, Path},
+};
+
+use memmap2::Mmap;
+
+use super::protos::{tensor_proto::DataLocation, ModelProto, TensorProto};
+
+/// Read the data of the tensors stored in external files into their raw data.
+///
+/// Models larger than the 2GB protobuf limit keep their initializers in files next to the model,
+/// each tensor describing the region of the file holding its data. The external files are memory
+/// mapped and only these regions are copied, so the files are never loaded as a whole.
+///
+/// # Arguments
+///
+/// * `model` - The model whose tensors are resolved
+/// * `base_dir` - The directory of the onnx file, which the locations are relative to
+pub(crate) fn load_external_data(model: &mut ModelProto, base_dir: &Path) {
+    let graph = model.graph.mut_or_insert_default();
+    let attributes = graph
+        .node
+        .iter_mut()
+        .flat_map(|node| node.attribute.iter_mut())
+        .filter_map(|attribute| attribute.t.as_mut());
+
+    // The memory mapped files, by location
+    let mut files = HashMap::new();
+    for tensor in graph.initializer.iter_mut().chain(attributes) {
+        if tensor.data_location.enum_value() == Ok(DataLocation::EXTERNAL) {
+            load_tensor_data(tensor, base_dir, &mut files);
+        }
+    }
+}
+
+fn load_tensor_data(tensor: &mut TensorProto, base_dir: &Path, files: &mut HashMap<String, Mmap>) {
+    let entry = |key: &str| {
+        tensor
+            .external_data
+            .iter()
+            .find(|entry| entry.key == key)
+            .map(|entry| entry.value.clone())
+    };
+    let parse = |key: &str| {
+        entry(key).map(|value| {
+            value.parse::<u64>().unwrap_or_else(|_| {
+                panic!(
+                    "Invalid external data {key} {value} for tensor {}",
+                    tensor.name
+                )
+            })
+        })
+    };
+
+    let location = entry("location")
+        .unwrap_or_else(|| panic!("External data of tensor {} has no location", tensor.name));
+    let offset = parse("offset").unwrap_or(0);
+    let length = parse("length");
+
+    // The location can't point outside of the model directory
+    let path = Path::new(&location);
+    if path.is_absolute() || path.components().any(|c| c == Component::ParentDir) {
+        panic!("External data location {location} must be relative to the model directory");
+    }
+
+    log::debug!(
+        "Reading tensor {} from {location} at offset {offset}",
+        tensor.name
+    );
+
+    let file = files.entry(location.clone()).or_insert_with(|| {
+        let file = File::open(base_dir.join(&location))
+            .unwrap_or_else(|err| panic!("Unable to open external data file {location}: {err}"));
+
+        // SAFETY: the file must not be modified while the model is loaded.
+        unsafe { Mmap::map(&file) }
+            .unwrap_or_else(|err| panic!("Unable to map external data file {location}: {err}"))
+    });
+
+    let start = offset as usize;
+    let end = length.map_or(file.len(), |length| start + length as usize);
+    let data = file.get(start..end).unwrap_or_else(|| {
+        panic!(
+            "External data of tensor {} at {start}..{end} is out of the {} bytes of {location}",
+            tensor.name,
+            file.len()
+        )
+    });
+
+    tensor.raw_data = data.to_vec();
+    tensor.external_data.clear();
+    tensor.data_location = DataLocation::DEFAULT.into();
+}
@@ -5,9 +5,11 @@ use std::{
 };
 
 use crate::node_remap::remap_node_type;
+use crate::opset_upgrade::upgrade_node;
 
 use super::{
     coalesce::coalesce,
+    external_data::load_external_data,
     ir::{Data, OnnxGraph, TensorType},
     proto_conversion::convert_node_proto,
     protos::{ModelProto, NodeProto, TensorProto, ValueInfoProto},
@@ -18,7 +20,10 @@ use super::ir::{ArgType, Argument, Node, NodeType};
 
 use protobuf::Message;
 
-const LIFT_CONSTANTS_FOR_NODE_TYPES: [NodeType; 12] = [
+/// The opset version assumed for the models which don't import the default operator set.
+const DEFAULT_OPSET_VERSION: i64 = 16;
+
+const LIFT_CONSTANTS_FOR_NODE_TYPES: [NodeType; 14] = [
     NodeType::BatchNormalization,
     NodeType::Clip,
     NodeType::Conv1d,
@@ -31,6 +36,8 @@ const LIFT_CONSTANTS_FOR_NODE_TYPES: [NodeType; 12] = [
     NodeType::ReduceSum,
     NodeType::Slice,
     NodeType::Squeeze,
+    NodeType::TopK,
+    NodeType::NonMaxSuppression,
 ];
 
 #[derive(Debug, Clone)]
@@ -52,6 +59,8 @@ pub struct GraphData {
     input_name_map: HashMap<String, IOEntry>,
     /// Maps the updated input name to the original input name. Required to check if the input is an initializer
     input_key_map: HashMap<String, String>,
+    /// The types of the intermediate values recorded by the ONNX shape inference, by original name
+    value_info: HashMap<String, ArgType>,
 }
 
 impl GraphData {
@@ -59,6 +68,7 @@ impl GraphData {
         inputs: &[ValueInfoProto],
         outputs: &[ValueInfoProto],
         initializers: &[TensorProto],
+        value_info: &[ValueInfoProto],
     ) -> Self {
         let mut input_name_map = HashMap::new();
         let mut input_key_map = HashMap::new();
@@ -92,6 +102,14 @@ impl GraphData {
                 arg
             })
             .collect::<Vec<Argument>>();
+        // The values which aren't tensors of a supported type are left to the dimension inference
+        let value_info = value_info
+            .iter()
+            .filter(|x| x.type_.as_ref().is_some_and(|ty| ty.has_tensor_type()))
+            .filter_map(|x| Argument::try_from(x.clone()).ok())
+            .map(|arg| (arg.name, arg.ty))
+            .chain(outputs.iter().map(|arg| (arg.name.clone(), arg.ty.clone())))
+            .collect();
         Self {
             inputs,
             outputs,
@@ -99,6 +117,7 @@ impl GraphData {
             processed_nodes: Vec::new(),
             input_name_map,
             input_key_map,
+            value_info,
         }
     }
 
@@ -123,6 +142,18 @@ impl GraphData {
         }
     }
 
+    /// Sets the types of the outputs of a node recorded in the value info of the graph.
+    ///
+    /// The types are overwritten by the dimension inference of the supported nodes, they are the
+    /// types of the outputs of the other nodes.
+    fn init_out_types(&self, node: &mut Node) {
+        for output in node.outputs.iter_mut() {
+            if let Some(ty) = self.value_info.get(&output.name) {
+                output.ty = ty.clone();
+            }
+        }
+    }
+
     /// Mark the graph_inputs to a node as passed, unless they are also initializers
     fn mark_input_passed(&mut self, node: &Node) {
         // we have to double map the inputs because the input might be replaced by an initializer
@@ -147,14 +178,16 @@ impl GraphData {
     fn add_node(&mut self, mut node: Node) {
         log::debug!("adding node {:?}", &node.name);
         self.mark_input_passed(&node);
-        let mut out_count = 1;
-        for output in node.outputs.iter_mut() {
+        for (index, output) in node.outputs.iter_mut().enumerate() {
+            // The optional outputs which aren't used keep their empty name.
+            if output.name.is_empty() {
+                continue;
+            }
             self.input_name_map.insert(
                 output.name.clone(),
-                IOEntry::Node(self.processed_nodes.len(), 0),
+                IOEntry::Node(self.processed_nodes.len(), index),
             );
-            output.name = format!("{}_out{}", node.name, out_count);
-            out_count += 1;
+            output.name = format!("{}_out{}", node.name, index + 1);
         }
         self.processed_nodes.push(node);
     }
@@ -202,10 +235,19 @@ impl OnnxGraphBuilder {
     pub(crate) fn build(mut self, model_proto: &ModelProto) -> OnnxGraph {
         self.constants_types = LIFT_CONSTANTS_FOR_NODE_TYPES.into_iter().collect();
 
+        // The default operator set is identified by an empty domain or "ai.onnx"
+        let opset_version = model_proto
+            .opset_import
+            .iter()
+            .find(|opset| opset.domain.is_empty() || opset.domain == "ai.onnx")
+            .map(|opset| opset.version)
+            .unwrap_or(DEFAULT_OPSET_VERSION);
+
         let mut graph_data = GraphData::new(
             &model_proto.graph.input,
             &model_proto.graph.output,
             &model_proto.graph.initializer,
+            &model_proto.graph.value_info,
         );
 
         let mut node_iter = model_proto.graph.node.iter().peekable();
@@ -215,6 +257,7 @@ impl OnnxGraphBuilder {
 
             remap_node_type(&mut node);
             self.handle_node_renaming(&mut node);
+            upgrade_node(&mut node, opset_version);
             coalesce(&mut node, &mut node_iter, &graph_data);
             self.handle_identity(&mut node, &graph_data);
             self.check_constants(&mut node, &graph_data);
@@ -223,6 +266,7 @@ impl OnnxGraphBuilder {
             // args : node, peek_iter, graph_data
             self.handle_unsqueeze(&mut node, &graph_data);
 
+            graph_data.init_out_types(&mut node);
             dim_inference(&mut node);
             graph_data.add_node(node);
         }
@@ -244,6 +288,7 @@ impl OnnxGraphBuilder {
             nodes: processed_nodes,
             inputs,
             outputs,
+            opset_version,
         }
     }
 
@@ -305,7 +350,15 @@ impl OnnxGraphBuilder {
         {
             //if the output has a shape, it's only because it's a graph output
             if let Some(out_arg) = graph_data.get_graph_output(&node.outputs[0].name) {
-                remap_unsqueeze_to_reshape(node, out_arg);
+                match &out_arg.ty {
+                    ArgType::Tensor(tensor) if tensor.static_shape().is_none() => {
+                        log::warn!(
+                            "Unsqueeze {} has runtime axes and an output with symbolic dimensions, it can't be remapped to a reshape",
+                            node.name
+                        );
+                    }
+                    _ => remap_unsqueeze_to_reshape(node, out_arg),
+                }
             }
         }
     }
@@ -349,9 +402,13 @@ pub fn parse_onnx(onnx_path: &Path) -> OnnxGraph {
 
     // Open the file
     let mut file = File::open(onnx_path).expect("Unable to open file");
-    let onnx_model: ModelProto =
+    let mut onnx_model: ModelProto =
         Message::parse_from_reader(&mut file).expect("Unable to parse ONNX file");
 
+    // Large models keep their initializers in external files next to the onnx file
+    let base_dir = onnx_path.parent().unwrap_or(Path::new(""));
+    load_external_data(&mut onnx_model, base_dir);
+
     // ONNX nodes must be topologically sorted per spec:
     // https://github.com/onnx/onnx/blob/main/docs/IR.md#graphs
     debug_assert!(
@@ -382,8 +439,7 @@ pub fn parse_onnx(onnx_path: &Path) -> OnnxGraph {
 pub(crate) fn remap_unsqueeze_to_reshape(node: &mut Node, out_arg: &Argument) {
     if let ArgType::Tensor(output_tensor) = &out_arg.ty {
         let inner = output_tensor
-            .shape
-            .clone()
+            .static_shape()
             .unwrap()
             .into_iter()
             .map(|x| x as i64)
@@ -396,7 +452,7 @@ pub(crate) fn remap_unsqueeze_to_reshape(node: &mut Node, out_arg: &Argument) {
             ty: ArgType::Tensor(TensorType {
                 elem_type: super::ir::ElementType::Int64,
                 dim: 1,
-                shape: Some(vec![shape_len]),
+                shape: Some(vec![Some(shape_len)]),
             }),
             value: new_rhs_value,
             passed: false,
@@ -8,6 +8,8 @@ use crate::protos::TensorProto;
 // TODO: Rename Dim to Rank
 pub type Dim = usize;
 pub type Shape = Vec<Dim>;
+/// A shape whose unknown dimensions, e.g. symbolic ones like the batch size, are `None`.
+pub type PartialShape = Vec<Option<Dim>>;
 
 /// A node input or output.
 #[derive(Debug, Clone)]
@@ -59,7 +61,7 @@ impl Argument {
                 ty: ArgType::Tensor(TensorType {
                     elem_type: tensor.elem_type,
                     dim: tensor.dim,
-                    shape: tensor.shape,
+                    shape: tensor.shape.map(partial_shape),
                 }),
                 value: tensor.data.clone(),
                 passed: false,
@@ -112,8 +114,20 @@ pub struct TensorType {
     /// TODO Rename to rank
     pub dim: Dim,
 
-    /// The shape of the tensor.
-    pub shape: Option<Shape>,
+    /// The shape of the tensor, each dimension being `None` when it's only known at runtime.
+    pub shape: Option<PartialShape>,
+}
+
+impl TensorType {
+    /// The shape of the tensor if all its dimensions are known.
+    pub fn static_shape(&self) -> Option<Shape> {
+        self.shape.as_ref()?.iter().copied().collect()
+    }
+}
+
+/// The partial shape of a shape whose dimensions are all known.
+pub fn partial_shape(shape: Shape) -> PartialShape {
+    shape.into_iter().map(Some).collect()
 }
 
 impl Default for ElementType {
@@ -211,6 +225,9 @@ pub struct OnnxGraph {
 
     /// The outputs of the graph.
     pub outputs: Vec<Argument>,
+
+    /// The version of the default ONNX operator set used by the nodes.
+    pub opset_version: i64,
 }
 
 /// Nodes produced by the ONNX parser
@@ -472,6 +489,9 @@ pub enum NodeType {
     Upsample,
     Where,
     Xor,
+    /// An operator unknown to the parser, by its ONNX type, e.g. `"GridSample"`.
+    #[strum(default)]
+    Unknown(String),
 }
 
 /// Truncate the vector display for debug display
@@ -776,7 +796,7 @@ impl From<AttributeValue> for Argument {
                 ty: ArgType::Tensor(TensorType {
                     dim: 1,
                     elem_type: ElementType::Float32,
-                    shape: Some(vec![values.len()]),
+                    shape: Some(vec![Some(values.len())]),
                 }),
                 name,
                 value: Some(Data::Float32s(values)),
@@ -792,7 +812,7 @@ impl From<AttributeValue> for Argument {
                 ty: ArgType::Tensor(TensorType {
                     dim: 1,
                     elem_type: ElementType::Int64,
-                    shape: Some(vec![values.len()]),
+                    shape: Some(vec![Some(values.len())]),
                 }),
                 name,
                 value: Some(Data::Int64s(values)),
@@ -808,7 +828,7 @@ impl From<AttributeValue> for Argument {
                 ty: ArgType::Tensor(TensorType {
                     dim: 1,
                     elem_type: ElementType::String,
-                    shape: Some(vec![values.len()]),
+                    shape: Some(vec![Some(values.len())]),
                 }),
                 name,
                 value: Some(Data::Strings(values)),
@@ -838,7 +858,7 @@ impl From<AttributeValue> for Argument {
                         ty: ArgType::Tensor(TensorType {
                             dim: tensor.dim,
                             elem_type: tensor.elem_type,
-                            shape: tensor.shape,
+                            shape: tensor.shape.map(partial_shape),
                         }),
                         name,
                         value: tensor.data,
@@ -855,10 +875,10 @@ impl Argument {
     pub fn into_tensor(self) -> Option<Tensor> {
         if let ArgType::Tensor(tensor_type) = self.ty {
             Some(Tensor {
+                shape: tensor_type.static_shape(),
                 elem_type: tensor_type.elem_type,
                 dim: tensor_type.dim,
                 data: self.value,
-                shape: tensor_type.shape,
             })
         } else {
             None
@@ -1,12 +1,19 @@
 mod coalesce;
 mod dim_inference;
+mod external_data;
 mod from_onnx;
 pub mod ir;
 mod node_remap;
+mod opset_upgrade;
+mod optimize;
 mod proto_conversion;
 mod protos;
+mod to_onnx;
 mod util;
 
+pub use dim_inference::dim_inference;
 pub use from_onnx::convert_constant_value;
 pub use from_onnx::parse_onnx;
 pub use ir::OnnxGraph;
+pub use optimize::optimize_graph;
+pub use to_onnx::node_to_onnx;
@@ -0,0 +1,112 @@
+use super::ir::{ArgType, Argument, AttributeValue, Data, ElementType, Node, NodeType, TensorType};
+
+/// Rewrite the nodes of the older operator sets into their current forms
+///
+/// Many operators moved their attributes to inputs over the opset versions, e.g. the `axes` of
+/// `Squeeze` in opset 13, so the conversion only has to handle the current form. The attributes
+/// become inputs with a value, as the constants lifted into the node inputs.
+pub fn upgrade_node(node: &mut Node, opset_version: i64) {
+    match node.node_type {
+        NodeType::Pad if opset_version < 11 => {
+            attribute_to_input(node, "pads", 1);
+            attribute_to_input(node, "value", 2);
+        }
+        NodeType::Clip if opset_version < 11 => {
+            attribute_to_input(node, "min", 1);
+            attribute_to_input(node, "max", 2);
+        }
+        NodeType::Slice if opset_version < 10 => {
+            attribute_to_input(node, "starts", 1);
+            attribute_to_input(node, "ends", 2);
+            attribute_to_input(node, "axes", 3);
+        }
+        NodeType::Squeeze | NodeType::Unsqueeze | NodeType::ReduceSum if opset_version < 13 => {
+            attribute_to_input(node, "axes", 1);
+        }
+        NodeType::Upsample => upsample_to_resize(node),
+        NodeType::Resize if opset_version < 11 => resize_10_to_11(node),
+        _ => {}
+    }
+}
+
+/// Move an attribute to the input at `index`, the missing optional inputs before it are empty
+fn attribute_to_input(node: &mut Node, attribute: &str, index: usize) {
+    let Some(value) = node.attrs.remove(attribute) else {
+        return;
+    };
+
+    let (ty, value) = match value {
+        AttributeValue::Float32(value) => {
+            (ArgType::Scalar(ElementType::Float32), Data::Float32(value))
+        }
+        AttributeValue::Int64(value) => (ArgType::Scalar(ElementType::Int64), Data::Int64(value)),
+        AttributeValue::Int64s(values) => (
+            ArgType::Tensor(TensorType {
+                elem_type: ElementType::Int64,
+                dim: 1,
+                shape: Some(vec![Some(values.len())]),
+            }),
+            Data::Int64s(values),
+        ),
+        AttributeValue::Float32s(values) => (
+            ArgType::Tensor(TensorType {
+                elem_type: ElementType::Float32,
+                dim: 1,
+                shape: Some(vec![Some(values.len())]),
+            }),
+            Data::Float32s(values),
+        ),
+        value => panic!(
+            "{}: unexpected value of the {attribute} attribute {value:?}",
+            node.name
+        ),
+    };
+
+    while node.inputs.len() <= index {
+        node.inputs.push(Argument::new(String::new()));
+    }
+
+    node.inputs[index] = Argument {
+        name: format!("{}_{attribute}", node.name),
+        ty,
+        value: Some(value),
+        passed: false,
+    };
+}
+
+/// Rewrite an `Upsample` node, deprecated since opset 10, as a `Resize` node
+///
+/// The scales are an attribute before opset 9 and an input after.
+fn upsample_to_resize(node: &mut Node) {
+    node.node_type = NodeType::Resize;
+
+    attribute_to_input(node, "scales", 1);
+    resize_10_to_11(node);
+}
+
+/// Rewrite a `Resize` node of opset 10, whose inputs are the data and the scales, with the inputs
+/// of opset 11, where the region of interest comes before the scales
+///
+/// The coordinates of opset 10 are computed as the `asymmetric` mode of opset 11, and the nearest
+/// pixel is rounded down.
+fn resize_10_to_11(node: &mut Node) {
+    if node.inputs.len() == 2 {
+        node.inputs.insert(1, Argument::new(String::new()));
+    }
+
+    node.attrs.insert(
+        "coordinate_transformation_mode".to_string(),
+        AttributeValue::String("asymmetric".to_string()),
+    );
+
+    let mode = node
+        .attrs
+        .entry("mode".to_string())
+        .or_insert_with(|| AttributeValue::String("nearest".to_string()));
+    if mode.clone().into_string() == "nearest" {
+        node.attrs.insert(
+            "nearest_mode".to_string(),
+            AttributeValue::String("floor".to_string()),
+        );
+    }
+}
@@ -0,0 +1,747 @@
+use std::collections::HashMap;
+
+use crate::from_onnx::convert_constant_value;
+use crate::ir::{
+    ArgType, Argument, AttributeValue, Data, ElementType, Node, NodeType, OnnxGraph, Tensor,
+    TensorType,
+};
+
+/// Simplifies a graph without changing its outputs:
+///
+/// * the Identity and the Dropout nodes, which pass their input through at inference, are removed;
+/// * the consecutive Transpose nodes are merged, and removed if they cancel each other;
+/// * the BatchNormalization nodes following a convolution are folded into its weights and bias;
+/// * the elementwise nodes whose inputs are all constant are replaced by their constant result.
+///
+/// The exporters often leave these artifacts in the graphs, which would otherwise be faithfully
+/// reproduced by the generated models.
+pub fn optimize_graph(graph: &mut OnnxGraph) {
+    let num_nodes = graph.nodes.len();
+
+    remove_passthrough(graph);
+    merge_transposes(graph);
+    fold_conv_batch_norm(graph);
+    fold_constants(graph);
+    remove_unused_constants(graph);
+
+    log::info!(
+        "Optimized the graph from {} to {} nodes",
+        num_nodes,
+        graph.nodes.len()
+    );
+}
+
+/// The number of uses of each argument, by the nodes and the outputs of the graph.
+fn uses(graph: &OnnxGraph) -> HashMap<String, usize> {
+    let mut uses = HashMap::new();
+    let names = graph
+        .nodes
+        .iter()
+        .flat_map(|node| node.inputs.iter())
+        .chain(graph.outputs.iter())
+        .map(|arg| arg.name.clone());
+
+    for name in names {
+        *uses.entry(name).or_insert(0) += 1;
+    }
+    uses
+}
+
+/// Removes a node passing its first input through to its first output.
+///
+/// The uses of the output are replaced by the input. When the output is an output of the graph,
+/// the node producing the input gives its output the name of the output instead, which is only
+/// possible if it's not used elsewhere. Returns whether the node was removed.
+fn bypass(graph: &mut OnnxGraph, index: usize) -> bool {
+    let input = graph.nodes[index].inputs[0].clone();
+    let output = graph.nodes[index].outputs[0].clone();
+
+    if graph.outputs.iter().any(|arg| arg.name == output.name) {
+        if uses(graph).get(&input.name) != Some(&1) {
+            return false;
+        }
+
+        let producer = graph.nodes[..index]
+            .iter_mut()
+            .flat_map(|node| node.outputs.iter_mut())
+            .find(|arg| arg.name == input.name);
+        match producer {
+            Some(arg) => arg.name = output.name,
+            // The graph inputs keep their name
+            None => return false,
+        }
+    } else {
+        for arg in graph
+            .nodes
+            .iter_mut()
+            .flat_map(|node| node.inputs.iter_mut())
+        {
+            if arg.name == output.name {
+                arg.name.clone_from(&input.name);
+            }
+        }
+    }
+
+    log::debug!("Removing the node {}", graph.nodes[index].name);
+    graph.nodes.remove(index);
+    true
+}
+
+fn remove_passthrough(graph: &mut OnnxGraph) {
+    let mut index = 0;
+    while index < graph.nodes.len() {
+        let node = &graph.nodes[index];
+
+        // The mask output of the Dropout nodes, if used, is not a pass through
+        let is_passthrough = match node.node_type {
+            NodeType::Identity => node.inputs[0].value.is_none(),
+            NodeType::Dropout => {
+                let uses = uses(graph);
+                node.outputs
+                    .iter()
+                    .skip(1)
+                    .all(|arg| arg.name.is_empty() || !uses.contains_key(&arg.name))
+            }
+            _ => false,
+        };
+
+        if !(is_passthrough && bypass(graph, index)) {
+            index += 1;
+        }
+    }
+}
+
+/// The permutation of a Transpose node, which reverses the dimensions by default.
+fn permutation(node: &Node) -> Option<Vec<usize>> {
+    match node.attrs.get("perm") {
+        Some(AttributeValue::Int64s(perm)) => {
+            Some(perm.iter().map(|axis| *axis as usize).collect())
+        }
+        _ => match &node.inputs[0].ty {
+            ArgType::Tensor(tensor) => Some((0..tensor.dim).rev().collect()),
+            _ => None,
+        },
+    }
+}
+
+fn merge_transposes(graph: &mut OnnxGraph) {
+    let mut index = 0;
+    while index < graph.nodes.len() {
+        if graph.nodes[index].node_type != NodeType::Transpose {
+            index += 1;
+            continue;
+        }
+
+        // The first transpose must only be used by the second one
+        let uses = uses(graph);
+        let first = graph.nodes[..index].iter().position(|node| {
+            node.node_type == NodeType::Transpose
+                && node.outputs[0].name == graph.nodes[index].inputs[0].name
+                && uses.get(&node.outputs[0].name) == Some(&1)
+        });
+        let (Some(first), Some(perm)) = (first, permutation(&graph.nodes[index])) else {
+            index += 1;
+            continue;
+        };
+        let first_perm = match permutation(&graph.nodes[first]) {
+            Some(first_perm) if first_perm.len() == perm.len() => first_perm,
+            _ => {
+                index += 1;
+                continue;
+            }
+        };
+
+        // The second transpose takes the input of the first one, with the composed permutation
+        let perm = perm
+            .iter()
+            .map(|axis| first_perm[*axis])
+            .collect::<Vec<_>>();
+        let input = graph.nodes[first].inputs[0].clone();
+        let is_identity = perm.iter().enumerate().all(|(i, axis)| i == *axis);
+
+        let node = &mut graph.nodes[index];
+        node.inputs[0] = input;
+        node.attrs.insert(
+            "perm".to_string(),
+            AttributeValue::Int64s(perm.iter().map(|axis| *axis as i64).collect()),
+        );
+        graph.nodes.remove(first);
+        index -= 1;
+
+        // The merged transpose is checked again, unless it's removed
+        if is_identity && !bypass(graph, index) {
+            index += 1;
+        }
+    }
+}
+
+fn float_values(arg: &Argument) -> Option<&Vec<f32>> {
+    match &arg.value {
+        Some(Data::Float32s(values)) => Some(values),
+        _ => None,
+    }
+}
+
+fn fold_conv_batch_norm(graph: &mut OnnxGraph) {
+    let mut index = 0;
+    while index < graph.nodes.len() {
+        let batch_norm = &graph.nodes[index];
+
+        // The convolution output must only be used by the batch norm
+        let conv = match batch_norm.node_type {
+            NodeType::BatchNormalization => {
+                let uses = uses(graph);
+                graph.nodes[..index].iter().position(|node| {
+                    matches!(
+                        node.node_type,
+                        NodeType::Conv1d | NodeType::Conv2d | NodeType::Conv3d
+                    ) && node.outputs[0].name == batch_norm.inputs[0].name
+                        && uses.get(&node.outputs[0].name) == Some(&1)
+                })
+            }
+            _ => None,
+        };
+
+        let folded = conv.and_then(|conv| fold_batch_norm(&graph.nodes[conv], batch_norm));
+        let (Some(conv), Some((weight, bias))) = (conv, folded) else {
+            index += 1;
+            continue;
+        };
+
+        let conv = &mut graph.nodes[conv];
+        conv.inputs[1].value = Some(Data::Float32s(weight));
+        match conv.inputs.get_mut(2) {
+            Some(arg) => arg.value = Some(Data::Float32s(bias)),
+            None => {
+                let mut arg = Argument::new(format!("{}_bias", conv.name));
+                arg.ty = ArgType::Tensor(TensorType {
+                    elem_type: ElementType::Float32,
+                    dim: 1,
+                    shape: Some(vec![Some(bias.len())]),
+                });
+                arg.value = Some(Data::Float32s(bias));
+                conv.inputs.push(arg);
+            }
+        }
+
+        // The convolution output is only used by the batch norm, so it can be bypassed
+        if !bypass(graph, index) {
+            index += 1;
+        }
+    }
+}
+
+/// The weight and the bias of a convolution followed by a batch norm, scaled by output channel.
+fn fold_batch_norm(conv: &Node, batch_norm: &Node) -> Option<(Vec<f32>, Vec<f32>)> {
+    let weight = float_values(&conv.inputs[1])?;
+    let scale = float_values(batch_norm.inputs.get(1)?)?;
+    let offset = float_values(batch_norm.inputs.get(2)?)?;
+    let mean = float_values(batch_norm.inputs.get(3)?)?;
+    let var = float_values(batch_norm.inputs.get(4)?)?;
+    let epsilon = match batch_norm.attrs.get("epsilon") {
+        Some(AttributeValue::Float32(epsilon)) => *epsilon,
+        _ => 1e-5,
+    };
+
+    let channels = scale.len();
+    if channels == 0 || weight.len() % channels != 0 {
+        return None;
+    }
+    let bias = match conv.inputs.get(2) {
+        Some(arg) => float_values(arg)?.clone(),
+        None => vec![0.; channels],
+    };
+
+    // y = (conv(x) + b - mean) * scale / sqrt(var + eps) + offset
+    let factors = (0..channels)
+        .map(|c| scale[c] / (var[c] + epsilon).sqrt())
+        .collect::<Vec<_>>();
+    let size = weight.len() / channels;
+    let weight = weight
+        .iter()
+        .enumerate()
+        .map(|(i, w)| w * factors[i / size])
+        .collect();
+    let bias = (0..channels)
+        .map(|c| (bias[c] - mean[c]) * factors[c] + offset[c])
+        .collect();
+
+    Some((weight, bias))
+}
+
+/// A constant value, whose shape is empty for the scalars.
+struct Constant {
+    shape: Vec<usize>,
+    values: Values,
+}
+
+enum Values {
+    Float32(Vec<f32>),
+    Int64(Vec<i64>),
+}
+
+impl Constant {
+    fn from_argument(arg: &Argument) -> Option<Self> {
+        let shape = match &arg.ty {
+            ArgType::Scalar(_) => vec![],
+            ArgType::Tensor(tensor) => tensor.static_shape()?,
+            _ => return None,
+        };
+        let values = match arg.value.clone()? {
+            Data::Float32(value) => Values::Float32(vec![value]),
+            Data::Float32s(values) => Values::Float32(values),
+            Data::Int64(value) => Values::Int64(vec![value]),
+            Data::Int64s(values) => Values::Int64(values),
+            _ => return None,
+        };
+
+        Some(Self { shape, values })
+    }
+
+    /// The attributes of the Constant node with this value.
+    fn into_attribute(self) -> (String, AttributeValue) {
+        let (elem_type, data) = match self.values {
+            Values::Float32(values) if self.shape.is_empty() => {
+                return (
+                    "value_float".to_string(),
+                    AttributeValue::Float32(values[0]),
+                );
+            }
+            Values::Int64(values) if self.shape.is_empty() => {
+                return ("value_int".to_string(), AttributeValue::Int64(values[0]));
+            }
+            Values::Float32(values) => (ElementType::Float32, Data::Float32s(values)),
+            Values::Int64(values) => (ElementType::Int64, Data::Int64s(values)),
+        };
+
+        let tensor = Tensor {
+            elem_type,
+            dim: self.shape.len(),
+            data: Some(data),
+            shape: Some(self.shape),
+        };
+        ("value".to_string(), AttributeValue::Tensor(tensor))
+    }
+}
+
+fn unary<T: Copy>(values: &[T], op: impl Fn(T) -> Option<T>) -> Option<Vec<T>> {
+    values.iter().map(|value| op(*value)).collect()
+}
+
+/// Applies an operation elementwise, broadcasting the single values.
+fn binary<T: Copy>(lhs: &[T], rhs: &[T], op: impl Fn(T, T) -> Option<T>) -> Option<Vec<T>> {
+    match (lhs.len(), rhs.len()) {
+        (l, r) if l == r => lhs.iter().zip(rhs).map(|(l, r)| op(*l, *r)).collect(),
+        (1, _) => rhs.iter().map(|r| op(lhs[0], *r)).collect(),
+        (_, 1) => lhs.iter().map(|l| op(*l, rhs[0])).collect(),
+        _ => None,
+    }
+}
+
+/// The shape of the result of an elementwise operation, if the broadcasting is trivial.
+fn binary_shape(lhs: &Constant, rhs: &Constant) -> Option<Vec<usize>> {
+    let lhs_len = lhs.shape.iter().product::<usize>();
+    let rhs_len = rhs.shape.iter().product::<usize>();
+    match (lhs_len, rhs_len) {
+        _ if lhs.shape == rhs.shape => Some(lhs.shape.clone()),
+        (1, _) if lhs.shape.len() <= rhs.shape.len() => Some(rhs.shape.clone()),
+        (_, 1) if rhs.shape.len() <= lhs.shape.len() => Some(lhs.shape.clone()),
+        _ => None,
+    }
+}
+
+/// Evaluates an elementwise node with constant inputs.
+fn evaluate(node_type: &NodeType, inputs: &[Constant]) -> Option<Constant> {
+    use Values::{Float32, Int64};
+
+    let values = match (node_type, inputs) {
+        (NodeType::Neg, [input]) => match &input.values {
+            Float32(values) => Float32(unary(values, |x| Some(-x))?),
+            Int64(values) => Int64(unary(values, |x| x.checked_neg())?),
+        },
+        (NodeType::Sqrt, [input]) => match &input.values {
+            Float32(values) => Float32(unary(values, |x| Some(x.sqrt()))?),
+            Int64(_) => return None,
+        },
+        (NodeType::Reciprocal, [input]) => match &input.values {
+            Float32(values) => Float32(unary(values, |x| Some(1. / x))?),
+            Int64(_) => return None,
+        },
+        (NodeType::Add | NodeType::Sub | NodeType::Mul | NodeType::Div, [lhs, rhs]) => {
+            let shape = binary_shape(lhs, rhs)?;
+            let values = match (&lhs.values, &rhs.values) {
+                (Float32(l), Float32(r)) => Float32(match node_type {
+                    NodeType::Add => binary(l, r, |l, r| Some(l + r))?,
+                    NodeType::Sub => binary(l, r, |l, r| Some(l - r))?,
+                    NodeType::Mul => binary(l, r, |l, r| Some(l * r))?,
+                    _ => binary(l, r, |l, r| Some(l / r))?,
+                }),
+                (Int64(l), Int64(r)) => Int64(match node_type {
+                    NodeType::Add => binary(l, r, i64::checked_add)?,
+                    NodeType::Sub => binary(l, r, i64::checked_sub)?,
+                    NodeType::Mul => binary(l, r, i64::checked_mul)?,
+                    _ => binary(l, r, i64::checked_div)?,
+                }),
+                _ => return None,
+            };
+            return Some(Constant { shape, values });
+        }
+        _ => return None,
+    };
+
+    Some(Constant {
+        shape: inputs[0].shape.clone(),
+        values,
+    })
+}
+
+fn fold_constants(graph: &mut OnnxGraph) {
+    // The outputs of the Constant nodes, by name
+    let mut constants = HashMap::new();
+
+    for node in graph.nodes.iter_mut() {
+        if node.node_type == NodeType::Constant {
+            constants.insert(node.outputs[0].name.clone(), convert_constant_value(node));
+            continue;
+        }
+
+        let inputs = node
+            .inputs
+            .iter()
+            .map(|input| match input.value {
+                Some(_) => Constant::from_argument(input),
+                None => constants.get(&input.name).and_then(Constant::from_argument),
+            })
+            .collect::<Option<Vec<_>>>();
+        let Some(result) = inputs.and_then(|inputs| evaluate(&node.node_type, &inputs)) else {
+            continue;
+        };
+
+        log::debug!("Folding the node {} into a constant", node.name);
+        let (key, value) = result.into_attribute();
+        node.node_type = NodeType::Constant;
+        node.inputs.clear();
+        node.attrs = [(key, value)].into_iter().collect();
+
+        let arg = convert_constant_value(node);
+        node.outputs[0].ty = arg.ty.clone();
+        constants.insert(node.outputs[0].name.clone(), arg);
+    }
+}
+
+fn remove_unused_constants(graph: &mut OnnxGraph) {
+    loop {
+        let uses = uses(graph);
+        let num_nodes = graph.nodes.len();
+
+        graph.nodes.retain(|node| {
+            node.node_type != NodeType::Constant || uses.contains_key(&node.outputs[0].name)
+        });
+
+        if graph.nodes.len() == num_nodes {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::partial_shape;
+
+    fn tensor(name: &str, shape: Vec<usize>) -> Argument {
+        let mut arg = Argument::new(name.to_string());
+        arg.ty = ArgType::Tensor(TensorType {
+            elem_type: ElementType::Float32,
+            dim: shape.len(),
+            shape: Some(partial_shape(shape)),
+        });
+        arg
+    }
+
+    fn constant(name: &str, shape: Vec<usize>, values: Vec<f32>) -> Argument {
+        let mut arg = tensor(name, shape);
+        arg.value = Some(Data::Float32s(values));
+        arg
+    }
+
+    fn node(node_type: NodeType, name: &str, inputs: Vec<Argument>, outputs: &[&str]) -> Node {
+        Node {
+            node_type,
+            name: name.to_string(),
+            inputs,
+            outputs: outputs
+                .iter()
+                .map(|output| tensor(output, vec![1, 2, 3]))
+                .collect(),
+            attrs: HashMap::new(),
+        }
+    }
+
+    fn transpose(name: &str, input: &str, perm: &[i64]) -> Node {
+        let mut node = node(
+            NodeType::Transpose,
+            name,
+            vec![tensor(input, vec![1, 2, 3])],
+            &[&format!("{name}_out")],
+        );
+        node.attrs
+            .insert("perm".to_string(), AttributeValue::Int64s(perm.to_vec()));
+        node
+    }
+
+    fn graph(nodes: Vec<Node>, outputs: &[&str]) -> OnnxGraph {
+        OnnxGraph {
+            nodes,
+            inputs: vec![tensor("x", vec![1, 2, 3])],
+            outputs: outputs
+                .iter()
+                .map(|output| tensor(output, vec![1, 2, 3]))
+                .collect(),
+            opset_version: 16,
+        }
+    }
+
+    fn node_types(graph: &OnnxGraph) -> Vec<NodeType> {
+        graph
+            .nodes
+            .iter()
+            .map(|node| node.node_type.clone())
+            .collect()
+    }
+
+    fn perm(node: &Node) -> Vec<i64> {
+        match node.attrs.get("perm") {
+            Some(AttributeValue::Int64s(perm)) => perm.clone(),
+            attr => panic!("Unexpected perm {attr:?}"),
+        }
+    }
+
+    #[test]
+    fn should_remove_identity_and_dropout() {
+        let mut graph = graph(
+            vec![
+                node(
+                    NodeType::Identity,
+                    "identity1",
+                    vec![tensor("x", vec![1, 2, 3])],
+                    &["identity1_out"],
+                ),
+                node(
+                    NodeType::Relu,
+                    "relu1",
+                    vec![tensor("identity1_out", vec![1, 2, 3])],
+                    &["relu1_out"],
+                ),
+                node(
+                    NodeType::Dropout,
+                    "dropout1",
+                    vec![tensor("relu1_out", vec![1, 2, 3])],
+                    &["dropout1_out"],
+                ),
+            ],
+            &["dropout1_out"],
+        );
+
+        optimize_graph(&mut graph);
+
+        // The relu produces the output of the graph in place of the dropout
+        assert_eq!(node_types(&graph), vec![NodeType::Relu]);
+        assert_eq!(graph.nodes[0].inputs[0].name, "x");
+        assert_eq!(graph.nodes[0].outputs[0].name, "dropout1_out");
+    }
+
+    #[test]
+    fn should_keep_dropout_with_used_mask() {
+        let mut graph = graph(
+            vec![
+                node(
+                    NodeType::Dropout,
+                    "dropout1",
+                    vec![tensor("x", vec![1, 2, 3])],
+                    &["dropout1_out", "dropout1_mask"],
+                ),
+                node(
+                    NodeType::Cast,
+                    "cast1",
+                    vec![tensor("dropout1_mask", vec![1, 2, 3])],
+                    &["cast1_out"],
+                ),
+            ],
+            &["dropout1_out", "cast1_out"],
+        );
+
+        optimize_graph(&mut graph);
+
+        assert_eq!(node_types(&graph), vec![NodeType::Dropout, NodeType::Cast]);
+    }
+
+    #[test]
+    fn should_remove_inverse_transposes() {
+        let mut graph = graph(
+            vec![
+                transpose("transpose1", "x", &[0, 2, 1]),
+                transpose("transpose2", "transpose1_out", &[0, 2, 1]),
+                node(
+                    NodeType::Relu,
+                    "relu1",
+                    vec![tensor("transpose2_out", vec![1, 2, 3])],
+                    &["relu1_out"],
+                ),
+            ],
+            &["relu1_out"],
+        );
+
+        optimize_graph(&mut graph);
+
+        assert_eq!(node_types(&graph), vec![NodeType::Relu]);
+        assert_eq!(graph.nodes[0].inputs[0].name, "x");
+    }
+
+    #[test]
+    fn should_merge_non_inverse_transposes() {
+        let mut graph = graph(
+            vec![
+                transpose("transpose1", "x", &[1, 2, 0]),
+                transpose("transpose2", "transpose1_out", &[1, 2, 0]),
+            ],
+            &["transpose2_out"],
+        );
+
+        optimize_graph(&mut graph);
+
+        // The axis i of the output is the axis first_perm[perm[i]] of the input
+        assert_eq!(node_types(&graph), vec![NodeType::Transpose]);
+        assert_eq!(graph.nodes[0].inputs[0].name, "x");
+        assert_eq!(graph.nodes[0].outputs[0].name, "transpose2_out");
+        assert_eq!(perm(&graph.nodes[0]), vec![2, 0, 1]);
+    }
+
+    #[test]
+    fn should_keep_transposes_used_elsewhere() {
+        let mut graph = graph(
+            vec![
+                transpose("transpose1", "x", &[0, 2, 1]),
+                transpose("transpose2", "transpose1_out", &[0, 2, 1]),
+            ],
+            &["transpose1_out", "transpose2_out"],
+        );
+
+        optimize_graph(&mut graph);
+
+        assert_eq!(
+            node_types(&graph),
+            vec![NodeType::Transpose, NodeType::Transpose]
+        );
+    }
+
+    fn conv_batch_norm(bias: Option<Vec<f32>>, epsilon: Option<f32>) -> OnnxGraph {
+        let mut inputs = vec![
+            tensor("x", vec![1, 1, 2, 2]),
+            constant("conv1_weight", vec![2, 1, 1, 1], vec![1.0, 2.0]),
+        ];
+        if let Some(bias) = bias {
+            inputs.push(constant("conv1_bias", vec![2], bias));
+        }
+        let conv = node(NodeType::Conv2d, "conv1", inputs, &["conv1_out"]);
+
+        let mut batch_norm = node(
+            NodeType::BatchNormalization,
+            "batchnormalization1",
+            vec![
+                tensor("conv1_out", vec![1, 2, 2, 2]),
+                constant("scale", vec![2], vec![1.0, 2.0]),
+                constant("offset", vec![2], vec![0.5, 0.5]),
+                constant("mean", vec![2], vec![1.0, 1.0]),
+                constant("var", vec![2], vec![3.0, 0.0]),
+            ],
+            &["batchnormalization1_out"],
+        );
+        if let Some(epsilon) = epsilon {
+            batch_norm
+                .attrs
+                .insert("epsilon".to_string(), AttributeValue::Float32(epsilon));
+        }
+
+        graph(vec![conv, batch_norm], &["batchnormalization1_out"])
+    }
+
+    fn assert_values(arg: &Argument, expected: &[f32]) {
+        let values = float_values(arg).unwrap();
+        assert_eq!(values.len(), expected.len());
+        for (value, expected) in values.iter().zip(expected) {
+            assert!(
+                (value - expected).abs() < 1e-4,
+                "{values:?} != {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn should_fold_batch_norm_into_conv_without_bias() {
+        let mut graph = conv_batch_norm(None, Some(1.0));
+
+        optimize_graph(&mut graph);
+
+        // The factors are scale / sqrt(var + epsilon) = [0.5, 2.0]
+        assert_eq!(node_types(&graph), vec![NodeType::Conv2d]);
+        let conv = &graph.nodes[0];
+        assert_eq!(conv.outputs[0].name, "batchnormalization1_out");
+        assert_eq!(conv.inputs.len(), 3);
+        assert_values(&conv.inputs[1], &[0.5, 4.0]);
+        assert_values(&conv.inputs[2], &[0.0, -1.5]);
+    }
+
+    #[test]
+    fn should_fold_batch_norm_with_default_epsilon() {
+        let mut graph = conv_batch_norm(Some(vec![1.0, 3.0]), None);
+
+        optimize_graph(&mut graph);
+
+        let factors = [1.0 / (3.0f32 + 1e-5).sqrt(), 2.0 / 1e-5f32.sqrt()];
+        let conv = &graph.nodes[0];
+        assert_eq!(node_types(&graph), vec![NodeType::Conv2d]);
+        assert_values(&conv.inputs[1], &[factors[0], 2.0 * factors[1]]);
+        assert_values(&conv.inputs[2], &[0.5, 2.0 * factors[1] + 0.5]);
+    }
+
+    #[test]
+    fn should_fold_constant_nodes() {
+        let scalar = |name: &str, value: f32| {
+            let mut node = node(NodeType::Constant, name, vec![], &[&format!("{name}_out")]);
+            node.attrs
+                .insert("value_float".to_string(), AttributeValue::Float32(value));
+            node
+        };
+        let scalar_arg = |name: &str| {
+            let mut arg = Argument::new(name.to_string());
+            arg.ty = ArgType::Scalar(ElementType::Float32);
+            arg
+        };
+        let mul = node(
+            NodeType::Mul,
+            "mul1",
+            vec![scalar_arg("constant1_out"), scalar_arg("constant2_out")],
+            &["mul1_out"],
+        );
+        let add = node(
+            NodeType::Add,
+            "add1",
+            vec![tensor("x", vec![1, 2, 3]), scalar_arg("mul1_out")],
+            &["add1_out"],
+        );
+        let mut graph = graph(
+            vec![scalar("constant1", 2.0), scalar("constant2", 3.0), mul, add],
+            &["add1_out"],
+        );
+
+        optimize_graph(&mut graph);
+
+        // The constants only used by the folded node are removed
+        assert_eq!(node_types(&graph), vec![NodeType::Constant, NodeType::Add]);
+        assert!(matches!(
+            graph.nodes[0].attrs.get("value_float"),
+            Some(AttributeValue::Float32(value)) if *value == 6.0
+        ));
+    }
+}
@@ -3,7 +3,7 @@ use std::str::{from_utf8, FromStr};
 use crate::ir::TensorType;
 
 use super::from_onnx::GraphData;
-use super::ir::Dim;
+use super::ir::PartialShape;
 use super::ir::{
     ArgType, Argument, AttributeValue, Attributes, Data, ElementType, Node, NodeType, Tensor,
 };
@@ -86,19 +86,21 @@ impl TryFrom<TensorProto> for Tensor {
     }
 }
 
-impl TryFrom<TensorShapeProto> for Vec<usize> {
-    type Error = ParseError;
-    fn try_from(shape: TensorShapeProto) -> Result<Vec<usize>, Self::Error> {
-        let mut result = Vec::new();
-
-        for dim in shape.dim {
-            if let Value::DimValue(value) = dim.value.unwrap() {
-                result.push(value as usize);
+/// The shape of a tensor, each of its symbolic (e.g. the batch size or the sequence length) or
+/// unknown dimensions being `None` since it's only known at runtime.
+fn partial_shape(shape: &TensorShapeProto) -> PartialShape {
+    shape
+        .dim
+        .iter()
+        .map(|dim| match &dim.value {
+            Some(Value::DimValue(value)) if *value >= 0 => Some(*value as usize),
+            Some(Value::DimParam(param)) => {
+                log::debug!("Symbolic dimension {param} is only known at runtime");
+                None
             }
-        }
-
-        Ok(result)
-    }
+            _ => None,
+        })
+        .collect()
 }
 
 /// Convert a vector of AttributeProto to a HashMap of AttributeValue
@@ -119,12 +121,12 @@ impl TryFrom<&type_proto::Tensor> for Tensor {
         };
 
         let shape_proto = tensor.shape.clone().unwrap();
-        let shape: Vec<usize> = shape_proto.try_into().unwrap();
 
+        // The rank is known even when some dimensions are symbolic
         Ok(Tensor {
             elem_type,
-            dim: shape.len(),
-            shape: Some(shape),
+            dim: shape_proto.dim.len(),
+            shape: partial_shape(&shape_proto).into_iter().collect(),
             data: None,
         })
     }
@@ -195,7 +197,8 @@ pub fn convert_node_proto(node: &NodeProto, graph_data: &GraphData) -> Node {
 
     let attrs = convert_vec_attrs_proto(node.attribute.clone());
 
-    let node_type = NodeType::from_str(node.op_type.as_str()).expect("Unknown node type");
+    // The unknown operators are parsed as `NodeType::Unknown`
+    let node_type = NodeType::from_str(node.op_type.as_str()).unwrap();
 
     Node {
         node_type,
@@ -246,18 +249,11 @@ impl TryFrom<ValueInfoProto> for Argument {
             // tensor_proto describes a scalar
             ArgType::Scalar(elem_type)
         } else {
-            // tensor_proto describes a tensor
+            // tensor_proto describes a tensor, whose symbolic dimensions are resolved at runtime
             let tensor_type = TensorType {
                 dim: tensor_proto.shape.dim.len(),
                 elem_type,
-                shape: Some(
-                    tensor_proto
-                        .shape
-                        .dim
-                        .iter()
-                        .map(|x| x.dim_value() as Dim)
-                        .collect(),
-                ),
+                shape: Some(partial_shape(&tensor_proto.shape)),
             };
 
             ArgType::Tensor(tensor_type)
@@ -271,3 +267,48 @@ impl TryFrom<ValueInfoProto> for Argument {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protos::{tensor_shape_proto::Dimension, TypeProto};
+    use protobuf::MessageField;
+
+    #[test]
+    fn value_info_keeps_the_static_dimensions() {
+        let dim = |value: Value| {
+            let mut dimension = Dimension::new();
+            dimension.value = Some(value);
+            dimension
+        };
+        let mut type_proto = TypeProto::new();
+        type_proto.set_tensor_type(type_proto::Tensor {
+            elem_type: DataType::FLOAT as i32,
+            shape: MessageField::some(TensorShapeProto {
+                dim: vec![
+                    dim(Value::DimParam("batch_size".to_string())),
+                    dim(Value::DimValue(3)),
+                    Dimension::new(),
+                ],
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        let value_info = ValueInfoProto {
+            name: "input".to_string(),
+            type_: MessageField::some(type_proto),
+            ..Default::default()
+        };
+
+        let arg = Argument::try_from(value_info).unwrap();
+
+        match arg.ty {
+            ArgType::Tensor(tensor) => {
+                assert_eq!(tensor.dim, 3);
+                assert_eq!(tensor.shape, Some(vec![None, Some(3), None]));
+                assert_eq!(tensor.static_shape(), None);
+            }
+            ty => panic!("Expected a tensor, got {ty:?}"),
+        }
+    }
+}
@@ -0,0 +1,276 @@
+use super::ir::{ArgType, Argument, AttributeValue, Data, ElementType, Node, Tensor};
+use super::protos::{
+    attribute_proto::AttributeType, tensor_proto::DataType, tensor_shape_proto::Dimension,
+    type_proto, AttributeProto, GraphProto, ModelProto, NodeProto, OperatorSetIdProto, TensorProto,
+    TensorShapeProto, TypeProto, ValueInfoProto,
+};
+
+use protobuf::{EnumOrUnknown, Message, MessageField};
+
+/// The IR version of the exported models, supported by the runtimes implementing opset 16 and up.
+const IR_VERSION: i64 = 8;
+
+/// Exports a node as a standalone ONNX model, serialized in the protobuf format.
+///
+/// The inputs of the node with a value become initializers of the model, the others become its
+/// inputs, whose dimensions are symbolic. The outputs of the node are the outputs of the model.
+///
+/// This is used to run a node with another runtime, e.g. ONNX Runtime, when it's not supported.
+/// The node must not have been remapped to a type which is not an ONNX operator, e.g. `Conv2d` or
+/// `Linear`.
+pub fn node_to_onnx(node: &Node, opset_version: i64) -> Vec<u8> {
+    let op_type = node.node_type.to_string();
+
+    let mut graph = GraphProto {
+        name: node.name.clone(),
+        ..Default::default()
+    };
+
+    let mut node_proto = NodeProto {
+        name: node.name.clone(),
+        op_type,
+        ..Default::default()
+    };
+
+    for input in node.inputs.iter() {
+        // The omitted optional inputs have an empty name
+        node_proto.input.push(input.name.clone());
+        if input.name.is_empty() {
+            continue;
+        }
+
+        match &input.value {
+            Some(data) => graph.initializer.push(tensor_proto(input, data)),
+            None => graph.input.push(value_info(input)),
+        }
+    }
+
+    for output in node.outputs.iter() {
+        node_proto.output.push(output.name.clone());
+        graph.output.push(value_info(output));
+    }
+
+    let mut attrs = node.attrs.iter().collect::<Vec<_>>();
+    attrs.sort_by_key(|(name, _)| name.as_str());
+    node_proto.attribute = attrs
+        .into_iter()
+        .map(|(name, value)| attribute_proto(name, value))
+        .collect();
+
+    graph.node.push(node_proto);
+
+    let model = ModelProto {
+        ir_version: IR_VERSION,
+        producer_name: "burn".to_string(),
+        opset_import: vec![OperatorSetIdProto {
+            domain: String::new(),
+            version: opset_version,
+            ..Default::default()
+        }],
+        graph: MessageField::some(graph),
+        ..Default::default()
+    };
+
+    model
+        .write_to_bytes()
+        .unwrap_or_else(|err| panic!("Could not serialize the node {}: {err}", node.name))
+}
+
+fn data_type(elem_type: &ElementType) -> DataType {
+    match elem_type {
+        ElementType::Float32 => DataType::FLOAT,
+        ElementType::Float64 => DataType::DOUBLE,
+        ElementType::Float16 => DataType::FLOAT16,
+        ElementType::Int32 => DataType::INT32,
+        ElementType::Int64 => DataType::INT64,
+        ElementType::String => DataType::STRING,
+        ElementType::Bool => DataType::BOOL,
+    }
+}
+
+/// The type of an argument, whose dimensions are symbolic unless its shape is known.
+fn value_info(arg: &Argument) -> ValueInfoProto {
+    let (elem_type, dims) = match &arg.ty {
+        ArgType::Scalar(elem_type) => (elem_type.clone(), vec![]),
+        ArgType::Shape(rank) => (ElementType::Int64, vec![Some(*rank)]),
+        ArgType::Tensor(tensor) => match &tensor.shape {
+            Some(shape) => (tensor.elem_type.clone(), shape.clone()),
+            None => (tensor.elem_type.clone(), vec![None; tensor.dim]),
+        },
+    };
+
+    let dim = dims
+        .into_iter()
+        .enumerate()
+        .map(|(i, dim)| {
+            let mut dimension = Dimension::new();
+            match dim {
+                Some(value) => dimension.set_dim_value(value as i64),
+                None => dimension.set_dim_param(format!("{}_dim{i}", arg.name)),
+            }
+            dimension
+        })
+        .collect();
+
+    let tensor_type = type_proto::Tensor {
+        elem_type: data_type(&elem_type) as i32,
+        shape: MessageField::some(TensorShapeProto {
+            dim,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let mut type_proto = TypeProto::new();
+    type_proto.set_tensor_type(tensor_type);
+
+    ValueInfoProto {
+        name: arg.name.clone(),
+        type_: MessageField::some(type_proto),
+        ..Default::default()
+    }
+}
+
+/// The initializer of an argument with a value.
+fn tensor_proto(arg: &Argument, data: &Data) -> TensorProto {
+    let dims = match &arg.ty {
+        ArgType::Tensor(tensor) => match tensor.static_shape() {
+            Some(shape) => shape,
+            None => vec![data_len(data); tensor.dim.min(1)],
+        },
+        ArgType::Shape(rank) => vec![*rank],
+        ArgType::Scalar(_) => vec![],
+    };
+
+    let mut tensor = tensor_data(data);
+    tensor.name = arg.name.clone();
+    tensor.dims = dims.into_iter().map(|dim| dim as i64).collect();
+    tensor
+}
+
+fn data_len(data: &Data) -> usize {
+    match data {
+        Data::Bools(values) => values.len(),
+        Data::Float16s(values) => values.len(),
+        Data::Float32s(values) => values.len(),
+        Data::Float64s(values) => values.len(),
+        Data::Int32s(values) => values.len(),
+        Data::Int64s(values) => values.len(),
+        Data::Strings(values) => values.len(),
+        _ => 1,
+    }
+}
+
+/// A tensor with the values of the data, without name nor dimensions.
+fn tensor_data(data: &Data) -> TensorProto {
+    let mut tensor = TensorProto::new();
+
+    // The booleans and the half precision floats are stored as `int32_data`, following the spec
+    let data_type = match data.clone() {
+        Data::Bool(value) => {
+            data_tensor(&mut tensor.int32_data, vec![value as i32], DataType::BOOL)
+        }
+        Data::Bools(values) => data_tensor(
+            &mut tensor.int32_data,
+            values.into_iter().map(|value| value as i32).collect(),
+            DataType::BOOL,
+        ),
+        Data::Float16(value) => data_tensor(
+            &mut tensor.int32_data,
+            vec![value.to_bits() as i32],
+            DataType::FLOAT16,
+        ),
+        Data::Float16s(values) => data_tensor(
+            &mut tensor.int32_data,
+            values
+                .into_iter()
+                .map(|value| value.to_bits() as i32)
+                .collect(),
+            DataType::FLOAT16,
+        ),
+        Data::Float32(value) => data_tensor(&mut tensor.float_data, vec![value], DataType::FLOAT),
+        Data::Float32s(values) => data_tensor(&mut tensor.float_data, values, DataType::FLOAT),
+        Data::Float64(value) => data_tensor(&mut tensor.double_data, vec![value], DataType::DOUBLE),
+        Data::Float64s(values) => data_tensor(&mut tensor.double_data, values, DataType::DOUBLE),
+        Data::Int32(value) => data_tensor(&mut tensor.int32_data, vec![value], DataType::INT32),
+        Data::Int32s(values) => data_tensor(&mut tensor.int32_data, values, DataType::INT32),
+        Data::Int64(value) => data_tensor(&mut tensor.int64_data, vec![value], DataType::INT64),
+        Data::Int64s(values) => data_tensor(&mut tensor.int64_data, values, DataType::INT64),
+        Data::String(value) => data_tensor(
+            &mut tensor.string_data,
+            vec![value.into_bytes()],
+            DataType::STRING,
+        ),
+        Data::Strings(values) => data_tensor(
+            &mut tensor.string_data,
+            values.into_iter().map(String::into_bytes).collect(),
+            DataType::STRING,
+        ),
+    };
+
+    tensor.data_type = data_type as i32;
+    tensor
+}
+
+fn data_tensor<T>(field: &mut Vec<T>, values: Vec<T>, data_type: DataType) -> DataType {
+    *field = values;
+    data_type
+}
+
+fn attribute_proto(name: &str, value: &AttributeValue) -> AttributeProto {
+    let mut attr = AttributeProto {
+        name: name.to_string(),
+        ..Default::default()
+    };
+
+    let attr_type = match value.clone() {
+        AttributeValue::Float32(value) => {
+            attr.f = value;
+            AttributeType::FLOAT
+        }
+        AttributeValue::Float32s(values) => {
+            attr.floats = values;
+            AttributeType::FLOATS
+        }
+        AttributeValue::Int64(value) => {
+            attr.i = value;
+            AttributeType::INT
+        }
+        AttributeValue::Int64s(values) => {
+            attr.ints = values;
+            AttributeType::INTS
+        }
+        AttributeValue::String(value) => {
+            attr.s = value.into_bytes();
+            AttributeType::STRING
+        }
+        AttributeValue::Strings(values) => {
+            attr.strings = values.into_iter().map(String::into_bytes).collect();
+            AttributeType::STRINGS
+        }
+        AttributeValue::Tensor(tensor) => {
+            attr.t = MessageField::some(attribute_tensor(&tensor));
+            AttributeType::TENSOR
+        }
+        AttributeValue::Tensors(tensors) => {
+            attr.tensors = tensors.iter().map(attribute_tensor).collect();
+            AttributeType::TENSORS
+        }
+    };
+
+    attr.type_ = EnumOrUnknown::new(attr_type);
+    attr
+}
+
+fn attribute_tensor(tensor: &Tensor) -> TensorProto {
+    let mut proto = match &tensor.data {
+        Some(data) => tensor_data(data),
+        None => TensorProto::new(),
+    };
+
+    proto.data_type = data_type(&tensor.elem_type) as i32;
+    if let Some(shape) = &tensor.shape {
+        proto.dims = shape.iter().map(|dim| *dim as i64).collect();
+    }
+    proto
+}